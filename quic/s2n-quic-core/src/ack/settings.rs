@@ -39,6 +39,12 @@ pub struct Settings {
 
     /// The number of packet number intervals an endpoint is willing to store
     pub ack_ranges_limit: u8,
+
+    /// The minimum amount of time by which the endpoint is able to delay sending
+    /// acknowledgments.
+    ///
+    /// If `None`, the endpoint does not support the ACK frequency extension.
+    pub min_ack_delay: Option<Duration>,
 }
 
 impl Default for Settings {
@@ -72,6 +78,7 @@ impl Settings {
         ack_delay_exponent: AckDelayExponent::RECOMMENDED.as_u8(),
         ack_elicitation_interval: RECOMMENDED_ELICITATION_INTERVAL,
         ack_ranges_limit: RECOMMENDED_RANGES_LIMIT,
+        min_ack_delay: None,
     };
 
     /// Decodes the peer's `Ack Delay` field
@@ -4,12 +4,13 @@
 use crate::{
     ack,
     event::{api::SocketAddress, IntoEvent},
-    inet, stream,
+    inet, path, stream,
     transport::parameters::{
-        AckDelayExponent, ActiveConnectionIdLimit, InitialFlowControlLimits, InitialMaxData,
-        InitialMaxStreamDataBidiLocal, InitialMaxStreamDataBidiRemote, InitialMaxStreamDataUni,
-        InitialMaxStreamsBidi, InitialMaxStreamsUni, InitialStreamLimits, MaxAckDelay,
-        MaxDatagramFrameSize, MaxIdleTimeout, TransportParameters,
+        AckDelayExponent, ActiveConnectionIdLimit, EnableMultipath, GreaseQuicBit,
+        InitialFlowControlLimits, InitialMaxData, InitialMaxStreamDataBidiLocal,
+        InitialMaxStreamDataBidiRemote, InitialMaxStreamDataUni, InitialMaxStreamsBidi,
+        InitialMaxStreamsUni, InitialStreamLimits, MaxAckDelay, MaxDatagramFrameSize,
+        MaxIdleTimeout, MinAckDelay, TransportParameters,
     },
 };
 use core::{convert::TryInto, time::Duration};
@@ -52,6 +53,8 @@ pub struct Limits {
     pub(crate) bidirectional_local_data_window: InitialMaxStreamDataBidiLocal,
     pub(crate) bidirectional_remote_data_window: InitialMaxStreamDataBidiRemote,
     pub(crate) unidirectional_data_window: InitialMaxStreamDataUni,
+    pub(crate) max_data_window: u32,
+    pub(crate) max_stream_data_window: u32,
     pub(crate) max_open_local_bidirectional_streams: stream::limits::LocalBidirectional,
     pub(crate) max_open_local_unidirectional_streams: stream::limits::LocalUnidirectional,
     pub(crate) max_open_remote_bidirectional_streams: InitialMaxStreamsBidi,
@@ -65,6 +68,14 @@ pub struct Limits {
     pub(crate) max_handshake_duration: Duration,
     pub(crate) max_keep_alive_period: Duration,
     pub(crate) max_datagram_frame_size: MaxDatagramFrameSize,
+    pub(crate) min_ack_delay: Option<MinAckDelay>,
+    pub(crate) enable_multipath: EnableMultipath,
+    pub(crate) stream_scheduler: &'static dyn stream::Scheduler,
+    pub(crate) mtu_config: path::mtu::Config,
+    pub(crate) spin_config: path::spin::Config,
+    pub(crate) grease_quic_bit: GreaseQuicBit,
+    pub(crate) grease_transport_parameters: bool,
+    pub(crate) randomized_quic_bit: bool,
 }
 
 impl Default for Limits {
@@ -74,7 +85,8 @@ impl Default for Limits {
 }
 
 macro_rules! setter {
-    ($name:ident, $field:ident, $inner:ty $(, |$validate_value:ident| $validaiton:block)?) => {
+    ($(#[doc = $doc:literal])* $name:ident, $field:ident, $inner:ty $(, |$validate_value:ident| $validaiton:block)?) => {
+        $(#[doc = $doc])*
         pub fn $name(mut self, value: $inner) -> Result<Self, ValidationError> {
             $(
                 let $validate_value = value;
@@ -94,6 +106,9 @@ impl Limits {
             bidirectional_local_data_window: InitialMaxStreamDataBidiLocal::RECOMMENDED,
             bidirectional_remote_data_window: InitialMaxStreamDataBidiRemote::RECOMMENDED,
             unidirectional_data_window: InitialMaxStreamDataUni::RECOMMENDED,
+            // auto-tuning is disabled by default
+            max_data_window: 0,
+            max_stream_data_window: 0,
             max_open_local_bidirectional_streams: stream::limits::LocalBidirectional::RECOMMENDED,
             max_open_local_unidirectional_streams: stream::limits::LocalUnidirectional::RECOMMENDED,
             max_open_remote_bidirectional_streams: InitialMaxStreamsBidi::RECOMMENDED,
@@ -107,6 +122,19 @@ impl Limits {
             max_handshake_duration: MAX_HANDSHAKE_DURATION_DEFAULT,
             max_keep_alive_period: MAX_KEEP_ALIVE_PERIOD_DEFAULT,
             max_datagram_frame_size: MaxDatagramFrameSize::DEFAULT,
+            // the ACK frequency extension is disabled by default
+            min_ack_delay: None,
+            // the multipath extension is disabled by default
+            enable_multipath: EnableMultipath::Disabled,
+            stream_scheduler: &stream::scheduler::StrictPriority,
+            mtu_config: path::mtu::Config::new(),
+            spin_config: path::spin::Config::new(),
+            // greasing the QUIC bit is disabled by default
+            grease_quic_bit: GreaseQuicBit::Disabled,
+            grease_transport_parameters: false,
+            // the QUIC bit is randomized for peers which advertise `grease_quic_bit`, as
+            // recommended by RFC 9287
+            randomized_quic_bit: true,
         }
     }
 
@@ -156,6 +184,42 @@ impl Limits {
         }
     );
 
+    setter!(
+        /// Sets the maximum size the connection receive window can grow to
+        ///
+        /// If the application consumes data fast enough that the peer is limited by the
+        /// connection flow control window rather than the network, the window is doubled
+        /// until it reaches this value. A value lower than the `data_window` disables
+        /// auto-tuning, which is the default.
+        with_max_data_window,
+        max_data_window,
+        u64,
+        |validate_value| {
+            decoder_invariant!(
+                validate_value <= u32::MAX.into(),
+                "max_data_window must be <= u32::MAX"
+            );
+        }
+    );
+
+    setter!(
+        /// Sets the maximum size the receive window of each stream can grow to
+        ///
+        /// If the application consumes data fast enough that the peer is limited by the
+        /// stream flow control window rather than the network, the window is doubled
+        /// until it reaches this value. A value lower than the stream data windows disables
+        /// auto-tuning, which is the default.
+        with_max_stream_data_window,
+        max_stream_data_window,
+        u64,
+        |validate_value| {
+            decoder_invariant!(
+                validate_value <= u32::MAX.into(),
+                "max_stream_data_window must be <= u32::MAX"
+            );
+        }
+    );
+
     setter!(with_max_idle_timeout, max_idle_timeout, Duration);
 
     /// Sets both the max local and remote limits for bidirectional streams.
@@ -204,6 +268,20 @@ impl Limits {
         u64
     );
     setter!(with_max_ack_delay, max_ack_delay, Duration);
+
+    /// Sets the minimum amount of time the endpoint is able to delay sending acknowledgements
+    ///
+    /// Setting this value enables the ACK frequency extension, which allows the peer to
+    /// request acknowledgements less frequently than every other ack-eliciting packet. The
+    /// value must not exceed the `max_ack_delay`. The extension is disabled by default.
+    pub fn with_min_ack_delay(mut self, value: Duration) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            value <= self.max_ack_delay.as_duration(),
+            "min_ack_delay must be <= max_ack_delay"
+        );
+        self.min_ack_delay = Some(value.try_into()?);
+        Ok(self)
+    }
     setter!(
         with_max_active_connection_ids,
         max_active_connection_ids,
@@ -219,6 +297,92 @@ impl Limits {
     );
     setter!(with_max_keep_alive_period, max_keep_alive_period, Duration);
 
+    /// Enables the multipath extension, which allows the connection to send application data
+    /// on several validated paths at the same time
+    ///
+    /// The extension is only used if the peer also supports it. Each path uses its own
+    /// packet number space if both endpoints support it, otherwise all paths share the
+    /// application data packet number space. The extension is disabled by default.
+    ///
+    /// The extension follows
+    /// [draft-ietf-quic-multipath-03](https://datatracker.ietf.org/doc/html/draft-ietf-quic-multipath-03),
+    /// so it only interoperates with peers implementing the same revision.
+    pub fn with_multipath(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.enable_multipath = if enabled {
+            EnableMultipath::MultiplePacketNumberSpaces
+        } else {
+            EnableMultipath::Disabled
+        };
+        Ok(self)
+    }
+
+    /// Sets the policy used to order the transmission of stream data
+    ///
+    /// Defaults to [`stream::scheduler::StrictPriority`].
+    pub fn with_stream_scheduler(
+        mut self,
+        scheduler: &'static dyn stream::Scheduler,
+    ) -> Result<Self, ValidationError> {
+        self.stream_scheduler = scheduler;
+        Ok(self)
+    }
+
+    /// Sets the path MTU discovery settings applied to each path
+    ///
+    /// Returns an error if the base MTU exceeds the initial MTU, or the initial MTU exceeds the
+    /// max MTU. Defaults to [`path::mtu::Config::default`].
+    pub fn with_mtu_config(mut self, config: path::mtu::Config) -> Result<Self, ValidationError> {
+        config.validate()?;
+        self.mtu_config = config;
+        Ok(self)
+    }
+
+    /// Sets whether the connection spins the latency spin bit
+    ///
+    /// Defaults to [`path::spin::Config::default`], which disables the spin bit.
+    pub fn with_spin_config(mut self, config: path::spin::Config) -> Result<Self, ValidationError> {
+        self.spin_config = config;
+        Ok(self)
+    }
+
+    /// Sets whether the connection greases the QUIC bit
+    ///
+    /// When enabled, the connection advertises the `grease_quic_bit` transport parameter and
+    /// accepts packets with the QUIC bit set to 0. Disabled by default.
+    ///
+    /// This setting only controls which packets are accepted from the peer. The QUIC bit of
+    /// the packets sent to the peer is controlled by [`Self::with_randomized_quic_bit`].
+    pub fn with_grease_quic_bit(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.grease_quic_bit = if enabled {
+            GreaseQuicBit::Enabled
+        } else {
+            GreaseQuicBit::Disabled
+        };
+        Ok(self)
+    }
+
+    /// Sets whether the QUIC bit of 1-RTT packets is set to a random value when the peer
+    /// advertises the `grease_quic_bit` transport parameter
+    ///
+    /// Enabled by default, as recommended by RFC 9287. When disabled, the QUIC bit of all packets
+    /// is set to 1.
+    pub fn with_randomized_quic_bit(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.randomized_quic_bit = enabled;
+        Ok(self)
+    }
+
+    /// Sets whether a reserved transport parameter with a random identifier and value is sent
+    /// to the peer
+    ///
+    /// Disabled by default.
+    pub fn with_grease_transport_parameters(
+        mut self,
+        enabled: bool,
+    ) -> Result<Self, ValidationError> {
+        self.grease_transport_parameters = enabled;
+        Ok(self)
+    }
+
     // internal APIs
 
     #[doc(hidden)]
@@ -236,6 +400,10 @@ impl Limits {
             max_ack_delay: self.max_ack_delay.as_duration(),
             ack_ranges_limit: self.ack_ranges_limit,
             ack_elicitation_interval: self.ack_elicitation_interval,
+            min_ack_delay: match self.min_ack_delay {
+                Some(min_ack_delay) => Some(min_ack_delay.as_duration()),
+                None => None,
+            },
         }
     }
 
@@ -274,6 +442,54 @@ impl Limits {
         }
     }
 
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_data_window(&self) -> u32 {
+        self.max_data_window
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_stream_data_window(&self) -> u32 {
+        self.max_stream_data_window
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn stream_scheduler(&self) -> &'static dyn stream::Scheduler {
+        self.stream_scheduler
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn mtu_config(&self) -> path::mtu::Config {
+        self.mtu_config
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn spin_config(&self) -> path::spin::Config {
+        self.spin_config
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn grease_quic_bit(&self) -> GreaseQuicBit {
+        self.grease_quic_bit
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn grease_transport_parameters(&self) -> bool {
+        self.grease_transport_parameters
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn randomized_quic_bit(&self) -> bool {
+        self.randomized_quic_bit
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn max_idle_timeout(&self) -> Option<Duration> {
@@ -291,6 +507,23 @@ impl Limits {
     pub fn max_keep_alive_period(&self) -> Duration {
         self.max_keep_alive_period
     }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn enable_multipath(&self) -> EnableMultipath {
+        self.enable_multipath
+    }
+
+    /// Only offers to share the application data packet number space between paths if the
+    /// packet protection keys can't use a path identifier
+    #[doc(hidden)]
+    #[inline]
+    pub fn with_path_identifier_support(mut self, supported: bool) -> Self {
+        if !supported && self.enable_multipath == EnableMultipath::MultiplePacketNumberSpaces {
+            self.enable_multipath = EnableMultipath::SinglePacketNumberSpace;
+        }
+        self
+    }
 }
 
 /// Creates limits for a given connection
@@ -318,11 +551,33 @@ mod tests {
         assert!(limits.with_bidirectional_local_data_window(data).is_err());
         assert!(limits.with_bidirectional_remote_data_window(data).is_err());
         assert!(limits.with_unidirectional_data_window(data).is_err());
+        assert!(limits.with_max_data_window(data).is_err());
+        assert!(limits.with_max_stream_data_window(data).is_err());
 
         data = u32::MAX as u64;
         assert!(limits.with_data_window(data).is_ok());
         assert!(limits.with_bidirectional_local_data_window(data).is_ok());
         assert!(limits.with_bidirectional_remote_data_window(data).is_ok());
         assert!(limits.with_unidirectional_data_window(data).is_ok());
+        assert!(limits.with_max_data_window(data).is_ok());
+        assert!(limits.with_max_stream_data_window(data).is_ok());
+    }
+
+    // min_ack_delay should be <= max_ack_delay
+    #[test]
+    fn min_ack_delay_validation() {
+        let limits = Limits::default();
+        assert!(limits.min_ack_delay.is_none());
+        assert!(limits.ack_settings().min_ack_delay.is_none());
+
+        assert!(limits
+            .with_min_ack_delay(Duration::from_millis(26))
+            .is_err());
+
+        let limits = limits.with_min_ack_delay(Duration::from_millis(1)).unwrap();
+        assert_eq!(
+            limits.ack_settings().min_ack_delay,
+            Some(Duration::from_millis(1))
+        );
     }
 }
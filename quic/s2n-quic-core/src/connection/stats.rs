@@ -0,0 +1,108 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Point-in-time snapshots of connection statistics
+
+use crate::inet::SocketAddress;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+/// A snapshot of the statistics for a connection
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Statistics for each of the paths the connection is currently tracking
+    pub paths: Vec<Path>,
+    /// The number of streams opened on the connection
+    pub streams: Streams,
+    /// The total amount of time the connection was blocked from sending by the peer's
+    /// connection flow control limit
+    pub flow_control_blocked_time: Duration,
+    /// The number of stream bytes which were declared lost and queued to be sent again
+    pub retransmitted_stream_bytes: u64,
+}
+
+impl Stats {
+    /// Returns the statistics for the path the connection is currently using
+    #[inline]
+    pub fn active_path(&self) -> Option<&Path> {
+        self.paths.iter().find(|path| path.is_active)
+    }
+}
+
+/// A snapshot of the statistics for a single path
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Path {
+    /// The local address of the path
+    pub local_address: SocketAddress,
+    /// The remote address of the path
+    pub remote_address: SocketAddress,
+    /// True if the path is currently used by the connection
+    pub is_active: bool,
+    /// The minimum round-trip time observed on the path
+    pub min_rtt: Duration,
+    /// The exponentially-weighted moving average of the round-trip time samples
+    pub smoothed_rtt: Duration,
+    /// The most recent round-trip time sample
+    pub latest_rtt: Duration,
+    /// The mean deviation of the round-trip time samples
+    pub rtt_variance: Duration,
+    /// The number of bytes the congestion controller allows to be in flight
+    pub congestion_window: u32,
+    /// The number of bytes sent on the path which have not been acknowledged or declared lost
+    pub bytes_in_flight: u32,
+    /// The maximum transmission unit of the path
+    pub mtu: u16,
+    /// The probe timeout backoff multiplier
+    pub pto_count: u32,
+    /// The number of packets sent on the path
+    pub packets_sent: u64,
+    /// The number of bytes sent on the path
+    pub bytes_sent: u64,
+    /// The number of packets declared lost on the path
+    ///
+    /// Any frames in lost packets which require reliable delivery are retransmitted in
+    /// new packets. The stream data which is retransmitted is counted by
+    /// [`Stats::retransmitted_stream_bytes`].
+    pub packets_lost: u64,
+    /// The number of bytes declared lost on the path
+    pub bytes_lost: u64,
+    /// The ECN markings the peer reported for packets sent on the path
+    pub ecn_counts: EcnCounts,
+}
+
+/// The number of packets the peer received with each ECN codepoint
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EcnCounts {
+    /// The number of packets received with the ECT(0) codepoint
+    pub ect_0: u64,
+    /// The number of packets received with the ECT(1) codepoint
+    pub ect_1: u64,
+    /// The number of packets received with the ECN-CE codepoint
+    pub ce: u64,
+}
+
+/// The number of streams opened on a connection
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Streams {
+    /// Bidirectional streams opened by the local endpoint
+    pub local_bidirectional: StreamCounts,
+    /// Unidirectional streams opened by the local endpoint
+    pub local_unidirectional: StreamCounts,
+    /// Bidirectional streams opened by the peer
+    pub remote_bidirectional: StreamCounts,
+    /// Unidirectional streams opened by the peer
+    pub remote_unidirectional: StreamCounts,
+}
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamCounts {
+    /// The number of streams which are currently open
+    pub open: u64,
+    /// The total number of streams opened over the lifetime of the connection
+    pub total: u64,
+}
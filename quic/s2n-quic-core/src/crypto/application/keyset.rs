@@ -109,10 +109,13 @@ impl<K: OneRttKey> KeySet<K> {
     /// Passes the key for the the requested phase to a callback function. Integrity limits are
     /// enforced.
     ///
+    /// The path identifier is `0` unless paths use separate packet number spaces.
+    ///
     /// Returns the decrypted packet and generation if the key phase was rotated.
     pub fn decrypt_packet<'a>(
         &mut self,
         packet: EncryptedShort<'a>,
+        path_identifier: u32,
         largest_acknowledged_packet_number: PacketNumber,
         pto: Timestamp,
     ) -> Result<(CleartextShort<'a>, Option<u16>), ProcessingError> {
@@ -142,7 +145,7 @@ impl<K: OneRttKey> KeySet<K> {
 
         let key = &mut self.crypto[phase_to_use.into()];
 
-        let result = packet.decrypt(key.key());
+        let result = packet.decrypt_for_path(key.key_mut(), path_identifier);
 
         key.on_packet_decryption(&self.limits);
 
@@ -229,7 +232,7 @@ impl<K: OneRttKey> KeySet<K> {
     where
         F: FnOnce(
             EncoderBuffer<'a>,
-            &K,
+            &mut K,
             KeyPhase,
         )
             -> Result<(ProtectedPayload<'a>, EncoderBuffer<'a>), PacketEncodingError<'a>>,
@@ -249,7 +252,7 @@ impl<K: OneRttKey> KeySet<K> {
             return Err(PacketEncodingError::AeadLimitReached(buffer));
         }
 
-        let r = f(buffer, self.crypto[phase].key(), phase)?;
+        let r = f(buffer, self.crypto[phase].key_mut(), phase)?;
 
         //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
         //# Endpoints MUST count the number of encrypted packets for each set of
@@ -431,6 +434,7 @@ mod tests {
         assert!(keyset
             .decrypt_packet(
                 encrypted_packet,
+                0,
                 PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(0)),
                 clock.get_time(),
             )
@@ -474,6 +478,7 @@ mod tests {
             keyset
                 .decrypt_packet(
                     encrypted_packet,
+                    0,
                     PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(0)),
                     clock.get_time(),
                 )
@@ -105,4 +105,9 @@ impl<K: OneRttKey> Key<K> {
     pub fn key(&self) -> &K {
         &self.key
     }
+
+    #[inline]
+    pub fn key_mut(&mut self) -> &mut K {
+        &mut self.key
+    }
 }
@@ -12,8 +12,11 @@ use hex_literal::hex;
 pub trait InitialKey: crypto::Key + Sized {
     type HeaderKey: crypto::HeaderKey;
 
-    fn new_server(connection_id: &[u8]) -> (Self, Self::HeaderKey);
-    fn new_client(connection_id: &[u8]) -> (Self, Self::HeaderKey);
+    /// Derives the server keys for the Initial packets of the given QUIC version
+    fn new_server(version: u32, connection_id: &[u8]) -> (Self, Self::HeaderKey);
+
+    /// Derives the client keys for the Initial packets of the given QUIC version
+    fn new_client(version: u32, connection_id: &[u8]) -> (Self, Self::HeaderKey);
 }
 
 /// Types for which are able to perform initial header cryptography.
@@ -28,6 +31,14 @@ pub trait InitialHeaderKey: crypto::HeaderKey {}
 
 pub const INITIAL_SALT: [u8; 20] = hex!("38762cf7f55934b34d179ae6a4c80cadccbb7f0a");
 
+//= https://www.rfc-editor.org/rfc/rfc9369#section-3.3.1
+//# The salt used to derive Initial keys in Section 5.2 of [QUIC-TLS]
+//# changes to:
+//#
+//# initial_salt = 0x0dede3def700a6db819381be6e269dcbf9bd2ed9
+
+pub const INITIAL_SALT_V2: [u8; 20] = hex!("0dede3def700a6db819381be6e269dcbf9bd2ed9");
+
 //= https://www.rfc-editor.org/rfc/rfc9001#section-5.2
 //# client_initial_secret = HKDF-Expand-Label(initial_secret,
 //#                                           "client in", "",
@@ -115,16 +115,19 @@ pub mod testing {
     impl InitialKey for Key {
         type HeaderKey = HeaderKey;
 
-        fn new_server(_connection_id: &[u8]) -> (Self, Self::HeaderKey) {
+        fn new_server(_version: u32, _connection_id: &[u8]) -> (Self, Self::HeaderKey) {
             (Key::default(), HeaderKey::default())
         }
 
-        fn new_client(_connection_id: &[u8]) -> (Self, Self::HeaderKey) {
+        fn new_client(_version: u32, _connection_id: &[u8]) -> (Self, Self::HeaderKey) {
             (Key::default(), HeaderKey::default())
         }
     }
     impl HandshakeKey for Key {}
     impl OneRttKey for Key {
+        // The testing key doesn't use a nonce so any path identifier is supported
+        const SUPPORTS_PATH_IDENTIFIER: bool = true;
+
         fn derive_next_key(&self) -> Self {
             Self {
                 integrity_limit: self.integrity_limit,
@@ -136,13 +139,33 @@ pub mod testing {
 
         fn update_sealer_pmtu(&mut self, _pmtu: u16) {}
         fn update_opener_pmtu(&mut self, _pmtu: u16) {}
+
+        fn decrypt_for_path(
+            &self,
+            _path_identifier: u32,
+            packet_number: u64,
+            header: &[u8],
+            payload: &mut [u8],
+        ) -> Result<(), CryptoError> {
+            super::Key::decrypt(self, packet_number, header, payload)
+        }
+
+        fn encrypt_for_path(
+            &self,
+            _path_identifier: u32,
+            packet_number: u64,
+            header: &[u8],
+            payload: &mut [u8],
+        ) -> Result<(), CryptoError> {
+            super::Key::encrypt(self, packet_number, header, payload)
+        }
     }
     impl ZeroRttKey for Key {}
     impl RetryKey for Key {
-        fn generate_tag(_payload: &[u8]) -> IntegrityTag {
+        fn generate_tag(_version: u32, _payload: &[u8]) -> IntegrityTag {
             [0u8; INTEGRITY_TAG_LEN]
         }
-        fn validate(_payload: &[u8], _tag: IntegrityTag) -> Result<(), CryptoError> {
+        fn validate(_version: u32, _payload: &[u8], _tag: IntegrityTag) -> Result<(), CryptoError> {
             Ok(())
         }
     }
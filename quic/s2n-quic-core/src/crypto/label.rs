@@ -54,6 +54,26 @@ pub const QUIC_KU_32: [u8; 17] = hex!("00200d746c7331332071756963206b7500");
 // 48-byte labels
 pub const QUIC_KU_48: [u8; 17] = hex!("00300d746c7331332071756963206b7500");
 
+//= https://www.rfc-editor.org/rfc/rfc9369#section-3.3.2
+//# The labels used in [QUIC-TLS] to derive packet protection keys
+//# (Section 5.1), header protection keys (Section 5.4), Retry Integrity
+//# Tag keys (Section 5.8), and key updates (Section 6.1) change from
+//# "quic key" to "quicv2 key", from "quic iv" to "quicv2 iv", from "quic
+//# hp" to "quicv2 hp", and from "quic ku" to "quicv2 ku"
+
+pub const QUICV2_KEY_16: [u8; 20] = hex!("001010746c73313320717569637632206b657900");
+pub const QUICV2_IV_12: [u8; 19] = hex!("000c0f746c7331332071756963763220697600");
+pub const QUICV2_HP_16: [u8; 19] = hex!("00100f746c7331332071756963763220687000");
+
+// 32-byte version 2 labels
+
+pub const QUICV2_KEY_32: [u8; 20] = hex!("002010746c73313320717569637632206b657900");
+pub const QUICV2_HP_32: [u8; 19] = hex!("00200f746c7331332071756963763220687000");
+pub const QUICV2_KU_32: [u8; 19] = hex!("00200f746c73313320717569637632206b7500");
+
+// 48-byte version 2 labels
+pub const QUICV2_KU_48: [u8; 19] = hex!("00300f746c73313320717569637632206b7500");
+
 /// Computes the label given the key len
 pub fn compute_label<T: Extend<u8>>(len: usize, label: &[u8], out: &mut T) {
     const TLS_LABEL: &[u8] = b"tls13 ";
@@ -97,6 +117,17 @@ mod tests {
         assert_eq!(compute_vec_label(48, b"quic ku"), QUIC_KU_48);
     }
 
+    #[test]
+    fn version_2_test() {
+        assert_eq!(compute_vec_label(16, b"quicv2 key"), QUICV2_KEY_16);
+        assert_eq!(compute_vec_label(12, b"quicv2 iv"), QUICV2_IV_12);
+        assert_eq!(compute_vec_label(16, b"quicv2 hp"), QUICV2_HP_16);
+        assert_eq!(compute_vec_label(32, b"quicv2 key"), QUICV2_KEY_32);
+        assert_eq!(compute_vec_label(32, b"quicv2 hp"), QUICV2_HP_32);
+        assert_eq!(compute_vec_label(32, b"quicv2 ku"), QUICV2_KU_32);
+        assert_eq!(compute_vec_label(48, b"quicv2 ku"), QUICV2_KU_48);
+    }
+
     fn compute_vec_label(len: usize, label: &[u8]) -> Vec<u8> {
         let mut out = vec![];
         compute_label(len, label, &mut out);
@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::crypto::{HeaderKey, Key};
+use crate::crypto::{CryptoError, HeaderKey, Key};
 
 /// Types for which are able to perform 1-RTT cryptography.
 ///
@@ -9,11 +9,57 @@ use crate::crypto::{HeaderKey, Key};
 /// are used with Short packets. Any key misuses are
 /// caught by the type system.
 pub trait OneRttKey: Key {
+    /// Set to `true` if the key can protect packets in the packet number space of a path
+    /// other than the initial path
+    ///
+    /// Keys that don't support it can only be used with the path identifier `0`.
+    const SUPPORTS_PATH_IDENTIFIER: bool = false;
+
     #[must_use]
     fn derive_next_key(&self) -> Self;
 
     fn update_sealer_pmtu(&mut self, pmtu: u16);
     fn update_opener_pmtu(&mut self, pmtu: u16);
+
+    /// Decrypt a payload that was sent in the packet number space of a path
+    ///
+    /// When paths use separate packet number spaces, the 32-bit path identifier is used in
+    /// place of the leading zero bits of the packet number, before it is combined with the
+    /// IV to form the nonce.
+    ///
+    /// See https://datatracker.ietf.org/doc/html/draft-ietf-quic-multipath-03
+    #[inline]
+    fn decrypt_for_path(
+        &self,
+        path_identifier: u32,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut [u8],
+    ) -> Result<(), CryptoError> {
+        if path_identifier == 0 {
+            return self.decrypt(packet_number, header, payload);
+        }
+
+        Err(CryptoError::DECRYPT_ERROR)
+    }
+
+    /// Encrypt a payload that is sent in the packet number space of a path
+    ///
+    /// See [`Self::decrypt_for_path`]
+    #[inline]
+    fn encrypt_for_path(
+        &self,
+        path_identifier: u32,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut [u8],
+    ) -> Result<(), CryptoError> {
+        if path_identifier == 0 {
+            return self.encrypt(packet_number, header, payload);
+        }
+
+        Err(CryptoError::INTERNAL_ERROR)
+    }
 }
 
 /// Types for which are able to perform 1-RTT header cryptography.
@@ -22,3 +68,66 @@ pub trait OneRttKey: Key {
 /// are used with Short packets. Any key misuses are
 /// caught by the type system.
 pub trait OneRttHeaderKey: HeaderKey {}
+
+/// A 1-RTT key bound to the packet number space of a path
+///
+/// The key is borrowed mutably so the wrapper can be sent across threads for any key
+/// implementation.
+pub struct PathKey<'a, K: OneRttKey> {
+    key: &'a mut K,
+    path_identifier: u32,
+}
+
+impl<'a, K: OneRttKey> PathKey<'a, K> {
+    #[inline]
+    pub fn new(key: &'a mut K, path_identifier: u32) -> Self {
+        Self {
+            key,
+            path_identifier,
+        }
+    }
+}
+
+impl<'a, K: OneRttKey> Key for PathKey<'a, K> {
+    #[inline]
+    fn decrypt(
+        &self,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut [u8],
+    ) -> Result<(), CryptoError> {
+        self.key
+            .decrypt_for_path(self.path_identifier, packet_number, header, payload)
+    }
+
+    #[inline]
+    fn encrypt(
+        &self,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut [u8],
+    ) -> Result<(), CryptoError> {
+        self.key
+            .encrypt_for_path(self.path_identifier, packet_number, header, payload)
+    }
+
+    #[inline]
+    fn tag_len(&self) -> usize {
+        self.key.tag_len()
+    }
+
+    #[inline]
+    fn aead_confidentiality_limit(&self) -> u64 {
+        self.key.aead_confidentiality_limit()
+    }
+
+    #[inline]
+    fn aead_integrity_limit(&self) -> u64 {
+        self.key.aead_integrity_limit()
+    }
+
+    #[inline]
+    fn cipher_suite(&self) -> crate::crypto::tls::CipherSuite {
+        self.key.cipher_suite()
+    }
+}
@@ -47,6 +47,13 @@ impl<'a> ProtectedPayload<'a> {
         }
     }
 
+    /// Reads the packet tag in the payload
+    ///
+    /// Note that the packet number length and key phase bits are still protected
+    pub fn get_tag(&self) -> u8 {
+        self.buffer.as_less_safe_slice()[0]
+    }
+
     /// Reads data from a `CheckedRange`
     pub fn get_checked_range(&self, range: &CheckedRange) -> DecoderBuffer {
         self.buffer.get_checked_range(range)
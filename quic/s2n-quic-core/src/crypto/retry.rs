@@ -8,8 +8,11 @@ pub const INTEGRITY_TAG_LEN: usize = 16;
 pub type IntegrityTag = [u8; INTEGRITY_TAG_LEN];
 
 pub trait RetryKey {
-    fn generate_tag(payload: &[u8]) -> IntegrityTag;
-    fn validate(payload: &[u8], tag: IntegrityTag) -> Result<(), CryptoError>;
+    /// Computes the Retry Integrity Tag of a pseudo-packet for the given QUIC version
+    fn generate_tag(version: u32, payload: &[u8]) -> IntegrityTag;
+
+    /// Validates the Retry Integrity Tag of a pseudo-packet for the given QUIC version
+    fn validate(version: u32, payload: &[u8], tag: IntegrityTag) -> Result<(), CryptoError>;
 }
 
 //= https://www.rfc-editor.org/rfc/rfc9001#section-5.8
@@ -26,6 +29,19 @@ pub const SECRET_KEY_BYTES: [u8; 16] = hex!("be0c690b9f66575a1d766b54e368c84e");
 
 pub const NONCE_BYTES: [u8; 12] = hex!("461599d35d632bf2239825bb");
 
+//= https://www.rfc-editor.org/rfc/rfc9369#section-3.3.3
+//# The key and nonce used for the Retry Integrity Tag (Section 5.8 of
+//# [QUIC-TLS]) change to:
+//#
+//# secret_key = 0x8fb4b01b56ac48e260fbcbcead7ccc92
+
+pub const SECRET_KEY_BYTES_V2: [u8; 16] = hex!("8fb4b01b56ac48e260fbcbcead7ccc92");
+
+//= https://www.rfc-editor.org/rfc/rfc9369#section-3.3.3
+//# nonce = 0xd86969bc2d7c6d9990efb04a
+
+pub const NONCE_BYTES_V2: [u8; 12] = hex!("d86969bc2d7c6d9990efb04a");
+
 pub mod example {
     use super::*;
 
@@ -58,6 +74,16 @@ pub mod example {
 
     pub const EXPECTED_TAG: [u8; 16] = hex!("04a265ba2eff4d829058fb3f0f2496ba");
 
+    //= https://www.rfc-editor.org/rfc/rfc9369#appendix-A.4
+    //# This shows a Retry packet that might be sent in response to the
+    //# Initial packet in Appendix A.2.  The integrity check includes the
+    //# client-chosen connection ID value of 0x8394c8f03e515708, but that
+    //# value is not included in the final Retry packet:
+    pub const PSEUDO_PACKET_V2: [u8; 29] =
+        hex!("088394c8f03e515708 cf6b3343cf 00 08f067a5502a4262b5 746f6b656e");
+
+    pub const EXPECTED_TAG_V2: [u8; 16] = hex!("c8646ce8bfe33952d955543665dcc7b6");
+
     // The server sends an empty destination connection ID back to the client
     pub const DCID: [u8; 0] = hex!("");
 
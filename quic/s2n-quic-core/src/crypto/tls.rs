@@ -6,6 +6,11 @@ pub use bytes::{Bytes, BytesMut};
 use core::{convert::TryFrom, fmt::Debug};
 use zerocopy::{AsBytes, FromBytes, Unaligned};
 
+#[cfg(feature = "alloc")]
+pub mod certificate;
+pub mod client_hello;
+#[cfg(feature = "alloc")]
+pub mod session_ticket;
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
 
@@ -37,6 +42,12 @@ pub trait Context<Crypto: crate::crypto::CryptoSuite> {
         application_parameters: ApplicationParameters,
     ) -> Result<(), crate::transport::Error>;
 
+    /// Called on the client before the 1-RTT keys are installed if 0-RTT keys were installed
+    ///
+    /// `accepted` is `true` if the server accepted the early data. Clients that are never told
+    /// the outcome treat the early data as rejected.
+    fn on_early_data(&mut self, accepted: bool) -> Result<(), crate::transport::Error>;
+
     fn on_one_rtt_keys(
         &mut self,
         key: Crypto::OneRttKey,
@@ -54,6 +65,27 @@ pub trait Context<Crypto: crate::crypto::CryptoSuite> {
         application_protocol: Bytes,
     ) -> Result<(), crate::transport::Error>;
 
+    /// Called when the TLS provider determines if the handshake resumed a previous session
+    fn on_session_resumption(&mut self, resumed: bool) -> Result<(), crate::transport::Error>;
+
+    /// Called when the server presented a certificate from a [`certificate::Store`]
+    ///
+    /// `server_name` is the name the certificate was added for, or `None` for the default
+    /// certificate.
+    fn on_server_certificate(
+        &mut self,
+        server_name: Option<&str>,
+    ) -> Result<(), crate::transport::Error>;
+
+    /// Called with the certificate chain presented by the peer
+    ///
+    /// The certificates are DER-encoded, starting with the peer's own certificate. This is not
+    /// called if the peer didn't present any certificates.
+    fn on_peer_certificate_chain(
+        &mut self,
+        chain: alloc::vec::Vec<Bytes>,
+    ) -> Result<(), crate::transport::Error>;
+
     //= https://www.rfc-editor.org/rfc/rfc9001#section-4.1.1
     //# The TLS handshake is considered complete when the
     //# TLS stack has reported that the handshake is complete.  This happens
@@ -89,6 +121,11 @@ pub trait Context<Crypto: crate::crypto::CryptoSuite> {
     fn send_application(&mut self, transmission: Bytes);
 
     fn waker(&self) -> &core::task::Waker;
+
+    /// Returns the QUIC version currently in use on the connection
+    ///
+    /// The packet protection keys for each version are derived with different labels.
+    fn quic_version(&self) -> u32;
 }
 
 #[cfg(feature = "alloc")]
@@ -100,6 +137,18 @@ pub trait Endpoint: 'static + Sized + Send {
         transport_parameters: &Params,
     ) -> Self::Session;
 
+    /// Creates a server session which rejects any early data offered by the client
+    ///
+    /// This is called instead of [`Self::new_server_session`] when the
+    /// [`early_data::Controller`](crate::early_data::Controller) rejects early data. Providers
+    /// that are able to accept early data must override the default implementation.
+    fn new_server_session_without_early_data<Params: s2n_codec::EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+    ) -> Self::Session {
+        self.new_server_session(transport_parameters)
+    }
+
     fn new_client_session<Params: s2n_codec::EncoderValue>(
         &mut self,
         transport_parameters: &Params,
@@ -108,6 +157,12 @@ pub trait Endpoint: 'static + Sized + Send {
 
     /// The maximum length of a tag for any algorithm that may be negotiated
     fn max_tag_length(&self) -> usize;
+
+    /// Returns `true` if the provider is able to derive packet protection keys for the
+    /// QUIC version
+    fn supports_quic_version(&self, version: u32) -> bool {
+        version == crate::version::VERSION_1
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -116,6 +171,17 @@ pub trait Session: crate::crypto::CryptoSuite + Sized + Send + Debug {
         &mut self,
         context: &mut C,
     ) -> core::task::Poll<Result<(), crate::transport::Error>>;
+
+    /// Fills `output` with keying material exported from the session, as described in
+    /// [RFC 8446 Section 7.5](https://www.rfc-editor.org/rfc/rfc8446#section-7.5)
+    ///
+    /// This is only available once the handshake is complete.
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), crate::transport::Error>;
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -179,10 +245,14 @@ macro_rules! handshake_type {
 //#     finished(20)
 //#     (255)
 //# } HandshakeType;
+//
+//= https://www.rfc-editor.org/rfc/rfc8446#section-4
+//# new_session_ticket(4),
 handshake_type!(
     HelloRequest(0),
     ClientHello(1),
     ServerHello(2),
+    NewSessionTicket(4),
     Certificate(11),
     ServerKeyExchange(12),
     CertificateRequest(13),
@@ -0,0 +1,249 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Types for selecting the certificate a server presents based on the requested server name
+//!
+//! Servers keep their [`Certificates`] in a [`Store`], which can be reloaded while the server is
+//! running. Handshakes which already selected a certificate are not affected by a reload.
+
+use alloc::{collections::BTreeMap, string::String};
+
+/// Certificates indexed by the server names they are presented for
+///
+/// `T` is the certificate type of the TLS provider.
+#[derive(Clone, Debug)]
+pub struct Certificates<T> {
+    default: Option<T>,
+    server_names: BTreeMap<String, T>,
+}
+
+impl<T> Default for Certificates<T> {
+    fn default() -> Self {
+        Self {
+            default: None,
+            server_names: BTreeMap::new(),
+        }
+    }
+}
+
+/// The certificate which was selected for a server name
+#[derive(Debug)]
+pub struct Selection<'a, T> {
+    pub certificate: &'a T,
+    /// The server name the certificate was added for, or `None` for the default certificate
+    pub server_name: Option<&'a str>,
+}
+
+impl<T> Certificates<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the certificate presented when no other certificate matches the requested server
+    /// name, or when the client doesn't request one
+    pub fn with_default(mut self, certificate: T) -> Self {
+        self.default = Some(certificate);
+        self
+    }
+
+    /// Adds a certificate presented for `server_name`
+    ///
+    /// Wildcard names such as `*.example.com` match exactly one additional label.
+    pub fn with_server_name(mut self, server_name: &str, certificate: T) -> Self {
+        self.server_names
+            .insert(normalize(server_name), certificate);
+        self
+    }
+
+    /// Selects the certificate for the server name requested by the client
+    pub fn select(&self, server_name: Option<&str>) -> Option<Selection<'_, T>> {
+        if let Some(server_name) = server_name {
+            let server_name = normalize(server_name);
+
+            if let Some((name, certificate)) = self.server_names.get_key_value(&server_name) {
+                return Some(Selection {
+                    certificate,
+                    server_name: Some(name),
+                });
+            }
+
+            if let Some(index) = server_name.find('.') {
+                let wildcard = alloc::format!("*{}", &server_name[index..]);
+                if let Some((name, certificate)) = self.server_names.get_key_value(&wildcard) {
+                    return Some(Selection {
+                        certificate,
+                        server_name: Some(name),
+                    });
+                }
+            }
+        }
+
+        self.default.as_ref().map(|certificate| Selection {
+            certificate,
+            server_name: None,
+        })
+    }
+
+    /// Returns `true` if no certificates were added
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.server_names.is_empty()
+    }
+}
+
+/// Server names are case-insensitive and may be fully qualified
+fn normalize(server_name: &str) -> String {
+    server_name.trim_end_matches('.').to_ascii_lowercase()
+}
+
+#[cfg(feature = "std")]
+pub use store::*;
+
+#[cfg(feature = "std")]
+mod store {
+    use super::Certificates;
+    use alloc::string::String;
+    use std::sync::{Arc, Mutex, RwLock};
+
+    /// Certificates which can be replaced while the server is running
+    ///
+    /// Clones of the store share the same certificates.
+    #[derive(Debug)]
+    pub struct Store<T> {
+        certificates: Arc<RwLock<Arc<Certificates<T>>>>,
+    }
+
+    impl<T> Clone for Store<T> {
+        fn clone(&self) -> Self {
+            Self {
+                certificates: self.certificates.clone(),
+            }
+        }
+    }
+
+    impl<T> Store<T> {
+        pub fn new(certificates: Certificates<T>) -> Self {
+            Self {
+                certificates: Arc::new(RwLock::new(Arc::new(certificates))),
+            }
+        }
+
+        /// Atomically replaces the certificates
+        ///
+        /// New handshakes select from the new certificates, while existing connections are
+        /// not affected.
+        pub fn reload(&self, certificates: Certificates<T>) {
+            *self.certificates.write().unwrap() = Arc::new(certificates);
+        }
+
+        /// Returns the current certificates
+        pub fn load(&self) -> Arc<Certificates<T>> {
+            self.certificates.read().unwrap().clone()
+        }
+    }
+
+    impl<T> Default for Store<T> {
+        fn default() -> Self {
+            Self::new(Certificates::new())
+        }
+    }
+
+    /// Selects the certificate for a single handshake and records which one was presented
+    ///
+    /// The selection is made from the certificates loaded when the handshake started, so the
+    /// certificate reported once the handshake completes is the one which was presented, even
+    /// if the store was reloaded in between.
+    #[derive(Debug)]
+    pub struct Selector<T> {
+        certificates: Arc<Certificates<T>>,
+        /// The server name of the selected certificate, set when the client hello is received
+        selected: Mutex<Option<Option<String>>>,
+    }
+
+    impl<T> Selector<T> {
+        pub fn new(certificates: Arc<Certificates<T>>) -> Self {
+            Self {
+                certificates,
+                selected: Mutex::new(None),
+            }
+        }
+
+        /// Selects the certificate for the server name requested by the client
+        pub fn select(&self, server_name: Option<&str>) -> Option<&T> {
+            let selection = self.certificates.select(server_name)?;
+            *self.selected.lock().unwrap() = Some(selection.server_name.map(String::from));
+            Some(selection.certificate)
+        }
+
+        /// Returns the server name the selected certificate was added for
+        ///
+        /// Returns `None` if no certificate was selected, and `Some(None)` if the default
+        /// certificate was selected.
+        pub fn selected(&self) -> Option<Option<String>> {
+            self.selected.lock().unwrap().clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_test() {
+        let certificates = Certificates::new()
+            .with_default(0)
+            .with_server_name("example.com", 1)
+            .with_server_name("*.example.com", 2);
+
+        let select = |server_name| {
+            let selection = certificates.select(server_name).unwrap();
+            (*selection.certificate, selection.server_name)
+        };
+
+        assert_eq!(select(None), (0, None));
+        assert_eq!(select(Some("example.com")), (1, Some("example.com")));
+        assert_eq!(select(Some("EXAMPLE.com.")), (1, Some("example.com")));
+        assert_eq!(select(Some("www.example.com")), (2, Some("*.example.com")));
+        // wildcards only match a single label
+        assert_eq!(select(Some("a.b.example.com")), (0, None));
+        assert_eq!(select(Some("example.org")), (0, None));
+
+        assert!(Certificates::<u8>::new()
+            .select(Some("example.com"))
+            .is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn reload_test() {
+        let store = Store::new(Certificates::new().with_default(0));
+        let previous = store.load();
+
+        store.clone().reload(Certificates::new().with_default(1));
+
+        // existing handles keep the previous certificates
+        assert_eq!(*previous.select(None).unwrap().certificate, 0);
+        assert_eq!(*store.load().select(None).unwrap().certificate, 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn selector_test() {
+        let store = Store::new(
+            Certificates::new()
+                .with_default(0)
+                .with_server_name("example.com", 1),
+        );
+        let selector = Selector::new(store.load());
+        assert_eq!(selector.selected(), None);
+
+        // reloading the store doesn't change the certificates the handshake selects from
+        store.reload(Certificates::new().with_default(2));
+
+        assert_eq!(selector.select(Some("example.com")), Some(&1));
+        assert_eq!(selector.selected(), Some(Some("example.com".into())));
+
+        assert_eq!(selector.select(Some("example.org")), Some(&0));
+        assert_eq!(selector.selected(), Some(None));
+    }
+}
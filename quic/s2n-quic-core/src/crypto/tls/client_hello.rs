@@ -0,0 +1,257 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Minimal parsing of a TLS ClientHello message
+//!
+//! This allows servers to inspect the client's transport parameters before the TLS
+//! session is created, which is required to select a compatible QUIC version and to decide
+//! if early data is accepted.
+
+use crate::transport::parameters::VersionInformation;
+use s2n_codec::{u24, DecoderBuffer, DecoderError};
+
+const CLIENT_HELLO: u8 = 1;
+
+//= https://www.rfc-editor.org/rfc/rfc9001#section-8.2
+//# enum {
+//#    quic_transport_parameters(0x39), (65535)
+//# } ExtensionType;
+const QUIC_TRANSPORT_PARAMETERS: u16 = 0x39;
+
+// See https://www.rfc-editor.org/rfc/rfc8446#section-4.2
+const SERVER_NAME: u16 = 0;
+const APPLICATION_LAYER_PROTOCOL_NEGOTIATION: u16 = 16;
+const EARLY_DATA: u16 = 42;
+
+// See https://www.rfc-editor.org/rfc/rfc6066#section-3
+const HOST_NAME: u8 = 0;
+
+/// The fields of a ClientHello message which are inspected before the TLS session is created
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct ClientHello<'a> {
+    /// The host name from the server_name extension, if it is valid UTF-8
+    pub server_name: Option<&'a str>,
+    /// The protocols offered in the application_layer_protocol_negotiation extension
+    pub application_protocols: ApplicationProtocols<'a>,
+    /// `true` if the client offered to send early data
+    pub early_data: bool,
+    /// The encoded QUIC transport parameters
+    pub transport_parameters: Option<&'a [u8]>,
+    /// The version_information transport parameter, if it's present and valid
+    pub version_information: Option<VersionInformation>,
+}
+
+impl<'a> ClientHello<'a> {
+    /// Parses a ClientHello message
+    ///
+    /// `None` is returned if the buffer does not start with a complete ClientHello.
+    pub fn decode(buffer: &'a [u8]) -> Option<Self> {
+        decode_client_hello(DecoderBuffer::new(buffer))
+            .ok()
+            .flatten()
+    }
+}
+
+/// The list of protocols offered by a client in the ALPN extension
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ApplicationProtocols<'a> {
+    buffer: &'a [u8],
+}
+
+impl<'a> ApplicationProtocols<'a> {
+    /// Returns an iterator over the offered protocols, in the client's order of preference
+    pub fn iter(&self) -> impl Iterator<Item = &'a [u8]> {
+        let mut buffer = DecoderBuffer::new(self.buffer);
+        core::iter::from_fn(move || {
+            let (protocol, remaining) = buffer.decode_slice_with_len_prefix::<u8>().ok()?;
+            buffer = remaining;
+            Some(protocol.into_less_safe_slice())
+        })
+    }
+
+    /// Returns `true` if the client offered the protocol
+    pub fn contains(&self, protocol: &[u8]) -> bool {
+        self.iter().any(|offered| offered == protocol)
+    }
+}
+
+/// Returns the encoded QUIC transport parameters from a ClientHello message
+///
+/// `None` is returned if the buffer does not start with a complete ClientHello or the
+/// extension is missing.
+pub fn transport_parameters(buffer: &[u8]) -> Option<&[u8]> {
+    ClientHello::decode(buffer)?.transport_parameters
+}
+
+fn decode_client_hello(buffer: DecoderBuffer<'_>) -> Result<Option<ClientHello<'_>>, DecoderError> {
+    let (msg_type, buffer) = buffer.decode::<u8>()?;
+    if msg_type != CLIENT_HELLO {
+        return Ok(None);
+    }
+
+    let (len, buffer) = buffer.decode::<u24>()?;
+    let (body, _) = buffer.decode_slice(*len as usize)?;
+
+    // legacy_version and random
+    let body = body.skip(2 + 32)?;
+    // legacy_session_id
+    let body = body.skip_with_len_prefix::<u8>()?;
+    // cipher_suites
+    let body = body.skip_with_len_prefix::<u16>()?;
+    // legacy_compression_methods
+    let body = body.skip_with_len_prefix::<u8>()?;
+
+    let (mut extensions, _) = body.decode_slice_with_len_prefix::<u16>()?;
+
+    let mut client_hello = ClientHello::default();
+
+    while !extensions.is_empty() {
+        let (extension_type, remaining) = extensions.decode::<u16>()?;
+        let (extension_data, remaining) = remaining.decode_slice_with_len_prefix::<u16>()?;
+
+        match extension_type {
+            QUIC_TRANSPORT_PARAMETERS => {
+                let transport_parameters = extension_data.into_less_safe_slice();
+                client_hello.transport_parameters = Some(transport_parameters);
+                client_hello.version_information = VersionInformation::find(transport_parameters);
+            }
+            // The TLS provider validates the extensions once the session is created so
+            // malformed values are ignored here
+            SERVER_NAME => {
+                client_hello.server_name = decode_server_name(extension_data).ok().flatten();
+            }
+            APPLICATION_LAYER_PROTOCOL_NEGOTIATION => {
+                if let Ok((protocols, _)) = extension_data.decode_slice_with_len_prefix::<u16>() {
+                    client_hello.application_protocols = ApplicationProtocols {
+                        buffer: protocols.into_less_safe_slice(),
+                    };
+                }
+            }
+            EARLY_DATA => {
+                client_hello.early_data = true;
+            }
+            _ => {}
+        }
+
+        extensions = remaining;
+    }
+
+    Ok(Some(client_hello))
+}
+
+fn decode_server_name<'a>(
+    extension_data: DecoderBuffer<'a>,
+) -> Result<Option<&'a str>, DecoderError> {
+    let (mut names, _) = extension_data.decode_slice_with_len_prefix::<u16>()?;
+
+    while !names.is_empty() {
+        let (name_type, remaining) = names.decode::<u8>()?;
+        let (name, remaining) = remaining.decode_slice_with_len_prefix::<u16>()?;
+
+        if name_type == HOST_NAME {
+            return Ok(core::str::from_utf8(name.into_less_safe_slice()).ok());
+        }
+
+        names = remaining;
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client_hello(extensions: &[(u16, &[u8])]) -> Vec<u8> {
+        let mut extension_bytes = vec![];
+        for (extension_type, data) in extensions {
+            extension_bytes.extend_from_slice(&extension_type.to_be_bytes());
+            extension_bytes.extend_from_slice(&(data.len() as u16).to_be_bytes());
+            extension_bytes.extend_from_slice(data);
+        }
+
+        let mut body = vec![0x03, 0x03];
+        body.extend_from_slice(&[7; 32]);
+        // legacy_session_id
+        body.extend_from_slice(&[0]);
+        // cipher_suites
+        body.extend_from_slice(&[0, 2, 0x13, 0x01]);
+        // legacy_compression_methods
+        body.extend_from_slice(&[1, 0]);
+        body.extend_from_slice(&(extension_bytes.len() as u16).to_be_bytes());
+        body.extend_from_slice(&extension_bytes);
+
+        let mut message = vec![CLIENT_HELLO];
+        message.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+        message.extend_from_slice(&body);
+        message
+    }
+
+    #[test]
+    fn transport_parameters_test() {
+        let message = client_hello(&[(0x0000, b"server"), (0x0039, &[1, 2, 3])]);
+        assert_eq!(transport_parameters(&message), Some(&[1u8, 2, 3][..]));
+
+        let message = client_hello(&[(0x0000, b"server")]);
+        assert_eq!(transport_parameters(&message), None);
+
+        // the message is split across multiple CRYPTO frames
+        let message = client_hello(&[(0x0039, &[1, 2, 3])]);
+        assert_eq!(transport_parameters(&message[..message.len() - 1]), None);
+
+        // not a ClientHello
+        assert_eq!(transport_parameters(&[2, 0, 0, 0]), None);
+    }
+
+    #[test]
+    fn early_data_test() {
+        let server_name = [
+            0, 12, HOST_NAME, 0, 9, b'l', b'o', b'c', b'a', b'l', b'h', b'o', b's', b't',
+        ];
+        let alpn = [0, 7, 2, b'h', b'3', 3, b'h', b'q', b'1'];
+        let message = client_hello(&[
+            (SERVER_NAME, &server_name),
+            (APPLICATION_LAYER_PROTOCOL_NEGOTIATION, &alpn),
+            (EARLY_DATA, &[]),
+        ]);
+
+        let hello = ClientHello::decode(&message).unwrap();
+        assert_eq!(hello.server_name, Some("localhost"));
+        assert!(hello.early_data);
+        assert!(hello.application_protocols.contains(b"h3"));
+        assert!(!hello.application_protocols.contains(b"h2"));
+        assert_eq!(
+            hello.application_protocols.iter().collect::<Vec<_>>(),
+            [&b"h3"[..], &b"hq1"[..]]
+        );
+
+        let message = client_hello(&[(SERVER_NAME, &server_name)]);
+        let hello = ClientHello::decode(&message).unwrap();
+        assert!(!hello.early_data);
+        assert_eq!(hello.application_protocols.iter().count(), 0);
+    }
+
+    #[test]
+    fn version_information_test() {
+        use crate::version::{VERSION_1, VERSION_2};
+
+        // version_information (0x11) with a length of 12
+        let mut transport_parameters = vec![0x11, 12];
+        for version in [VERSION_1, VERSION_2, VERSION_1] {
+            transport_parameters.extend_from_slice(&version.to_be_bytes());
+        }
+        let message = client_hello(&[(QUIC_TRANSPORT_PARAMETERS, &transport_parameters)]);
+        let hello = ClientHello::decode(&message).unwrap();
+        assert_eq!(
+            hello.version_information,
+            Some(VersionInformation::new(VERSION_1, &[VERSION_1, VERSION_2]))
+        );
+
+        // the parameter is missing
+        let message = client_hello(&[(QUIC_TRANSPORT_PARAMETERS, &[1, 1, 42])]);
+        let hello = ClientHello::decode(&message).unwrap();
+        assert!(hello.transport_parameters.is_some());
+        assert_eq!(hello.version_information, None);
+    }
+}
@@ -0,0 +1,252 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Types for resuming TLS sessions with session tickets
+//!
+//! Clients remember the tickets issued by a server in a [`Store`] and use them to resume the
+//! session on a later connection, skipping certificate authentication and enabling early data.
+//! Servers encrypt the tickets they issue with keys from a [`KeySource`], which are rotated
+//! according to a [`KeyRotation`] policy.
+
+use crate::{application::ServerName, transport::parameters::ValidationError};
+use bytes::Bytes;
+use core::{fmt, time::Duration};
+
+/// A session ticket issued to the client by a server
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Ticket {
+    /// The serialized session state produced by the TLS provider
+    pub session: Bytes,
+    /// The transport parameters sent by the server on the connection that issued the ticket
+    ///
+    /// This is `None` if the TLS provider stores the transport parameters as part of the
+    /// session state.
+    pub transport_parameters: Option<Bytes>,
+    /// How long the server indicated the ticket can be used to resume the session
+    pub lifetime: Duration,
+}
+
+impl Ticket {
+    pub fn new(session: Bytes, transport_parameters: Option<Bytes>, lifetime: Duration) -> Self {
+        Self {
+            session,
+            transport_parameters,
+            lifetime,
+        }
+    }
+}
+
+/// Stores the session tickets issued to a client, keyed by the server name
+pub trait Store: 'static + Send + Sync {
+    /// Called when the server identified by `server_name` issues a new ticket
+    fn on_ticket(&self, server_name: &ServerName, ticket: Ticket);
+
+    /// Removes and returns a ticket to resume a session with `server_name`, if any
+    ///
+    /// Tickets that are past their lifetime should not be returned.
+    //= https://www.rfc-editor.org/rfc/rfc8446#appendix-C.4
+    //# Clients SHOULD NOT reuse a
+    //# ticket for multiple connections.
+    fn take(&self, server_name: &ServerName) -> Option<Ticket>;
+}
+
+//= https://www.rfc-editor.org/rfc/rfc8446#section-4.6.1
+//# Servers MUST NOT use any value greater than
+//# 604800 seconds (7 days).
+const MAX_TICKET_LIFETIME: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Controls how long a server uses each key to encrypt and decrypt session tickets
+///
+/// A new key is introduced at the start of every encrypt period and is used to encrypt all of
+/// the tickets issued during that period. Once the period ends, the key continues to decrypt
+/// tickets for the duration of the decrypt period before it is discarded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyRotation {
+    encrypt_period: Duration,
+    decrypt_period: Duration,
+}
+
+impl Default for KeyRotation {
+    fn default() -> Self {
+        Self {
+            encrypt_period: Duration::from_secs(2 * 60 * 60),
+            decrypt_period: Duration::from_secs(13 * 60 * 60),
+        }
+    }
+}
+
+impl KeyRotation {
+    /// Sets how long each key is used to encrypt new tickets
+    pub fn with_encrypt_period(mut self, period: Duration) -> Result<Self, ValidationError> {
+        if period.is_zero() {
+            return Err(ValidationError("the encrypt period must be non-zero"));
+        }
+        self.encrypt_period = period;
+        Ok(self)
+    }
+
+    /// Sets how long each key continues to decrypt tickets after its encrypt period ends
+    ///
+    /// This is also the lifetime issued with each ticket and cannot exceed 7 days.
+    pub fn with_decrypt_period(mut self, period: Duration) -> Result<Self, ValidationError> {
+        if period.is_zero() || period > MAX_TICKET_LIFETIME {
+            return Err(ValidationError(
+                "the decrypt period must be non-zero and no longer than 7 days",
+            ));
+        }
+        self.decrypt_period = period;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn encrypt_period(&self) -> Duration {
+        self.encrypt_period
+    }
+
+    #[inline]
+    pub fn decrypt_period(&self) -> Duration {
+        self.decrypt_period
+    }
+
+    /// Returns the lifetime to advertise with each ticket
+    ///
+    /// A ticket can always be decrypted for at least the decrypt period, regardless of when it
+    /// was issued in the encrypt period of its key.
+    #[inline]
+    pub fn ticket_lifetime(&self) -> Duration {
+        self.decrypt_period
+    }
+
+    /// Returns the total amount of time a key is retained after being introduced
+    #[inline]
+    pub fn key_lifetime(&self) -> Duration {
+        self.encrypt_period + self.decrypt_period
+    }
+
+    /// Returns the index of the encrypt period containing `since_epoch`
+    ///
+    /// Periods are counted from the UNIX epoch so independent servers with the same policy
+    /// agree on the current period.
+    #[inline]
+    pub fn period(&self, since_epoch: Duration) -> u64 {
+        (since_epoch.as_nanos() / self.encrypt_period.as_nanos()) as u64
+    }
+
+    /// Returns the time since the UNIX epoch at which the encrypt period `period` starts
+    #[inline]
+    pub fn period_start(&self, period: u64) -> Duration {
+        let nanos = self.encrypt_period.as_nanos() * period as u128;
+        Duration::new(
+            (nanos / 1_000_000_000) as u64,
+            (nanos % 1_000_000_000) as u32,
+        )
+    }
+}
+
+/// A key used by a server to encrypt and decrypt session tickets
+#[derive(Clone, PartialEq, Eq)]
+pub struct Key {
+    name: [u8; 16],
+    material: [u8; 32],
+}
+
+impl Key {
+    /// Creates a key with a `name` that identifies it in the tickets it encrypts
+    ///
+    /// Each key must have a unique name.
+    pub fn new(name: [u8; 16], material: [u8; 32]) -> Self {
+        Self { name, material }
+    }
+
+    #[inline]
+    pub fn name(&self) -> &[u8; 16] {
+        &self.name
+    }
+
+    #[inline]
+    pub fn material(&self) -> &[u8; 32] {
+        &self.material
+    }
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // don't leak the key material into logs
+        f.debug_struct("Key").field("name", &self.name).finish()
+    }
+}
+
+/// The error returned by a [`KeySource`] that could not provide a key
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyError(pub &'static str);
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for KeyError {}
+
+/// Provides the keys a server uses to encrypt and decrypt session tickets
+///
+/// The server asks for the key of each encrypt period it needs, as returned by
+/// [`KeyRotation::period`]. Servers which share a source and a [`KeyRotation`] policy encrypt
+/// tickets with the same keys, allowing clients to resume their sessions on any of them.
+pub trait KeySource: 'static + Send + Sync {
+    /// Returns the key for the encrypt period `period`
+    ///
+    /// The same key must be returned every time the same period is requested.
+    fn key(&self, period: u64) -> Result<Key, KeyError>;
+}
+
+impl<F: Fn(u64) -> Result<Key, KeyError> + 'static + Send + Sync> KeySource for F {
+    #[inline]
+    fn key(&self, period: u64) -> Result<Key, KeyError> {
+        (self)(period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_rotation_test() {
+        let rotation = KeyRotation::default();
+        assert_eq!(rotation.ticket_lifetime(), rotation.decrypt_period());
+        assert_eq!(
+            rotation.key_lifetime(),
+            rotation.encrypt_period() + rotation.decrypt_period()
+        );
+
+        assert!(rotation.with_encrypt_period(Duration::ZERO).is_err());
+        assert!(rotation.with_decrypt_period(Duration::ZERO).is_err());
+        assert!(rotation
+            .with_decrypt_period(MAX_TICKET_LIFETIME + Duration::from_secs(1))
+            .is_err());
+        assert!(rotation.with_decrypt_period(MAX_TICKET_LIFETIME).is_ok());
+    }
+
+    #[test]
+    fn key_rotation_period_test() {
+        let rotation = KeyRotation::default()
+            .with_encrypt_period(Duration::from_millis(1500))
+            .unwrap();
+
+        assert_eq!(rotation.period(Duration::ZERO), 0);
+        assert_eq!(rotation.period(Duration::from_millis(1499)), 0);
+        assert_eq!(rotation.period(Duration::from_millis(1500)), 1);
+        assert_eq!(rotation.period(Duration::from_secs(30)), 20);
+
+        assert_eq!(rotation.period_start(0), Duration::ZERO);
+        assert_eq!(rotation.period_start(1), Duration::from_millis(1500));
+        assert_eq!(rotation.period_start(20), Duration::from_secs(30));
+
+        for period in [0, 1, 1_000, u32::MAX as u64] {
+            assert_eq!(rotation.period(rotation.period_start(period)), period);
+        }
+    }
+}
@@ -88,6 +88,15 @@ impl super::Session for Session {
     ) -> Poll<Result<(), transport::Error>> {
         todo!("implement dummy handshake")
     }
+
+    fn export_keying_material(
+        &self,
+        _label: &[u8],
+        _context: &[u8],
+        _output: &mut [u8],
+    ) -> Result<(), transport::Error> {
+        todo!("implement dummy handshake")
+    }
 }
 
 impl CryptoSuite for Session {
@@ -140,12 +149,18 @@ impl<S: tls::Session, C: tls::Session> Pair<S, C> {
         let server = server_endpoint.new_server_session(&TEST_SERVER_TRANSPORT_PARAMS);
         let mut server_context =
             Context::new(endpoint::Type::Server, ServerState::WaitingClientHello);
-        server_context.initial.crypto = Some(S::InitialKey::new_server(server_name.as_bytes()));
+        server_context.initial.crypto = Some(S::InitialKey::new_server(
+            server_context.quic_version,
+            server_name.as_bytes(),
+        ));
 
         let client =
             client_endpoint.new_client_session(&TEST_CLIENT_TRANSPORT_PARAMS, server_name.clone());
         let mut client_context = Context::new(endpoint::Type::Client, ClientState::ClientHelloSent);
-        client_context.initial.crypto = Some(C::InitialKey::new_client(server_name.as_bytes()));
+        client_context.initial.crypto = Some(C::InitialKey::new_client(
+            client_context.quic_version,
+            server_name.as_bytes(),
+        ));
 
         Self {
             server: TlsEndpoint::new(server, server_context),
@@ -337,7 +352,11 @@ pub struct Context<C: CryptoSuite, State: Debug> {
     pub handshake_complete: bool,
     pub server_name: Option<Bytes>,
     pub application_protocol: Option<Bytes>,
+    pub peer_certificate_chain: Option<Vec<Bytes>>,
+    /// The server name of the certificate the server selected from its certificate store
+    pub server_certificate: Option<Option<String>>,
     pub transport_parameters: Option<Bytes>,
+    pub quic_version: u32,
     endpoint: endpoint::Type,
     pub state: State,
     waker: Waker,
@@ -370,7 +389,10 @@ impl<C: CryptoSuite, State: Debug> Context<C, State> {
             handshake_complete: false,
             server_name: None,
             application_protocol: None,
+            peer_certificate_chain: None,
+            server_certificate: None,
             transport_parameters: None,
+            quic_version: crate::version::VERSION_1,
             endpoint,
             state,
             waker,
@@ -587,6 +609,23 @@ impl<C: CryptoSuite, State: Debug> tls::Context<C> for Context<C, State> {
         Ok(())
     }
 
+    fn on_early_data(&mut self, accepted: bool) -> Result<(), transport::Error> {
+        assert!(
+            self.endpoint.is_client(),
+            "only clients are told if early data was accepted"
+        );
+        assert!(
+            self.zero_rtt_crypto.is_some(),
+            "early data outcome reported without 0-rtt keys"
+        );
+        self.log(if accepted {
+            "early data accepted"
+        } else {
+            "early data rejected"
+        });
+        Ok(())
+    }
+
     fn on_one_rtt_keys(
         &mut self,
         key: C::OneRttKey,
@@ -622,6 +661,36 @@ impl<C: CryptoSuite, State: Debug> tls::Context<C> for Context<C, State> {
         Ok(())
     }
 
+    fn on_session_resumption(&mut self, resumed: bool) -> Result<(), transport::Error> {
+        self.log(if resumed {
+            "session resumed"
+        } else {
+            "session not resumed"
+        });
+        Ok(())
+    }
+
+    fn on_server_certificate(&mut self, server_name: Option<&str>) -> Result<(), transport::Error> {
+        self.log(if server_name.is_some() {
+            "server certificate selected"
+        } else {
+            "default server certificate selected"
+        });
+        assert!(
+            self.server_certificate.is_none(),
+            "server certificate selected more than once"
+        );
+        self.server_certificate = Some(server_name.map(String::from));
+        Ok(())
+    }
+
+    fn on_peer_certificate_chain(&mut self, chain: Vec<Bytes>) -> Result<(), transport::Error> {
+        assert!(!chain.is_empty(), "peer certificate chain is empty");
+        self.log("peer certificate chain");
+        self.peer_certificate_chain = Some(chain);
+        Ok(())
+    }
+
     fn on_handshake_complete(&mut self) -> Result<(), transport::Error> {
         assert!(
             !self.handshake_complete,
@@ -689,4 +758,8 @@ impl<C: CryptoSuite, State: Debug> tls::Context<C> for Context<C, State> {
     fn waker(&self) -> &Waker {
         &self.waker
     }
+
+    fn quic_version(&self) -> u32 {
+        self.quic_version
+    }
 }
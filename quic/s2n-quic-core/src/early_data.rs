@@ -0,0 +1,215 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Controls the acceptance of early data (0-RTT) by servers
+//!
+//! Data sent in 0-RTT packets is not protected against replay attacks. Applications can use
+//! a [`Controller`] to decide which connections are allowed to make use of early data.
+
+use crate::{
+    application::ServerName,
+    crypto::tls::client_hello::ApplicationProtocols,
+    event::{self, api::SocketAddress, IntoEvent, Timestamp},
+    inet,
+};
+use core::time::Duration;
+
+/// Information about a connection attempting to use early data
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Attempt<'a> {
+    /// The unverified address of the peer sending early data
+    pub remote_address: SocketAddress<'a>,
+    /// The server name indicated by the client, if any
+    pub server_name: Option<&'a ServerName>,
+    /// The application protocols offered by the client
+    pub application_protocols: ApplicationProtocols<'a>,
+    pub timestamp: Timestamp,
+}
+
+impl<'a> Attempt<'a> {
+    #[doc(hidden)]
+    pub fn new(
+        remote_address: &'a inet::SocketAddress,
+        server_name: Option<&'a ServerName>,
+        application_protocols: ApplicationProtocols<'a>,
+        timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            remote_address: remote_address.into_event(),
+            server_name,
+            application_protocols,
+            timestamp,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Outcome {
+    /// Early data is processed as it arrives
+    Accept,
+
+    /// Early data is rejected by the TLS handshake
+    ///
+    /// The 0-RTT packets are discarded and the client sends the data again once the
+    /// 1-RTT keys are available.
+    Reject(RejectReason),
+}
+
+impl Outcome {
+    /// Process early data as it arrives
+    pub fn accept() -> Self {
+        Self::Accept
+    }
+
+    /// Reject any early data offered on the connection
+    pub fn reject() -> Self {
+        Self::Reject(RejectReason::Rejected)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RejectReason {
+    /// The controller rejected the early data
+    Rejected,
+    /// Early data is disabled on the endpoint
+    Disabled,
+    /// Too many connections have recently used early data
+    RateLimited,
+}
+
+impl IntoEvent<event::builder::EarlyDataRejected> for RejectReason {
+    #[inline]
+    fn into_event(self) -> event::builder::EarlyDataRejected {
+        let reason = match self {
+            RejectReason::Rejected => event::builder::EarlyDataRejectReason::Rejected,
+            RejectReason::Disabled => event::builder::EarlyDataRejectReason::Disabled,
+            RejectReason::RateLimited => event::builder::EarlyDataRejectReason::RateLimited,
+        };
+        event::builder::EarlyDataRejected { reason }
+    }
+}
+
+/// Decides if a connection is allowed to process early data
+pub trait Controller: 'static + Send {
+    /// Called by the server when a client offers early data in its ClientHello
+    ///
+    /// The outcome is decided once the whole ClientHello has been received and before the TLS
+    /// session is created, so the handshake never accepts early data that is rejected here.
+    ///
+    /// ```rust
+    /// # mod s2n_quic { pub mod provider { pub mod early_data { pub use s2n_quic_core::early_data::*; } } }
+    /// use s2n_quic::provider::early_data::{Attempt, Controller, Outcome};
+    ///
+    /// struct MyController;
+    ///
+    /// impl Controller for MyController {
+    ///     fn on_early_data(&mut self, attempt: &Attempt) -> Outcome {
+    ///         if attempt.application_protocols.contains(b"h3") {
+    ///             Outcome::accept()
+    ///         } else {
+    ///             Outcome::reject()
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn on_early_data(&mut self, attempt: &Attempt) -> Outcome;
+}
+
+pub mod default {
+    use super::*;
+
+    /// Accepts all early data that has been accepted by the TLS provider
+    #[derive(Debug, Default)]
+    pub struct Controller;
+
+    impl super::Controller for Controller {
+        #[inline]
+        fn on_early_data(&mut self, _attempt: &Attempt) -> Outcome {
+            Outcome::Accept
+        }
+    }
+}
+
+/// Rejects all early data
+#[derive(Debug, Default)]
+pub struct Disabled;
+
+impl Controller for Disabled {
+    #[inline]
+    fn on_early_data(&mut self, _attempt: &Attempt) -> Outcome {
+        Outcome::Reject(RejectReason::Disabled)
+    }
+}
+
+/// Accepts early data on a limited number of connections per period
+#[derive(Debug)]
+pub struct RateLimited {
+    max_connections: u32,
+    period: Duration,
+    window_start: Option<Timestamp>,
+    accepted: u32,
+}
+
+impl RateLimited {
+    /// Creates a controller accepting early data on at most `max_connections` in each `period`
+    pub fn new(max_connections: u32, period: Duration) -> Self {
+        Self {
+            max_connections,
+            period,
+            window_start: None,
+            accepted: 0,
+        }
+    }
+}
+
+impl Controller for RateLimited {
+    #[inline]
+    fn on_early_data(&mut self, attempt: &Attempt) -> Outcome {
+        let now = attempt.timestamp;
+
+        match self.window_start {
+            Some(start) if now.saturating_duration_since(start) < self.period => {}
+            _ => {
+                self.window_start = Some(now);
+                self.accepted = 0;
+            }
+        }
+
+        if self.accepted >= self.max_connections {
+            return Outcome::Reject(RejectReason::RateLimited);
+        }
+
+        self.accepted += 1;
+        Outcome::Accept
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::testing::now;
+
+    #[test]
+    fn rate_limited_test() {
+        let mut controller = RateLimited::new(2, Duration::from_secs(1));
+        let addr = inet::SocketAddress::default();
+        let mut now = now();
+
+        for expected in [
+            Outcome::Accept,
+            Outcome::Accept,
+            Outcome::Reject(RejectReason::RateLimited),
+        ] {
+            let attempt = Attempt::new(&addr, None, Default::default(), now);
+            assert_eq!(controller.on_early_data(&attempt), expected);
+        }
+
+        // the limit resets once the period has elapsed
+        now += Duration::from_secs(1);
+        let attempt = Attempt::new(&addr, None, Default::default(), now);
+        assert_eq!(controller.on_early_data(&attempt), Outcome::Accept);
+    }
+}
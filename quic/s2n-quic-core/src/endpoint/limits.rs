@@ -5,6 +5,7 @@ use crate::{
     event::{api::SocketAddress, IntoEvent, Timestamp},
     inet,
 };
+use core::time::Duration;
 
 /// Outcome describes how the library should proceed on a connection attempt. The implementor will
 /// use information from the ConnectionAttempt object to determine how the library should handle
@@ -94,6 +95,31 @@ impl<'a> ConnectionAttempt<'a> {
     }
 }
 
+/// Limits the number of stateless resets an endpoint sends in response to packets for unknown
+/// connections
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StatelessResetLimit {
+    /// The maximum number of stateless resets sent in each period
+    pub max_resets: usize,
+
+    /// The period over which the number of sent stateless resets is limited
+    pub period: Duration,
+}
+
+impl StatelessResetLimit {
+    /// Limits the number of stateless resets to `max_resets` in each `period`
+    pub fn new(max_resets: usize, period: Duration) -> Self {
+        Self { max_resets, period }
+    }
+}
+
+impl Default for StatelessResetLimit {
+    fn default() -> Self {
+        Self::new(1024, Duration::from_secs(1))
+    }
+}
+
 pub trait Limiter: 'static + Send {
     /// This trait is used to determine the outcome of connection attempts on an endpoint. The
     /// implementor returns an Outcome based on the ConnectionAttempt, or other information that the
@@ -119,4 +145,11 @@ pub trait Limiter: 'static + Send {
     /// }
     /// ```
     fn on_connection_attempt(&mut self, info: &ConnectionAttempt) -> Outcome;
+
+    /// Returns the limit on the number of stateless resets the endpoint sends
+    ///
+    /// The limit is read once when the endpoint is created.
+    fn stateless_reset_limit(&self) -> StatelessResetLimit {
+        StatelessResetLimit::default()
+    }
 }
@@ -132,6 +132,43 @@ pub trait Endpoint: 'static + Send + Sized {
     /// Sets the largest maximum transmission unit (MTU) that can be sent on a path
     fn set_max_mtu(&mut self, max_mtu: MaxMtu);
 
+    /// Called by IO providers that report ICMP Packet Too Big messages through
+    /// `on_packet_too_big`, before the endpoint is started
+    ///
+    /// Endpoints may skip tracking the state used for validating the messages until this
+    /// is called.
+    #[inline]
+    fn enable_packet_too_big(&mut self) {}
+
+    /// Called when the IO provider receives an ICMP Packet Too Big message
+    ///
+    /// `remote_address` is the destination of the datagram which was too big, `quoted_packet`
+    /// contains the start of the QUIC packet quoted by the message, and `mtu` is the largest
+    /// MTU the message reported for the path. The message is ignored by default.
+    #[inline]
+    fn on_packet_too_big<C: Clock>(
+        &mut self,
+        remote_address: &path::RemoteAddress,
+        quoted_packet: &[u8],
+        mtu: u16,
+        clock: &C,
+    ) {
+        let _ = (remote_address, quoted_packet, mtu, clock);
+    }
+
+    /// Sets the hook used to bind sockets to additional local addresses
+    ///
+    /// This is called by IO providers that support binding sockets before the endpoint is
+    /// started. Without a binder, paths can only be opened from the socket of the endpoint.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn set_binder(
+        &mut self,
+        binder: alloc::sync::Arc<dyn crate::io::bind::Bind<PathHandle = Self::PathHandle>>,
+    ) {
+        let _ = binder;
+    }
+
     /// Returns the endpoint's event subscriber
     fn subscriber(&mut self) -> &mut Self::Subscriber;
 }
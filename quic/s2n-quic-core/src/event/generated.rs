@@ -199,6 +199,27 @@ pub mod api {
         HandshakeDone {},
         #[non_exhaustive]
         Datagram { len: u16 },
+        #[non_exhaustive]
+        AckFrequency {
+            sequence_number: u64,
+            ack_eliciting_threshold: u64,
+            request_max_ack_delay: u64,
+            reordering_threshold: u64,
+        },
+        #[non_exhaustive]
+        ImmediateAck {},
+        #[non_exhaustive]
+        AckMp {
+            path_identifier: u64,
+            ecn_counts: Option<EcnCounts>,
+            largest_acknowledged: u64,
+            ack_range_count: u64,
+        },
+        #[non_exhaustive]
+        PathAbandon {
+            path_identifier: u64,
+            error_code: u64,
+        },
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -302,6 +323,9 @@ pub mod api {
         #[non_exhaustive]
         #[doc = " The peer initiated a connection migration without supplying enough connection IDs to use."]
         InsufficientConnectionIds {},
+        #[non_exhaustive]
+        #[doc = " The peer cleared the QUIC bit before the `grease_quic_bit` transport parameter was negotiated."]
+        QuicBitCleared {},
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -357,11 +381,18 @@ pub mod api {
         #[doc = " The client received a non-empty retry token."]
         NonEmptyRetryToken { path: Path<'a> },
         #[non_exhaustive]
+        #[doc = " Early data was rejected for the connection and the 0-RTT packet was discarded."]
+        EarlyDataRejected { path: Path<'a> },
+        #[non_exhaustive]
         #[doc = " A Retry packet was discarded."]
         RetryDiscarded {
             reason: RetryDiscardReason<'a>,
             path: Path<'a>,
         },
+        #[non_exhaustive]
+        #[doc = " The packet had the QUIC bit set to 0 but the connection didn't advertise"]
+        #[doc = " the `grease_quic_bit` transport parameter."]
+        QuicBitCleared { path: Path<'a> },
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -416,6 +447,22 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    pub enum EarlyDataRejectReason {
+        #[non_exhaustive]
+        #[doc = " The early data controller rejected the early data"]
+        Rejected {},
+        #[non_exhaustive]
+        #[doc = " Early data is disabled on the endpoint"]
+        Disabled {},
+        #[non_exhaustive]
+        #[doc = " Too many connections have recently used early data"]
+        RateLimited {},
+        #[non_exhaustive]
+        #[doc = " The peer rejected the early data sent by the local endpoint"]
+        Peer {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " The current state of the ECN controller for the path"]
     pub enum EcnState {
         #[non_exhaustive]
@@ -521,6 +568,9 @@ pub mod api {
         #[non_exhaustive]
         #[doc = " A blackhole was detected"]
         Blackhole {},
+        #[non_exhaustive]
+        #[doc = " An ICMP Packet Too Big message reported a smaller MTU"]
+        PacketTooBig {},
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -848,6 +898,93 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The client validated the path to the server's preferred address and migrated to it"]
+    pub struct PreferredAddressMigrated<'a> {
+        pub previous: Path<'a>,
+        pub active: Path<'a>,
+    }
+    impl<'a> Event for PreferredAddressMigrated<'a> {
+        const NAME: &'static str = "connectivity:preferred_address_migrated";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The client was unable to validate the path to the server's preferred address"]
+    #[doc = ""]
+    #[doc = " The connection continues to use the original path."]
+    pub struct PreferredAddressMigrationFailed<'a> {
+        pub path: Path<'a>,
+    }
+    impl<'a> Event for PreferredAddressMigrationFailed<'a> {
+        const NAME: &'static str = "connectivity:preferred_address_migration_failed";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The application migrated the connection to a new local address and the path was validated"]
+    pub struct LocalAddressMigrated<'a> {
+        pub previous: Path<'a>,
+        pub active: Path<'a>,
+    }
+    impl<'a> Event for LocalAddressMigrated<'a> {
+        const NAME: &'static str = "connectivity:local_address_migrated";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The path from the new local address requested by the application failed validation"]
+    #[doc = ""]
+    #[doc = " The connection continues to use the original path."]
+    pub struct LocalAddressMigrationFailed<'a> {
+        pub path: Path<'a>,
+    }
+    impl<'a> Event for LocalAddressMigrationFailed<'a> {
+        const NAME: &'static str = "connectivity:local_address_migration_failed";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " Early data (0-RTT) was accepted on the connection"]
+    pub struct EarlyDataAccepted {}
+    impl Event for EarlyDataAccepted {
+        const NAME: &'static str = "transport:early_data_accepted";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " Early data (0-RTT) was rejected on the connection"]
+    pub struct EarlyDataRejected {
+        pub reason: EarlyDataRejectReason,
+    }
+    impl Event for EarlyDataRejected {
+        const NAME: &'static str = "transport:early_data_rejected";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The TLS handshake completed and reported if a previous session was resumed"]
+    pub struct SessionResumption {
+        pub resumed: bool,
+    }
+    impl Event for SessionResumption {
+        const NAME: &'static str = "transport:session_resumption";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The server kept the client's original QUIC version since the client didn't report its versions"]
+    pub struct VersionUpgradeSkipped {
+        #[doc = " The version used by the connection"]
+        pub version: u32,
+    }
+    impl Event for VersionUpgradeSkipped {
+        const NAME: &'static str = "transport:version_upgrade_skipped";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The server presented a certificate from its certificate store"]
+    pub struct ServerCertificateSelected<'a> {
+        #[doc = " The server name the certificate was added for, or `None` for the default certificate"]
+        pub server_name: Option<&'a str>,
+    }
+    impl<'a> Event for ServerCertificateSelected<'a> {
+        const NAME: &'static str = "transport:server_certificate_selected";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     pub struct HandshakeStatusUpdated {
         pub status: HandshakeStatus,
     }
@@ -963,6 +1100,19 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " A receive window was grown because the application is consuming data faster than the"]
+    #[doc = " peer is able to send it"]
+    pub struct ReceiveWindowUpdated {
+        #[doc = " The stream of the window, or `None` for the connection window"]
+        pub stream_id: Option<u64>,
+        #[doc = " The new size of the window"]
+        pub window: u64,
+    }
+    impl Event for ReceiveWindowUpdated {
+        const NAME: &'static str = "transport:receive_window_updated";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " QUIC version"]
     pub struct VersionInformation<'a> {
         pub server_versions: &'a [u32],
@@ -1491,6 +1641,45 @@ pub mod api {
             }
         }
     }
+    impl IntoEvent<builder::Frame> for &crate::frame::AckFrequency {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::AckFrequency {
+                sequence_number: self.sequence_number.as_u64(),
+                ack_eliciting_threshold: self.ack_eliciting_threshold.as_u64(),
+                request_max_ack_delay: self.request_max_ack_delay.as_u64(),
+                reordering_threshold: self.reordering_threshold.as_u64(),
+            }
+        }
+    }
+    impl IntoEvent<builder::Frame> for &crate::frame::ImmediateAck {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::ImmediateAck {}
+        }
+    }
+    impl<AckRanges: crate::frame::ack::AckRanges> IntoEvent<builder::Frame>
+        for &crate::frame::AckMp<AckRanges>
+    {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::AckMp {
+                path_identifier: self.path_identifier.as_u64(),
+                ecn_counts: self.ack.ecn_counts.map(|val| val.into_event()),
+                largest_acknowledged: self.ack.largest_acknowledged().into_event(),
+                ack_range_count: self.ack.ack_ranges().len() as u64,
+            }
+        }
+    }
+    impl<'a> IntoEvent<builder::Frame> for &crate::frame::PathAbandon<'a> {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::PathAbandon {
+                path_identifier: self.path_identifier.as_u64(),
+                error_code: self.error_code.as_u64(),
+            }
+        }
+    }
     impl IntoEvent<builder::StreamType> for &crate::stream::StreamType {
         #[inline]
         fn into_event(self) -> builder::StreamType {
@@ -1618,7 +1807,7 @@ pub mod tracing {
             let api::ApplicationProtocolInformation {
                 chosen_application_protocol,
             } = event;
-            tracing :: event ! (target : "application_protocol_information" , parent : id , tracing :: Level :: DEBUG , chosen_application_protocol = tracing :: field :: debug (chosen_application_protocol));
+            tracing :: event ! (target : "application_protocol_information" , parent : id , tracing :: Level :: DEBUG , { chosen_application_protocol = tracing :: field :: debug (chosen_application_protocol) });
         }
         #[inline]
         fn on_server_name_information(
@@ -1629,7 +1818,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::ServerNameInformation { chosen_server_name } = event;
-            tracing :: event ! (target : "server_name_information" , parent : id , tracing :: Level :: DEBUG , chosen_server_name = tracing :: field :: debug (chosen_server_name));
+            tracing :: event ! (target : "server_name_information" , parent : id , tracing :: Level :: DEBUG , { chosen_server_name = tracing :: field :: debug (chosen_server_name) });
         }
         #[inline]
         fn on_packet_sent(
@@ -1643,7 +1832,7 @@ pub mod tracing {
                 packet_header,
                 packet_len,
             } = event;
-            tracing :: event ! (target : "packet_sent" , parent : id , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header) , packet_len = tracing :: field :: debug (packet_len));
+            tracing :: event ! (target : "packet_sent" , parent : id , tracing :: Level :: DEBUG , { packet_header = tracing :: field :: debug (packet_header) , packet_len = tracing :: field :: debug (packet_len) });
         }
         #[inline]
         fn on_packet_received(
@@ -1654,7 +1843,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::PacketReceived { packet_header } = event;
-            tracing :: event ! (target : "packet_received" , parent : id , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header));
+            tracing :: event ! (target : "packet_received" , parent : id , tracing :: Level :: DEBUG , { packet_header = tracing :: field :: debug (packet_header) });
         }
         #[inline]
         fn on_active_path_updated(
@@ -1665,7 +1854,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::ActivePathUpdated { previous, active } = event;
-            tracing :: event ! (target : "active_path_updated" , parent : id , tracing :: Level :: DEBUG , previous = tracing :: field :: debug (previous) , active = tracing :: field :: debug (active));
+            tracing :: event ! (target : "active_path_updated" , parent : id , tracing :: Level :: DEBUG , { previous = tracing :: field :: debug (previous) , active = tracing :: field :: debug (active) });
         }
         #[inline]
         fn on_path_created(
@@ -1676,7 +1865,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::PathCreated { active, new } = event;
-            tracing :: event ! (target : "path_created" , parent : id , tracing :: Level :: DEBUG , active = tracing :: field :: debug (active) , new = tracing :: field :: debug (new));
+            tracing :: event ! (target : "path_created" , parent : id , tracing :: Level :: DEBUG , { active = tracing :: field :: debug (active) , new = tracing :: field :: debug (new) });
         }
         #[inline]
         fn on_frame_sent(
@@ -1691,7 +1880,7 @@ pub mod tracing {
                 path_id,
                 frame,
             } = event;
-            tracing :: event ! (target : "frame_sent" , parent : id , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header) , path_id = tracing :: field :: debug (path_id) , frame = tracing :: field :: debug (frame));
+            tracing :: event ! (target : "frame_sent" , parent : id , tracing :: Level :: DEBUG , { packet_header = tracing :: field :: debug (packet_header) , path_id = tracing :: field :: debug (path_id) , frame = tracing :: field :: debug (frame) });
         }
         #[inline]
         fn on_frame_received(
@@ -1706,7 +1895,7 @@ pub mod tracing {
                 path,
                 frame,
             } = event;
-            tracing :: event ! (target : "frame_received" , parent : id , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header) , path = tracing :: field :: debug (path) , frame = tracing :: field :: debug (frame));
+            tracing :: event ! (target : "frame_received" , parent : id , tracing :: Level :: DEBUG , { packet_header = tracing :: field :: debug (packet_header) , path = tracing :: field :: debug (path) , frame = tracing :: field :: debug (frame) });
         }
         #[inline]
         fn on_packet_lost(
@@ -1722,7 +1911,7 @@ pub mod tracing {
                 bytes_lost,
                 is_mtu_probe,
             } = event;
-            tracing :: event ! (target : "packet_lost" , parent : id , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header) , path = tracing :: field :: debug (path) , bytes_lost = tracing :: field :: debug (bytes_lost) , is_mtu_probe = tracing :: field :: debug (is_mtu_probe));
+            tracing :: event ! (target : "packet_lost" , parent : id , tracing :: Level :: DEBUG , { packet_header = tracing :: field :: debug (packet_header) , path = tracing :: field :: debug (path) , bytes_lost = tracing :: field :: debug (bytes_lost) , is_mtu_probe = tracing :: field :: debug (is_mtu_probe) });
         }
         #[inline]
         fn on_recovery_metrics(
@@ -1744,7 +1933,7 @@ pub mod tracing {
                 bytes_in_flight,
                 congestion_limited,
             } = event;
-            tracing :: event ! (target : "recovery_metrics" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , min_rtt = tracing :: field :: debug (min_rtt) , smoothed_rtt = tracing :: field :: debug (smoothed_rtt) , latest_rtt = tracing :: field :: debug (latest_rtt) , rtt_variance = tracing :: field :: debug (rtt_variance) , max_ack_delay = tracing :: field :: debug (max_ack_delay) , pto_count = tracing :: field :: debug (pto_count) , congestion_window = tracing :: field :: debug (congestion_window) , bytes_in_flight = tracing :: field :: debug (bytes_in_flight) , congestion_limited = tracing :: field :: debug (congestion_limited));
+            tracing :: event ! (target : "recovery_metrics" , parent : id , tracing :: Level :: DEBUG , { path = tracing :: field :: debug (path) , min_rtt = tracing :: field :: debug (min_rtt) , smoothed_rtt = tracing :: field :: debug (smoothed_rtt) , latest_rtt = tracing :: field :: debug (latest_rtt) , rtt_variance = tracing :: field :: debug (rtt_variance) , max_ack_delay = tracing :: field :: debug (max_ack_delay) , pto_count = tracing :: field :: debug (pto_count) , congestion_window = tracing :: field :: debug (congestion_window) , bytes_in_flight = tracing :: field :: debug (bytes_in_flight) , congestion_limited = tracing :: field :: debug (congestion_limited) });
         }
         #[inline]
         fn on_congestion(
@@ -1755,7 +1944,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::Congestion { path, source } = event;
-            tracing :: event ! (target : "congestion" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , source = tracing :: field :: debug (source));
+            tracing :: event ! (target : "congestion" , parent : id , tracing :: Level :: DEBUG , { path = tracing :: field :: debug (path) , source = tracing :: field :: debug (source) });
         }
         #[inline]
         #[allow(deprecated)]
@@ -1767,7 +1956,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::AckProcessed { action, path } = event;
-            tracing :: event ! (target : "ack_processed" , parent : id , tracing :: Level :: DEBUG , action = tracing :: field :: debug (action) , path = tracing :: field :: debug (path));
+            tracing :: event ! (target : "ack_processed" , parent : id , tracing :: Level :: DEBUG , { action = tracing :: field :: debug (action) , path = tracing :: field :: debug (path) });
         }
         #[inline]
         fn on_rx_ack_range_dropped(
@@ -1783,7 +1972,7 @@ pub mod tracing {
                 capacity,
                 stored_range,
             } = event;
-            tracing :: event ! (target : "rx_ack_range_dropped" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , packet_number_range = tracing :: field :: debug (packet_number_range) , capacity = tracing :: field :: debug (capacity) , stored_range = tracing :: field :: debug (stored_range));
+            tracing :: event ! (target : "rx_ack_range_dropped" , parent : id , tracing :: Level :: DEBUG , { path = tracing :: field :: debug (path) , packet_number_range = tracing :: field :: debug (packet_number_range) , capacity = tracing :: field :: debug (capacity) , stored_range = tracing :: field :: debug (stored_range) });
         }
         #[inline]
         fn on_ack_range_received(
@@ -1798,7 +1987,7 @@ pub mod tracing {
                 path,
                 ack_range,
             } = event;
-            tracing :: event ! (target : "ack_range_received" , parent : id , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header) , path = tracing :: field :: debug (path) , ack_range = tracing :: field :: debug (ack_range));
+            tracing :: event ! (target : "ack_range_received" , parent : id , tracing :: Level :: DEBUG , { packet_header = tracing :: field :: debug (packet_header) , path = tracing :: field :: debug (path) , ack_range = tracing :: field :: debug (ack_range) });
         }
         #[inline]
         fn on_ack_range_sent(
@@ -1813,7 +2002,7 @@ pub mod tracing {
                 path_id,
                 ack_range,
             } = event;
-            tracing :: event ! (target : "ack_range_sent" , parent : id , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header) , path_id = tracing :: field :: debug (path_id) , ack_range = tracing :: field :: debug (ack_range));
+            tracing :: event ! (target : "ack_range_sent" , parent : id , tracing :: Level :: DEBUG , { packet_header = tracing :: field :: debug (packet_header) , path_id = tracing :: field :: debug (path_id) , ack_range = tracing :: field :: debug (ack_range) });
         }
         #[inline]
         fn on_packet_dropped(
@@ -1824,7 +2013,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::PacketDropped { reason } = event;
-            tracing :: event ! (target : "packet_dropped" , parent : id , tracing :: Level :: DEBUG , reason = tracing :: field :: debug (reason));
+            tracing :: event ! (target : "packet_dropped" , parent : id , tracing :: Level :: DEBUG , { reason = tracing :: field :: debug (reason) });
         }
         #[inline]
         fn on_key_update(
@@ -1838,7 +2027,7 @@ pub mod tracing {
                 key_type,
                 cipher_suite,
             } = event;
-            tracing :: event ! (target : "key_update" , parent : id , tracing :: Level :: DEBUG , key_type = tracing :: field :: debug (key_type) , cipher_suite = tracing :: field :: debug (cipher_suite));
+            tracing :: event ! (target : "key_update" , parent : id , tracing :: Level :: DEBUG , { key_type = tracing :: field :: debug (key_type) , cipher_suite = tracing :: field :: debug (cipher_suite) });
         }
         #[inline]
         fn on_key_space_discarded(
@@ -1849,7 +2038,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::KeySpaceDiscarded { space } = event;
-            tracing :: event ! (target : "key_space_discarded" , parent : id , tracing :: Level :: DEBUG , space = tracing :: field :: debug (space));
+            tracing :: event ! (target : "key_space_discarded" , parent : id , tracing :: Level :: DEBUG , { space = tracing :: field :: debug (space) });
         }
         #[inline]
         fn on_connection_started(
@@ -1860,7 +2049,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::ConnectionStarted { path } = event;
-            tracing :: event ! (target : "connection_started" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path));
+            tracing :: event ! (target : "connection_started" , parent : id , tracing :: Level :: DEBUG , { path = tracing :: field :: debug (path) });
         }
         #[inline]
         fn on_connection_closed(
@@ -1871,7 +2060,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::ConnectionClosed { error } = event;
-            tracing :: event ! (target : "connection_closed" , parent : id , tracing :: Level :: DEBUG , error = tracing :: field :: debug (error));
+            tracing :: event ! (target : "connection_closed" , parent : id , tracing :: Level :: DEBUG , { error = tracing :: field :: debug (error) });
         }
         #[inline]
         fn on_duplicate_packet(
@@ -1886,7 +2075,7 @@ pub mod tracing {
                 path,
                 error,
             } = event;
-            tracing :: event ! (target : "duplicate_packet" , parent : id , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header) , path = tracing :: field :: debug (path) , error = tracing :: field :: debug (error));
+            tracing :: event ! (target : "duplicate_packet" , parent : id , tracing :: Level :: DEBUG , { packet_header = tracing :: field :: debug (packet_header) , path = tracing :: field :: debug (path) , error = tracing :: field :: debug (error) });
         }
         #[inline]
         fn on_transport_parameters_received(
@@ -1899,7 +2088,7 @@ pub mod tracing {
             let api::TransportParametersReceived {
                 transport_parameters,
             } = event;
-            tracing :: event ! (target : "transport_parameters_received" , parent : id , tracing :: Level :: DEBUG , transport_parameters = tracing :: field :: debug (transport_parameters));
+            tracing :: event ! (target : "transport_parameters_received" , parent : id , tracing :: Level :: DEBUG , { transport_parameters = tracing :: field :: debug (transport_parameters) });
         }
         #[inline]
         fn on_datagram_sent(
@@ -1910,7 +2099,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::DatagramSent { len, gso_offset } = event;
-            tracing :: event ! (target : "datagram_sent" , parent : id , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len) , gso_offset = tracing :: field :: debug (gso_offset));
+            tracing :: event ! (target : "datagram_sent" , parent : id , tracing :: Level :: DEBUG , { len = tracing :: field :: debug (len) , gso_offset = tracing :: field :: debug (gso_offset) });
         }
         #[inline]
         fn on_datagram_received(
@@ -1921,7 +2110,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::DatagramReceived { len } = event;
-            tracing :: event ! (target : "datagram_received" , parent : id , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len));
+            tracing :: event ! (target : "datagram_received" , parent : id , tracing :: Level :: DEBUG , { len = tracing :: field :: debug (len) });
         }
         #[inline]
         fn on_datagram_dropped(
@@ -1932,7 +2121,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::DatagramDropped { len, reason } = event;
-            tracing :: event ! (target : "datagram_dropped" , parent : id , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len) , reason = tracing :: field :: debug (reason));
+            tracing :: event ! (target : "datagram_dropped" , parent : id , tracing :: Level :: DEBUG , { len = tracing :: field :: debug (len) , reason = tracing :: field :: debug (reason) });
         }
         #[inline]
         fn on_connection_id_updated(
@@ -1948,7 +2137,7 @@ pub mod tracing {
                 previous,
                 current,
             } = event;
-            tracing :: event ! (target : "connection_id_updated" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , cid_consumer = tracing :: field :: debug (cid_consumer) , previous = tracing :: field :: debug (previous) , current = tracing :: field :: debug (current));
+            tracing :: event ! (target : "connection_id_updated" , parent : id , tracing :: Level :: DEBUG , { path_id = tracing :: field :: debug (path_id) , cid_consumer = tracing :: field :: debug (cid_consumer) , previous = tracing :: field :: debug (previous) , current = tracing :: field :: debug (current) });
         }
         #[inline]
         fn on_ecn_state_changed(
@@ -1959,7 +2148,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::EcnStateChanged { path, state } = event;
-            tracing :: event ! (target : "ecn_state_changed" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , state = tracing :: field :: debug (state));
+            tracing :: event ! (target : "ecn_state_changed" , parent : id , tracing :: Level :: DEBUG , { path = tracing :: field :: debug (path) , state = tracing :: field :: debug (state) });
         }
         #[inline]
         fn on_connection_migration_denied(
@@ -1970,7 +2159,106 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::ConnectionMigrationDenied { reason } = event;
-            tracing :: event ! (target : "connection_migration_denied" , parent : id , tracing :: Level :: DEBUG , reason = tracing :: field :: debug (reason));
+            tracing :: event ! (target : "connection_migration_denied" , parent : id , tracing :: Level :: DEBUG , { reason = tracing :: field :: debug (reason) });
+        }
+        #[inline]
+        fn on_preferred_address_migrated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::PreferredAddressMigrated,
+        ) {
+            let id = context.id();
+            let api::PreferredAddressMigrated { previous, active } = event;
+            tracing :: event ! (target : "preferred_address_migrated" , parent : id , tracing :: Level :: DEBUG , { previous = tracing :: field :: debug (previous) , active = tracing :: field :: debug (active) });
+        }
+        #[inline]
+        fn on_preferred_address_migration_failed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::PreferredAddressMigrationFailed,
+        ) {
+            let id = context.id();
+            let api::PreferredAddressMigrationFailed { path } = event;
+            tracing :: event ! (target : "preferred_address_migration_failed" , parent : id , tracing :: Level :: DEBUG , { path = tracing :: field :: debug (path) });
+        }
+        #[inline]
+        fn on_local_address_migrated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::LocalAddressMigrated,
+        ) {
+            let id = context.id();
+            let api::LocalAddressMigrated { previous, active } = event;
+            tracing :: event ! (target : "local_address_migrated" , parent : id , tracing :: Level :: DEBUG , { previous = tracing :: field :: debug (previous) , active = tracing :: field :: debug (active) });
+        }
+        #[inline]
+        fn on_local_address_migration_failed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::LocalAddressMigrationFailed,
+        ) {
+            let id = context.id();
+            let api::LocalAddressMigrationFailed { path } = event;
+            tracing :: event ! (target : "local_address_migration_failed" , parent : id , tracing :: Level :: DEBUG , { path = tracing :: field :: debug (path) });
+        }
+        #[inline]
+        fn on_early_data_accepted(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::EarlyDataAccepted,
+        ) {
+            let id = context.id();
+            let api::EarlyDataAccepted {} = event;
+            tracing :: event ! (target : "early_data_accepted" , parent : id , tracing :: Level :: DEBUG , { });
+        }
+        #[inline]
+        fn on_early_data_rejected(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::EarlyDataRejected,
+        ) {
+            let id = context.id();
+            let api::EarlyDataRejected { reason } = event;
+            tracing :: event ! (target : "early_data_rejected" , parent : id , tracing :: Level :: DEBUG , { reason = tracing :: field :: debug (reason) });
+        }
+        #[inline]
+        fn on_session_resumption(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::SessionResumption,
+        ) {
+            let id = context.id();
+            let api::SessionResumption { resumed } = event;
+            tracing :: event ! (target : "session_resumption" , parent : id , tracing :: Level :: DEBUG , { resumed = tracing :: field :: debug (resumed) });
+        }
+        #[inline]
+        fn on_version_upgrade_skipped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::VersionUpgradeSkipped,
+        ) {
+            let id = context.id();
+            let api::VersionUpgradeSkipped { version } = event;
+            tracing :: event ! (target : "version_upgrade_skipped" , parent : id , tracing :: Level :: DEBUG , { version = tracing :: field :: debug (version) });
+        }
+        #[inline]
+        fn on_server_certificate_selected(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::ServerCertificateSelected,
+        ) {
+            let id = context.id();
+            let api::ServerCertificateSelected { server_name } = event;
+            tracing :: event ! (target : "server_certificate_selected" , parent : id , tracing :: Level :: DEBUG , { server_name = tracing :: field :: debug (server_name) });
         }
         #[inline]
         fn on_handshake_status_updated(
@@ -1981,7 +2269,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::HandshakeStatusUpdated { status } = event;
-            tracing :: event ! (target : "handshake_status_updated" , parent : id , tracing :: Level :: DEBUG , status = tracing :: field :: debug (status));
+            tracing :: event ! (target : "handshake_status_updated" , parent : id , tracing :: Level :: DEBUG , { status = tracing :: field :: debug (status) });
         }
         #[inline]
         fn on_path_challenge_updated(
@@ -1996,7 +2284,7 @@ pub mod tracing {
                 path,
                 challenge_data,
             } = event;
-            tracing :: event ! (target : "path_challenge_updated" , parent : id , tracing :: Level :: DEBUG , path_challenge_status = tracing :: field :: debug (path_challenge_status) , path = tracing :: field :: debug (path) , challenge_data = tracing :: field :: debug (challenge_data));
+            tracing :: event ! (target : "path_challenge_updated" , parent : id , tracing :: Level :: DEBUG , { path_challenge_status = tracing :: field :: debug (path_challenge_status) , path = tracing :: field :: debug (path) , challenge_data = tracing :: field :: debug (challenge_data) });
         }
         #[inline]
         fn on_tls_client_hello(
@@ -2007,7 +2295,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::TlsClientHello { payload } = event;
-            tracing :: event ! (target : "tls_client_hello" , parent : id , tracing :: Level :: DEBUG , payload = tracing :: field :: debug (payload));
+            tracing :: event ! (target : "tls_client_hello" , parent : id , tracing :: Level :: DEBUG , { payload = tracing :: field :: debug (payload) });
         }
         #[inline]
         fn on_tls_server_hello(
@@ -2018,7 +2306,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::TlsServerHello { payload } = event;
-            tracing :: event ! (target : "tls_server_hello" , parent : id , tracing :: Level :: DEBUG , payload = tracing :: field :: debug (payload));
+            tracing :: event ! (target : "tls_server_hello" , parent : id , tracing :: Level :: DEBUG , { payload = tracing :: field :: debug (payload) });
         }
         #[inline]
         fn on_rx_stream_progress(
@@ -2029,7 +2317,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::RxStreamProgress { bytes } = event;
-            tracing :: event ! (target : "rx_stream_progress" , parent : id , tracing :: Level :: DEBUG , bytes = tracing :: field :: debug (bytes));
+            tracing :: event ! (target : "rx_stream_progress" , parent : id , tracing :: Level :: DEBUG , { bytes = tracing :: field :: debug (bytes) });
         }
         #[inline]
         fn on_tx_stream_progress(
@@ -2040,7 +2328,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::TxStreamProgress { bytes } = event;
-            tracing :: event ! (target : "tx_stream_progress" , parent : id , tracing :: Level :: DEBUG , bytes = tracing :: field :: debug (bytes));
+            tracing :: event ! (target : "tx_stream_progress" , parent : id , tracing :: Level :: DEBUG , { bytes = tracing :: field :: debug (bytes) });
         }
         #[inline]
         fn on_keep_alive_timer_expired(
@@ -2051,7 +2339,7 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::KeepAliveTimerExpired { timeout } = event;
-            tracing :: event ! (target : "keep_alive_timer_expired" , parent : id , tracing :: Level :: DEBUG , timeout = tracing :: field :: debug (timeout));
+            tracing :: event ! (target : "keep_alive_timer_expired" , parent : id , tracing :: Level :: DEBUG , { timeout = tracing :: field :: debug (timeout) });
         }
         #[inline]
         fn on_mtu_updated(
@@ -2066,7 +2354,7 @@ pub mod tracing {
                 mtu,
                 cause,
             } = event;
-            tracing :: event ! (target : "mtu_updated" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , mtu = tracing :: field :: debug (mtu) , cause = tracing :: field :: debug (cause));
+            tracing :: event ! (target : "mtu_updated" , parent : id , tracing :: Level :: DEBUG , { path_id = tracing :: field :: debug (path_id) , mtu = tracing :: field :: debug (mtu) , cause = tracing :: field :: debug (cause) });
         }
         #[inline]
         fn on_slow_start_exited(
@@ -2081,7 +2369,7 @@ pub mod tracing {
                 cause,
                 congestion_window,
             } = event;
-            tracing :: event ! (target : "slow_start_exited" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , cause = tracing :: field :: debug (cause) , congestion_window = tracing :: field :: debug (congestion_window));
+            tracing :: event ! (target : "slow_start_exited" , parent : id , tracing :: Level :: DEBUG , { path_id = tracing :: field :: debug (path_id) , cause = tracing :: field :: debug (cause) , congestion_window = tracing :: field :: debug (congestion_window) });
         }
         #[inline]
         fn on_delivery_rate_sampled(
@@ -2095,7 +2383,7 @@ pub mod tracing {
                 path_id,
                 rate_sample,
             } = event;
-            tracing :: event ! (target : "delivery_rate_sampled" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , rate_sample = tracing :: field :: debug (rate_sample));
+            tracing :: event ! (target : "delivery_rate_sampled" , parent : id , tracing :: Level :: DEBUG , { path_id = tracing :: field :: debug (path_id) , rate_sample = tracing :: field :: debug (rate_sample) });
         }
         #[inline]
         fn on_pacing_rate_updated(
@@ -2111,7 +2399,7 @@ pub mod tracing {
                 burst_size,
                 pacing_gain,
             } = event;
-            tracing :: event ! (target : "pacing_rate_updated" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , bytes_per_second = tracing :: field :: debug (bytes_per_second) , burst_size = tracing :: field :: debug (burst_size) , pacing_gain = tracing :: field :: debug (pacing_gain));
+            tracing :: event ! (target : "pacing_rate_updated" , parent : id , tracing :: Level :: DEBUG , { path_id = tracing :: field :: debug (path_id) , bytes_per_second = tracing :: field :: debug (bytes_per_second) , burst_size = tracing :: field :: debug (burst_size) , pacing_gain = tracing :: field :: debug (pacing_gain) });
         }
         #[inline]
         fn on_bbr_state_changed(
@@ -2122,7 +2410,18 @@ pub mod tracing {
         ) {
             let id = context.id();
             let api::BbrStateChanged { path_id, state } = event;
-            tracing :: event ! (target : "bbr_state_changed" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , state = tracing :: field :: debug (state));
+            tracing :: event ! (target : "bbr_state_changed" , parent : id , tracing :: Level :: DEBUG , { path_id = tracing :: field :: debug (path_id) , state = tracing :: field :: debug (state) });
+        }
+        #[inline]
+        fn on_receive_window_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::ReceiveWindowUpdated,
+        ) {
+            let id = context.id();
+            let api::ReceiveWindowUpdated { stream_id, window } = event;
+            tracing :: event ! (target : "receive_window_updated" , parent : id , tracing :: Level :: DEBUG , { stream_id = tracing :: field :: debug (stream_id) , window = tracing :: field :: debug (window) });
         }
         #[inline]
         fn on_version_information(
@@ -2139,7 +2438,7 @@ pub mod tracing {
                 client_versions,
                 chosen_version,
             } = event;
-            tracing :: event ! (target : "version_information" , parent : parent , tracing :: Level :: DEBUG , server_versions = tracing :: field :: debug (server_versions) , client_versions = tracing :: field :: debug (client_versions) , chosen_version = tracing :: field :: debug (chosen_version));
+            tracing :: event ! (target : "version_information" , parent : parent , tracing :: Level :: DEBUG , { server_versions = tracing :: field :: debug (server_versions) , client_versions = tracing :: field :: debug (client_versions) , chosen_version = tracing :: field :: debug (chosen_version) });
         }
         #[inline]
         fn on_endpoint_packet_sent(
@@ -2152,7 +2451,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::EndpointPacketSent { packet_header } = event;
-            tracing :: event ! (target : "endpoint_packet_sent" , parent : parent , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header));
+            tracing :: event ! (target : "endpoint_packet_sent" , parent : parent , tracing :: Level :: DEBUG , { packet_header = tracing :: field :: debug (packet_header) });
         }
         #[inline]
         fn on_endpoint_packet_received(
@@ -2165,7 +2464,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::EndpointPacketReceived { packet_header } = event;
-            tracing :: event ! (target : "endpoint_packet_received" , parent : parent , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header));
+            tracing :: event ! (target : "endpoint_packet_received" , parent : parent , tracing :: Level :: DEBUG , { packet_header = tracing :: field :: debug (packet_header) });
         }
         #[inline]
         fn on_endpoint_datagram_sent(
@@ -2178,7 +2477,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::EndpointDatagramSent { len, gso_offset } = event;
-            tracing :: event ! (target : "endpoint_datagram_sent" , parent : parent , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len) , gso_offset = tracing :: field :: debug (gso_offset));
+            tracing :: event ! (target : "endpoint_datagram_sent" , parent : parent , tracing :: Level :: DEBUG , { len = tracing :: field :: debug (len) , gso_offset = tracing :: field :: debug (gso_offset) });
         }
         #[inline]
         fn on_endpoint_datagram_received(
@@ -2191,7 +2490,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::EndpointDatagramReceived { len } = event;
-            tracing :: event ! (target : "endpoint_datagram_received" , parent : parent , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len));
+            tracing :: event ! (target : "endpoint_datagram_received" , parent : parent , tracing :: Level :: DEBUG , { len = tracing :: field :: debug (len) });
         }
         #[inline]
         fn on_endpoint_datagram_dropped(
@@ -2204,7 +2503,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::EndpointDatagramDropped { len, reason } = event;
-            tracing :: event ! (target : "endpoint_datagram_dropped" , parent : parent , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len) , reason = tracing :: field :: debug (reason));
+            tracing :: event ! (target : "endpoint_datagram_dropped" , parent : parent , tracing :: Level :: DEBUG , { len = tracing :: field :: debug (len) , reason = tracing :: field :: debug (reason) });
         }
         #[inline]
         fn on_endpoint_connection_attempt_failed(
@@ -2217,7 +2516,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::EndpointConnectionAttemptFailed { error } = event;
-            tracing :: event ! (target : "endpoint_connection_attempt_failed" , parent : parent , tracing :: Level :: DEBUG , error = tracing :: field :: debug (error));
+            tracing :: event ! (target : "endpoint_connection_attempt_failed" , parent : parent , tracing :: Level :: DEBUG , { error = tracing :: field :: debug (error) });
         }
         #[inline]
         fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
@@ -2226,7 +2525,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::PlatformTx { count } = event;
-            tracing :: event ! (target : "platform_tx" , parent : parent , tracing :: Level :: DEBUG , count = tracing :: field :: debug (count));
+            tracing :: event ! (target : "platform_tx" , parent : parent , tracing :: Level :: DEBUG , { count = tracing :: field :: debug (count) });
         }
         #[inline]
         fn on_platform_tx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTxError) {
@@ -2235,7 +2534,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::PlatformTxError { errno } = event;
-            tracing :: event ! (target : "platform_tx_error" , parent : parent , tracing :: Level :: DEBUG , errno = tracing :: field :: debug (errno));
+            tracing :: event ! (target : "platform_tx_error" , parent : parent , tracing :: Level :: DEBUG , { errno = tracing :: field :: debug (errno) });
         }
         #[inline]
         fn on_platform_rx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRx) {
@@ -2244,7 +2543,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::PlatformRx { count } = event;
-            tracing :: event ! (target : "platform_rx" , parent : parent , tracing :: Level :: DEBUG , count = tracing :: field :: debug (count));
+            tracing :: event ! (target : "platform_rx" , parent : parent , tracing :: Level :: DEBUG , { count = tracing :: field :: debug (count) });
         }
         #[inline]
         fn on_platform_rx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRxError) {
@@ -2253,7 +2552,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::PlatformRxError { errno } = event;
-            tracing :: event ! (target : "platform_rx_error" , parent : parent , tracing :: Level :: DEBUG , errno = tracing :: field :: debug (errno));
+            tracing :: event ! (target : "platform_rx_error" , parent : parent , tracing :: Level :: DEBUG , { errno = tracing :: field :: debug (errno) });
         }
         #[inline]
         fn on_platform_feature_configured(
@@ -2266,7 +2565,7 @@ pub mod tracing {
                 api::EndpointType::Server {} => self.server.id(),
             };
             let api::PlatformFeatureConfigured { configuration } = event;
-            tracing :: event ! (target : "platform_feature_configured" , parent : parent , tracing :: Level :: DEBUG , configuration = tracing :: field :: debug (configuration));
+            tracing :: event ! (target : "platform_feature_configured" , parent : parent , tracing :: Level :: DEBUG , { configuration = tracing :: field :: debug (configuration) });
         }
         #[inline]
         fn on_platform_event_loop_wakeup(
@@ -2284,7 +2583,7 @@ pub mod tracing {
                 tx_ready,
                 application_wakeup,
             } = event;
-            tracing :: event ! (target : "platform_event_loop_wakeup" , parent : parent , tracing :: Level :: DEBUG , timeout_expired = tracing :: field :: debug (timeout_expired) , rx_ready = tracing :: field :: debug (rx_ready) , tx_ready = tracing :: field :: debug (tx_ready) , application_wakeup = tracing :: field :: debug (application_wakeup));
+            tracing :: event ! (target : "platform_event_loop_wakeup" , parent : parent , tracing :: Level :: DEBUG , { timeout_expired = tracing :: field :: debug (timeout_expired) , rx_ready = tracing :: field :: debug (rx_ready) , tx_ready = tracing :: field :: debug (tx_ready) , application_wakeup = tracing :: field :: debug (application_wakeup) });
         }
         #[inline]
         fn on_platform_event_loop_sleep(
@@ -2300,7 +2599,7 @@ pub mod tracing {
                 timeout,
                 processing_duration,
             } = event;
-            tracing :: event ! (target : "platform_event_loop_sleep" , parent : parent , tracing :: Level :: DEBUG , timeout = tracing :: field :: debug (timeout) , processing_duration = tracing :: field :: debug (processing_duration));
+            tracing :: event ! (target : "platform_event_loop_sleep" , parent : parent , tracing :: Level :: DEBUG , { timeout = tracing :: field :: debug (timeout) , processing_duration = tracing :: field :: debug (processing_duration) });
         }
     }
 }
@@ -2669,6 +2968,23 @@ pub mod builder {
         Datagram {
             len: u16,
         },
+        AckFrequency {
+            sequence_number: u64,
+            ack_eliciting_threshold: u64,
+            request_max_ack_delay: u64,
+            reordering_threshold: u64,
+        },
+        ImmediateAck,
+        AckMp {
+            path_identifier: u64,
+            ecn_counts: Option<EcnCounts>,
+            largest_acknowledged: u64,
+            ack_range_count: u64,
+        },
+        PathAbandon {
+            path_identifier: u64,
+            error_code: u64,
+        },
     }
     impl IntoEvent<api::Frame> for Frame {
         #[inline]
@@ -2757,6 +3073,36 @@ pub mod builder {
                 Self::Datagram { len } => Datagram {
                     len: len.into_event(),
                 },
+                Self::AckFrequency {
+                    sequence_number,
+                    ack_eliciting_threshold,
+                    request_max_ack_delay,
+                    reordering_threshold,
+                } => AckFrequency {
+                    sequence_number: sequence_number.into_event(),
+                    ack_eliciting_threshold: ack_eliciting_threshold.into_event(),
+                    request_max_ack_delay: request_max_ack_delay.into_event(),
+                    reordering_threshold: reordering_threshold.into_event(),
+                },
+                Self::ImmediateAck => ImmediateAck {},
+                Self::AckMp {
+                    path_identifier,
+                    ecn_counts,
+                    largest_acknowledged,
+                    ack_range_count,
+                } => AckMp {
+                    path_identifier: path_identifier.into_event(),
+                    ecn_counts: ecn_counts.into_event(),
+                    largest_acknowledged: largest_acknowledged.into_event(),
+                    ack_range_count: ack_range_count.into_event(),
+                },
+                Self::PathAbandon {
+                    path_identifier,
+                    error_code,
+                } => PathAbandon {
+                    path_identifier: path_identifier.into_event(),
+                    error_code: error_code.into_event(),
+                },
             }
         }
     }
@@ -2904,6 +3250,8 @@ pub mod builder {
         PathLimitExceeded,
         #[doc = " The peer initiated a connection migration without supplying enough connection IDs to use."]
         InsufficientConnectionIds,
+        #[doc = " The peer cleared the QUIC bit before the `grease_quic_bit` transport parameter was negotiated."]
+        QuicBitCleared,
     }
     impl IntoEvent<api::DatagramDropReason> for DatagramDropReason {
         #[inline]
@@ -2922,6 +3270,7 @@ pub mod builder {
                 Self::RejectedConnectionMigration => RejectedConnectionMigration {},
                 Self::PathLimitExceeded => PathLimitExceeded {},
                 Self::InsufficientConnectionIds => InsufficientConnectionIds {},
+                Self::QuicBitCleared => QuicBitCleared {},
             }
         }
     }
@@ -2976,11 +3325,16 @@ pub mod builder {
         DecodingFailed { path: Path<'a> },
         #[doc = " The client received a non-empty retry token."]
         NonEmptyRetryToken { path: Path<'a> },
+        #[doc = " Early data was rejected for the connection and the 0-RTT packet was discarded."]
+        EarlyDataRejected { path: Path<'a> },
         #[doc = " A Retry packet was discarded."]
         RetryDiscarded {
             reason: RetryDiscardReason<'a>,
             path: Path<'a>,
         },
+        #[doc = " The packet had the QUIC bit set to 0 but the connection didn't advertise"]
+        #[doc = " the `grease_quic_bit` transport parameter."]
+        QuicBitCleared { path: Path<'a> },
     }
     impl<'a> IntoEvent<api::PacketDropReason<'a>> for PacketDropReason<'a> {
         #[inline]
@@ -3018,10 +3372,16 @@ pub mod builder {
                 Self::NonEmptyRetryToken { path } => NonEmptyRetryToken {
                     path: path.into_event(),
                 },
+                Self::EarlyDataRejected { path } => EarlyDataRejected {
+                    path: path.into_event(),
+                },
                 Self::RetryDiscarded { reason, path } => RetryDiscarded {
                     reason: reason.into_event(),
                     path: path.into_event(),
                 },
+                Self::QuicBitCleared { path } => QuicBitCleared {
+                    path: path.into_event(),
+                },
             }
         }
     }
@@ -3108,6 +3468,29 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    pub enum EarlyDataRejectReason {
+        #[doc = " The early data controller rejected the early data"]
+        Rejected,
+        #[doc = " Early data is disabled on the endpoint"]
+        Disabled,
+        #[doc = " Too many connections have recently used early data"]
+        RateLimited,
+        #[doc = " The peer rejected the early data sent by the local endpoint"]
+        Peer,
+    }
+    impl IntoEvent<api::EarlyDataRejectReason> for EarlyDataRejectReason {
+        #[inline]
+        fn into_event(self) -> api::EarlyDataRejectReason {
+            use api::EarlyDataRejectReason::*;
+            match self {
+                Self::Rejected => Rejected {},
+                Self::Disabled => Disabled {},
+                Self::RateLimited => RateLimited {},
+                Self::Peer => Peer {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " The current state of the ECN controller for the path"]
     pub enum EcnState {
         #[doc = " ECN capability is being actively tested"]
@@ -3252,6 +3635,8 @@ pub mod builder {
         ProbeAcknowledged,
         #[doc = " A blackhole was detected"]
         Blackhole,
+        #[doc = " An ICMP Packet Too Big message reported a smaller MTU"]
+        PacketTooBig,
     }
     impl IntoEvent<api::MtuUpdatedCause> for MtuUpdatedCause {
         #[inline]
@@ -3261,6 +3646,7 @@ pub mod builder {
                 Self::NewPath => NewPath {},
                 Self::ProbeAcknowledged => ProbeAcknowledged {},
                 Self::Blackhole => Blackhole {},
+                Self::PacketTooBig => PacketTooBig {},
             }
         }
     }
@@ -3827,89 +4213,223 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
-    pub struct HandshakeStatusUpdated {
-        pub status: HandshakeStatus,
+    #[doc = " The client validated the path to the server's preferred address and migrated to it"]
+    pub struct PreferredAddressMigrated<'a> {
+        pub previous: Path<'a>,
+        pub active: Path<'a>,
     }
-    impl IntoEvent<api::HandshakeStatusUpdated> for HandshakeStatusUpdated {
+    impl<'a> IntoEvent<api::PreferredAddressMigrated<'a>> for PreferredAddressMigrated<'a> {
         #[inline]
-        fn into_event(self) -> api::HandshakeStatusUpdated {
-            let HandshakeStatusUpdated { status } = self;
-            api::HandshakeStatusUpdated {
-                status: status.into_event(),
+        fn into_event(self) -> api::PreferredAddressMigrated<'a> {
+            let PreferredAddressMigrated { previous, active } = self;
+            api::PreferredAddressMigrated {
+                previous: previous.into_event(),
+                active: active.into_event(),
             }
         }
     }
     #[derive(Clone, Debug)]
-    #[doc = " Path challenge updated"]
-    pub struct PathChallengeUpdated<'a> {
-        pub path_challenge_status: PathChallengeStatus,
+    #[doc = " The client was unable to validate the path to the server's preferred address"]
+    #[doc = ""]
+    #[doc = " The connection continues to use the original path."]
+    pub struct PreferredAddressMigrationFailed<'a> {
         pub path: Path<'a>,
-        pub challenge_data: &'a [u8],
     }
-    impl<'a> IntoEvent<api::PathChallengeUpdated<'a>> for PathChallengeUpdated<'a> {
+    impl<'a> IntoEvent<api::PreferredAddressMigrationFailed<'a>>
+        for PreferredAddressMigrationFailed<'a>
+    {
         #[inline]
-        fn into_event(self) -> api::PathChallengeUpdated<'a> {
-            let PathChallengeUpdated {
-                path_challenge_status,
-                path,
-                challenge_data,
-            } = self;
-            api::PathChallengeUpdated {
-                path_challenge_status: path_challenge_status.into_event(),
+        fn into_event(self) -> api::PreferredAddressMigrationFailed<'a> {
+            let PreferredAddressMigrationFailed { path } = self;
+            api::PreferredAddressMigrationFailed {
                 path: path.into_event(),
-                challenge_data: challenge_data.into_event(),
             }
         }
     }
     #[derive(Clone, Debug)]
-    pub struct TlsClientHello<'a> {
-        pub payload: &'a [&'a [u8]],
+    #[doc = " The application migrated the connection to a new local address and the path was validated"]
+    pub struct LocalAddressMigrated<'a> {
+        pub previous: Path<'a>,
+        pub active: Path<'a>,
     }
-    impl<'a> IntoEvent<api::TlsClientHello<'a>> for TlsClientHello<'a> {
+    impl<'a> IntoEvent<api::LocalAddressMigrated<'a>> for LocalAddressMigrated<'a> {
         #[inline]
-        fn into_event(self) -> api::TlsClientHello<'a> {
-            let TlsClientHello { payload } = self;
-            api::TlsClientHello {
-                payload: payload.into_event(),
+        fn into_event(self) -> api::LocalAddressMigrated<'a> {
+            let LocalAddressMigrated { previous, active } = self;
+            api::LocalAddressMigrated {
+                previous: previous.into_event(),
+                active: active.into_event(),
             }
         }
     }
     #[derive(Clone, Debug)]
-    pub struct TlsServerHello<'a> {
-        pub payload: &'a [&'a [u8]],
+    #[doc = " The path from the new local address requested by the application failed validation"]
+    #[doc = ""]
+    #[doc = " The connection continues to use the original path."]
+    pub struct LocalAddressMigrationFailed<'a> {
+        pub path: Path<'a>,
     }
-    impl<'a> IntoEvent<api::TlsServerHello<'a>> for TlsServerHello<'a> {
+    impl<'a> IntoEvent<api::LocalAddressMigrationFailed<'a>> for LocalAddressMigrationFailed<'a> {
         #[inline]
-        fn into_event(self) -> api::TlsServerHello<'a> {
-            let TlsServerHello { payload } = self;
-            api::TlsServerHello {
-                payload: payload.into_event(),
+        fn into_event(self) -> api::LocalAddressMigrationFailed<'a> {
+            let LocalAddressMigrationFailed { path } = self;
+            api::LocalAddressMigrationFailed {
+                path: path.into_event(),
             }
         }
     }
     #[derive(Clone, Debug)]
-    pub struct RxStreamProgress {
-        pub bytes: usize,
+    #[doc = " Early data (0-RTT) was accepted on the connection"]
+    pub struct EarlyDataAccepted {}
+    impl IntoEvent<api::EarlyDataAccepted> for EarlyDataAccepted {
+        #[inline]
+        fn into_event(self) -> api::EarlyDataAccepted {
+            let EarlyDataAccepted {} = self;
+            api::EarlyDataAccepted {}
+        }
     }
-    impl IntoEvent<api::RxStreamProgress> for RxStreamProgress {
+    #[derive(Clone, Debug)]
+    #[doc = " Early data (0-RTT) was rejected on the connection"]
+    pub struct EarlyDataRejected {
+        pub reason: EarlyDataRejectReason,
+    }
+    impl IntoEvent<api::EarlyDataRejected> for EarlyDataRejected {
         #[inline]
-        fn into_event(self) -> api::RxStreamProgress {
-            let RxStreamProgress { bytes } = self;
-            api::RxStreamProgress {
-                bytes: bytes.into_event(),
+        fn into_event(self) -> api::EarlyDataRejected {
+            let EarlyDataRejected { reason } = self;
+            api::EarlyDataRejected {
+                reason: reason.into_event(),
             }
         }
     }
     #[derive(Clone, Debug)]
-    pub struct TxStreamProgress {
-        pub bytes: usize,
+    #[doc = " The TLS handshake completed and reported if a previous session was resumed"]
+    pub struct SessionResumption {
+        pub resumed: bool,
     }
-    impl IntoEvent<api::TxStreamProgress> for TxStreamProgress {
+    impl IntoEvent<api::SessionResumption> for SessionResumption {
         #[inline]
-        fn into_event(self) -> api::TxStreamProgress {
-            let TxStreamProgress { bytes } = self;
-            api::TxStreamProgress {
-                bytes: bytes.into_event(),
+        fn into_event(self) -> api::SessionResumption {
+            let SessionResumption { resumed } = self;
+            api::SessionResumption {
+                resumed: resumed.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The server kept the client's original QUIC version since the client didn't report its versions"]
+    pub struct VersionUpgradeSkipped {
+        #[doc = " The version used by the connection"]
+        pub version: u32,
+    }
+    impl IntoEvent<api::VersionUpgradeSkipped> for VersionUpgradeSkipped {
+        #[inline]
+        fn into_event(self) -> api::VersionUpgradeSkipped {
+            let VersionUpgradeSkipped { version } = self;
+            api::VersionUpgradeSkipped {
+                version: version.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The server presented a certificate from its certificate store"]
+    pub struct ServerCertificateSelected<'a> {
+        #[doc = " The server name the certificate was added for, or `None` for the default certificate"]
+        pub server_name: Option<&'a str>,
+    }
+    impl<'a> IntoEvent<api::ServerCertificateSelected<'a>> for ServerCertificateSelected<'a> {
+        #[inline]
+        fn into_event(self) -> api::ServerCertificateSelected<'a> {
+            let ServerCertificateSelected { server_name } = self;
+            api::ServerCertificateSelected {
+                server_name: server_name.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub struct HandshakeStatusUpdated {
+        pub status: HandshakeStatus,
+    }
+    impl IntoEvent<api::HandshakeStatusUpdated> for HandshakeStatusUpdated {
+        #[inline]
+        fn into_event(self) -> api::HandshakeStatusUpdated {
+            let HandshakeStatusUpdated { status } = self;
+            api::HandshakeStatusUpdated {
+                status: status.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " Path challenge updated"]
+    pub struct PathChallengeUpdated<'a> {
+        pub path_challenge_status: PathChallengeStatus,
+        pub path: Path<'a>,
+        pub challenge_data: &'a [u8],
+    }
+    impl<'a> IntoEvent<api::PathChallengeUpdated<'a>> for PathChallengeUpdated<'a> {
+        #[inline]
+        fn into_event(self) -> api::PathChallengeUpdated<'a> {
+            let PathChallengeUpdated {
+                path_challenge_status,
+                path,
+                challenge_data,
+            } = self;
+            api::PathChallengeUpdated {
+                path_challenge_status: path_challenge_status.into_event(),
+                path: path.into_event(),
+                challenge_data: challenge_data.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub struct TlsClientHello<'a> {
+        pub payload: &'a [&'a [u8]],
+    }
+    impl<'a> IntoEvent<api::TlsClientHello<'a>> for TlsClientHello<'a> {
+        #[inline]
+        fn into_event(self) -> api::TlsClientHello<'a> {
+            let TlsClientHello { payload } = self;
+            api::TlsClientHello {
+                payload: payload.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub struct TlsServerHello<'a> {
+        pub payload: &'a [&'a [u8]],
+    }
+    impl<'a> IntoEvent<api::TlsServerHello<'a>> for TlsServerHello<'a> {
+        #[inline]
+        fn into_event(self) -> api::TlsServerHello<'a> {
+            let TlsServerHello { payload } = self;
+            api::TlsServerHello {
+                payload: payload.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub struct RxStreamProgress {
+        pub bytes: usize,
+    }
+    impl IntoEvent<api::RxStreamProgress> for RxStreamProgress {
+        #[inline]
+        fn into_event(self) -> api::RxStreamProgress {
+            let RxStreamProgress { bytes } = self;
+            api::RxStreamProgress {
+                bytes: bytes.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub struct TxStreamProgress {
+        pub bytes: usize,
+    }
+    impl IntoEvent<api::TxStreamProgress> for TxStreamProgress {
+        #[inline]
+        fn into_event(self) -> api::TxStreamProgress {
+            let TxStreamProgress { bytes } = self;
+            api::TxStreamProgress {
+                bytes: bytes.into_event(),
             }
         }
     }
@@ -4033,6 +4553,25 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " A receive window was grown because the application is consuming data faster than the"]
+    #[doc = " peer is able to send it"]
+    pub struct ReceiveWindowUpdated {
+        #[doc = " The stream of the window, or `None` for the connection window"]
+        pub stream_id: Option<u64>,
+        #[doc = " The new size of the window"]
+        pub window: u64,
+    }
+    impl IntoEvent<api::ReceiveWindowUpdated> for ReceiveWindowUpdated {
+        #[inline]
+        fn into_event(self) -> api::ReceiveWindowUpdated {
+            let ReceiveWindowUpdated { stream_id, window } = self;
+            api::ReceiveWindowUpdated {
+                stream_id: stream_id.into_event(),
+                window: window.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " QUIC version"]
     pub struct VersionInformation<'a> {
         pub server_versions: &'a [u32],
@@ -4816,6 +5355,114 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `PreferredAddressMigrated` event is triggered"]
+        #[inline]
+        fn on_preferred_address_migrated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PreferredAddressMigrated,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PreferredAddressMigrationFailed` event is triggered"]
+        #[inline]
+        fn on_preferred_address_migration_failed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PreferredAddressMigrationFailed,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `LocalAddressMigrated` event is triggered"]
+        #[inline]
+        fn on_local_address_migrated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &LocalAddressMigrated,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `LocalAddressMigrationFailed` event is triggered"]
+        #[inline]
+        fn on_local_address_migration_failed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &LocalAddressMigrationFailed,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `EarlyDataAccepted` event is triggered"]
+        #[inline]
+        fn on_early_data_accepted(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &EarlyDataAccepted,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `EarlyDataRejected` event is triggered"]
+        #[inline]
+        fn on_early_data_rejected(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &EarlyDataRejected,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `SessionResumption` event is triggered"]
+        #[inline]
+        fn on_session_resumption(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &SessionResumption,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `VersionUpgradeSkipped` event is triggered"]
+        #[inline]
+        fn on_version_upgrade_skipped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &VersionUpgradeSkipped,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ServerCertificateSelected` event is triggered"]
+        #[inline]
+        fn on_server_certificate_selected(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ServerCertificateSelected,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `HandshakeStatusUpdated` event is triggered"]
         #[inline]
         fn on_handshake_status_updated(
@@ -4960,6 +5607,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `ReceiveWindowUpdated` event is triggered"]
+        #[inline]
+        fn on_receive_window_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ReceiveWindowUpdated,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `VersionInformation` event is triggered"]
         #[inline]
         fn on_version_information(&mut self, meta: &EndpointMeta, event: &VersionInformation) {
@@ -5452,6 +6111,96 @@ mod traits {
             (self.1).on_connection_migration_denied(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_preferred_address_migrated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PreferredAddressMigrated,
+        ) {
+            (self.0).on_preferred_address_migrated(&mut context.0, meta, event);
+            (self.1).on_preferred_address_migrated(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_preferred_address_migration_failed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PreferredAddressMigrationFailed,
+        ) {
+            (self.0).on_preferred_address_migration_failed(&mut context.0, meta, event);
+            (self.1).on_preferred_address_migration_failed(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_local_address_migrated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &LocalAddressMigrated,
+        ) {
+            (self.0).on_local_address_migrated(&mut context.0, meta, event);
+            (self.1).on_local_address_migrated(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_local_address_migration_failed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &LocalAddressMigrationFailed,
+        ) {
+            (self.0).on_local_address_migration_failed(&mut context.0, meta, event);
+            (self.1).on_local_address_migration_failed(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_early_data_accepted(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &EarlyDataAccepted,
+        ) {
+            (self.0).on_early_data_accepted(&mut context.0, meta, event);
+            (self.1).on_early_data_accepted(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_early_data_rejected(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &EarlyDataRejected,
+        ) {
+            (self.0).on_early_data_rejected(&mut context.0, meta, event);
+            (self.1).on_early_data_rejected(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_session_resumption(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &SessionResumption,
+        ) {
+            (self.0).on_session_resumption(&mut context.0, meta, event);
+            (self.1).on_session_resumption(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_version_upgrade_skipped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &VersionUpgradeSkipped,
+        ) {
+            (self.0).on_version_upgrade_skipped(&mut context.0, meta, event);
+            (self.1).on_version_upgrade_skipped(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_server_certificate_selected(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ServerCertificateSelected,
+        ) {
+            (self.0).on_server_certificate_selected(&mut context.0, meta, event);
+            (self.1).on_server_certificate_selected(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_handshake_status_updated(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -5572,6 +6321,16 @@ mod traits {
             (self.1).on_bbr_state_changed(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_receive_window_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ReceiveWindowUpdated,
+        ) {
+            (self.0).on_receive_window_updated(&mut context.0, meta, event);
+            (self.1).on_receive_window_updated(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_version_information(&mut self, meta: &EndpointMeta, event: &VersionInformation) {
             (self.0).on_version_information(meta, event);
             (self.1).on_version_information(meta, event);
@@ -5928,6 +6687,30 @@ mod traits {
         fn on_ecn_state_changed(&mut self, event: builder::EcnStateChanged);
         #[doc = "Publishes a `ConnectionMigrationDenied` event to the publisher's subscriber"]
         fn on_connection_migration_denied(&mut self, event: builder::ConnectionMigrationDenied);
+        #[doc = "Publishes a `PreferredAddressMigrated` event to the publisher's subscriber"]
+        fn on_preferred_address_migrated(&mut self, event: builder::PreferredAddressMigrated);
+        #[doc = "Publishes a `PreferredAddressMigrationFailed` event to the publisher's subscriber"]
+        fn on_preferred_address_migration_failed(
+            &mut self,
+            event: builder::PreferredAddressMigrationFailed,
+        );
+        #[doc = "Publishes a `LocalAddressMigrated` event to the publisher's subscriber"]
+        fn on_local_address_migrated(&mut self, event: builder::LocalAddressMigrated);
+        #[doc = "Publishes a `LocalAddressMigrationFailed` event to the publisher's subscriber"]
+        fn on_local_address_migration_failed(
+            &mut self,
+            event: builder::LocalAddressMigrationFailed,
+        );
+        #[doc = "Publishes a `EarlyDataAccepted` event to the publisher's subscriber"]
+        fn on_early_data_accepted(&mut self, event: builder::EarlyDataAccepted);
+        #[doc = "Publishes a `EarlyDataRejected` event to the publisher's subscriber"]
+        fn on_early_data_rejected(&mut self, event: builder::EarlyDataRejected);
+        #[doc = "Publishes a `SessionResumption` event to the publisher's subscriber"]
+        fn on_session_resumption(&mut self, event: builder::SessionResumption);
+        #[doc = "Publishes a `VersionUpgradeSkipped` event to the publisher's subscriber"]
+        fn on_version_upgrade_skipped(&mut self, event: builder::VersionUpgradeSkipped);
+        #[doc = "Publishes a `ServerCertificateSelected` event to the publisher's subscriber"]
+        fn on_server_certificate_selected(&mut self, event: builder::ServerCertificateSelected);
         #[doc = "Publishes a `HandshakeStatusUpdated` event to the publisher's subscriber"]
         fn on_handshake_status_updated(&mut self, event: builder::HandshakeStatusUpdated);
         #[doc = "Publishes a `PathChallengeUpdated` event to the publisher's subscriber"]
@@ -5952,6 +6735,8 @@ mod traits {
         fn on_pacing_rate_updated(&mut self, event: builder::PacingRateUpdated);
         #[doc = "Publishes a `BbrStateChanged` event to the publisher's subscriber"]
         fn on_bbr_state_changed(&mut self, event: builder::BbrStateChanged);
+        #[doc = "Publishes a `ReceiveWindowUpdated` event to the publisher's subscriber"]
+        fn on_receive_window_updated(&mut self, event: builder::ReceiveWindowUpdated);
         #[doc = r" Returns the QUIC version negotiated for the current connection, if any"]
         fn quic_version(&self) -> u32;
         #[doc = r" Returns the [`Subject`] for the current publisher"]
@@ -6248,6 +7033,93 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_preferred_address_migrated(&mut self, event: builder::PreferredAddressMigrated) {
+            let event = event.into_event();
+            self.subscriber
+                .on_preferred_address_migrated(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_preferred_address_migration_failed(
+            &mut self,
+            event: builder::PreferredAddressMigrationFailed,
+        ) {
+            let event = event.into_event();
+            self.subscriber
+                .on_preferred_address_migration_failed(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_local_address_migrated(&mut self, event: builder::LocalAddressMigrated) {
+            let event = event.into_event();
+            self.subscriber
+                .on_local_address_migrated(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_local_address_migration_failed(
+            &mut self,
+            event: builder::LocalAddressMigrationFailed,
+        ) {
+            let event = event.into_event();
+            self.subscriber
+                .on_local_address_migration_failed(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_early_data_accepted(&mut self, event: builder::EarlyDataAccepted) {
+            let event = event.into_event();
+            self.subscriber
+                .on_early_data_accepted(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_early_data_rejected(&mut self, event: builder::EarlyDataRejected) {
+            let event = event.into_event();
+            self.subscriber
+                .on_early_data_rejected(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_session_resumption(&mut self, event: builder::SessionResumption) {
+            let event = event.into_event();
+            self.subscriber
+                .on_session_resumption(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_version_upgrade_skipped(&mut self, event: builder::VersionUpgradeSkipped) {
+            let event = event.into_event();
+            self.subscriber
+                .on_version_upgrade_skipped(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_server_certificate_selected(&mut self, event: builder::ServerCertificateSelected) {
+            let event = event.into_event();
+            self.subscriber
+                .on_server_certificate_selected(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_handshake_status_updated(&mut self, event: builder::HandshakeStatusUpdated) {
             let event = event.into_event();
             self.subscriber
@@ -6356,6 +7228,15 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_receive_window_updated(&mut self, event: builder::ReceiveWindowUpdated) {
+            let event = event.into_event();
+            self.subscriber
+                .on_receive_window_updated(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn quic_version(&self) -> u32 {
             self.quic_version
         }
@@ -6400,6 +7281,15 @@ pub mod testing {
         pub connection_id_updated: u32,
         pub ecn_state_changed: u32,
         pub connection_migration_denied: u32,
+        pub preferred_address_migrated: u32,
+        pub preferred_address_migration_failed: u32,
+        pub local_address_migrated: u32,
+        pub local_address_migration_failed: u32,
+        pub early_data_accepted: u32,
+        pub early_data_rejected: u32,
+        pub session_resumption: u32,
+        pub version_upgrade_skipped: u32,
+        pub server_certificate_selected: u32,
         pub handshake_status_updated: u32,
         pub path_challenge_updated: u32,
         pub tls_client_hello: u32,
@@ -6412,6 +7302,7 @@ pub mod testing {
         pub delivery_rate_sampled: u32,
         pub pacing_rate_updated: u32,
         pub bbr_state_changed: u32,
+        pub receive_window_updated: u32,
         pub version_information: u32,
         pub endpoint_packet_sent: u32,
         pub endpoint_packet_received: u32,
@@ -6478,6 +7369,15 @@ pub mod testing {
                 connection_id_updated: 0,
                 ecn_state_changed: 0,
                 connection_migration_denied: 0,
+                preferred_address_migrated: 0,
+                preferred_address_migration_failed: 0,
+                local_address_migrated: 0,
+                local_address_migration_failed: 0,
+                early_data_accepted: 0,
+                early_data_rejected: 0,
+                session_resumption: 0,
+                version_upgrade_skipped: 0,
+                server_certificate_selected: 0,
                 handshake_status_updated: 0,
                 path_challenge_updated: 0,
                 tls_client_hello: 0,
@@ -6490,6 +7390,7 @@ pub mod testing {
                 delivery_rate_sampled: 0,
                 pacing_rate_updated: 0,
                 bbr_state_changed: 0,
+                receive_window_updated: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
                 endpoint_packet_received: 0,
@@ -6824,6 +7725,105 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_preferred_address_migrated(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PreferredAddressMigrated,
+        ) {
+            self.preferred_address_migrated += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_preferred_address_migration_failed(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PreferredAddressMigrationFailed,
+        ) {
+            self.preferred_address_migration_failed += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_local_address_migrated(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::LocalAddressMigrated,
+        ) {
+            self.local_address_migrated += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_local_address_migration_failed(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::LocalAddressMigrationFailed,
+        ) {
+            self.local_address_migration_failed += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_early_data_accepted(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::EarlyDataAccepted,
+        ) {
+            self.early_data_accepted += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_early_data_rejected(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::EarlyDataRejected,
+        ) {
+            self.early_data_rejected += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_session_resumption(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::SessionResumption,
+        ) {
+            self.session_resumption += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_version_upgrade_skipped(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::VersionUpgradeSkipped,
+        ) {
+            self.version_upgrade_skipped += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_server_certificate_selected(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ServerCertificateSelected,
+        ) {
+            self.server_certificate_selected += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_handshake_status_updated(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -6956,6 +7956,17 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_receive_window_updated(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ReceiveWindowUpdated,
+        ) {
+            self.receive_window_updated += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_version_information(
             &mut self,
             meta: &api::EndpointMeta,
@@ -7085,6 +8096,15 @@ pub mod testing {
         pub connection_id_updated: u32,
         pub ecn_state_changed: u32,
         pub connection_migration_denied: u32,
+        pub preferred_address_migrated: u32,
+        pub preferred_address_migration_failed: u32,
+        pub local_address_migrated: u32,
+        pub local_address_migration_failed: u32,
+        pub early_data_accepted: u32,
+        pub early_data_rejected: u32,
+        pub session_resumption: u32,
+        pub version_upgrade_skipped: u32,
+        pub server_certificate_selected: u32,
         pub handshake_status_updated: u32,
         pub path_challenge_updated: u32,
         pub tls_client_hello: u32,
@@ -7097,6 +8117,7 @@ pub mod testing {
         pub delivery_rate_sampled: u32,
         pub pacing_rate_updated: u32,
         pub bbr_state_changed: u32,
+        pub receive_window_updated: u32,
         pub version_information: u32,
         pub endpoint_packet_sent: u32,
         pub endpoint_packet_received: u32,
@@ -7153,6 +8174,15 @@ pub mod testing {
                 connection_id_updated: 0,
                 ecn_state_changed: 0,
                 connection_migration_denied: 0,
+                preferred_address_migrated: 0,
+                preferred_address_migration_failed: 0,
+                local_address_migrated: 0,
+                local_address_migration_failed: 0,
+                early_data_accepted: 0,
+                early_data_rejected: 0,
+                session_resumption: 0,
+                version_upgrade_skipped: 0,
+                server_certificate_selected: 0,
                 handshake_status_updated: 0,
                 path_challenge_updated: 0,
                 tls_client_hello: 0,
@@ -7165,6 +8195,7 @@ pub mod testing {
                 delivery_rate_sampled: 0,
                 pacing_rate_updated: 0,
                 bbr_state_changed: 0,
+                receive_window_updated: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
                 endpoint_packet_received: 0,
@@ -7464,6 +8495,75 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_preferred_address_migrated(&mut self, event: builder::PreferredAddressMigrated) {
+            self.preferred_address_migrated += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_preferred_address_migration_failed(
+            &mut self,
+            event: builder::PreferredAddressMigrationFailed,
+        ) {
+            self.preferred_address_migration_failed += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_local_address_migrated(&mut self, event: builder::LocalAddressMigrated) {
+            self.local_address_migrated += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_local_address_migration_failed(
+            &mut self,
+            event: builder::LocalAddressMigrationFailed,
+        ) {
+            self.local_address_migration_failed += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_early_data_accepted(&mut self, event: builder::EarlyDataAccepted) {
+            self.early_data_accepted += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_early_data_rejected(&mut self, event: builder::EarlyDataRejected) {
+            self.early_data_rejected += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_session_resumption(&mut self, event: builder::SessionResumption) {
+            self.session_resumption += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_version_upgrade_skipped(&mut self, event: builder::VersionUpgradeSkipped) {
+            self.version_upgrade_skipped += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_server_certificate_selected(&mut self, event: builder::ServerCertificateSelected) {
+            self.server_certificate_selected += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn on_handshake_status_updated(&mut self, event: builder::HandshakeStatusUpdated) {
             self.handshake_status_updated += 1;
             let event = event.into_event();
@@ -7548,6 +8648,13 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_receive_window_updated(&mut self, event: builder::ReceiveWindowUpdated) {
+            self.receive_window_updated += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn quic_version(&self) -> u32 {
             1
         }
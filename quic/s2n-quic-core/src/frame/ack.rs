@@ -23,8 +23,8 @@ macro_rules! ack_tag {
         0x02u8..=0x03u8
     };
 }
-const ACK_TAG: u8 = 0x02;
-const ACK_W_ECN_TAG: u8 = 0x03;
+pub(crate) const ACK_TAG: u8 = 0x02;
+pub(crate) const ACK_W_ECN_TAG: u8 = 0x03;
 
 //= https://www.rfc-editor.org/rfc/rfc9000#section-19.3
 //# ACK Frame {
@@ -148,7 +148,15 @@ decoder_parameterized_value!(
 impl<A: AckRanges> EncoderValue for Ack<A> {
     fn encode<E: Encoder>(&self, buffer: &mut E) {
         buffer.encode(&self.tag());
+        self.encode_fields(buffer);
+    }
+}
 
+impl<A: AckRanges> Ack<A> {
+    /// Encodes the fields following the frame type
+    ///
+    /// This is shared with the ACK_MP frame, which uses the same encoding.
+    pub(crate) fn encode_fields<E: Encoder>(&self, buffer: &mut E) {
         let mut iter = self.ack_ranges.ack_ranges();
 
         let first_ack_range = iter.next().expect("at least one ack range is required");
@@ -64,6 +64,13 @@ impl<AckRanges> AckElicitable for crate::frame::Ack<AckRanges> {
         AckElicitation::NonEliciting
     }
 }
+impl AckElicitable for crate::frame::AckFrequency {}
+impl<AckRanges> AckElicitable for crate::frame::AckMp<AckRanges> {
+    #[inline]
+    fn ack_elicitation(&self) -> AckElicitation {
+        AckElicitation::NonEliciting
+    }
+}
 impl AckElicitable for crate::frame::ConnectionClose<'_> {
     #[inline]
     fn ack_elicitation(&self) -> AckElicitation {
@@ -77,6 +84,7 @@ impl<Data> AckElicitable for crate::frame::Crypto<Data> {}
 impl<Data> AckElicitable for crate::frame::Datagram<Data> {}
 impl AckElicitable for crate::frame::DataBlocked {}
 impl AckElicitable for crate::frame::HandshakeDone {}
+impl AckElicitable for crate::frame::ImmediateAck {}
 impl AckElicitable for crate::frame::MaxData {}
 impl AckElicitable for crate::frame::MaxStreamData {}
 impl AckElicitable for crate::frame::MaxStreams {}
@@ -88,6 +96,7 @@ impl AckElicitable for crate::frame::Padding {
         AckElicitation::NonEliciting
     }
 }
+impl AckElicitable for crate::frame::PathAbandon<'_> {}
 impl AckElicitable for crate::frame::PathChallenge<'_> {}
 impl AckElicitable for crate::frame::PathResponse<'_> {}
 impl AckElicitable for crate::frame::Ping {}
@@ -0,0 +1,92 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{frame::Tag, varint::VarInt};
+use core::time::Duration;
+use s2n_codec::{decoder_parameterized_value, Encoder, EncoderValue};
+
+// An endpoint sends an ACK_FREQUENCY frame (type=0xaf) to request that its peer
+// changes the number of ack-eliciting packets and the amount of time it waits
+// before sending an acknowledgement.
+//
+// See https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency#section-4
+//
+// The frame type does not fit in a single byte so it is encoded as a 2-byte
+// variable-length integer on the wire.
+
+macro_rules! ack_frequency_tag {
+    () => {
+        0xafu8
+    };
+}
+
+// ACK_FREQUENCY Frame {
+//   Type (i) = 0xaf,
+//   Sequence Number (i),
+//   Ack-Eliciting Threshold (i),
+//   Request Max Ack Delay (i),
+//   Reordering Threshold (i),
+// }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AckFrequency {
+    /// A sequence number assigned by the sender so the receiver can ignore
+    /// frames that were received out of order
+    pub sequence_number: VarInt,
+
+    /// The maximum number of ack-eliciting packets the receiver of the frame
+    /// can receive without sending an acknowledgement
+    pub ack_eliciting_threshold: VarInt,
+
+    /// The requested maximum amount of time, in microseconds, the receiver of
+    /// the frame should delay sending an acknowledgement
+    pub request_max_ack_delay: VarInt,
+
+    /// The number of out-of-order packets the receiver of the frame can receive
+    /// before sending an immediate acknowledgement. A value of 0 indicates
+    /// out-of-order packets do not trigger an immediate acknowledgement.
+    pub reordering_threshold: VarInt,
+}
+
+impl AckFrequency {
+    pub const fn tag(self) -> u8 {
+        ack_frequency_tag!()
+    }
+
+    /// Returns the `request_max_ack_delay` field as a `Duration`
+    #[inline]
+    pub fn request_max_ack_delay(&self) -> Duration {
+        Duration::from_micros(self.request_max_ack_delay.as_u64())
+    }
+}
+
+decoder_parameterized_value!(
+    impl<'a> AckFrequency {
+        fn decode(_tag: Tag, buffer: Buffer) -> Result<Self> {
+            let (sequence_number, buffer) = buffer.decode()?;
+            let (ack_eliciting_threshold, buffer) = buffer.decode()?;
+            let (request_max_ack_delay, buffer) = buffer.decode()?;
+            let (reordering_threshold, buffer) = buffer.decode()?;
+
+            let frame = AckFrequency {
+                sequence_number,
+                ack_eliciting_threshold,
+                request_max_ack_delay,
+                reordering_threshold,
+            };
+
+            Ok((frame, buffer))
+        }
+    }
+);
+
+impl EncoderValue for AckFrequency {
+    #[inline]
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&VarInt::from_u8(self.tag()));
+        buffer.encode(&self.sequence_number);
+        buffer.encode(&self.ack_eliciting_threshold);
+        buffer.encode(&self.request_max_ack_delay);
+        buffer.encode(&self.reordering_threshold);
+    }
+}
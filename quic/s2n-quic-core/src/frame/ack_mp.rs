@@ -0,0 +1,96 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    frame::{
+        ack::{self, Ack, AckRangesDecoder},
+        Tag,
+    },
+    varint::VarInt,
+};
+use s2n_codec::{decoder_parameterized_value, Encoder, EncoderValue};
+
+// An endpoint sends an ACK_MP frame (types 0x15228c00 and 0x15228c01) to acknowledge
+// packets that were sent in the packet number space of a specific path. The frame
+// contains the same fields as the ACK frame, preceded by the sequence number of the
+// Destination Connection ID that identifies the packet number space.
+//
+// See https://datatracker.ietf.org/doc/html/draft-ietf-quic-multipath-03
+//
+// The frame type is encoded as a 4-byte variable-length integer on the wire. The
+// last byte of the frame type is used as the tag.
+
+macro_rules! ack_mp_tag {
+    () => {
+        0x1522_8c00u64..=0x1522_8c01u64
+    };
+}
+const ACK_MP_TYPE: u32 = 0x1522_8c00;
+const ACK_MP_TAG: u8 = 0x00;
+const ACK_MP_W_ECN_TAG: u8 = 0x01;
+
+// ACK_MP Frame {
+//   Type (i) = 0x15228c00..0x15228c01,
+//   Destination Connection ID Sequence Number (i),
+//   Largest Acknowledged (i),
+//   ACK Delay (i),
+//   ACK Range Count (i),
+//   First ACK Range (i),
+//   ACK Range (..) ...,
+//   [ECN Counts (..)],
+// }
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AckMp<AckRanges> {
+    /// The sequence number of the connection ID that identifies the packet
+    /// number space being acknowledged
+    pub path_identifier: VarInt,
+
+    /// The packets being acknowledged in the packet number space
+    pub ack: Ack<AckRanges>,
+}
+
+impl<AckRanges> AckMp<AckRanges> {
+    pub fn tag(&self) -> u8 {
+        if self.ack.ecn_counts.is_some() {
+            ACK_MP_W_ECN_TAG
+        } else {
+            ACK_MP_TAG
+        }
+    }
+
+    /// Returns the full frame type, which doesn't fit into the tag
+    pub fn frame_type(&self) -> VarInt {
+        VarInt::from_u32(ACK_MP_TYPE | self.tag() as u32)
+    }
+}
+
+decoder_parameterized_value!(
+    impl<'a> AckMp<AckRangesDecoder<'a>> {
+        fn decode(tag: Tag, buffer: Buffer) -> Result<Self> {
+            let (path_identifier, buffer) = buffer.decode()?;
+
+            let ack_tag = if tag == ACK_MP_W_ECN_TAG {
+                ack::ACK_W_ECN_TAG
+            } else {
+                ack::ACK_TAG
+            };
+            let (ack, buffer) = buffer.decode_parameterized(ack_tag)?;
+
+            let frame = AckMp {
+                path_identifier,
+                ack,
+            };
+
+            Ok((frame, buffer))
+        }
+    }
+);
+
+impl<A: ack::AckRanges> EncoderValue for AckMp<A> {
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&self.frame_type());
+        buffer.encode(&self.path_identifier);
+        self.ack.encode_fields(buffer);
+    }
+}
@@ -19,6 +19,13 @@ impl<AckRanges> CongestionControlled for crate::frame::Ack<AckRanges> {
         false
     }
 }
+impl CongestionControlled for crate::frame::AckFrequency {}
+impl<AckRanges> CongestionControlled for crate::frame::AckMp<AckRanges> {
+    #[inline]
+    fn is_congestion_controlled(&self) -> bool {
+        false
+    }
+}
 impl CongestionControlled for crate::frame::ConnectionClose<'_> {}
 impl<Data> CongestionControlled for crate::frame::Crypto<Data> {}
 //= https://www.rfc-editor.org/rfc/rfc9221#section-5.4
@@ -26,6 +33,7 @@ impl<Data> CongestionControlled for crate::frame::Crypto<Data> {}
 impl<Data> CongestionControlled for crate::frame::Datagram<Data> {}
 impl CongestionControlled for crate::frame::DataBlocked {}
 impl CongestionControlled for crate::frame::HandshakeDone {}
+impl CongestionControlled for crate::frame::ImmediateAck {}
 impl CongestionControlled for crate::frame::MaxData {}
 impl CongestionControlled for crate::frame::MaxStreamData {}
 impl CongestionControlled for crate::frame::MaxStreams {}
@@ -48,6 +56,7 @@ impl CongestionControlled for crate::frame::Padding {
         false
     }
 }
+impl CongestionControlled for crate::frame::PathAbandon<'_> {}
 impl CongestionControlled for crate::frame::PathChallenge<'_> {}
 impl CongestionControlled for crate::frame::PathResponse<'_> {}
 impl CongestionControlled for crate::frame::Ping {}
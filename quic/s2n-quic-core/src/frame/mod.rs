@@ -6,6 +6,7 @@
 use crate::{
     event,
     frame::{ack_elicitation::AckElicitable, congestion_controlled::CongestionControlled},
+    varint::VarInt,
 };
 use core::fmt;
 use s2n_codec::{
@@ -36,12 +37,22 @@ pub trait FrameTrait: AckElicitable + CongestionControlled + path_validation::Pr
 impl<T: AckElicitable + CongestionControlled + path_validation::Probing> FrameTrait for T {}
 
 macro_rules! frames {
-    ($ack:ident, $data:ident | $($tag_macro:ident => $module:ident, $handler:ident, $ty:ident $([$($generics:tt)+])?;)*) => {
+    (
+        $ack:ident, $data:ident |
+        $($tag_macro:ident => $module:ident, $handler:ident, $ty:ident $([$($generics:tt)+])?;)*
+        |
+        $($ext_tag_macro:ident => $ext_module:ident, $ext_handler:ident, $ext_ty:ident $([$($ext_generics:tt)+])?;)*
+    ) => {
         $(
             #[macro_use]
             pub mod $module;
             pub use $module::$ty;
         )*
+        $(
+            #[macro_use]
+            pub mod $ext_module;
+            pub use $ext_module::$ext_ty;
+        )*
 
         pub type RemainingBuffer<'a> = Option<DecoderBufferMut<'a>>;
 
@@ -50,6 +61,9 @@ macro_rules! frames {
             $(
                 $ty($module::$ty $(<$($generics)*>)?),
             )*
+            $(
+                $ext_ty($ext_module::$ext_ty $(<$($ext_generics)*>)?),
+            )*
         }
 
         impl<'a, $ack, $data> Frame<'a, $ack, $data> {
@@ -59,6 +73,9 @@ macro_rules! frames {
                     $(
                         Frame::$ty(frame) => frame.tag(),
                     )*
+                    $(
+                        Frame::$ext_ty(frame) => frame.tag(),
+                    )*
                 }
             }
         }
@@ -74,6 +91,9 @@ macro_rules! frames {
                     $(
                         Frame::$ty(inner) => inner.into_event(),
                     )*
+                    $(
+                        Frame::$ext_ty(inner) => inner.into_event(),
+                    )*
                 }
             }
         }
@@ -85,6 +105,9 @@ macro_rules! frames {
                     $(
                         Frame::$ty(frame) => frame.ack_elicitation(),
                     )*
+                    $(
+                        Frame::$ext_ty(frame) => frame.ack_elicitation(),
+                    )*
                 }
             }
         }
@@ -96,6 +119,9 @@ macro_rules! frames {
                     $(
                         Frame::$ty(frame) => frame.path_validation(),
                     )*
+                    $(
+                        Frame::$ext_ty(frame) => frame.path_validation(),
+                    )*
                 }
             }
         }
@@ -108,9 +134,20 @@ macro_rules! frames {
                 }
             }
         )*
+        $(
+            impl<'a, $ack, $data> From<$ext_module::$ext_ty $(<$($ext_generics)*>)?> for Frame<'a, $ack, $data> {
+                #[inline]
+                fn from(v: $ext_module::$ext_ty $(<$($ext_generics)*>)?)  -> Frame<'a, $ack, $data> {
+                    Frame::$ext_ty(v)
+                }
+            }
+        )*
 
         impl<'a, $ack, $data: DecoderValueMut<'a>> DecoderValueMut<'a> for Frame<'a, $ack, $data>
-        where ack::Ack<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag> {
+        where
+            ack::Ack<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag>,
+            ack_mp::AckMp<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag>,
+        {
             #[inline]
             fn decode_mut(buffer: DecoderBufferMut<'a>) -> DecoderBufferMutResult<'a, Self> {
                 BasicFrameDecoder.decode_frame(buffer)
@@ -124,6 +161,9 @@ macro_rules! frames {
                     $(
                         Frame::$ty(frame) => buffer.encode(frame),
                     )*
+                    $(
+                        Frame::$ext_ty(frame) => buffer.encode(frame),
+                    )*
                 }
             }
         }
@@ -131,7 +171,10 @@ macro_rules! frames {
         struct BasicFrameDecoder;
 
         impl<'a, $ack, $data: DecoderValueMut<'a>> FrameDecoder<'a, $ack, $data> for BasicFrameDecoder
-        where ack::Ack<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag> {
+        where
+            ack::Ack<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag>,
+            ack_mp::AckMp<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag>,
+        {
             type Output = Frame<'a, $ack, $data>;
 
             $(
@@ -140,15 +183,27 @@ macro_rules! frames {
                     Ok(Frame::$ty(frame))
                 }
             )*
+            $(
+                #[inline]
+                fn $ext_handler(&mut self, frame: $ext_module::$ext_ty $(<$($ext_generics)*>)?) -> Result<Self::Output, DecoderError> {
+                    Ok(Frame::$ext_ty(frame))
+                }
+            )*
         }
 
         pub trait FrameDecoder<'a, $ack, $data: DecoderValueMut<'a>>
-        where ack::Ack<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag> {
+        where
+            ack::Ack<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag>,
+            ack_mp::AckMp<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag>,
+        {
             type Output;
 
             $(
                 fn $handler(&mut self, frame: $module::$ty $(<$($generics)*>)?) -> Result<Self::Output, DecoderError>;
             )*
+            $(
+                fn $ext_handler(&mut self, frame: $ext_module::$ext_ty $(<$($ext_generics)*>)?) -> Result<Self::Output, DecoderError>;
+            )*
 
             fn handle_extension_frame(&mut self, buffer: DecoderBufferMut<'a>) -> DecoderBufferMutResult<'a, Self::Output> {
                 let _ = buffer;
@@ -163,11 +218,44 @@ macro_rules! frames {
             ) -> DecoderBufferMutResult<'a, Self::Output> {
                 let tag = buffer.peek_byte(0)?;
                 match tag {
+                    // Frame types between 0x40 and 0xff are encoded as 2-byte
+                    // variable-integers with a first byte of 0x40. Frame types that
+                    // don't fit into a single byte are selected with the second byte.
+                    0b0100_0000 => {
+                        let tag = buffer.peek_byte(1)?;
+                        match tag {
+                            $(
+                                $tag_macro!() if tag >= 0b0100_0000 => {
+                                    let buffer = buffer.skip(2)?;
+                                    let (frame, buffer) = buffer.decode_parameterized(tag)?;
+                                    let output = self.$handler(frame)?;
+                                    Ok((output, buffer))
+                                },
+                            )*
+                            _ => self.handle_extension_frame(buffer),
+                        }
+                    }
+                    // Frame types that don't fit into a 2-byte variable-integer are
+                    // selected with the entire frame type. The last byte of the frame
+                    // type is used as the tag.
+                    0b1000_0000..=0b1111_1111 => {
+                        let (frame_type, _) = buffer.peek().decode::<VarInt>()?;
+                        match frame_type.as_u64() {
+                            $(
+                                $ext_tag_macro!() => {
+                                    let buffer = buffer.skip(frame_type.encoding_size())?;
+                                    let (frame, buffer) = buffer.decode_parameterized(frame_type.as_u64() as Tag)?;
+                                    let output = self.$ext_handler(frame)?;
+                                    Ok((output, buffer))
+                                },
+                            )*
+                            _ => self.handle_extension_frame(buffer),
+                        }
+                    }
                     // Make sure the single byte frame tags fit into a small variable-integer
                     // otherwise fallback to extension selection
-                    0b0100_0000..=0xff => self.handle_extension_frame(buffer),
                     $(
-                        $tag_macro!() => {
+                        $tag_macro!() if tag < 0b0100_0000 => {
                             let buffer = buffer.skip(core::mem::size_of::<Tag>())?;
                             let (frame, buffer) = buffer.decode_parameterized(tag)?;
                             let output = self.$handler(frame)?;
@@ -194,6 +282,16 @@ macro_rules! frames {
                     ));
                 }
             )*
+            $(
+                #[test]
+                fn $ext_module() {
+                    assert_codec_round_trip_sample_file!(FrameMut, concat!(
+                        "src/frame/test_samples/",
+                        stringify!($ext_module),
+                        ".bin"
+                    ));
+                }
+            )*
         }
     };
 }
@@ -255,6 +353,11 @@ frames! {
     connection_close_tag => connection_close, handle_connection_close_frame, ConnectionClose['a];
     handshake_done_tag => handshake_done, handle_handshake_done_frame, HandshakeDone;
     datagram_tag => datagram, handle_datagram_frame, Datagram[Data];
+    ack_frequency_tag => ack_frequency, handle_ack_frequency_frame, AckFrequency;
+    immediate_ack_tag => immediate_ack, handle_immediate_ack_frame, ImmediateAck;
+    |
+    ack_mp_tag => ack_mp, handle_ack_mp_frame, AckMp[AckRanges];
+    path_abandon_tag => path_abandon, handle_path_abandon_frame, PathAbandon['a];
 }
 
 #[derive(Clone, Copy, Debug, Default)]
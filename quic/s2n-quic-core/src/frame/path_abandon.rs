@@ -0,0 +1,96 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{frame::Tag, varint::VarInt};
+use s2n_codec::{decoder_parameterized_value, Encoder, EncoderValue};
+
+// An endpoint sends a PATH_ABANDON frame (type=0x15228c05) to inform its peer that it
+// stops using a path. The path is identified by the sequence number of the Destination
+// Connection ID that the sender of the frame uses on the path.
+//
+// See https://datatracker.ietf.org/doc/html/draft-ietf-quic-multipath-03
+//
+// The frame type is encoded as a 4-byte variable-length integer on the wire. The
+// last byte of the frame type is used as the tag.
+
+macro_rules! path_abandon_tag {
+    () => {
+        0x1522_8c05u64
+    };
+}
+const PATH_ABANDON_TYPE: u32 = 0x1522_8c05;
+
+// PATH_ABANDON Frame {
+//   Type (i) = 0x15228c05,
+//   Destination Connection ID Sequence Number (i),
+//   Error Code (i),
+//   Reason Phrase Length (i),
+//   Reason Phrase (..),
+// }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PathAbandon<'a> {
+    /// The sequence number of the Destination Connection ID the sender
+    /// of the frame uses on the abandoned path
+    pub path_identifier: VarInt,
+
+    /// A variable length integer error code which indicates the reason
+    /// for abandoning the path
+    pub error_code: VarInt,
+
+    /// A human-readable explanation for why the path was abandoned.
+    /// This SHOULD be a UTF-8 encoded string.
+    pub reason: Option<&'a [u8]>,
+}
+
+impl<'a> PathAbandon<'a> {
+    pub const fn tag(&self) -> u8 {
+        PATH_ABANDON_TYPE as u8
+    }
+
+    /// Returns the full frame type, which doesn't fit into the tag
+    pub const fn frame_type(&self) -> VarInt {
+        VarInt::from_u32(PATH_ABANDON_TYPE)
+    }
+}
+
+decoder_parameterized_value!(
+    impl<'a> PathAbandon<'a> {
+        fn decode(_tag: Tag, buffer: Buffer) -> Result<Self> {
+            let (path_identifier, buffer) = buffer.decode()?;
+            let (error_code, buffer) = buffer.decode()?;
+            let (reason, buffer) = buffer.decode_slice_with_len_prefix::<VarInt>()?;
+
+            let reason = if reason.is_empty() {
+                None
+            } else {
+                // newer versions of clippy complain about redundant slicing
+                // but we don't know if this is a `&slice` or `&mut slice`
+                #[allow(clippy::all)]
+                Some(&reason.into_less_safe_slice()[..])
+            };
+
+            let frame = PathAbandon {
+                path_identifier,
+                error_code,
+                reason,
+            };
+
+            Ok((frame, buffer))
+        }
+    }
+);
+
+impl<'a> EncoderValue for PathAbandon<'a> {
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&self.frame_type());
+        buffer.encode(&self.path_identifier);
+        buffer.encode(&self.error_code);
+
+        if let Some(reason) = &self.reason {
+            buffer.encode_with_len_prefix::<VarInt, _>(reason);
+        } else {
+            buffer.encode(&0u8);
+        }
+    }
+}
@@ -546,6 +546,29 @@ impl Strategy for Frame {
                 }
                 .into()
             },
+            |rand, _data, _cap| {
+                frame::AckFrequency {
+                    sequence_number: rand.gen_varint(),
+                    ack_eliciting_threshold: rand.gen_varint(),
+                    request_max_ack_delay: rand.gen_varint(),
+                    reordering_threshold: rand.gen_varint(),
+                }
+                .into()
+            },
+            |_rand, _data, _cap| frame::ImmediateAck.into(),
+            |rand, data, cap| {
+                frame::PathAbandon {
+                    path_identifier: rand.gen_varint(),
+                    error_code: rand.gen_varint(),
+                    reason: if rand.gen_bool() {
+                        let reason = rand.gen_slice(&mut data[..cap]);
+                        Some(reason)
+                    } else {
+                        None
+                    },
+                }
+                .into()
+            },
         ];
 
         let index = rand.gen_range(0..frames.len() as u64) as usize;
@@ -0,0 +1,54 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::path::{self, LocalAddress, RemoteAddress};
+use core::fmt;
+
+/// A hook for IO providers that can bind sockets to additional local addresses
+///
+/// Clients use it to migrate a connection, or open an additional path, from a local address
+/// that the socket of the endpoint isn't bound to. The IO provider receives the packets of the
+/// bound sockets along with the packets of the endpoint socket, and sends the packets of a path
+/// through the socket bound to its local address.
+pub trait Bind: 'static + Send + Sync {
+    type PathHandle: path::Handle;
+
+    /// Binds a socket to `local_address` and returns the handle of a path from it to
+    /// `remote_address`
+    ///
+    /// A port of `0` binds the socket to an ephemeral port. The local address of the returned
+    /// handle is the address the socket was bound to. Every successful call must be paired with
+    /// a call to [`Self::unbind`] with that address.
+    fn bind(
+        &self,
+        local_address: LocalAddress,
+        remote_address: RemoteAddress,
+    ) -> Result<Self::PathHandle, Error>;
+
+    /// Releases a socket returned by [`Self::bind`]
+    ///
+    /// The socket is closed once all of the paths bound to it have been released.
+    fn unbind(&self, local_address: LocalAddress);
+}
+
+/// Errors that can occur when binding a socket to a local address
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The IO provider can't bind sockets to the local address
+    Unsupported,
+    /// The socket couldn't be bound to the local address, for example because it's in use
+    AddressUnavailable,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "The IO provider can't bind sockets to the address"),
+            Self::AddressUnavailable => write!(f, "The socket couldn't be bound to the address"),
+        }
+    }
+}
@@ -58,6 +58,7 @@ pub mod counter;
 pub mod crypto;
 pub mod ct;
 pub mod datagram;
+pub mod early_data;
 pub mod endpoint;
 pub mod event;
 pub mod frame;
@@ -82,6 +83,7 @@ pub mod token;
 pub mod transmission;
 pub mod transport;
 pub mod varint;
+pub mod version;
 pub mod xdp;
 
 #[cfg(any(test, feature = "testing"))]
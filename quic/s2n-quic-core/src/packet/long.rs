@@ -4,6 +4,7 @@
 use crate::{
     packet::{encoding::PacketPayloadLenCursor, number::TruncatedPacketNumber},
     varint::VarInt,
+    version::VERSION_2,
 };
 use core::convert::TryFrom;
 use s2n_codec::{
@@ -38,6 +39,9 @@ use s2n_codec::{
 pub(crate) const PACKET_TYPE_MASK: u8 = 0x30;
 const PACKET_TYPE_OFFSET: u8 = 4;
 
+/// The header form and fixed bits of a long header, shifted down by `PACKET_TYPE_OFFSET`
+const LONG_HEADER_TAG: u8 = 0b1100;
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
 //# Type-Specific Bits:  The semantics of the lower four bits (those with
 //# a mask of 0x0f) of byte 0 are determined by the packet type.
@@ -150,6 +154,38 @@ impl PacketType {
     pub fn from_bits(bits: u8) -> Self {
         (bits & PACKET_TYPE_MASK >> PACKET_TYPE_OFFSET).into()
     }
+
+    //= https://www.rfc-editor.org/rfc/rfc9369#section-3.2
+    //# All version 2 long header packet types are different.  The Type field
+    //# values are:
+    //#
+    //# Initial:  0b01
+    //# 0-RTT:  0b10
+    //# Handshake:  0b11
+    //# Retry:  0b00
+
+    /// Returns the header form, fixed bit and long packet type bits of the first byte
+    /// of a packet with the given QUIC version
+    #[inline]
+    pub const fn into_tag(self, version: Version) -> u8 {
+        let bits = if version == VERSION_2 {
+            (self as u8 + 1) & 0b11
+        } else {
+            self as u8
+        };
+        (LONG_HEADER_TAG | bits) << PACKET_TYPE_OFFSET
+    }
+
+    /// Returns the packet type of a long header packet, given its first byte and QUIC version
+    #[inline]
+    pub fn from_tag(version: Version, tag: u8) -> Self {
+        let bits = (tag & PACKET_TYPE_MASK) >> PACKET_TYPE_OFFSET;
+        if version == VERSION_2 {
+            (bits.wrapping_sub(1) & 0b11).into()
+        } else {
+            bits.into()
+        }
+    }
 }
 
 impl From<u8> for PacketType {
@@ -263,3 +299,32 @@ impl EncoderValue for LongPayloadLenCursor {
         self.max_value.encode(encoder)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::VERSION_1;
+
+    #[test]
+    fn packet_type_tag_test() {
+        for (packet_type, v1, v2) in [
+            (PacketType::Initial, 0xc0, 0xd0),
+            (PacketType::ZeroRtt, 0xd0, 0xe0),
+            (PacketType::Handshake, 0xe0, 0xf0),
+            (PacketType::Retry, 0xf0, 0xc0),
+        ] {
+            assert_eq!(packet_type.into_tag(VERSION_1), v1);
+            assert_eq!(packet_type.into_tag(VERSION_2), v2);
+
+            // the type-specific bits should be ignored
+            assert_eq!(
+                PacketType::from_tag(VERSION_1, v1 | 0x0f) as u8,
+                packet_type as u8
+            );
+            assert_eq!(
+                PacketType::from_tag(VERSION_2, v2 | 0x0f) as u8,
+                packet_type as u8
+            );
+        }
+    }
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::connection;
-use s2n_codec::{decoder_invariant, DecoderBufferMut, DecoderBufferMutResult, DecoderError};
+use s2n_codec::{DecoderBufferMut, DecoderBufferMutResult, DecoderError};
 
 pub(crate) type Tag = u8;
 
@@ -24,6 +24,7 @@ pub mod encoding;
 pub mod interceptor;
 pub mod key_phase;
 pub mod long;
+pub mod quic_bit;
 
 pub mod number;
 pub mod stateless_reset;
@@ -32,6 +33,7 @@ pub mod stateless_reset;
 mod tests;
 
 pub use key_phase::{KeyPhase, ProtectedKeyPhase};
+pub use quic_bit::QuicBit;
 
 use connection::id::ConnectionInfo;
 use handshake::ProtectedHandshake;
@@ -98,6 +100,20 @@ impl<'a> ProtectedPacket<'a> {
             ProtectedPacket::Retry(packet) => Some(packet.version),
         }
     }
+
+    /// Returns the value of the packet's QUIC bit
+    ///
+    /// Version Negotiation packets don't use the QUIC bit so they always return [`QuicBit::One`].
+    pub fn quic_bit(&self) -> QuicBit {
+        match self {
+            ProtectedPacket::Short(packet) => packet.quic_bit,
+            ProtectedPacket::VersionNegotiation(_) => QuicBit::One,
+            ProtectedPacket::Initial(packet) => QuicBit::from_tag(packet.payload.get_tag()),
+            ProtectedPacket::ZeroRtt(packet) => QuicBit::from_tag(packet.payload.get_tag()),
+            ProtectedPacket::Handshake(packet) => QuicBit::from_tag(packet.payload.get_tag()),
+            ProtectedPacket::Retry(packet) => QuicBit::from_tag(packet.tag),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -212,20 +228,22 @@ pub trait PacketDecoder<'a> {
         }
 
         macro_rules! long_packet {
-            ($struct:ident, $handler:ident) => {{
-                let (version, _peek) = peek.decode()?;
-                if version == version_negotiation::VERSION {
-                    version_negotiation!(version)
-                } else {
-                    let (packet, buffer) = $struct::decode(tag, version, buffer)?;
-                    let output = self.$handler(packet)?;
-                    Ok((output, buffer))
-                }
+            ($struct:ident, $handler:ident, $version:ident) => {{
+                let (packet, buffer) = $struct::decode(tag, $version, buffer)?;
+                let output = self.$handler(packet)?;
+                Ok((output, buffer))
             }};
         }
 
+        //= https://www.rfc-editor.org/rfc/rfc9287#section-3
+        //# Its presence in the transport parameters indicates that the
+        //# endpoint is willing to receive QUIC packets containing any value
+        //# for the fixed bit.
+        // Packets with a cleared QUIC bit are decoded so connections that advertised the
+        // `grease_quic_bit` transport parameter can accept them. All other connections
+        // discard these packets after checking `ProtectedPacket::quic_bit`.
         match tag >> 4 {
-            short_tag!() => {
+            short_tag!() | short_no_quic_bit_tag!() => {
                 let (packet, buffer) = short::ProtectedShort::decode(
                     tag,
                     buffer,
@@ -235,18 +253,32 @@ pub trait PacketDecoder<'a> {
                 let output = self.handle_short_packet(packet)?;
                 Ok((output, buffer))
             }
-            version_negotiation_no_fixed_bit_tag!() => {
+            version_negotiation_no_fixed_bit_tag!()
+            | initial_tag!()
+            | zero_rtt_tag!()
+            | handshake_tag!()
+            | retry_tag!() => {
                 let (version, _peek) = peek.decode()?;
-                decoder_invariant!(
-                    version_negotiation::VERSION == version,
-                    "invalid version negotiation packet"
-                );
-                version_negotiation!(version)
+                if version == version_negotiation::VERSION {
+                    return version_negotiation!(version);
+                }
+
+                // The long packet type bits are assigned differently in each QUIC version
+                match long::PacketType::from_tag(version, tag) {
+                    long::PacketType::Initial => {
+                        long_packet!(ProtectedInitial, handle_initial_packet, version)
+                    }
+                    long::PacketType::ZeroRtt => {
+                        long_packet!(ProtectedZeroRtt, handle_zero_rtt_packet, version)
+                    }
+                    long::PacketType::Handshake => {
+                        long_packet!(ProtectedHandshake, handle_handshake_packet, version)
+                    }
+                    long::PacketType::Retry => {
+                        long_packet!(ProtectedRetry, handle_retry_packet, version)
+                    }
+                }
             }
-            initial_tag!() => long_packet!(ProtectedInitial, handle_initial_packet),
-            zero_rtt_tag!() => long_packet!(ProtectedZeroRtt, handle_zero_rtt_packet),
-            handshake_tag!() => long_packet!(ProtectedHandshake, handle_handshake_packet),
-            retry_tag!() => long_packet!(ProtectedRetry, handle_retry_packet),
             _ => Err(DecoderError::InvariantViolation("invalid packet").into()),
         }
     }
@@ -0,0 +1,62 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::Tag;
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
+//# Fixed Bit:  The next bit (0x40) of byte 0 is set to 1, unless the
+//#    packet is a Version Negotiation packet.
+
+//= https://www.rfc-editor.org/rfc/rfc9287#section-1
+//# This document defines an extension to QUIC that allows endpoints to
+//# negotiate the use of the QUIC bit as a way of testing that it can be
+//# set to a value of 0 without causing packets to be dropped.
+
+const QUIC_BIT_MASK: u8 = 0x40;
+
+/// The second most significant bit of the first byte of a packet
+///
+/// The bit is always set unless the peer advertised the `grease_quic_bit` transport parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuicBit {
+    Zero,
+    One,
+}
+
+impl Default for QuicBit {
+    fn default() -> Self {
+        Self::One
+    }
+}
+
+impl QuicBit {
+    #[inline]
+    pub fn from_tag(tag: Tag) -> Self {
+        if tag & QUIC_BIT_MASK == QUIC_BIT_MASK {
+            Self::One
+        } else {
+            Self::Zero
+        }
+    }
+
+    #[inline]
+    pub fn into_packet_tag_mask(self) -> u8 {
+        match self {
+            Self::One => QUIC_BIT_MASK,
+            Self::Zero => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quic_bit_from_tag_test() {
+        for tag in 0..=255 {
+            let quic_bit = QuicBit::from_tag(tag);
+            assert_eq!(quic_bit.into_packet_tag_mask(), tag & QUIC_BIT_MASK);
+        }
+    }
+}
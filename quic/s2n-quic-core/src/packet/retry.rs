@@ -12,7 +12,7 @@ use crate::{
     packet::{
         decoding::HeaderDecoder,
         initial::ProtectedInitial,
-        long::{DestinationConnectionIdLen, SourceConnectionIdLen, Version},
+        long::{DestinationConnectionIdLen, PacketType, SourceConnectionIdLen, Version},
         Tag,
     },
     random, token,
@@ -157,7 +157,7 @@ impl<'a> Retry<'a> {
 
         outcome?;
 
-        let tag = C::generate_tag(buffer.as_mut_slice());
+        let tag = C::generate_tag(retry_packet.version, buffer.as_mut_slice());
         buffer.write_slice(&tag);
         let end = buffer.len();
         let start =
@@ -190,7 +190,7 @@ impl<'a> Retry<'a> {
         //# of packets that have accidentally been corrupted by the network, and
         //# only an entity that observes an Initial packet can send a valid Retry
         //# packet.
-        Crypto::validate(buf, *self.retry_integrity_tag)?;
+        Crypto::validate(self.version, buf, *self.retry_integrity_tag)?;
 
         Ok(())
     }
@@ -209,7 +209,7 @@ impl<'a> Retry<'a> {
             // The last 4 bits are unused. They are set to 0x0f here to allow easy testing with
             // example packets provided in the RFC.
             // https://www.rfc-editor.org/rfc/rfc9001#section-A.2
-            tag: (retry_tag!() << 4) | 0x0f,
+            tag: PacketType::Retry.into_tag(initial_packet.version) | 0x0f,
             version: initial_packet.version,
             destination_connection_id: initial_packet.source_connection_id(),
             source_connection_id: local_connection_id,
@@ -4,7 +4,9 @@
 use crate::{
     connection,
     connection::id::ConnectionInfo,
-    crypto::{CryptoError, EncryptedPayload, OneRttHeaderKey, OneRttKey, ProtectedPayload},
+    crypto::{
+        CryptoError, EncryptedPayload, Key, OneRttHeaderKey, OneRttKey, PathKey, ProtectedPayload,
+    },
     packet::{
         decoding::HeaderDecoder,
         encoding::{PacketEncoder, PacketPayloadEncoder},
@@ -12,7 +14,7 @@ use crate::{
             PacketNumber, PacketNumberLen, PacketNumberSpace, ProtectedPacketNumber,
             TruncatedPacketNumber,
         },
-        KeyPhase, ProtectedKeyPhase, Tag,
+        KeyPhase, ProtectedKeyPhase, QuicBit, Tag,
     },
     transport,
 };
@@ -43,7 +45,14 @@ macro_rules! short_tag {
     };
 }
 
-const ENCODING_TAG: u8 = 0b0100_0000;
+//= https://www.rfc-editor.org/rfc/rfc9287#section-3.1
+//# An endpoint that advertises the grease_quic_bit transport parameter
+//# MUST accept packets with the QUIC Bit set to a value of 0.
+macro_rules! short_no_quic_bit_tag {
+    () => {
+        0b0000u8..=0b0011u8
+    };
+}
 
 //= https://www.rfc-editor.org/rfc/rfc9000#section-17.3.1
 //# Spin Bit:  The third most significant bit (0x20) of byte 0 is the
@@ -57,7 +66,7 @@ const SPIN_BIT_MASK: u8 = 0x20;
 
 const RESERVED_BITS_MASK: u8 = 0x18;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SpinBit {
     Zero,
     One,
@@ -70,7 +79,8 @@ impl Default for SpinBit {
 }
 
 impl SpinBit {
-    fn from_tag(tag: Tag) -> Self {
+    #[inline]
+    pub fn from_tag(tag: Tag) -> Self {
         if tag & SPIN_BIT_MASK == SPIN_BIT_MASK {
             Self::One
         } else {
@@ -78,6 +88,16 @@ impl SpinBit {
         }
     }
 
+    /// Returns the opposite value of the spin bit
+    #[inline]
+    #[must_use]
+    pub fn invert(self) -> Self {
+        match self {
+            Self::Zero => Self::One,
+            Self::One => Self::Zero,
+        }
+    }
+
     fn into_packet_tag_mask(self) -> u8 {
         match self {
             Self::One => SPIN_BIT_MASK,
@@ -115,6 +135,7 @@ impl SpinBit {
 
 #[derive(Debug)]
 pub struct Short<DCID, KeyPhase, PacketNumber, Payload> {
+    pub quic_bit: QuicBit,
     pub spin_bit: SpinBit,
     pub key_phase: KeyPhase,
     pub destination_connection_id: DCID,
@@ -137,6 +158,7 @@ impl<'a> ProtectedShort<'a> {
     ) -> DecoderBufferMutResult<'a, ProtectedShort<'a>> {
         let mut decoder = HeaderDecoder::new_short(&buffer);
 
+        let quic_bit = QuicBit::from_tag(tag);
         let spin_bit = SpinBit::from_tag(tag);
         let key_phase = ProtectedKeyPhase;
 
@@ -150,6 +172,7 @@ impl<'a> ProtectedShort<'a> {
             decoder.finish_short()?.split_off_packet(buffer)?;
 
         let packet = Short {
+            quic_bit,
             spin_bit,
             key_phase,
             destination_connection_id,
@@ -166,6 +189,7 @@ impl<'a> ProtectedShort<'a> {
         largest_acknowledged_packet_number: PacketNumber,
     ) -> Result<EncryptedShort<'a>, CryptoError> {
         let Short {
+            quic_bit,
             spin_bit,
             destination_connection_id,
             payload,
@@ -180,6 +204,7 @@ impl<'a> ProtectedShort<'a> {
         let packet_number = truncated_packet_number.expand(largest_acknowledged_packet_number);
 
         Ok(Short {
+            quic_bit,
             spin_bit,
             key_phase,
             destination_connection_id,
@@ -198,7 +223,27 @@ impl<'a> ProtectedShort<'a> {
 
 impl<'a> EncryptedShort<'a> {
     pub fn decrypt<C: OneRttKey>(self, crypto: &C) -> Result<CleartextShort<'a>, transport::Error> {
+        self.decrypt_with_key(crypto)
+    }
+
+    /// Decrypts a packet that was sent in the packet number space of a path
+    ///
+    /// The path identifier is the sequence number of the packet's destination connection ID.
+    pub fn decrypt_for_path<C: OneRttKey>(
+        self,
+        crypto: &mut C,
+        path_identifier: u32,
+    ) -> Result<CleartextShort<'a>, transport::Error> {
+        if path_identifier == 0 {
+            return self.decrypt_with_key(crypto);
+        }
+
+        self.decrypt_with_key(&PathKey::new(crypto, path_identifier))
+    }
+
+    fn decrypt_with_key<C: Key>(self, crypto: &C) -> Result<CleartextShort<'a>, transport::Error> {
         let Short {
+            quic_bit,
             spin_bit,
             key_phase,
             destination_connection_id,
@@ -224,6 +269,7 @@ impl<'a> EncryptedShort<'a> {
         let destination_connection_id = destination_connection_id.get(header);
 
         Ok(Short {
+            quic_bit,
             spin_bit,
             key_phase,
             destination_connection_id,
@@ -266,7 +312,7 @@ impl<DCID: EncoderValue, Payload: EncoderValue> EncoderValue
 impl<DCID: EncoderValue, PacketNumber, Payload> Short<DCID, KeyPhase, PacketNumber, Payload> {
     #[inline]
     fn encode_header<E: Encoder>(&self, packet_number_len: PacketNumberLen, encoder: &mut E) {
-        (ENCODING_TAG
+        (self.quic_bit.into_packet_tag_mask()
             | self.spin_bit.into_packet_tag_mask()
             | self.key_phase.into_packet_tag_mask()
             | packet_number_len.into_packet_tag_mask())
@@ -296,3 +342,24 @@ impl<DCID: EncoderValue, Payload: PacketPayloadEncoder, K: OneRttKey, H: OneRttH
         &mut self.payload
     }
 }
+
+impl<'k, DCID: EncoderValue, Payload: PacketPayloadEncoder, K: OneRttKey, H: OneRttHeaderKey>
+    PacketEncoder<PathKey<'k, K>, H, Payload> for Short<DCID, KeyPhase, PacketNumber, Payload>
+{
+    type PayloadLenCursor = ();
+
+    #[inline]
+    fn packet_number(&self) -> PacketNumber {
+        self.packet_number
+    }
+
+    #[inline]
+    fn encode_header<E: Encoder>(&self, packet_number_len: PacketNumberLen, encoder: &mut E) {
+        Short::encode_header(self, packet_number_len, encoder);
+    }
+
+    #[inline]
+    fn payload(&mut self) -> &mut Payload {
+        &mut self.payload
+    }
+}
@@ -6,7 +6,10 @@ use crate::{
     crypto::key::testing,
     inet::SocketAddress,
     packet::{
-        encoding::PacketEncoder, number::PacketNumberSpace, CleartextPacket, ProtectedPacket,
+        encoding::PacketEncoder,
+        number::PacketNumberSpace,
+        short::{Short, SpinBit},
+        CleartextPacket, KeyPhase, ProtectedPacket, QuicBit,
     },
     transport,
 };
@@ -37,6 +40,58 @@ fn round_trip() {
     });
 }
 
+//= https://www.rfc-editor.org/rfc/rfc9287#section-3.1
+//= type=test
+//# An endpoint that advertises the grease_quic_bit transport parameter
+//# MUST accept packets with the QUIC Bit set to a value of 0.
+#[test]
+fn quic_bit_round_trip_test() {
+    for quic_bit in [QuicBit::Zero, QuicBit::One] {
+        let mut buffer = vec![0; 1200];
+        let packet = Short {
+            quic_bit,
+            spin_bit: SpinBit::One,
+            key_phase: KeyPhase::Zero,
+            destination_connection_id: &[1u8, 2, 3, 4][..],
+            packet_number: PacketNumberSpace::ApplicationData.new_packet_number(Default::default()),
+            payload: &[1u8, 2, 3, 4, 5, 6, 7, 8][..],
+        };
+        let (_, encoder) = packet
+            .encode_packet(
+                &testing::Key::new(),
+                &testing::HeaderKey::new(),
+                PacketNumberSpace::ApplicationData.new_packet_number(Default::default()),
+                None,
+                EncoderBuffer::new(&mut buffer),
+            )
+            .unwrap();
+        let len = encoder.len();
+
+        let remote_address = SocketAddress::default();
+        let connection_info = ConnectionInfo::new(&remote_address);
+        let (packet, remaining) = ProtectedPacket::decode(
+            DecoderBufferMut::new(&mut buffer[..len]),
+            &connection_info,
+            &4,
+        )
+        .unwrap();
+        assert!(remaining.is_empty());
+        assert_eq!(packet.quic_bit(), quic_bit);
+
+        let packet = match decrypt_packet(packet).unwrap() {
+            CleartextPacket::Short(packet) => packet,
+            packet => panic!("unexpected packet {:?}", packet),
+        };
+        assert_eq!(packet.quic_bit, quic_bit);
+        assert_eq!(packet.spin_bit, SpinBit::One);
+        assert_eq!(packet.destination_connection_id(), &[1, 2, 3, 4]);
+        assert_eq!(
+            packet.payload.as_less_safe_slice(),
+            &[1, 2, 3, 4, 5, 6, 7, 8]
+        );
+    }
+}
+
 fn decrypt_packet(packet: ProtectedPacket) -> Result<CleartextPacket, transport::Error> {
     use ProtectedPacket::*;
     match packet {
@@ -2,13 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    event,
+    connection, event,
     event::{
         api::{Path, SocketAddress},
         IntoEvent,
     },
-    inet,
+    inet, io,
 };
+use core::fmt;
 
 #[derive(Debug)]
 #[non_exhaustive]
@@ -112,6 +113,100 @@ pub trait Validator: 'static + Send {
     fn on_migration_attempt(&mut self, attempt: &Attempt) -> Outcome;
 }
 
+/// Errors that can occur when an application migrates a connection to a new local address
+/// or opens and closes additional paths
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Only clients are able to initiate a connection migration
+    NotClient,
+    /// The handshake has not been confirmed yet
+    HandshakeNotConfirmed,
+    /// The peer does not support active connection migration
+    DisabledByPeer,
+    /// A migration to a different local address is already in progress
+    InProgress,
+    /// The connection has reached the maximum number of paths
+    PathLimitExceeded,
+    /// The peer has not issued any unused connection IDs
+    InsufficientConnectionIds,
+    /// The peer did not respond to path validation from the new local address
+    ValidationFailed,
+    /// The multipath extension was not negotiated with the peer
+    MultipathDisabled,
+    /// The connection doesn't have a path from the local address
+    PathNotFound,
+    /// The last path of the connection can't be closed
+    LastPath,
+    /// The IO provider can't send packets from the local address
+    UnsupportedLocalAddress,
+    /// The local address has a different port than the socket of the endpoint and the IO
+    /// provider can't bind sockets to new local addresses
+    LocalPortChanged,
+    /// The IO provider couldn't bind a socket to the local address
+    BindFailed,
+    /// The connection was closed
+    Connection(connection::Error),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotClient => write!(f, "Only clients can migrate a connection"),
+            Self::HandshakeNotConfirmed => write!(
+                f,
+                "The connection can't be migrated until the handshake is confirmed"
+            ),
+            Self::DisabledByPeer => write!(f, "The peer disabled active connection migration"),
+            Self::InProgress => write!(f, "A connection migration is already in progress"),
+            Self::PathLimitExceeded => write!(f, "The connection has too many paths"),
+            Self::InsufficientConnectionIds => write!(
+                f,
+                "The peer has not issued enough connection IDs to migrate the connection"
+            ),
+            Self::ValidationFailed => write!(f, "Path validation from the new address failed"),
+            Self::MultipathDisabled => {
+                write!(
+                    f,
+                    "The multipath extension was not negotiated with the peer"
+                )
+            }
+            Self::PathNotFound => write!(f, "The connection has no path from the local address"),
+            Self::LastPath => write!(f, "The last path of the connection can't be closed"),
+            Self::UnsupportedLocalAddress => write!(
+                f,
+                "The IO provider can't send packets from the local address"
+            ),
+            Self::LocalPortChanged => write!(
+                f,
+                "The local port can't be changed since packets are sent from the endpoint socket"
+            ),
+            Self::BindFailed => write!(f, "A socket couldn't be bound to the local address"),
+            Self::Connection(error) => error.fmt(f),
+        }
+    }
+}
+
+impl From<connection::Error> for Error {
+    #[inline]
+    fn from(error: connection::Error) -> Self {
+        Self::Connection(error)
+    }
+}
+
+impl From<io::bind::Error> for Error {
+    #[inline]
+    fn from(error: io::bind::Error) -> Self {
+        match error {
+            io::bind::Error::Unsupported => Self::UnsupportedLocalAddress,
+            io::bind::Error::AddressUnavailable => Self::BindFailed,
+        }
+    }
+}
+
 pub mod default {
     use super::*;
     use crate::path::remote_port_blocked;
@@ -16,6 +16,9 @@ use core::{
 use bolero_generator::*;
 
 pub mod migration;
+pub mod mtu;
+pub mod preferred_address;
+pub mod spin;
 
 //= https://www.rfc-editor.org/rfc/rfc9000#section-14
 //# QUIC MUST NOT be used if the network path cannot support a
@@ -91,6 +94,19 @@ pub trait Handle: 'static + Copy + Send + fmt::Debug {
     /// Returns the local address for the given handle
     fn local_address(&self) -> LocalAddress;
 
+    /// Returns a copy of the handle that sends from the given local address
+    ///
+    /// This is used by clients that migrate a connection to a new local address when the IO
+    /// provider can't bind a socket to it with [`crate::io::bind::Bind`]. Only the source IP of
+    /// the packets is changed, so the port of `local_address` is always the port of the socket
+    /// the handle sends from. `None` is returned if packets can't be sent from the local address
+    /// with this handle, which is the default.
+    #[inline]
+    fn with_local_address(&self, local_address: LocalAddress) -> Option<Self> {
+        let _ = local_address;
+        None
+    }
+
     /// Returns `true` if the two handles are equal from a network perspective
     ///
     /// This function is used to determine if a connection has migrated to another
@@ -228,6 +244,14 @@ impl Handle for Tuple {
         self.local_address
     }
 
+    #[inline]
+    fn with_local_address(&self, local_address: LocalAddress) -> Option<Self> {
+        Some(Self {
+            remote_address: self.remote_address,
+            local_address,
+        })
+    }
+
     #[inline]
     fn eq(&self, other: &Self) -> bool {
         PartialEq::eq(&self.local_address.unmap(), &other.local_address.unmap())
@@ -396,11 +420,31 @@ pub fn remote_port_throttled(port: u16) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use crate::path::{
-        remote_port_blocked, remote_port_throttled, BLOCKED_PORTS, MAX_BLOCKED_PORT,
-        MAX_THROTTLED_PORT, THROTTLED_PORTS,
+    use crate::{
+        inet::SocketAddressV4,
+        path::{
+            remote_port_blocked, remote_port_throttled, Handle, LocalAddress, RemoteAddress, Tuple,
+            BLOCKED_PORTS, MAX_BLOCKED_PORT, MAX_THROTTLED_PORT, THROTTLED_PORTS,
+        },
     };
 
+    #[test]
+    fn with_local_address() {
+        let remote_address: RemoteAddress = SocketAddressV4::new([192, 0, 2, 1], 443).into();
+        let local_address: LocalAddress = SocketAddressV4::new([198, 51, 100, 7], 4433).into();
+
+        // the local address isn't tracked by the handle so it can't be changed
+        assert!(remote_address.with_local_address(local_address).is_none());
+
+        let tuple = Tuple {
+            remote_address,
+            local_address: SocketAddressV4::new([127, 0, 0, 1], 4433).into(),
+        };
+        let tuple = tuple.with_local_address(local_address).unwrap();
+        assert_eq!(tuple.local_address, local_address);
+        assert_eq!(tuple.remote_address, remote_address);
+    }
+
     #[test]
     fn blocked_ports_is_sorted() {
         assert_eq!(Some(MAX_BLOCKED_PORT), BLOCKED_PORTS.iter().max().copied());
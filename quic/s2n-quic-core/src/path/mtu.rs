@@ -0,0 +1,466 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Configuration for Datagram Packetization Layer Path MTU Discovery (DPLPMTUD)
+//!
+//! Each path starts sending datagrams no larger than the base MTU and probes for larger sizes
+//! until the [`Search`] strategy has no more sizes worth probing. A [`Config`] is applied to
+//! connections with
+//! [`Limits::with_mtu_config`](crate::connection::limits::Limits::with_mtu_config).
+//!
+//! Unless otherwise noted, the sizes in a [`Config`] are maximum transmission units, which
+//! include the IP and UDP headers.
+
+use crate::{
+    path::{MaxMtu, IPV4_MIN_HEADER_LEN, MINIMUM_MTU, UDP_HEADER_LEN},
+    transport::parameters::ValidationError,
+};
+use core::{convert::TryInto, fmt, num::NonZeroU16, time::Duration};
+use s2n_codec::decoder_invariant;
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-14.3
+//# Endpoints SHOULD set the initial value of BASE_PLPMTU (Section 5.1 of
+//# [DPLPMTUD]) to be consistent with QUIC's smallest allowed maximum
+//# datagram size.
+
+//= https://www.rfc-editor.org/rfc/rfc8899#section-5.1.2
+//# When using IPv4, there is no currently equivalent size specified,
+//# and a default BASE_PLPMTU of 1200 bytes is RECOMMENDED.
+pub const BASE_PLPMTU: u16 = MINIMUM_MTU;
+
+//= https://www.rfc-editor.org/rfc/rfc8899#section-5.1.2
+//# The MAX_PROBES is the maximum value of the PROBE_COUNT
+//# counter (see Section 5.1.3).  MAX_PROBES represents the limit for
+//# the number of consecutive probe attempts of any size.  Search
+//# algorithms benefit from a MAX_PROBES value greater than 1 because
+//# this can provide robustness to isolated packet loss.  The default
+//# value of MAX_PROBES is 3.
+pub const MAX_PROBES: u8 = 3;
+
+/// The minimum length of the data field of a packet sent over an
+/// Ethernet is 1500 octets, thus the maximum length of an IP datagram
+/// sent over an Ethernet is 1500 octets.
+/// See https://www.rfc-editor.org/rfc/rfc894.txt
+pub const ETHERNET_MTU: u16 = 1500;
+
+/// If the next value to probe is within the PROBE_THRESHOLD bytes of
+/// the current Path MTU, probing will be considered complete.
+pub const PROBE_THRESHOLD: u16 = 20;
+
+/// When the black_hole_counter exceeds this threshold, on_black_hole_detected will be
+/// called to reduce the MTU to the BASE_PLPMTU. The black_hole_counter is incremented when
+/// a burst of consecutive packets is lost that starts with a packet that is:
+///      1) not an MTU probe
+///      2) larger than the BASE_PLPMTU
+///      3) sent after the largest MTU-sized acknowledged packet number
+/// This is a possible indication that the path cannot support the MTU that was previously confirmed.
+pub const BLACK_HOLE_THRESHOLD: u8 = 3;
+
+/// After a black hole has been detected, the mtu::Controller will wait this duration
+/// before probing for a larger MTU again.
+pub const BLACK_HOLE_COOL_OFF_DURATION: Duration = Duration::from_secs(60);
+
+//= https://www.rfc-editor.org/rfc/rfc8899#section-5.1.1
+//# The PMTU_RAISE_TIMER is configured to the period a
+//# sender will continue to use the current PLPMTU, after which it
+//# reenters the Search Phase.  This timer has a period of 600
+//# seconds, as recommended by PLPMTUD [RFC4821].
+pub const PMTU_RAISE_TIMER_DURATION: Duration = Duration::from_secs(600);
+
+/// The smallest MTU which carries a UDP payload of BASE_PLPMTU over IPv4
+const MIN_MTU: u16 = BASE_PLPMTU + UDP_HEADER_LEN + IPV4_MIN_HEADER_LEN;
+
+/// Path MTU discovery settings applied to each path of a connection
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    initial_mtu: u16,
+    base_mtu: u16,
+    max_mtu: MaxMtu,
+    max_probes: u8,
+    probe_threshold: u16,
+    black_hole_threshold: u8,
+    black_hole_cool_off_duration: Duration,
+    pmtu_raise_timer_duration: Duration,
+    search: &'static dyn Search,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<MaxMtu> for Config {
+    #[inline]
+    fn from(max_mtu: MaxMtu) -> Self {
+        Self {
+            max_mtu,
+            ..Self::new()
+        }
+    }
+}
+
+impl Config {
+    pub const fn new() -> Self {
+        Self {
+            initial_mtu: ETHERNET_MTU,
+            base_mtu: MIN_MTU,
+            // The max MTU is only limited by the IO provider by default
+            // Safety: u16::MAX is greater than zero
+            max_mtu: MaxMtu(unsafe { NonZeroU16::new_unchecked(u16::MAX) }),
+            max_probes: MAX_PROBES,
+            probe_threshold: PROBE_THRESHOLD,
+            black_hole_threshold: BLACK_HOLE_THRESHOLD,
+            black_hole_cool_off_duration: BLACK_HOLE_COOL_OFF_DURATION,
+            pmtu_raise_timer_duration: PMTU_RAISE_TIMER_DURATION,
+            search: &Binary,
+        }
+    }
+
+    /// Sets the first MTU probed on each path
+    ///
+    /// The value must be between the base MTU and the max MTU, and is further limited by the
+    /// max MTU of the IO provider. Defaults to 1500, the MTU of Ethernet.
+    pub fn with_initial_mtu(mut self, value: u16) -> Result<Self, ValidationError> {
+        decoder_invariant!(value >= MIN_MTU, "initial_mtu must be at least 1228");
+        self.initial_mtu = value;
+        Ok(self)
+    }
+
+    /// Sets the MTU each path uses before probing, and falls back to when a black hole
+    /// is detected
+    ///
+    /// The value must not exceed the initial MTU. Defaults to the smallest MTU that QUIC
+    /// allows, which is 1228 for IPv4 and 1248 for IPv6.
+    pub fn with_base_mtu(mut self, value: u16) -> Result<Self, ValidationError> {
+        decoder_invariant!(value >= MIN_MTU, "base_mtu must be at least 1228");
+        self.base_mtu = value;
+        Ok(self)
+    }
+
+    /// Sets the largest MTU probed on each path
+    ///
+    /// The value is also limited by the max MTU of the IO provider, which is the only limit
+    /// by default.
+    pub fn with_max_mtu(mut self, value: u16) -> Result<Self, ValidationError> {
+        self.max_mtu = value
+            .try_into()
+            .map_err(|_| ValidationError("max_mtu is below the minimum allowed MTU"))?;
+        Ok(self)
+    }
+
+    /// Sets the number of consecutive unacknowledged probes of a size after which a smaller
+    /// size is probed
+    ///
+    /// Defaults to 3.
+    pub fn with_max_probes(mut self, value: u8) -> Result<Self, ValidationError> {
+        decoder_invariant!(value > 0, "max_probes must be greater than 0");
+        self.max_probes = value;
+        Ok(self)
+    }
+
+    /// Sets the difference between the confirmed and next probed size below which
+    /// the search completes
+    ///
+    /// Defaults to 20 bytes.
+    pub fn with_probe_threshold(mut self, value: u16) -> Result<Self, ValidationError> {
+        decoder_invariant!(value > 0, "probe_threshold must be greater than 0");
+        self.probe_threshold = value;
+        Ok(self)
+    }
+
+    /// Sets the number of bursts of lost packets larger than the base MTU after which the
+    /// path is considered to be a black hole and falls back to the base MTU
+    ///
+    /// Defaults to 3.
+    pub fn with_black_hole_threshold(mut self, value: u8) -> Result<Self, ValidationError> {
+        self.black_hole_threshold = value;
+        Ok(self)
+    }
+
+    /// Sets the amount of time to wait after detecting a black hole before probing again
+    ///
+    /// Defaults to 60 seconds.
+    pub fn with_black_hole_cool_off_duration(
+        mut self,
+        value: Duration,
+    ) -> Result<Self, ValidationError> {
+        self.black_hole_cool_off_duration = value;
+        Ok(self)
+    }
+
+    /// Sets the amount of time to wait after a search completes before searching for a
+    /// larger MTU again
+    ///
+    /// Defaults to 600 seconds.
+    pub fn with_pmtu_raise_timer_duration(
+        mut self,
+        value: Duration,
+    ) -> Result<Self, ValidationError> {
+        self.pmtu_raise_timer_duration = value;
+        Ok(self)
+    }
+
+    /// Sets the strategy used to select the sizes to probe
+    ///
+    /// Defaults to [`Binary`].
+    pub fn with_search(mut self, search: &'static dyn Search) -> Result<Self, ValidationError> {
+        self.search = search;
+        Ok(self)
+    }
+
+    /// Checks that the base MTU doesn't exceed the initial MTU, and the initial MTU doesn't
+    /// exceed the max MTU
+    ///
+    /// The setters can be called in any order, so the sizes are only checked once the config
+    /// is applied.
+    pub(crate) fn validate(&self) -> Result<(), ValidationError> {
+        decoder_invariant!(
+            self.base_mtu <= self.initial_mtu,
+            "base_mtu must not exceed initial_mtu"
+        );
+        decoder_invariant!(
+            self.initial_mtu <= u16::from(self.max_mtu),
+            "initial_mtu must not exceed max_mtu"
+        );
+        Ok(())
+    }
+
+    // internal APIs
+
+    /// Limits the max MTU to the max MTU supported by the IO provider
+    #[doc(hidden)]
+    #[inline]
+    pub fn limit_max_mtu(mut self, max_mtu: MaxMtu) -> Self {
+        if u16::from(max_mtu) < u16::from(self.max_mtu) {
+            self.max_mtu = max_mtu;
+        }
+        self
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn initial_mtu(&self) -> u16 {
+        self.initial_mtu
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn base_mtu(&self) -> u16 {
+        self.base_mtu
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_mtu(&self) -> MaxMtu {
+        self.max_mtu
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_probes(&self) -> u8 {
+        self.max_probes
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn probe_threshold(&self) -> u16 {
+        self.probe_threshold
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn black_hole_threshold(&self) -> u8 {
+        self.black_hole_threshold
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn black_hole_cool_off_duration(&self) -> Duration {
+        self.black_hole_cool_off_duration
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn pmtu_raise_timer_duration(&self) -> Duration {
+        self.pmtu_raise_timer_duration
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn search(&self) -> &'static dyn Search {
+        self.search
+    }
+}
+
+/// The progress of a search, expressed as UDP payload sizes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Bounds {
+    /// The largest size the path is confirmed to support
+    pub plpmtu: u16,
+    /// The largest size the search may probe
+    pub max_probe_size: u16,
+    /// `true` if probes of `max_probe_size` were not acknowledged
+    pub max_probe_size_lost: bool,
+    /// The combined length of the IP and UDP headers, which converts the sizes to MTUs
+    pub header_len: u16,
+}
+
+impl Bounds {
+    #[doc(hidden)]
+    #[inline]
+    pub fn new(
+        plpmtu: u16,
+        max_probe_size: u16,
+        max_probe_size_lost: bool,
+        header_len: u16,
+    ) -> Self {
+        Self {
+            plpmtu,
+            max_probe_size,
+            max_probe_size_lost,
+            header_len,
+        }
+    }
+}
+
+/// Selects the sizes probed during path MTU discovery
+pub trait Search: 'static + Send + Sync + fmt::Debug {
+    /// Returns the UDP payload size to probe after the initial MTU
+    ///
+    /// The search completes when the returned size is within the probe threshold of
+    /// `bounds.plpmtu`. Sizes above `bounds.max_probe_size` are not probed.
+    fn next_probe_size(&self, bounds: &Bounds) -> u16;
+}
+
+/// Probes halfway between the confirmed size and the largest size that may be probed
+///
+/// This is the default strategy.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Binary;
+
+impl Search for Binary {
+    #[inline]
+    fn next_probe_size(&self, bounds: &Bounds) -> u16 {
+        //= https://www.rfc-editor.org/rfc/rfc8899#section-5.3.2
+        //# Implementations SHOULD select the set of probe packet sizes to
+        //# maximize the gain in PLPMTU from each search step.
+        bounds.plpmtu + (bounds.max_probe_size.saturating_sub(bounds.plpmtu) / 2)
+    }
+}
+
+/// Probes the smallest MTU in the list which is larger than the confirmed MTU
+///
+/// This is useful when the MTUs of the networks a path may cross are known in advance, for
+/// example `&Steps(&[1280, 1400, 1500, 9001])`. Sizes which were probed without being
+/// acknowledged are skipped.
+#[derive(Clone, Copy, Debug)]
+pub struct Steps(pub &'static [u16]);
+
+impl Search for Steps {
+    #[inline]
+    fn next_probe_size(&self, bounds: &Bounds) -> u16 {
+        self.0
+            .iter()
+            .map(|mtu| mtu.saturating_sub(bounds.header_len))
+            .filter(|&size| {
+                size > bounds.plpmtu
+                    && (size < bounds.max_probe_size
+                        || size == bounds.max_probe_size && !bounds.max_probe_size_lost)
+            })
+            .min()
+            .unwrap_or(bounds.plpmtu)
+    }
+}
+
+/// Only probes the initial MTU
+///
+/// The path uses the initial MTU if it is acknowledged and the base MTU otherwise.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Fixed;
+
+impl Search for Fixed {
+    #[inline]
+    fn next_probe_size(&self, bounds: &Bounds) -> u16 {
+        bounds.plpmtu
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER_LEN: u16 = UDP_HEADER_LEN + IPV4_MIN_HEADER_LEN;
+
+    #[test]
+    fn config_test() {
+        let config = Config::default();
+        assert_eq!(config.base_mtu() - HEADER_LEN, BASE_PLPMTU);
+        assert_eq!(config.initial_mtu(), ETHERNET_MTU);
+        assert_eq!(u16::from(config.max_mtu()), u16::MAX);
+
+        let config = config.limit_max_mtu(9001.try_into().unwrap());
+        assert_eq!(u16::from(config.max_mtu()), 9001);
+        let config = config.limit_max_mtu(u16::MAX.try_into().unwrap());
+        assert_eq!(u16::from(config.max_mtu()), 9001);
+
+        assert!(Config::new().with_base_mtu(MIN_MTU - 1).is_err());
+        assert!(Config::new().with_initial_mtu(MIN_MTU - 1).is_err());
+        assert!(Config::new().with_max_mtu(MIN_MTU - 1).is_err());
+        assert!(Config::new().with_max_probes(0).is_err());
+        assert!(Config::new().with_probe_threshold(0).is_err());
+    }
+
+    #[test]
+    fn validate_test() {
+        assert!(Config::new().validate().is_ok());
+
+        // the sizes are only checked together, so they can be set in any order
+        let config = Config::new()
+            .with_max_mtu(1400)
+            .unwrap()
+            .with_base_mtu(1300)
+            .unwrap();
+        assert!(config.validate().is_err());
+        let config = config.with_initial_mtu(1400).unwrap();
+        assert!(config.validate().is_ok());
+
+        let config = Config::new().with_base_mtu(1400).unwrap();
+        assert!(config.with_initial_mtu(1300).unwrap().validate().is_err());
+        assert!(config.with_initial_mtu(1400).unwrap().validate().is_ok());
+    }
+
+    #[test]
+    fn binary_test() {
+        let bounds = Bounds::new(1200, 1472, false, HEADER_LEN);
+        assert_eq!(Binary.next_probe_size(&bounds), 1336);
+
+        let bounds = Bounds::new(1472, 1472, false, HEADER_LEN);
+        assert_eq!(Binary.next_probe_size(&bounds), 1472);
+    }
+
+    #[test]
+    fn steps_test() {
+        let steps = Steps(&[9001, 1280, 1400, 1500]);
+
+        let bounds = Bounds::new(1200, 8973, false, HEADER_LEN);
+        assert_eq!(steps.next_probe_size(&bounds), 1280 - HEADER_LEN);
+
+        let bounds = Bounds::new(1472, 8973, false, HEADER_LEN);
+        assert_eq!(steps.next_probe_size(&bounds), 9001 - HEADER_LEN);
+
+        // a step which was not acknowledged is skipped
+        let bounds = Bounds::new(1472, 8973, true, HEADER_LEN);
+        assert_eq!(steps.next_probe_size(&bounds), 1472);
+
+        let bounds = Bounds::new(1252, 1472, true, HEADER_LEN);
+        assert_eq!(steps.next_probe_size(&bounds), 1400 - HEADER_LEN);
+    }
+
+    #[test]
+    fn fixed_test() {
+        let bounds = Bounds::new(1200, 1472, false, HEADER_LEN);
+        assert_eq!(Fixed.next_probe_size(&bounds), 1200);
+    }
+}
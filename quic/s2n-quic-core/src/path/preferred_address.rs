@@ -0,0 +1,159 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allows servers to advertise a preferred address to clients
+//!
+//! Clients that receive a preferred address validate the new path once the handshake is
+//! confirmed and migrate the connection to it. This can be used to move connections from a
+//! shared (e.g. anycast) address to an address that is unique to the server.
+
+use crate::{
+    event::{api::SocketAddress, IntoEvent},
+    inet::{self, SocketAddressV4, SocketAddressV6, Unspecified},
+};
+
+/// Information about the connection being offered a preferred address
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ConnectionInfo<'a> {
+    /// The address of the client initiating the connection
+    pub remote_address: SocketAddress<'a>,
+}
+
+impl<'a> ConnectionInfo<'a> {
+    #[doc(hidden)]
+    pub fn new(remote_address: &'a inet::SocketAddress) -> Self {
+        Self {
+            remote_address: remote_address.into_event(),
+        }
+    }
+}
+
+/// The addresses advertised to the client in the `preferred_address` transport parameter
+///
+/// Clients select the address matching the address family they are currently using
+/// to communicate with the server.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Addresses {
+    pub ipv4: Option<SocketAddressV4>,
+    pub ipv6: Option<SocketAddressV6>,
+}
+
+impl Addresses {
+    /// Sets the IPv4 address advertised to clients
+    #[must_use]
+    pub fn with_ipv4<A: Into<SocketAddressV4>>(mut self, address: A) -> Self {
+        self.ipv4 = Some(address.into()).filter(|address| !address.is_unspecified());
+        self
+    }
+
+    /// Sets the IPv6 address advertised to clients
+    #[must_use]
+    pub fn with_ipv6<A: Into<SocketAddressV6>>(mut self, address: A) -> Self {
+        self.ipv6 = Some(address.into()).filter(|address| !address.is_unspecified());
+        self
+    }
+
+    /// Returns `true` if no addresses are specified
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ipv4.is_none() && self.ipv6.is_none()
+    }
+
+    /// Returns the advertised address that matches the address family of `remote_address`
+    ///
+    /// IPv4-mapped IPv6 addresses select the IPv4 address, which is returned in the
+    /// mapped form so it can be used with the same socket.
+    #[inline]
+    pub fn select(&self, remote_address: &inet::SocketAddress) -> Option<inet::SocketAddress> {
+        match (remote_address, remote_address.unmap()) {
+            (inet::SocketAddress::IpV6(_), inet::SocketAddress::IpV4(_)) => {
+                self.ipv4.map(|address| address.to_ipv6_mapped().into())
+            }
+            (inet::SocketAddress::IpV4(_), _) => self.ipv4.map(Into::into),
+            (inet::SocketAddress::IpV6(_), _) => self.ipv6.map(Into::into),
+        }
+    }
+}
+
+impl From<inet::SocketAddress> for Addresses {
+    #[inline]
+    fn from(address: inet::SocketAddress) -> Self {
+        match address.unmap() {
+            inet::SocketAddress::IpV4(address) => Self::default().with_ipv4(address),
+            inet::SocketAddress::IpV6(address) => Self::default().with_ipv6(address),
+        }
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+impl From<std::net::SocketAddr> for Addresses {
+    #[inline]
+    fn from(address: std::net::SocketAddr) -> Self {
+        inet::SocketAddress::from(address).into()
+    }
+}
+
+/// Selects the preferred address advertised to each new connection
+pub trait Endpoint: 'static + Send {
+    /// Called by the server when accepting a new connection
+    ///
+    /// Returning `None` will not advertise a preferred address to the client.
+    fn on_connection(&mut self, info: &ConnectionInfo) -> Option<Addresses>;
+}
+
+impl Endpoint for Addresses {
+    #[inline]
+    fn on_connection(&mut self, _info: &ConnectionInfo) -> Option<Addresses> {
+        Some(*self).filter(|addresses| !addresses.is_empty())
+    }
+}
+
+/// Does not advertise a preferred address
+#[derive(Debug, Default)]
+pub struct Disabled;
+
+impl Endpoint for Disabled {
+    #[inline]
+    fn on_connection(&mut self, _info: &ConnectionInfo) -> Option<Addresses> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inet::{IpV4Address, IpV6Address};
+
+    #[test]
+    fn select_test() {
+        let ipv4 = SocketAddressV4::new(IpV4Address::new([192, 0, 2, 1]), 443);
+        let ipv6 = SocketAddressV6::new(IpV6Address::from([0x2001, 0xdb8, 0, 0, 0, 0, 0, 1]), 443);
+
+        let remote_v4: inet::SocketAddress =
+            SocketAddressV4::new(IpV4Address::new([198, 51, 100, 1]), 1234).into();
+        let remote_v6: inet::SocketAddress =
+            SocketAddressV6::new(IpV6Address::from([0x2001, 0xdb8, 0, 0, 0, 0, 0, 2]), 1234).into();
+        let remote_mapped: inet::SocketAddress = remote_v4.to_ipv6_mapped().into();
+
+        let addresses = Addresses::default().with_ipv4(ipv4).with_ipv6(ipv6);
+        assert_eq!(addresses.select(&remote_v4), Some(ipv4.into()));
+        assert_eq!(addresses.select(&remote_v6), Some(ipv6.into()));
+        assert_eq!(
+            addresses.select(&remote_mapped),
+            Some(ipv4.to_ipv6_mapped().into())
+        );
+
+        // only a single family is advertised
+        let addresses = Addresses::default().with_ipv4(ipv4);
+        assert_eq!(addresses.select(&remote_v6), None);
+
+        // unspecified addresses are ignored
+        let mut addresses = Addresses::default().with_ipv4(SocketAddressV4::default());
+        assert!(addresses.is_empty());
+        assert_eq!(
+            addresses.on_connection(&ConnectionInfo::new(&remote_v4)),
+            None
+        );
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The latency spin bit
+//!
+//! The spin bit of 1-RTT packets allows on-path observers to measure the round-trip time of a
+//! connection. A [`Config`] controls whether endpoints spin the bit, and is applied to connections
+//! with [`Limits::with_spin_config`](crate::connection::limits::Limits::with_spin_config).
+//!
+//! The [`Observer`] estimates the round-trip time from the spin bits of a stream of packets, such
+//! as captured traffic, in the same way an on-path observer would.
+
+use crate::{packet::short::SpinBit, random, transport::parameters::ValidationError};
+use core::time::Duration;
+use s2n_codec::decoder_invariant;
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-17.4
+//# Even when the spin bit is not disabled by
+//# the administrator, endpoints MUST disable their use of the spin bit
+//# for a random selection of at least one in every 16 network paths, or
+//# for one in every 16 connection IDs, in order to ensure that QUIC
+//# connections that disable the spin bit are commonly observed on the
+//# network.
+pub const MAX_DISABLE_RATIO: u8 = 16;
+
+/// Spin bit settings applied to each connection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Config {
+    enabled: bool,
+    disable_ratio: u8,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Config {
+    pub const fn new() -> Self {
+        Self {
+            enabled: false,
+            disable_ratio: MAX_DISABLE_RATIO,
+        }
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-17.4
+    //# Implementations MUST allow administrators
+    //# of clients and servers to disable the spin bit either globally or on
+    //# a per-connection basis.
+    /// Sets whether the spin bit is enabled
+    ///
+    /// The spin bit is disabled by default. Connections can be configured individually with a
+    /// [`Limiter`](crate::connection::limits::Limiter).
+    pub fn with_enabled(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.enabled = enabled;
+        Ok(self)
+    }
+
+    /// Sets the share of connections which randomly disable the spin bit even though it is
+    /// enabled, as one in every `value` connections
+    ///
+    /// The value must be between 1, which disables the spin bit on every connection, and 16.
+    /// Defaults to 16.
+    pub fn with_disable_ratio(mut self, value: u8) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            (1..=MAX_DISABLE_RATIO).contains(&value),
+            "disable_ratio must be between 1 and 16"
+        );
+        self.disable_ratio = value;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    #[inline]
+    pub fn disable_ratio(&self) -> u8 {
+        self.disable_ratio
+    }
+
+    /// Randomly selects whether a new connection spins the bit
+    #[doc(hidden)]
+    #[inline]
+    pub fn select(&self, random_generator: &mut dyn random::Generator) -> bool {
+        self.enabled
+            && random::gen_range_biased(random_generator, 1..=self.disable_ratio as usize) != 1
+    }
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-17.4
+//# On-path observers can measure the time between two
+//# spin bit toggle events to estimate the end-to-end RTT of a
+//# connection.
+/// Passively estimates the round-trip time of a connection from the spin bit
+///
+/// The observer should be given the 1-RTT packets sent in one direction of a single connection,
+/// in the order they were observed. The time between two consecutive changes of the spin bit is
+/// one round-trip time, as measured at the observation point.
+///
+/// Reordered packets produce spurious changes of the spin bit, which show up as short samples.
+/// Connections that disable the spin bit may set it to random values, which makes the estimate
+/// meaningless; see [QUIC-MANAGEABILITY](https://www.rfc-editor.org/rfc/rfc9312).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Observer {
+    /// The spin value of the last observed packet
+    spin_bit: Option<SpinBit>,
+    /// The time at which the spin value last changed
+    edge: Option<Duration>,
+    latest_rtt: Option<Duration>,
+    min_rtt: Option<Duration>,
+    smoothed_rtt: Option<Duration>,
+    sample_count: u64,
+}
+
+impl Observer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Called with the first byte of each datagram that was observed
+    ///
+    /// `timestamp` is the time the datagram was observed, relative to a fixed point such as the
+    /// start of a capture. Datagrams which don't start with a short header packet are ignored.
+    /// Returns the new RTT sample, if any.
+    #[inline]
+    pub fn on_datagram(&mut self, datagram: &[u8], timestamp: Duration) -> Option<Duration> {
+        let tag = *datagram.first()?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-17.3.1
+        //# Header Form:  The most significant bit (0x80) of byte 0 is set to 0
+        //#    for the short header.
+        if tag & 0x80 != 0 {
+            return None;
+        }
+
+        self.on_packet(SpinBit::from_tag(tag), timestamp)
+    }
+
+    /// Called with the spin bit of each 1-RTT packet that was observed
+    ///
+    /// Returns the new RTT sample, if any.
+    pub fn on_packet(&mut self, spin_bit: SpinBit, timestamp: Duration) -> Option<Duration> {
+        let previous = self.spin_bit.replace(spin_bit)?;
+
+        if previous == spin_bit {
+            return None;
+        }
+
+        let edge = self.edge.replace(timestamp)?;
+        let rtt = timestamp.checked_sub(edge)?;
+
+        self.sample_count += 1;
+        self.latest_rtt = Some(rtt);
+        self.min_rtt = Some(self.min_rtt.map_or(rtt, |min_rtt| min_rtt.min(rtt)));
+        // Use the same weight as the RTT estimator of the endpoints so the values are comparable
+        self.smoothed_rtt = Some(
+            self.smoothed_rtt
+                .map_or(rtt, |smoothed_rtt| (smoothed_rtt * 7 + rtt) / 8),
+        );
+
+        Some(rtt)
+    }
+
+    /// Returns the most recent RTT sample
+    #[inline]
+    pub fn latest_rtt(&self) -> Option<Duration> {
+        self.latest_rtt
+    }
+
+    /// Returns the smallest RTT sample
+    #[inline]
+    pub fn min_rtt(&self) -> Option<Duration> {
+        self.min_rtt
+    }
+
+    /// Returns the exponentially weighted moving average of the RTT samples
+    #[inline]
+    pub fn smoothed_rtt(&self) -> Option<Duration> {
+        self.smoothed_rtt
+    }
+
+    /// Returns the number of RTT samples
+    #[inline]
+    pub fn sample_count(&self) -> u64 {
+        self.sample_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_test() {
+        assert!(!Config::default().is_enabled());
+        assert!(Config::new().with_disable_ratio(0).is_err());
+        assert!(Config::new().with_disable_ratio(17).is_err());
+
+        let mut random = random::testing::Generator(123);
+
+        // the spin bit is never selected when it is disabled
+        let config = Config::new();
+        assert!((0..100).all(|_| !config.select(&mut random)));
+
+        // a ratio of 1 disables the spin bit on every connection
+        let config = Config::new()
+            .with_enabled(true)
+            .unwrap()
+            .with_disable_ratio(1)
+            .unwrap();
+        assert!((0..100).all(|_| !config.select(&mut random)));
+
+        let config = Config::new()
+            .with_enabled(true)
+            .unwrap()
+            .with_disable_ratio(2)
+            .unwrap();
+        let enabled = (0..1000).filter(|_| config.select(&mut random)).count();
+        assert!((400..600).contains(&enabled), "{}", enabled);
+    }
+
+    #[test]
+    fn observer_test() {
+        let mut observer = Observer::new();
+        let ms = Duration::from_millis;
+
+        // long header packets are ignored
+        assert_eq!(observer.on_datagram(&[0xc0 | 0x20], ms(0)), None);
+        assert_eq!(observer.on_datagram(&[], ms(0)), None);
+
+        // the first change only marks the start of a period
+        assert_eq!(observer.on_datagram(&[0x40], ms(0)), None);
+        assert_eq!(observer.on_datagram(&[0x40 | 0x20], ms(5)), None);
+        assert_eq!(observer.on_datagram(&[0x40 | 0x20], ms(10)), None);
+
+        assert_eq!(observer.on_datagram(&[0x40], ms(45)), Some(ms(40)));
+        assert_eq!(observer.on_datagram(&[0x40], ms(50)), None);
+        assert_eq!(observer.on_datagram(&[0x40 | 0x20], ms(65)), Some(ms(20)));
+
+        assert_eq!(observer.latest_rtt(), Some(ms(20)));
+        assert_eq!(observer.min_rtt(), Some(ms(20)));
+        assert_eq!(observer.smoothed_rtt(), Some((ms(40) * 7 + ms(20)) / 8));
+        assert_eq!(observer.sample_count(), 2);
+    }
+}
@@ -61,6 +61,13 @@ const HEADROOM: Ratio<u64> = Ratio::new_raw(85, 100);
 //# that follow an "ACK every other packet" delayed-ACK policy: 4 * SMSS.
 const MIN_PIPE_CWND_PACKETS: u16 = 4;
 
+/// The number of acknowledgements requested per congestion window when BBR
+/// reduces the acknowledgement rate of the peer
+const ACKS_PER_CWND: u32 = 4;
+
+/// The largest ack-eliciting threshold BBR will request from the peer
+const MAX_ACK_ELICITING_THRESHOLD: u32 = 10;
+
 //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#4.1.1
 //# The following state transition diagram summarizes the flow of control and the relationship between the different states:
 //#
@@ -533,6 +540,23 @@ impl CongestionController for BbrCongestionController {
     fn send_quantum(&self) -> Option<usize> {
         Some(self.pacer.send_quantum())
     }
+
+    #[inline]
+    fn ack_eliciting_threshold(&self) -> Option<u64> {
+        // Delivery rate samples need to be timely while BBR is probing for bandwidth, so
+        // the peer's default acknowledgement behavior is requested outside of ProbeBW_CRUISE
+        if !self.state.is_probing_bw_cruise() {
+            return None;
+        }
+
+        // Otherwise request an acknowledgement around 4 times per congestion window, which
+        // reduces the acknowledgement processing overhead on both endpoints without
+        // starving the bandwidth estimator
+        let cwnd_packets = self.cwnd / self.max_datagram_size as u32;
+        let threshold = (cwnd_packets / ACKS_PER_CWND).clamp(1, MAX_ACK_ELICITING_THRESHOLD);
+
+        Some(threshold as u64)
+    }
 }
 
 impl BbrCongestionController {
@@ -549,6 +549,33 @@ fn bound_cwnd_for_model() {
     assert_eq!(4800, bbr.bound_cwnd_for_model());
 }
 
+#[test]
+fn ack_eliciting_threshold() {
+    let mut bbr = BbrCongestionController::new(MINIMUM_MTU);
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+
+    // the default threshold is requested while in startup
+    assert_eq!(None, bbr.ack_eliciting_threshold());
+
+    enter_probe_bw_state(&mut bbr, CyclePhase::Up, &mut publisher);
+    assert_eq!(None, bbr.ack_eliciting_threshold());
+
+    enter_probe_bw_state(&mut bbr, CyclePhase::Cruise, &mut publisher);
+
+    // 20 packets / 4 acks per cwnd = 5
+    bbr.cwnd = 20 * MINIMUM_MTU as u32;
+    assert_eq!(Some(5), bbr.ack_eliciting_threshold());
+
+    // the threshold is capped at 10
+    bbr.cwnd = 1000 * MINIMUM_MTU as u32;
+    assert_eq!(Some(10), bbr.ack_eliciting_threshold());
+
+    // the threshold is never less than 1
+    bbr.cwnd = 2 * MINIMUM_MTU as u32;
+    assert_eq!(Some(1), bbr.ack_eliciting_threshold());
+}
+
 //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#4.6.4.6
 //= type=test
 //#     if (BBR.filled_pipe)
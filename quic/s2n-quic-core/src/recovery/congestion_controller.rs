@@ -245,6 +245,15 @@ pub trait CongestionController: 'static + Clone + Send + Debug + private::Sealed
     fn send_quantum(&self) -> Option<usize> {
         None
     }
+
+    /// The number of ack-eliciting packets the peer may receive before sending an acknowledgement.
+    ///
+    /// If the peer supports the ACK frequency extension, the value is requested from the peer
+    /// with an ACK_FREQUENCY frame. If the value is `None`, the congestion controller does not
+    /// influence the acknowledgement rate and the peer's default behavior is requested.
+    fn ack_eliciting_threshold(&self) -> Option<u64> {
+        None
+    }
 }
 
 // Prevent implementation of the `CongestionController` trait if the
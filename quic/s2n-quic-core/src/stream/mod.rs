@@ -7,11 +7,15 @@ pub mod iter;
 pub mod limits;
 #[cfg(feature = "alloc")]
 pub mod ops;
+mod priority;
+pub mod scheduler;
 mod type_;
 
 pub use error::*;
 pub use id::*;
 pub use limits::Limits;
+pub use priority::Priority;
+pub use scheduler::Scheduler;
 pub use type_::*;
 
 #[cfg(any(test, feature = "testing"))]
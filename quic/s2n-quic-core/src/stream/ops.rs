@@ -66,6 +66,12 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Sets the transmission priority of the tx stream
+    pub fn with_priority(&mut self, priority: stream::Priority) -> &mut Self {
+        self.tx_mut().priority = Some(priority);
+        self
+    }
+
     /// Requests data on the rx stream to be received into the provided slice of chunks
     pub fn receive(&mut self, chunks: &'a mut [bytes::Bytes]) -> &mut Self {
         self.rx_mut().chunks = Some(chunks);
@@ -196,6 +202,9 @@ pub mod tx {
         /// Marks the tx stream as detached, which makes the stream make progress, regardless of
         /// application observations.
         pub detached: bool,
+
+        /// Optionally updates the transmission priority of the stream
+        pub priority: Option<stream::Priority>,
     }
 
     /// The result of a tx request
@@ -499,6 +508,7 @@ mod tests {
             .finish()
             .flush()
             .reset(application::Error::new(1).unwrap())
+            .with_priority(stream::Priority::new(0, true).unwrap())
             .receive(&mut receive_chunks)
             .with_watermark(5, 10)
             .stop_sending(application::Error::new(2).unwrap());
@@ -512,6 +522,7 @@ mod tests {
                     flush: true,
                     reset: Some(reset),
                     detached: false,
+                    priority: Some(priority),
                 }),
                 rx: Some(rx::Request {
                     chunks: Some(rx_chunks),
@@ -521,6 +532,7 @@ mod tests {
                     detached: false,
                 })
             } if reset == application::Error::new(1).unwrap()
+              && priority == stream::Priority::new(0, true).unwrap()
               && stop_sending == application::Error::new(2).unwrap()
               && tx_chunks.len() == 1
               && rx_chunks.len() == 2
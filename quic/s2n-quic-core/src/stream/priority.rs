@@ -0,0 +1,87 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::transport::parameters::ValidationError;
+
+/// The transmission priority of a stream
+///
+/// The parameters follow the model of the
+/// [Extensible Prioritization Scheme for HTTP](https://www.rfc-editor.org/rfc/rfc9218).
+/// The [`Scheduler`](crate::stream::scheduler::Scheduler) configured on the connection
+/// decides how the priorities of multiple streams are compared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Priority {
+    urgency: u8,
+    incremental: bool,
+}
+
+impl Default for Priority {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Priority {
+    /// The most urgent value that can be assigned to a stream
+    pub const MAX_URGENCY: u8 = 0;
+
+    /// The least urgent value that can be assigned to a stream
+    pub const MIN_URGENCY: u8 = 7;
+
+    /// The priority assigned to streams that haven't been prioritized by the application
+    ///
+    /// Streams default to an urgency of `3` and are not incremental.
+    pub const DEFAULT: Self = Self {
+        urgency: 3,
+        incremental: false,
+    };
+
+    /// Creates a new priority
+    ///
+    /// `urgency` ranges from [`Self::MAX_URGENCY`] (`0`) to [`Self::MIN_URGENCY`] (`7`), where
+    /// streams with lower values are transmitted first. Incremental streams with the same urgency
+    /// share the available capacity with each other, while non-incremental streams are
+    /// transmitted one at a time.
+    #[inline]
+    pub const fn new(urgency: u8, incremental: bool) -> Result<Self, ValidationError> {
+        if urgency > Self::MIN_URGENCY {
+            return Err(ValidationError("urgency must be in the range 0..=7"));
+        }
+
+        Ok(Self {
+            urgency,
+            incremental,
+        })
+    }
+
+    /// Returns the urgency of the stream
+    #[inline]
+    pub const fn urgency(&self) -> u8 {
+        self.urgency
+    }
+
+    /// Returns `true` if the stream's data can be interleaved with other streams
+    #[inline]
+    pub const fn is_incremental(&self) -> bool {
+        self.incremental
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn priority_validation_test() {
+        for urgency in Priority::MAX_URGENCY..=Priority::MIN_URGENCY {
+            let priority = Priority::new(urgency, true).unwrap();
+            assert_eq!(priority.urgency(), urgency);
+            assert!(priority.is_incremental());
+        }
+
+        assert!(Priority::new(Priority::MIN_URGENCY + 1, false).is_err());
+        assert_eq!(Priority::default().urgency(), 3);
+        assert!(!Priority::default().is_incremental());
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Policies for ordering the transmission of stream data
+//!
+//! Each time a packet is assembled, streams with pending data are visited in order and given
+//! the opportunity to fill the remaining space. A [`Scheduler`] maps the [`Priority`] of each
+//! stream to a [`Queue`], which determines its position in that order.
+//!
+//! Schedulers are configured with
+//! [`Limits::with_stream_scheduler`](crate::connection::limits::Limits::with_stream_scheduler).
+
+use crate::stream::Priority;
+use core::fmt;
+
+/// The number of queues available to schedulers
+pub const QUEUE_COUNT: usize = 8;
+
+/// Determines the order in which streams transmit data
+pub trait Scheduler: 'static + Send + Sync + fmt::Debug {
+    /// Returns the queue for a stream with the given priority
+    fn queue(&self, priority: Priority) -> Queue;
+}
+
+/// The position of a stream in the transmission order
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Queue {
+    index: u8,
+    round_robin: bool,
+}
+
+impl Queue {
+    /// Creates a new queue
+    ///
+    /// Streams in queues with a lower `index` are always transmitted before streams in
+    /// queues with a higher `index`. Indices greater than or equal to [`QUEUE_COUNT`] are
+    /// clamped to the last queue.
+    ///
+    /// Streams in `round_robin` queues are moved behind the other streams in the queue after
+    /// filling a packet. Otherwise a stream keeps its position until it has no more data to
+    /// transmit.
+    #[inline]
+    pub const fn new(index: u8, round_robin: bool) -> Self {
+        let index = if index as usize >= QUEUE_COUNT {
+            (QUEUE_COUNT - 1) as u8
+        } else {
+            index
+        };
+
+        Self { index, round_robin }
+    }
+
+    /// Returns the index of the queue
+    #[inline]
+    pub const fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    /// Returns `true` if streams in the queue take turns filling packets
+    #[inline]
+    pub const fn is_round_robin(&self) -> bool {
+        self.round_robin
+    }
+}
+
+/// Transmits streams in the order they became ready, ignoring their priorities
+///
+/// Each stream transmits all of its pending data before the next stream is visited.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Fifo;
+
+impl Scheduler for Fifo {
+    #[inline]
+    fn queue(&self, _priority: Priority) -> Queue {
+        Queue::new(0, false)
+    }
+}
+
+/// Streams take turns filling packets, ignoring their priorities
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RoundRobin;
+
+impl Scheduler for RoundRobin {
+    #[inline]
+    fn queue(&self, _priority: Priority) -> Queue {
+        Queue::new(0, true)
+    }
+}
+
+/// Transmits streams in order of their urgency
+///
+/// Streams with a lower urgency value are always transmitted first. Incremental streams with
+/// the same urgency take turns filling packets, while non-incremental streams are transmitted
+/// one at a time.
+///
+/// This is the default scheduler.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StrictPriority;
+
+impl Scheduler for StrictPriority {
+    #[inline]
+    fn queue(&self, priority: Priority) -> Queue {
+        Queue::new(priority.urgency(), priority.is_incremental())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_test() {
+        let priorities = [
+            Priority::default(),
+            Priority::new(0, true).unwrap(),
+            Priority::new(7, false).unwrap(),
+        ];
+
+        for priority in priorities {
+            assert_eq!(Fifo.queue(priority), Queue::new(0, false));
+            assert_eq!(RoundRobin.queue(priority), Queue::new(0, true));
+
+            let queue = StrictPriority.queue(priority);
+            assert_eq!(queue.index(), priority.urgency() as usize);
+            assert_eq!(queue.is_round_robin(), priority.is_incremental());
+        }
+
+        assert_eq!(Queue::new(u8::MAX, false).index(), QUEUE_COUNT - 1);
+    }
+}
@@ -1,11 +1,15 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "alloc")]
+use crate::application::ServerName;
 use crate::{
     connection,
     event::{api::SocketAddress, IntoEvent},
     inet, random,
 };
+#[cfg(feature = "alloc")]
+use bytes::Bytes;
 
 #[non_exhaustive]
 pub struct Context<'a> {
@@ -58,6 +62,69 @@ pub trait Format: 'static + Send {
         context: &mut Context<'_>,
         token: &[u8],
     ) -> Option<connection::InitialId>;
+
+    /// Return `Some` if the token was delivered in a NEW_TOKEN frame and is valid.
+    /// If the token is invalid, return None.
+    /// Callers should detect duplicate tokens and treat them as invalid.
+    fn validate_new_token(&mut self, context: &mut Context<'_>, token: &[u8]) -> Option<()> {
+        let _ = (context, token);
+        None
+    }
+
+    /// Return the source of the token, without validating it.
+    ///
+    /// Tokens from Retry packets are validated with `validate_token` and Initial packets
+    /// carrying invalid tokens are discarded. Tokens from NEW_TOKEN frames are validated with
+    /// `validate_new_token` and the connection proceeds without a validated address if the token
+    /// is invalid.
+    fn token_source(&self, token: &[u8]) -> Source {
+        let _ = token;
+        Source::RetryPacket
+    }
+}
+
+/// Stores the address validation tokens issued to a client in NEW_TOKEN frames, keyed by the
+/// server name
+///
+#[cfg(feature = "alloc")]
+pub trait Store: 'static + Send + Sync {
+    /// Called when the server identified by `server_name` issues a new token
+    fn on_new_token(&self, server_name: &ServerName, token: &[u8]);
+
+    /// Removes and returns a token to validate the address of the client on a new connection
+    /// to `server_name`, if any
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+    //# A client SHOULD NOT reuse a token from a NEW_TOKEN frame for
+    //# different connection attempts.
+    fn take(&self, server_name: &ServerName) -> Option<Bytes>;
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Store + ?Sized> Store for alloc::sync::Arc<S> {
+    #[inline]
+    fn on_new_token(&self, server_name: &ServerName, token: &[u8]) {
+        (**self).on_new_token(server_name, token)
+    }
+
+    #[inline]
+    fn take(&self, server_name: &ServerName) -> Option<Bytes> {
+        (**self).take(server_name)
+    }
+}
+
+/// Does not store any tokens
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Disabled;
+
+#[cfg(feature = "alloc")]
+impl Store for Disabled {
+    #[inline]
+    fn on_new_token(&self, _server_name: &ServerName, _token: &[u8]) {}
+
+    #[inline]
+    fn take(&self, _server_name: &ServerName) -> Option<Bytes> {
+        None
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
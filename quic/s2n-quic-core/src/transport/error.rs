@@ -317,6 +317,11 @@ impl_errors! {
     /// confidentiality or integrity limit for the AEAD algorithm used by
     /// the given connection.
     AEAD_LIMIT_REACHED = 0xf.with_frame_type(UNKNOWN_FRAME_TYPE),
+
+    // Defined in https://www.rfc-editor.org/rfc/rfc9368#section-10.2
+    /// An endpoint detected an error while negotiating
+    /// the version of the connection.
+    VERSION_NEGOTIATION_ERROR = 0x11.with_frame_type(UNKNOWN_FRAME_TYPE),
 }
 
 //= https://www.rfc-editor.org/rfc/rfc9000#section-20.1
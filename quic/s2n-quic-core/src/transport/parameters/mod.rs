@@ -5,9 +5,10 @@ use crate::{
     ack, connection, endpoint, event,
     event::IntoEvent,
     inet::{SocketAddressV4, SocketAddressV6, Unspecified},
-    stateless_reset,
+    random, stateless_reset,
     stream::{StreamId, StreamType},
     varint::VarInt,
+    version,
 };
 use core::{
     convert::{TryFrom, TryInto},
@@ -127,6 +128,49 @@ impl<
     }
 }
 
+impl ZeroRttParameters {
+    /// Validates the parameters provided by the server in a resumed connection against the
+    /// values remembered by the client
+    pub fn validate_resumption(&self, remembered: &Self) -> Result<(), crate::transport::Error> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4.1
+        //# If 0-RTT data is accepted by the server, the server MUST NOT reduce
+        //# any limits or alter any values that might be violated by the client
+        //# with its 0-RTT data.
+        macro_rules! check {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    if self.$field < remembered.$field {
+                        return Err(crate::transport::Error::PROTOCOL_VIOLATION.with_reason(
+                            concat!(stringify!($field), " was reduced after accepting 0-RTT"),
+                        ));
+                    }
+                )*
+            };
+        }
+
+        check!(
+            active_connection_id_limit,
+            initial_max_data,
+            initial_max_stream_data_bidi_local,
+            initial_max_stream_data_bidi_remote,
+            initial_max_stream_data_uni,
+            initial_max_streams_bidi,
+            initial_max_streams_uni,
+        );
+
+        //= https://www.rfc-editor.org/rfc/rfc9221#section-3
+        //# If a client stores the value of the max_datagram_frame_size
+        //# transport parameter with their 0-RTT state, they MUST validate that
+        //# the new value of the max_datagram_frame_size transport parameter sent
+        //# by the server in the handshake is greater than or equal to the stored
+        //# value; if not, the client MUST terminate the connection with error
+        //# PROTOCOL_VIOLATION.
+        check!(max_datagram_frame_size);
+
+        Ok(())
+    }
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-18
 //# The extension_data field of the quic_transport_parameters extension
 //# defined in [QUIC-TLS] contains the QUIC transport parameters.  They
@@ -215,7 +259,7 @@ where
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
-pub struct ValidationError(&'static str);
+pub struct ValidationError(pub(crate) &'static str);
 
 const MAX_ENCODABLE_VALUE: ValidationError =
     ValidationError("provided value exceeds maximum encodable value");
@@ -851,6 +895,213 @@ impl TransportParameterValidator for MaxAckDelay {
     }
 }
 
+// min_ack_delay (0xff04de1b): The minimum amount of time, in microseconds, the
+// endpoint is able to delay sending an acknowledgement. Sending this transport
+// parameter indicates support for the ACK frequency extension, which allows the peer
+// to send ACK_FREQUENCY and IMMEDIATE_ACK frames.
+//
+// See https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency#section-3
+
+optional_transport_parameter!(MinAckDelay);
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
+pub struct MinAckDelay(VarInt);
+
+impl MinAckDelay {
+    /// Converts the value into a `core::time::Duration`
+    pub const fn as_duration(self) -> Duration {
+        Duration::from_micros(self.0.as_u64())
+    }
+}
+
+impl TransportParameter for MinAckDelay {
+    type CodecValue = Self;
+
+    const ID: TransportParameterId = TransportParameterId::from_u32(0xff04_de1b);
+
+    fn from_codec_value(value: Self) -> Self {
+        value
+    }
+
+    fn try_into_codec_value(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn default_value() -> Self {
+        unimplemented!("MinAckDelay is an optional transport parameter, so the default is None")
+    }
+}
+
+impl TransportParameterValidator for MinAckDelay {
+    fn validate(self) -> Result<Self, DecoderError> {
+        // The min_ack_delay can't exceed the largest valid max_ack_delay of 2^14 milliseconds.
+        // Validating it against the max_ack_delay sent by the peer is done once all of the
+        // transport parameters have been decoded.
+        decoder_invariant!(
+            *self.0 <= 2u64.pow(14) * 1000,
+            "min_ack_delay cannot be greater than 2^14 milliseconds"
+        );
+        Ok(self)
+    }
+}
+
+decoder_value!(
+    impl<'a> MinAckDelay {
+        fn decode(buffer: Buffer) -> Result<Self> {
+            let (value, buffer) = buffer.decode()?;
+            Ok((Self(value), buffer))
+        }
+    }
+);
+
+impl EncoderValue for MinAckDelay {
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&self.0);
+    }
+}
+
+impl TryFrom<Duration> for MinAckDelay {
+    type Error = ValidationError;
+
+    fn try_from(value: Duration) -> Result<Self, Self::Error> {
+        let value: VarInt = value.as_micros().try_into()?;
+        Self(value).validate().map_err(|err| err.into())
+    }
+}
+
+impl From<MinAckDelay> for Duration {
+    fn from(value: MinAckDelay) -> Self {
+        value.as_duration()
+    }
+}
+
+// enable_multipath (0x0f739bbc1b666d04): Indicates support for the multipath extension,
+// which allows the endpoints to send and receive application data on several paths at
+// the same time. A value of 1 indicates all paths share the application data packet
+// number space and a value of 2 indicates each path uses its own packet number space.
+// Omitting the parameter, or sending a value of 0, disables the extension.
+//
+// See https://datatracker.ietf.org/doc/html/draft-ietf-quic-multipath-03
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EnableMultipath {
+    #[default]
+    Disabled,
+    /// All paths share the application data packet number space
+    SinglePacketNumberSpace,
+    /// Each path uses its own packet number space
+    MultiplePacketNumberSpaces,
+}
+
+impl EnableMultipath {
+    /// Returns `true` if the multipath extension is supported
+    #[inline]
+    pub fn is_enabled(self) -> bool {
+        !matches!(self, Self::Disabled)
+    }
+}
+
+impl TransportParameter for EnableMultipath {
+    type CodecValue = Self;
+
+    // The identifier doesn't fit into a `u32` so it's constructed from the raw value,
+    // which is less than `VarInt::MAX`
+    const ID: TransportParameterId = unsafe { VarInt::new_unchecked(0x0f73_9bbc_1b66_6d04) };
+
+    fn from_codec_value(value: Self) -> Self {
+        value
+    }
+
+    fn try_into_codec_value(&self) -> Option<&Self> {
+        if self.is_enabled() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn default_value() -> Self {
+        Self::Disabled
+    }
+}
+
+impl TransportParameterValidator for EnableMultipath {}
+
+decoder_value!(
+    impl<'a> EnableMultipath {
+        fn decode(buffer: Buffer) -> Result<Self> {
+            let (value, buffer) = buffer.decode::<VarInt>()?;
+            let value = match value.as_u64() {
+                0 => Self::Disabled,
+                1 => Self::SinglePacketNumberSpace,
+                2 => Self::MultiplePacketNumberSpaces,
+                _ => {
+                    return Err(DecoderError::InvariantViolation(
+                        "invalid enable_multipath value",
+                    ))
+                }
+            };
+            Ok((value, buffer))
+        }
+    }
+);
+
+impl EncoderValue for EnableMultipath {
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        let value: u8 = match self {
+            Self::Disabled => 0,
+            Self::SinglePacketNumberSpace => 1,
+            Self::MultiplePacketNumberSpaces => 2,
+        };
+        buffer.encode(&VarInt::from_u8(value));
+    }
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9287#section-3
+//# grease_quic_bit (0x2ab2):  This transport parameter has a zero-length
+//#    value.  Its presence in the transport parameters indicates that the
+//#    endpoint is willing to receive QUIC packets containing any value
+//#    for the fixed bit.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GreaseQuicBit {
+    #[default]
+    Disabled,
+    Enabled,
+}
+
+impl GreaseQuicBit {
+    /// Returns `true` if the endpoint accepts packets with a cleared QUIC bit
+    #[inline]
+    pub fn is_enabled(self) -> bool {
+        matches!(self, Self::Enabled)
+    }
+}
+
+impl TransportParameter for GreaseQuicBit {
+    type CodecValue = ();
+
+    const ID: TransportParameterId = TransportParameterId::from_u16(0x2ab2);
+
+    fn from_codec_value(_value: ()) -> Self {
+        Self::Enabled
+    }
+
+    fn try_into_codec_value(&self) -> Option<&()> {
+        if self.is_enabled() {
+            Some(&())
+        } else {
+            None
+        }
+    }
+
+    fn default_value() -> Self {
+        Self::Disabled
+    }
+}
+
+impl TransportParameterValidator for GreaseQuicBit {}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
 //# disable_active_migration (0x0c): The disable active migration
 //#    transport parameter is included if the endpoint does not support
@@ -1063,6 +1314,233 @@ impl From<connection::id::LocalId> for InitialSourceConnectionId {
 connection_id_parameter!(RetrySourceConnectionId, LocalId, 0x10);
 optional_transport_parameter!(RetrySourceConnectionId);
 
+//= https://www.rfc-editor.org/rfc/rfc9368#section-3
+//# Version Information {
+//#   Chosen Version (32),
+//#   Available Versions (32) ...,
+//# }
+
+optional_transport_parameter!(VersionInformation);
+
+/// The versions reported by an endpoint for compatible version negotiation
+///
+/// Only the versions supported by this implementation are retained from the
+/// Available Versions field when decoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VersionInformation {
+    chosen_version: u32,
+    available_versions: [u32; version::SUPPORTED.len()],
+    available_versions_len: u8,
+}
+
+impl VersionInformation {
+    /// Creates a version information parameter for the chosen version
+    ///
+    /// Any versions in `available_versions` not supported by the implementation are omitted.
+    pub fn new(chosen_version: u32, available_versions: &[u32]) -> Self {
+        let mut value = Self {
+            chosen_version,
+            available_versions: [0; version::SUPPORTED.len()],
+            available_versions_len: 0,
+        };
+
+        for version in available_versions.iter().copied() {
+            value.push_available_version(version);
+        }
+
+        value
+    }
+
+    /// The version the sender has chosen to use for the connection
+    #[inline]
+    pub fn chosen_version(&self) -> u32 {
+        self.chosen_version
+    }
+
+    /// The versions the sender supports, in its order of preference
+    #[inline]
+    pub fn available_versions(&self) -> &[u32] {
+        &self.available_versions[..self.available_versions_len as usize]
+    }
+
+    /// Finds the parameter in encoded transport parameters without decoding the other
+    /// parameters
+    ///
+    /// `None` is returned if the parameter is missing or malformed.
+    pub fn find(transport_parameters: &[u8]) -> Option<Self> {
+        let mut buffer = DecoderBuffer::new(transport_parameters);
+
+        while !buffer.is_empty() {
+            let (id, remaining) = buffer.decode::<TransportParameterId>().ok()?;
+
+            if id == Self::ID {
+                let (value, _) = remaining
+                    .decode_with_len_prefix::<TransportParameterLength, Self>()
+                    .ok()?;
+                return value.validate().ok();
+            }
+
+            buffer = remaining
+                .skip_with_len_prefix::<TransportParameterLength>()
+                .ok()?;
+        }
+
+        None
+    }
+
+    #[inline]
+    fn push_available_version(&mut self, version: u32) {
+        if !version::is_supported(version) || self.available_versions().contains(&version) {
+            return;
+        }
+
+        self.available_versions[self.available_versions_len as usize] = version;
+        self.available_versions_len += 1;
+    }
+}
+
+impl TransportParameter for VersionInformation {
+    type CodecValue = Self;
+
+    const ID: TransportParameterId = TransportParameterId::from_u8(0x11);
+
+    fn from_codec_value(value: Self) -> Self {
+        value
+    }
+
+    fn try_into_codec_value(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn default_value() -> Self {
+        unimplemented!(
+            "VersionInformation is an optional transport parameter, so the default is None"
+        )
+    }
+}
+
+impl TransportParameterValidator for VersionInformation {
+    fn validate(self) -> Result<Self, DecoderError> {
+        // A chosen version of 0 is treated as a parsing failure (RFC 9368 Section 3)
+        decoder_invariant!(self.chosen_version != 0, "chosen version cannot be 0");
+        Ok(self)
+    }
+}
+
+decoder_value!(
+    impl<'a> VersionInformation {
+        fn decode(buffer: Buffer) -> Result<Self> {
+            let (chosen_version, mut buffer) = buffer.decode::<u32>()?;
+            let mut value = Self::new(chosen_version, &[]);
+
+            while !buffer.is_empty() {
+                let (version, remaining) = buffer.decode::<u32>()?;
+                decoder_invariant!(version != 0, "available versions cannot contain 0");
+                value.push_available_version(version);
+                buffer = remaining;
+            }
+
+            Ok((value, buffer))
+        }
+    }
+);
+
+impl EncoderValue for VersionInformation {
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&self.chosen_version);
+        for version in self.available_versions() {
+            buffer.encode(version);
+        }
+    }
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-18.1
+//# Transport parameters with an identifier of the form "31 * N + 27" for
+//# integer values of N are reserved to exercise the requirement that
+//# unknown transport parameters be ignored.  These transport parameters
+//# have no semantics and can carry arbitrary values.
+
+const RESERVED_PARAMETER_MAX_VALUE_LEN: usize = 16;
+
+/// A reserved transport parameter, which is sent to ensure the peer ignores unknown parameters
+///
+/// Reserved parameters received from the peer are ignored like any other unknown parameter,
+/// so decoded transport parameters never contain one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReservedParameter {
+    id: TransportParameterId,
+    value: [u8; RESERVED_PARAMETER_MAX_VALUE_LEN],
+    value_len: u8,
+}
+
+impl ReservedParameter {
+    /// The maximum length of the value of a reserved parameter
+    pub const MAX_VALUE_LEN: usize = RESERVED_PARAMETER_MAX_VALUE_LEN;
+
+    /// Creates a reserved parameter with the given identifier and value
+    ///
+    /// The identifier must be of the form `31 * N + 27` and the value can't be longer
+    /// than [`Self::MAX_VALUE_LEN`].
+    pub fn new(id: VarInt, value: &[u8]) -> Result<Self, ValidationError> {
+        if !Self::is_reserved(id) {
+            return Err(ValidationError("transport parameter id is not reserved"));
+        }
+
+        if value.len() > Self::MAX_VALUE_LEN {
+            return Err(ValidationError(
+                "reserved transport parameter value is too long",
+            ));
+        }
+
+        let mut parameter = Self {
+            id,
+            value: [0; Self::MAX_VALUE_LEN],
+            value_len: value.len() as u8,
+        };
+        parameter.value[..value.len()].copy_from_slice(value);
+        Ok(parameter)
+    }
+
+    /// Creates a reserved parameter with a random identifier and value
+    pub fn random(random_generator: &mut dyn random::Generator) -> Self {
+        let mut n = [0; 4];
+        random_generator.public_random_fill(&mut n);
+        let n = u32::from_be_bytes(n) as u64;
+
+        let mut value = [0; Self::MAX_VALUE_LEN];
+        random_generator.public_random_fill(&mut value);
+        let len = random::gen_range_biased(random_generator, 0..=Self::MAX_VALUE_LEN);
+
+        // the largest identifier is less than 2^37 so it always fits in a VarInt
+        let id = VarInt::new(31 * n + 27).expect("reserved id fits in a VarInt");
+
+        Self::new(id, &value[..len]).expect("parameter is reserved")
+    }
+
+    /// Returns `true` if the transport parameter identifier is reserved
+    #[inline]
+    pub fn is_reserved(id: VarInt) -> bool {
+        id.as_u64() % 31 == 27
+    }
+
+    #[inline]
+    pub fn id(&self) -> VarInt {
+        self.id
+    }
+
+    #[inline]
+    pub fn value(&self) -> &[u8] {
+        &self.value[..self.value_len as usize]
+    }
+}
+
+impl EncoderValue for ReservedParameter {
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&self.id);
+        buffer.encode_with_len_prefix::<TransportParameterLength, _>(&self.value());
+    }
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
 //# If present, transport parameters that set initial per-stream flow
 //# control limits (initial_max_stream_data_bidi_local,
@@ -1156,12 +1634,14 @@ impl<
         let Self {
             max_ack_delay,
             ack_delay_exponent,
+            min_ack_delay,
             ..
         } = self;
 
         ack::Settings {
             max_ack_delay: max_ack_delay.as_duration(),
             ack_delay_exponent: **ack_delay_exponent,
+            min_ack_delay: min_ack_delay.map(MinAckDelay::as_duration),
             ..Default::default()
         }
     }
@@ -1288,8 +1768,12 @@ macro_rules! impl_transport_parameters {
         #[derive(Clone, Copy, Debug, PartialEq)]
         pub struct TransportParameters<$($server_param),*> {
             $(
-                pub $field: $field_ty
-            ),*
+                pub $field: $field_ty,
+            )*
+            /// A reserved parameter which is appended to the encoded parameters
+            ///
+            /// This is always `None` for decoded parameters.
+            pub reserved_parameter: Option<ReservedParameter>,
         }
 
         impl<$($server_param),*> Default for TransportParameters<$($server_param),*>
@@ -1303,6 +1787,7 @@ macro_rules! impl_transport_parameters {
                     $(
                         $field: TransportParameter::default_value(),
                     )*
+                    reserved_parameter: None,
                 }
             }
         }
@@ -1318,6 +1803,10 @@ macro_rules! impl_transport_parameters {
                 $(
                     buffer.encode(&TransportParameterCodec(&self.$field));
                 )*
+
+                if let Some(reserved_parameter) = &self.reserved_parameter {
+                    buffer.encode(reserved_parameter);
+                }
             }
         }
 
@@ -1419,6 +1908,10 @@ impl_transport_parameters!(
         preferred_address: PreferredAddress,
         initial_source_connection_id: Option<InitialSourceConnectionId>,
         retry_source_connection_id: RetrySourceConnectionId,
+        version_information: Option<VersionInformation>,
+        min_ack_delay: Option<MinAckDelay>,
+        enable_multipath: EnableMultipath,
+        grease_quic_bit: GreaseQuicBit,
     }
 );
 
@@ -1466,5 +1959,8 @@ impl<
         load!(ack_delay_exponent, ack_delay_exponent);
         load!(max_active_connection_ids, active_connection_id_limit);
         load!(max_datagram_frame_size, max_datagram_frame_size);
+        load!(min_ack_delay, min_ack_delay);
+        load!(enable_multipath, enable_multipath);
+        load!(grease_quic_bit, grease_quic_bit);
     }
 }
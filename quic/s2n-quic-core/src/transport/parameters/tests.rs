@@ -80,6 +80,11 @@ fn server_transport_parameters() -> ServerTransportParameters {
         }),
         initial_source_connection_id: Some([1, 2, 3, 4][..].try_into().unwrap()),
         retry_source_connection_id: Some([1, 2, 3, 4][..].try_into().unwrap()),
+        version_information: None,
+        min_ack_delay: None,
+        enable_multipath: EnableMultipath::Disabled,
+        grease_quic_bit: GreaseQuicBit::Disabled,
+        reserved_parameter: None,
     }
 }
 
@@ -117,6 +122,11 @@ fn client_transport_parameters() -> ClientTransportParameters {
         preferred_address: Default::default(),
         initial_source_connection_id: Some([1, 2, 3, 4][..].try_into().unwrap()),
         retry_source_connection_id: Default::default(),
+        version_information: None,
+        min_ack_delay: None,
+        enable_multipath: EnableMultipath::Disabled,
+        grease_quic_bit: GreaseQuicBit::Disabled,
+        reserved_parameter: None,
     }
 }
 
@@ -199,3 +209,154 @@ fn compute_data_window_test() {
         37_500_000
     );
 }
+
+#[test]
+fn zero_rtt_resumption_test() {
+    let mut remembered = server_transport_parameters().zero_rtt_parameters();
+    remembered.max_datagram_frame_size = VarInt::from_u16(1200);
+
+    assert!(remembered.validate_resumption(&remembered).is_ok());
+
+    let mut increased = remembered;
+    increased.initial_max_data = VarInt::from_u32(u32::MAX);
+    assert!(increased.validate_resumption(&remembered).is_ok());
+
+    let mut reduced = remembered;
+    reduced.initial_max_streams_bidi = VarInt::from_u8(0);
+    assert!(reduced.validate_resumption(&remembered).is_err());
+
+    let mut reduced = remembered;
+    reduced.max_datagram_frame_size = VarInt::from_u8(0);
+    assert!(reduced.validate_resumption(&remembered).is_err());
+}
+
+#[test]
+fn version_information_test() {
+    use crate::version::{VERSION_1, VERSION_2};
+
+    let mut value = client_transport_parameters();
+    value.version_information = Some(VersionInformation::new(
+        VERSION_1,
+        &[VERSION_2, 0xdada_dada, VERSION_1],
+    ));
+    assert_codec_round_trip_value!(ClientTransportParameters, value);
+
+    let version_information = value.version_information.unwrap();
+    assert_eq!(version_information.chosen_version(), VERSION_1);
+    // unknown versions are omitted
+    assert_eq!(
+        version_information.available_versions(),
+        &[VERSION_2, VERSION_1]
+    );
+
+    // the parameter can be found without decoding the other parameters
+    let encoded = s2n_codec::testing::encode(&value).unwrap();
+    assert_eq!(
+        VersionInformation::find(&encoded),
+        Some(version_information)
+    );
+    let encoded = s2n_codec::testing::encode(&client_transport_parameters()).unwrap();
+    assert_eq!(VersionInformation::find(&encoded), None);
+
+    // a chosen version of 0 is invalid
+    let mut value = server_transport_parameters();
+    value.version_information = Some(VersionInformation::new(0, &[VERSION_1]));
+    let encoded = s2n_codec::testing::encode(&value).unwrap();
+    assert!(ServerTransportParameters::decode(DecoderBuffer::new(&encoded)).is_err());
+    assert_eq!(VersionInformation::find(&encoded), None);
+}
+
+#[test]
+fn min_ack_delay_test() {
+    let mut value = client_transport_parameters();
+    value.min_ack_delay = Some(Duration::from_millis(1).try_into().unwrap());
+    assert_codec_round_trip_value!(ClientTransportParameters, value);
+    assert_eq!(
+        value.min_ack_delay.unwrap().as_duration(),
+        Duration::from_millis(1)
+    );
+
+    // values greater than the largest valid max_ack_delay are invalid
+    assert!(MinAckDelay::try_from(Duration::from_secs(17)).is_err());
+}
+
+#[test]
+fn enable_multipath_test() {
+    let mut value = client_transport_parameters();
+    value.enable_multipath = EnableMultipath::SinglePacketNumberSpace;
+    assert_codec_round_trip_value!(ClientTransportParameters, value);
+
+    let mut value = server_transport_parameters();
+    value.enable_multipath = EnableMultipath::MultiplePacketNumberSpaces;
+    assert_codec_round_trip_value!(ServerTransportParameters, value);
+
+    // the parameter is omitted when the extension is disabled
+    let value = client_transport_parameters();
+    let disabled = s2n_codec::testing::encode(&value).unwrap();
+    let mut value = client_transport_parameters();
+    value.enable_multipath = EnableMultipath::SinglePacketNumberSpace;
+    let enabled = s2n_codec::testing::encode(&value).unwrap();
+    assert!(enabled.len() > disabled.len());
+}
+
+#[test]
+fn grease_quic_bit_test() {
+    let mut value = client_transport_parameters();
+    value.grease_quic_bit = GreaseQuicBit::Enabled;
+    let enabled = assert_codec_round_trip_value!(ClientTransportParameters, value);
+
+    let mut value = server_transport_parameters();
+    value.grease_quic_bit = GreaseQuicBit::Enabled;
+    assert_codec_round_trip_value!(ServerTransportParameters, value);
+
+    //= https://www.rfc-editor.org/rfc/rfc9287#section-3
+    //= type=test
+    //# grease_quic_bit (0x2ab2):  This transport parameter has a zero-length
+    //#    value.
+    let disabled = s2n_codec::testing::encode(&client_transport_parameters()).unwrap();
+    assert_eq!(&enabled[..disabled.len()], &disabled[..]);
+    assert_eq!(&enabled[disabled.len()..], &[0x40 | 0x2a, 0xb2, 0x00]);
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-18.1
+//= type=test
+//# Transport parameters with an identifier of the form "31 * N + 27" for
+//# integer values of N are reserved to exercise the requirement that
+//# unknown transport parameters be ignored.
+#[test]
+fn reserved_parameter_test() {
+    assert!(ReservedParameter::new(VarInt::from_u8(27), &[1, 2, 3]).is_ok());
+    assert!(ReservedParameter::new(VarInt::from_u8(58), &[]).is_ok());
+    assert!(ReservedParameter::new(VarInt::from_u8(28), &[]).is_err());
+    assert!(ReservedParameter::new(VarInt::from_u8(27), &[0; 17]).is_err());
+
+    let mut random = crate::random::testing::Generator(123);
+
+    for _ in 0..100 {
+        let reserved_parameter = ReservedParameter::random(&mut random);
+        assert!(ReservedParameter::is_reserved(reserved_parameter.id()));
+        assert!(reserved_parameter.value().len() <= ReservedParameter::MAX_VALUE_LEN);
+
+        let mut value = client_transport_parameters();
+        value.reserved_parameter = Some(reserved_parameter);
+        let encoded = s2n_codec::testing::encode(&value).unwrap();
+
+        // the reserved parameter is appended to the other parameters
+        let expected = s2n_codec::testing::encode(&client_transport_parameters()).unwrap();
+        assert_eq!(&encoded[..expected.len()], &expected[..]);
+        let reserved = s2n_codec::testing::encode(&reserved_parameter).unwrap();
+        assert_eq!(&encoded[expected.len()..], &reserved[..]);
+
+        // peers ignore the reserved parameter
+        let (decoded, remaining) =
+            ClientTransportParameters::decode(DecoderBuffer::new(&encoded)).unwrap();
+        assert_eq!(decoded, client_transport_parameters());
+        assert!(remaining.is_empty());
+
+        let mut value = server_transport_parameters();
+        value.reserved_parameter = Some(reserved_parameter);
+        let encoded = s2n_codec::testing::encode(&value).unwrap();
+        let (decoded, _) = ServerTransportParameters::decode(DecoderBuffer::new(&encoded)).unwrap();
+        assert_eq!(decoded, server_transport_parameters());
+    }
+}
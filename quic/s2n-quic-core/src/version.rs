@@ -0,0 +1,290 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! QUIC version identifiers and negotiation preferences
+
+use crate::{random, transport::parameters::ValidationError};
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-15
+//# The version 0x00000001 is reserved for the protocol defined in this
+//# document.
+pub const VERSION_1: u32 = 0x0000_0001;
+
+//= https://www.rfc-editor.org/rfc/rfc9369#section-3.1
+//# The Version field of long header packets is 0x6b3343cf.
+pub const VERSION_2: u32 = 0x6b33_43cf;
+
+/// All of the QUIC versions supported by the implementation
+pub const SUPPORTED: &[u32] = &[VERSION_1, VERSION_2];
+
+/// Returns `true` if the version is supported by the implementation
+#[inline]
+pub fn is_supported(version: u32) -> bool {
+    SUPPORTED.contains(&version)
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-15
+//# Versions that follow the pattern 0x?a?a?a?a are reserved for use in
+//# forcing version negotiation to be exercised -- that is, any version
+//# number where the low four bits of all bytes is 1010 (in binary).
+const RESERVED_MASK: u32 = 0x0f0f_0f0f;
+const RESERVED_PATTERN: u32 = 0x0a0a_0a0a;
+
+/// Returns `true` if the version is reserved for exercising version negotiation
+#[inline]
+pub fn is_reserved(version: u32) -> bool {
+    version & RESERVED_MASK == RESERVED_PATTERN
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-15
+//# A
+//# client or server MAY advertise support for any of these reserved
+//# versions.
+/// Returns a random reserved version
+#[inline]
+pub fn random_reserved(random_generator: &mut dyn random::Generator) -> u32 {
+    let mut version = [0; 4];
+    random_generator.public_random_fill(&mut version);
+    u32::from_be_bytes(version) & !RESERVED_MASK | RESERVED_PATTERN
+}
+
+/// Returns `true` if a connection started with the `original` version can be
+/// upgraded to the `negotiated` version without an additional round trip
+///
+/// QUIC version 1 and QUIC version 2 are compatible with each other.
+#[inline]
+pub fn is_compatible(original: u32, negotiated: u32) -> bool {
+    is_supported(original) && is_supported(negotiated)
+}
+
+/// The QUIC versions an endpoint is willing to use, in order of preference
+///
+/// Servers select the most preferred version that is compatible with the version of
+/// the client's first Initial packet and that the client reports as available.
+/// Clients use the `original` version for their first Initial packet and offer
+/// the remaining versions for compatible version negotiation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Preferences {
+    versions: [u32; SUPPORTED.len()],
+    len: u8,
+    original: u32,
+    grease: bool,
+}
+
+impl Default for Preferences {
+    /// Only QUIC version 1 is enabled by default
+    fn default() -> Self {
+        Self::new(&[VERSION_1]).expect("valid preferences")
+    }
+}
+
+impl Preferences {
+    /// Creates a set of preferences from a list of versions, ordered from the most
+    /// to least preferred
+    ///
+    /// Clients will use version 1 for their first Initial packet if it is enabled. Otherwise
+    /// the most preferred version is used. This can be changed with
+    /// [`Self::with_original_version`].
+    pub fn new(versions: &[u32]) -> Result<Self, ValidationError> {
+        if versions.is_empty() {
+            return Err(ValidationError("at least one QUIC version must be enabled"));
+        }
+
+        let mut preferences = Self {
+            versions: [0; SUPPORTED.len()],
+            len: 0,
+            original: versions[0],
+            grease: true,
+        };
+
+        for version in versions.iter().copied() {
+            if !is_supported(version) {
+                return Err(ValidationError("unsupported QUIC version"));
+            }
+
+            if preferences.contains(version) {
+                return Err(ValidationError("duplicate QUIC version"));
+            }
+
+            preferences.versions[preferences.len as usize] = version;
+            preferences.len += 1;
+        }
+
+        if preferences.contains(VERSION_1) {
+            preferences.original = VERSION_1;
+        }
+
+        Ok(preferences)
+    }
+
+    /// Sets the version a client uses for its first Initial packet
+    ///
+    /// The version must be one of the enabled versions.
+    pub fn with_original_version(mut self, version: u32) -> Result<Self, ValidationError> {
+        if !self.contains(version) {
+            return Err(ValidationError(
+                "the original QUIC version must be one of the enabled versions",
+            ));
+        }
+
+        self.original = version;
+        Ok(self)
+    }
+
+    /// Sets whether a random reserved version is added to Version Negotiation packets
+    ///
+    /// Reserved versions ensure clients keep ignoring versions they don't support.
+    /// Defaults to `true`.
+    pub fn with_grease(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.grease = enabled;
+        Ok(self)
+    }
+
+    /// Returns `true` if reserved versions are added to Version Negotiation packets
+    #[inline]
+    pub fn grease(&self) -> bool {
+        self.grease
+    }
+
+    /// Returns the enabled versions, ordered from the most to least preferred
+    #[inline]
+    pub fn versions(&self) -> &[u32] {
+        &self.versions[..self.len as usize]
+    }
+
+    /// Returns the version a client uses for its first Initial packet
+    #[inline]
+    pub fn original(&self) -> u32 {
+        self.original
+    }
+
+    /// Returns `true` if the version is enabled
+    #[inline]
+    pub fn contains(&self, version: u32) -> bool {
+        self.versions().contains(&version)
+    }
+
+    /// Selects the version for a connection started by a peer with the `original` version
+    ///
+    /// `available` contains the versions the peer has reported it supports. The most
+    /// preferred version that is both available and compatible with the `original` version
+    /// is returned. If none of the versions match, the `original` version is kept.
+    pub fn negotiate<A>(&self, original: u32, available: A) -> u32
+    where
+        A: IntoIterator<Item = u32> + Clone,
+    {
+        // Only select versions that the peer has reported as available to prevent downgrades
+        self.versions()
+            .iter()
+            .copied()
+            .find(|&version| {
+                is_compatible(original, version)
+                    && available.clone().into_iter().any(|v| v == version)
+            })
+            .unwrap_or(original)
+    }
+
+    /// Returns `true` if a server chose a different version than it should have for a
+    /// connection started with the `original` version
+    ///
+    /// `available` contains the versions the server has reported it supports, in its order of
+    /// preference. The server is expected to select its most preferred version that is
+    /// compatible with the `original` version and was offered by this endpoint. Any other
+    /// choice means the negotiation was tampered with or the server doesn't follow its own
+    /// preferences.
+    pub fn is_downgrade(&self, original: u32, chosen: u32, available: &[u32]) -> bool {
+        let expected = available
+            .iter()
+            .copied()
+            .find(|&version| self.contains(version) && is_compatible(original, version))
+            .unwrap_or(original);
+
+        chosen != expected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preferences_validation_test() {
+        assert!(Preferences::new(&[]).is_err());
+        assert!(Preferences::new(&[0xdada_dada]).is_err());
+        assert!(Preferences::new(&[VERSION_1, VERSION_1]).is_err());
+
+        let preferences = Preferences::new(&[VERSION_2, VERSION_1]).unwrap();
+        assert_eq!(preferences.versions(), &[VERSION_2, VERSION_1]);
+        assert_eq!(preferences.original(), VERSION_1);
+
+        let preferences = preferences.with_original_version(VERSION_2).unwrap();
+        assert_eq!(preferences.original(), VERSION_2);
+
+        let preferences = Preferences::default();
+        assert_eq!(preferences.versions(), &[VERSION_1]);
+        assert!(preferences.with_original_version(VERSION_2).is_err());
+    }
+
+    #[test]
+    fn reserved_test() {
+        assert!(is_reserved(0xdada_dada));
+        assert!(is_reserved(0x0a1a_2a3a));
+        assert!(!is_reserved(VERSION_1));
+        assert!(!is_reserved(VERSION_2));
+
+        let mut random = random::testing::Generator(123);
+        for _ in 0..100 {
+            let version = random_reserved(&mut random);
+            assert!(is_reserved(version), "{:#x}", version);
+            assert!(!is_supported(version));
+            assert!(Preferences::new(&[version]).is_err());
+        }
+
+        let preferences = Preferences::default();
+        assert!(preferences.grease());
+        assert!(!preferences.with_grease(false).unwrap().grease());
+    }
+
+    #[test]
+    fn negotiate_test() {
+        let preferences = Preferences::new(&[VERSION_2, VERSION_1]).unwrap();
+
+        // the client supports both versions so the server's preference is used
+        assert_eq!(
+            preferences.negotiate(VERSION_1, [VERSION_1, VERSION_2]),
+            VERSION_2
+        );
+
+        // the client didn't report any other versions
+        assert_eq!(preferences.negotiate(VERSION_1, [VERSION_1]), VERSION_1);
+        assert_eq!(preferences.negotiate(VERSION_1, []), VERSION_1);
+
+        let preferences = Preferences::default();
+        assert_eq!(
+            preferences.negotiate(VERSION_1, [VERSION_2, VERSION_1]),
+            VERSION_1
+        );
+    }
+
+    #[test]
+    fn downgrade_test() {
+        let preferences = Preferences::new(&[VERSION_2, VERSION_1]).unwrap();
+
+        // the server picked its most preferred version
+        assert!(!preferences.is_downgrade(VERSION_1, VERSION_2, &[VERSION_2, VERSION_1]));
+        assert!(!preferences.is_downgrade(VERSION_1, VERSION_1, &[VERSION_1, VERSION_2]));
+        assert!(!preferences.is_downgrade(VERSION_1, VERSION_1, &[VERSION_1]));
+
+        // the server prefers a version other than the one that was chosen
+        assert!(preferences.is_downgrade(VERSION_1, VERSION_1, &[VERSION_2, VERSION_1]));
+        assert!(preferences.is_downgrade(VERSION_1, VERSION_2, &[VERSION_1, VERSION_2]));
+
+        // the server switched versions without reporting the chosen version as available
+        assert!(preferences.is_downgrade(VERSION_1, VERSION_2, &[]));
+        assert!(!preferences.is_downgrade(VERSION_1, VERSION_1, &[]));
+
+        // versions that the client didn't offer are skipped
+        let preferences = Preferences::default();
+        assert!(!preferences.is_downgrade(VERSION_1, VERSION_1, &[VERSION_2, VERSION_1]));
+    }
+}
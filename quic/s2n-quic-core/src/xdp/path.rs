@@ -101,6 +101,14 @@ impl Handle for Tuple {
         self.local_address.into()
     }
 
+    #[inline]
+    fn with_local_address(&self, local_address: path::LocalAddress) -> Option<Self> {
+        Some(Self {
+            remote_address: self.remote_address,
+            local_address: local_address.into(),
+        })
+    }
+
     #[inline]
     fn eq(&self, other: &Self) -> bool {
         // TODO only compare everything if the other is all filled out
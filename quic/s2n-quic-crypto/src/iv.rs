@@ -23,12 +23,18 @@ impl Iv {
         Self(bytes)
     }
 
+    /// Returns the nonce for a packet sent in the packet number space of a path
+    ///
+    /// The path identifier takes the place of the leading zero bits of the left-padded packet
+    /// number, so the path identifier `0` results in the nonce of RFC 9001.
+    ///
+    /// See https://datatracker.ietf.org/doc/html/draft-ietf-quic-multipath-03
     #[inline]
-    pub fn nonce(&self, packet_number: u64) -> [u8; NONCE_LEN] {
+    pub fn nonce_for_path(&self, path_identifier: u32, packet_number: u64) -> [u8; NONCE_LEN] {
         let mut nonce = [0; NONCE_LEN];
         let mut encoder = EncoderBuffer::new(&mut nonce);
 
-        encoder.encode(&0u32);
+        encoder.encode(&path_identifier);
         encoder.encode(&packet_number);
 
         for (a, b) in nonce.iter_mut().zip(self.0.iter()) {
@@ -55,3 +61,17 @@ impl hkdf::KeyType for IvLen {
         NONCE_LEN
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn nonce_for_path_test() {
+        let iv = Iv(hex!("6b26114b9cba2b63a9e8dd4f"));
+
+        assert_eq!(iv.nonce_for_path(0, 0xa), hex!("6b26114b9cba2b63a9e8dd45"));
+        assert_eq!(iv.nonce_for_path(3, 0xa), hex!("6b2611489cba2b63a9e8dd45"));
+    }
+}
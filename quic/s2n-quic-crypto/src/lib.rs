@@ -25,7 +25,7 @@ pub use ring::{
     aead::{Algorithm, MAX_TAG_LEN},
     constant_time, digest, hkdf,
     hkdf::Prk,
-    hmac,
+    hmac, rand,
 };
 
 #[derive(Clone)]
@@ -19,6 +19,7 @@ pub struct KeyPair {
 impl KeyPair {
     pub fn new(
         endpoint: endpoint::Type,
+        version: u32,
         algorithm: &Algorithm,
         secrets: SecretPair,
     ) -> Option<(Self, HeaderKeyPair)> {
@@ -27,8 +28,8 @@ impl KeyPair {
             endpoint::Type::Server => (secrets.server, secrets.client),
         };
 
-        let (sealer, header_sealer) = CipherSuite::new(algorithm, sealer_secret)?;
-        let (opener, header_opener) = CipherSuite::new(algorithm, opener_secret)?;
+        let (sealer, header_sealer) = CipherSuite::new(version, algorithm, sealer_secret)?;
+        let (opener, header_opener) = CipherSuite::new(version, algorithm, opener_secret)?;
 
         let key = Self { sealer, opener };
         let header_key = HeaderKeyPair {
@@ -98,30 +99,43 @@ macro_rules! negotiated_crypto {
         pub struct $name(crate::negotiated::KeyPair);
 
         impl $name {
-            /// Create a server cipher suite with a given negotiated algorithm and secret
+            /// Create a server cipher suite for a QUIC version with a given negotiated algorithm and secret
             pub fn new_server(
+                version: u32,
                 algorithm: &$crate::Algorithm,
                 secrets: $crate::SecretPair,
             ) -> Option<(Self, $header_key)> {
-                Self::new(s2n_quic_core::endpoint::Type::Server, algorithm, secrets)
+                Self::new(
+                    s2n_quic_core::endpoint::Type::Server,
+                    version,
+                    algorithm,
+                    secrets,
+                )
             }
 
-            /// Create a client cipher suite with a given negotiated algorithm and secret
+            /// Create a client cipher suite for a QUIC version with a given negotiated algorithm and secret
             pub fn new_client(
+                version: u32,
                 algorithm: &$crate::Algorithm,
                 secrets: $crate::SecretPair,
             ) -> Option<(Self, $header_key)> {
-                Self::new(s2n_quic_core::endpoint::Type::Client, algorithm, secrets)
+                Self::new(
+                    s2n_quic_core::endpoint::Type::Client,
+                    version,
+                    algorithm,
+                    secrets,
+                )
             }
 
-            /// Create a cipher_suite for an endpoint type with a given negotiated algorithm and secret
+            /// Create a cipher_suite for an endpoint type and QUIC version with a given negotiated algorithm and secret
             pub fn new(
                 endpoint: s2n_quic_core::endpoint::Type,
+                version: u32,
                 algorithm: &$crate::Algorithm,
                 secrets: $crate::SecretPair,
             ) -> Option<(Self, $header_key)> {
                 let (key, header_key) =
-                    crate::negotiated::KeyPair::new(endpoint, algorithm, secrets)?;
+                    crate::negotiated::KeyPair::new(endpoint, version, algorithm, secrets)?;
 
                 let key = Self(key);
                 let header_key = $header_key::from(header_key);
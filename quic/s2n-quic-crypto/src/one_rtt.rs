@@ -7,6 +7,8 @@ header_key!(OneRttHeaderKey);
 negotiated_crypto!(OneRttKey, OneRttHeaderKey);
 
 impl crypto::OneRttKey for OneRttKey {
+    const SUPPORTS_PATH_IDENTIFIER: bool = true;
+
     #[inline]
     #[must_use]
     fn derive_next_key(&self) -> Self {
@@ -22,6 +24,32 @@ impl crypto::OneRttKey for OneRttKey {
     fn update_opener_pmtu(&mut self, pmtu: u16) {
         self.0.opener.update_pmtu(pmtu)
     }
+
+    #[inline]
+    fn decrypt_for_path(
+        &self,
+        path_identifier: u32,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut [u8],
+    ) -> Result<(), crypto::CryptoError> {
+        self.0
+            .opener
+            .decrypt_for_path(path_identifier, packet_number, header, payload)
+    }
+
+    #[inline]
+    fn encrypt_for_path(
+        &self,
+        path_identifier: u32,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut [u8],
+    ) -> Result<(), crypto::CryptoError> {
+        self.0
+            .sealer
+            .encrypt_for_path(path_identifier, packet_number, header, payload)
+    }
 }
 
 impl crypto::OneRttHeaderKey for OneRttHeaderKey {}
@@ -30,7 +58,7 @@ impl crypto::OneRttHeaderKey for OneRttHeaderKey {}
 mod tests {
     use crate::{cipher_suite::TLS_CHACHA20_POLY1305_SHA256, hkdf};
     use hex_literal::hex;
-    use s2n_quic_core::crypto::Key;
+    use s2n_quic_core::{crypto::Key, version::VERSION_1};
 
     //= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.5
     //# In this example, TLS produces an application write secret from which
@@ -74,14 +102,14 @@ mod tests {
     ) -> (TLS_CHACHA20_POLY1305_SHA256, TLS_CHACHA20_POLY1305_SHA256) {
         // Create a cipher based on the initial secret
         let key = hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, secret);
-        let cipher = TLS_CHACHA20_POLY1305_SHA256::new(key);
+        let cipher = TLS_CHACHA20_POLY1305_SHA256::new(VERSION_1, key);
 
         // Create the cipher after a Key Update has occurred
         let next_cipher = cipher.0.update();
 
         // Create a cipher based on the expected post-update secret
         let next_key = hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, next_secret);
-        let expected_next_cipher = TLS_CHACHA20_POLY1305_SHA256::new(next_key);
+        let expected_next_cipher = TLS_CHACHA20_POLY1305_SHA256::new(VERSION_1, next_key);
 
         (next_cipher, expected_next_cipher.0)
     }
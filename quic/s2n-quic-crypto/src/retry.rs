@@ -3,10 +3,13 @@
 
 use crate::{constant_time, ring_aead as aead};
 use core::convert::TryInto;
-use s2n_quic_core::crypto::{
-    self,
-    retry::{IntegrityTag, NONCE_BYTES, SECRET_KEY_BYTES},
-    CryptoError,
+use s2n_quic_core::{
+    crypto::{
+        self,
+        retry::{IntegrityTag, NONCE_BYTES, NONCE_BYTES_V2, SECRET_KEY_BYTES, SECRET_KEY_BYTES_V2},
+        CryptoError,
+    },
+    version::VERSION_2,
 };
 
 lazy_static::lazy_static! {
@@ -14,15 +17,23 @@ lazy_static::lazy_static! {
     static ref SECRET_KEY: aead::LessSafeKey = aead::LessSafeKey::new(
         aead::UnboundKey::new(&aead::AES_128_GCM, &SECRET_KEY_BYTES).unwrap(),
     );
+    static ref SECRET_KEY_V2: aead::LessSafeKey = aead::LessSafeKey::new(
+        aead::UnboundKey::new(&aead::AES_128_GCM, &SECRET_KEY_BYTES_V2).unwrap(),
+    );
 }
 
 #[derive(Debug)]
 pub struct RetryKey;
 
 impl crypto::RetryKey for RetryKey {
-    fn generate_tag(pseudo_packet: &[u8]) -> IntegrityTag {
-        let nonce = aead::Nonce::assume_unique_for_key(NONCE_BYTES);
-        let tag = SECRET_KEY
+    fn generate_tag(version: u32, pseudo_packet: &[u8]) -> IntegrityTag {
+        let (key, nonce): (&aead::LessSafeKey, _) = if version == VERSION_2 {
+            (&SECRET_KEY_V2, NONCE_BYTES_V2)
+        } else {
+            (&SECRET_KEY, NONCE_BYTES)
+        };
+        let nonce = aead::Nonce::assume_unique_for_key(nonce);
+        let tag = key
             .seal_in_place_separate_tag(nonce, aead::Aad::from(pseudo_packet), &mut [])
             .expect("in_out len is 0 and should always be less than the nonce max bytes");
 
@@ -31,8 +42,8 @@ impl crypto::RetryKey for RetryKey {
             .expect("AES_128_GCM tag len should always be 128 bits")
     }
 
-    fn validate(pseudo_packet: &[u8], tag: IntegrityTag) -> Result<(), CryptoError> {
-        let expected = Self::generate_tag(pseudo_packet);
+    fn validate(version: u32, pseudo_packet: &[u8], tag: IntegrityTag) -> Result<(), CryptoError> {
+        let expected = Self::generate_tag(version, pseudo_packet);
 
         constant_time::verify_slices_are_equal(&expected, &tag)
             .map_err(|_| CryptoError::DECRYPT_ERROR)
@@ -52,17 +63,39 @@ mod tests {
         packet::number::{PacketNumberSpace, TruncatedPacketNumber},
         random, token,
         varint::VarInt,
+        version::VERSION_1,
     };
 
     #[test]
     fn test_tag_validation() {
         let invalid_tag: [u8; 16] = hex!("00112233445566778899aabbccddeeff");
 
+        assert!(RetryKey::validate(
+            VERSION_1,
+            &retry::example::PSEUDO_PACKET,
+            retry::example::EXPECTED_TAG
+        )
+        .is_ok());
+        assert!(
+            RetryKey::validate(VERSION_1, &retry::example::PSEUDO_PACKET, invalid_tag).is_err()
+        );
+
+        assert!(RetryKey::validate(
+            VERSION_2,
+            &retry::example::PSEUDO_PACKET_V2,
+            retry::example::EXPECTED_TAG_V2
+        )
+        .is_ok());
         assert!(
-            RetryKey::validate(&retry::example::PSEUDO_PACKET, retry::example::EXPECTED_TAG)
-                .is_ok()
+            RetryKey::validate(VERSION_2, &retry::example::PSEUDO_PACKET_V2, invalid_tag).is_err()
         );
-        assert!(RetryKey::validate(&retry::example::PSEUDO_PACKET, invalid_tag).is_err());
+        // the tag is bound to the version
+        assert!(RetryKey::validate(
+            VERSION_1,
+            &retry::example::PSEUDO_PACKET_V2,
+            retry::example::EXPECTED_TAG_V2
+        )
+        .is_err());
     }
 
     fn pn(space: PacketNumberSpace) -> TruncatedPacketNumber {
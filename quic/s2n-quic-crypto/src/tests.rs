@@ -7,7 +7,10 @@ use ring::{
     hkdf,
     hkdf::KeyType,
 };
-use s2n_quic_core::crypto::{initial::InitialKey as _, key::Key, CryptoError, HeaderKey};
+use s2n_quic_core::{
+    crypto::{initial::InitialKey as _, key::Key, CryptoError, HeaderKey},
+    version::{VERSION_1, VERSION_2},
+};
 use s2n_quic_crypto::{
     handshake::{HandshakeHeaderKey, HandshakeKey},
     initial::{InitialHeaderKey, InitialKey},
@@ -275,9 +278,9 @@ fn gen_crypto() -> impl ValueGenerator<Output = CryptoTest> {
 }
 
 fn gen_initial() -> impl ValueGenerator<Output = CryptoTest> {
-    gen_dcid().map(|dcid| {
-        let server_keys = InitialKey::new_server(&dcid);
-        let client_keys = InitialKey::new_client(&dcid);
+    (gen_version(), gen_dcid()).map_gen(|(version, dcid)| {
+        let server_keys = InitialKey::new_server(version, &dcid);
+        let client_keys = InitialKey::new_client(version, &dcid);
         CryptoTest::Initial {
             server_keys,
             client_keys,
@@ -285,14 +288,18 @@ fn gen_initial() -> impl ValueGenerator<Output = CryptoTest> {
     })
 }
 
+fn gen_version() -> impl ValueGenerator<Output = u32> {
+    gen::<bool>().map_gen(|v2| if v2 { VERSION_2 } else { VERSION_1 })
+}
+
 fn gen_dcid() -> impl ValueGenerator<Output = Vec<u8>> {
     gen_unique_bytes(0..=20)
 }
 
 fn gen_handshake() -> impl ValueGenerator<Output = CryptoTest> {
-    gen_negotiated_secrets().map(|(algo, secrets)| {
-        let server_keys = HandshakeKey::new_server(algo, secrets.clone()).unwrap();
-        let client_keys = HandshakeKey::new_client(algo, secrets).unwrap();
+    (gen_version(), gen_negotiated_secrets()).map_gen(|(version, (algo, secrets))| {
+        let server_keys = HandshakeKey::new_server(version, algo, secrets.clone()).unwrap();
+        let client_keys = HandshakeKey::new_client(version, algo, secrets).unwrap();
         CryptoTest::Handshake {
             server_keys,
             client_keys,
@@ -301,9 +308,9 @@ fn gen_handshake() -> impl ValueGenerator<Output = CryptoTest> {
 }
 
 fn gen_one_rtt() -> impl ValueGenerator<Output = CryptoTest> {
-    gen_negotiated_secrets().map(|(algo, secrets)| {
-        let server_keys = OneRttKey::new_server(algo, secrets.clone()).unwrap();
-        let client_keys = OneRttKey::new_client(algo, secrets).unwrap();
+    (gen_version(), gen_negotiated_secrets()).map_gen(|(version, (algo, secrets))| {
+        let server_keys = OneRttKey::new_server(version, algo, secrets.clone()).unwrap();
+        let client_keys = OneRttKey::new_client(version, algo, secrets).unwrap();
         CryptoTest::OneRtt {
             server_keys,
             client_keys,
@@ -312,8 +319,8 @@ fn gen_one_rtt() -> impl ValueGenerator<Output = CryptoTest> {
 }
 
 fn gen_zero_rtt() -> impl ValueGenerator<Output = CryptoTest> {
-    gen_secret(hkdf::HKDF_SHA256).map(|secret| {
-        let keys = ZeroRttKey::new(secret);
+    (gen_version(), gen_secret(hkdf::HKDF_SHA256)).map_gen(|(version, secret)| {
+        let keys = ZeroRttKey::new(version, secret);
         CryptoTest::ZeroRtt { keys }
     })
 }
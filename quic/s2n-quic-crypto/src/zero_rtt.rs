@@ -1,20 +1,35 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{cipher_suite::TLS_AES_128_GCM_SHA256 as CipherSuite, header_key::HeaderKey};
+use crate::{
+    cipher_suite::{NegotiatedCipherSuite, TLS_AES_128_GCM_SHA256},
+    header_key::HeaderKey,
+    Algorithm,
+};
 use s2n_quic_core::crypto::{self, CryptoError, HeaderProtectionMask, Key};
 
 #[derive(Debug)]
-pub struct ZeroRttKey(CipherSuite);
+pub struct ZeroRttKey(NegotiatedCipherSuite);
 
 impl ZeroRttKey {
-    /// Create a ZeroRTT cipher suite with a given secret
-    pub fn new(secret: crate::Prk) -> (Self, ZeroRttHeaderKey) {
-        let (key, header_key) = CipherSuite::new(secret);
-        let key = Self(key);
+    /// Create a ZeroRTT cipher suite for a QUIC version with a given secret
+    pub fn new(version: u32, secret: crate::Prk) -> (Self, ZeroRttHeaderKey) {
+        let (key, header_key) = TLS_AES_128_GCM_SHA256::new(version, secret);
+        let key = Self(key.into());
         let header_key = ZeroRttHeaderKey(header_key);
         (key, header_key)
     }
+
+    /// Create a ZeroRTT cipher suite for a QUIC version with the algorithm negotiated by the
+    /// session being resumed
+    pub fn new_negotiated(
+        version: u32,
+        algorithm: &Algorithm,
+        secret: crate::Prk,
+    ) -> Option<(Self, ZeroRttHeaderKey)> {
+        let (key, header_key) = NegotiatedCipherSuite::new(version, algorithm, secret)?;
+        Some((Self(key), ZeroRttHeaderKey(header_key)))
+    }
 }
 
 impl crypto::ZeroRttKey for ZeroRttKey {}
@@ -366,6 +366,23 @@ enum Frame {
     Datagram {
         len: u16,
     },
+    AckFrequency {
+        sequence_number: u64,
+        ack_eliciting_threshold: u64,
+        request_max_ack_delay: u64,
+        reordering_threshold: u64,
+    },
+    ImmediateAck,
+    AckMp {
+        path_identifier: u64,
+        ecn_counts: Option<EcnCounts>,
+        largest_acknowledged: u64,
+        ack_range_count: u64,
+    },
+    PathAbandon {
+        path_identifier: u64,
+        error_code: u64,
+    },
 }
 
 impl IntoEvent<builder::Frame> for &crate::frame::Padding {
@@ -566,6 +583,49 @@ where
     }
 }
 
+impl IntoEvent<builder::Frame> for &crate::frame::AckFrequency {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::AckFrequency {
+            sequence_number: self.sequence_number.as_u64(),
+            ack_eliciting_threshold: self.ack_eliciting_threshold.as_u64(),
+            request_max_ack_delay: self.request_max_ack_delay.as_u64(),
+            reordering_threshold: self.reordering_threshold.as_u64(),
+        }
+    }
+}
+
+impl IntoEvent<builder::Frame> for &crate::frame::ImmediateAck {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::ImmediateAck {}
+    }
+}
+
+impl<AckRanges: crate::frame::ack::AckRanges> IntoEvent<builder::Frame>
+    for &crate::frame::AckMp<AckRanges>
+{
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::AckMp {
+            path_identifier: self.path_identifier.as_u64(),
+            ecn_counts: self.ack.ecn_counts.map(|val| val.into_event()),
+            largest_acknowledged: self.ack.largest_acknowledged().into_event(),
+            ack_range_count: self.ack.ack_ranges().len() as u64,
+        }
+    }
+}
+
+impl<'a> IntoEvent<builder::Frame> for &crate::frame::PathAbandon<'a> {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::PathAbandon {
+            path_identifier: self.path_identifier.as_u64(),
+            error_code: self.error_code.as_u64(),
+        }
+    }
+}
+
 enum StreamType {
     Bidirectional,
     Unidirectional,
@@ -709,6 +769,8 @@ enum DatagramDropReason {
     PathLimitExceeded,
     /// The peer initiated a connection migration without supplying enough connection IDs to use.
     InsufficientConnectionIds,
+    /// The peer cleared the QUIC bit before the `grease_quic_bit` transport parameter was negotiated.
+    QuicBitCleared,
 }
 
 enum KeySpace {
@@ -749,11 +811,16 @@ enum PacketDropReason<'a> {
     DecodingFailed { path: Path<'a> },
     /// The client received a non-empty retry token.
     NonEmptyRetryToken { path: Path<'a> },
+    /// Early data was rejected for the connection and the 0-RTT packet was discarded.
+    EarlyDataRejected { path: Path<'a> },
     /// A Retry packet was discarded.
     RetryDiscarded {
         reason: RetryDiscardReason<'a>,
         path: Path<'a>,
     },
+    /// The packet had the QUIC bit set to 0 but the connection didn't advertise
+    /// the `grease_quic_bit` transport parameter.
+    QuicBitCleared { path: Path<'a> },
 }
 
 #[deprecated(note = "use on_rx_ack_range_dropped event instead")]
@@ -795,6 +862,17 @@ enum MigrationDenyReason {
     ConnectionMigrationDisabled,
 }
 
+enum EarlyDataRejectReason {
+    /// The early data controller rejected the early data
+    Rejected,
+    /// Early data is disabled on the endpoint
+    Disabled,
+    /// Too many connections have recently used early data
+    RateLimited,
+    /// The peer rejected the early data sent by the local endpoint
+    Peer,
+}
+
 /// The current state of the ECN controller for the path
 enum EcnState {
     /// ECN capability is being actively tested
@@ -884,6 +962,8 @@ enum MtuUpdatedCause {
     ProbeAcknowledged,
     /// A blackhole was detected
     Blackhole,
+    /// An ICMP Packet Too Big message reported a smaller MTU
+    PacketTooBig,
 }
 
 /// A bandwidth delivery rate estimate with associated metadata
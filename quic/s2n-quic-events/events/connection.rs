@@ -245,6 +245,66 @@ struct ConnectionMigrationDenied {
     reason: MigrationDenyReason,
 }
 
+#[event("connectivity:preferred_address_migrated")]
+/// The client validated the path to the server's preferred address and migrated to it
+struct PreferredAddressMigrated<'a> {
+    previous: Path<'a>,
+    active: Path<'a>,
+}
+
+#[event("connectivity:preferred_address_migration_failed")]
+/// The client was unable to validate the path to the server's preferred address
+///
+/// The connection continues to use the original path.
+struct PreferredAddressMigrationFailed<'a> {
+    path: Path<'a>,
+}
+
+#[event("connectivity:local_address_migrated")]
+/// The application migrated the connection to a new local address and the path was validated
+struct LocalAddressMigrated<'a> {
+    previous: Path<'a>,
+    active: Path<'a>,
+}
+
+#[event("connectivity:local_address_migration_failed")]
+/// The path from the new local address requested by the application failed validation
+///
+/// The connection continues to use the original path.
+struct LocalAddressMigrationFailed<'a> {
+    path: Path<'a>,
+}
+
+#[event("transport:early_data_accepted")]
+/// Early data (0-RTT) was accepted on the connection
+struct EarlyDataAccepted {}
+
+#[event("transport:early_data_rejected")]
+/// Early data (0-RTT) was rejected on the connection
+struct EarlyDataRejected {
+    reason: EarlyDataRejectReason,
+}
+
+#[event("transport:session_resumption")]
+/// The TLS handshake completed and reported if a previous session was resumed
+struct SessionResumption {
+    resumed: bool,
+}
+
+#[event("transport:version_upgrade_skipped")]
+/// The server kept the client's original QUIC version since the client didn't report its versions
+struct VersionUpgradeSkipped {
+    /// The version used by the connection
+    version: u32,
+}
+
+#[event("transport:server_certificate_selected")]
+/// The server presented a certificate from its certificate store
+struct ServerCertificateSelected<'a> {
+    /// The server name the certificate was added for, or `None` for the default certificate
+    server_name: Option<&'a str>,
+}
+
 #[event("connectivity:handshake_status_updated")]
 struct HandshakeStatusUpdated {
     status: HandshakeStatus,
@@ -323,3 +383,13 @@ struct BbrStateChanged {
     path_id: u64,
     state: BbrState,
 }
+
+#[event("transport:receive_window_updated")]
+/// A receive window was grown because the application is consuming data faster than the
+/// peer is able to send it
+struct ReceiveWindowUpdated {
+    /// The stream of the window, or `None` for the connection window
+    stream_id: Option<u64>,
+    /// The new size of the window
+    window: u64,
+}
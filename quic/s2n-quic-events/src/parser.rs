@@ -191,7 +191,7 @@ impl Struct {
                                 }
                             };
                             let api::#ident { #(#destructure_fields),* } = event;
-                            tracing::event!(target: #snake, parent: parent, tracing::Level::DEBUG, #(#destructure_fields = tracing::field::debug(#destructure_fields)),*);
+                            tracing::event!(target: #snake, parent: parent, tracing::Level::DEBUG, { #(#destructure_fields = tracing::field::debug(#destructure_fields)),* });
                         }
                     ));
 
@@ -255,7 +255,7 @@ impl Struct {
                         fn #function(&mut self, context: &mut Self::ConnectionContext, _meta: &api::ConnectionMeta, event: &api::#ident) {
                             let id = context.id();
                             let api::#ident { #(#destructure_fields),* } = event;
-                            tracing::event!(target: #snake, parent: id, tracing::Level::DEBUG, #(#destructure_fields = tracing::field::debug(#destructure_fields)),*);
+                            tracing::event!(target: #snake, parent: id, tracing::Level::DEBUG, { #(#destructure_fields = tracing::field::debug(#destructure_fields)),* });
                         }
                     ));
 
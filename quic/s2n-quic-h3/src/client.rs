@@ -0,0 +1,185 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sends HTTP/3 requests over an s2n-quic connection
+//!
+//! ```ignore
+//! let (mut client, mut driver) = s2n_quic_h3::client::Builder::default()
+//!     .connect(connection)
+//!     .await?;
+//! tokio::spawn(async move { driver.drive().await });
+//!
+//! let request = http::Request::get("https://localhost/").body(Bytes::new())?;
+//! let response = client.send(request).await?;
+//! ```
+
+use crate::{BidiStream, OpenStreams};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use http::{Request, Response};
+
+/// The default limit for response bodies
+const DEFAULT_MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// Configures HTTP/3 client connections
+#[derive(Clone, Debug)]
+pub struct Builder {
+    max_field_section_size: Option<u64>,
+    send_grease: bool,
+    max_body_size: usize,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            max_field_section_size: None,
+            send_grease: true,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+}
+
+impl Builder {
+    /// Sets the SETTINGS_MAX_FIELD_SECTION_SIZE which is advertised to the server
+    pub fn with_max_field_section_size(mut self, size: u64) -> Self {
+        self.max_field_section_size = Some(size);
+        self
+    }
+
+    /// Sets whether reserved SETTINGS, frame and stream types are sent to exercise the server
+    pub fn with_grease(mut self, enabled: bool) -> Self {
+        self.send_grease = enabled;
+        self
+    }
+
+    /// Sets the maximum size of a response body
+    pub fn with_max_body_size(mut self, size: usize) -> Self {
+        self.max_body_size = size;
+        self
+    }
+
+    /// Starts HTTP/3 on the connection
+    ///
+    /// The returned [`Driver`] must be polled for the connection to make progress.
+    pub async fn connect(
+        &self,
+        connection: impl Into<crate::Connection>,
+    ) -> Result<(Client, Driver), h3::Error> {
+        let mut builder = h3::client::builder();
+        if let Some(size) = self.max_field_section_size {
+            builder.max_field_section_size(size);
+        }
+        builder.send_grease(self.send_grease);
+
+        let (driver, send_request) = builder.build(connection.into()).await?;
+
+        let client = Client {
+            send_request,
+            max_body_size: self.max_body_size,
+        };
+
+        Ok((client, Driver(driver)))
+    }
+}
+
+/// Sends requests on an HTTP/3 connection
+///
+/// Clones of the client send requests on the same connection.
+#[derive(Clone)]
+pub struct Client {
+    send_request: h3::client::SendRequest<OpenStreams, Bytes>,
+    max_body_size: usize,
+}
+
+impl Client {
+    /// Sends the request and receives the full response
+    pub async fn send(&mut self, request: Request<Bytes>) -> Result<Response<Bytes>, Error> {
+        let (parts, body) = request.into_parts();
+        let mut stream = self
+            .send_request
+            .send_request(Request::from_parts(parts, ()))
+            .await?;
+
+        if !body.is_empty() {
+            stream.send_data(body).await?;
+        }
+        stream.finish().await?;
+
+        let response = stream.recv_response().await?;
+
+        let mut body = BytesMut::new();
+        while let Some(chunk) = stream.recv_data().await? {
+            if body.len() + chunk.remaining() > self.max_body_size {
+                return Err(Error::BodyTooLarge);
+            }
+            body.put(chunk);
+        }
+
+        let (parts, ()) = response.into_parts();
+        Ok(Response::from_parts(parts, body.freeze()))
+    }
+
+    /// Sends the request headers and waits for the response headers
+    ///
+    /// The request stream stays open, which is required for the extended CONNECT requests that
+    /// establish WebTransport sessions. The session ID is the ID of the returned stream. The
+    /// connection should be created with [`crate::Connection::with_webtransport`] to receive the
+    /// streams of sessions.
+    ///
+    /// The h3 client doesn't advertise SETTINGS_H3_DATAGRAM or the WebTransport SETTINGS, so
+    /// sessions can only be established with servers which don't require them from clients, such
+    /// as the [`crate::server`] of this crate.
+    pub async fn open(
+        &mut self,
+        request: Request<()>,
+    ) -> Result<(Response<()>, RequestStream), Error> {
+        let mut stream = self.send_request.send_request(request).await?;
+        let response = stream.recv_response().await?;
+        Ok((response, stream))
+    }
+}
+
+/// A request stream which was opened with [`Client::open`]
+pub type RequestStream = h3::client::RequestStream<BidiStream<Bytes>, Bytes>;
+
+/// Drives an HTTP/3 client connection
+pub struct Driver(h3::client::Connection<crate::Connection, Bytes>);
+
+impl Driver {
+    /// Processes the connection until it is closed
+    ///
+    /// `Ok` is returned if the connection was closed without an error.
+    pub async fn drive(&mut self) -> Result<(), h3::Error> {
+        futures::future::poll_fn(|cx| self.0.poll_close(cx)).await
+    }
+
+    /// Sends a GOAWAY frame and waits for the connection to close
+    pub async fn shutdown(&mut self) -> Result<(), h3::Error> {
+        self.0.shutdown(0).await?;
+        self.drive().await
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The response body exceeded the configured limit
+    BodyTooLarge,
+    Http(h3::Error),
+}
+
+impl std::error::Error for Error {}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::BodyTooLarge => write!(f, "the response body exceeded the limit"),
+            Self::Http(error) => error.fmt(f),
+        }
+    }
+}
+
+impl From<h3::Error> for Error {
+    fn from(error: h3::Error) -> Self {
+        Self::Http(error)
+    }
+}
@@ -0,0 +1,324 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! HTTP/3 datagrams, as specified in [RFC 9297](https://www.rfc-editor.org/rfc/rfc9297)
+//!
+//! HTTP/3 datagrams are carried in QUIC DATAGRAM frames. Each datagram starts with the quarter
+//! stream ID of the client-initiated bidirectional request stream it is associated with. The
+//! connection must be configured with the default datagram provider, which requires the
+//! `unstable-provider-datagram` feature of s2n-quic. Servers advertise support with
+//! [`crate::server::Builder::with_datagrams`].
+
+use bytes::Bytes;
+use s2n_codec::{DecoderBuffer, Encoder, EncoderBuffer, EncoderValue};
+use s2n_quic_core::{
+    datagram::default::{DatagramError, Receiver, Sender},
+    varint::VarInt,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+};
+
+/// The maximum number of datagrams which are buffered for each stream
+///
+/// Older datagrams are dropped when the application doesn't receive them fast enough.
+const STREAM_QUEUE_CAPACITY: usize = 64;
+
+/// An HTTP/3 datagram associated with a request stream
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Datagram {
+    stream_id: u64,
+    payload: Bytes,
+}
+
+impl Datagram {
+    /// Creates a datagram for the client-initiated bidirectional stream `stream_id`
+    pub fn new(stream_id: u64, payload: Bytes) -> Result<Self, Error> {
+        if !is_request_stream(stream_id) {
+            return Err(Error::InvalidStreamId);
+        }
+
+        Ok(Self { stream_id, payload })
+    }
+
+    /// Decodes a datagram from the payload of a QUIC DATAGRAM frame
+    pub fn decode(datagram: Bytes) -> Result<Self, Error> {
+        let buffer = DecoderBuffer::new(&datagram);
+        let (quarter_stream_id, remaining) = buffer
+            .decode::<VarInt>()
+            .map_err(|_| Error::MalformedDatagram)?;
+        let header_len = datagram.len() - remaining.len();
+
+        //= https://www.rfc-editor.org/rfc/rfc9297#section-2.1
+        //# This field MUST be treated as a connection error of type
+        //# H3_DATAGRAM_ERROR if it has a value greater than 2^60 - 1.
+        let stream_id = quarter_stream_id
+            .as_u64()
+            .checked_mul(4)
+            .filter(|stream_id| VarInt::new(*stream_id).is_ok())
+            .ok_or(Error::InvalidStreamId)?;
+
+        Ok(Self {
+            stream_id,
+            payload: datagram.slice(header_len..),
+        })
+    }
+
+    /// Encodes the datagram into the payload of a QUIC DATAGRAM frame
+    pub fn encode(&self) -> Bytes {
+        // the stream ID is validated on creation so the quarter stream ID fits in a VarInt
+        let quarter_stream_id = VarInt::new(self.stream_id / 4).expect("valid stream id");
+        let header_len = quarter_stream_id.encoding_size();
+
+        let mut bytes = vec![0; header_len + self.payload.len()];
+        let mut buffer = EncoderBuffer::new(&mut bytes);
+        buffer.encode(&quarter_stream_id);
+        buffer.write_slice(&self.payload);
+
+        bytes.into()
+    }
+
+    /// Returns the ID of the request stream the datagram is associated with
+    pub fn stream_id(&self) -> u64 {
+        self.stream_id
+    }
+
+    /// Returns the payload of the datagram
+    pub fn payload(&self) -> &Bytes {
+        &self.payload
+    }
+
+    /// Returns the payload of the datagram
+    pub fn into_payload(self) -> Bytes {
+        self.payload
+    }
+}
+
+/// Returns `true` if the stream is a client-initiated bidirectional stream
+#[inline]
+pub(crate) fn is_request_stream(stream_id: u64) -> bool {
+    stream_id % 4 == 0 && VarInt::new(stream_id).is_ok()
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Datagrams can only be associated with client-initiated bidirectional streams
+    InvalidStreamId,
+    /// The datagram doesn't start with a quarter stream ID
+    MalformedDatagram,
+    /// The stream was not registered to receive datagrams
+    UnknownStream,
+    /// The datagram could not be sent or received
+    Datagram(DatagramError),
+    /// The datagram provider of the connection could not be accessed
+    Query(s2n_quic_core::query::Error),
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidStreamId => write!(
+                f,
+                "datagrams can only be associated with client-initiated bidirectional streams"
+            ),
+            Self::MalformedDatagram => write!(f, "the datagram has no quarter stream ID"),
+            Self::UnknownStream => write!(f, "the stream was not registered for datagrams"),
+            Self::Datagram(error) => error.fmt(f),
+            Self::Query(error) => error.fmt(f),
+        }
+    }
+}
+
+impl From<DatagramError> for Error {
+    fn from(error: DatagramError) -> Self {
+        Self::Datagram(error)
+    }
+}
+
+impl From<s2n_quic_core::query::Error> for Error {
+    fn from(error: s2n_quic_core::query::Error) -> Self {
+        Self::Query(error)
+    }
+}
+
+/// Sends and receives the HTTP/3 datagrams of a connection
+///
+/// Received datagrams are dispatched to the request streams which were registered with
+/// [`Datagrams::register`]. Datagrams for other streams are dropped.
+#[derive(Clone)]
+pub struct Datagrams {
+    conn: s2n_quic::connection::Handle,
+    streams: Arc<Mutex<HashMap<u64, StreamQueue>>>,
+    wakers: Arc<Wakers>,
+}
+
+#[derive(Default)]
+struct StreamQueue {
+    datagrams: VecDeque<Bytes>,
+    waker: Option<Waker>,
+}
+
+/// Wakes every stream waiting for a datagram when the connection receives one
+///
+/// The wakers are stored separately from the stream queues since they are woken while the
+/// connection is locked.
+#[derive(Default)]
+struct Wakers(Mutex<HashMap<u64, Waker>>);
+
+impl Wake for Wakers {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        let wakers = core::mem::take(&mut *self.0.lock().unwrap());
+        for waker in wakers.into_values() {
+            waker.wake();
+        }
+    }
+}
+
+impl Datagrams {
+    pub fn new(conn: s2n_quic::connection::Handle) -> Self {
+        Self {
+            conn,
+            streams: Default::default(),
+            wakers: Default::default(),
+        }
+    }
+
+    /// Starts buffering datagrams received for the request stream
+    pub fn register(&self, stream_id: u64) -> Result<(), Error> {
+        if !is_request_stream(stream_id) {
+            return Err(Error::InvalidStreamId);
+        }
+
+        self.streams.lock().unwrap().entry(stream_id).or_default();
+        Ok(())
+    }
+
+    /// Drops the datagrams buffered for the request stream and stops receiving new ones
+    pub fn unregister(&self, stream_id: u64) {
+        self.streams.lock().unwrap().remove(&stream_id);
+        self.wakers.0.lock().unwrap().remove(&stream_id);
+    }
+
+    /// Enqueues a datagram for the request stream
+    pub fn send(&self, stream_id: u64, payload: Bytes) -> Result<(), Error> {
+        let datagram = Datagram::new(stream_id, payload)?.encode();
+        self.conn
+            .datagram_mut(|sender: &mut Sender| sender.send_datagram(datagram))??;
+        Ok(())
+    }
+
+    /// Polls for the next datagram received for the request stream
+    pub fn poll_recv(&self, stream_id: u64, cx: &mut Context) -> Poll<Result<Bytes, Error>> {
+        let mut streams = self.streams.lock().unwrap();
+
+        if !streams.contains_key(&stream_id) {
+            return Err(Error::UnknownStream).into();
+        }
+
+        // Register the waker before draining the connection so a datagram that arrives
+        // in between isn't missed
+        self.wakers
+            .0
+            .lock()
+            .unwrap()
+            .insert(stream_id, cx.waker().clone());
+
+        let waker = Waker::from(self.wakers.clone());
+        let mut recv_cx = Context::from_waker(&waker);
+        let result = self.conn.datagram_mut(|receiver: &mut Receiver| loop {
+            match receiver.poll_recv_datagram(&mut recv_cx) {
+                Poll::Ready(Ok(datagram)) => dispatch(&mut streams, datagram),
+                Poll::Ready(Err(error)) => return Err(error),
+                Poll::Pending => return Ok(()),
+            }
+        });
+
+        let queue = streams.get_mut(&stream_id).expect("stream is registered");
+
+        if let Some(datagram) = queue.datagrams.pop_front() {
+            self.wakers.0.lock().unwrap().remove(&stream_id);
+            return Ok(datagram).into();
+        }
+
+        // only report errors once the buffered datagrams are received
+        result??;
+
+        queue.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Receives the next datagram for the request stream
+    pub async fn recv(&self, stream_id: u64) -> Result<Bytes, Error> {
+        futures::future::poll_fn(|cx| self.poll_recv(stream_id, cx)).await
+    }
+}
+
+/// Queues the datagram on the stream it is associated with
+fn dispatch(streams: &mut HashMap<u64, StreamQueue>, datagram: Bytes) {
+    // Malformed datagrams and datagrams for unknown streams are dropped, since
+    // datagrams are unreliable and the stream might have been closed already
+    let datagram = match Datagram::decode(datagram) {
+        Ok(datagram) => datagram,
+        Err(_) => return,
+    };
+
+    if let Some(queue) = streams.get_mut(&datagram.stream_id) {
+        if queue.datagrams.len() == STREAM_QUEUE_CAPACITY {
+            queue.datagrams.pop_front();
+        }
+        queue.datagrams.push_back(datagram.payload);
+
+        if let Some(waker) = queue.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        let datagram = Datagram::new(8, Bytes::from_static(b"hello")).unwrap();
+        let encoded = datagram.encode();
+        // the quarter stream ID is encoded in front of the payload
+        assert_eq!(&encoded[..], b"\x02hello");
+        assert_eq!(Datagram::decode(encoded).unwrap(), datagram);
+    }
+
+    #[test]
+    fn invalid_stream_test() {
+        // server-initiated and unidirectional streams can't carry datagrams
+        for stream_id in [1, 2, 3, 5] {
+            assert!(matches!(
+                Datagram::new(stream_id, Bytes::new()),
+                Err(Error::InvalidStreamId)
+            ));
+        }
+
+        assert!(matches!(
+            Datagram::decode(Bytes::new()),
+            Err(Error::MalformedDatagram)
+        ));
+
+        // 2^60 is the smallest quarter stream ID that is too large
+        let quarter_stream_id = VarInt::new(1 << 60).unwrap();
+        let mut bytes = vec![0; quarter_stream_id.encoding_size()];
+        EncoderBuffer::new(&mut bytes).encode(&quarter_stream_id);
+        assert!(matches!(
+            Datagram::decode(bytes.into()),
+            Err(Error::InvalidStreamId)
+        ));
+    }
+}
@@ -1,7 +1,14 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod client;
+pub mod datagram;
 mod s2n_quic;
+pub mod server;
+pub mod webtransport;
+
+#[cfg(test)]
+mod tests;
 
 pub use self::s2n_quic::*;
 pub use h3;
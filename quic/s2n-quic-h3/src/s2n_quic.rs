@@ -1,6 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::webtransport::{self, Header, HeaderReader, Sessions};
 use bytes::{Buf, Bytes};
 use futures::ready;
 use h3::quic::{self, Error, StreamId, WriteBuf};
@@ -17,6 +18,7 @@ pub struct Connection {
     conn: s2n_quic::connection::Handle,
     bidi_acceptor: s2n_quic::connection::BidirectionalStreamAcceptor,
     recv_acceptor: s2n_quic::connection::ReceiveStreamAcceptor,
+    webtransport: Option<WebTransport>,
 }
 
 impl Connection {
@@ -28,8 +30,156 @@ impl Connection {
             conn: handle,
             bidi_acceptor: bidi,
             recv_acceptor: recv,
+            webtransport: None,
         }
     }
+
+    /// Creates a connection which routes the streams of WebTransport sessions to the returned
+    /// [`Sessions`]
+    ///
+    /// All other streams are handled by HTTP/3. Streams are routed while HTTP/3 accepts streams,
+    /// so servers must keep accepting requests and clients must keep driving the connection
+    /// for the sessions to receive streams.
+    pub fn with_webtransport(new_conn: s2n_quic::Connection) -> (Self, Sessions) {
+        let mut conn = Self::new(new_conn);
+        let sessions = Sessions::new(conn.conn.clone());
+
+        conn.webtransport = Some(WebTransport {
+            sessions: sessions.clone(),
+            bidi_streams: Vec::new(),
+            recv_streams: Vec::new(),
+        });
+
+        (conn, sessions)
+    }
+}
+
+impl From<s2n_quic::Connection> for Connection {
+    fn from(new_conn: s2n_quic::Connection) -> Self {
+        Self::new(new_conn)
+    }
+}
+
+/// Streams which were accepted from the peer but have not been routed yet
+struct WebTransport {
+    sessions: Sessions,
+    bidi_streams: Vec<(BidirectionalStream, HeaderReader)>,
+    recv_streams: Vec<(ReceiveStream, HeaderReader)>,
+}
+
+impl WebTransport {
+    fn poll_accept_recv(
+        &mut self,
+        acceptor: &mut s2n_quic::connection::ReceiveStreamAcceptor,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<RecvStream>, ConnectionError>> {
+        loop {
+            match acceptor.poll_accept_receive_stream(cx) {
+                Poll::Ready(Ok(Some(stream))) => {
+                    let header = HeaderReader::new(webtransport::UNI_STREAM_TYPE);
+                    self.recv_streams.push((stream, header));
+                }
+                Poll::Ready(Ok(None)) => {
+                    self.sessions.on_close(None);
+                    return Ok(None).into();
+                }
+                Poll::Ready(Err(error)) => {
+                    self.sessions.on_close(Some(error));
+                    return Err(error.into()).into();
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        let mut index = 0;
+        while index < self.recv_streams.len() {
+            let (stream, header) = &mut self.recv_streams[index];
+            let header = match header.poll_read(cx, |cx| stream.poll_receive(cx)) {
+                Poll::Ready(header) => header,
+                Poll::Pending => {
+                    index += 1;
+                    continue;
+                }
+            };
+
+            let (stream, _) = self.recv_streams.swap_remove(index);
+            match header {
+                Ok(Header::WebTransport {
+                    session_id,
+                    buffered,
+                }) => self
+                    .sessions
+                    .on_receive_stream(session_id, stream, buffered),
+                Ok(Header::Http(buffered)) => {
+                    return Ok(Some(RecvStream::with_buffered(stream, buffered))).into()
+                }
+                // let HTTP/3 observe the stream error
+                Err(_) => return Ok(Some(RecvStream::new(stream))).into(),
+            }
+        }
+
+        Poll::Pending
+    }
+
+    fn poll_accept_bidi<B: Buf>(
+        &mut self,
+        acceptor: &mut s2n_quic::connection::BidirectionalStreamAcceptor,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<BidiStream<B>>, ConnectionError>> {
+        loop {
+            match acceptor.poll_accept_bidirectional_stream(cx) {
+                Poll::Ready(Ok(Some(stream))) => {
+                    let header = HeaderReader::new(webtransport::BIDI_SIGNAL_VALUE);
+                    self.bidi_streams.push((stream, header));
+                }
+                Poll::Ready(Ok(None)) => {
+                    self.sessions.on_close(None);
+                    return Ok(None).into();
+                }
+                Poll::Ready(Err(error)) => {
+                    self.sessions.on_close(Some(error));
+                    return Err(error.into()).into();
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        let mut index = 0;
+        while index < self.bidi_streams.len() {
+            let (stream, header) = &mut self.bidi_streams[index];
+            let header = match header.poll_read(cx, |cx| stream.poll_receive(cx)) {
+                Poll::Ready(header) => header,
+                Poll::Pending => {
+                    index += 1;
+                    continue;
+                }
+            };
+
+            let (stream, _) = self.bidi_streams.swap_remove(index);
+            let buffered = match header {
+                Ok(Header::WebTransport {
+                    session_id,
+                    buffered,
+                }) => {
+                    self.sessions
+                        .on_bidirectional_stream(session_id, stream, buffered);
+                    continue;
+                }
+                Ok(Header::Http(buffered)) => buffered,
+                // let HTTP/3 observe the stream error
+                Err(_) => Bytes::new(),
+            };
+
+            let (recv, send) = stream.split();
+            return Ok(Some(BidiStream {
+                send: SendStream::new(send),
+                recv: RecvStream::with_buffered(recv, buffered),
+            }))
+            .into();
+        }
+
+        Poll::Pending
+    }
 }
 
 #[derive(Debug)]
@@ -76,6 +226,10 @@ where
         &mut self,
         cx: &mut task::Context<'_>,
     ) -> Poll<Result<Option<Self::RecvStream>, Self::Error>> {
+        if let Some(webtransport) = self.webtransport.as_mut() {
+            return webtransport.poll_accept_recv(&mut self.recv_acceptor, cx);
+        }
+
         let recv = match ready!(self.recv_acceptor.poll_accept_receive_stream(cx))? {
             Some(x) => x,
             None => return Poll::Ready(Ok(None)),
@@ -87,6 +241,10 @@ where
         &mut self,
         cx: &mut task::Context<'_>,
     ) -> Poll<Result<Option<Self::BidiStream>, Self::Error>> {
+        if let Some(webtransport) = self.webtransport.as_mut() {
+            return webtransport.poll_accept_bidi(&mut self.bidi_acceptor, cx);
+        }
+
         let (recv, send) = match ready!(self.bidi_acceptor.poll_accept_bidirectional_stream(cx))? {
             Some(x) => x.split(),
             None => return Poll::Ready(Ok(None)),
@@ -211,6 +369,10 @@ where
     fn stop_sending(&mut self, error_code: u64) {
         self.recv.stop_sending(error_code)
     }
+
+    fn recv_id(&self) -> StreamId {
+        self.recv.recv_id()
+    }
 }
 
 impl<B> quic::SendStream<B> for BidiStream<B>
@@ -235,8 +397,8 @@ where
         self.send.send_data(data)
     }
 
-    fn id(&self) -> StreamId {
-        self.send.id()
+    fn send_id(&self) -> StreamId {
+        self.send.send_id()
     }
 }
 
@@ -255,11 +417,23 @@ where
 
 pub struct RecvStream {
     stream: s2n_quic::stream::ReceiveStream,
+    /// Data which was received while checking for a WebTransport stream header
+    buffered: Option<Bytes>,
 }
 
 impl RecvStream {
     fn new(stream: s2n_quic::stream::ReceiveStream) -> Self {
-        Self { stream }
+        Self {
+            stream,
+            buffered: None,
+        }
+    }
+
+    fn with_buffered(stream: s2n_quic::stream::ReceiveStream, buffered: Bytes) -> Self {
+        Self {
+            stream,
+            buffered: Some(buffered).filter(|buffered| !buffered.is_empty()),
+        }
     }
 }
 
@@ -271,16 +445,25 @@ impl quic::RecvStream for RecvStream {
         &mut self,
         cx: &mut task::Context<'_>,
     ) -> Poll<Result<Option<Self::Buf>, Self::Error>> {
+        if let Some(buffered) = self.buffered.take() {
+            return Ok(Some(buffered)).into();
+        }
+
         let buf = ready!(self.stream.poll_receive(cx))?;
         Ok(buf).into()
     }
 
     fn stop_sending(&mut self, error_code: u64) {
+        self.buffered = None;
         let _ = self.stream.stop_sending(
             s2n_quic::application::Error::new(error_code)
                 .expect("s2n-quic supports error codes up to 2^62-1"),
         );
     }
+
+    fn recv_id(&self) -> StreamId {
+        self.stream.id().try_into().expect("invalid stream id")
+    }
 }
 
 impl From<ReceiveStream> for RecvStream {
@@ -422,7 +605,9 @@ where
     fn poll_finish(&mut self, cx: &mut task::Context<'_>) -> Poll<Result<(), Self::Error>> {
         // ensure all chunks are flushed to the QUIC stream before finishing
         ready!(self.poll_ready(cx))?;
-        self.stream.finish()?;
+        // wait for the peer to acknowledge the stream so closing the connection afterwards, for
+        // example after draining the requests on shutdown, doesn't discard the data
+        ready!(self.stream.poll_close(cx))?;
         Ok(()).into()
     }
 
@@ -432,7 +617,7 @@ where
             .reset(reset_code.try_into().unwrap_or_else(|_| VarInt::MAX.into()));
     }
 
-    fn id(&self) -> StreamId {
+    fn send_id(&self) -> StreamId {
         self.stream.id().try_into().expect("invalid stream id")
     }
 }
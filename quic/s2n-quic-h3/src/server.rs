@@ -0,0 +1,310 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Serves HTTP/3 requests with a [`Service`]
+//!
+//! ```ignore
+//! let server = s2n_quic_h3::server::Builder::default().with_max_body_size(1 << 20);
+//!
+//! while let Some(connection) = quic_server.accept().await {
+//!     let server = server.clone();
+//!     let service = service.clone();
+//!     tokio::spawn(async move {
+//!         let _ = server.serve(connection, service, shutdown_signal()).await;
+//!     });
+//! }
+//! ```
+
+use crate::BidiStream;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use core::future::Future;
+use futures::{
+    future::poll_fn,
+    stream::{FuturesUnordered, StreamExt},
+};
+use h3::{
+    error::{Code, ErrorLevel},
+    server::RequestStream,
+    Error,
+};
+use http::{Request, Response, StatusCode};
+use std::task::Poll;
+use tower_service::Service;
+
+/// The default limit for request bodies
+const DEFAULT_MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// Configures how HTTP/3 connections are served
+#[derive(Clone, Debug)]
+pub struct Builder {
+    max_field_section_size: Option<u64>,
+    send_grease: bool,
+    enable_datagrams: bool,
+    max_webtransport_sessions: u64,
+    max_body_size: usize,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            max_field_section_size: None,
+            send_grease: true,
+            enable_datagrams: false,
+            max_webtransport_sessions: 0,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+}
+
+impl Builder {
+    /// Sets the SETTINGS_MAX_FIELD_SECTION_SIZE which is advertised to the client
+    ///
+    /// Requests with larger header sections are rejected.
+    pub fn with_max_field_section_size(mut self, size: u64) -> Self {
+        self.max_field_section_size = Some(size);
+        self
+    }
+
+    /// Sets whether reserved SETTINGS, frame and stream types are sent to exercise the client
+    pub fn with_grease(mut self, enabled: bool) -> Self {
+        self.send_grease = enabled;
+        self
+    }
+
+    /// Sets whether SETTINGS_H3_DATAGRAM is advertised to the client
+    ///
+    /// Datagrams are sent and received with [`crate::datagram::Datagrams`], which requires the
+    /// connection to be configured with the default datagram provider.
+    pub fn with_datagrams(mut self, enabled: bool) -> Self {
+        self.enable_datagrams = enabled;
+        self
+    }
+
+    /// Enables WebTransport with up to `max_sessions` sessions on each connection
+    ///
+    /// This advertises SETTINGS_ENABLE_WEBTRANSPORT and SETTINGS_WEBTRANSPORT_MAX_SESSIONS, along
+    /// with SETTINGS_ENABLE_CONNECT_PROTOCOL for the extended CONNECT requests which establish
+    /// sessions and SETTINGS_H3_DATAGRAM for the datagrams of sessions. Connections should be
+    /// created with [`crate::Connection::with_webtransport`]. A value of `0` disables WebTransport.
+    pub fn with_webtransport(mut self, max_sessions: u64) -> Self {
+        self.max_webtransport_sessions = max_sessions;
+        self
+    }
+
+    /// Sets the maximum size of a request body
+    ///
+    /// Requests with larger bodies are answered with `413 Payload Too Large`.
+    pub fn with_max_body_size(mut self, size: usize) -> Self {
+        self.max_body_size = size;
+        self
+    }
+
+    /// Starts HTTP/3 on the connection with the configured SETTINGS
+    ///
+    /// This can be used instead of [`Self::serve`] to accept requests directly, for example
+    /// the extended CONNECT requests of WebTransport sessions.
+    pub async fn build(
+        &self,
+        connection: impl Into<crate::Connection>,
+    ) -> Result<h3::server::Connection<crate::Connection, Bytes>, Error> {
+        let mut builder = h3::server::builder();
+        if let Some(size) = self.max_field_section_size {
+            builder.max_field_section_size(size);
+        }
+        builder.send_grease(self.send_grease);
+
+        let enable_webtransport = self.max_webtransport_sessions > 0;
+        builder
+            .enable_datagram(self.enable_datagrams || enable_webtransport)
+            .enable_webtransport(enable_webtransport)
+            .enable_connect(enable_webtransport)
+            .max_webtransport_sessions(self.max_webtransport_sessions);
+
+        builder.build(connection.into()).await
+    }
+
+    /// Serves the requests on the connection until the client closes it or `shutdown` completes
+    ///
+    /// Once `shutdown` completes, a GOAWAY frame stops the client from sending new requests and
+    /// the requests which were already accepted are completed before the connection is closed.
+    ///
+    /// Requests which fail are reset with an HTTP/3 error code, which is how the client is notified
+    /// of the failure. Connection errors, either while accepting requests or while responding to
+    /// them, are returned.
+    pub async fn serve<S, F>(
+        &self,
+        connection: impl Into<crate::Connection>,
+        service: S,
+        shutdown: F,
+    ) -> Result<(), Error>
+    where
+        S: Service<Request<Bytes>, Response = Response<Bytes>> + Clone,
+        F: Future<Output = ()>,
+    {
+        let mut conn = self.build(connection).await?;
+
+        let mut requests = FuturesUnordered::new();
+        let mut is_shutting_down = false;
+        futures::pin_mut!(shutdown);
+
+        let result = loop {
+            let event = {
+                let accept = conn.accept();
+                futures::pin_mut!(accept);
+
+                poll_fn(|cx| {
+                    // make progress on the requests while waiting for the next one
+                    while let Poll::Ready(Some(result)) = requests.poll_next_unpin(cx) {
+                        if let Err(error) = result {
+                            return Poll::Ready(Event::Failed(error));
+                        }
+                    }
+
+                    if is_shutting_down {
+                        if requests.is_empty() {
+                            return Poll::Ready(Event::Drained);
+                        }
+                    } else if shutdown.as_mut().poll(cx).is_ready() {
+                        return Poll::Ready(Event::Shutdown);
+                    }
+
+                    // h3 rejects the requests which arrive after the GOAWAY frame was sent, so
+                    // the connection keeps being polled while the in-flight requests complete
+                    accept.as_mut().poll(cx).map(Event::Accepted)
+                })
+                .await
+            };
+
+            match event {
+                Event::Shutdown => {
+                    is_shutting_down = true;
+                    if let Err(error) = conn.shutdown(0).await {
+                        break Err(error);
+                    }
+                }
+                Event::Drained => break Ok(()),
+                Event::Failed(error) => break Err(error),
+                Event::Accepted(Ok(Some((request, stream)))) => {
+                    let service = service.clone();
+                    requests.push(respond(service, request, stream, self.max_body_size));
+                }
+                Event::Accepted(Ok(None)) => break Ok(()),
+                // h3 resets the streams of requests which can't be accepted
+                Event::Accepted(Err(error)) if !is_connection_error(&error) => continue,
+                Event::Accepted(Err(error)) if is_no_error(&error) => break Ok(()),
+                Event::Accepted(Err(error)) => break Err(error),
+            }
+        };
+
+        // the remaining requests fail quickly if the connection was closed
+        while requests.next().await.is_some() {}
+
+        result
+    }
+}
+
+enum Event<R> {
+    /// The shutdown future completed
+    Shutdown,
+    /// All of the in-flight requests completed after shutting down
+    Drained,
+    /// A request failed with a connection error
+    Failed(Error),
+    /// The connection accepted a request or was closed
+    Accepted(Result<Option<R>, Error>),
+}
+
+type Stream = RequestStream<BidiStream<Bytes>, Bytes>;
+
+/// Responds to the request
+///
+/// Stream errors are reported to the client by resetting the stream and only connection errors
+/// are returned.
+async fn respond<S>(
+    service: S,
+    request: Request<()>,
+    mut stream: Stream,
+    max_body_size: usize,
+) -> Result<(), Error>
+where
+    S: Service<Request<Bytes>, Response = Response<Bytes>>,
+{
+    let error = match try_respond(service, request, &mut stream, max_body_size).await {
+        Ok(()) => return Ok(()),
+        Err(error) => error,
+    };
+
+    if is_connection_error(&error) {
+        if is_no_error(&error) {
+            return Ok(());
+        }
+        return Err(error);
+    }
+
+    let code = error.try_get_code().unwrap_or(Code::H3_INTERNAL_ERROR);
+    stream.stop_sending(code);
+    stream.stop_stream(code);
+
+    Ok(())
+}
+
+#[inline]
+fn is_connection_error(error: &Error) -> bool {
+    matches!(error.get_error_level(), ErrorLevel::ConnectionError)
+}
+
+/// Returns `true` if the connection was closed without an error
+#[inline]
+fn is_no_error(error: &Error) -> bool {
+    error.try_get_code() == Some(Code::H3_NO_ERROR)
+}
+
+async fn try_respond<S>(
+    mut service: S,
+    request: Request<()>,
+    stream: &mut Stream,
+    max_body_size: usize,
+) -> Result<(), Error>
+where
+    S: Service<Request<Bytes>, Response = Response<Bytes>>,
+{
+    let mut body = BytesMut::new();
+    while let Some(chunk) = stream.recv_data().await? {
+        if body.len() + chunk.remaining() > max_body_size {
+            return send_status(stream, StatusCode::PAYLOAD_TOO_LARGE).await;
+        }
+        body.put(chunk);
+    }
+
+    let (parts, ()) = request.into_parts();
+    let request = Request::from_parts(parts, body.freeze());
+
+    if poll_fn(|cx| service.poll_ready(cx)).await.is_err() {
+        return send_status(stream, StatusCode::SERVICE_UNAVAILABLE).await;
+    }
+
+    let response = match service.call(request).await {
+        Ok(response) => response,
+        Err(_) => return send_status(stream, StatusCode::INTERNAL_SERVER_ERROR).await,
+    };
+
+    let (parts, body) = response.into_parts();
+    stream
+        .send_response(Response::from_parts(parts, ()))
+        .await?;
+
+    if !body.is_empty() {
+        stream.send_data(body).await?;
+    }
+
+    stream.finish().await
+}
+
+async fn send_status(stream: &mut Stream, status: StatusCode) -> Result<(), Error> {
+    let response = Response::builder()
+        .status(status)
+        .body(())
+        .expect("a status code is a valid response");
+    stream.send_response(response).await?;
+    stream.finish().await
+}
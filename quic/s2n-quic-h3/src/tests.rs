@@ -0,0 +1,307 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{client, server, Connection};
+use bytes::Bytes;
+use core::{
+    convert::Infallible,
+    future::Future,
+    task::{Context, Poll},
+};
+use futures::future::{BoxFuture, FutureExt};
+use http::{Method, Request, Response, StatusCode};
+use s2n_quic::{client::Connect, provider::datagram::default::Endpoint, Client, Server};
+use s2n_quic_core::crypto::tls::testing::certificates;
+use std::{net::SocketAddr, time::Duration};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::timeout,
+};
+use tower_service::Service;
+
+/// Fails tests which don't complete instead of hanging the test run
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn datagram_endpoint() -> Endpoint {
+    Endpoint::builder()
+        .with_send_capacity(16)
+        .unwrap()
+        .with_recv_capacity(16)
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+fn start_server() -> Server {
+    Server::builder()
+        .with_tls((certificates::CERT_PEM, certificates::KEY_PEM))
+        .unwrap()
+        .with_io("127.0.0.1:0")
+        .unwrap()
+        .with_datagram(datagram_endpoint())
+        .unwrap()
+        .start()
+        .unwrap()
+}
+
+/// Returns the client along with the connection since the connection is closed once the
+/// client is dropped
+async fn connect(server_addr: SocketAddr) -> (Client, s2n_quic::Connection) {
+    let client = Client::builder()
+        .with_tls(certificates::CERT_PEM)
+        .unwrap()
+        .with_io("127.0.0.1:0")
+        .unwrap()
+        .with_datagram(datagram_endpoint())
+        .unwrap()
+        .start()
+        .unwrap();
+
+    let connect = Connect::new(server_addr).with_server_name("localhost");
+    let connection = client.connect(connect).await.unwrap();
+    (client, connection)
+}
+
+/// Responds to requests with an async function
+#[derive(Clone)]
+struct ServiceFn<F>(F);
+
+impl<F, Fut> Service<Request<Bytes>> for ServiceFn<F>
+where
+    F: FnMut(Request<Bytes>) -> Fut,
+    Fut: Future<Output = Response<Bytes>> + Send + 'static,
+{
+    type Response = Response<Bytes>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response<Bytes>, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Bytes>) -> Self::Future {
+        (self.0)(request).map(Ok).boxed()
+    }
+}
+
+fn post(body: &'static [u8]) -> Request<Bytes> {
+    Request::post("https://localhost/")
+        .body(Bytes::from_static(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn serve_test() {
+    let mut server = start_server();
+    let server_addr = server.local_addr().unwrap();
+
+    let server_task = tokio::spawn(async move {
+        let connection = server.accept().await.unwrap();
+        let echo =
+            ServiceFn(|request: Request<Bytes>| async move { Response::new(request.into_body()) });
+        server::Builder::default()
+            .with_max_body_size(16)
+            .serve(connection, echo, futures::future::pending())
+            .await
+    });
+
+    let (_client, connection) = connect(server_addr).await;
+    let (mut client, mut driver) = client::Builder::default()
+        .connect(connection)
+        .await
+        .unwrap();
+    let driver = tokio::spawn(async move { driver.drive().await });
+
+    timeout(TIMEOUT, async {
+        let response = client.send(post(b"hello")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(&response.body()[..], b"hello");
+
+        let response = client.send(post(&[1; 17])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(response.body().is_empty());
+    })
+    .await
+    .unwrap();
+
+    // dropping the last client closes the connection without an error
+    drop(client);
+    timeout(TIMEOUT, driver).await.unwrap().unwrap().unwrap();
+    timeout(TIMEOUT, server_task)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn goaway_test() {
+    let mut server = start_server();
+    let server_addr = server.local_addr().unwrap();
+    let (responders, mut requests) = mpsc::unbounded_channel();
+    let (shutdown, is_shutdown) = oneshot::channel::<()>();
+    let (shutting_down, is_shutting_down) = oneshot::channel();
+
+    let server_task = tokio::spawn(async move {
+        let connection = server.accept().await.unwrap();
+
+        // the test decides when each request is responded to
+        let service = ServiceFn(move |_request| {
+            let (respond, response) = oneshot::channel::<Response<Bytes>>();
+            responders.send(respond).unwrap();
+            async move { response.await.unwrap() }
+        });
+
+        let shutdown = async move {
+            let _ = is_shutdown.await;
+            let _ = shutting_down.send(());
+        };
+
+        server::Builder::default()
+            .serve(connection, service, shutdown)
+            .await
+    });
+
+    let (_client, connection) = connect(server_addr).await;
+    let (client, mut driver) = client::Builder::default()
+        .connect(connection)
+        .await
+        .unwrap();
+    let driver = tokio::spawn(async move { driver.drive().await });
+
+    let in_flight = tokio::spawn({
+        let mut client = client.clone();
+        async move { client.send(post(b"in-flight")).await }
+    });
+
+    timeout(TIMEOUT, async {
+        let respond = requests.recv().await.unwrap();
+
+        shutdown.send(()).unwrap();
+        is_shutting_down.await.unwrap();
+
+        // requests which are sent after the GOAWAY frame are rejected
+        let mut client = client.clone();
+        assert!(client.send(post(b"rejected")).await.is_err());
+
+        // the in-flight request is still served
+        assert!(!server_task.is_finished());
+        let response = Response::new(Bytes::from_static(b"done"));
+        respond.send(response).unwrap();
+
+        let response = in_flight.await.unwrap().unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(&response.body()[..], b"done");
+    })
+    .await
+    .unwrap();
+
+    // the server closes the connection once the in-flight requests are drained
+    timeout(TIMEOUT, server_task)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    timeout(TIMEOUT, driver).await.unwrap().unwrap().unwrap();
+    assert!(requests.try_recv().is_err());
+    drop(client);
+}
+
+#[tokio::test]
+async fn webtransport_session_test() {
+    let mut server = start_server();
+    let server_addr = server.local_addr().unwrap();
+    let (done, is_done) = oneshot::channel::<()>();
+
+    let server_task = tokio::spawn(async move {
+        let connection = server.accept().await.unwrap();
+        let (connection, sessions) = Connection::with_webtransport(connection);
+        let mut conn = server::Builder::default()
+            .with_webtransport(1)
+            .build(connection)
+            .await
+            .unwrap();
+
+        let (request, mut stream) = conn.accept().await.unwrap().unwrap();
+        assert_eq!(request.method(), Method::CONNECT);
+        assert_eq!(
+            request.extensions().get::<h3::ext::Protocol>(),
+            Some(&h3::ext::Protocol::WEB_TRANSPORT)
+        );
+
+        let response = Response::builder().status(StatusCode::OK).body(()).unwrap();
+        stream.send_response(response).await.unwrap();
+        let session = sessions.establish(stream.id().into_inner()).unwrap();
+
+        // echo a stream and a datagram back to the client
+        let echo = async move {
+            let (mut recv, mut send) = session
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+            while let Some(chunk) = recv.receive().await.unwrap() {
+                send.send(chunk).await.unwrap();
+            }
+            send.finish().unwrap();
+
+            let datagram = session.recv_datagram().await.unwrap();
+            session.send_datagram(datagram).unwrap();
+
+            // keep the session open until the client is done
+            let _ = is_done.await;
+        };
+
+        // the streams of the session are routed while the connection accepts requests
+        tokio::select! {
+            _ = echo => {}
+            _ = conn.accept() => panic!("the client only sends a single request"),
+        }
+        drop(stream);
+    });
+
+    let (_client, connection) = connect(server_addr).await;
+    let (connection, sessions) = Connection::with_webtransport(connection);
+    let (mut client, mut driver) = client::Builder::default()
+        .connect(connection)
+        .await
+        .unwrap();
+    tokio::spawn(async move { driver.drive().await });
+
+    let mut request = Request::builder()
+        .method(Method::CONNECT)
+        .uri("https://localhost/session")
+        .body(())
+        .unwrap();
+    request
+        .extensions_mut()
+        .insert(h3::ext::Protocol::WEB_TRANSPORT);
+
+    timeout(TIMEOUT, async {
+        let (response, stream) = client.open(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let session = sessions.establish(stream.id().into_inner()).unwrap();
+
+        let (mut recv, mut send) = session.open_bidirectional_stream().await.unwrap();
+        send.send(Bytes::from_static(b"hello")).await.unwrap();
+        send.finish().unwrap();
+
+        let mut echoed = Vec::new();
+        while let Some(chunk) = recv.receive().await.unwrap() {
+            echoed.extend_from_slice(&chunk);
+        }
+        assert_eq!(echoed, b"hello");
+
+        // datagrams are unreliable, but none are lost on the loopback interface
+        session
+            .send_datagram(Bytes::from_static(b"datagram"))
+            .unwrap();
+        let datagram = session.recv_datagram().await.unwrap();
+        assert_eq!(&datagram[..], b"datagram");
+    })
+    .await
+    .unwrap();
+
+    done.send(()).unwrap();
+    timeout(TIMEOUT, server_task).await.unwrap().unwrap();
+}
@@ -0,0 +1,564 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! WebTransport sessions over HTTP/3
+//!
+//! A session is established with an extended CONNECT request and identified by the stream ID
+//! of the request stream. Streams of a session start with a header which contains the session
+//! ID, and datagrams of a session are HTTP/3 datagrams associated with the request stream.
+//!
+//! Servers advertise the SETTINGS which enable WebTransport with
+//! [`crate::server::Builder::with_webtransport`].
+//!
+//! See https://datatracker.ietf.org/doc/html/draft-ietf-webtrans-http3
+
+use crate::datagram::{self, is_request_stream, Datagrams};
+use bytes::Bytes;
+use futures::ready;
+use s2n_codec::{DecoderBuffer, Encoder, EncoderBuffer, EncoderValue};
+use s2n_quic::{
+    application,
+    stream::{self, BidirectionalStream, ReceiveStream, SendStream},
+};
+use s2n_quic_core::varint::VarInt;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// The stream type which starts a unidirectional WebTransport stream
+pub(crate) const UNI_STREAM_TYPE: u64 = 0x54;
+
+/// The signal value which starts a bidirectional WebTransport stream
+pub(crate) const BIDI_SIGNAL_VALUE: u64 = 0x41;
+
+/// The error code used to reset streams for sessions which are not established
+const BUFFERED_STREAM_REJECTED: u32 = 0x3994_bd84;
+
+/// The maximum number of streams which are buffered for a session that is not established yet
+const MAX_BUFFERED_STREAMS: usize = 16;
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The session was already established
+    SessionExists,
+    /// Sessions are identified by the stream ID of a client-initiated bidirectional stream
+    InvalidSessionId,
+    Connection(s2n_quic::connection::Error),
+    Stream(stream::Error),
+    Datagram(datagram::Error),
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SessionExists => write!(f, "the session was already established"),
+            Self::InvalidSessionId => write!(f, "the session ID is not a request stream ID"),
+            Self::Connection(error) => error.fmt(f),
+            Self::Stream(error) => error.fmt(f),
+            Self::Datagram(error) => error.fmt(f),
+        }
+    }
+}
+
+impl From<s2n_quic::connection::Error> for Error {
+    fn from(error: s2n_quic::connection::Error) -> Self {
+        Self::Connection(error)
+    }
+}
+
+impl From<stream::Error> for Error {
+    fn from(error: stream::Error) -> Self {
+        Self::Stream(error)
+    }
+}
+
+impl From<datagram::Error> for Error {
+    fn from(error: datagram::Error) -> Self {
+        Self::Datagram(error)
+    }
+}
+
+/// The WebTransport sessions of a connection
+///
+/// Streams which the peer opens for a session are buffered until the session is established
+/// with [`Sessions::establish`].
+#[derive(Clone)]
+pub struct Sessions {
+    conn: s2n_quic::connection::Handle,
+    datagrams: Datagrams,
+    registry: Arc<Mutex<Registry>>,
+}
+
+#[derive(Default)]
+struct Registry {
+    sessions: HashMap<u64, SessionState>,
+    /// Set once the connection stops accepting streams
+    is_closed: bool,
+    /// The error which closed the connection, if any
+    error: Option<s2n_quic::connection::Error>,
+}
+
+#[derive(Default)]
+struct SessionState {
+    established: bool,
+    bidi_streams: VecDeque<(RecvStream, SendStream)>,
+    bidi_waker: Option<Waker>,
+    recv_streams: VecDeque<RecvStream>,
+    recv_waker: Option<Waker>,
+}
+
+impl SessionState {
+    fn buffered_streams(&self) -> usize {
+        self.bidi_streams.len() + self.recv_streams.len()
+    }
+
+    fn wake(&mut self) {
+        if let Some(waker) = self.bidi_waker.take() {
+            waker.wake();
+        }
+        if let Some(waker) = self.recv_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl Sessions {
+    pub(crate) fn new(conn: s2n_quic::connection::Handle) -> Self {
+        Self {
+            datagrams: Datagrams::new(conn.clone()),
+            conn,
+            registry: Default::default(),
+        }
+    }
+
+    /// Establishes the session for the extended CONNECT request on `session_id`
+    ///
+    /// This should be called by servers after accepting the request with a 2xx response and by
+    /// clients after receiving a 2xx response.
+    pub fn establish(&self, session_id: u64) -> Result<Session, Error> {
+        self.datagrams
+            .register(session_id)
+            .map_err(|error| match error {
+                datagram::Error::InvalidStreamId => Error::InvalidSessionId,
+                error => error.into(),
+            })?;
+
+        let mut registry = self.registry.lock().unwrap();
+        let state = registry.sessions.entry(session_id).or_default();
+
+        if state.established {
+            return Err(Error::SessionExists);
+        }
+        state.established = true;
+
+        Ok(Session {
+            id: session_id,
+            sessions: self.clone(),
+        })
+    }
+
+    /// Called when the peer opened a bidirectional stream for the session
+    pub(crate) fn on_bidirectional_stream(
+        &self,
+        session_id: u64,
+        stream: BidirectionalStream,
+        buffered: Bytes,
+    ) {
+        let (recv, send) = stream.split();
+        let recv = RecvStream::new(recv, buffered);
+
+        let rejected = {
+            let mut registry = self.registry.lock().unwrap();
+            match Self::session_state(&mut registry, session_id) {
+                Some(state) => {
+                    state.bidi_streams.push_back((recv, send));
+                    if let Some(waker) = state.bidi_waker.take() {
+                        waker.wake();
+                    }
+                    None
+                }
+                None => Some((recv, send)),
+            }
+        };
+
+        if let Some((mut recv, mut send)) = rejected {
+            let _ = recv.stop_sending(rejected_error());
+            let _ = send.reset(rejected_error());
+        }
+    }
+
+    /// Called when the peer opened a unidirectional stream for the session
+    pub(crate) fn on_receive_stream(
+        &self,
+        session_id: u64,
+        stream: ReceiveStream,
+        buffered: Bytes,
+    ) {
+        let recv = RecvStream::new(stream, buffered);
+
+        let rejected = {
+            let mut registry = self.registry.lock().unwrap();
+            match Self::session_state(&mut registry, session_id) {
+                Some(state) => {
+                    state.recv_streams.push_back(recv);
+                    if let Some(waker) = state.recv_waker.take() {
+                        waker.wake();
+                    }
+                    None
+                }
+                None => Some(recv),
+            }
+        };
+
+        if let Some(mut recv) = rejected {
+            let _ = recv.stop_sending(rejected_error());
+        }
+    }
+
+    /// Called when the connection stops accepting streams
+    pub(crate) fn on_close(&self, error: Option<s2n_quic::connection::Error>) {
+        let mut registry = self.registry.lock().unwrap();
+        registry.is_closed = true;
+        registry.error = error;
+        for state in registry.sessions.values_mut() {
+            state.wake();
+        }
+    }
+
+    /// Returns the state of the session that can take another stream, if any
+    ///
+    /// Streams can arrive before the session is established, so a limited number of streams are
+    /// buffered for sessions the application has not established yet.
+    fn session_state(registry: &mut Registry, session_id: u64) -> Option<&mut SessionState> {
+        if registry.is_closed || !is_request_stream(session_id) {
+            return None;
+        }
+
+        let state = registry.sessions.entry(session_id).or_default();
+        if !state.established && state.buffered_streams() >= MAX_BUFFERED_STREAMS {
+            return None;
+        }
+
+        Some(state)
+    }
+
+    fn close(&self, session_id: u64) {
+        self.datagrams.unregister(session_id);
+        self.registry.lock().unwrap().sessions.remove(&session_id);
+    }
+}
+
+#[inline]
+fn rejected_error() -> application::Error {
+    application::Error::from(VarInt::from_u32(BUFFERED_STREAM_REJECTED))
+}
+
+/// An established WebTransport session
+///
+/// The session stops receiving streams and datagrams once it is dropped.
+pub struct Session {
+    id: u64,
+    sessions: Sessions,
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Session").field("id", &self.id).finish()
+    }
+}
+
+impl Session {
+    /// Returns the session ID, which is the stream ID of the extended CONNECT request
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Opens a bidirectional stream for the session
+    pub async fn open_bidirectional_stream(&self) -> Result<(RecvStream, SendStream), Error> {
+        let stream = self
+            .sessions
+            .conn
+            .clone()
+            .open_bidirectional_stream()
+            .await?;
+        let (recv, mut send) = stream.split();
+        send.send(stream_header(BIDI_SIGNAL_VALUE, self.id)).await?;
+        Ok((RecvStream::new(recv, Bytes::new()), send))
+    }
+
+    /// Opens a unidirectional stream for the session
+    pub async fn open_send_stream(&self) -> Result<SendStream, Error> {
+        let mut send = self.sessions.conn.clone().open_send_stream().await?;
+        send.send(stream_header(UNI_STREAM_TYPE, self.id)).await?;
+        Ok(send)
+    }
+
+    /// Polls for a bidirectional stream the peer opened for the session
+    ///
+    /// Returns `None` once the connection stops accepting streams.
+    pub fn poll_accept_bidirectional_stream(
+        &self,
+        cx: &mut Context,
+    ) -> Poll<Result<Option<(RecvStream, SendStream)>, Error>> {
+        self.poll_accept(cx, |state| {
+            (state.bidi_streams.pop_front(), &mut state.bidi_waker)
+        })
+    }
+
+    /// Polls for a unidirectional stream the peer opened for the session
+    ///
+    /// Returns `None` once the connection stops accepting streams.
+    pub fn poll_accept_receive_stream(
+        &self,
+        cx: &mut Context,
+    ) -> Poll<Result<Option<RecvStream>, Error>> {
+        self.poll_accept(cx, |state| {
+            (state.recv_streams.pop_front(), &mut state.recv_waker)
+        })
+    }
+
+    /// Accepts a bidirectional stream the peer opened for the session
+    pub async fn accept_bidirectional_stream(
+        &self,
+    ) -> Result<Option<(RecvStream, SendStream)>, Error> {
+        futures::future::poll_fn(|cx| self.poll_accept_bidirectional_stream(cx)).await
+    }
+
+    /// Accepts a unidirectional stream the peer opened for the session
+    pub async fn accept_receive_stream(&self) -> Result<Option<RecvStream>, Error> {
+        futures::future::poll_fn(|cx| self.poll_accept_receive_stream(cx)).await
+    }
+
+    /// Enqueues a datagram for the session
+    pub fn send_datagram(&self, payload: Bytes) -> Result<(), Error> {
+        self.sessions.datagrams.send(self.id, payload)?;
+        Ok(())
+    }
+
+    /// Polls for a datagram the peer sent for the session
+    pub fn poll_recv_datagram(&self, cx: &mut Context) -> Poll<Result<Bytes, Error>> {
+        let datagram = ready!(self.sessions.datagrams.poll_recv(self.id, cx))?;
+        Ok(datagram).into()
+    }
+
+    /// Receives a datagram the peer sent for the session
+    pub async fn recv_datagram(&self) -> Result<Bytes, Error> {
+        futures::future::poll_fn(|cx| self.poll_recv_datagram(cx)).await
+    }
+
+    fn poll_accept<T>(
+        &self,
+        cx: &mut Context,
+        pop: impl FnOnce(&mut SessionState) -> (Option<T>, &mut Option<Waker>),
+    ) -> Poll<Result<Option<T>, Error>> {
+        let mut registry = self.sessions.registry.lock().unwrap();
+        let is_closed = registry.is_closed;
+        let error = registry.error;
+        let state = registry
+            .sessions
+            .get_mut(&self.id)
+            .expect("the session is registered until it is dropped");
+
+        let (stream, waker) = pop(state);
+
+        if let Some(stream) = stream {
+            return Ok(Some(stream)).into();
+        }
+
+        if let Some(error) = error {
+            return Err(error.into()).into();
+        }
+
+        if is_closed {
+            return Ok(None).into();
+        }
+
+        *waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.sessions.close(self.id);
+    }
+}
+
+/// Encodes the header which starts a WebTransport stream
+fn stream_header(kind: u64, session_id: u64) -> Bytes {
+    let kind = VarInt::new(kind).expect("valid stream type");
+    let session_id = VarInt::new(session_id).expect("valid session id");
+
+    let mut bytes = vec![0; kind.encoding_size() + session_id.encoding_size()];
+    let mut buffer = EncoderBuffer::new(&mut bytes);
+    buffer.encode(&kind);
+    buffer.encode(&session_id);
+
+    bytes.into()
+}
+
+/// A stream which receives data from the peer for a WebTransport session
+pub struct RecvStream {
+    stream: ReceiveStream,
+    /// Data which was received while reading the stream header
+    buffered: Option<Bytes>,
+}
+
+impl fmt::Debug for RecvStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecvStream")
+            .field("id", &self.stream.id())
+            .finish()
+    }
+}
+
+impl RecvStream {
+    fn new(stream: ReceiveStream, buffered: Bytes) -> Self {
+        Self {
+            stream,
+            buffered: Some(buffered).filter(|buffered| !buffered.is_empty()),
+        }
+    }
+
+    /// Returns the QUIC stream ID
+    pub fn id(&self) -> u64 {
+        self.stream.id()
+    }
+
+    /// Polls for the next chunk of data from the peer
+    ///
+    /// Returns `None` once the peer finished the stream.
+    pub fn poll_receive(&mut self, cx: &mut Context) -> Poll<Result<Option<Bytes>, stream::Error>> {
+        if let Some(buffered) = self.buffered.take() {
+            return Ok(Some(buffered)).into();
+        }
+
+        self.stream.poll_receive(cx)
+    }
+
+    /// Receives the next chunk of data from the peer
+    pub async fn receive(&mut self) -> Result<Option<Bytes>, stream::Error> {
+        futures::future::poll_fn(|cx| self.poll_receive(cx)).await
+    }
+
+    /// Requests the peer to stop sending data on the stream
+    pub fn stop_sending(&mut self, error_code: application::Error) -> Result<(), stream::Error> {
+        self.buffered = None;
+        self.stream.stop_sending(error_code)
+    }
+}
+
+/// The header at the start of a stream the peer opened
+pub(crate) enum Header {
+    /// The stream belongs to HTTP/3, with the data that was read while looking for a header
+    Http(Bytes),
+    /// The stream belongs to a WebTransport session
+    WebTransport { session_id: u64, buffered: Bytes },
+}
+
+/// Reads the header at the start of a stream the peer opened
+pub(crate) struct HeaderReader {
+    /// The stream type or signal value which starts WebTransport streams
+    kind: u64,
+    buffer: Vec<u8>,
+}
+
+impl HeaderReader {
+    pub(crate) fn new(kind: u64) -> Self {
+        Self {
+            kind,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Reads from the stream until the header is known
+    pub(crate) fn poll_read(
+        &mut self,
+        cx: &mut Context,
+        mut poll_receive: impl FnMut(&mut Context) -> Poll<Result<Option<Bytes>, stream::Error>>,
+    ) -> Poll<Result<Header, stream::Error>> {
+        loop {
+            if let Some(header) = self.parse() {
+                return Ok(header).into();
+            }
+
+            match ready!(poll_receive(cx))? {
+                Some(chunk) => self.buffer.extend_from_slice(&chunk),
+                // let HTTP/3 handle streams that end before the header is complete
+                None => return Ok(Header::Http(self.take())).into(),
+            }
+        }
+    }
+
+    /// Parses the header, or returns `None` if more data is needed
+    fn parse(&mut self) -> Option<Header> {
+        let buffer = DecoderBuffer::new(&self.buffer);
+        let (kind, buffer) = buffer.decode::<VarInt>().ok()?;
+
+        if kind.as_u64() != self.kind {
+            return Some(Header::Http(self.take()));
+        }
+
+        let (session_id, remaining) = buffer.decode::<VarInt>().ok()?;
+        let header_len = self.buffer.len() - remaining.len();
+        let buffered = self.take().slice(header_len..);
+
+        Some(Header::WebTransport {
+            session_id: session_id.as_u64(),
+            buffered,
+        })
+    }
+
+    fn take(&mut self) -> Bytes {
+        core::mem::take(&mut self.buffer).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(kind: u64, chunks: &[&'static [u8]]) -> Poll<Result<Header, stream::Error>> {
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut chunks = chunks.iter().map(|chunk| Bytes::from_static(chunk));
+        let mut reader = HeaderReader::new(kind);
+        reader.poll_read(&mut cx, |_| match chunks.next() {
+            Some(chunk) => Ok(Some(chunk)).into(),
+            None => Poll::Pending,
+        })
+    }
+
+    #[test]
+    fn header_test() {
+        let header = stream_header(BIDI_SIGNAL_VALUE, 4);
+        assert_eq!(&header[..], b"\x40\x41\x04");
+
+        // the header can be split across chunks
+        match read(BIDI_SIGNAL_VALUE, &[b"\x40", b"\x41\x04da", b"ta"]) {
+            Poll::Ready(Ok(Header::WebTransport {
+                session_id,
+                buffered,
+            })) => {
+                assert_eq!(session_id, 4);
+                assert_eq!(&buffered[..], b"da");
+            }
+            _ => panic!("expected a WebTransport header"),
+        }
+
+        // HEADERS frames start HTTP/3 request streams
+        match read(BIDI_SIGNAL_VALUE, &[b"\x01\x02"]) {
+            Poll::Ready(Ok(Header::Http(buffered))) => assert_eq!(&buffered[..], b"\x01\x02"),
+            _ => panic!("expected an HTTP/3 stream"),
+        }
+
+        // more data is needed for the session ID
+        assert!(read(UNI_STREAM_TYPE, &[b"\x40\x54"]).is_pending());
+    }
+}
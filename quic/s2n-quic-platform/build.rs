@@ -22,6 +22,8 @@ fn main() -> Result<(), Error> {
             supports("gro");
             supports("mtu_disc");
             supports("pktinfo");
+            supports("recverr");
+            supports("reuseport_cbpf");
             supports("tos");
         }
         "macos" => {
@@ -31,6 +33,8 @@ fn main() -> Result<(), Error> {
         "android" => {
             supports("mtu_disc");
             supports("pktinfo");
+            supports("recverr");
+            supports("reuseport_cbpf");
             supports("tos");
         }
         _ => {
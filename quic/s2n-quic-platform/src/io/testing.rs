@@ -4,7 +4,14 @@
 use bach::time::scheduler;
 use core::task::Poll;
 use s2n_quic_core::{
-    endpoint::Endpoint, inet::SocketAddress, io::event_loop::EventLoop, path::MaxMtu,
+    endpoint::Endpoint,
+    inet::{IpAddress, SocketAddress},
+    io::{bind, event_loop::EventLoop},
+    path::{LocalAddress, MaxMtu, RemoteAddress},
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
 };
 
 type Error = std::io::Error;
@@ -219,6 +226,7 @@ impl Handle {
         Builder {
             handle: self.clone(),
             address: None,
+            additional_ips: Vec::new(),
             max_mtu: MaxMtu::default(),
         }
     }
@@ -227,6 +235,7 @@ impl Handle {
 pub struct Builder {
     handle: Handle,
     address: Option<SocketAddress>,
+    additional_ips: Vec<IpAddress>,
     max_mtu: MaxMtu,
 }
 
@@ -239,6 +248,13 @@ impl Builder {
         self.max_mtu = max_mtu.try_into().unwrap();
         self
     }
+
+    /// Adds an IP the endpoint receives packets on and can send packets from, using the port of
+    /// its address
+    pub fn with_additional_ip(mut self, ip: IpAddress) -> Self {
+        self.additional_ips.push(ip);
+        self
+    }
 }
 
 pub struct Io {
@@ -253,6 +269,7 @@ impl Io {
         let Builder {
             handle: Handle { executor, buffers },
             address,
+            additional_ips,
             max_mtu,
         } = self.builder;
         endpoint.set_max_mtu(max_mtu);
@@ -261,6 +278,16 @@ impl Io {
 
         let (tx, rx) = buffers.register(handle);
 
+        for ip in additional_ips {
+            buffers.register_alias(handle, ip.with_port(handle.port()));
+        }
+
+        endpoint.set_binder(Arc::new(Binder {
+            buffers,
+            handle,
+            bound: Default::default(),
+        }));
+
         let clock = time::Clock::default();
 
         let event_loop = EventLoop {
@@ -273,3 +300,60 @@ impl Io {
         Ok((join, handle))
     }
 }
+
+/// Binds new local addresses of an endpoint by registering them as aliases of its address
+struct Binder {
+    buffers: network::Buffers,
+    handle: SocketAddress,
+    /// The number of paths using each of the aliases registered by the binder
+    bound: Mutex<HashMap<SocketAddress, usize>>,
+}
+
+impl bind::Bind for Binder {
+    type PathHandle = network::PathHandle;
+
+    fn bind(
+        &self,
+        local_address: LocalAddress,
+        remote_address: RemoteAddress,
+    ) -> Result<Self::PathHandle, bind::Error> {
+        let mut local_address = *local_address;
+        if local_address.port() == 0 {
+            local_address.set_port(self.buffers.generate_port());
+        }
+
+        let mut bound = self.bound.lock().unwrap();
+
+        if let Some(count) = bound.get_mut(&local_address) {
+            *count += 1;
+        } else {
+            match self.buffers.endpoint_address(local_address) {
+                // the endpoint already receives packets on the address
+                Some(handle) if handle == self.handle => {}
+                Some(_) => return Err(bind::Error::AddressUnavailable),
+                None => {
+                    self.buffers.register_alias(self.handle, local_address);
+                    bound.insert(local_address, 1);
+                }
+            }
+        }
+
+        Ok(network::PathHandle {
+            remote_address,
+            local_address: local_address.into(),
+        })
+    }
+
+    fn unbind(&self, local_address: LocalAddress) {
+        let local_address = *local_address;
+        let mut bound = self.bound.lock().unwrap();
+
+        if let Some(count) = bound.get_mut(&local_address) {
+            *count -= 1;
+            if *count == 0 {
+                bound.remove(&local_address);
+                self.buffers.unregister_alias(local_address);
+            }
+        }
+    }
+}
@@ -82,6 +82,7 @@ impl Buffers {
 
     pub fn rx<F: FnOnce(&mut Queue)>(&self, handle: SocketAddress, f: F) {
         let mut lock = self.inner.lock().unwrap();
+        let handle = lock.aliases.get(&handle).copied().unwrap_or(handle);
         if let Some(queue) = lock.rx.get_mut(&handle) {
             f(queue)
         }
@@ -151,11 +152,16 @@ impl Buffers {
             .fetch_add(1, Ordering::SeqCst)
             .to_be_bytes()
             .into();
-        let port = self.next_port.fetch_add(1, Ordering::SeqCst);
+        let port = self.generate_port();
         let addr = (ip, port);
         SocketAddress::IpV4(addr.into())
     }
 
+    /// Generate a unique port
+    pub fn generate_port(&self) -> u16 {
+        self.next_port.fetch_add(1, Ordering::SeqCst)
+    }
+
     /// Register an address on the network
     pub fn register(&self, handle: SocketAddress) -> (TxIo, RxIo) {
         let mut lock = self.inner.lock().unwrap();
@@ -176,6 +182,41 @@ impl Buffers {
 
         (tx, rx)
     }
+
+    /// Registers an additional address for the endpoint registered at `handle`
+    ///
+    /// The endpoint receives the packets sent to the alias and is able to send packets from it.
+    pub fn register_alias(&self, handle: SocketAddress, alias: SocketAddress) {
+        let mut lock = self.inner.lock().unwrap();
+
+        lock.aliases.insert(alias, handle);
+
+        if let Some(queue) = lock.tx.get_mut(&handle) {
+            queue.aliases.push(alias.into());
+        }
+    }
+
+    /// Removes an address registered with [`Self::register_alias`]
+    pub fn unregister_alias(&self, alias: SocketAddress) {
+        let mut lock = self.inner.lock().unwrap();
+
+        if let Some(handle) = lock.aliases.remove(&alias) {
+            if let Some(queue) = lock.tx.get_mut(&handle) {
+                queue.aliases.retain(|address| **address != alias);
+            }
+        }
+    }
+
+    /// Returns the address of the endpoint that receives the packets sent to `address`
+    pub fn endpoint_address(&self, address: SocketAddress) -> Option<SocketAddress> {
+        let lock = self.inner.lock().unwrap();
+
+        if lock.rx.contains_key(&address) {
+            return Some(address);
+        }
+
+        lock.aliases.get(&address).copied()
+    }
 }
 
 pub struct TxIo {
@@ -260,6 +301,7 @@ struct State {
     is_open: bool,
     tx: HashMap<SocketAddress, Queue>,
     rx: HashMap<SocketAddress, Queue>,
+    aliases: HashMap<SocketAddress, SocketAddress>,
 }
 
 impl Default for State {
@@ -268,6 +310,7 @@ impl Default for State {
             is_open: true,
             tx: Default::default(),
             rx: Default::default(),
+            aliases: Default::default(),
         }
     }
 }
@@ -279,6 +322,8 @@ pub struct Queue {
     packets: VecDeque<Packet>,
     pending: Packet,
     local_address: LocalAddress,
+    /// The additional addresses the endpoint can send packets from
+    aliases: Vec<LocalAddress>,
     is_blocked: bool,
     waker: Option<Waker>,
 }
@@ -293,6 +338,7 @@ impl Queue {
             packets: VecDeque::new(),
             pending: Packet::new(mtu, local_address),
             local_address,
+            aliases: Vec::new(),
             is_blocked: false,
             waker: None,
         }
@@ -335,11 +381,17 @@ impl io::tx::Queue for Queue {
             return Err(io::tx::Error::AtCapacity);
         }
 
+        let local_address = message.path_handle().local_address;
         let len = self.pending.write(message)?;
 
         // create a packet for the next transmission
         let next = Packet::new(self.mtu, self.local_address);
-        let packet = core::mem::replace(&mut self.pending, next);
+        let mut packet = core::mem::replace(&mut self.pending, next);
+
+        // send the packet from an additional address if the path uses one
+        if self.aliases.contains(&local_address) {
+            packet.path.local_address = local_address;
+        }
 
         self.packets.push_back(packet);
 
@@ -17,6 +17,7 @@ use s2n_quic_core::{
 use std::{convert::TryInto, io, io::ErrorKind};
 use tokio::{net::UdpSocket, runtime::Handle};
 
+mod bind;
 mod builder;
 mod clock;
 #[cfg(test)]
@@ -70,6 +71,8 @@ impl Io {
             mut max_mtu,
             max_segments,
             reuse_port,
+            reuse_port_steering,
+            packet_too_big,
         } = self.builder;
 
         let clock = Clock::default();
@@ -111,6 +114,10 @@ impl Io {
         // ensure the socket is non-blocking
         rx_socket.set_nonblocking(true)?;
 
+        if let Some(socket_count) = reuse_port_steering {
+            syscall::configure_reuse_port_steering(&rx_socket, socket_count)?;
+        }
+
         let tx_socket = if let Some(tx_socket) = tx_socket {
             tx_socket
         } else if let Some(send_addr) = send_addr {
@@ -156,6 +163,10 @@ impl Io {
             },
         });
 
+        // Configure the queueing of ICMP errors so the endpoint is notified of Packet Too Big
+        // messages
+        let packet_too_big = packet_too_big && syscall::configure_recverr(&tx_socket);
+
         // Configure packet info CMSG
         syscall::configure_pktinfo(&rx_socket);
 
@@ -193,6 +204,14 @@ impl Io {
         // Notify the endpoint of the MTU that we chose
         endpoint.set_max_mtu(max_mtu);
 
+        if packet_too_big {
+            endpoint.enable_packet_too_big();
+        }
+
+        // Allow clients to bind sockets for paths from new local addresses
+        let binder = bind::Binder::new(handle.clone(), max_mtu);
+        endpoint.set_binder(std::sync::Arc::new(binder.clone()));
+
         let instance = Instance {
             clock,
             rx_socket: rx_socket.into(),
@@ -200,6 +219,9 @@ impl Io {
             rx,
             tx,
             endpoint,
+            packet_too_big,
+            binder,
+            max_mtu,
         };
 
         let local_addr = instance.rx_socket.local_addr()?.into();
@@ -229,6 +251,11 @@ struct Instance<E> {
     rx: socket::Queue<buffer::Buffer>,
     tx: socket::Queue<buffer::Buffer>,
     endpoint: E,
+    /// Whether ICMP errors are queued on the tx socket
+    packet_too_big: bool,
+    /// Binds sockets for paths from new local addresses
+    binder: bind::Binder,
+    max_mtu: MaxMtu,
 }
 
 impl<E: Endpoint<PathHandle = PathHandle>> Instance<E> {
@@ -240,8 +267,14 @@ impl<E: Endpoint<PathHandle = PathHandle>> Instance<E> {
             mut rx,
             mut tx,
             mut endpoint,
+            packet_too_big,
+            binder,
+            max_mtu,
         } = self;
 
+        #[cfg(not(s2n_quic_platform_recverr))]
+        let _ = packet_too_big;
+
         cfg_if! {
             if #[cfg(any(s2n_quic_platform_socket_msg, s2n_quic_platform_socket_mmsg))] {
                 let rx_socket = tokio::io::unix::AsyncFd::new(rx_socket)?;
@@ -254,14 +287,17 @@ impl<E: Endpoint<PathHandle = PathHandle>> Instance<E> {
 
         let mut timer = clock.timer();
 
+        // packets sent through bound sockets are written to this buffer first
+        let mut bound_tx_buffer = vec![0; usize::from(max_mtu)];
+
         loop {
-            // Poll for readability if we have free slots available
+            // Poll for readability if we have free slots available, as well as for datagrams
+            // received on bound sockets
             let rx_interest = rx.free_len() > 0;
             let rx_task = async {
-                if rx_interest {
-                    rx_socket.readable().await
-                } else {
-                    futures::future::pending().await
+                tokio::select! {
+                    guard = rx_socket.readable(), if rx_interest => Some(guard),
+                    _ = futures::future::poll_fn(|cx| binder.poll_received(cx)) => None,
                 }
             };
 
@@ -311,20 +347,55 @@ impl<E: Endpoint<PathHandle = PathHandle>> Instance<E> {
                 application_wakeup,
             });
 
+            // Queued ICMP errors are signaled with EPOLLERR, which tokio reports as a
+            // WRITE_CLOSED readiness
+            #[cfg(s2n_quic_platform_recverr)]
+            let has_errors = packet_too_big
+                && matches!(&tx_result, Some(Ok(guard)) if guard.ready().is_write_closed());
+
             if let Some(guard) = tx_result {
                 if let Ok(result) = guard?.try_io(|socket| tx.tx(socket, &mut publisher)) {
                     result?;
                 }
             }
 
-            if let Some(guard) = rx_result {
+            if let Some(Some(guard)) = rx_result {
+                let mut error = None;
                 if let Ok(result) = guard?.try_io(|socket| rx.rx(socket, &mut publisher)) {
-                    result?;
+                    error = result.err();
                 }
+
+                if let Some(error) = error {
+                    // The error of the most recent ICMP message is also returned by the next
+                    // receive call, so it isn't fatal
+                    #[cfg(s2n_quic_platform_recverr)]
+                    let is_fatal = !(packet_too_big && syscall::recverr::is_icmp_error(&error));
+                    #[cfg(not(s2n_quic_platform_recverr))]
+                    let is_fatal = true;
+
+                    if is_fatal {
+                        return Err(error);
+                    }
+
+                    #[cfg(s2n_quic_platform_recverr)]
+                    recv_errors(&mut endpoint, tx_socket.get_ref(), &clock);
+                }
+
                 endpoint.receive(&mut rx.rx_queue(), &clock);
             }
 
-            endpoint.transmit(&mut tx.tx_queue(), &clock);
+            // tokio treats WRITE_CLOSED as a final state and never clears it, so once an error
+            // is queued the queue is checked on every following tx wakeup. This costs a single
+            // recvmsg call when the queue is empty.
+            #[cfg(s2n_quic_platform_recverr)]
+            if has_errors {
+                recv_errors(&mut endpoint, tx_socket.get_ref(), &clock);
+            }
+
+            binder.receive(&mut endpoint, &clock);
+
+            let mut tx_queue = bind::Router::new(tx.tx_queue(), &binder, &mut bound_tx_buffer);
+            endpoint.transmit(&mut tx_queue, &clock);
 
             let timeout = endpoint.timeout();
 
@@ -353,6 +424,21 @@ impl<E: Endpoint<PathHandle = PathHandle>> Instance<E> {
     }
 }
 
+/// Reads the ICMP errors queued on the socket and notifies the endpoint of any Packet Too Big
+/// messages
+///
+/// Returns the number of errors which were read.
+#[cfg(s2n_quic_platform_recverr)]
+fn recv_errors<E: Endpoint>(
+    endpoint: &mut E,
+    socket: &std::net::UdpSocket,
+    clock: &Clock,
+) -> usize {
+    syscall::recverr::recv(socket, |remote_address, quoted_packet, mtu| {
+        endpoint.on_packet_too_big(&remote_address.into(), quoted_packet, mtu, clock);
+    })
+}
+
 /// A shim for the AsyncFd API
 ///
 /// Tokio only provides the AsyncFd interface for unix platforms so for
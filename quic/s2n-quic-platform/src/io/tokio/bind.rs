@@ -0,0 +1,298 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::task::{Context, Poll};
+use futures::task::AtomicWaker;
+use s2n_quic_core::{
+    inet::datagram,
+    io::{bind, rx, tx},
+    path::{Handle as _, LocalAddress, RemoteAddress},
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+/// The maximum number of datagrams received on bound sockets that are queued for the endpoint
+const RECEIVE_QUEUE_CAPACITY: usize = 1024;
+
+/// Whether the path handle distinguishes paths by their local address
+///
+/// Without packet info, msg handles ignore the local address when comparing paths, so packets
+/// received on a bound socket would be attributed to the path of the endpoint socket.
+pub(super) const IS_SUPPORTED: bool = cfg!(any(
+    s2n_quic_platform_pktinfo,
+    not(any(
+        s2n_quic_platform_socket_msg,
+        s2n_quic_platform_socket_mmsg
+    ))
+));
+
+/// Binds sockets for the paths of clients from new local addresses
+///
+/// Each bound socket is read by a separate task, which queues the datagrams for the event loop.
+/// Packets of the paths from a bound socket are sent through it by the [`Router`].
+#[derive(Clone, Debug)]
+pub(super) struct Binder {
+    handle: Handle,
+    max_mtu: MaxMtu,
+    sockets: Arc<Mutex<HashMap<SocketAddress, Socket>>>,
+    received: Arc<Received>,
+}
+
+#[derive(Debug)]
+struct Socket {
+    socket: Arc<UdpSocket>,
+    reader: tokio::task::JoinHandle<()>,
+    /// The number of paths using the socket
+    paths: usize,
+}
+
+#[derive(Debug, Default)]
+struct Received {
+    datagrams: Mutex<VecDeque<(PathHandle, Vec<u8>)>>,
+    waker: AtomicWaker,
+}
+
+impl Binder {
+    pub fn new(handle: Handle, max_mtu: MaxMtu) -> Self {
+        Self {
+            handle,
+            max_mtu,
+            sockets: Default::default(),
+            received: Default::default(),
+        }
+    }
+
+    /// Polls for datagrams received on the bound sockets
+    pub fn poll_received(&self, cx: &mut Context) -> Poll<()> {
+        self.received.waker.register(cx.waker());
+
+        if self.received.datagrams.lock().unwrap().is_empty() {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+
+    /// Passes the datagrams received on the bound sockets to the endpoint
+    pub fn receive<E: Endpoint<PathHandle = PathHandle>>(&self, endpoint: &mut E, clock: &Clock) {
+        let mut datagrams = core::mem::take(&mut *self.received.datagrams.lock().unwrap());
+
+        if !datagrams.is_empty() {
+            endpoint.receive(&mut RxQueue(&mut datagrams), clock);
+        }
+    }
+
+    /// Returns the bound sockets with their local addresses
+    pub fn sockets(&self) -> Vec<(LocalAddress, Arc<UdpSocket>)> {
+        self.sockets
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(local_address, socket)| ((*local_address).into(), socket.socket.clone()))
+            .collect()
+    }
+
+    fn bind_socket(&self, local_address: SocketAddress) -> io::Result<UdpSocket> {
+        let socket = syscall::bind_udp(std::net::SocketAddr::from(local_address), false)?;
+        socket.set_nonblocking(true)?;
+
+        let _guard = self.handle.enter();
+        UdpSocket::from_std(socket.into())
+    }
+}
+
+impl bind::Bind for Binder {
+    type PathHandle = PathHandle;
+
+    fn bind(
+        &self,
+        local_address: LocalAddress,
+        remote_address: RemoteAddress,
+    ) -> Result<Self::PathHandle, bind::Error> {
+        if !IS_SUPPORTED {
+            return Err(bind::Error::Unsupported);
+        }
+
+        let mut sockets = self.sockets.lock().unwrap();
+
+        // a port of 0 always binds a new socket to an ephemeral port
+        if local_address.port() != 0 {
+            if let Some(socket) = sockets.get_mut(&*local_address) {
+                socket.paths += 1;
+                return Ok(PathHandle {
+                    remote_address,
+                    local_address,
+                });
+            }
+        }
+
+        let socket = self
+            .bind_socket(*local_address)
+            .map_err(|_| bind::Error::AddressUnavailable)?;
+        let local_address: SocketAddress = socket
+            .local_addr()
+            .map_err(|_| bind::Error::AddressUnavailable)?
+            .into();
+
+        let socket = Arc::new(socket);
+        let reader = self.handle.spawn(read(
+            socket.clone(),
+            local_address,
+            self.max_mtu,
+            self.received.clone(),
+        ));
+
+        sockets.insert(
+            local_address,
+            Socket {
+                socket,
+                reader,
+                paths: 1,
+            },
+        );
+
+        Ok(PathHandle {
+            remote_address,
+            local_address: local_address.into(),
+        })
+    }
+
+    fn unbind(&self, local_address: LocalAddress) {
+        let mut sockets = self.sockets.lock().unwrap();
+
+        if let Some(socket) = sockets.get_mut(&*local_address) {
+            socket.paths -= 1;
+            if socket.paths == 0 {
+                if let Some(socket) = sockets.remove(&*local_address) {
+                    // the socket is closed once the reader stops
+                    socket.reader.abort();
+                }
+            }
+        }
+    }
+}
+
+/// Reads the datagrams of a bound socket and queues them for the event loop
+async fn read(
+    socket: Arc<UdpSocket>,
+    local_address: SocketAddress,
+    max_mtu: MaxMtu,
+    received: Arc<Received>,
+) {
+    let mut buffer = vec![0; usize::from(max_mtu)];
+
+    loop {
+        let (len, remote_address) = match socket.recv_from(&mut buffer).await {
+            Ok(result) => result,
+            // some platforms report ICMP errors for previously sent datagrams
+            Err(error)
+                if matches!(
+                    error.kind(),
+                    ErrorKind::ConnectionReset | ErrorKind::ConnectionRefused
+                ) =>
+            {
+                continue;
+            }
+            Err(_) => return,
+        };
+
+        let path = PathHandle {
+            remote_address: SocketAddress::from(remote_address).into(),
+            local_address: local_address.into(),
+        };
+
+        let mut datagrams = received.datagrams.lock().unwrap();
+        if datagrams.len() == RECEIVE_QUEUE_CAPACITY {
+            // drop old datagrams if the event loop isn't keeping up
+            let _ = datagrams.pop_front();
+        }
+        datagrams.push_back((path, buffer[..len].to_vec()));
+        drop(datagrams);
+
+        received.waker.wake();
+    }
+}
+
+struct RxQueue<'a>(&'a mut VecDeque<(PathHandle, Vec<u8>)>);
+
+impl<'a> rx::Queue for RxQueue<'a> {
+    type Handle = PathHandle;
+
+    fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
+        for (path, mut payload) in self.0.drain(..) {
+            let header = datagram::Header {
+                path,
+                ecn: Default::default(),
+            };
+            on_packet(header, &mut payload);
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Sends the packets of paths from bound sockets through the sockets and all other packets
+/// through the queue of the endpoint socket
+pub(super) struct Router<'a, Q> {
+    queue: Q,
+    sockets: Vec<(LocalAddress, Arc<UdpSocket>)>,
+    buffer: &'a mut [u8],
+}
+
+impl<'a, Q> Router<'a, Q> {
+    pub fn new(queue: Q, binder: &Binder, buffer: &'a mut [u8]) -> Self {
+        Self {
+            queue,
+            sockets: binder.sockets(),
+            buffer,
+        }
+    }
+}
+
+impl<'a, Q: tx::Queue<Handle = PathHandle>> tx::Queue for Router<'a, Q> {
+    type Handle = PathHandle;
+
+    const SUPPORTS_ECN: bool = Q::SUPPORTS_ECN;
+    const SUPPORTS_PACING: bool = Q::SUPPORTS_PACING;
+    const SUPPORTS_FLOW_LABELS: bool = Q::SUPPORTS_FLOW_LABELS;
+
+    fn push<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        mut message: M,
+    ) -> Result<tx::Outcome, tx::Error> {
+        let path = *message.path_handle();
+        let socket = self
+            .sockets
+            .iter()
+            .find(|(local_address, _)| *local_address == path.local_address())
+            .map(|(_, socket)| socket);
+
+        let socket = if let Some(socket) = socket {
+            socket
+        } else {
+            return self.queue.push(message);
+        };
+
+        let len = message.write_payload(tx::PayloadBuffer::new(self.buffer), 0)?;
+
+        // a full socket buffer is treated as packet loss
+        let _ = socket.try_send_to(&self.buffer[..len], (*path.remote_address()).into());
+
+        Ok(tx::Outcome { len, index: 0 })
+    }
+
+    #[inline]
+    fn flush(&mut self) {
+        self.queue.flush()
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+}
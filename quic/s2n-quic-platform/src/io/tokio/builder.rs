@@ -15,6 +15,8 @@ pub struct Builder {
     pub(super) max_mtu: MaxMtu,
     pub(super) max_segments: gso::MaxSegments,
     pub(super) reuse_port: bool,
+    pub(super) reuse_port_steering: Option<u8>,
+    pub(super) packet_too_big: bool,
 }
 
 impl Builder {
@@ -57,6 +59,22 @@ impl Builder {
         Ok(self)
     }
 
+    /// Binds the socket used for receiving to the receive address, returning another handle to it
+    ///
+    /// The socket remains open until both the IO provider and the returned handle are closed.
+    /// Holding the handle keeps the socket in its `SO_REUSEPORT` group after the endpoint shuts
+    /// down; see [`Builder::with_reuse_port_steering`].
+    pub fn bind_rx_socket(mut self) -> io::Result<(Self, std::net::UdpSocket)> {
+        let recv_addr = self
+            .recv_addr
+            .take()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing bind address"))?;
+        let socket = syscall::bind_udp(recv_addr, self.reuse_port)?;
+        let handle = socket.try_clone()?.into();
+        self.rx_socket = Some(socket);
+        Ok((self, handle))
+    }
+
     /// Sets the socket used for transmitting on for the runtime. If no tx_socket or send address is
     /// specified, the rx_socket will be used for transmitting.
     ///
@@ -111,6 +129,51 @@ impl Builder {
         Ok(self)
     }
 
+    /// Steers received packets to one of `socket_count` sockets bound to the same port, based on
+    /// the first byte of the destination connection ID
+    ///
+    /// Each socket in the group must be bound with [`Builder::with_reuse_port`], in the order of
+    /// the indices its endpoint encodes in the connection IDs it issues. This is currently only
+    /// supported on Linux.
+    ///
+    /// When a socket in the group is closed, the kernel moves the last socket in the group to the
+    /// index of the closed one, after which packets are steered to the wrong sockets. The sockets
+    /// should be kept open until all of them are closed, which can be done by holding the handles
+    /// returned by [`Builder::bind_rx_socket`].
+    pub fn with_reuse_port_steering(mut self, socket_count: u8) -> io::Result<Self> {
+        if !cfg!(any(target_os = "linux", target_os = "android")) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "reuse_port_steering is not supported on the current platform",
+            ));
+        }
+        if socket_count == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "socket_count must be at least 1",
+            ));
+        }
+        self.reuse_port = true;
+        self.reuse_port_steering = Some(socket_count);
+        Ok(self)
+    }
+
+    /// Lowers the MTU of a path when an ICMP Packet Too Big message is received for it
+    ///
+    /// The messages are read from the error queue of the tx socket. Since ICMP messages aren't
+    /// authenticated, the endpoint only uses messages which quote a packet it sent on an active
+    /// connection. This is currently only supported on Linux.
+    pub fn with_packet_too_big_enabled(mut self) -> io::Result<Self> {
+        if !cfg!(any(target_os = "linux", target_os = "android")) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "packet_too_big is not supported on the current platform",
+            ));
+        }
+        self.packet_too_big = true;
+        Ok(self)
+    }
+
     pub fn build(self) -> io::Result<Io> {
         Ok(Io { builder: self })
     }
@@ -9,11 +9,12 @@ use core::{
 use s2n_quic_core::{
     endpoint::{self, CloseError},
     event,
-    io::{rx, tx},
-    path::Handle as _,
+    inet::SocketAddressV4,
+    io::{bind, rx, tx},
+    path::{Handle as _, LocalAddress, RemoteAddress},
     time::{Clock, Duration, Timestamp},
 };
-use std::{collections::BTreeMap, net::ToSocketAddrs};
+use std::{collections::BTreeMap, net::ToSocketAddrs, sync::Arc};
 
 struct TestEndpoint<const IS_SERVER: bool> {
     handle: PathHandle,
@@ -254,3 +255,211 @@ async fn ipv6_two_socket_test() -> io::Result<()> {
         other => other,
     }
 }
+
+/// A client which sends a datagram from a socket bound through the IO provider and waits for the
+/// server to echo it back to that socket
+#[derive(Default)]
+struct BindClient {
+    server_address: RemoteAddress,
+    endpoint_address: LocalAddress,
+    binder: Option<Arc<dyn bind::Bind<PathHandle = PathHandle>>>,
+    path: Option<PathHandle>,
+    is_done: bool,
+    now: Option<Timestamp>,
+    subscriber: NoopSubscriber,
+}
+
+/// A server which echoes datagrams back on the path they were received on
+#[derive(Default)]
+struct EchoServer {
+    pending: Vec<(PathHandle, Vec<u8>)>,
+    now: Option<Timestamp>,
+    subscriber: NoopSubscriber,
+}
+
+impl Endpoint for BindClient {
+    type PathHandle = PathHandle;
+    type Subscriber = NoopSubscriber;
+
+    const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Client;
+
+    fn transmit<Tx: tx::Queue<Handle = PathHandle>, C: Clock>(
+        &mut self,
+        queue: &mut Tx,
+        clock: &C,
+    ) {
+        self.now = Some(clock.get_time());
+
+        let binder = self
+            .binder
+            .as_ref()
+            .expect("the IO provider supports binding");
+        let path = *self.path.get_or_insert_with(|| {
+            let local_address = SocketAddressV4::new([127, 0, 0, 1], 0).into();
+            binder.bind(local_address, self.server_address).unwrap()
+        });
+
+        // the socket is bound to a new port
+        assert_ne!(path.local_address().port(), 0);
+        assert_ne!(path.local_address().port(), self.endpoint_address.port());
+
+        let _ = queue.push((path, *b"ping"));
+    }
+
+    fn receive<Rx: rx::Queue<Handle = PathHandle>, C: Clock>(&mut self, queue: &mut Rx, clock: &C) {
+        self.now = Some(clock.get_time());
+
+        let path = self.path;
+        queue.for_each(|header, payload| {
+            let is_bound_path = path.map_or(false, |path| path.strict_eq(&header.path));
+            if is_bound_path && payload == b"ping" {
+                self.is_done = true;
+            }
+        });
+    }
+
+    fn poll_wakeups<C: Clock>(
+        &mut self,
+        _cx: &mut Context<'_>,
+        clock: &C,
+    ) -> Poll<Result<usize, CloseError>> {
+        self.now = Some(clock.get_time());
+
+        if self.is_done {
+            if let (Some(binder), Some(path)) = (&self.binder, self.path.take()) {
+                binder.unbind(path.local_address());
+            }
+            return Err(CloseError).into();
+        }
+
+        Poll::Pending
+    }
+
+    fn timeout(&self) -> Option<Timestamp> {
+        self.now.map(|now| now + Duration::from_millis(50))
+    }
+
+    fn set_max_mtu(&mut self, _max_mtu: MaxMtu) {
+        // noop
+    }
+
+    fn set_binder(&mut self, binder: Arc<dyn bind::Bind<PathHandle = PathHandle>>) {
+        self.binder = Some(binder);
+    }
+
+    fn subscriber(&mut self) -> &mut Self::Subscriber {
+        &mut self.subscriber
+    }
+}
+
+impl Endpoint for EchoServer {
+    type PathHandle = PathHandle;
+    type Subscriber = NoopSubscriber;
+
+    const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Server;
+
+    fn transmit<Tx: tx::Queue<Handle = PathHandle>, C: Clock>(
+        &mut self,
+        queue: &mut Tx,
+        clock: &C,
+    ) {
+        self.now = Some(clock.get_time());
+
+        for message in self.pending.drain(..) {
+            let _ = queue.push(message);
+        }
+    }
+
+    fn receive<Rx: rx::Queue<Handle = PathHandle>, C: Clock>(&mut self, queue: &mut Rx, clock: &C) {
+        self.now = Some(clock.get_time());
+
+        queue.for_each(|header, payload| {
+            self.pending.push((header.path, payload.to_vec()));
+        });
+    }
+
+    fn poll_wakeups<C: Clock>(
+        &mut self,
+        _cx: &mut Context<'_>,
+        clock: &C,
+    ) -> Poll<Result<usize, CloseError>> {
+        self.now = Some(clock.get_time());
+        Poll::Pending
+    }
+
+    fn timeout(&self) -> Option<Timestamp> {
+        self.now.map(|now| now + Duration::from_millis(50))
+    }
+
+    fn set_max_mtu(&mut self, _max_mtu: MaxMtu) {
+        // noop
+    }
+
+    fn subscriber(&mut self) -> &mut Self::Subscriber {
+        &mut self.subscriber
+    }
+}
+
+/// Sends and receives datagrams through a socket the client binds to a new local port
+#[tokio::test]
+async fn bind_test() -> io::Result<()> {
+    if !bind::IS_SUPPORTED {
+        eprintln!("The platform does not support binding sockets for paths; skipping");
+        return Ok(());
+    }
+
+    let (server_io, server_addr) = runtime(IPV4_LOCALHOST, None).await?;
+    let (client_io, client_addr) = runtime(IPV4_LOCALHOST, None).await?;
+
+    let client_endpoint = BindClient {
+        server_address: server_addr.into(),
+        endpoint_address: client_addr.into(),
+        ..Default::default()
+    };
+
+    let (server_task, _) = server_io.start(EchoServer::default())?;
+    let (client_task, _) = client_io.start(client_endpoint)?;
+
+    tokio::time::timeout(core::time::Duration::from_secs(10), client_task).await??;
+
+    server_task.abort();
+
+    Ok(())
+}
+
+#[test]
+#[cfg(s2n_quic_platform_reuseport_cbpf)]
+fn reuse_port_steering_test() -> io::Result<()> {
+    const SOCKET_COUNT: u8 = 4;
+
+    let mut sockets = vec![syscall::bind_udp(IPV4_LOCALHOST, true)?];
+    let addr = sockets[0].local_addr()?;
+    for _ in 1..SOCKET_COUNT {
+        sockets.push(syscall::bind_udp(addr.as_socket().unwrap(), true)?);
+    }
+    for socket in &sockets {
+        syscall::configure_reuse_port_steering(socket, SOCKET_COUNT)?;
+        socket.set_read_timeout(Some(core::time::Duration::from_secs(1)))?;
+    }
+    let sockets: Vec<std::net::UdpSocket> = sockets.into_iter().map(Into::into).collect();
+
+    let client = std::net::UdpSocket::bind(IPV4_LOCALHOST)?;
+    client.connect(addr.as_socket().unwrap())?;
+
+    for index in 0..SOCKET_COUNT * 2 {
+        let dcid_byte = index + SOCKET_COUNT * 3;
+        // a 1-RTT packet followed by an Initial packet
+        let short = [0b0100_0000, dcid_byte, 1, 2, 3];
+        let long = [0b1100_0000, 0, 0, 0, 1, 8, dcid_byte, 1, 2, 3];
+
+        for packet in [&short[..], &long[..]] {
+            client.send(packet)?;
+
+            let mut buffer = [0; 16];
+            let len = sockets[(dcid_byte % SOCKET_COUNT) as usize].recv(&mut buffer)?;
+            assert_eq!(&buffer[..len], packet);
+        }
+    }
+
+    Ok(())
+}
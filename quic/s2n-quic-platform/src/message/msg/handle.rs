@@ -86,6 +86,26 @@ impl path::Handle for Handle {
         self.local_address
     }
 
+    #[inline]
+    fn with_local_address(&self, local_address: LocalAddress) -> Option<Self> {
+        // The source IP is selected with the packet info control message, which isn't
+        // available on all platforms
+        if !cfg!(s2n_quic_platform_pktinfo) {
+            return None;
+        }
+
+        // Packets are always sent from the port of the socket
+        let port = self.local_address.port();
+        if port != 0 && port != local_address.port() {
+            return None;
+        }
+
+        Some(Self {
+            remote_address: self.remote_address,
+            local_address,
+        })
+    }
+
     #[inline]
     fn eq(&self, other: &Self) -> bool {
         let mut eq = true;
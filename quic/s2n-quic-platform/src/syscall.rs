@@ -13,6 +13,8 @@ use std::io;
 pub mod mmsg;
 #[cfg(s2n_quic_platform_socket_msg)]
 pub mod msg;
+#[cfg(s2n_quic_platform_recverr)]
+pub mod recverr;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[allow(dead_code)] // TODO remove once used
@@ -65,6 +67,105 @@ pub fn bind_udp<A: std::net::ToSocketAddrs>(addr: A, reuse_port: bool) -> io::Re
     Ok(socket)
 }
 
+/// Steers datagrams received by the `SO_REUSEPORT` group of the socket to the socket at the index
+/// encoded in the first byte of the QUIC destination connection ID, modulo `socket_count`
+///
+/// Sockets are indexed in the order they joined the group. If the computed index is out of
+/// range, the kernel falls back to selecting a socket by hashing the 4-tuple. Closing a socket
+/// moves the last socket in the group to its index, so the indices only match the connection IDs
+/// while all of the sockets in the group are open.
+pub fn configure_reuse_port_steering(socket: &Socket, socket_count: u8) -> io::Result<()> {
+    #[cfg(s2n_quic_platform_reuseport_cbpf)]
+    {
+        use std::os::unix::io::AsRawFd;
+
+        // Not all versions of libc define this for linux targets
+        #[cfg(not(any(target_arch = "sparc", target_arch = "sparc64")))]
+        const SO_ATTACH_REUSEPORT_CBPF: libc::c_int = 51;
+        #[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
+        const SO_ATTACH_REUSEPORT_CBPF: libc::c_int = 0x35;
+
+        // Instruction classes and fields from linux/bpf_common.h
+        const BPF_LD: u16 = 0x00;
+        const BPF_ALU: u16 = 0x04;
+        const BPF_JMP: u16 = 0x05;
+        const BPF_RET: u16 = 0x06;
+        const BPF_B: u16 = 0x10;
+        const BPF_ABS: u16 = 0x20;
+        const BPF_MOD: u16 = 0x90;
+        const BPF_JA: u16 = 0x00;
+        const BPF_JSET: u16 = 0x40;
+        const BPF_K: u16 = 0x00;
+        const BPF_A: u16 = 0x10;
+
+        const fn op(code: u16, jt: u8, jf: u8, k: u32) -> libc::sock_filter {
+            libc::sock_filter { code, jt, jf, k }
+        }
+
+        if socket_count == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "at least one socket is required",
+            ));
+        }
+
+        // The program is run with the UDP payload at offset 0
+        let mut program = [
+            // load the first byte of the packet
+            op(BPF_LD | BPF_B | BPF_ABS, 0, 0, 0),
+            // check the header form bit
+            op(BPF_JMP | BPF_JSET | BPF_K, 0, 2, 0x80),
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
+            //# Long Header Packet {
+            //#   Header Form (1) = 1,
+            //#   Fixed Bit (1) = 1,
+            //#   Long Packet Type (2),
+            //#   Type-Specific Bits (4),
+            //#   Version (32),
+            //#   Destination Connection ID Length (8),
+            //#   Destination Connection ID (0..160),
+            op(BPF_LD | BPF_B | BPF_ABS, 0, 0, 6),
+            op(BPF_JMP | BPF_JA, 0, 0, 1),
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-17.3.1
+            //# 1-RTT Packet {
+            //#   Header Form (1) = 0,
+            //#   Fixed Bit (1) = 1,
+            //#   Spin Bit (1),
+            //#   Reserved Bits (2),
+            //#   Key Phase (1),
+            //#   Packet Number Length (2),
+            //#   Destination Connection ID (0..160),
+            op(BPF_LD | BPF_B | BPF_ABS, 0, 0, 1),
+            op(BPF_ALU | BPF_MOD | BPF_K, 0, 0, socket_count as u32),
+            // return the index of the socket
+            op(BPF_RET | BPF_A, 0, 0, 0),
+        ];
+
+        let program = libc::sock_fprog {
+            len: program.len() as _,
+            filter: program.as_mut_ptr(),
+        };
+
+        libc!(setsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            SO_ATTACH_REUSEPORT_CBPF,
+            &program as *const _ as _,
+            core::mem::size_of_val(&program) as _,
+        ))?;
+
+        Ok(())
+    }
+
+    #[cfg(not(s2n_quic_platform_reuseport_cbpf))]
+    {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "reuse port steering is not supported on the current platform",
+        ))
+    }
+}
+
 /// Binds a socket to a specified interface by name
 #[cfg(feature = "xdp")]
 pub fn bind_to_interface<F: std::os::unix::io::AsRawFd>(
@@ -163,6 +264,39 @@ pub fn configure_tos(rx_socket: &Socket) -> bool {
     success
 }
 
+/// Configures the socket to queue the ICMP errors caused by the datagrams it sends
+///
+/// The queued errors are read with `recverr::recv`.
+pub fn configure_recverr(tx_socket: &Socket) -> bool {
+    let mut success = false;
+
+    #[cfg(s2n_quic_platform_recverr)]
+    {
+        use std::os::unix::io::AsRawFd;
+        let enabled: libc::c_int = 1;
+
+        success |= libc!(setsockopt(
+            tx_socket.as_raw_fd(),
+            libc::IPPROTO_IP,
+            libc::IP_RECVERR,
+            &enabled as *const _ as _,
+            core::mem::size_of_val(&enabled) as _,
+        ))
+        .is_ok();
+
+        success |= libc!(setsockopt(
+            tx_socket.as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            libc::IPV6_RECVERR,
+            &enabled as *const _ as _,
+            core::mem::size_of_val(&enabled) as _,
+        ))
+        .is_ok();
+    }
+
+    success
+}
+
 /// Configures the socket to return local address and interface information as part of the
 /// ancillary data
 pub fn configure_pktinfo(rx_socket: &Socket) -> bool {
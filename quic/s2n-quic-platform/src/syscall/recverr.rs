@@ -0,0 +1,337 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::mem::{size_of, size_of_val, zeroed};
+use s2n_quic_core::inet::SocketAddress;
+use socket2::SockAddr;
+use std::{io, os::unix::io::AsRawFd};
+
+/// The number of bytes read from the start of the quoted datagram
+///
+/// This is enough to contain the destination connection ID of any QUIC packet.
+const QUOTED_LEN: usize = 64;
+
+/// The maximum number of errors read in a single call
+///
+/// This bounds the amount of work a flood of ICMP messages can cause on each wakeup.
+const MAX_ERRORS: usize = 32;
+
+/// The number of bytes allocated for cmsg data
+///
+/// This should be enough for the extended error + offender address, along with the
+/// IP_PKTINFO and IP_TOS cmsgs which are also included in the error.
+const CMSG_LEN: usize = 256;
+
+// ICMP types and codes from linux/icmp.h and linux/icmpv6.h
+const ICMP_DEST_UNREACH: u8 = 3;
+const ICMP_FRAG_NEEDED: u8 = 4;
+const ICMPV6_PKT_TOOBIG: u8 = 2;
+
+/// Reads the errors queued on the socket and calls `on_packet_too_big` for each ICMP Packet
+/// Too Big message with the destination of the datagram, the start of the quoted datagram, and
+/// the reported MTU
+///
+/// Any other errors are discarded. Returns the number of errors read from the queue.
+pub fn recv<Sock: AsRawFd, F: FnMut(SocketAddress, &[u8], u16)>(
+    socket: &Sock,
+    mut on_packet_too_big: F,
+) -> usize {
+    let mut count = 0;
+
+    while count < MAX_ERRORS {
+        let mut quoted = [0u8; QUOTED_LEN];
+        // use u64 to make sure the buffer is aligned for cmsghdr
+        let mut control = [0u64; CMSG_LEN / size_of::<u64>()];
+
+        let mut iov = libc::iovec {
+            iov_base: quoted.as_mut_ptr() as _,
+            iov_len: quoted.len(),
+        };
+
+        // Safety: calling a libc function is inherently unsafe as rust cannot
+        // make any invariant guarantees. This has to be reviewed by humans instead
+        // so the [docs](https://man7.org/linux/man-pages/man7/ip.7.html) are inlined here:
+        let result = unsafe {
+            SockAddr::try_init(|msg_name, msg_namelen| {
+                let mut msghdr: libc::msghdr = zeroed();
+
+                // > The msg_name field points to the destination address of the datagram
+                // > which caused the error.
+                msghdr.msg_name = msg_name as _;
+                msghdr.msg_namelen = *msg_namelen;
+
+                // > The payload of the original packet that caused the error is passed as
+                // > normal data via msg_iovec.
+                msghdr.msg_iov = &mut iov;
+                msghdr.msg_iovlen = 1;
+
+                // > The error is supplied in a sock_extended_err structure.
+                msghdr.msg_control = control.as_mut_ptr() as _;
+                msghdr.msg_controllen = size_of_val(&control) as _;
+
+                // > When the user receives an error from a socket operation, the errors
+                // > can be received by calling recvmsg(2) with the MSG_ERRQUEUE flag set.
+                let len = libc::recvmsg(socket.as_raw_fd(), &mut msghdr, libc::MSG_ERRQUEUE);
+
+                if len < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+
+                *msg_namelen = msghdr.msg_namelen;
+
+                Ok((len as usize, packet_too_big_mtu(&msghdr)))
+            })
+        };
+
+        // the queue is empty once the call would block
+        let ((len, mtu), remote_address) = if let Ok(result) = result {
+            result
+        } else {
+            break;
+        };
+
+        count += 1;
+
+        if let (Some(mtu), Some(remote_address)) = (mtu, remote_address.as_socket()) {
+            let quoted = &quoted[..len.min(QUOTED_LEN)];
+            on_packet_too_big(remote_address.into(), quoted, mtu);
+        }
+    }
+
+    count
+}
+
+/// Returns true if `error` could have been caused by an ICMP message
+///
+/// With IP_RECVERR enabled, the error of the most recent ICMP message is also returned by the
+/// next send or receive call on the socket.
+pub fn is_icmp_error(error: &io::Error) -> bool {
+    // Errors from icmp_err_convert and icmpv6_err_convert in linux/net
+    matches!(
+        error.raw_os_error(),
+        Some(
+            libc::EMSGSIZE
+                | libc::ENETUNREACH
+                | libc::EHOSTUNREACH
+                | libc::ENOPROTOOPT
+                | libc::ECONNREFUSED
+                | libc::EOPNOTSUPP
+                | libc::ENONET
+                | libc::EHOSTDOWN
+                | libc::EACCES
+                | libc::EPROTO
+        )
+    )
+}
+
+/// Returns the MTU if the extended error in the `msghdr` is an ICMP Packet Too Big message
+///
+/// # Safety
+///
+/// `msghdr` must contain valid control messages
+unsafe fn packet_too_big_mtu(msghdr: &libc::msghdr) -> Option<u16> {
+    let mut cmsghdr = libc::CMSG_FIRSTHDR(msghdr);
+
+    while let Some(cmsg) = cmsghdr.as_ref() {
+        // make sure we have a length, otherwise it'll loop forever
+        if cmsg.cmsg_len == 0 {
+            break;
+        }
+
+        let is_recverr = matches!(
+            (cmsg.cmsg_level, cmsg.cmsg_type),
+            (libc::IPPROTO_IP, libc::IP_RECVERR) | (libc::IPPROTO_IPV6, libc::IPV6_RECVERR)
+        );
+
+        if is_recverr
+            && cmsg.cmsg_len as usize
+                >= libc::CMSG_LEN(size_of::<libc::sock_extended_err>() as _) as usize
+        {
+            let error: libc::sock_extended_err =
+                core::ptr::read_unaligned(libc::CMSG_DATA(cmsg) as *const _);
+
+            let is_packet_too_big = match error.ee_origin {
+                libc::SO_EE_ORIGIN_ICMP => {
+                    error.ee_type == ICMP_DEST_UNREACH && error.ee_code == ICMP_FRAG_NEEDED
+                }
+                libc::SO_EE_ORIGIN_ICMP6 => error.ee_type == ICMPV6_PKT_TOOBIG,
+                // Errors with a local origin don't quote the datagram
+                _ => false,
+            };
+
+            // > ee_info contains the discovered MTU for EMSGSIZE errors.
+            return if is_packet_too_big {
+                Some(error.ee_info.min(u16::MAX as u32) as u16)
+            } else {
+                None
+            };
+        }
+
+        cmsghdr = libc::CMSG_NXTHDR(msghdr, cmsg);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a `msghdr` with the given control messages
+    ///
+    /// Returns the buffer backing the control messages, which must outlive the `msghdr`.
+    fn msghdr(cmsgs: &[(libc::c_int, libc::c_int, &[u8])]) -> (libc::msghdr, Vec<u64>) {
+        let len: usize = cmsgs
+            .iter()
+            .map(|(_, _, data)| unsafe { libc::CMSG_SPACE(data.len() as _) } as usize)
+            .sum();
+        // use u64 to make sure the buffer is aligned for cmsghdr
+        let mut control = vec![0u64; (len + 7) / 8];
+
+        unsafe {
+            let mut msghdr: libc::msghdr = zeroed();
+            msghdr.msg_control = control.as_mut_ptr() as _;
+            msghdr.msg_controllen = len as _;
+
+            let mut cmsg = libc::CMSG_FIRSTHDR(&msghdr);
+            for (level, ty, data) in cmsgs {
+                let header = &mut *cmsg;
+                header.cmsg_level = *level;
+                header.cmsg_type = *ty;
+                header.cmsg_len = libc::CMSG_LEN(data.len() as _) as _;
+                core::ptr::copy_nonoverlapping(data.as_ptr(), libc::CMSG_DATA(cmsg), data.len());
+                cmsg = libc::CMSG_NXTHDR(&msghdr, cmsg);
+            }
+
+            (msghdr, control)
+        }
+    }
+
+    fn extended_err(origin: u8, ty: u8, code: u8, info: u32) -> Vec<u8> {
+        let mut error: libc::sock_extended_err = unsafe { zeroed() };
+        error.ee_errno = libc::EMSGSIZE as _;
+        error.ee_origin = origin;
+        error.ee_type = ty;
+        error.ee_code = code;
+        error.ee_info = info;
+
+        let bytes = unsafe {
+            core::slice::from_raw_parts(&error as *const _ as *const u8, size_of_val(&error))
+        };
+        bytes.to_vec()
+    }
+
+    fn mtu(cmsgs: &[(libc::c_int, libc::c_int, &[u8])]) -> Option<u16> {
+        let (msghdr, _control) = msghdr(cmsgs);
+        unsafe { packet_too_big_mtu(&msghdr) }
+    }
+
+    #[test]
+    fn packet_too_big_test() {
+        let ipv4 = extended_err(
+            libc::SO_EE_ORIGIN_ICMP,
+            ICMP_DEST_UNREACH,
+            ICMP_FRAG_NEEDED,
+            1300,
+        );
+        assert_eq!(
+            mtu(&[(libc::IPPROTO_IP, libc::IP_RECVERR, &ipv4)]),
+            Some(1300)
+        );
+
+        let ipv6 = extended_err(libc::SO_EE_ORIGIN_ICMP6, ICMPV6_PKT_TOOBIG, 0, 1280);
+        assert_eq!(
+            mtu(&[(libc::IPPROTO_IPV6, libc::IPV6_RECVERR, &ipv6)]),
+            Some(1280)
+        );
+
+        // the MTU is limited to the largest u16
+        let jumbo = extended_err(libc::SO_EE_ORIGIN_ICMP6, ICMPV6_PKT_TOOBIG, 0, 100_000);
+        assert_eq!(
+            mtu(&[(libc::IPPROTO_IPV6, libc::IPV6_RECVERR, &jumbo)]),
+            Some(u16::MAX)
+        );
+    }
+
+    #[test]
+    fn other_cmsgs_test() {
+        let error = extended_err(
+            libc::SO_EE_ORIGIN_ICMP,
+            ICMP_DEST_UNREACH,
+            ICMP_FRAG_NEEDED,
+            1300,
+        );
+        let pktinfo = [1u8; size_of::<libc::in_pktinfo>()];
+        let tos = [0u8; 1];
+
+        // the extended error is found after the other control messages
+        assert_eq!(
+            mtu(&[
+                (libc::IPPROTO_IP, libc::IP_PKTINFO, &pktinfo),
+                (libc::IPPROTO_IP, libc::IP_TOS, &tos),
+                (libc::IPPROTO_IP, libc::IP_RECVERR, &error),
+            ]),
+            Some(1300)
+        );
+
+        // the extended error is only read from a RECVERR cmsg
+        assert_eq!(mtu(&[(libc::IPPROTO_IP, libc::IP_PKTINFO, &error)]), None);
+        assert_eq!(mtu(&[]), None);
+    }
+
+    #[test]
+    fn other_errors_test() {
+        // port unreachable
+        let error = extended_err(libc::SO_EE_ORIGIN_ICMP, ICMP_DEST_UNREACH, 3, 0);
+        assert_eq!(mtu(&[(libc::IPPROTO_IP, libc::IP_RECVERR, &error)]), None);
+
+        // ICMPv4 type/code pairs aren't Packet Too Big messages for ICMPv6
+        let error = extended_err(
+            libc::SO_EE_ORIGIN_ICMP6,
+            ICMP_DEST_UNREACH,
+            ICMP_FRAG_NEEDED,
+            1300,
+        );
+        assert_eq!(
+            mtu(&[(libc::IPPROTO_IPV6, libc::IPV6_RECVERR, &error)]),
+            None
+        );
+
+        // locally generated errors don't quote a datagram
+        let error = extended_err(
+            libc::SO_EE_ORIGIN_LOCAL,
+            ICMP_DEST_UNREACH,
+            ICMP_FRAG_NEEDED,
+            1300,
+        );
+        assert_eq!(mtu(&[(libc::IPPROTO_IP, libc::IP_RECVERR, &error)]), None);
+    }
+
+    #[test]
+    fn truncated_error_test() {
+        let error = extended_err(
+            libc::SO_EE_ORIGIN_ICMP,
+            ICMP_DEST_UNREACH,
+            ICMP_FRAG_NEEDED,
+            1300,
+        );
+        let truncated = &error[..error.len() - 1];
+        assert_eq!(
+            mtu(&[(libc::IPPROTO_IP, libc::IP_RECVERR, truncated)]),
+            None
+        );
+    }
+
+    #[test]
+    fn is_icmp_error_test() {
+        assert!(is_icmp_error(&io::Error::from_raw_os_error(libc::EMSGSIZE)));
+        assert!(is_icmp_error(&io::Error::from_raw_os_error(
+            libc::ECONNREFUSED
+        )));
+        assert!(!is_icmp_error(&io::Error::from_raw_os_error(libc::EBADF)));
+        assert!(!is_icmp_error(&io::Error::new(
+            io::ErrorKind::Other,
+            "not an OS error"
+        )));
+    }
+}
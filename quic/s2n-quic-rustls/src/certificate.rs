@@ -4,6 +4,8 @@
 #![allow(dead_code)]
 
 use rustls::Error;
+use s2n_quic_core::crypto::tls::certificate;
+use std::sync::Arc;
 
 macro_rules! cert_type {
     ($name:ident, $trait:ident, $method:ident, $inner:ty) => {
@@ -85,6 +87,30 @@ cert_type!(
     Vec<rustls::Certificate>
 );
 
+/// A certificate chain and its private key
+pub type CertifiedKey = Arc<rustls::sign::CertifiedKey>;
+
+/// Certificates which are selected by the server name requested by the client
+pub type Certificates = certificate::Certificates<CertifiedKey>;
+
+/// Certificates which can be reloaded while the server is running
+pub type Store = certificate::Store<CertifiedKey>;
+
+/// Selects the certificate for a single handshake
+pub(crate) type Selector = certificate::Selector<CertifiedKey>;
+
+/// Loads a certificate chain and its private key
+pub fn certified_key<C: IntoCertificate, PK: IntoPrivateKey>(
+    certificate: C,
+    private_key: PK,
+) -> Result<CertifiedKey, Error> {
+    let chain = certificate.into_certificate()?;
+    let private_key = private_key.into_private_key()?;
+    let key = rustls::sign::any_supported_type(&private_key.0)
+        .map_err(|_| Error::General("invalid private key".into()))?;
+    Ok(Arc::new(rustls::sign::CertifiedKey::new(chain.0, key)))
+}
+
 mod pem {
     use super::*;
 
@@ -317,6 +317,28 @@ pub static DEFAULT_CIPHERSUITES: &[SupportedCipherSuite] = &[
     ciphers::TLS13_CHACHA20_POLY1305_SHA256,
 ];
 
+/// Ensures the configured cipher suites can be used to protect QUIC packets
+pub(crate) fn validate(
+    cipher_suites: &[SupportedCipherSuite],
+) -> Result<Vec<SupportedCipherSuite>, rustls::Error> {
+    if cipher_suites.is_empty() {
+        return Err(rustls::Error::General(
+            "at least one cipher suite is required".to_string(),
+        ));
+    }
+
+    for cipher_suite in cipher_suites {
+        if !DEFAULT_CIPHERSUITES.contains(cipher_suite) {
+            return Err(rustls::Error::General(format!(
+                "{:?} is not supported with QUIC",
+                cipher_suite.suite()
+            )));
+        }
+    }
+
+    Ok(cipher_suites.to_vec())
+}
+
 #[test]
 fn test_default_cipher_suites() {
     insta::assert_debug_snapshot!("default_cipher_suites", DEFAULT_CIPHERSUITES);
@@ -1,22 +1,29 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{certificate, encode_transport_parameters, session::Session};
+use crate::{
+    certificate, encode_transport_parameters, session::Session, session_ticket::ClientStore,
+};
 use core::convert::TryFrom;
-use rustls::{quic, ClientConfig};
+use rustls::{quic, ClientConfig, SupportedCipherSuite, SupportedKxGroup};
 use s2n_codec::EncoderValue;
-use s2n_quic_core::{application::ServerName, crypto::tls};
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::tls::{self, session_ticket},
+};
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct Client {
     config: Arc<ClientConfig>,
+    session_ticket_store: Option<Arc<dyn session_ticket::Store>>,
 }
 
 impl Client {
     pub fn new(config: ClientConfig) -> Self {
         Self {
             config: Arc::new(config),
+            session_ticket_store: None,
         }
     }
 
@@ -42,7 +49,10 @@ impl From<ClientConfig> for Client {
 
 impl From<Arc<ClientConfig>> for Client {
     fn from(config: Arc<ClientConfig>) -> Self {
-        Self { config }
+        Self {
+            config,
+            session_ticket_store: None,
+        }
     }
 }
 
@@ -70,15 +80,33 @@ impl tls::Endpoint for Client {
         let rustls_server_name =
             rustls::ServerName::try_from(server_name.as_ref()).expect("invalid server name");
 
+        // each connection gets its own session storage, which knows the server the tickets
+        // are issued by
+        let ticket_store = self
+            .session_ticket_store
+            .as_ref()
+            .map(|store| Arc::new(ClientStore::new(store.clone(), server_name.clone())));
+        let config = if let Some(ticket_store) = ticket_store.as_ref() {
+            let mut config = (*self.config).clone();
+            config.session_storage = ticket_store.clone();
+            Arc::new(config)
+        } else {
+            self.config.clone()
+        };
+
         let session = rustls::ClientConnection::new_quic(
-            self.config.clone(),
+            config,
             crate::QUIC_VERSION,
             rustls_server_name,
             transport_parameters,
         )
         .expect("could not create rustls client session");
 
-        Session::new(session.into(), Some(server_name))
+        let mut session = Session::new(session.into(), Some(server_name));
+        if let Some(ticket_store) = ticket_store {
+            session.with_ticket_store(ticket_store);
+        }
+        session
     }
 
     fn max_tag_length(&self) -> usize {
@@ -90,6 +118,12 @@ pub struct Builder {
     cert_store: rustls::RootCertStore,
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
+    early_data: bool,
+    session_ticket_store: Option<Arc<dyn session_ticket::Store>>,
+    client_identity: Option<(Vec<rustls::Certificate>, rustls::PrivateKey)>,
+    server_cert_verifier: Option<Arc<dyn rustls::client::ServerCertVerifier>>,
+    cipher_suites: Vec<SupportedCipherSuite>,
+    kx_groups: Vec<&'static SupportedKxGroup>,
 }
 
 impl Default for Builder {
@@ -104,6 +138,12 @@ impl Builder {
             cert_store: rustls::RootCertStore::empty(),
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
+            early_data: false,
+            session_ticket_store: None,
+            client_identity: None,
+            server_cert_verifier: None,
+            cipher_suites: crate::cipher_suite::DEFAULT_CIPHERSUITES.to_vec(),
+            kx_groups: rustls::ALL_KX_GROUPS.to_vec(),
         }
     }
 
@@ -121,6 +161,64 @@ impl Builder {
         Ok(self)
     }
 
+    /// Clears the certificates added with `with_certificate`
+    ///
+    /// rustls doesn't load the trust store of the host operating system, so the trust store
+    /// only contains the certificates added to the builder.
+    pub fn with_empty_trust_store(mut self) -> Result<Self, rustls::Error> {
+        self.cert_store = rustls::RootCertStore::empty();
+        Ok(self)
+    }
+
+    /// Sets the certificate chain and private key which are presented when the server requires
+    /// client authentication (mutual TLS)
+    pub fn with_client_identity<
+        C: certificate::IntoCertificate,
+        PK: certificate::IntoPrivateKey,
+    >(
+        mut self,
+        certificate: C,
+        private_key: PK,
+    ) -> Result<Self, rustls::Error> {
+        let certificate = certificate.into_certificate()?;
+        let private_key = private_key.into_private_key()?;
+        self.client_identity = Some((certificate.0, private_key.0));
+        Ok(self)
+    }
+
+    /// Sets the verifier for certificates presented by servers
+    ///
+    /// The verifier replaces the default validation against the trusted certificates,
+    /// including the check that the certificate is valid for the server name. This should only
+    /// be used when normal TLS validation is not appropriate.
+    pub fn with_server_cert_verifier(
+        mut self,
+        verifier: Arc<dyn rustls::client::ServerCertVerifier>,
+    ) -> Result<Self, rustls::Error> {
+        self.server_cert_verifier = Some(verifier);
+        Ok(self)
+    }
+
+    /// Sets the cipher suites which can be negotiated, in order of preference
+    ///
+    /// Only the cipher suites in [`crate::DEFAULT_CIPHERSUITES`] can protect QUIC packets.
+    pub fn with_cipher_suites(
+        mut self,
+        cipher_suites: &[SupportedCipherSuite],
+    ) -> Result<Self, rustls::Error> {
+        self.cipher_suites = crate::cipher_suite::validate(cipher_suites)?;
+        Ok(self)
+    }
+
+    /// Sets the key exchange groups which can be negotiated, in order of preference
+    pub fn with_kx_groups(
+        mut self,
+        kx_groups: &[&'static SupportedKxGroup],
+    ) -> Result<Self, rustls::Error> {
+        self.kx_groups = kx_groups.to_vec();
+        Ok(self)
+    }
+
     pub fn with_max_cert_chain_depth(self, len: u16) -> Result<Self, rustls::Error> {
         // TODO is there a way to configure this?
         let _ = len;
@@ -140,9 +238,24 @@ impl Builder {
         Ok(self)
     }
 
+    /// Sends early data (0-RTT) when resuming a session with a server
+    pub fn with_early_data(mut self) -> Result<Self, rustls::Error> {
+        self.early_data = true;
+        Ok(self)
+    }
+
+    /// Stores the session tickets issued by servers to resume sessions on later connections
+    pub fn with_session_ticket_store(
+        mut self,
+        store: Arc<dyn session_ticket::Store>,
+    ) -> Result<Self, rustls::Error> {
+        self.session_ticket_store = Some(store);
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Client, rustls::Error> {
         // TODO load system root store?
-        if self.cert_store.is_empty() {
+        if self.cert_store.is_empty() && self.server_cert_verifier.is_none() {
             //= https://www.rfc-editor.org/rfc/rfc9001#section-4.4
             //# A client MUST authenticate the identity of the server.
             return Err(rustls::Error::General(
@@ -150,20 +263,34 @@ impl Builder {
             ));
         }
 
-        let mut config = ClientConfig::builder()
-            .with_cipher_suites(crate::cipher_suite::DEFAULT_CIPHERSUITES)
-            .with_safe_default_kx_groups()
+        let builder = ClientConfig::builder()
+            .with_cipher_suites(&self.cipher_suites)
+            .with_kx_groups(&self.kx_groups)
             .with_protocol_versions(crate::PROTOCOL_VERSIONS)?
-            .with_root_certificates(self.cert_store)
-            .with_no_client_auth();
+            .with_root_certificates(self.cert_store);
+
+        let mut config = if let Some((certificates, private_key)) = self.client_identity {
+            builder.with_single_cert(certificates, private_key)?
+        } else {
+            builder.with_no_client_auth()
+        };
+
+        if let Some(verifier) = self.server_cert_verifier {
+            config.dangerous().set_certificate_verifier(verifier);
+        }
 
         config.max_fragment_size = None;
         config.alpn_protocols = self.application_protocols;
 
+        config.enable_early_data = self.early_data;
+
         if let Some(key_log) = self.key_log {
             config.key_log = key_log;
         }
 
-        Ok(Client::new(config))
+        Ok(Client {
+            config: Arc::new(config),
+            session_ticket_store: self.session_ticket_store,
+        })
     }
 }
@@ -8,11 +8,13 @@ pub use rustls::{self, Certificate, PrivateKey};
 mod cipher_suite;
 mod error;
 mod session;
+mod session_ticket;
 
 pub mod certificate;
 pub mod client;
 pub mod server;
 
+pub use cipher_suite::DEFAULT_CIPHERSUITES;
 pub use client::Client;
 pub use server::Server;
 
@@ -56,4 +58,131 @@ fn client_server_test() {
     }
 
     pair.finish();
+
+    assert!(
+        pair.client.context.peer_certificate_chain.is_some(),
+        "the server should present a certificate"
+    );
+    assert!(
+        pair.server.context.peer_certificate_chain.is_none(),
+        "the client should not present a certificate without client authentication"
+    );
+
+    let export = |session: &session::Session| {
+        let mut output = [0; 32];
+        tls::Session::export_keying_material(session, b"EXPORTER-test", b"context", &mut output)
+            .unwrap();
+        output
+    };
+    assert_eq!(export(&pair.client.session), export(&pair.server.session));
+}
+
+#[test]
+fn client_authentication_test() {
+    use s2n_quic_core::crypto::tls::{self, testing::certificates::*};
+
+    let mut client = client::Builder::new()
+        .with_certificate(MTLS_CA_CERT)
+        .unwrap()
+        .with_client_identity(MTLS_CLIENT_CERT, MTLS_CLIENT_KEY)
+        .unwrap()
+        .with_cipher_suites(&[rustls::cipher_suite::TLS13_AES_256_GCM_SHA384])
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut server = server::Builder::new()
+        .with_certificate(MTLS_SERVER_CERT, MTLS_SERVER_KEY)
+        .unwrap()
+        .with_client_authentication()
+        .unwrap()
+        .with_trusted_certificate(MTLS_CA_CERT)
+        .unwrap()
+        .with_kx_groups(&[&rustls::kx_group::X25519])
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    pair.finish();
+
+    assert!(
+        pair.server.context.peer_certificate_chain.is_some(),
+        "the client should present a certificate"
+    );
+}
+
+#[test]
+fn client_authentication_builder_test() {
+    use s2n_quic_core::crypto::tls::testing::certificates::*;
+
+    // client authentication needs something to verify client certificates with
+    assert!(server::Builder::new()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_client_authentication()
+        .unwrap()
+        .build()
+        .is_err());
+
+    // only TLS 1.3 cipher suites can protect QUIC packets
+    assert!(client::Builder::new()
+        .with_cipher_suites(&[rustls::cipher_suite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256])
+        .is_err());
+    assert!(client::Builder::new().with_cipher_suites(&[]).is_err());
+}
+
+#[test]
+fn certificate_store_test() {
+    use s2n_quic_core::crypto::tls::{self, testing::certificates::*};
+
+    // each handshake uses a new client so the session isn't resumed
+    let client = || {
+        client::Builder::new()
+            .with_certificate(CERT_PEM)
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+
+    let certified_key = || certificate::certified_key(CERT_PEM, KEY_PEM).unwrap();
+    let store = certificate::Store::new(
+        certificate::Certificates::new().with_server_name("localhost", certified_key()),
+    );
+
+    let mut server = server::Builder::new()
+        .with_certificate_store(store.clone())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut pair = tls::testing::Pair::new(&mut server, &mut client(), "localhost".into());
+
+    // the handshake selects from the certificates loaded when it started
+    store.reload(certificate::Certificates::new().with_default(certified_key()));
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    pair.finish();
+    assert_eq!(
+        pair.server.context.server_certificate,
+        Some(Some("localhost".into()))
+    );
+
+    // the certificates can be replaced without rebuilding the server
+    let mut pair = tls::testing::Pair::new(&mut server, &mut client(), "localhost".into());
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    pair.finish();
+    assert_eq!(pair.server.context.server_certificate, Some(None));
 }
@@ -2,26 +2,71 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{certificate, encode_transport_parameters, session::Session};
-use rustls::{quic, ServerConfig};
+use rustls::{
+    quic,
+    server::{AllowAnyAuthenticatedClient, ClientCertVerifier, NoClientAuth},
+    RootCertStore, ServerConfig, SupportedCipherSuite, SupportedKxGroup,
+};
 use s2n_codec::EncoderValue;
-use s2n_quic_core::{application::ServerName, crypto::tls};
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::tls::{self, session_ticket::KeyRotation},
+};
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct Server {
     config: Arc<ServerConfig>,
+    /// The config used for connections where the early data controller rejected early data
+    config_without_early_data: Arc<ServerConfig>,
+    certificate_store: Option<certificate::Store>,
 }
 
 impl Server {
     pub fn new(config: ServerConfig) -> Self {
-        Self {
-            config: Arc::new(config),
-        }
+        Arc::new(config).into()
     }
 
     pub fn builder() -> Builder {
         Builder::new()
     }
+
+    fn new_session<Params: EncoderValue>(
+        &self,
+        config: Arc<ServerConfig>,
+        transport_parameters: &Params,
+    ) -> Session {
+        use quic::ServerQuicExt;
+
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-8.2
+        //# Endpoints MUST send the quic_transport_parameters extension;
+        let transport_parameters = encode_transport_parameters(transport_parameters);
+
+        // The resolver of the shared config can't tell which connection it's resolving for, so
+        // each connection gets a copy of the config which selects from the certificates loaded
+        // for it and records the selection
+        let selector = self
+            .certificate_store
+            .as_ref()
+            .map(|store| Arc::new(certificate::Selector::new(store.load())));
+        let config = if let Some(selector) = selector.as_ref() {
+            let mut config = (*config).clone();
+            config.cert_resolver = Arc::new(ResolvesFromSelector(selector.clone()));
+            Arc::new(config)
+        } else {
+            config
+        };
+
+        let session =
+            rustls::ServerConnection::new_quic(config, crate::QUIC_VERSION, transport_parameters)
+                .expect("could not create rustls server session");
+
+        let mut session = Session::new(session.into(), None);
+        if let Some(selector) = selector {
+            session.with_certificate_selector(selector);
+        }
+        session
+    }
 }
 
 impl Default for Server {
@@ -40,7 +85,19 @@ impl From<ServerConfig> for Server {
 
 impl From<Arc<ServerConfig>> for Server {
     fn from(config: Arc<ServerConfig>) -> Self {
-        Self { config }
+        let config_without_early_data = if config.max_early_data_size == 0 {
+            config.clone()
+        } else {
+            let mut config_without_early_data = (*config).clone();
+            config_without_early_data.max_early_data_size = 0;
+            Arc::new(config_without_early_data)
+        };
+
+        Self {
+            config,
+            config_without_early_data,
+            certificate_store: None,
+        }
     }
 }
 
@@ -51,20 +108,14 @@ impl tls::Endpoint for Server {
         &mut self,
         transport_parameters: &Params,
     ) -> Self::Session {
-        use quic::ServerQuicExt;
-
-        //= https://www.rfc-editor.org/rfc/rfc9001#section-8.2
-        //# Endpoints MUST send the quic_transport_parameters extension;
-        let transport_parameters = encode_transport_parameters(transport_parameters);
-
-        let session = rustls::ServerConnection::new_quic(
-            self.config.clone(),
-            crate::QUIC_VERSION,
-            transport_parameters,
-        )
-        .expect("could not create rustls server session");
+        self.new_session(self.config.clone(), transport_parameters)
+    }
 
-        Session::new(session.into(), None)
+    fn new_server_session_without_early_data<Params: EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+    ) -> Self::Session {
+        self.new_session(self.config_without_early_data.clone(), transport_parameters)
     }
 
     fn new_client_session<Params: EncoderValue>(
@@ -82,8 +133,16 @@ impl tls::Endpoint for Server {
 
 pub struct Builder {
     cert_resolver: Option<Arc<dyn rustls::server::ResolvesServerCert>>,
+    certificate_store: Option<certificate::Store>,
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
+    early_data: bool,
+    session_tickets: Option<KeyRotation>,
+    trust_store: RootCertStore,
+    client_authentication: bool,
+    client_cert_verifier: Option<Arc<dyn ClientCertVerifier>>,
+    cipher_suites: Vec<SupportedCipherSuite>,
+    kx_groups: Vec<&'static SupportedKxGroup>,
 }
 
 impl Default for Builder {
@@ -96,8 +155,16 @@ impl Builder {
     pub fn new() -> Self {
         Self {
             cert_resolver: None,
+            certificate_store: None,
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
+            early_data: false,
+            session_tickets: None,
+            trust_store: RootCertStore::empty(),
+            client_authentication: false,
+            client_cert_verifier: None,
+            cipher_suites: crate::cipher_suite::DEFAULT_CIPHERSUITES.to_vec(),
+            kx_groups: rustls::ALL_KX_GROUPS.to_vec(),
         }
     }
 
@@ -106,10 +173,8 @@ impl Builder {
         certificate: C,
         private_key: PK,
     ) -> Result<Self, rustls::Error> {
-        let certificate = certificate.into_certificate()?;
-        let private_key = private_key.into_private_key()?;
-        let resolver = AlwaysResolvesChain::new(certificate, private_key)?;
-        let resolver = Arc::new(resolver);
+        let certified_key = certificate::certified_key(certificate, private_key)?;
+        let resolver = Arc::new(AlwaysResolvesChain(certified_key));
         self.with_cert_resolver(resolver)
     }
 
@@ -118,6 +183,20 @@ impl Builder {
         cert_resolver: Arc<dyn rustls::server::ResolvesServerCert>,
     ) -> Result<Self, rustls::Error> {
         self.cert_resolver = Some(cert_resolver);
+        self.certificate_store = None;
+        Ok(self)
+    }
+
+    /// Presents certificates from `store`, selected by the server name requested by the client
+    ///
+    /// The certificates can be replaced with [`certificate::Store::reload`] while the server is
+    /// running. The selected certificate is reported with the `ServerCertificateSelected` event.
+    pub fn with_certificate_store(
+        mut self,
+        store: certificate::Store,
+    ) -> Result<Self, rustls::Error> {
+        self.cert_resolver = Some(Arc::new(ResolvesFromStore(store.clone())));
+        self.certificate_store = Some(store);
         Ok(self)
     }
 
@@ -129,17 +208,109 @@ impl Builder {
         Ok(self)
     }
 
+    /// Adds a certificate which is trusted to sign client certificates
+    pub fn with_trusted_certificate<C: certificate::IntoCertificate>(
+        mut self,
+        certificate: C,
+    ) -> Result<Self, rustls::Error> {
+        let certificates = certificate.into_certificate()?;
+        for certificate in certificates.0.iter() {
+            self.trust_store
+                .add(certificate)
+                .map_err(|err| rustls::Error::General(err.to_string()))?;
+        }
+        Ok(self)
+    }
+
+    /// Clears the certificates added with `with_trusted_certificate`
+    ///
+    /// rustls doesn't load the trust store of the host operating system, so the trust store
+    /// only contains the certificates added to the builder.
+    pub fn with_empty_trust_store(mut self) -> Result<Self, rustls::Error> {
+        self.trust_store = RootCertStore::empty();
+        Ok(self)
+    }
+
+    /// Configures this server instance to require client authentication (mutual TLS).
+    ///
+    /// Client certificates are verified against the trusted certificates, unless a verifier is
+    /// set with `with_client_cert_verifier`.
+    pub fn with_client_authentication(mut self) -> Result<Self, rustls::Error> {
+        self.client_authentication = true;
+        Ok(self)
+    }
+
+    /// Sets the verifier for certificates presented by clients
+    ///
+    /// The verifier decides if client authentication is offered or required, which takes
+    /// precedence over `with_client_authentication`.
+    pub fn with_client_cert_verifier(
+        mut self,
+        verifier: Arc<dyn ClientCertVerifier>,
+    ) -> Result<Self, rustls::Error> {
+        self.client_cert_verifier = Some(verifier);
+        Ok(self)
+    }
+
+    /// Sets the cipher suites which can be negotiated, in order of preference
+    ///
+    /// Only the cipher suites in [`crate::DEFAULT_CIPHERSUITES`] can protect QUIC packets.
+    pub fn with_cipher_suites(
+        mut self,
+        cipher_suites: &[SupportedCipherSuite],
+    ) -> Result<Self, rustls::Error> {
+        self.cipher_suites = crate::cipher_suite::validate(cipher_suites)?;
+        Ok(self)
+    }
+
+    /// Sets the key exchange groups which can be negotiated, in order of preference
+    pub fn with_kx_groups(
+        mut self,
+        kx_groups: &[&'static SupportedKxGroup],
+    ) -> Result<Self, rustls::Error> {
+        self.kx_groups = kx_groups.to_vec();
+        Ok(self)
+    }
+
     pub fn with_key_logging(mut self) -> Result<Self, rustls::Error> {
         self.key_log = Some(Arc::new(rustls::KeyLogFile::new()));
         Ok(self)
     }
 
+    /// Accepts early data (0-RTT) from clients resuming a session
+    ///
+    /// Early data can be replayed by an attacker, so applications should only enable it for
+    /// requests that are safe to process more than once.
+    pub fn with_early_data(mut self) -> Result<Self, rustls::Error> {
+        self.early_data = true;
+        Ok(self)
+    }
+
+    /// Issues session tickets to clients, encrypted with keys rotated according to `rotation`
+    pub fn with_session_tickets(mut self, rotation: KeyRotation) -> Result<Self, rustls::Error> {
+        self.session_tickets = Some(rotation);
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Server, rustls::Error> {
+        let client_cert_verifier = if let Some(verifier) = self.client_cert_verifier {
+            verifier
+        } else if self.client_authentication {
+            if self.trust_store.is_empty() {
+                return Err(rustls::Error::General(
+                    "client authentication requires trusted certificate(s)".to_string(),
+                ));
+            }
+            AllowAnyAuthenticatedClient::new(self.trust_store)
+        } else {
+            NoClientAuth::new()
+        };
+
         let builder = ServerConfig::builder()
-            .with_cipher_suites(crate::cipher_suite::DEFAULT_CIPHERSUITES)
-            .with_safe_default_kx_groups()
+            .with_cipher_suites(&self.cipher_suites)
+            .with_kx_groups(&self.kx_groups)
             .with_protocol_versions(crate::PROTOCOL_VERSIONS)?
-            .with_no_client_auth();
+            .with_client_cert_verifier(client_cert_verifier);
 
         let mut config = if let Some(cert_resolver) = self.cert_resolver {
             builder.with_cert_resolver(cert_resolver)
@@ -153,28 +324,28 @@ impl Builder {
         config.max_fragment_size = None;
         config.alpn_protocols = self.application_protocols;
 
+        if self.early_data {
+            //= https://www.rfc-editor.org/rfc/rfc9001#section-4.6.1
+            //# Servers MUST NOT send the early_data extension with a
+            //# max_early_data_size field set to any value other than 0xffffffff.
+            config.max_early_data_size = u32::MAX;
+        }
+
+        if let Some(rotation) = self.session_tickets {
+            config.ticketer = Arc::new(crate::session_ticket::Ticketer::new(rotation)?);
+        }
+
         if let Some(key_log) = self.key_log {
             config.key_log = key_log;
         }
 
-        Ok(Server::new(config))
+        let mut server = Server::new(config);
+        server.certificate_store = self.certificate_store;
+        Ok(server)
     }
 }
 
-struct AlwaysResolvesChain(Arc<rustls::sign::CertifiedKey>);
-
-impl AlwaysResolvesChain {
-    fn new(
-        chain: certificate::Certificate,
-        priv_key: certificate::PrivateKey,
-    ) -> Result<Self, rustls::Error> {
-        let key = rustls::sign::any_supported_type(&priv_key.0)
-            .map_err(|_| rustls::Error::General("invalid private key".into()))?;
-        Ok(Self(Arc::new(rustls::sign::CertifiedKey::new(
-            chain.0, key,
-        ))))
-    }
-}
+struct AlwaysResolvesChain(certificate::CertifiedKey);
 
 impl rustls::server::ResolvesServerCert for AlwaysResolvesChain {
     fn resolve(
@@ -184,3 +355,27 @@ impl rustls::server::ResolvesServerCert for AlwaysResolvesChain {
         Some(Arc::clone(&self.0))
     }
 }
+
+struct ResolvesFromStore(certificate::Store);
+
+impl rustls::server::ResolvesServerCert for ResolvesFromStore {
+    fn resolve(
+        &self,
+        client_hello: rustls::server::ClientHello,
+    ) -> Option<Arc<rustls::sign::CertifiedKey>> {
+        let certificates = self.0.load();
+        let selection = certificates.select(client_hello.server_name())?;
+        Some(Arc::clone(selection.certificate))
+    }
+}
+
+struct ResolvesFromSelector(Arc<certificate::Selector>);
+
+impl rustls::server::ResolvesServerCert for ResolvesFromSelector {
+    fn resolve(
+        &self,
+        client_hello: rustls::server::ClientHello,
+    ) -> Option<Arc<rustls::sign::CertifiedKey>> {
+        self.0.select(client_hello.server_name()).cloned()
+    }
+}
@@ -1,8 +1,10 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::cipher_suite::{
-    HeaderProtectionKey, HeaderProtectionKeys, OneRttKey, PacketKey, PacketKeys,
+use crate::{
+    certificate::Selector,
+    cipher_suite::{HeaderProtectionKey, HeaderProtectionKeys, OneRttKey, PacketKey, PacketKeys},
+    session_ticket::{self, ClientStore, Messages},
 };
 use bytes::Bytes;
 use core::{fmt, fmt::Debug, task::Poll};
@@ -12,9 +14,10 @@ use rustls::{
 };
 use s2n_quic_core::{
     application::ServerName,
-    crypto::{self, tls, CryptoError},
+    crypto::{self, tls, tls::HandshakeType, CryptoError},
     transport,
 };
+use std::sync::Arc;
 
 pub struct Session {
     connection: Connection,
@@ -25,6 +28,16 @@ pub struct Session {
     emitted_server_name: bool,
     emitted_application_protocol: bool,
     server_name: Option<ServerName>,
+    /// Records the certificate the server selected, if it uses a certificate store
+    certificate_selector: Option<Arc<Selector>>,
+    /// The handshake messages received by a client in the Initial space
+    initial_messages: Messages,
+    /// Set if the client is resuming a session accepted by the server
+    resumed: bool,
+    /// The storage for the session tickets received by a client
+    ticket_store: Option<Arc<ClientStore>>,
+    /// The handshake messages received by a client after the handshake
+    application_messages: Messages,
 }
 
 impl fmt::Debug for Session {
@@ -47,10 +60,57 @@ impl Session {
             emitted_server_name: false,
             emitted_application_protocol: false,
             server_name,
+            certificate_selector: None,
+            initial_messages: Default::default(),
+            resumed: false,
+            ticket_store: None,
+            application_messages: Default::default(),
+        }
+    }
+
+    /// Reports the certificate recorded by `selector` once the handshake completes
+    pub(crate) fn with_certificate_selector(&mut self, selector: Arc<Selector>) {
+        self.certificate_selector = Some(selector);
+    }
+
+    /// Reports the session tickets received by a client to `store`
+    pub(crate) fn with_ticket_store(&mut self, store: Arc<ClientStore>) {
+        self.ticket_store = Some(store);
+    }
+
+    /// Inspects the messages received by a client for details which rustls doesn't expose
+    fn on_client_data(&mut self, crypto_data: &[u8]) {
+        match self.rx_phase {
+            HandshakePhase::Initial => {
+                let resumed = &mut self.resumed;
+                self.initial_messages
+                    .read(crypto_data, |msg_type, message| {
+                        if msg_type == HandshakeType::ServerHello {
+                            *resumed |= session_ticket::accepts_pre_shared_key(message);
+                        }
+                    });
+            }
+            HandshakePhase::Application => {
+                if let Some(store) = self.ticket_store.as_ref() {
+                    self.application_messages
+                        .read(crypto_data, |msg_type, message| {
+                            if msg_type == HandshakeType::NewSessionTicket {
+                                store.on_new_session_ticket(message);
+                            }
+                        });
+                }
+            }
+            HandshakePhase::Handshake => {}
         }
     }
 
     fn receive(&mut self, crypto_data: &[u8]) -> Result<(), transport::Error> {
+        if let Connection::Client(_) = &self.connection {
+            // the messages are inspected before rustls processes them, since rustls stores
+            // the tickets as it processes the messages
+            self.on_client_data(crypto_data);
+        }
+
         self.connection
             .read_hs(crypto_data)
             .map_err(crate::error::reason)
@@ -148,6 +208,35 @@ impl Session {
 
             // the handshake is complete!
             if !self.emitted_handshake_complete {
+                match &self.connection {
+                    Connection::Server(server) => {
+                        let resumed = server.received_resumption_data().is_some();
+                        context.on_session_resumption(resumed)?;
+
+                        // resumed sessions don't present a certificate
+                        let selected = self
+                            .certificate_selector
+                            .as_ref()
+                            .and_then(|selector| selector.selected());
+                        if let (false, Some(server_name)) = (resumed, selected) {
+                            context.on_server_certificate(server_name.as_deref())?;
+                        }
+                    }
+                    // rustls only indicates if a session was resumed on the server, so the
+                    // client relies on the ServerHello
+                    Connection::Client(_) => context.on_session_resumption(self.resumed)?,
+                }
+
+                if let Some(chain) = self.connection.peer_certificates() {
+                    if !chain.is_empty() {
+                        let chain = chain
+                            .iter()
+                            .map(|certificate| Bytes::copy_from_slice(&certificate.0))
+                            .collect();
+                        context.on_peer_certificate_chain(chain)?;
+                    }
+                }
+
                 self.rx_phase.transition();
                 context.on_handshake_complete()?;
             }
@@ -253,6 +342,12 @@ impl Session {
 
                             let application_parameters = self.application_parameters()?;
 
+                            if self.emitted_zero_rtt_keys {
+                                if let Connection::Client(client) = &self.connection {
+                                    context.on_early_data(client.is_early_data_accepted())?;
+                                }
+                            }
+
                             context.on_one_rtt_keys(key, header_key, application_parameters)?;
 
                             // Transition the tx_phase to Application
@@ -310,6 +405,20 @@ impl tls::Session for Session {
         self.emit_events(context)?;
         result
     }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), transport::Error> {
+        self.connection
+            .export_keying_material(output, label, Some(context))
+            .map_err(|error| {
+                CryptoError::INTERNAL_ERROR.with_reason(crate::error::reason(error))
+            })?;
+        Ok(())
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
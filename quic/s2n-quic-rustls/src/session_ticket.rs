@@ -0,0 +1,446 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::{convert::TryInto, time::Duration};
+use rustls::{client::StoresClientSessions, server::ProducesTickets};
+use s2n_codec::{DecoderBuffer, DecoderError};
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::tls::{
+        session_ticket::{KeyRotation, Store, Ticket},
+        HandshakeHeader, HandshakeType,
+    },
+};
+use s2n_quic_crypto::{
+    rand::{SecureRandom, SystemRandom},
+    ring_aead as aead,
+};
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+/// Adapts a [`Store`] to the rustls client session storage of a single connection
+///
+/// rustls doesn't expose the server name or the lifetime of the tickets it stores, so each
+/// connection has its own storage, which is told about the lifetime of each ticket by the
+/// session as it receives `NewSessionTicket` messages. rustls stores the peer's transport
+/// parameters in the session value, so tickets are stored without separate transport
+/// parameters.
+pub struct ClientStore {
+    store: Arc<dyn Store>,
+    server_name: ServerName,
+    /// The lifetimes of the tickets received by the session that rustls hasn't stored yet
+    lifetimes: Mutex<VecDeque<Duration>>,
+    looked_up: AtomicBool,
+}
+
+impl ClientStore {
+    pub fn new(store: Arc<dyn Store>, server_name: ServerName) -> Self {
+        Self {
+            store,
+            server_name,
+            lifetimes: Default::default(),
+            looked_up: AtomicBool::new(false),
+        }
+    }
+
+    /// Called by the session with each `NewSessionTicket` message before rustls processes it
+    pub fn on_new_session_ticket(&self, message: &[u8]) {
+        //= https://www.rfc-editor.org/rfc/rfc8446#section-4.6.1
+        //# struct {
+        //#     uint32 ticket_lifetime;
+        //#     uint32 ticket_age_add;
+        //#     opaque ticket_nonce<0..255>;
+        //#     opaque ticket<1..2^16-1>;
+        //#     Extension extensions<0..2^16-2>;
+        //# } NewSessionTicket;
+        if let Ok((lifetime, _)) = DecoderBuffer::new(message).decode::<u32>() {
+            let lifetime = Duration::from_secs(lifetime as u64);
+            self.lifetimes.lock().unwrap().push_back(lifetime);
+        }
+    }
+}
+
+impl StoresClientSessions for ClientStore {
+    fn put(&self, _key: Vec<u8>, value: Vec<u8>) -> bool {
+        // rustls stores a value for every `NewSessionTicket` message, after the session has
+        // reported its lifetime. Anything else is a key exchange hint, which isn't stored.
+        if let Some(lifetime) = self.lifetimes.lock().unwrap().pop_front() {
+            let ticket = Ticket::new(value.into(), None, lifetime);
+            self.store.on_ticket(&self.server_name, ticket);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn get(&self, _key: &[u8]) -> Option<Vec<u8>> {
+        // rustls looks up the session to resume before anything else when it starts the
+        // handshake, so any later lookups are for key exchange hints
+        if self.looked_up.swap(true, Ordering::Relaxed) {
+            return None;
+        }
+
+        let ticket = self.store.take(&self.server_name)?;
+        Some(ticket.session.to_vec())
+    }
+}
+
+/// Reassembles the handshake messages received by a client
+///
+/// This allows the client to learn about the session from the messages, which rustls doesn't
+/// expose.
+#[derive(Debug, Default)]
+pub struct Messages {
+    buffer: Vec<u8>,
+    disabled: bool,
+}
+
+impl Messages {
+    /// Messages larger than this are rejected by rustls, so they're not reassembled
+    const MAX_LEN: usize = u16::MAX as usize;
+
+    /// Appends `data` and calls `on_message` with each complete message
+    pub fn read<F: FnMut(HandshakeType, &[u8])>(&mut self, data: &[u8], mut on_message: F) {
+        if self.disabled {
+            return;
+        }
+
+        self.buffer.extend_from_slice(data);
+
+        let mut offset = 0;
+        while let Ok((header, buffer)) =
+            DecoderBuffer::new(&self.buffer[offset..]).decode::<HandshakeHeader>()
+        {
+            let len = header.len();
+
+            if len > Self::MAX_LEN {
+                self.disabled = true;
+                self.buffer = Vec::new();
+                return;
+            }
+
+            let message = if let Ok((message, _)) = buffer.decode_slice(len) {
+                message
+            } else {
+                // wait for the rest of the message
+                break;
+            };
+
+            if let Some(msg_type) = header.msg_type() {
+                on_message(msg_type, message.into_less_safe_slice());
+            }
+
+            offset += core::mem::size_of::<HandshakeHeader>() + len;
+        }
+
+        self.buffer.drain(..offset);
+    }
+}
+
+/// Returns `true` if the `ServerHello` message indicates the server accepted a pre-shared key
+///
+/// Servers only accept a pre-shared key from a session ticket if they resume the session.
+pub fn accepts_pre_shared_key(server_hello: &[u8]) -> bool {
+    //= https://www.rfc-editor.org/rfc/rfc8446#section-4.2
+    //# pre_shared_key(41),                         /* RFC 8446 */
+    const PRE_SHARED_KEY: u16 = 41;
+
+    //= https://www.rfc-editor.org/rfc/rfc8446#section-4.1.3
+    //# struct {
+    //#     ProtocolVersion legacy_version = 0x0303;    /* TLS v1.2 */
+    //#     Random random;
+    //#     opaque legacy_session_id_echo<0..32>;
+    //#     CipherSuite cipher_suite;
+    //#     uint8 legacy_compression_method = 0;
+    //#     Extension extensions<6..2^16-1>;
+    //# } ServerHello;
+    let parse = || -> Result<bool, DecoderError> {
+        let buffer = DecoderBuffer::new(server_hello).skip(2 + 32)?;
+        let buffer = buffer.skip_with_len_prefix::<u8>()?;
+        let buffer = buffer.skip(2 + 1)?;
+        let (mut extensions, _) = buffer.decode_slice_with_len_prefix::<u16>()?;
+
+        while !extensions.is_empty() {
+            let (extension_type, buffer) = extensions.decode::<u16>()?;
+            if extension_type == PRE_SHARED_KEY {
+                return Ok(true);
+            }
+            extensions = buffer.skip_with_len_prefix::<u16>()?;
+        }
+
+        Ok(false)
+    };
+
+    parse().unwrap_or(false)
+}
+
+/// Encrypts session tickets with keys rotated according to a [`KeyRotation`] policy
+pub struct Ticketer {
+    rotation: KeyRotation,
+    random: SystemRandom,
+    keys: Mutex<VecDeque<Key>>,
+}
+
+struct Key {
+    id: [u8; KEY_ID_LEN],
+    introduced: Instant,
+    key: aead::LessSafeKey,
+}
+
+const KEY_ID_LEN: usize = 4;
+const NONCE_LEN: usize = aead::NONCE_LEN;
+
+impl Ticketer {
+    pub fn new(rotation: KeyRotation) -> Result<Self, rustls::Error> {
+        let ticketer = Self {
+            rotation,
+            random: SystemRandom::new(),
+            keys: Default::default(),
+        };
+
+        // make sure a key can be generated before accepting any connections
+        ticketer.key_for_encrypt(Instant::now())?;
+
+        Ok(ticketer)
+    }
+
+    fn generate_key(&self, now: Instant) -> Result<Key, rustls::Error> {
+        let mut id = [0; KEY_ID_LEN];
+        let mut material = [0; 32];
+        self.random
+            .fill(&mut id)
+            .and_then(|_| self.random.fill(&mut material))
+            .map_err(|_| rustls::Error::FailedToGetRandomBytes)?;
+
+        let key = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, &material)
+            .map_err(|_| rustls::Error::General("invalid ticket key".into()))?;
+
+        Ok(Key {
+            id,
+            introduced: now,
+            key: aead::LessSafeKey::new(key),
+        })
+    }
+
+    /// Rotates the keys if needed and returns the id of the key to encrypt tickets with
+    fn key_for_encrypt(&self, now: Instant) -> Result<[u8; KEY_ID_LEN], rustls::Error> {
+        let mut keys = self.keys.lock().unwrap();
+
+        // discard any keys that are no longer allowed to decrypt tickets
+        while keys.front().map_or(false, |key| {
+            now.saturating_duration_since(key.introduced) >= self.rotation.key_lifetime()
+        }) {
+            keys.pop_front();
+        }
+
+        match keys.back() {
+            Some(key)
+                if now.saturating_duration_since(key.introduced)
+                    < self.rotation.encrypt_period() =>
+            {
+                Ok(key.id)
+            }
+            _ => {
+                let key = self.generate_key(now)?;
+                let id = key.id;
+                keys.push_back(key);
+                Ok(id)
+            }
+        }
+    }
+}
+
+impl ProducesTickets for Ticketer {
+    fn enabled(&self) -> bool {
+        true
+    }
+
+    fn lifetime(&self) -> u32 {
+        self.rotation.ticket_lifetime().as_secs() as u32
+    }
+
+    fn encrypt(&self, plain: &[u8]) -> Option<Vec<u8>> {
+        let id = self.key_for_encrypt(Instant::now()).ok()?;
+
+        // Random nonces are used so tickets can't be linked to each other
+        let mut nonce = [0; NONCE_LEN];
+        self.random.fill(&mut nonce).ok()?;
+
+        let mut ticket = Vec::with_capacity(KEY_ID_LEN + NONCE_LEN + plain.len() + 16);
+        ticket.extend_from_slice(&id);
+        ticket.extend_from_slice(&nonce);
+        ticket.extend_from_slice(plain);
+
+        let keys = self.keys.lock().unwrap();
+        let key = keys.iter().rev().find(|key| key.id == id)?;
+        let tag = key
+            .key
+            .seal_in_place_separate_tag(
+                aead::Nonce::assume_unique_for_key(nonce),
+                aead::Aad::from(&id),
+                &mut ticket[KEY_ID_LEN + NONCE_LEN..],
+            )
+            .ok()?;
+        ticket.extend_from_slice(tag.as_ref());
+
+        Some(ticket)
+    }
+
+    fn decrypt(&self, ticket: &[u8]) -> Option<Vec<u8>> {
+        let id: [u8; KEY_ID_LEN] = ticket.get(..KEY_ID_LEN)?.try_into().ok()?;
+        let nonce = ticket.get(KEY_ID_LEN..KEY_ID_LEN + NONCE_LEN)?;
+        let nonce = aead::Nonce::try_assume_unique_for_key(nonce).ok()?;
+        let mut payload = ticket.get(KEY_ID_LEN + NONCE_LEN..)?.to_vec();
+
+        let now = Instant::now();
+        let keys = self.keys.lock().unwrap();
+        let key = keys.iter().find(|key| {
+            key.id == id
+                && now.saturating_duration_since(key.introduced) < self.rotation.key_lifetime()
+        })?;
+
+        let len = key
+            .key
+            .open_in_place(nonce, aead::Aad::from(&id), &mut payload)
+            .ok()?
+            .len();
+        payload.truncate(len);
+
+        Some(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticketer_round_trip_test() {
+        let ticketer = Ticketer::new(KeyRotation::default()).unwrap();
+        let ticket = ticketer.encrypt(b"session state").unwrap();
+        assert_eq!(ticketer.decrypt(&ticket).unwrap(), b"session state");
+
+        let mut tampered = ticket.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(ticketer.decrypt(&tampered).is_none());
+        assert!(ticketer.decrypt(&ticket[..KEY_ID_LEN]).is_none());
+    }
+
+    #[derive(Default)]
+    struct Tickets(Mutex<Vec<(ServerName, Ticket)>>);
+
+    impl Store for Tickets {
+        fn on_ticket(&self, server_name: &ServerName, ticket: Ticket) {
+            self.0.lock().unwrap().push((server_name.clone(), ticket));
+        }
+
+        fn take(&self, server_name: &ServerName) -> Option<Ticket> {
+            let mut tickets = self.0.lock().unwrap();
+            let index = tickets.iter().position(|(name, _)| name == server_name)?;
+            Some(tickets.remove(index).1)
+        }
+    }
+
+    fn new_session_ticket(lifetime: u32) -> Vec<u8> {
+        let mut message = lifetime.to_be_bytes().to_vec();
+        // ticket_age_add, ticket_nonce, ticket and extensions
+        message.extend_from_slice(&[0, 0, 0, 0, 0, 0, 1, 42, 0, 0]);
+        message
+    }
+
+    #[test]
+    fn client_store_test() {
+        let tickets = Arc::new(Tickets::default());
+        let store = ClientStore::new(tickets.clone(), "localhost".into());
+
+        // key exchange hints aren't stored as tickets
+        assert!(!store.put(b"hint".to_vec(), vec![1]));
+        assert!(tickets.0.lock().unwrap().is_empty());
+
+        store.on_new_session_ticket(&new_session_ticket(3600));
+        store.on_new_session_ticket(&new_session_ticket(60));
+        assert!(store.put(b"ticket".to_vec(), vec![2]));
+        assert!(store.put(b"ticket".to_vec(), vec![3]));
+        assert!(!store.put(b"hint".to_vec(), vec![4]));
+
+        {
+            let tickets = tickets.0.lock().unwrap();
+            assert_eq!(tickets.len(), 2);
+            assert_eq!(&*tickets[0].0, "localhost");
+            assert_eq!(tickets[0].1.lifetime, Duration::from_secs(3600));
+            assert_eq!(&tickets[0].1.session[..], &[2]);
+            assert_eq!(tickets[1].1.lifetime, Duration::from_secs(60));
+        }
+
+        // only the first lookup takes a ticket
+        assert_eq!(store.get(b"ticket"), Some(vec![2]));
+        assert_eq!(store.get(b"hint"), None);
+        assert_eq!(tickets.0.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn messages_test() {
+        let mut stream = vec![];
+        for (msg_type, body) in [(4u8, &[1u8, 2, 3][..]), (8, &[]), (4, &[4; 300])] {
+            stream.push(msg_type);
+            stream.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+            stream.extend_from_slice(body);
+        }
+
+        // the messages are reassembled regardless of how the stream is split up
+        for chunk_len in [1, 2, 5, 100, stream.len()] {
+            let mut messages = Messages::default();
+            let mut received = vec![];
+            for chunk in stream.chunks(chunk_len) {
+                messages.read(chunk, |msg_type, body| {
+                    received.push((msg_type == HandshakeType::NewSessionTicket, body.to_vec()))
+                });
+            }
+
+            assert_eq!(
+                received,
+                vec![(true, vec![1, 2, 3]), (false, vec![]), (true, vec![4; 300])]
+            );
+            assert!(messages.buffer.is_empty());
+        }
+    }
+
+    #[test]
+    fn accepts_pre_shared_key_test() {
+        fn server_hello(extensions: &[(u16, &[u8])]) -> Vec<u8> {
+            let mut message = vec![0x03, 0x03];
+            message.extend_from_slice(&[0; 32]);
+            // session id
+            message.extend_from_slice(&[2, 1, 2]);
+            // cipher suite and compression method
+            message.extend_from_slice(&[0x13, 0x01, 0]);
+
+            let mut encoded = vec![];
+            for (extension_type, data) in extensions {
+                encoded.extend_from_slice(&extension_type.to_be_bytes());
+                encoded.extend_from_slice(&(data.len() as u16).to_be_bytes());
+                encoded.extend_from_slice(data);
+            }
+            message.extend_from_slice(&(encoded.len() as u16).to_be_bytes());
+            message.extend_from_slice(&encoded);
+            message
+        }
+
+        // supported_versions and key_share
+        let full = server_hello(&[(43, &[3, 4]), (51, &[0, 29, 0, 1, 9])]);
+        assert!(!accepts_pre_shared_key(&full));
+
+        let resumed = server_hello(&[(43, &[3, 4]), (51, &[0, 29, 0, 1, 9]), (41, &[0, 0])]);
+        assert!(accepts_pre_shared_key(&resumed));
+
+        // truncated messages are ignored
+        assert!(!accepts_pre_shared_key(&resumed[..resumed.len() - 4]));
+        assert!(!accepts_pre_shared_key(&[]));
+    }
+}
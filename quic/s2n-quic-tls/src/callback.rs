@@ -9,7 +9,8 @@ use s2n_quic_core::{
     endpoint, transport,
 };
 use s2n_quic_crypto::{
-    handshake::HandshakeKey, hkdf, one_rtt::OneRttKey, ring_aead as aead, Prk, SecretPair, Suite,
+    handshake::HandshakeKey, hkdf, one_rtt::OneRttKey, ring_aead as aead, zero_rtt::ZeroRttKey,
+    Prk, SecretPair, Suite,
 };
 use s2n_tls::{connection::Connection, error::Fallible, ffi::*};
 
@@ -158,14 +159,13 @@ where
         id: s2n_secret_type_t::Type,
         secret: &mut [u8],
     ) -> Result<(), transport::Error> {
+        // The early traffic secret isn't paired with a server secret
+        if id == s2n_secret_type_t::CLIENT_EARLY_TRAFFIC_SECRET {
+            return self.on_early_traffic_secret(conn, secret);
+        }
+
         match core::mem::replace(&mut self.state.secrets, Secrets::Waiting) {
             Secrets::Waiting => {
-                if id == s2n_secret_type_t::CLIENT_EARLY_TRAFFIC_SECRET {
-                    // TODO enable with 0rtt
-                    // I couldn't find a good citation but here's the issue: https://github.com/aws/s2n-quic/issues/301
-                    return Ok(());
-                }
-
                 let (prk_algo, _aead) = get_algo_type(conn).ok_or(CryptoError::INTERNAL_ERROR)?;
                 let secret = Prk::new_less_safe(prk_algo, secret);
                 self.state.secrets = Secrets::Half { secret, id };
@@ -213,16 +213,26 @@ where
 
                 match self.state.tx_phase {
                     HandshakePhase::Initial => {
-                        let (key, header_key) = HandshakeKey::new(self.endpoint, aead_algo, pair)
-                            .expect("invalid cipher");
+                        let (key, header_key) = HandshakeKey::new(
+                            self.endpoint,
+                            self.context.quic_version(),
+                            aead_algo,
+                            pair,
+                        )
+                        .expect("invalid cipher");
 
                         self.context.on_handshake_keys(key, header_key)?;
                         self.state.tx_phase.transition();
                         self.state.rx_phase.transition();
                     }
                     _ => {
-                        let (key, header_key) =
-                            OneRttKey::new(self.endpoint, aead_algo, pair).expect("invalid cipher");
+                        let (key, header_key) = OneRttKey::new(
+                            self.endpoint,
+                            self.context.quic_version(),
+                            aead_algo,
+                            pair,
+                        )
+                        .expect("invalid cipher");
 
                         let params = unsafe {
                             // Safety: conn needs to outlive params
@@ -238,6 +248,17 @@ where
                             get_application_params(conn)?
                         };
 
+                        if self.endpoint.is_client() && self.state.emitted_zero_rtt_keys {
+                            //= https://www.rfc-editor.org/rfc/rfc9001#section-4.6.2
+                            //# A server rejects 0-RTT by sending the EncryptedExtensions without an
+                            //# early_data extension.
+                            let accepted = unsafe {
+                                // Safety: conn is valid for the duration of the callback
+                                get_early_data_accepted(conn)?
+                            };
+                            self.context.on_early_data(accepted)?;
+                        }
+
                         self.context.on_one_rtt_keys(key, header_key, params)?;
                     }
                 }
@@ -247,6 +268,52 @@ where
         }
     }
 
+    /// Installs the 0-RTT keys derived from the client early traffic secret
+    ///
+    /// s2n-tls only emits the secret on a server once it accepts early data, and on a client
+    /// once it offers early data with a resumed session.
+    fn on_early_traffic_secret(
+        &mut self,
+        conn: *mut s2n_connection,
+        secret: &mut [u8],
+    ) -> Result<(), transport::Error> {
+        let (prk_algo, aead_algo) = get_algo_type(conn).ok_or(CryptoError::INTERNAL_ERROR)?;
+        let secret = Prk::new_less_safe(prk_algo, secret);
+        let (key, header_key) =
+            ZeroRttKey::new_negotiated(self.context.quic_version(), aead_algo, secret)
+                .ok_or(CryptoError::INTERNAL_ERROR)?;
+
+        match self.endpoint {
+            endpoint::Type::Server => {
+                let params = unsafe {
+                    // Safety: conn needs to outlive params
+                    get_application_params(conn)?
+                };
+                self.context.on_zero_rtt_keys(key, header_key, params)?;
+            }
+            endpoint::Type::Client => {
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4.1
+                //# When sending frames in 0-RTT packets, a client MUST only use
+                //# remembered transport parameters;
+                let transport_parameters =
+                    if let Some(params) = self.state.zero_rtt_parameters.as_ref() {
+                        params
+                    } else {
+                        // without the remembered parameters the client can't send 0-RTT packets
+                        return Ok(());
+                    };
+                let params = tls::ApplicationParameters {
+                    transport_parameters,
+                };
+                self.context.on_zero_rtt_keys(key, header_key, params)?;
+            }
+        }
+
+        self.state.emitted_zero_rtt_keys = true;
+
+        Ok(())
+    }
+
     /// The function s2n-tls calls when it wants to send data
     unsafe extern "C" fn send_cb(
         context: *mut c_void,
@@ -344,9 +411,17 @@ pub struct State {
     rx_phase: HandshakePhase,
     tx_phase: HandshakePhase,
     secrets: Secrets,
+    /// The transport parameters the server sent on the connection a client is resuming
+    zero_rtt_parameters: Option<Bytes>,
+    emitted_zero_rtt_keys: bool,
 }
 
 impl State {
+    /// Sets the transport parameters a client uses for the 0-RTT packets it sends
+    pub fn with_zero_rtt_parameters(&mut self, params: Bytes) {
+        self.zero_rtt_parameters = Some(params);
+    }
+
     /// Complete the handshake
     pub fn on_handshake_complete(&mut self) {
         debug_assert_eq!(self.tx_phase, HandshakePhase::Handshake);
@@ -486,6 +561,21 @@ unsafe fn get_application_protocol<'a>(
         .ok_or(CryptoError::MISSING_EXTENSION)
 }
 
+/// Returns true if the server accepted the early data the client offered
+unsafe fn get_early_data_accepted(connection: *mut s2n_connection) -> Result<bool, CryptoError> {
+    let mut status = s2n_early_data_status_t::EARLY_DATA_STATUS_NOT_REQUESTED;
+
+    s2n_connection_get_early_data_status(connection, &mut status)
+        .into_result()
+        .map_err(|_| CryptoError::INTERNAL_ERROR)?;
+
+    Ok(matches!(
+        status,
+        s2n_early_data_status_t::EARLY_DATA_STATUS_OK
+            | s2n_early_data_status_t::EARLY_DATA_STATUS_END
+    ))
+}
+
 unsafe fn get_transport_parameters<'a>(connection: *mut s2n_connection) -> Option<&'a [u8]> {
     let mut ptr = core::ptr::null();
     let mut len = 0u16;
@@ -2,7 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use bytes::Bytes;
-use s2n_tls::error::Error;
+use core::pin::Pin;
+use s2n_quic_core::crypto::tls::certificate;
+use s2n_tls::{
+    callbacks::{ClientHelloCallback, ConnectionFuture},
+    config::Config,
+    connection::Connection,
+    error::Error,
+};
+use std::sync::Arc;
 
 impl Format {
     pub fn as_pem(&self) -> Option<&[u8]> {
@@ -109,5 +117,54 @@ macro_rules! cert_type {
 cert_type!(PrivateKey, IntoPrivateKey, into_private_key);
 cert_type!(Certificate, IntoCertificate, into_certificate);
 
+/// Certificates which are selected by the server name requested by the client
+///
+/// Each certificate is a server [`Config`] loaded with the certificate chain, such as
+/// `Config::from(Server::builder().with_certificate(certificate, private_key)?.build()?)`.
+pub type Certificates = certificate::Certificates<Config>;
+
+/// Certificates which can be reloaded while the server is running
+pub type Store = certificate::Store<Config>;
+
+/// Selects the certificate for a single handshake
+pub(crate) type Selector = certificate::Selector<Config>;
+
+/// The client hello callback of a server, which selects its certificate from a store before
+/// calling the application's handler
+pub(crate) struct ClientHello {
+    pub(crate) store: Option<Store>,
+    pub(crate) handler: Option<Arc<dyn ClientHelloCallback>>,
+}
+
+impl ClientHelloCallback for ClientHello {
+    fn on_client_hello(
+        &self,
+        connection: &mut Connection,
+    ) -> Result<Option<Pin<Box<dyn ConnectionFuture>>>, Error> {
+        if let Some(store) = self.store.as_ref() {
+            // Select from the certificates the session loaded when it was created, so the
+            // selection it reports is the certificate presented here
+            let config = if let Some(selector) = connection.application_context::<Selector>() {
+                selector.select(connection.server_name()).cloned()
+            } else {
+                store
+                    .load()
+                    .select(connection.server_name())
+                    .map(|selection| selection.certificate.clone())
+            };
+
+            if let Some(config) = config {
+                connection.set_config(config)?;
+            }
+        }
+
+        if let Some(handler) = self.handler.as_ref() {
+            return handler.on_client_hello(connection);
+        }
+
+        Ok(None)
+    }
+}
+
 #[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_private_key")))]
 pub const OFFLOAD_PRIVATE_KEY: PrivateKey = PrivateKey(Format::None);
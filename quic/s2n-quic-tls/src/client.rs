@@ -9,7 +9,11 @@ use crate::{
     ConfigLoader,
 };
 use s2n_codec::EncoderValue;
-use s2n_quic_core::{application::ServerName, crypto::tls, endpoint};
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::tls::{self, session_ticket},
+    endpoint,
+};
 use s2n_tls::{
     callbacks::VerifyHostNameCallback,
     config::{self, Config},
@@ -23,6 +27,8 @@ pub struct Client<L: ConfigLoader = Config> {
     #[allow(dead_code)] // we need to hold on to the handle to ensure it is cleaned up correctly
     keylog: Option<KeyLogHandle>,
     params: Params,
+    session_ticket_store: Option<Arc<dyn session_ticket::Store>>,
+    early_data: bool,
 }
 
 impl Client {
@@ -44,6 +50,8 @@ impl<L: ConfigLoader> Client<L> {
             loader,
             keylog: None,
             params: Default::default(),
+            session_ticket_store: None,
+            early_data: false,
         }
     }
 }
@@ -66,6 +74,8 @@ impl<L: ConfigLoader> ConfigLoader for Client<L> {
 pub struct Builder {
     config: config::Builder,
     keylog: Option<KeyLogHandle>,
+    session_ticket_store: Option<Arc<dyn session_ticket::Store>>,
+    early_data: bool,
 }
 
 impl Default for Builder {
@@ -79,6 +89,8 @@ impl Default for Builder {
         Self {
             config,
             keylog: None,
+            session_ticket_store: None,
+            early_data: false,
         }
     }
 }
@@ -181,11 +193,35 @@ impl Builder {
         Ok(self)
     }
 
+    /// Stores the session tickets issued by servers to resume sessions on later connections
+    pub fn with_session_ticket_store(
+        mut self,
+        store: Arc<dyn session_ticket::Store>,
+    ) -> Result<Self, Error> {
+        self.config.enable_session_tickets(true)?;
+        self.config
+            .set_session_ticket_callback(crate::session_ticket::ClientStore(store.clone()))?;
+        self.session_ticket_store = Some(store);
+        Ok(self)
+    }
+
+    /// Sends early data (0-RTT) when resuming a session with a server that accepts it
+    ///
+    /// Early data can be replayed by an attacker, so it should only carry requests that are safe
+    /// to process more than once. Sessions are only resumed with a
+    /// [`with_session_ticket_store`](Self::with_session_ticket_store) store.
+    pub fn with_early_data(mut self) -> Result<Self, Error> {
+        self.early_data = true;
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Client, Error> {
         Ok(Client {
             loader: self.config.build()?,
             keylog: self.keylog,
             params: Default::default(),
+            session_ticket_store: self.session_ticket_store,
+            early_data: self.early_data,
         })
     }
 }
@@ -205,12 +241,32 @@ impl<L: ConfigLoader> tls::Endpoint for Client<L> {
         let config = self.loader.load(crate::ConnectionContext {
             server_name: Some(&server_name),
         });
+        let ticket = self
+            .session_ticket_store
+            .as_ref()
+            .and_then(|store| store.take(&server_name));
+        let early_data = self.early_data;
         self.params.with(params, |params| {
-            Session::new(endpoint::Type::Client, config, params, Some(server_name)).unwrap()
+            let mut session =
+                Session::new(endpoint::Type::Client, config, params, Some(server_name)).unwrap();
+            if let Some(ticket) = ticket {
+                session.with_session_ticket(&ticket.session);
+
+                if early_data {
+                    if let Some(transport_parameters) = ticket.transport_parameters {
+                        session.with_zero_rtt_parameters(transport_parameters);
+                    }
+                }
+            }
+            session
         })
     }
 
     fn max_tag_length(&self) -> usize {
         s2n_quic_crypto::MAX_TAG_LEN
     }
+
+    fn supports_quic_version(&self, version: u32) -> bool {
+        s2n_quic_core::version::is_supported(version)
+    }
 }
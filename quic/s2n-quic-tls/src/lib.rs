@@ -55,6 +55,7 @@ mod session;
 pub mod certificate;
 pub mod client;
 pub mod server;
+pub mod session_ticket;
 
 pub use client::Client;
 pub use server::Server;
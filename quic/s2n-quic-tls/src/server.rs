@@ -2,24 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    certificate::{Format, IntoCertificate, IntoPrivateKey},
+    certificate::{self, Format, IntoCertificate, IntoPrivateKey},
     keylog::KeyLogHandle,
     params::Params,
     session::Session,
+    session_ticket::{self, KeyRotation, KeySource},
     ConfigLoader,
 };
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{application::ServerName, crypto::tls, endpoint};
-#[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_client_hello")))]
-use s2n_tls::callbacks::ClientHelloCallback;
-#[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_private_key")))]
-use s2n_tls::callbacks::PrivateKeyCallback;
 use s2n_tls::{
-    callbacks::VerifyHostNameCallback,
+    callbacks::{ClientHelloCallback, VerifyHostNameCallback},
     config::{self, Config},
     enums::ClientAuthType,
     error::Error,
 };
+#[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_private_key")))]
+use s2n_tls::{
+    callbacks::{ConnectionFuture, PrivateKeyCallback, PrivateKeyOperation},
+    connection::Connection,
+};
 use std::sync::Arc;
 
 pub struct Server<L: ConfigLoader = Config> {
@@ -27,6 +29,8 @@ pub struct Server<L: ConfigLoader = Config> {
     #[allow(dead_code)] // we need to hold on to the handle to ensure it is cleaned up correctly
     keylog: Option<KeyLogHandle>,
     params: Params,
+    certificate_store: Option<certificate::Store>,
+    session_tickets: Option<session_ticket::Rotation>,
 }
 
 impl Server {
@@ -48,7 +52,28 @@ impl<L: ConfigLoader> Server<L> {
             loader,
             keylog: None,
             params: Default::default(),
+            certificate_store: None,
+            session_tickets: None,
+        }
+    }
+
+    fn load_config(&mut self, cx: crate::ConnectionContext, early_data: bool) -> Config {
+        if let Some(session_tickets) = self.session_tickets.as_mut() {
+            return session_tickets.load(early_data);
+        }
+
+        self.loader.load(cx)
+    }
+
+    fn new_session<Params: EncoderValue>(&mut self, params: &Params, early_data: bool) -> Session {
+        let config = self.load_config(crate::ConnectionContext { server_name: None }, early_data);
+        let mut session = self.params.with(params, |params| {
+            Session::new(endpoint::Type::Server, config, params, None).unwrap()
+        });
+        if let Some(store) = self.certificate_store.as_ref() {
+            session.with_certificates(store.load());
         }
+        session
     }
 }
 
@@ -63,7 +88,7 @@ impl Default for Server {
 impl<L: ConfigLoader> ConfigLoader for Server<L> {
     #[inline]
     fn load(&mut self, cx: crate::ConnectionContext) -> s2n_tls::config::Config {
-        self.loader.load(cx)
+        self.load_config(cx, true)
     }
 }
 
@@ -73,82 +98,140 @@ impl<L: ConfigLoader> From<Server<L>> for Config {
     }
 }
 
+/// A setting applied to the config
+type Step = Arc<dyn Fn(&mut config::Builder) -> Result<(), Error> + Send + Sync>;
+
 pub struct Builder {
     config: config::Builder,
+    /// The settings applied to `config`
+    ///
+    /// The steps are replayed to build a new config each time the session ticket keys rotate,
+    /// since s2n-tls doesn't allow a config to be changed once it's built.
+    steps: Vec<Step>,
     keylog: Option<KeyLogHandle>,
+    certificate_store: Option<certificate::Store>,
+    client_hello_handler: Option<Arc<dyn ClientHelloCallback>>,
+    session_tickets: Option<(KeyRotation, Arc<dyn KeySource>)>,
+    early_data: bool,
 }
 
 impl Default for Builder {
     fn default() -> Self {
-        let mut config = config::Builder::default();
-        config.enable_quic().unwrap();
-        // https://github.com/aws/s2n-tls/blob/main/docs/USAGE-GUIDE.md#s2n_config_set_cipher_preferences
-        config.set_security_policy(crate::DEFAULT_POLICY).unwrap();
-        config.set_application_protocol_preference([b"h3"]).unwrap();
-
         Self {
-            config,
+            config: default_config(),
+            steps: vec![],
             keylog: None,
+            certificate_store: None,
+            client_hello_handler: None,
+            session_tickets: None,
+            early_data: false,
         }
     }
 }
 
+fn default_config() -> config::Builder {
+    let mut config = config::Builder::default();
+    config.enable_quic().unwrap();
+    // https://github.com/aws/s2n-tls/blob/main/docs/USAGE-GUIDE.md#s2n_config_set_cipher_preferences
+    config.set_security_policy(crate::DEFAULT_POLICY).unwrap();
+    config.set_application_protocol_preference([b"h3"]).unwrap();
+    config
+}
+
 impl Builder {
+    /// Applies `step` to the config and records it so it can be replayed
+    fn apply<F>(mut self, step: F) -> Result<Self, Error>
+    where
+        F: Fn(&mut config::Builder) -> Result<(), Error> + 'static + Send + Sync,
+    {
+        step(&mut self.config)?;
+        self.steps.push(Arc::new(step));
+        Ok(self)
+    }
+
+    /// Calls `handler` when the server receives a client hello
+    ///
+    /// If a certificate store is configured with [`Self::with_certificate_store`], the handler
+    /// is called after the certificate was selected.
     #[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_client_hello")))]
     pub fn with_client_hello_handler<T: 'static + ClientHelloCallback>(
         mut self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_client_hello_callback(handler)?;
+        self.client_hello_handler = Some(Arc::new(handler));
         Ok(self)
     }
 
     #[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_private_key")))]
     pub fn with_private_key_handler<T: 'static + PrivateKeyCallback>(
-        mut self,
+        self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_private_key_callback(handler)?;
-        Ok(self)
+        let handler = Shared(Arc::new(handler));
+        self.apply(move |config| {
+            config.set_private_key_callback(handler.clone())?;
+            Ok(())
+        })
     }
 
     pub fn with_application_protocols<P: IntoIterator<Item = I>, I: AsRef<[u8]>>(
-        mut self,
+        self,
         protocols: P,
     ) -> Result<Self, Error> {
-        self.config.set_application_protocol_preference(protocols)?;
-        Ok(self)
+        let protocols: Vec<Vec<u8>> = protocols
+            .into_iter()
+            .map(|protocol| protocol.as_ref().to_vec())
+            .collect();
+        self.apply(move |config| {
+            config.set_application_protocol_preference(&protocols)?;
+            Ok(())
+        })
     }
 
     pub fn with_certificate<C: IntoCertificate, PK: IntoPrivateKey>(
-        mut self,
+        self,
         certificate: C,
         private_key: PK,
     ) -> Result<Self, Error> {
         let private_key = private_key.into_private_key()?.0;
         let certificate = certificate.into_certificate()?.0;
-        let certificate = certificate
-            .as_pem()
-            .expect("pem is currently the only certificate format supported");
-        match private_key {
-            Format::Pem(bytes) => self.config.load_pem(certificate, bytes.as_ref())?,
-            Format::None => self.config.load_public_pem(certificate)?,
-            Format::Der(_) => panic!("der private keys not supported"),
-        };
+        self.apply(move |config| {
+            let certificate = certificate
+                .as_pem()
+                .expect("pem is currently the only certificate format supported");
+            match &private_key {
+                Format::Pem(bytes) => config.load_pem(certificate, bytes.as_ref())?,
+                Format::None => config.load_public_pem(certificate)?,
+                Format::Der(_) => panic!("der private keys not supported"),
+            };
+            Ok(())
+        })
+    }
+
+    /// Presents certificates from `store`, selected by the server name requested by the client
+    ///
+    /// The certificates can be replaced with [`certificate::Store::reload`] while the server is
+    /// running. Connections which don't match a certificate in the store use the certificate
+    /// configured with [`Self::with_certificate`]. The selected certificate is reported with
+    /// the `ServerCertificateSelected` event.
+    pub fn with_certificate_store(mut self, store: certificate::Store) -> Result<Self, Error> {
+        self.certificate_store = Some(store);
         Ok(self)
     }
 
     pub fn with_trusted_certificate<C: IntoCertificate>(
-        mut self,
+        self,
         certificate: C,
     ) -> Result<Self, Error> {
         let certificate = certificate.into_certificate()?;
-        let certificate = certificate
-            .0
-            .as_pem()
-            .expect("pem is currently the only certificate format supported");
-        self.config.trust_pem(certificate)?;
-        Ok(self)
+        self.apply(move |config| {
+            let certificate = certificate
+                .0
+                .as_pem()
+                .expect("pem is currently the only certificate format supported");
+            config.trust_pem(certificate)?;
+            Ok(())
+        })
     }
 
     /// Clears the default trust store for this client.
@@ -160,26 +243,29 @@ impl Builder {
     /// Note that call ordering matters. The caller should call this
     /// method before making any calls to `with_trusted_certificate()`.
     /// Calling this method after a method that modifies the trust store will clear it.
-    pub fn with_empty_trust_store(mut self) -> Result<Self, Error> {
-        self.config.wipe_trust_store()?;
-        Ok(self)
+    pub fn with_empty_trust_store(self) -> Result<Self, Error> {
+        self.apply(|config| {
+            config.wipe_trust_store()?;
+            Ok(())
+        })
     }
 
     /// Configures this server instance to require client authentication (mutual TLS).
-    pub fn with_client_authentication(mut self) -> Result<Self, Error> {
-        self.config.set_client_auth_type(ClientAuthType::Required)?;
-        Ok(self)
+    pub fn with_client_authentication(self) -> Result<Self, Error> {
+        self.apply(|config| {
+            config.set_client_auth_type(ClientAuthType::Required)?;
+            Ok(())
+        })
     }
 
     /// Set the application level certificate verification handler which will be invoked on this
     /// server instance when a client certificate is presented during the mutual TLS handshake.
     #[deprecated(note = "use `with_verify_host_name_callback` instead")]
     pub fn with_verify_client_certificate_handler<T: 'static + VerifyHostNameCallback>(
-        mut self,
+        self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_verify_host_callback(handler)?;
-        Ok(self)
+        self.with_verify_host_name_callback(handler)
     }
 
     /// Set the host name verification callback.
@@ -187,52 +273,162 @@ impl Builder {
     /// This will be invoked when a client certificate is presented during a mutual TLS
     /// handshake.
     pub fn with_verify_host_name_callback<T: 'static + VerifyHostNameCallback>(
-        mut self,
+        self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_verify_host_callback(handler)?;
-        Ok(self)
+        let handler = Shared(Arc::new(handler));
+        self.apply(move |config| {
+            config.set_verify_host_callback(handler.clone())?;
+            Ok(())
+        })
     }
 
     pub fn with_key_logging(mut self) -> Result<Self, Error> {
         use crate::keylog::KeyLog;
 
-        self.keylog = KeyLog::try_open();
-
-        unsafe {
-            // Safety: the KeyLog is stored on `self` to ensure it outlives `config`
-            if let Some(keylog) = self.keylog.as_ref() {
-                self.config
-                    .set_key_log_callback(Some(KeyLog::callback), Arc::as_ptr(keylog) as *mut _)?;
-            } else {
-                // disable key logging if it failed to create a file
-                self.config
-                    .set_key_log_callback(None, core::ptr::null_mut())?;
+        let keylog = KeyLog::try_open();
+        self.keylog = keylog.clone();
+
+        self.apply(move |config| {
+            unsafe {
+                // Safety: the KeyLog is stored on the step and the server to ensure it outlives
+                // every `config` built from them
+                if let Some(keylog) = keylog.as_ref() {
+                    config.set_key_log_callback(
+                        Some(KeyLog::callback),
+                        Arc::as_ptr(keylog) as *mut _,
+                    )?;
+                } else {
+                    // disable key logging if it failed to create a file
+                    config.set_key_log_callback(None, core::ptr::null_mut())?;
+                }
             }
-        }
+            Ok(())
+        })
+    }
+
+    /// Issues session tickets to clients, encrypted with random keys rotated according to
+    /// `rotation`
+    ///
+    /// The keys are only known to this server, so clients can only resume their sessions with
+    /// it until it restarts. Use [`Self::with_session_ticket_keys`] to share keys between
+    /// servers.
+    pub fn with_session_tickets(self, rotation: KeyRotation) -> Result<Self, Error> {
+        self.with_session_ticket_keys(rotation, session_ticket::RandomKeys::default())
+    }
+
+    /// Issues session tickets to clients, encrypted with keys from `source` rotated according
+    /// to `rotation`
+    ///
+    /// The server loads the keys it needs from `source` at the start of every encrypt period.
+    /// If `source` fails to provide a key, the server keeps using the keys it already loaded
+    /// and tries again shortly after.
+    pub fn with_session_ticket_keys<K: KeySource>(
+        mut self,
+        rotation: KeyRotation,
+        source: K,
+    ) -> Result<Self, Error> {
+        self.session_tickets = Some((rotation, Arc::new(source)));
+        Ok(self)
+    }
 
+    /// Accepts early data (0-RTT) from clients resuming a session
+    ///
+    /// Early data can be replayed by an attacker, so it should only be accepted for requests that
+    /// are safe to process more than once. Session tickets must also be enabled with
+    /// [`Self::with_session_tickets`] or [`Self::with_session_ticket_keys`].
+    pub fn with_early_data(mut self) -> Result<Self, Error> {
+        self.early_data = true;
         Ok(self)
     }
 
-    pub fn build(self) -> Result<Server, Error> {
+    pub fn build(mut self) -> Result<Server, Error> {
+        if self.early_data && self.session_tickets.is_none() {
+            return Err(Error::application(
+                "early data requires session tickets to be enabled".into(),
+            ));
+        }
+
+        // the certificate store and the application's handler share the client hello callback
+        if self.certificate_store.is_some() || self.client_hello_handler.is_some() {
+            let store = self.certificate_store.clone();
+            let handler = self.client_hello_handler.take();
+            self = self.apply(move |config| {
+                config.set_client_hello_callback(certificate::ClientHello {
+                    store: store.clone(),
+                    handler: handler.clone(),
+                })?;
+                Ok(())
+            })?;
+        }
+
+        let early_data = self.early_data;
+        let session_tickets = if let Some((rotation, source)) = self.session_tickets {
+            let steps = self.steps;
+            let new_config: session_ticket::NewConfig = Box::new(move || {
+                let mut config = default_config();
+                for step in steps.iter() {
+                    step(&mut config)?;
+                }
+                Ok(config)
+            });
+            Some(session_ticket::Rotation::new(
+                new_config, rotation, source, early_data,
+            )?)
+        } else {
+            None
+        };
+
         Ok(Server {
             loader: self.config.build()?,
             keylog: self.keylog,
             params: Default::default(),
+            certificate_store: self.certificate_store,
+            session_tickets,
         })
     }
 }
 
+/// Shares a callback between the configs built from the same [`Builder`]
+struct Shared<T>(Arc<T>);
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+#[cfg(any(test, all(s2n_quic_unstable, feature = "unstable_private_key")))]
+impl<T: PrivateKeyCallback> PrivateKeyCallback for Shared<T> {
+    #[inline]
+    fn handle_operation(
+        &self,
+        connection: &mut Connection,
+        operation: PrivateKeyOperation,
+    ) -> Result<Option<core::pin::Pin<Box<dyn ConnectionFuture>>>, Error> {
+        self.0.handle_operation(connection, operation)
+    }
+}
+
+impl<T: VerifyHostNameCallback> VerifyHostNameCallback for Shared<T> {
+    #[inline]
+    fn verify_host_name(&self, host_name: &str) -> bool {
+        self.0.verify_host_name(host_name)
+    }
+}
+
 impl<L: ConfigLoader> tls::Endpoint for Server<L> {
     type Session = Session;
 
     fn new_server_session<Params: EncoderValue>(&mut self, params: &Params) -> Self::Session {
-        let config = self
-            .loader
-            .load(crate::ConnectionContext { server_name: None });
-        self.params.with(params, |params| {
-            Session::new(endpoint::Type::Server, config, params, None).unwrap()
-        })
+        self.new_session(params, true)
+    }
+
+    fn new_server_session_without_early_data<Params: EncoderValue>(
+        &mut self,
+        params: &Params,
+    ) -> Self::Session {
+        self.new_session(params, false)
     }
 
     fn new_client_session<Params: EncoderValue>(
@@ -246,4 +442,8 @@ impl<L: ConfigLoader> tls::Endpoint for Server<L> {
     fn max_tag_length(&self) -> usize {
         s2n_quic_crypto::MAX_TAG_LEN
     }
+
+    fn supports_quic_version(&self, version: u32) -> bool {
+        s2n_quic_core::version::is_supported(version)
+    }
 }
@@ -1,8 +1,11 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::callback::{self, Callback};
-use bytes::BytesMut;
+use crate::{
+    callback::{self, Callback},
+    certificate::{Certificates, Selector},
+};
+use bytes::{Bytes, BytesMut};
 use core::{marker::PhantomData, task::Poll};
 use s2n_quic_core::{
     application::ServerName,
@@ -16,6 +19,7 @@ use s2n_tls::{
     enums::{Blinding, Mode},
     error::Error,
 };
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct Session {
@@ -27,6 +31,8 @@ pub struct Session {
     emitted_server_name: bool,
     // This is only set for the client to avoid an extra allocation
     server_name: Option<ServerName>,
+    /// An error encountered while setting up the session, which is returned on the first poll
+    error: Option<transport::Error>,
 }
 
 impl Session {
@@ -61,8 +67,49 @@ impl Session {
             send_buffer: BytesMut::new(),
             emitted_server_name: false,
             server_name,
+            error: None,
         })
     }
+
+    /// Resumes the session from a ticket previously issued by the server
+    pub(crate) fn with_session_ticket(&mut self, ticket: &[u8]) {
+        if self.connection.set_session_ticket(ticket).is_err() {
+            self.error = Some(
+                CryptoError::INTERNAL_ERROR
+                    .with_reason("the session ticket could not be used")
+                    .into(),
+            );
+        }
+    }
+
+    /// Sends early data within the limits of the transport parameters the server sent on the
+    /// connection being resumed
+    pub(crate) fn with_zero_rtt_parameters(&mut self, params: Bytes) {
+        self.state.with_zero_rtt_parameters(params);
+    }
+
+    /// Selects the certificate from `certificates` when the client hello is received and
+    /// reports it once the handshake completes
+    pub(crate) fn with_certificates(&mut self, certificates: Arc<Certificates>) {
+        self.connection
+            .set_application_context(Selector::new(certificates));
+    }
+
+    /// Returns the DER-encoded certificates presented by the peer, if any
+    fn peer_certificate_chain(&self) -> Option<Vec<Bytes>> {
+        let chain = self.connection.peer_cert_chain().ok()?;
+        let chain = chain
+            .iter()
+            .map(|certificate| Ok(Bytes::copy_from_slice(certificate?.der()?)))
+            .collect::<Result<Vec<_>, Error>>()
+            .ok()?;
+
+        if chain.is_empty() {
+            None
+        } else {
+            Some(chain)
+        }
+    }
 }
 
 impl CryptoSuite for Session {
@@ -82,6 +129,10 @@ impl tls::Session for Session {
     where
         W: tls::Context<Self>,
     {
+        if let Some(error) = self.error.take() {
+            return Poll::Ready(Err(error));
+        }
+
         let mut callback: Callback<W, Self> = Callback {
             context,
             endpoint: self.endpoint,
@@ -107,6 +158,22 @@ impl tls::Session for Session {
             Poll::Ready(Ok(())) => {
                 // s2n-tls has indicated that the handshake is complete
                 if !self.handshake_complete {
+                    let resumed = self.connection.resumed();
+                    context.on_session_resumption(resumed)?;
+
+                    // resumed sessions don't present a certificate
+                    let selected = self
+                        .connection
+                        .application_context::<Selector>()
+                        .and_then(|selector| selector.selected());
+                    if let (false, Some(server_name)) = (resumed, selected) {
+                        context.on_server_certificate(server_name.as_deref())?;
+                    }
+
+                    if let Some(chain) = self.peer_certificate_chain() {
+                        context.on_peer_certificate_chain(chain)?;
+                    }
+
                     self.state.on_handshake_complete();
                     context.on_handshake_complete()?;
                     self.handshake_complete = true;
@@ -121,4 +188,33 @@ impl tls::Session for Session {
             Poll::Pending => Poll::Pending,
         }
     }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), transport::Error> {
+        //= https://www.rfc-editor.org/rfc/rfc8446#section-7.5
+        //# The exporter value is computed as:
+        //#
+        //# TLS-Exporter(label, context_value, key_length) =
+        //#     HKDF-Expand-Label(Derive-Secret(Secret, label, ""),
+        //#                       "exporter", Hash(context_value), key_length)
+
+        // The exporter master secret is only available once the handshake completes
+        if !self.handshake_complete {
+            return Err(CryptoError::INTERNAL_ERROR
+                .with_reason("keying material can't be exported before the handshake completes")
+                .into());
+        }
+
+        self.connection
+            .tls_exporter(label, context, output)
+            .map_err(|_| {
+                CryptoError::INTERNAL_ERROR.with_reason("could not export keying material")
+            })?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,352 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Keys for encrypting the session tickets issued by a [`Server`](crate::Server)
+
+use bytes::Bytes;
+pub use s2n_quic_core::crypto::tls::session_ticket::{Key, KeyError, KeyRotation, KeySource};
+use s2n_quic_core::crypto::tls::session_ticket::{Store, Ticket};
+use s2n_quic_crypto::{
+    hkdf,
+    rand::{SecureRandom, SystemRandom},
+};
+use s2n_tls::{
+    callbacks::{SessionTicket, SessionTicketCallback},
+    config::{self, Config},
+    connection::Connection,
+    error::{Error, Fallible},
+    ffi::s2n_config_set_server_max_early_data_size,
+};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The maximum number of ticket keys s2n-tls holds at once
+const MAX_TICKET_KEYS: u64 = 48;
+
+/// How long to wait before asking the key source again after it failed to provide a key
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Forwards the tickets issued to a client to a [`Store`]
+pub(crate) struct ClientStore(pub Arc<dyn Store>);
+
+impl SessionTicketCallback for ClientStore {
+    fn on_session_ticket(&self, connection: &mut Connection, session_ticket: &SessionTicket) {
+        let server_name = if let Some(server_name) = connection.server_name() {
+            server_name.into()
+        } else {
+            return;
+        };
+
+        let len = if let Ok(len) = session_ticket.len() {
+            len
+        } else {
+            return;
+        };
+        let mut session = vec![0; len];
+        if session_ticket.data(&mut session).is_err() {
+            return;
+        }
+        let lifetime = session_ticket.lifetime().unwrap_or_default();
+
+        // The transport parameters of a client connection are the ones sent by the server
+        let transport_parameters = connection
+            .quic_transport_parameters()
+            .ok()
+            .map(Bytes::copy_from_slice);
+
+        let ticket = Ticket::new(session.into(), transport_parameters, lifetime);
+        self.0.on_ticket(&server_name, ticket);
+    }
+}
+
+/// Generates a random key for each encrypt period
+///
+/// The keys are only known to the current process, so a client can't resume its session with
+/// another server or after the server restarts. Use [`SharedSecret`] to share keys across
+/// servers.
+#[derive(Default)]
+pub struct RandomKeys {
+    keys: Mutex<BTreeMap<u64, Key>>,
+}
+
+impl KeySource for RandomKeys {
+    fn key(&self, period: u64) -> Result<Key, KeyError> {
+        let mut keys = self.keys.lock().map_err(|_| KeyError("lock poisoned"))?;
+
+        // Forget the keys which can no longer be part of a config
+        let oldest = period.saturating_sub(MAX_TICKET_KEYS);
+        *keys = keys.split_off(&oldest);
+
+        if let Some(key) = keys.get(&period) {
+            return Ok(key.clone());
+        }
+
+        let random = SystemRandom::new();
+        let mut name = [0; 16];
+        let mut material = [0; 32];
+        random
+            .fill(&mut name)
+            .and_then(|_| random.fill(&mut material))
+            .map_err(|_| KeyError("could not generate a session ticket key"))?;
+
+        let key = Key::new(name, material);
+        keys.insert(period, key.clone());
+        Ok(key)
+    }
+}
+
+/// Derives the key for each encrypt period from a secret shared by a group of servers
+///
+/// Every server configured with the same secret and [`KeyRotation`] policy encrypts tickets
+/// with the same keys, so clients can resume their sessions with any of them, including
+/// after a restart.
+pub struct SharedSecret {
+    prk: hkdf::Prk,
+}
+
+impl SharedSecret {
+    /// The minimum length of the secret
+    pub const MIN_LEN: usize = 32;
+
+    /// Creates a key source from `secret`, which should contain at least 256 bits of entropy
+    pub fn new(secret: &[u8]) -> Result<Self, KeyError> {
+        if secret.len() < Self::MIN_LEN {
+            return Err(KeyError(
+                "the session ticket secret must be at least 32 bytes long",
+            ));
+        }
+
+        let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, &[]).extract(secret);
+        Ok(Self { prk })
+    }
+}
+
+impl KeySource for SharedSecret {
+    fn key(&self, period: u64) -> Result<Key, KeyError> {
+        struct Len(usize);
+
+        impl hkdf::KeyType for Len {
+            fn len(&self) -> usize {
+                self.0
+            }
+        }
+
+        let period = period.to_be_bytes();
+        let info = [&b"s2n-quic session ticket key"[..], &period[..]];
+        let mut okm = [0; 48];
+        self.prk
+            .expand(&info, Len(okm.len()))
+            .and_then(|expanded| expanded.fill(&mut okm))
+            .map_err(|_| KeyError("could not derive a session ticket key"))?;
+
+        let (name, material) = okm.split_at(16);
+        Ok(Key::new(
+            name.try_into().unwrap(),
+            material.try_into().unwrap(),
+        ))
+    }
+}
+
+/// Creates a config builder with all of the server settings except for the ticket keys
+pub(crate) type NewConfig = Box<dyn Fn() -> Result<config::Builder, Error> + Send>;
+
+/// Rebuilds the server config with new ticket keys at the start of each encrypt period
+///
+/// s2n-tls doesn't allow keys to be added to a config once it's built, so each config holds the
+/// keys of the previous periods which can still decrypt tickets, the key for the current period
+/// and the key for the next period. The next key allows the server to keep issuing tickets if
+/// the source fails to provide a key when the period rolls over.
+pub(crate) struct Rotation {
+    new_config: NewConfig,
+    rotation: KeyRotation,
+    source: Arc<dyn KeySource>,
+    early_data: bool,
+    period: u64,
+    retry_at: Duration,
+    configs: Configs,
+}
+
+/// The configs built with the keys of a single period
+struct Configs {
+    config: Config,
+    /// The config for connections which reject early data, if `config` accepts it
+    without_early_data: Option<Config>,
+}
+
+impl Rotation {
+    pub fn new(
+        new_config: NewConfig,
+        rotation: KeyRotation,
+        source: Arc<dyn KeySource>,
+        early_data: bool,
+    ) -> Result<Self, Error> {
+        let now = since_epoch();
+        let period = rotation.period(now);
+        let configs = build(&new_config, rotation, &*source, early_data, period, now)?;
+
+        Ok(Self {
+            new_config,
+            rotation,
+            source,
+            early_data,
+            period,
+            retry_at: now,
+            configs,
+        })
+    }
+
+    /// Returns the config for the current period
+    ///
+    /// If `early_data` is false, the config rejects any early data offered by the client.
+    pub fn load(&mut self, early_data: bool) -> Config {
+        let now = since_epoch();
+        let period = self.rotation.period(now);
+
+        if period != self.period && now >= self.retry_at {
+            match build(
+                &self.new_config,
+                self.rotation,
+                &*self.source,
+                self.early_data,
+                period,
+                now,
+            ) {
+                Ok(configs) => {
+                    self.configs = configs;
+                    self.period = period;
+                }
+                // The current config still holds the key for this period, so keep using it
+                // until the source recovers
+                Err(_) => self.retry_at = now + RETRY_INTERVAL,
+            }
+        }
+
+        match (&self.configs.without_early_data, early_data) {
+            (Some(config), false) => config.clone(),
+            _ => self.configs.config.clone(),
+        }
+    }
+}
+
+fn build(
+    new_config: &NewConfig,
+    rotation: KeyRotation,
+    source: &dyn KeySource,
+    early_data: bool,
+    period: u64,
+    now: Duration,
+) -> Result<Configs, Error> {
+    // the current and next periods take up two of the keys
+    let oldest = period.saturating_sub(MAX_TICKET_KEYS - 2);
+    let mut keys = vec![];
+    for period in oldest..=period + 1 {
+        let intro_time = rotation.period_start(period);
+
+        // skip the keys which can no longer decrypt tickets
+        if intro_time + rotation.key_lifetime() <= now {
+            continue;
+        }
+
+        let key = source
+            .key(period)
+            .map_err(|err| Error::application(Box::new(err)))?;
+        keys.push((key, intro_time));
+    }
+
+    let config = build_config(new_config, rotation, &keys, early_data)?;
+    let without_early_data = if early_data {
+        Some(build_config(new_config, rotation, &keys, false)?)
+    } else {
+        None
+    };
+
+    Ok(Configs {
+        config,
+        without_early_data,
+    })
+}
+
+fn build_config(
+    new_config: &NewConfig,
+    rotation: KeyRotation,
+    keys: &[(Key, Duration)],
+    early_data: bool,
+) -> Result<Config, Error> {
+    let mut config = new_config()?;
+    config.enable_session_tickets(true)?;
+    config.set_ticket_key_encrypt_decrypt_lifetime(rotation.encrypt_period())?;
+    config.set_ticket_key_decrypt_lifetime(rotation.decrypt_period())?;
+
+    for (key, intro_time) in keys {
+        config.add_session_ticket_key(key.name(), key.material(), UNIX_EPOCH + *intro_time)?;
+    }
+
+    if early_data {
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-4.6.1
+        //# Servers MUST NOT send the early_data extension with a
+        //# max_early_data_size field set to any value other than 0xffffffff.
+        unsafe {
+            // Safety: the config pointer is valid for as long as the builder
+            s2n_config_set_server_max_early_data_size(config.as_mut_ptr(), u32::MAX)
+                .into_result()?;
+        }
+    }
+
+    config.build()
+}
+
+fn since_epoch() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_keys_test() {
+        let keys = RandomKeys::default();
+
+        let key = keys.key(1).unwrap();
+        assert_eq!(keys.key(1).unwrap(), key);
+        assert_ne!(keys.key(2).unwrap(), key);
+        assert_ne!(RandomKeys::default().key(1).unwrap(), key);
+
+        // old keys are forgotten
+        keys.key(1 + MAX_TICKET_KEYS * 2).unwrap();
+        assert_ne!(keys.key(1).unwrap(), key);
+    }
+
+    #[test]
+    fn shared_secret_test() {
+        assert!(SharedSecret::new(&[1; SharedSecret::MIN_LEN - 1]).is_err());
+
+        let a = SharedSecret::new(&[1; SharedSecret::MIN_LEN]).unwrap();
+        let b = SharedSecret::new(&[1; SharedSecret::MIN_LEN]).unwrap();
+        let c = SharedSecret::new(&[2; SharedSecret::MIN_LEN]).unwrap();
+
+        let key = a.key(1).unwrap();
+        assert_eq!(b.key(1).unwrap(), key);
+        assert_ne!(a.key(2).unwrap(), key);
+        assert_ne!(a.key(2).unwrap().name(), key.name());
+        assert_ne!(c.key(1).unwrap(), key);
+    }
+
+    #[test]
+    fn rotation_key_source_error_test() {
+        let new_config: NewConfig = Box::new(|| {
+            let mut config = config::Builder::default();
+            config.enable_quic()?;
+            Ok(config)
+        });
+        let source =
+            Arc::new(|_period: u64| -> Result<Key, KeyError> { Err(KeyError("unavailable")) });
+
+        assert!(Rotation::new(new_config, KeyRotation::default(), source, false).is_err());
+    }
+}
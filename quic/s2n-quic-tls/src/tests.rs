@@ -241,6 +241,16 @@ fn s2n_server_with_private_key_callback(wait_counter: u8) -> (server::Server, Ar
     (tls, done)
 }
 
+/// A config presenting the certificate trusted by the clients, for use in a certificate store
+fn certificate_config() -> s2n_tls::config::Config {
+    server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .build()
+        .unwrap()
+        .into()
+}
+
 fn rustls_server() -> s2n_quic_rustls::server::Server {
     s2n_quic_rustls::server::Builder::default()
         .with_certificate(CERT_PEM, KEY_PEM)
@@ -288,6 +298,82 @@ fn s2n_client_s2n_server_test() {
     run(&mut server_endpoint, &mut client_endpoint, None);
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_certificate_store_test() {
+    let store = certificate::Store::new(
+        certificate::Certificates::new().with_server_name("localhost", certificate_config()),
+    );
+    let mut server_endpoint = server::Builder::default()
+        .with_certificate(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+        .unwrap()
+        .with_certificate_store(store.clone())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut client_endpoint = s2n_client();
+    let mut pair = tls::testing::Pair::new(
+        &mut server_endpoint,
+        &mut client_endpoint,
+        "localhost".into(),
+    );
+
+    // the handshake selects from the certificates loaded when it started
+    store.reload(certificate::Certificates::new().with_default(certificate_config()));
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    pair.finish();
+    assert_eq!(
+        pair.server.context.server_certificate,
+        Some(Some("localhost".into()))
+    );
+
+    // certificates loaded after the server was built are presented for new connections
+    let mut pair = tls::testing::Pair::new(
+        &mut server_endpoint,
+        &mut client_endpoint,
+        "localhost".into(),
+    );
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    pair.finish();
+    assert_eq!(pair.server.context.server_certificate, Some(None));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_certificate_store_ch_callback_test() {
+    for wait_counter in 0..=10 {
+        let store = certificate::Store::new(
+            certificate::Certificates::new().with_server_name("localhost", certificate_config()),
+        );
+        let handle = MyCallbackHandler::new(wait_counter);
+        let done = handle.done.clone();
+
+        // the client only trusts the certificate in the store, so the handshake fails unless
+        // both the store and the handler are used
+        let mut server_endpoint = server::Builder::default()
+            .with_certificate(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+            .unwrap()
+            .with_certificate_store(store)
+            .unwrap()
+            .with_client_hello_handler(handle)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut client_endpoint = s2n_client();
+
+        run(&mut server_endpoint, &mut client_endpoint, Some(done));
+    }
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn rustls_client_s2n_server_test() {
@@ -306,6 +392,59 @@ fn s2n_client_rustls_server_test() {
     run(&mut server_endpoint, &mut client_endpoint, None);
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn export_keying_material_test() {
+    fn export<S: tls::Session>(session: &S) -> [u8; 32] {
+        let mut output = [0; 32];
+        session
+            .export_keying_material(b"EXPORTER-test", b"context", &mut output)
+            .unwrap();
+        output
+    }
+
+    let mut client_endpoint = s2n_client();
+    let mut server_endpoint = s2n_server();
+    let mut pair = tls::testing::Pair::new(
+        &mut server_endpoint,
+        &mut client_endpoint,
+        "localhost".into(),
+    );
+
+    // the exporter secret isn't available until the handshake completes
+    let mut output = [0; 32];
+    assert!(tls::Session::export_keying_material(
+        &pair.client.session,
+        b"EXPORTER-test",
+        b"context",
+        &mut output
+    )
+    .is_err());
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+    pair.finish();
+
+    assert_eq!(export(&pair.client.session), export(&pair.server.session));
+    assert_ne!(export(&pair.client.session), [0; 32]);
+
+    // the exporter is compatible with other TLS implementations
+    let mut client_endpoint = s2n_client();
+    let mut server_endpoint = rustls_server();
+    let mut pair = tls::testing::Pair::new(
+        &mut server_endpoint,
+        &mut client_endpoint,
+        "localhost".into(),
+    );
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+    pair.finish();
+
+    assert_eq!(export(&pair.client.session), export(&pair.server.session));
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn s2n_client_s2n_server_client_auth_test() {
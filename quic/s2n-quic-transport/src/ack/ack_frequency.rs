@@ -0,0 +1,272 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Requests the peer to adjust its acknowledgement frequency with ACK_FREQUENCY frames
+//!
+//! See https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency
+
+use crate::{contexts::WriteContext, transmission};
+use core::time::Duration;
+use s2n_quic_core::{ack, frame::AckFrequency, packet::number::PacketNumber, varint::VarInt};
+
+/// The ack-eliciting threshold a peer uses if it has not received an ACK_FREQUENCY frame
+const DEFAULT_ACK_ELICITING_THRESHOLD: u64 = 1;
+
+/// Out-of-order packets trigger an immediate acknowledgement, as recommended in RFC 9000
+const REORDERING_THRESHOLD: VarInt = VarInt::from_u8(1);
+
+#[derive(Debug)]
+pub struct AckFrequencySync {
+    /// The `min_ack_delay` transport parameter sent by the peer
+    ///
+    /// This is `None` if the peer does not support the ACK frequency extension.
+    peer_min_ack_delay: Option<Duration>,
+
+    /// The `max_ack_delay` transport parameter sent by the peer
+    peer_max_ack_delay: Duration,
+
+    /// The sequence number of the next ACK_FREQUENCY frame
+    next_sequence_number: VarInt,
+
+    /// The ack-eliciting threshold that was last requested from the peer
+    ack_eliciting_threshold: u64,
+
+    delivery: DeliveryState,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DeliveryState {
+    Idle,
+    RequiresTransmission,
+    RequiresRetransmission,
+    InFlight(PacketNumber),
+}
+
+impl DeliveryState {
+    #[inline]
+    fn can_transmit(self, constraint: transmission::Constraint) -> bool {
+        match self {
+            Self::RequiresTransmission => constraint.can_transmit(),
+            Self::RequiresRetransmission => constraint.can_retransmit(),
+            _ => false,
+        }
+    }
+}
+
+impl Default for AckFrequencySync {
+    fn default() -> Self {
+        Self {
+            peer_min_ack_delay: None,
+            peer_max_ack_delay: Duration::ZERO,
+            next_sequence_number: VarInt::from_u8(0),
+            ack_eliciting_threshold: DEFAULT_ACK_ELICITING_THRESHOLD,
+            delivery: DeliveryState::Idle,
+        }
+    }
+}
+
+impl AckFrequencySync {
+    /// Enables sending ACK_FREQUENCY frames to a peer that advertised support for the extension
+    pub fn enable(&mut self, peer_min_ack_delay: Duration, peer_max_ack_delay: Duration) {
+        self.peer_min_ack_delay = Some(peer_min_ack_delay);
+        self.peer_max_ack_delay = peer_max_ack_delay;
+    }
+
+    /// Returns `true` if the peer supports the ACK frequency extension
+    pub fn is_enabled(&self) -> bool {
+        self.peer_min_ack_delay.is_some()
+    }
+
+    /// Updates the ack-eliciting threshold to request from the peer
+    ///
+    /// `None` indicates the peer should use its default acknowledgement behavior.
+    pub fn update_ack_eliciting_threshold(&mut self, threshold: Option<u64>) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let threshold = threshold.unwrap_or(DEFAULT_ACK_ELICITING_THRESHOLD);
+
+        if threshold == self.ack_eliciting_threshold {
+            return;
+        }
+
+        // Limit the number of ACK_FREQUENCY frames to one per round trip by waiting for
+        // the in-flight request to be acknowledged or lost before requesting a new value
+        if matches!(self.delivery, DeliveryState::InFlight(_)) {
+            return;
+        }
+
+        self.ack_eliciting_threshold = threshold;
+        self.delivery = DeliveryState::RequiresTransmission;
+    }
+
+    /// Called when an outgoing packet is being assembled
+    pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) {
+        let peer_min_ack_delay = match self.peer_min_ack_delay {
+            Some(peer_min_ack_delay) => peer_min_ack_delay,
+            None => return,
+        };
+
+        if !self
+            .delivery
+            .can_transmit(context.transmission_constraint())
+        {
+            return;
+        }
+
+        // Always send the latest threshold, even when retransmitting a lost request
+        let threshold = self.ack_eliciting_threshold;
+
+        // The requested max ack delay can't be less than the min_ack_delay of the peer
+        let request_max_ack_delay = self.peer_max_ack_delay.max(peer_min_ack_delay);
+        let request_max_ack_delay =
+            VarInt::try_from(request_max_ack_delay.as_micros() as u64).unwrap_or(VarInt::MAX);
+
+        let frame = AckFrequency {
+            sequence_number: self.next_sequence_number,
+            ack_eliciting_threshold: VarInt::try_from(threshold).unwrap_or(VarInt::MAX),
+            request_max_ack_delay,
+            reordering_threshold: REORDERING_THRESHOLD,
+        };
+
+        if let Some(packet_nr) = context.write_frame(&frame) {
+            self.next_sequence_number =
+                self.next_sequence_number.saturating_add(VarInt::from_u8(1));
+
+            self.delivery = DeliveryState::InFlight(packet_nr);
+        }
+    }
+
+    /// This method gets called when a packet delivery got acknowledged
+    pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
+        if let DeliveryState::InFlight(packet_nr) = self.delivery {
+            if ack_set.contains(packet_nr) {
+                self.delivery = DeliveryState::Idle;
+            }
+        }
+    }
+
+    /// This method gets called when a packet loss is reported
+    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
+        if let DeliveryState::InFlight(packet_nr) = self.delivery {
+            if ack_set.contains(packet_nr) {
+                self.delivery = DeliveryState::RequiresRetransmission;
+            }
+        }
+    }
+}
+
+impl transmission::interest::Provider for AckFrequencySync {
+    #[inline]
+    fn transmission_interest<Q: transmission::interest::Query>(
+        &self,
+        query: &mut Q,
+    ) -> transmission::interest::Result {
+        match self.delivery {
+            DeliveryState::RequiresTransmission => query.on_new_data(),
+            DeliveryState::RequiresRetransmission => query.on_lost_data(),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{contexts::testing::*, transmission::interest::Provider};
+    use s2n_quic_core::{endpoint, frame::Frame, time::clock::testing as time};
+
+    #[test]
+    fn disabled_test() {
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut context = MockWriteContext::new(
+            time::now(),
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Client,
+        );
+
+        let mut sync = AckFrequencySync::default();
+        sync.update_ack_eliciting_threshold(Some(5));
+
+        assert!(!sync.has_transmission_interest());
+        sync.on_transmit(&mut context);
+        assert!(context.frame_buffer.is_empty());
+    }
+
+    #[test]
+    fn sync_test() {
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut context = MockWriteContext::new(
+            time::now(),
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Client,
+        );
+
+        let mut sync = AckFrequencySync::default();
+        sync.enable(Duration::from_millis(1), Duration::from_millis(25));
+
+        // the default threshold doesn't need to be requested
+        sync.update_ack_eliciting_threshold(None);
+        assert!(!sync.has_transmission_interest());
+
+        sync.update_ack_eliciting_threshold(Some(5));
+        assert_eq!(
+            sync.get_transmission_interest(),
+            transmission::Interest::NewData
+        );
+
+        sync.on_transmit(&mut context);
+        let mut written = context.frame_buffer.pop_front().unwrap();
+        let packet_nr = written.packet_nr;
+        match written.as_frame() {
+            Frame::AckFrequency(frame) => {
+                assert_eq!(VarInt::from_u8(0), frame.sequence_number);
+                assert_eq!(VarInt::from_u8(5), frame.ack_eliciting_threshold);
+                assert_eq!(Duration::from_millis(25), frame.request_max_ack_delay());
+                assert_eq!(REORDERING_THRESHOLD, frame.reordering_threshold);
+            }
+            frame => panic!("unexpected frame {:?}", frame),
+        }
+        assert!(!sync.has_transmission_interest());
+
+        // new values are not requested while a request is in flight
+        sync.update_ack_eliciting_threshold(Some(8));
+        assert!(!sync.has_transmission_interest());
+
+        // lost requests are retransmitted with a new sequence number
+        sync.on_packet_loss(&packet_nr);
+        assert_eq!(
+            sync.get_transmission_interest(),
+            transmission::Interest::LostData
+        );
+        sync.on_transmit(&mut context);
+        let mut written = context.frame_buffer.pop_front().unwrap();
+        let packet_nr = written.packet_nr;
+        match written.as_frame() {
+            Frame::AckFrequency(frame) => {
+                assert_eq!(VarInt::from_u8(1), frame.sequence_number);
+                assert_eq!(VarInt::from_u8(5), frame.ack_eliciting_threshold);
+            }
+            frame => panic!("unexpected frame {:?}", frame),
+        }
+
+        sync.on_packet_ack(&packet_nr);
+        assert!(!sync.has_transmission_interest());
+
+        sync.update_ack_eliciting_threshold(Some(8));
+        sync.on_transmit(&mut context);
+        let mut written = context.frame_buffer.pop_front().unwrap();
+        match written.as_frame() {
+            Frame::AckFrequency(frame) => {
+                assert_eq!(VarInt::from_u8(2), frame.sequence_number);
+                assert_eq!(VarInt::from_u8(8), frame.ack_eliciting_threshold);
+            }
+            frame => panic!("unexpected frame {:?}", frame),
+        }
+    }
+}
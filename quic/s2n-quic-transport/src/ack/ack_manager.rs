@@ -19,12 +19,17 @@ use s2n_quic_core::{
         builder::{AckAction, AckProcessed},
         IntoEvent as _,
     },
-    frame::{ack::EcnCounts, Ack, Ping},
+    frame::{ack::EcnCounts, Ack, AckFrequency, AckMp, Ping},
     packet::number::{PacketNumber, PacketNumberSpace},
     time::{timer, Timer, Timestamp},
+    transport,
     varint::VarInt,
 };
 
+/// The number of ack-eliciting packets that can be processed before an ACK is sent, if the
+/// peer has not requested a different value with an ACK_FREQUENCY frame
+const DEFAULT_PACKET_TOLERANCE: u8 = 10;
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2
 //# Endpoints acknowledge all packets they receive and process.  However,
 //# only ack-eliciting packets cause an ACK frame to be sent within the
@@ -72,6 +77,18 @@ pub struct AckManager {
 
     /// Explicit Congestion Notification counts from processed packets
     ecn_counts: EcnCounts,
+
+    /// The number of processed packets that trigger an immediate ACK
+    packet_tolerance: u8,
+
+    /// If `true`, out-of-order packets trigger an immediate ACK
+    ack_on_reorder: bool,
+
+    /// The sequence number of the last ACK_FREQUENCY frame that was processed
+    ack_frequency_sequence_number: Option<VarInt>,
+
+    /// The packet number space of a path, which is acknowledged with ACK_MP frames
+    path_identifier: Option<VarInt>,
 }
 
 impl AckManager {
@@ -88,9 +105,26 @@ impl AckManager {
             transmissions_since_elicitation: Counter::new(0),
             transmission_state: AckTransmissionState::default(),
             ecn_counts: EcnCounts::default(),
+            packet_tolerance: DEFAULT_PACKET_TOLERANCE,
+            ack_on_reorder: true,
+            ack_frequency_sequence_number: None,
+            path_identifier: None,
         }
     }
 
+    /// Creates an ack manager for the packet number space of a path
+    ///
+    /// The manager uses the same settings as `self` and acknowledges the packets with
+    /// ACK_MP frames.
+    pub fn new_for_path(&self, path_identifier: u32) -> Self {
+        let mut manager = Self::new(PacketNumberSpace::ApplicationData, self.ack_settings);
+        manager.packet_tolerance = self.packet_tolerance;
+        manager.ack_on_reorder = self.ack_on_reorder;
+        manager.ack_frequency_sequence_number = self.ack_frequency_sequence_number;
+        manager.path_identifier = Some(VarInt::from_u32(path_identifier));
+        manager
+    }
+
     /// Called when an outgoing packet is being assembled
     pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) -> bool {
         let constraint = context.transmission_constraint();
@@ -110,13 +144,22 @@ impl AckManager {
         //# Even if an endpoint does not set an ECT field on packets it sends,
         //# the endpoint MUST provide feedback about ECN markings it receives, if
         //# these are accessible.
-        context
-            .write_ack_frame(&Ack {
-                ack_delay,
-                ack_ranges: &self.ack_ranges,
-                ecn_counts: self.ecn_counts.as_option(),
-            })
-            .is_some()
+        let ack = Ack {
+            ack_delay,
+            ack_ranges: &self.ack_ranges,
+            ecn_counts: self.ecn_counts.as_option(),
+        };
+
+        if let Some(path_identifier) = self.path_identifier {
+            return context
+                .write_frame(&AckMp {
+                    path_identifier,
+                    ack,
+                })
+                .is_some();
+        }
+
+        context.write_ack_frame(&ack).is_some()
     }
 
     /// Called after an outgoing packet is assembled and `on_transmit` returned `true`
@@ -301,14 +344,14 @@ impl AckManager {
             //# *  when the received packet has a packet number less than another
             //#    ack-eliciting packet that has been received, or
 
-            should_activate |= !is_largest;
+            should_activate |= self.ack_on_reorder && !is_largest;
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.1
             //# *  when the packet has a packet number larger than the highest-
             //#    numbered ack-eliciting packet that has been received and there are
             //#    missing packets between that packet and this packet.
 
-            should_activate |= !is_ordered;
+            should_activate |= self.ack_on_reorder && !is_ordered;
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.1
             //# Similarly, packets marked with the ECN Congestion Experienced (CE)
@@ -316,15 +359,14 @@ impl AckManager {
             //# reduce the peer's response time to congestion events.
             should_activate |= processed_packet.datagram.ecn.congestion_experienced();
 
-            // TODO update to draft link after published
-            // https://github.com/quicwg/base-drafts/pull/3623
-            // An ACK frame SHOULD be generated for at least every 10th ack-eliciting packet
+            // An ACK frame SHOULD be generated for at least every 10th ack-eliciting packet,
+            // unless the peer requested a different threshold with an ACK_FREQUENCY frame.
+            //
+            // See https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency
+            should_activate |= self.processed_packets_since_transmission >= self.packet_tolerance;
 
-            // TODO support delayed ack proposal
-            // https://tools.ietf.org/html/draft-iyengar-quic-delayed-ack-00
-            let packet_tolerance = 10;
-
-            should_activate |= self.processed_packets_since_transmission >= packet_tolerance;
+            // An IMMEDIATE_ACK frame requests an ACK frame to be sent without delay
+            should_activate |= processed_packet.immediate_ack;
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-9.3.3
             //# An endpoint that receives a PATH_CHALLENGE on an active path SHOULD
@@ -351,6 +393,56 @@ impl AckManager {
         }
     }
 
+    /// Called when an ACK_FREQUENCY frame is received from the peer
+    pub fn on_ack_frequency_frame(&mut self, frame: &AckFrequency) -> Result<(), transport::Error> {
+        // Receiving an ACK_FREQUENCY frame when the min_ack_delay transport parameter was
+        // not advertised is a PROTOCOL_VIOLATION.
+        //
+        // See https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency#section-3
+        let min_ack_delay = self.ack_settings.min_ack_delay.ok_or_else(|| {
+            transport::Error::PROTOCOL_VIOLATION
+                .with_reason("ACK_FREQUENCY frame received without min_ack_delay")
+        })?;
+
+        let max_ack_delay = frame.request_max_ack_delay();
+
+        // A Request Max Ack Delay value less than the advertised min_ack_delay is a
+        // PROTOCOL_VIOLATION.
+        //
+        // See https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency#section-4
+        if max_ack_delay < min_ack_delay {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("requested max ack delay is less than min_ack_delay"));
+        }
+
+        // Frames with a sequence number less than or equal to the largest one processed
+        // are stale and are ignored.
+        if let Some(sequence_number) = self.ack_frequency_sequence_number {
+            if frame.sequence_number <= sequence_number {
+                return Ok(());
+            }
+        }
+
+        self.ack_frequency_sequence_number = Some(frame.sequence_number);
+
+        // The threshold is the number of packets that can be received without an ACK, so
+        // an ACK is sent once the threshold is exceeded
+        self.packet_tolerance = frame
+            .ack_eliciting_threshold
+            .as_u64()
+            .saturating_add(1)
+            .min(u8::MAX as u64) as u8;
+
+        // A reordering threshold of 0 means out-of-order packets do not trigger an
+        // immediate ACK. Any other value is treated as 1, which matches the behavior
+        // recommended in RFC 9000.
+        self.ack_on_reorder = frame.reordering_threshold != VarInt::from_u8(0);
+
+        self.ack_settings.max_ack_delay = max_ack_delay;
+
+        Ok(())
+    }
+
     /// Called when the connection timer expired
     pub fn on_timeout(&mut self, timestamp: Timestamp) {
         // NOTE: ack_elicitation_timer is not actively polled
@@ -451,6 +543,76 @@ mod tests {
         assert!(manager.transmission_state.is_active());
     }
 
+    #[test]
+    fn immediate_ack() {
+        let mut manager =
+            AckManager::new(PacketNumberSpace::ApplicationData, ack::Settings::default());
+
+        let pn = PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(1));
+        let datagram = helper_datagram_info(ExplicitCongestionNotification::NotEct);
+        let mut processed_packet = ProcessedPacket::new(pn, &datagram);
+        processed_packet.immediate_ack = true;
+        processed_packet.ack_elicitation = AckElicitation::Eliciting;
+
+        assert!(!manager.transmission_state.is_active());
+
+        let path = helper_path_server();
+        let path_id = path::Id::test_id();
+        manager.on_processed_packet(
+            &processed_packet,
+            path_event!(path, path_id),
+            &mut Publisher::no_snapshot(),
+        );
+
+        assert!(manager.transmission_state.is_active());
+    }
+
+    #[test]
+    fn ack_frequency_frame() {
+        let frame = |sequence_number: u8, ack_eliciting_threshold: u8, reordering_threshold: u8| {
+            AckFrequency {
+                sequence_number: VarInt::from_u8(sequence_number),
+                ack_eliciting_threshold: VarInt::from_u8(ack_eliciting_threshold),
+                request_max_ack_delay: VarInt::from_u32(25_000),
+                reordering_threshold: VarInt::from_u8(reordering_threshold),
+            }
+        };
+
+        // the frame is rejected if min_ack_delay was not advertised
+        let mut manager =
+            AckManager::new(PacketNumberSpace::ApplicationData, ack::Settings::default());
+        assert!(manager.on_ack_frequency_frame(&frame(0, 1, 1)).is_err());
+
+        let ack_settings = ack::Settings {
+            min_ack_delay: Some(Duration::from_millis(1)),
+            ..Default::default()
+        };
+        let mut manager = AckManager::new(PacketNumberSpace::ApplicationData, ack_settings);
+
+        assert!(manager.on_ack_frequency_frame(&frame(1, 4, 0)).is_ok());
+        assert_eq!(5, manager.packet_tolerance);
+        assert!(!manager.ack_on_reorder);
+        assert_eq!(
+            Duration::from_millis(25),
+            manager.ack_settings.max_ack_delay
+        );
+
+        // stale frames are ignored
+        assert!(manager.on_ack_frequency_frame(&frame(1, 9, 1)).is_ok());
+        assert!(manager.on_ack_frequency_frame(&frame(0, 9, 1)).is_ok());
+        assert_eq!(5, manager.packet_tolerance);
+        assert!(!manager.ack_on_reorder);
+
+        assert!(manager.on_ack_frequency_frame(&frame(2, 255, 1)).is_ok());
+        assert_eq!(u8::MAX, manager.packet_tolerance);
+        assert!(manager.ack_on_reorder);
+
+        // a requested max ack delay less than min_ack_delay is rejected
+        let mut invalid = frame(3, 1, 1);
+        invalid.request_max_ack_delay = VarInt::from_u16(999);
+        assert!(manager.on_ack_frequency_frame(&invalid).is_err());
+    }
+
     #[test]
     fn ecn_counts() {
         // Setup:
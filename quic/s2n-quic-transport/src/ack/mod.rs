@@ -5,6 +5,7 @@ pub use ack_manager::*;
 pub use s2n_quic_core::ack::*;
 
 mod ack_eliciting_transmission;
+pub(crate) mod ack_frequency;
 mod ack_manager;
 pub(crate) mod ack_ranges;
 mod ack_transmission_state;
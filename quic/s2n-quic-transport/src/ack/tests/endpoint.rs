@@ -71,6 +71,7 @@ impl Endpoint {
             datagram: &datagram,
             packet_number: packet.packet_number,
             path_challenge_on_active_path: false,
+            immediate_ack: false,
             frames: 1,
             path_validation_probing: Default::default(),
             bytes_progressed: 0,
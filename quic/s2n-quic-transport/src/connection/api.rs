@@ -17,6 +17,7 @@ use s2n_quic_core::{
     application,
     application::ServerName,
     inet::SocketAddress,
+    path::migration,
     query::{Query, QueryMut},
     stream::StreamType,
 };
@@ -175,6 +176,21 @@ impl Connection {
         self.api.application_protocol()
     }
 
+    #[inline]
+    pub fn peer_certificate_chain(&self) -> Result<Vec<Bytes>, connection::Error> {
+        self.api.peer_certificate_chain()
+    }
+
+    #[inline]
+    pub fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        self.api.export_keying_material(label, context, output)
+    }
+
     #[inline]
     pub fn id(&self) -> u64 {
         self.api.id()
@@ -189,6 +205,29 @@ impl Connection {
         self.api.keep_alive(enabled)
     }
 
+    #[inline]
+    pub fn poll_migrate(
+        &self,
+        local_address: SocketAddress,
+        context: &Context,
+    ) -> Poll<Result<(), migration::Error>> {
+        self.api.poll_migrate(local_address, context)
+    }
+
+    #[inline]
+    pub fn poll_open_path(
+        &self,
+        local_address: SocketAddress,
+        context: &Context,
+    ) -> Poll<Result<(), migration::Error>> {
+        self.api.poll_open_path(local_address, context)
+    }
+
+    #[inline]
+    pub fn close_path(&self, local_address: SocketAddress) -> Result<(), migration::Error> {
+        self.api.close_path(local_address)
+    }
+
     #[inline]
     pub fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api.local_address()
@@ -199,6 +238,11 @@ impl Connection {
         self.api.remote_address()
     }
 
+    #[inline]
+    pub fn stats(&self) -> Result<connection::Stats, connection::Error> {
+        self.api.stats()
+    }
+
     #[inline]
     pub fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
         self.api.query_event_context(query)
@@ -18,6 +18,7 @@ use s2n_quic_core::{
     application,
     application::ServerName,
     inet::SocketAddress,
+    path::migration,
     query::{Query, QueryMut},
     stream::{ops, StreamId, StreamType},
 };
@@ -58,16 +59,41 @@ pub(crate) trait ConnectionApiProvider: Sync + Send {
 
     fn application_protocol(&self) -> Result<Bytes, connection::Error>;
 
+    fn peer_certificate_chain(&self) -> Result<Vec<Bytes>, connection::Error>;
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error>;
+
     fn id(&self) -> u64;
 
     fn ping(&self) -> Result<(), connection::Error>;
 
     fn keep_alive(&self, enabled: bool) -> Result<(), connection::Error>;
 
+    fn poll_migrate(
+        &self,
+        local_address: SocketAddress,
+        context: &Context,
+    ) -> Poll<Result<(), migration::Error>>;
+
+    fn poll_open_path(
+        &self,
+        local_address: SocketAddress,
+        context: &Context,
+    ) -> Poll<Result<(), migration::Error>>;
+
+    fn close_path(&self, local_address: SocketAddress) -> Result<(), migration::Error>;
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn stats(&self) -> Result<connection::Stats, connection::Error>;
+
     fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error>;
 
     fn query_event_context_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error>;
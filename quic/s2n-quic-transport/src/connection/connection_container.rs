@@ -35,6 +35,7 @@ use s2n_quic_core::{
     application::ServerName,
     event::supervisor,
     inet::SocketAddress,
+    path::migration,
     query::{Query, QueryMut},
     recovery::K_GRANULARITY,
     time::Timestamp,
@@ -309,6 +310,19 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_read_call(|conn| Ok(conn.application_protocol()))
     }
 
+    fn peer_certificate_chain(&self) -> Result<Vec<Bytes>, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.peer_certificate_chain()))
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        self.api_read_call(|conn| conn.export_keying_material(label, context, output))
+    }
+
     fn id(&self) -> u64 {
         self.internal_connection_id.into()
     }
@@ -321,6 +335,26 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_write_call(|conn| conn.keep_alive(enabled))
     }
 
+    fn poll_migrate(
+        &self,
+        local_address: SocketAddress,
+        context: &Context,
+    ) -> Poll<Result<(), migration::Error>> {
+        self.api_poll_call(|conn| conn.poll_migrate(local_address, context))
+    }
+
+    fn poll_open_path(
+        &self,
+        local_address: SocketAddress,
+        context: &Context,
+    ) -> Poll<Result<(), migration::Error>> {
+        self.api_poll_call(|conn| conn.poll_open_path(local_address, context))
+    }
+
+    fn close_path(&self, local_address: SocketAddress) -> Result<(), migration::Error> {
+        self.api_write_call(|conn| conn.close_path(local_address))
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api_read_call(|conn| conn.local_address())
     }
@@ -329,6 +363,10 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_read_call(|conn| conn.remote_address())
     }
 
+    fn stats(&self) -> Result<connection::Stats, connection::Error> {
+        self.api_read_call(|conn| conn.stats())
+    }
+
     #[inline]
     fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
         self.api_read_call(|conn| {
@@ -873,6 +911,22 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         Some((result, interests))
     }
 
+    /// Executes the provided function on every `Connection` in the container
+    pub fn for_each<F>(&mut self, mut func: F)
+    where
+        F: FnMut(&mut C),
+    {
+        let ids: Vec<_> = self
+            .connection_map
+            .iter()
+            .map(|node| node.internal_connection_id)
+            .collect();
+
+        for id in ids {
+            self.with_connection(id, &mut func);
+        }
+    }
+
     /// Removes all Connections in the `done` state from the `ConnectionContainer`.
     pub fn finalize_done_connections(&mut self) {
         for connection in self.interest_lists.done_connections.take() {
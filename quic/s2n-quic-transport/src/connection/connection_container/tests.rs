@@ -102,6 +102,24 @@ impl connection::Trait for TestConnection {
         Ok(())
     }
 
+    fn on_new_token(
+        &mut self,
+        _token_format: &mut <Self::Config as endpoint::Config>::TokenFormat,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
+    ) {
+    }
+
+    fn on_client_hello(
+        &mut self,
+        _datagram: &DatagramInfo,
+        _tls_endpoint: &mut <Self::Config as endpoint::Config>::TLSEndpoint,
+        _early_data_controller: &mut <Self::Config as endpoint::Config>::EarlyDataController,
+        _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+        _datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
+    ) -> Result<(), connection::Error> {
+        Ok(())
+    }
+
     fn on_transmit<Tx: tx::Queue>(
         &mut self,
         _queue: &mut Tx,
@@ -129,12 +147,25 @@ impl connection::Trait for TestConnection {
     fn on_wakeup(
         &mut self,
         _timestamp: Timestamp,
+        _congestion_controller_endpoint: &mut <Self::Config as endpoint::Config>::CongestionControllerEndpoint,
+        _max_mtu: MaxMtu,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         _datagram: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
     ) -> Result<(), connection::Error> {
         Ok(())
     }
 
+    fn on_packet_too_big(
+        &mut self,
+        _remote_address: &path::RemoteAddress,
+        _quoted_packet: &[u8],
+        _mtu: u16,
+        _timestamp: Timestamp,
+        _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+    ) {
+    }
+
     fn handle_initial_packet(
         &mut self,
         _datagram: &DatagramInfo,
@@ -207,6 +238,7 @@ impl connection::Trait for TestConnection {
         _datagram: &DatagramInfo,
         _path_id: path::Id,
         _packet: ProtectedZeroRtt,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         _packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
     ) -> Result<(), ProcessingError> {
@@ -233,6 +265,7 @@ impl connection::Trait for TestConnection {
         _congestion_controller_endpoint: &mut <Self::Config as endpoint::Config>::CongestionControllerEndpoint,
         _path_migration: &mut <Self::Config as endpoint::Config>::PathMigrationValidator,
         _max_mtu: MaxMtu,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
     ) -> Result<path::Id, DatagramDropReason> {
         todo!()
@@ -248,6 +281,23 @@ impl connection::Trait for TestConnection {
         123
     }
 
+    fn accepts_cleared_quic_bit(&self) -> bool {
+        false
+    }
+
+    fn on_quic_version_upgrade(
+        &mut self,
+        _datagram: &DatagramInfo,
+        _version: u32,
+        _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+    ) -> bool {
+        false
+    }
+
+    fn accepts_original_version(&self, _version: u32) -> bool {
+        false
+    }
+
     fn poll_stream_request(
         &mut self,
         _stream_id: stream::StreamId,
@@ -286,6 +336,19 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn peer_certificate_chain(&self) -> Vec<Bytes> {
+        todo!()
+    }
+
+    fn export_keying_material(
+        &self,
+        _label: &[u8],
+        _context: &[u8],
+        _output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        todo!()
+    }
+
     fn ping(&mut self) -> Result<(), connection::Error> {
         todo!()
     }
@@ -294,6 +357,26 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn poll_migrate(
+        &mut self,
+        _local_address: SocketAddress,
+        _context: &Context,
+    ) -> Poll<Result<(), migration::Error>> {
+        todo!()
+    }
+
+    fn poll_open_path(
+        &mut self,
+        _local_address: SocketAddress,
+        _context: &Context,
+    ) -> Poll<Result<(), migration::Error>> {
+        todo!()
+    }
+
+    fn close_path(&mut self, _local_address: SocketAddress) -> Result<(), migration::Error> {
+        todo!()
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         todo!()
     }
@@ -302,6 +385,10 @@ impl connection::Trait for TestConnection {
         Ok(SocketAddress::default())
     }
 
+    fn stats(&self) -> Result<connection::Stats, connection::Error> {
+        todo!()
+    }
+
     fn error(&self) -> Option<connection::Error> {
         None
     }
@@ -8,6 +8,7 @@ use core::{convert::TryFrom as _, hash::BuildHasher};
 use hashbrown::hash_map::{Entry, HashMap};
 use s2n_quic_core::{connection, endpoint, random, stateless_reset, time::Timestamp};
 use siphasher::sip::SipHasher13;
+use smallvec::SmallVec;
 use std::sync::{Arc, Mutex};
 
 // Since the input to the hash function (stateless reset token) come from the peer, we need to
@@ -129,6 +130,115 @@ impl LocalIdMap {
     }
 }
 
+/// Maps from peer connection IDs to the internal connection IDs using them
+///
+/// The map is only used for routing ICMP Packet Too Big messages, so it is only created if the
+/// endpoint handles them.
+#[derive(Debug)]
+pub(crate) struct PeerIdMap {
+    /// Maps from peer connection IDs to the internal connection IDs which registered them
+    map: HashMap<connection::PeerId, SmallVec<[InternalConnectionId; 1]>, HashState>,
+    /// The number of peer connection IDs in the map for each length
+    ///
+    /// Short header packets don't encode the length of the destination connection ID, so
+    /// lookups try each of the lengths in use.
+    len_counts: [u32; connection::id::MAX_LEN + 1],
+}
+
+impl PeerIdMap {
+    /// Constructs a new `PeerIdMap`
+    fn new(hash_state: HashState) -> Self {
+        Self {
+            map: HashMap::with_hasher(hash_state),
+            len_counts: [0; connection::id::MAX_LEN + 1],
+        }
+    }
+
+    /// Associates the given `PeerId` with the given `InternalConnectionId`
+    ///
+    /// Peers choose their own connection IDs, so different peers may use the same ID. An ID
+    /// that is used by more than one connection doesn't match any of them, since a quoted
+    /// packet with that ID could have been sent by either connection.
+    pub(crate) fn insert(
+        &mut self,
+        peer_id: &connection::PeerId,
+        internal_id: InternalConnectionId,
+    ) {
+        // Zero-length connection IDs don't identify a connection
+        if peer_id.is_empty() {
+            return;
+        }
+
+        match self.map.entry(*peer_id) {
+            Entry::Occupied(mut entry) => {
+                if !entry.get().contains(&internal_id) {
+                    entry.get_mut().push(internal_id);
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(SmallVec::from_elem(internal_id, 1));
+                self.len_counts[peer_id.len()] += 1;
+            }
+        }
+    }
+
+    /// Removes the given `PeerId` from the map if it is associated with the given
+    /// `InternalConnectionId`
+    pub(crate) fn remove(
+        &mut self,
+        peer_id: &connection::PeerId,
+        internal_id: InternalConnectionId,
+    ) {
+        if let Entry::Occupied(mut entry) = self.map.entry(*peer_id) {
+            entry.get_mut().retain(|id| *id != internal_id);
+            if entry.get().is_empty() {
+                entry.remove();
+                self.len_counts[peer_id.len()] -= 1;
+            }
+        }
+    }
+
+    /// Gets the `InternalConnectionId` (if any) associated with the destination connection ID
+    /// of the given packet
+    fn get_by_packet(&self, packet: &[u8]) -> Option<InternalConnectionId> {
+        match packet.first()? {
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
+            //# Long Header Packet {
+            //#   Header Form (1) = 1,
+            //#   Fixed Bit (1) = 1,
+            //#   Long Packet Type (2),
+            //#   Type-Specific Bits (4),
+            //#   Version (32),
+            //#   Destination Connection ID Length (8),
+            //#   Destination Connection ID (0..160),
+            tag if tag & 0x80 != 0 => {
+                let len = *packet.get(5)? as usize;
+                self.get(packet.get(6..6 + len)?)
+            }
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-17.3.1
+            //# 1-RTT Packet {
+            //#   Header Form (1) = 0,
+            //#   Fixed Bit (1) = 1,
+            //#   Spin Bit (1),
+            //#   Reserved Bits (2),
+            //#   Key Phase (1),
+            //#   Packet Number Length (2),
+            //#   Destination Connection ID (0..160),
+            _ => (1..=connection::id::MAX_LEN)
+                .filter(|len| self.len_counts[*len] > 0)
+                .find_map(|len| self.get(packet.get(1..1 + len)?)),
+        }
+    }
+
+    fn get(&self, peer_id: &[u8]) -> Option<InternalConnectionId> {
+        let peer_id = connection::PeerId::try_from_bytes(peer_id)?;
+        match self.map.get(&peer_id)?.as_slice() {
+            [internal_id] => Some(*internal_id),
+            _ => None,
+        }
+    }
+}
+
 /// Bidirectional map for mapping from initial ID to internal connection ID and vice-versa
 #[derive(Debug)]
 pub(crate) struct InitialIdMap {
@@ -194,6 +304,9 @@ pub(crate) struct ConnectionIdMapperState {
     pub(crate) stateless_reset_map: StatelessResetMap,
     /// Maps from initial id to internal connection IDs
     pub(crate) initial_id_map: InitialIdMap,
+    /// Maps from peer connection IDs to internal connection IDs, if ICMP Packet Too Big
+    /// messages are handled
+    pub(crate) peer_id_map: Option<PeerIdMap>,
 }
 
 impl ConnectionIdMapperState {
@@ -205,6 +318,7 @@ impl ConnectionIdMapperState {
                 HashState::new(random_generator),
                 HashState::new(random_generator),
             ),
+            peer_id_map: None,
         }
     }
 }
@@ -215,6 +329,8 @@ pub struct ConnectionIdMapper {
     state: Arc<Mutex<ConnectionIdMapperState>>,
     /// The endpoint type for the endpoint using this mapper
     endpoint_type: endpoint::Type,
+    /// Whether peer connection IDs are mapped to look up connections by quoted packets
+    is_peer_id_map_enabled: bool,
 }
 
 impl ConnectionIdMapper {
@@ -226,7 +342,24 @@ impl ConnectionIdMapper {
         Self {
             state: Arc::new(Mutex::new(ConnectionIdMapperState::new(random_generator))),
             endpoint_type,
+            is_peer_id_map_enabled: false,
+        }
+    }
+
+    /// Maps the peer connection IDs of connections so they can be looked up by the packets
+    /// quoted in ICMP messages
+    ///
+    /// This must be called before any `PeerIdRegistry` is created, since only the registries
+    /// created afterwards populate the map.
+    pub fn enable_peer_id_map(&mut self, random_generator: &mut dyn random::Generator) {
+        let mut guard = self
+            .state
+            .lock()
+            .expect("should succeed unless the lock is poisoned");
+        if guard.peer_id_map.is_none() {
+            guard.peer_id_map = Some(PeerIdMap::new(HashState::new(random_generator)));
         }
+        self.is_peer_id_map_enabled = true;
     }
 
     /// Looks up the internal Connection ID which is associated with an external
@@ -252,6 +385,24 @@ impl ConnectionIdMapper {
         })
     }
 
+    /// Looks up the internal Connection ID of the connection which sent the quoted packet in
+    /// an ICMP message, using the destination connection ID of the packet.
+    pub fn lookup_internal_connection_id_by_quoted_packet(
+        &self,
+        quoted_packet: &[u8],
+    ) -> Option<InternalConnectionId> {
+        if !self.is_peer_id_map_enabled {
+            return None;
+        }
+
+        self.state
+            .lock()
+            .expect("should succeed unless the lock is poisoned")
+            .peer_id_map
+            .as_ref()?
+            .get_by_packet(quoted_packet)
+    }
+
     /// Inserts the given `InitialId` into the map if it is not already in the map,
     /// otherwise returns an Err
     pub fn try_insert_initial_id(
@@ -334,7 +485,8 @@ impl ConnectionIdMapper {
         internal_id: InternalConnectionId,
         initial_connection_id: connection::PeerId,
     ) -> PeerIdRegistry {
-        let mut registry = PeerIdRegistry::new(internal_id, self.state.clone());
+        let mut registry =
+            PeerIdRegistry::new(internal_id, self.state.clone(), self.is_peer_id_map_enabled);
 
         registry.register_initial_connection_id(initial_connection_id);
         registry
@@ -349,7 +501,7 @@ impl ConnectionIdMapper {
         &mut self,
         internal_id: InternalConnectionId,
     ) -> PeerIdRegistry {
-        PeerIdRegistry::new(internal_id, self.state.clone())
+        PeerIdRegistry::new(internal_id, self.state.clone(), self.is_peer_id_map_enabled)
     }
 }
 
@@ -400,6 +552,92 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lookup_internal_connection_id_by_quoted_packet_test() {
+        let mut random_generator = random::testing::Generator(123);
+        let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+        let mut id_generator = InternalConnectionIdGenerator::new();
+        let internal_id_1 = id_generator.generate_id();
+        let internal_id_2 = id_generator.generate_id();
+
+        // peer IDs aren't mapped unless the map is enabled
+        let registry = mapper.create_server_peer_id_registry(internal_id_1, id(b"id01"));
+        assert_eq!(
+            None,
+            mapper.lookup_internal_connection_id_by_quoted_packet(b"\x40id01payload")
+        );
+        drop(registry);
+
+        mapper.enable_peer_id_map(&mut random_generator);
+
+        let registry_1 = mapper.create_server_peer_id_registry(internal_id_1, id(b"id01"));
+        let mut registry_2 = mapper.create_server_peer_id_registry(internal_id_2, id(b"id000002"));
+
+        // short header packets are matched with each connection ID length in use
+        assert_eq!(
+            Some(internal_id_1),
+            mapper.lookup_internal_connection_id_by_quoted_packet(b"\x40id01payload")
+        );
+        assert_eq!(
+            Some(internal_id_2),
+            mapper.lookup_internal_connection_id_by_quoted_packet(b"\x40id000002payload")
+        );
+
+        // long header packets include the connection ID length
+        assert_eq!(
+            Some(internal_id_2),
+            mapper.lookup_internal_connection_id_by_quoted_packet(
+                b"\xc0\x00\x00\x00\x01\x08id000002\x00"
+            )
+        );
+        assert_eq!(
+            None,
+            mapper.lookup_internal_connection_id_by_quoted_packet(
+                b"\xc0\x00\x00\x00\x01\x04id00\x00"
+            )
+        );
+
+        // truncated packets don't match
+        assert_eq!(
+            None,
+            mapper.lookup_internal_connection_id_by_quoted_packet(b"\x40id0")
+        );
+        assert_eq!(
+            None,
+            mapper.lookup_internal_connection_id_by_quoted_packet(b"")
+        );
+
+        // an ID used by more than one connection doesn't match either of them
+        registry_2
+            .on_new_connection_id(&id(b"id01"), 1, 0, &TEST_TOKEN_1)
+            .unwrap();
+        assert_eq!(
+            None,
+            mapper.lookup_internal_connection_id_by_quoted_packet(b"\x40id01")
+        );
+        assert_eq!(
+            Some(internal_id_2),
+            mapper.lookup_internal_connection_id_by_quoted_packet(b"\x40id000002")
+        );
+
+        // the IDs are removed once the registry is dropped
+        drop(registry_2);
+        assert_eq!(
+            Some(internal_id_1),
+            mapper.lookup_internal_connection_id_by_quoted_packet(b"\x40id01")
+        );
+        assert_eq!(
+            None,
+            mapper.lookup_internal_connection_id_by_quoted_packet(b"\x40id000002")
+        );
+
+        drop(registry_1);
+        assert_eq!(
+            None,
+            mapper.lookup_internal_connection_id_by_quoted_packet(b"\x40id01")
+        );
+    }
+
     #[test]
     fn initial_id_map() {
         let mut random_generator = random::testing::Generator(123);
@@ -38,6 +38,7 @@ use s2n_quic_core::{
     connection::{error::Error, id::Generator as _, InitialId, PeerId},
     crypto::{tls, CryptoSuite},
     datagram::{Receiver, Sender},
+    early_data,
     event::{
         self,
         builder::{DatagramDropReason, MtuUpdatedCause, RxStreamProgress, TxStreamProgress},
@@ -54,12 +55,13 @@ use s2n_quic_core::{
         version_negotiation::ProtectedVersionNegotiation,
         zero_rtt::ProtectedZeroRtt,
     },
-    path::{Handle as _, MaxMtu},
+    path::{migration, Handle as _, LocalAddress, MaxMtu, RemoteAddress},
     query,
     recovery::CongestionController,
     stateless_reset::token::Generator as _,
     time::{timer, Timestamp},
-    transport,
+    token::{self, Format as _},
+    transport, version,
 };
 
 /// Possible states for handing over a connection from the endpoint to the
@@ -178,6 +180,64 @@ pub struct ConnectionImpl<Config: endpoint::Config> {
     /// A Waker to the connection.
     waker: Waker,
     event_context: EventContext<Config>,
+    /// The decision made by the early data controller for the connection
+    ///
+    /// This is only set on servers when the client offered early data in its ClientHello.
+    early_data: Option<early_data::Outcome>,
+    /// The QUIC versions a client is willing to switch to when chosen by the server
+    quic_versions: version::Preferences,
+    /// The sockets the IO provider bound for paths from new local addresses
+    bound_sockets: BoundSockets<Config>,
+}
+
+/// The sockets the IO provider bound for the paths of a connection
+///
+/// The sockets are released when the connection is dropped, since packets can still arrive on
+/// a path after it's closed.
+struct BoundSockets<Config: endpoint::Config> {
+    binder: Option<connection::Binder<Config>>,
+    local_addresses: Vec<LocalAddress>,
+}
+
+impl<Config: endpoint::Config> BoundSockets<Config> {
+    /// Binds a socket to `local_address` for a path to `remote_address`
+    ///
+    /// Returns `None` if the IO provider can't bind sockets.
+    fn bind(
+        &mut self,
+        local_address: LocalAddress,
+        remote_address: RemoteAddress,
+    ) -> Option<Result<Config::PathHandle, migration::Error>> {
+        let binder = self.binder.as_ref()?;
+
+        let result = binder
+            .bind(local_address, remote_address)
+            .map(|handle| {
+                self.local_addresses.push(handle.local_address());
+                handle
+            })
+            .map_err(migration::Error::from);
+
+        Some(result)
+    }
+}
+
+impl<Config: endpoint::Config> fmt::Debug for BoundSockets<Config> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundSockets")
+            .field("local_addresses", &self.local_addresses)
+            .finish()
+    }
+}
+
+impl<Config: endpoint::Config> Drop for BoundSockets<Config> {
+    fn drop(&mut self) {
+        if let Some(binder) = self.binder.as_ref() {
+            for local_address in self.local_addresses.drain(..) {
+                binder.unbind(local_address);
+            }
+        }
+    }
 }
 
 struct EventContext<Config: endpoint::Config> {
@@ -273,17 +333,38 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         let mut publisher = self.event_context.publisher(timestamp, subscriber);
         let space_manager = &mut self.space_manager;
 
-        match space_manager.poll_crypto(
+        let poll = space_manager.poll_crypto(
             &mut self.path_manager,
             &mut self.local_id_registry,
             &mut self.limits,
+            &self.quic_versions,
             timestamp,
             &self.waker,
             &mut publisher,
             datagram,
-        ) {
-            Poll::Ready(res) => res?,
-            Poll::Pending => return Ok(()),
+        );
+
+        if let Poll::Ready(Err(err)) = poll {
+            return Err(err.into());
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-4.6.1
+        //# A client that wishes to send 0-RTT packets uses the
+        //# early_data extension in the ClientHello message of a subsequent
+        //# handshake; see Section 4.2.10 of [TLS13].  It then sends
+        //# application data in 0-RTT packets.
+        //
+        // Clients that derived 0-RTT keys have an application space before the handshake
+        // completes, so the connection can be handed to the application early.
+        if Config::ENDPOINT_TYPE.is_client()
+            && self.accept_state == AcceptState::Handshaking
+            && space_manager.application().is_some()
+        {
+            self.accept_state = AcceptState::HandshakeCompleted;
+        }
+
+        if poll.is_pending() {
+            return Ok(());
         }
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.1
@@ -313,7 +394,11 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         {
             // Move into the HandshakeCompleted state. This will signal the
             // necessary interest to hand over the connection to the application.
-            self.accept_state = AcceptState::HandshakeCompleted;
+            //
+            // Clients sending early data may have already been handed over.
+            if self.accept_state == AcceptState::Handshaking {
+                self.accept_state = AcceptState::HandshakeCompleted;
+            }
             // Move the connection into the active state.
             self.state = ConnectionState::Active;
 
@@ -411,6 +496,48 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         })
     }
 
+    /// Returns the handle of a path from `local_address` to the peer address of the active path
+    ///
+    /// Paths from a new local address are sent through a socket the IO provider binds to it. If
+    /// the IO provider can't bind sockets, packets are sent from the socket of the endpoint with
+    /// the source IP set to `local_address`, which requires the port to match the socket.
+    fn local_path_handle(
+        &mut self,
+        local_address: SocketAddress,
+    ) -> Result<Config::PathHandle, migration::Error> {
+        // Reuse the handle of an earlier request so the socket is only bound once
+        if let Some(handle) = self.path_manager.local_path_handle(&local_address) {
+            return Ok(handle);
+        }
+
+        let active_path = self.path_manager.active_path();
+
+        if let Some(handle) = self
+            .bound_sockets
+            .bind(local_address.into(), active_path.remote_address())
+        {
+            return handle;
+        }
+
+        let port = active_path.local_address().port();
+
+        // Packets are sent from the socket of the endpoint so keep using its port
+        // if the application only specified an IP
+        let mut local_address = local_address;
+        match local_address.port() {
+            0 => local_address.set_port(port),
+            local_port if port != 0 && local_port != port => {
+                return Err(migration::Error::LocalPortChanged)
+            }
+            _ => {}
+        }
+
+        active_path
+            .handle
+            .with_local_address(local_address.into())
+            .ok_or(migration::Error::UnsupportedLocalAddress)
+    }
+
     /// Send path validation frames for the non-active path.
     ///
     /// Since non-probing frames can only be sent on the active path, a separate
@@ -569,17 +696,25 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         // Assume clients validate the server's address implicitly.
         let peer_validated = Self::Config::ENDPOINT_TYPE.is_server();
 
-        let initial_path = path::Path::new(
+        let mut initial_path = path::Path::new(
             parameters.path_handle,
             parameters.peer_connection_id,
             parameters.local_connection_id,
             rtt_estimator,
             parameters.congestion_controller,
             peer_validated,
-            parameters.max_mtu,
+            parameters
+                .limits
+                .mtu_config()
+                .limit_max_mtu(parameters.max_mtu),
+        );
+        initial_path.spin_controller = path::spin::Controller::new(
+            parameters.limits.spin_config(),
+            parameters.random_generator,
         );
 
-        let path_manager = path::Manager::new(initial_path, parameters.peer_id_registry);
+        let mut path_manager = path::Manager::new(initial_path, parameters.peer_id_registry);
+        path_manager.quic_bit = path::quic_bit::Controller::new(parameters.random_generator);
 
         let mut publisher =
             event_context.publisher(parameters.timestamp, parameters.event_subscriber);
@@ -616,6 +751,12 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             wakeup_handle,
             waker,
             event_context,
+            early_data: None,
+            quic_versions: parameters.quic_versions,
+            bound_sockets: BoundSockets {
+                binder: parameters.binder,
+                local_addresses: Vec::new(),
+            },
         };
 
         if Config::ENDPOINT_TYPE.is_client() {
@@ -674,6 +815,52 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.event_context.quic_version
     }
 
+    /// Returns `true` if the connection advertised the `grease_quic_bit` transport parameter
+    fn accepts_cleared_quic_bit(&self) -> bool {
+        self.limits.grease_quic_bit().is_enabled()
+    }
+
+    fn on_quic_version_upgrade(
+        &mut self,
+        datagram: &DatagramInfo,
+        version: u32,
+        subscriber: &mut Config::EventSubscriber,
+    ) -> bool {
+        // Only clients switch versions and only before the first Initial packet from the server
+        // has been processed. Any downgrade is detected once the server's version_information
+        // transport parameter is validated.
+        if Config::ENDPOINT_TYPE.is_server() || self.path_manager.valid_initial_received() {
+            return false;
+        }
+
+        if !self.quic_versions.contains(version)
+            || !version::is_compatible(self.event_context.quic_version, version)
+        {
+            return false;
+        }
+
+        let space = match self.space_manager.initial_mut() {
+            Some((space, _handshake_status)) => space,
+            None => return false,
+        };
+
+        self.event_context.quic_version = version;
+
+        let peer_connection_id = self.path_manager.active_path().peer_connection_id;
+        let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
+        space.on_version_upgrade(&peer_connection_id, &mut publisher);
+
+        true
+    }
+
+    fn accepts_original_version(&self, version: u32) -> bool {
+        Config::ENDPOINT_TYPE.is_server()
+            && self
+                .space_manager
+                .initial()
+                .map_or(false, |space| space.accepts_original_version(version))
+    }
+
     /// Initiates closing the connection as described in
     /// https://www.rfc-editor.org/rfc/rfc9000#section-10
     fn close(
@@ -821,6 +1008,74 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         }
     }
 
+    /// Generates the address validation token sent to the client in a NEW_TOKEN frame, if the
+    /// handshake was confirmed since the last call
+    fn on_new_token(
+        &mut self,
+        token_format: &mut Config::TokenFormat,
+        random_generator: &mut Config::RandomGenerator,
+    ) {
+        let new_token =
+            if let Some((space, _handshake_status)) = self.space_manager.application_mut() {
+                &mut space.new_token
+            } else {
+                return;
+            };
+
+        if !new_token.requires_token() {
+            return;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# A server MAY provide clients with an address validation token during
+        //# one connection that can be used on a subsequent connection.
+        let path = self.path_manager.active_path();
+        let remote_address = path.remote_address();
+        let mut context =
+            token::Context::new(&remote_address, &path.peer_connection_id, random_generator);
+        let mut token = vec![0; <Config::TokenFormat as token::Format>::TOKEN_LEN];
+        let token = token_format
+            .generate_new_token(&mut context, &path.local_connection_id, &mut token)
+            .map(|_| token);
+
+        new_token.on_token(token);
+    }
+
+    /// Creates the server's TLS session once the whole ClientHello has been received
+    fn on_client_hello(
+        &mut self,
+        datagram: &DatagramInfo,
+        tls_endpoint: &mut Config::TLSEndpoint,
+        early_data_controller: &mut Config::EarlyDataController,
+        subscriber: &mut Config::EventSubscriber,
+        datagram_endpoint: &mut Config::DatagramEndpoint,
+    ) -> Result<(), connection::Error> {
+        if Config::ENDPOINT_TYPE.is_client() {
+            return Ok(());
+        }
+
+        let remote_address = self.path_manager.active_path().remote_address();
+        let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
+
+        let (quic_version, early_data) = if let Some(result) = self.space_manager.on_client_hello(
+            &remote_address,
+            &self.quic_versions,
+            tls_endpoint,
+            early_data_controller,
+            datagram.timestamp,
+            &mut publisher,
+        ) {
+            result
+        } else {
+            return Ok(());
+        };
+
+        self.event_context.quic_version = quic_version;
+        self.early_data = early_data;
+
+        self.update_crypto_state(datagram.timestamp, subscriber, datagram_endpoint)
+    }
+
     /// Queries the connection for outgoing packets
     fn on_transmit<Tx: tx::Queue<Handle = Config::PathHandle>>(
         &mut self,
@@ -889,6 +1144,31 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                     count += 1;
                 }
 
+                // With the multipath extension, data that didn't fit on the active path is sent
+                // on the other usable paths, starting with the path with the lowest RTT
+                if self.state == ConnectionState::Active {
+                    for path_id in self.path_manager.multipath_transmission_order() {
+                        while self.path_manager[path_id].can_transmit(timestamp)
+                            && queue
+                                .push(ConnectionTransmission {
+                                    context: transmission_context!(
+                                        self,
+                                        &mut outcome,
+                                        path_id,
+                                        timestamp,
+                                        transmission::Mode::Normal,
+                                        subscriber,
+                                        packet_interceptor,
+                                    ),
+                                    space_manager: &mut self.space_manager,
+                                })
+                                .is_ok()
+                        {
+                            count += 1;
+                        }
+                    }
+                }
+
                 if outcome.ack_elicitation.is_ack_eliciting() {
                     self.on_ack_eliciting_packet_sent(timestamp);
                 }
@@ -1080,6 +1360,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
     fn on_wakeup(
         &mut self,
         timestamp: Timestamp,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        max_mtu: MaxMtu,
+        random_generator: &mut Config::RandomGenerator,
         subscriber: &mut Config::EventSubscriber,
         datagram: &mut Config::DatagramEndpoint,
     ) -> Result<(), connection::Error> {
@@ -1092,9 +1375,53 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         // return an error if the application set one
         self.error?;
 
+        // start any migrations or path changes requested by the application
+        if Config::ENDPOINT_TYPE.is_client() && self.space_manager.is_handshake_confirmed() {
+            let mut publisher = self.event_context.publisher(timestamp, subscriber);
+            self.path_manager.on_local_migration_request(
+                congestion_controller_endpoint,
+                max_mtu,
+                random_generator,
+                &mut publisher,
+            );
+        }
+
         Ok(())
     }
 
+    fn on_packet_too_big(
+        &mut self,
+        remote_address: &path::RemoteAddress,
+        quoted_packet: &[u8],
+        mtu: u16,
+        timestamp: Timestamp,
+        subscriber: &mut Config::EventSubscriber,
+    ) {
+        let application = if let Some(application) = self.space_manager.application() {
+            application
+        } else {
+            // Only 1-RTT packets can be matched with the packets that were sent
+            return;
+        };
+
+        let mut publisher = self.event_context.publisher(timestamp, subscriber);
+        self.path_manager.on_packet_too_big(
+            remote_address,
+            quoted_packet,
+            mtu,
+            |peer_connection_id, path_identifier| {
+                application.is_unacknowledged_quoted_packet(
+                    quoted_packet,
+                    remote_address,
+                    peer_connection_id,
+                    path_identifier,
+                )
+            },
+            timestamp,
+            &mut publisher,
+        );
+    }
+
     // Packet handling
     fn on_datagram_received(
         &mut self,
@@ -1103,6 +1430,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
         path_migration: &mut Config::PathMigrationValidator,
         max_mtu: MaxMtu,
+        random_generator: &mut Config::RandomGenerator,
         subscriber: &mut Config::EventSubscriber,
     ) -> Result<path::Id, DatagramDropReason> {
         let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
@@ -1120,6 +1448,15 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         //# size of packets it receives from that address.
         let handshake_confirmed = self.space_manager.is_handshake_confirmed();
 
+        if Config::ENDPOINT_TYPE.is_client() && handshake_confirmed {
+            self.path_manager.probe_preferred_address(
+                congestion_controller_endpoint,
+                max_mtu,
+                random_generator,
+                &mut publisher,
+            );
+        }
+
         let (id, unblocked) = self.path_manager.on_datagram_received(
             path_handle,
             datagram,
@@ -1153,8 +1490,11 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             //# datagram unblocks it, even if none of the packets in the datagram are
             //# successfully processed.  In such a case, the PTO timer will need to
             //# be re-armed.
-            self.space_manager
-                .on_amplification_unblocked(&self.path_manager[id], datagram.timestamp);
+            self.space_manager.on_amplification_unblocked(
+                id,
+                &self.path_manager[id],
+                datagram.timestamp,
+            );
         }
 
         Ok(id)
@@ -1407,9 +1747,20 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             return Ok(());
         }
 
+        // Each path sends packets in the packet number space of the sequence number of the
+        // destination connection id
+        let path_identifier = if self.path_manager.has_path_packet_number_spaces() {
+            self.local_id_registry
+                .sequence_number(&datagram.destination_connection_id)
+                .unwrap_or_default()
+        } else {
+            0
+        };
+
         if let Some((space, handshake_status)) = self.space_manager.application_mut() {
             let packet = space.validate_and_decrypt_packet(
                 packet,
+                path_identifier,
                 datagram,
                 path_id,
                 &self.path_manager[path_id],
@@ -1423,6 +1774,10 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 ),
             });
 
+            self.path_manager[path_id]
+                .spin_controller
+                .on_packet_received(Config::ENDPOINT_TYPE, packet.spin_bit, packet.packet_number);
+
             // Connection Ids are issued to the peer after the handshake is
             // confirmed and the handshake space is discarded. Therefore only
             // short packets need to be processed for local_connection_id changes.
@@ -1448,6 +1803,12 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
 
             // notify the connection a packet was processed
             self.on_processed_packet(&processed_packet, subscriber)?;
+
+            // The 0-RTT keys are kept for a short time to open reordered 0-RTT packets
+            if Config::ENDPOINT_TYPE.is_server() {
+                self.space_manager
+                    .on_one_rtt_packet(&self.path_manager[path_id], datagram.timestamp);
+            }
         }
 
         Ok(())
@@ -1498,28 +1859,127 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
     fn handle_zero_rtt_packet(
         &mut self,
         datagram: &DatagramInfo,
-        _path_id: path::Id,
-        _packet: ProtectedZeroRtt,
+        path_id: path::Id,
+        packet: ProtectedZeroRtt,
+        random_generator: &mut Config::RandomGenerator,
         subscriber: &mut Config::EventSubscriber,
-        _packet_interceptor: &mut Config::PacketInterceptor,
+        packet_interceptor: &mut Config::PacketInterceptor,
     ) -> Result<(), ProcessingError> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.3
+        //# A client MUST NOT process 0-RTT packets.
+        if Config::ENDPOINT_TYPE.is_client() {
+            return Ok(());
+        }
+
         let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
 
+        let (space, crypto) = if let Some(result) = self.space_manager.zero_rtt_mut() {
+            result
+        } else {
+            let path = &self.path_manager[path_id];
+            if let Some(early_data::Outcome::Reject(_)) = self.early_data {
+                publisher.on_packet_dropped(event::builder::PacketDropped {
+                    reason: event::builder::PacketDropReason::EarlyDataRejected {
+                        path: path_event!(path, path_id),
+                    },
+                });
+            } else {
+                publisher.on_packet_dropped(event::builder::PacketDropped {
+                    reason: event::builder::PacketDropReason::UnprotectFailed {
+                        space: event::builder::KeySpace::ZeroRtt,
+                        path: path_event!(path, path_id),
+                    },
+                });
+            }
+
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-5.2.2
+            //= type=TODO
+            //= tracking-issue=339
+            //# If the packet is a 0-RTT packet, the server MAY buffer a limited
+            //# number of these packets in anticipation of a late-arriving Initial
+            //# packet.
+
+            return Ok(());
+        };
+
+        let packet = space.validate_and_decrypt_zero_rtt_packet(
+            packet,
+            crypto,
+            datagram,
+            path_id,
+            &self.path_manager[path_id],
+            &mut publisher,
+        )?;
+
         publisher.on_packet_received(event::builder::PacketReceived {
             packet_header: event::builder::PacketHeader::ZeroRtt {
-                // FIXME: replace with PacketHeader::new when we support zero-rtt.
-                number: 0,
-                version: publisher.quic_version(),
+                number: packet.packet_number.into_event(),
+                version: packet.version,
             },
         });
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-5.2.2
-        //= type=TODO
-        //= tracking-issue=339
-        //# If the packet is a 0-RTT packet, the server MAY buffer a limited
-        //# number of these packets in anticipation of a late-arriving Initial
-        //# packet.
 
-        // TODO
+        let payload = packet.payload.into_less_safe_slice();
+
+        {
+            use s2n_codec::DecoderBufferMut;
+            use s2n_quic_core::frame::{Frame, FrameMut};
+
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.3
+            //# A server MUST treat receipt of a CRYPTO frame in a 0-RTT packet as a
+            //# connection error of type PROTOCOL_VIOLATION.
+            //
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-12.5
+            //# Note that it is not possible to send the following frames in 0-RTT
+            //# packets for various reasons: ACK, CRYPTO, HANDSHAKE_DONE, NEW_TOKEN,
+            //# PATH_RESPONSE, and RETIRE_CONNECTION_ID.  A server MAY treat receipt
+            //# of these frames in 0-RTT packets as a connection error of type
+            //# PROTOCOL_VIOLATION.
+            let mut frames = DecoderBufferMut::new(payload);
+            while !frames.is_empty() {
+                let (frame, remaining) = frames
+                    .decode::<FrameMut>()
+                    .map_err(transport::Error::from)?;
+
+                if matches!(
+                    frame,
+                    Frame::Ack(_)
+                        | Frame::Crypto(_)
+                        | Frame::HandshakeDone(_)
+                        | Frame::NewToken(_)
+                        | Frame::PathResponse(_)
+                        | Frame::RetireConnectionId(_)
+                ) {
+                    return Err(transport::Error::PROTOCOL_VIOLATION
+                        .with_reason("invalid frame in 0-RTT packet")
+                        .with_frame_type(frame.tag().into())
+                        .into());
+                }
+
+                frames = remaining;
+            }
+        }
+
+        let (space, handshake_status) = self
+            .space_manager
+            .application_mut()
+            .expect("the application space is checked above");
+
+        let processed_packet = space.handle_cleartext_payload(
+            packet.packet_number,
+            s2n_codec::DecoderBufferMut::new(payload),
+            datagram,
+            path_id,
+            &mut self.path_manager,
+            handshake_status,
+            &mut self.local_id_registry,
+            random_generator,
+            &mut publisher,
+            packet_interceptor,
+        )?;
+
+        // notify the connection a packet was processed
+        self.on_processed_packet(&processed_packet, subscriber)?;
+
         Ok(())
     }
 
@@ -1801,6 +2261,35 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.space_manager.application_protocol.clone()
     }
 
+    fn peer_certificate_chain(&self) -> Vec<Bytes> {
+        self.space_manager.peer_certificate_chain.clone()
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        self.error?;
+
+        match self
+            .space_manager
+            .export_keying_material(label, context, output)
+        {
+            Some(Ok(())) => Ok(()),
+            // the TLS provider failed to export the keying material
+            Some(Err(_)) => Err(connection::Error::unspecified()),
+            None => {
+                debug_assert!(
+                    false,
+                    "applications can't interact with the connection until the handshake is complete"
+                );
+                Err(connection::Error::unspecified())
+            }
+        }
+    }
+
     fn ping(&mut self) -> Result<(), connection::Error> {
         self.error?;
 
@@ -1837,6 +2326,89 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(())
     }
 
+    fn poll_migrate(
+        &mut self,
+        local_address: SocketAddress,
+        context: &Context,
+    ) -> Poll<Result<(), migration::Error>> {
+        self.error?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# Clients are responsible for initiating all migrations.
+        if Config::ENDPOINT_TYPE.is_server() {
+            return Err(migration::Error::NotClient).into();
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# An endpoint MUST NOT initiate
+        //# connection migration before the handshake is confirmed, as defined
+        //# in section 4.1.2 of [QUIC-TLS].
+        if !self.space_manager.is_handshake_confirmed() {
+            return Err(migration::Error::HandshakeNotConfirmed).into();
+        }
+
+        let path_handle = self.local_path_handle(local_address)?;
+        let outcome = self.path_manager.poll_local_migration(path_handle, context);
+
+        if outcome.is_pending() {
+            // wake up the endpoint to start the migration
+            self.wakeup_handle.wakeup();
+        }
+
+        outcome
+    }
+
+    fn poll_open_path(
+        &mut self,
+        local_address: SocketAddress,
+        context: &Context,
+    ) -> Poll<Result<(), migration::Error>> {
+        self.error?;
+
+        // Additional paths are opened in the same way as a migration, which only clients
+        // can initiate once the handshake is confirmed
+        if Config::ENDPOINT_TYPE.is_server() {
+            return Err(migration::Error::NotClient).into();
+        }
+
+        if !self.space_manager.is_handshake_confirmed() {
+            return Err(migration::Error::HandshakeNotConfirmed).into();
+        }
+
+        let path_handle = self.local_path_handle(local_address)?;
+        let outcome = self.path_manager.poll_open_local_path(path_handle, context);
+
+        if outcome.is_pending() {
+            // wake up the endpoint to start probing the path
+            self.wakeup_handle.wakeup();
+        }
+
+        outcome
+    }
+
+    fn close_path(&mut self, local_address: SocketAddress) -> Result<(), migration::Error> {
+        self.error?;
+
+        if Config::ENDPOINT_TYPE.is_server() {
+            return Err(migration::Error::NotClient);
+        }
+
+        if !self.space_manager.is_handshake_confirmed() {
+            return Err(migration::Error::HandshakeNotConfirmed);
+        }
+
+        let path_handle = self
+            .path_manager
+            .local_path_handle(&local_address)
+            .ok_or(migration::Error::PathNotFound)?;
+        self.path_manager.close_local_path(&path_handle)?;
+
+        // wake up the endpoint to move the connection off the closed path
+        self.wakeup_handle.wakeup();
+
+        Ok(())
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         Ok(*self.path_manager.active_path().handle.local_address())
     }
@@ -1845,6 +2417,17 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(*self.path_manager.active_path().handle.remote_address())
     }
 
+    fn stats(&self) -> Result<connection::Stats, connection::Error> {
+        let mut stats = connection::Stats::default();
+        stats.paths.extend(self.path_manager.stats());
+
+        if let Some(space) = self.space_manager.application() {
+            space.stream_manager.write_stats(&mut stats);
+        }
+
+        Ok(stats)
+    }
+
     fn error(&self) -> Option<connection::Error> {
         self.error.err()
     }
@@ -30,9 +30,9 @@ use s2n_quic_core::{
         short::ProtectedShort,
         version_negotiation::ProtectedVersionNegotiation,
         zero_rtt::ProtectedZeroRtt,
-        ProtectedPacket,
+        ProtectedPacket, QuicBit,
     },
-    path::{Handle as _, MaxMtu},
+    path::{migration, Handle as _, MaxMtu},
     query,
     time::Timestamp,
 };
@@ -77,6 +77,27 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         timestamp: Timestamp,
     ) -> Result<(), LocalIdRegistrationError>;
 
+    /// Generates the address validation token sent to the client in a NEW_TOKEN frame, if the
+    /// handshake was confirmed since the last call
+    fn on_new_token(
+        &mut self,
+        token_format: &mut <Self::Config as endpoint::Config>::TokenFormat,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
+    );
+
+    /// Creates the server's TLS session once the whole ClientHello has been received
+    ///
+    /// The QUIC version and the early data outcome are decided from the ClientHello, which can
+    /// span multiple Initial packets.
+    fn on_client_hello(
+        &mut self,
+        datagram: &DatagramInfo,
+        tls_endpoint: &mut <Self::Config as endpoint::Config>::TLSEndpoint,
+        early_data_controller: &mut <Self::Config as endpoint::Config>::EarlyDataController,
+        subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+        datagram_endpoint: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
+    ) -> Result<(), connection::Error>;
+
     /// Queries the connection for outgoing packets
     fn on_transmit<Tx>(
         &mut self,
@@ -104,10 +125,27 @@ pub trait ConnectionTrait: 'static + Send + Sized {
     fn on_wakeup(
         &mut self,
         timestamp: Timestamp,
+        congestion_controller_endpoint: &mut <Self::Config as endpoint::Config>::CongestionControllerEndpoint,
+        max_mtu: MaxMtu,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         datagram: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
     ) -> Result<(), connection::Error>;
 
+    /// Is called when an ICMP Packet Too Big message is received for a datagram sent to
+    /// `remote_address`
+    ///
+    /// `quoted_packet` contains the start of the QUIC packet quoted by the message and
+    /// `mtu` is the largest MTU the message reported for the path.
+    fn on_packet_too_big(
+        &mut self,
+        remote_address: &path::RemoteAddress,
+        quoted_packet: &[u8],
+        mtu: u16,
+        timestamp: Timestamp,
+        subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+    );
+
     // Packet handling
 
     /// Is called when an initial packet had been received
@@ -176,6 +214,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         datagram: &DatagramInfo,
         path_id: path::Id,
         packet: ProtectedZeroRtt,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
     ) -> Result<(), ProcessingError>;
@@ -199,6 +238,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         congestion_controller_endpoint: &mut <Self::Config as endpoint::Config>::CongestionControllerEndpoint,
         migration_validator: &mut <Self::Config as endpoint::Config>::PathMigrationValidator,
         max_mtu: MaxMtu,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
     ) -> Result<path::Id, DatagramDropReason>;
 
@@ -208,6 +248,24 @@ pub trait ConnectionTrait: 'static + Send + Sized {
     /// Returns the QUIC version selected for the current connection
     fn quic_version(&self) -> u32;
 
+    /// Returns `true` if the connection accepts packets with the QUIC bit set to 0
+    fn accepts_cleared_quic_bit(&self) -> bool;
+
+    /// Is called when a client receives an Initial packet from the server with a
+    /// different version than the connection is using
+    ///
+    /// Returns `true` if the connection switched to the server's version.
+    fn on_quic_version_upgrade(
+        &mut self,
+        datagram: &DatagramInfo,
+        version: u32,
+        subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+    ) -> bool;
+
+    /// Returns `true` if a server still accepts Initial packets with the client's original
+    /// version after switching to a compatible version
+    fn accepts_original_version(&self, version: u32) -> bool;
+
     /// Handles reception of a single QUIC packet
     #[allow(clippy::too_many_arguments)]
     fn handle_packet(
@@ -224,7 +282,14 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         //# If a client receives a packet that uses a different version than it
         //# initially selected, it MUST discard that packet.
         if let Some(version) = packet.version() {
-            if version != self.quic_version() {
+            // Clients follow the server's choice of a compatible version, which is indicated by
+            // the version of the server's first Initial packet. Servers keep accepting Initial
+            // packets with the client's original version until the client switches as well.
+            if version != self.quic_version()
+                && !(matches!(packet, ProtectedPacket::Initial(_))
+                    && (self.accepts_original_version(version)
+                        || self.on_quic_version_upgrade(datagram, version, subscriber)))
+            {
                 self.with_event_publisher(
                     datagram.timestamp,
                     Some(path_id),
@@ -242,6 +307,30 @@ pub trait ConnectionTrait: 'static + Send + Sized {
             }
         }
 
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
+        //# Packets containing a zero
+        //# value for this bit are not valid packets in this version and MUST
+        //# be discarded.
+
+        //= https://www.rfc-editor.org/rfc/rfc9287#section-3.1
+        //# An endpoint that advertises the grease_quic_bit transport parameter
+        //# MUST accept packets with the QUIC Bit set to a value of 0.
+        if packet.quic_bit() == QuicBit::Zero && !self.accepts_cleared_quic_bit() {
+            self.with_event_publisher(
+                datagram.timestamp,
+                Some(path_id),
+                subscriber,
+                |publisher, path| {
+                    publisher.on_packet_dropped(event::builder::PacketDropped {
+                        reason: event::builder::PacketDropReason::QuicBitCleared {
+                            path: path_event!(path, path_id),
+                        },
+                    })
+                },
+            );
+            return Ok(());
+        }
+
         //= https://www.rfc-editor.org/rfc/rfc9001#section-4.1.4
         //# An endpoint SHOULD continue
         //# to respond to packets that can be processed during this time.
@@ -278,6 +367,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
                 datagram,
                 path_id,
                 packet,
+                random_generator,
                 subscriber,
                 packet_interceptor,
             ),
@@ -434,14 +524,39 @@ pub trait ConnectionTrait: 'static + Send + Sized {
 
     fn application_protocol(&self) -> Bytes;
 
+    fn peer_certificate_chain(&self) -> Vec<Bytes>;
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error>;
+
     fn ping(&mut self) -> Result<(), connection::Error>;
 
     fn keep_alive(&mut self, enabled: bool) -> Result<(), connection::Error>;
 
+    fn poll_migrate(
+        &mut self,
+        local_address: SocketAddress,
+        context: &Context,
+    ) -> Poll<Result<(), migration::Error>>;
+
+    fn poll_open_path(
+        &mut self,
+        local_address: SocketAddress,
+        context: &Context,
+    ) -> Poll<Result<(), migration::Error>>;
+
+    fn close_path(&mut self, local_address: SocketAddress) -> Result<(), migration::Error>;
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn stats(&self) -> Result<connection::Stats, connection::Error>;
+
     fn error(&self) -> Option<connection::Error>;
 
     fn query_event_context(&self, query: &mut dyn query::Query);
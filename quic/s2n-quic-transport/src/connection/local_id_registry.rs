@@ -380,6 +380,46 @@ impl LocalIdRegistry {
         Ok(())
     }
 
+    /// Registers the connection ID advertised in the server's preferred_address transport
+    /// parameter.
+    ///
+    /// The connection ID is sent to the peer during the handshake, so it starts in the
+    /// `Active` status instead of being issued in a NEW_CONNECTION_ID frame.
+    pub fn register_preferred_address_connection_id(
+        &mut self,
+        id: &connection::LocalId,
+        expiration: Option<Timestamp>,
+        stateless_reset_token: stateless_reset::Token,
+    ) -> Result<(), LocalIdRegistrationError> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+        //# The value of the
+        //# active_connection_id_limit parameter MUST be at least 2.
+        //
+        // The peer's limit isn't known until its transport parameters are received, but it
+        // always allows for the handshake and preferred address connection IDs.
+        self.active_connection_id_limit = self.active_connection_id_limit.max(2);
+
+        self.register_connection_id(id, expiration, stateless_reset_token)?;
+
+        let id_info = self
+            .registered_ids
+            .last_mut()
+            .expect("connection ID registered above");
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+        //# If the preferred_address transport
+        //# parameter is sent, the sequence number of the supplied connection ID
+        //# is 1.
+        debug_assert_eq!(id_info.sequence_number, 1);
+
+        id_info.status = Active;
+        self.transmission_interest.clear();
+
+        self.check_consistency();
+
+        Ok(())
+    }
+
     /// Unregisters connection IDs that have expired
     fn unregister_expired_ids(&mut self, timestamp: Timestamp) {
         {
@@ -412,6 +452,14 @@ impl LocalIdRegistry {
         self.check_consistency();
     }
 
+    /// Returns the sequence number of the connection ID if it is registered
+    pub fn sequence_number(&self, id: &connection::LocalId) -> Option<u32> {
+        self.registered_ids
+            .iter()
+            .find(|id_info| id_info.id == *id)
+            .map(|id_info| id_info.sequence_number)
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
     //# When an endpoint issues a connection ID, it MUST accept packets that
     //# carry this connection ID for the duration of the connection or until
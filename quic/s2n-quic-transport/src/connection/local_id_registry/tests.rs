@@ -16,8 +16,8 @@ use crate::{
     connection::{
         connection_id_mapper::*,
         local_id_registry::{
-            LocalIdInfo, LocalIdRegistrationError, LocalIdRegistry, EXPIRATION_BUFFER,
-            MAX_ACTIVE_CONNECTION_ID_LIMIT, RTT_MULTIPLIER,
+            LocalIdInfo, LocalIdRegistrationError, LocalIdRegistry, LocalIdStatus,
+            EXPIRATION_BUFFER, MAX_ACTIVE_CONNECTION_ID_LIMIT, RTT_MULTIPLIER,
         },
         InternalConnectionIdGenerator,
     },
@@ -420,6 +420,32 @@ fn connection_id_interest() {
     );
 }
 
+#[test]
+fn preferred_address_connection_id() {
+    let ext_id_1 = id(b"id01");
+    let ext_id_2 = id(b"id02");
+
+    let (_, mut reg1) = mapper(ext_id_1, None, TEST_TOKEN_1);
+
+    assert!(reg1
+        .register_preferred_address_connection_id(&ext_id_2, None, TEST_TOKEN_2)
+        .is_ok());
+
+    let id_info = reg1.get_connection_id_info(&ext_id_2).unwrap();
+    assert_eq!(1, id_info.sequence_number);
+    // The connection ID was sent in the transport parameters so no NEW_CONNECTION_ID
+    // frame is needed
+    assert_eq!(LocalIdStatus::Active, id_info.status);
+    assert_eq!(
+        transmission::Interest::None,
+        reg1.get_transmission_interest()
+    );
+    assert_eq!(
+        connection::id::Interest::None,
+        reg1.connection_id_interest()
+    );
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
 //= type=test
 //# An endpoint MUST NOT
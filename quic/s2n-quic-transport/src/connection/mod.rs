@@ -7,7 +7,8 @@ use crate::{
     endpoint, path::MaxMtu, recovery::congestion_controller, space::PacketSpaceManager,
     wakeup_queue::WakeupHandle,
 };
-use s2n_quic_core::{connection, event, event::supervisor, time::Timestamp};
+use alloc::sync::Arc;
+use s2n_quic_core::{connection, event, event::supervisor, io::bind, time::Timestamp};
 
 mod api;
 mod api_provider;
@@ -44,6 +45,10 @@ pub use open_token::Pair as OpenToken;
 /// re-export core
 pub use s2n_quic_core::connection::*;
 
+/// Binds sockets to additional local addresses through the IO provider
+pub(crate) type Binder<Cfg> =
+    Arc<dyn bind::Bind<PathHandle = <Cfg as endpoint::Config>::PathHandle>>;
+
 /// Parameters which are passed to a Connection.
 /// These are unique per created connection.
 pub struct Parameters<'a, Cfg: endpoint::Config> {
@@ -71,10 +76,14 @@ pub struct Parameters<'a, Cfg: endpoint::Config> {
     pub timestamp: Timestamp,
     /// The QUIC protocol version which is used for this particular connection
     pub quic_version: u32,
+    /// The QUIC versions enabled on the endpoint
+    pub quic_versions: s2n_quic_core::version::Preferences,
     /// The limits that were advertised to the peer
     pub limits: connection::Limits,
     /// The largest maximum transmission unit (MTU) that can be sent on a path
     pub max_mtu: MaxMtu,
+    /// Binds sockets to new local addresses, if the IO provider supports it
+    pub binder: Option<Binder<Cfg>>,
     /// The context that should be passed to all related connection events
     pub event_context: <Cfg::EventSubscriber as event::Subscriber>::ConnectionContext,
     /// The context passed to the connection supervisor
@@ -83,4 +92,6 @@ pub struct Parameters<'a, Cfg: endpoint::Config> {
     pub datagram_endpoint: &'a mut Cfg::DatagramEndpoint,
     /// The event subscriber for the endpoint
     pub event_subscriber: &'a mut Cfg::EventSubscriber,
+    /// The random number generator for the endpoint
+    pub random_generator: &'a mut Cfg::RandomGenerator,
 }
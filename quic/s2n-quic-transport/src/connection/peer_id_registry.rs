@@ -11,7 +11,7 @@ use crate::{
             },
             PeerIdStatus::{
                 InUse, InUsePendingNewConnectionId, New, PendingAcknowledgement, PendingRetirement,
-                PendingRetirementRetransmission,
+                PendingRetirementRetransmission, Reserved,
             },
         },
         InternalConnectionId,
@@ -57,6 +57,8 @@ pub struct PeerIdRegistry {
     internal_id: InternalConnectionId,
     /// The shared state between mapper and registration
     state: Arc<Mutex<ConnectionIdMapperState>>,
+    /// Whether the registered IDs are added to the peer ID map of the mapper
+    is_peer_id_map_enabled: bool,
     /// The connection IDs which are currently registered
     registered_ids: RegisteredIds,
     /// The largest retire prior to value that has been received from the peer
@@ -130,7 +132,10 @@ impl PeerIdInfo {
 
     /// Returns true if this PeerId may be used to send packets to the peer
     fn is_active(&self) -> bool {
-        matches!(self.status, New | InUse | InUsePendingNewConnectionId)
+        matches!(
+            self.status,
+            New | Reserved | InUse | InUsePendingNewConnectionId
+        )
     }
 
     /// Returns true if the status of this ID allows for transmission
@@ -149,6 +154,9 @@ impl PeerIdInfo {
 enum PeerIdStatus {
     /// Connection IDs received in NEW_CONNECTION_ID frames start in the `New` status.
     New,
+    /// The connection ID received in the server's preferred_address transport parameter is
+    /// reserved for the path to the preferred address and will not be used on other paths.
+    Reserved,
     /// Once a connection ID is used on a path it moves to the `InUse` status.
     InUse,
     /// The initial connection ID used during the handshake is in use, but will be retired
@@ -230,13 +238,15 @@ impl From<PeerIdRegistrationError> for transport::Error {
 impl Drop for PeerIdRegistry {
     fn drop(&mut self) {
         if let Ok(mut guard) = self.state.lock() {
-            // Stop tracking all associated stateless reset tokens
-            for token in self
-                .registered_ids
-                .iter()
-                .flat_map(|id_info| id_info.stateless_reset_token)
-            {
-                guard.stateless_reset_map.remove(&token);
+            for id_info in self.registered_ids.iter() {
+                // Stop tracking all associated stateless reset tokens
+                if let Some(token) = id_info.stateless_reset_token {
+                    guard.stateless_reset_map.remove(&token);
+                }
+
+                if let Some(peer_id_map) = guard.peer_id_map.as_mut() {
+                    peer_id_map.remove(&id_info.id, self.internal_id);
+                }
             }
         }
     }
@@ -247,10 +257,12 @@ impl PeerIdRegistry {
     pub(crate) fn new(
         internal_id: InternalConnectionId,
         state: Arc<Mutex<ConnectionIdMapperState>>,
+        is_peer_id_map_enabled: bool,
     ) -> Self {
         Self {
             internal_id,
             state,
+            is_peer_id_map_enabled,
             registered_ids: SmallVec::new(),
             retire_prior_to: 0,
             ack_interest: Memo::new(|ids| {
@@ -294,6 +306,8 @@ impl PeerIdRegistry {
             status: PeerIdStatus::InUsePendingNewConnectionId,
         });
 
+        self.insert_into_peer_id_map(&peer_id);
+
         self.check_consistency();
     }
 
@@ -321,6 +335,56 @@ impl PeerIdRegistry {
             .insert(stateless_reset_token, self.internal_id);
     }
 
+    /// Used to register the connection ID the server provided in its preferred_address
+    /// transport parameter.
+    ///
+    /// The connection ID is reserved for the path to the preferred address until it
+    /// is consumed with `consume_preferred_address_id`.
+    pub(crate) fn register_preferred_address_connection_id(
+        &mut self,
+        peer_id: &connection::PeerId,
+        stateless_reset_token: &stateless_reset::Token,
+    ) -> Result<(), PeerIdRegistrationError> {
+        debug_assert!(!self.is_empty());
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+        //# If the preferred_address transport parameter is sent, the sequence number of the
+        //# supplied connection ID is 1.
+        let sequence_number = 1;
+
+        let mut active_id_count = 1;
+
+        for id_info in self.registered_ids.iter() {
+            if id_info.validate_new_connection_id(
+                peer_id,
+                stateless_reset_token,
+                sequence_number,
+            )? {
+                // The transport parameters are only received once
+                return Err(InvalidNewConnectionId);
+            }
+
+            if id_info.is_active() {
+                active_id_count += 1;
+            }
+        }
+
+        self.registered_ids.push(PeerIdInfo {
+            id: *peer_id,
+            sequence_number,
+            stateless_reset_token: Some(*stateless_reset_token),
+            status: Reserved,
+        });
+
+        self.insert_into_peer_id_map(peer_id);
+
+        self.check_active_connection_id_limit(active_id_count)?;
+
+        self.check_consistency();
+
+        Ok(())
+    }
+
     /// Check if registered_ids is empty.
     ///
     /// This is only expected to be true when an endpoint creates a new
@@ -413,6 +477,8 @@ impl PeerIdRegistry {
 
             self.registered_ids.push(new_id_info);
 
+            self.insert_into_peer_id_map(new_id);
+
             self.check_active_connection_id_limit(active_id_count)?;
         }
 
@@ -482,6 +548,10 @@ impl PeerIdRegistry {
                         mapper_state.stateless_reset_map.remove(&token);
                     }
 
+                    if let Some(peer_id_map) = mapper_state.peer_id_map.as_mut() {
+                        peer_id_map.remove(&id_info.id, self.internal_id);
+                    }
+
                     self.ack_interest.clear();
 
                     //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.2
@@ -525,12 +595,25 @@ impl PeerIdRegistry {
             .any(|id_info| peer_id == &id_info.id && id_info.is_active())
     }
 
+    /// Returns the sequence number of the peer_id if it is registered
+    pub fn sequence_number(&self, peer_id: &connection::PeerId) -> Option<u32> {
+        self.registered_ids
+            .iter()
+            .find(|id_info| peer_id == &id_info.id)
+            .map(|id_info| id_info.sequence_number)
+    }
+
     /// Tries to consume a new peer_id if one is available.
+    fn consume_new_id_inner(&mut self) -> Option<connection::PeerId> {
+        self.consume_id_inner(New)
+    }
+
+    /// Tries to consume a peer_id with the given status if one is available.
     ///
     /// Register the stateless reset token once a connection ID is in use.
-    fn consume_new_id_inner(&mut self) -> Option<connection::PeerId> {
+    fn consume_id_inner(&mut self, status: PeerIdStatus) -> Option<connection::PeerId> {
         for id_info in self.registered_ids.iter_mut() {
-            if id_info.status == New {
+            if id_info.status == status {
                 // Start tracking the stateless reset token
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-10.3.1
                 //# An endpoint MUST NOT check for any stateless reset tokens associated
@@ -579,6 +662,29 @@ impl PeerIdRegistry {
         self.consume_new_id_inner()
     }
 
+    /// Tries to consume the peer_id reserved for the server's preferred address.
+    pub fn consume_preferred_address_id(&mut self) -> Option<connection::PeerId> {
+        self.consume_id_inner(Reserved)
+    }
+
+    /// Adds the peer_id to the peer ID map of the mapper, which is only used if the endpoint
+    /// handles ICMP Packet Too Big messages
+    fn insert_into_peer_id_map(&self, peer_id: &connection::PeerId) {
+        if !self.is_peer_id_map_enabled {
+            return;
+        }
+
+        if let Some(peer_id_map) = self
+            .state
+            .lock()
+            .expect("should succeed unless the lock is poisoned")
+            .peer_id_map
+            .as_mut()
+        {
+            peer_id_map.insert(peer_id, self.internal_id);
+        }
+    }
+
     // Validate that the ACTIVE_CONNECTION_ID_LIMIT has not been exceeded
     fn check_active_connection_id_limit(
         &self,
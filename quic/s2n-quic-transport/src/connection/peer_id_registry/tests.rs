@@ -11,7 +11,7 @@ use crate::{
             },
             PeerIdStatus::{
                 InUse, InUsePendingNewConnectionId, New, PendingAcknowledgement, PendingRetirement,
-                PendingRetirementRetransmission,
+                PendingRetirementRetransmission, Reserved,
             },
             RETIRED_CONNECTION_ID_LIMIT,
         },
@@ -441,6 +441,50 @@ pub fn consume_new_id_should_error_if_no_ids_are_available() {
     assert_eq!(None, reg.consume_new_id_inner());
 }
 
+#[test]
+pub fn preferred_address_id_is_reserved() {
+    let id_1 = id(b"id01");
+    let mut reg = peer_registry(id_1, Some(TEST_TOKEN_1));
+
+    let id_2 = id(b"id02");
+    assert!(reg
+        .register_preferred_address_connection_id(&id_2, &TEST_TOKEN_2)
+        .is_ok());
+    assert_eq!(1, reg.registered_ids[1].sequence_number);
+    assert_eq!(Reserved, reg.registered_ids[1].status);
+    assert!(reg.is_active(&id_2));
+
+    // The reserved ID is not used for other paths
+    assert_eq!(None, reg.consume_new_id_inner());
+
+    // The handshake connection ID is still retired once a new connection ID is received
+    let id_3 = id(b"id03");
+    assert!(reg.on_new_connection_id(&id_3, 2, 0, &TEST_TOKEN_3).is_ok());
+    assert_eq!(PendingRetirement, reg.registered_ids[0].status);
+    assert_eq!(Reserved, reg.registered_ids[1].status);
+
+    assert_eq!(Some(id_2), reg.consume_preferred_address_id());
+    assert_eq!(InUse, reg.registered_ids[1].status);
+    assert_eq!(None, reg.consume_preferred_address_id());
+}
+
+#[test]
+pub fn preferred_address_id_must_be_unique() {
+    let id_1 = id(b"id01");
+    let mut reg = peer_registry(id_1, Some(TEST_TOKEN_1));
+
+    assert_eq!(
+        Some(InvalidNewConnectionId),
+        reg.register_preferred_address_connection_id(&id_1, &TEST_TOKEN_2)
+            .err()
+    );
+    assert_eq!(
+        Some(InvalidNewConnectionId),
+        reg.register_preferred_address_connection_id(&id(b"id02"), &TEST_TOKEN_1)
+            .err()
+    );
+}
+
 #[test]
 fn error_conversion() {
     //= https://www.rfc-editor.org/rfc/rfc9000#section-19.15
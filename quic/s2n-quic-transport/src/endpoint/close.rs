@@ -7,7 +7,46 @@ use alloc::sync::Arc;
 use core::{
     sync::atomic::{AtomicBool, Ordering},
     task::{Context, Poll, Waker},
+    time::Duration,
 };
+use s2n_quic_core::{
+    application,
+    time::{Clock, Timestamp},
+};
+
+/// Sent by the application to request the endpoint be closed
+#[derive(Debug)]
+pub(crate) struct Request {
+    /// Woken once the endpoint has closed
+    waker: Waker,
+    /// Set if the endpoint should stop accepting new connections
+    shutdown: Option<Shutdown>,
+}
+
+/// Describes how an endpoint stops accepting connections and drains the existing ones
+#[derive(Clone, Copy, Debug)]
+pub struct Shutdown {
+    /// The amount of time existing connections are given to finish
+    pub grace_period: Duration,
+    /// The error used to close any connections still open after the grace period
+    pub error: application::Error,
+}
+
+impl Shutdown {
+    /// Creates a shutdown request with the given grace period
+    pub fn new(grace_period: Duration) -> Self {
+        Self {
+            grace_period,
+            error: application::Error::UNKNOWN,
+        }
+    }
+
+    /// Sets the error used to close any connections still open after the grace period
+    pub fn with_error(mut self, error: application::Error) -> Self {
+        self.error = error;
+        self
+    }
+}
 
 /// Held by library. Used to receive close attempts and track close state.
 #[derive(Debug)]
@@ -18,6 +57,16 @@ pub(crate) struct CloseHandle {
     close_receiver: CloseReceiver,
     /// Track the endpoint open state
     endpoint_state: EndpointState,
+    /// Set once the application requests the endpoint shut down
+    shutdown: Option<ShutdownState>,
+}
+
+#[derive(Debug)]
+struct ShutdownState {
+    /// The time at which any remaining connections are closed
+    deadline: Timestamp,
+    /// The error to close the remaining connections with, or `None` once they have been closed
+    error: Option<application::Error>,
 }
 
 impl CloseHandle {
@@ -26,17 +75,24 @@ impl CloseHandle {
             first_waker: None,
             close_receiver,
             endpoint_state,
+            shutdown: None,
         }
     }
 
     /// Returns `Poll::Ready` if there is interest in closing the endpoint.
-    pub fn poll_interest(&mut self) -> Poll<()> {
+    pub fn poll_interest<C: Clock>(&mut self, clock: &C) -> Poll<()> {
         if self.first_waker.is_some() {
             Poll::Ready(())
         } else {
             match self.close_receiver.try_next() {
-                Ok(Some(waker)) => {
-                    self.first_waker = Some(waker);
+                Ok(Some(request)) => {
+                    if let Some(shutdown) = request.shutdown {
+                        self.shutdown = Some(ShutdownState {
+                            deadline: clock.get_time() + shutdown.grace_period,
+                            error: Some(shutdown.error),
+                        });
+                    }
+                    self.first_waker = Some(request.waker);
                     Poll::Ready(())
                 }
                 _ => Poll::Pending,
@@ -44,6 +100,31 @@ impl CloseHandle {
         }
     }
 
+    /// Returns `true` if the application has requested the endpoint shut down
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutdown.is_some()
+    }
+
+    /// Returns the time at which the remaining connections should be closed
+    pub fn shutdown_deadline(&self) -> Option<Timestamp> {
+        let shutdown = self.shutdown.as_ref()?;
+        shutdown.error?;
+        Some(shutdown.deadline)
+    }
+
+    /// Returns the error to close the remaining connections with if the grace period has elapsed
+    ///
+    /// The error is only returned once.
+    pub fn on_timeout(&mut self, now: Timestamp) -> Option<application::Error> {
+        let shutdown = self.shutdown.as_mut()?;
+
+        if shutdown.deadline.has_elapsed(now) {
+            shutdown.error.take()
+        } else {
+            None
+        }
+    }
+
     /// Marks that the endpoint has finished processing and accepting connections and is
     /// ready to be closed.
     pub fn close(&mut self) {
@@ -52,8 +133,8 @@ impl CloseHandle {
         if let Some(waker) = self.first_waker.take() {
             waker.wake();
         }
-        while let Ok(Some(waker)) = self.close_receiver.try_next() {
-            waker.wake_by_ref();
+        while let Ok(Some(request)) = self.close_receiver.try_next() {
+            request.waker.wake_by_ref();
         }
     }
 }
@@ -101,6 +182,23 @@ impl Closer {
     pub(crate) fn poll_close(
         &mut self,
         context: &mut Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.poll_request(context, None)
+    }
+
+    /// Stops the endpoint from accepting new connections and waits for the existing ones to close
+    pub(crate) fn poll_shutdown(
+        &mut self,
+        context: &mut Context,
+        shutdown: Shutdown,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.poll_request(context, Some(shutdown))
+    }
+
+    fn poll_request(
+        &mut self,
+        context: &mut Context,
+        shutdown: Option<Shutdown>,
     ) -> Poll<Result<(), connection::Error>> {
         if !self.endpoint_state.is_open() {
             return Poll::Ready(Ok(()));
@@ -110,7 +208,11 @@ impl Closer {
             match self.close_sender.poll_ready(context) {
                 Poll::Ready(Ok(())) => {
                     // send a waker to the endpoint, which is woken once the endpoint has closed
-                    match self.close_sender.try_send(context.waker().clone()) {
+                    let request = Request {
+                        waker: context.waker().clone(),
+                        shutdown,
+                    };
+                    match self.close_sender.try_send(request) {
                         Ok(_) => {
                             self.request_sent = true;
                         }
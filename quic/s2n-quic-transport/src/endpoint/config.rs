@@ -5,8 +5,8 @@
 
 use crate::{connection, stream};
 use s2n_quic_core::{
-    crypto::tls, datagram, endpoint, event, packet, path, random, recovery::congestion_controller,
-    stateless_reset,
+    crypto::tls, datagram, early_data, endpoint, event, packet, path, random,
+    recovery::congestion_controller, stateless_reset, token,
 };
 
 /// Configuration parameters for a QUIC endpoint
@@ -26,6 +26,8 @@ pub trait Config: 'static + Send + Sized + core::fmt::Debug {
     type RandomGenerator: random::Generator;
     /// The validation token format
     type TokenFormat: s2n_quic_core::token::Format;
+    /// The store for tokens received in NEW_TOKEN frames
+    type TokenStore: token::Store + Clone;
     /// The endpoint limits
     type EndpointLimits: endpoint::Limiter;
     /// The connection limits
@@ -44,6 +46,10 @@ pub trait Config: 'static + Send + Sized + core::fmt::Debug {
     type PacketInterceptor: packet::interceptor::Interceptor;
     /// The datagram implementation for the endpoint
     type DatagramEndpoint: datagram::Endpoint;
+    /// The early data controller for the endpoint
+    type EarlyDataController: early_data::Controller;
+    /// The preferred address endpoint
+    type PreferredAddressEndpoint: path::preferred_address::Endpoint;
 
     /// The type of the local endpoint
     const ENDPOINT_TYPE: endpoint::Type;
@@ -75,6 +81,9 @@ pub struct Context<'a, Cfg: Config> {
     /// Token generator / validator
     pub token: &'a mut Cfg::TokenFormat,
 
+    /// Store for tokens issued by servers
+    pub token_store: &'a mut Cfg::TokenStore,
+
     /// The connection limits
     pub connection_limits: &'a mut Cfg::ConnectionLimits,
 
@@ -87,4 +96,11 @@ pub struct Context<'a, Cfg: Config> {
     pub packet_interceptor: &'a mut Cfg::PacketInterceptor,
 
     pub datagram: &'a mut Cfg::DatagramEndpoint,
+
+    pub early_data: &'a mut Cfg::EarlyDataController,
+
+    pub preferred_address: &'a mut Cfg::PreferredAddressEndpoint,
+
+    /// The QUIC versions enabled on the endpoint
+    pub quic_versions: &'a s2n_quic_core::version::Preferences,
 }
@@ -0,0 +1,233 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::endpoint;
+use alloc::collections::VecDeque;
+use core::ops::Range;
+use s2n_codec::{Encoder, EncoderBuffer, EncoderValue};
+use s2n_quic_core::{
+    connection,
+    crypto::{CryptoSuite, InitialKey},
+    event, frame,
+    inet::ExplicitCongestionNotification,
+    io::tx,
+    packet::{
+        self,
+        encoding::{PacketEncoder, PacketPayloadEncoder},
+        number::PacketNumberSpace,
+    },
+    path::{self, MINIMUM_MTU},
+    time, transport,
+    varint::VarInt,
+};
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-14.1
+//# A server MUST discard an Initial packet that is carried in a UDP
+//# datagram with a payload that is smaller than the smallest allowed
+//# maximum datagram size of 1200 bytes.
+const MIN_INITIAL_DATAGRAM_LEN: usize = 1200;
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-7.2
+//# This Destination Connection ID MUST be at least 8 bytes in
+//# length.
+const MIN_ORIGINAL_DESTINATION_CONNECTION_ID_LEN: usize = 8;
+
+/// Sends Initial packets containing a CONNECTION_CLOSE frame to refuse new connections
+#[derive(Debug)]
+pub struct Dispatch<Path: path::Handle> {
+    // TODO: Find a better datastructure capable of handling delays in transmission
+    // https://github.com/aws/s2n-quic/issues/280
+    transmissions: VecDeque<Transmission<Path>>,
+}
+
+impl<Path: path::Handle> Default for Dispatch<Path> {
+    fn default() -> Self {
+        Self::new(endpoint::DEFAULT_MAX_PEERS)
+    }
+}
+
+impl<Path: path::Handle> Dispatch<Path> {
+    pub fn new(max_peers: usize) -> Self {
+        Self {
+            transmissions: VecDeque::with_capacity(max_peers),
+        }
+    }
+
+    /// Queues a CONNECTION_CLOSE with the CONNECTION_REFUSED error in response to the Initial
+    /// `packet`
+    ///
+    /// Initial packets which would have been discarded by the server are not answered.
+    pub fn queue<C: CryptoSuite>(
+        &mut self,
+        path_handle: Path,
+        packet: &packet::initial::ProtectedInitial,
+        datagram_len: usize,
+        local_connection_id: connection::LocalId,
+    ) {
+        if datagram_len < MIN_INITIAL_DATAGRAM_LEN
+            || packet.destination_connection_id().len() < MIN_ORIGINAL_DESTINATION_CONNECTION_ID_LEN
+        {
+            return;
+        }
+
+        if let Some(transmission) = Transmission::new::<C>(path_handle, packet, local_connection_id)
+        {
+            self.transmissions.push_back(transmission);
+        }
+    }
+
+    pub fn on_transmit<Tx: tx::Queue<Handle = Path>, Pub: event::EndpointPublisher>(
+        &mut self,
+        queue: &mut Tx,
+        publisher: &mut Pub,
+    ) {
+        while let Some(transmission) = self.transmissions.pop_front() {
+            match queue.push(&transmission) {
+                Ok(tx::Outcome { len, .. }) => {
+                    publisher.on_endpoint_packet_sent(event::builder::EndpointPacketSent {
+                        packet_header: event::builder::PacketHeader::Initial {
+                            number: 0,
+                            version: transmission.version,
+                        },
+                    });
+
+                    publisher.on_endpoint_datagram_sent(event::builder::EndpointDatagramSent {
+                        len: len as u16,
+                        gso_offset: 0,
+                    });
+                }
+                Err(_) => {
+                    self.transmissions.push_front(transmission);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+pub struct Transmission<Path: path::Handle> {
+    path: Path,
+    packet: [u8; MINIMUM_MTU as usize],
+    packet_range: Range<usize>,
+    version: u32,
+}
+
+impl<Path: path::Handle> core::fmt::Debug for Transmission<Path> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Transmission")
+            .field("remote_address", &self.path.remote_address())
+            .field("local_address", &self.path.local_address())
+            .field("packet", &&self.packet[self.packet_range.clone()])
+            .finish()
+    }
+}
+
+impl<Path: path::Handle> Transmission<Path> {
+    pub fn new<C: CryptoSuite>(
+        path: Path,
+        packet: &packet::initial::ProtectedInitial,
+        local_connection_id: connection::LocalId,
+    ) -> Option<Self> {
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-5.2
+        //# Initial packets apply the packet protection process, but use a
+        //# secret derived from the Destination Connection ID field from the
+        //# client's first Initial packet.
+        let (key, header_key) =
+            C::InitialKey::new_server(packet.version, packet.destination_connection_id());
+
+        let packet_number = PacketNumberSpace::Initial.new_packet_number(VarInt::from_u8(0));
+
+        let initial = packet::initial::Initial {
+            version: packet.version,
+            destination_connection_id: packet.source_connection_id(),
+            source_connection_id: local_connection_id.as_bytes(),
+            token: &[][..],
+            packet_number,
+            payload: Payload(transport::Error::CONNECTION_REFUSED.into()),
+        };
+
+        let mut packet_buf = [0u8; MINIMUM_MTU as usize];
+        let buffer = EncoderBuffer::new(&mut packet_buf);
+        let (protected_packet, _remaining) = initial
+            .encode_packet(&key, &header_key, packet_number, None, buffer)
+            .ok()?;
+        let len = protected_packet.len();
+
+        Some(Self {
+            path,
+            packet: packet_buf,
+            packet_range: 0..len,
+            version: packet.version,
+        })
+    }
+}
+
+/// The payload of the Initial packet, which only contains the CONNECTION_CLOSE frame
+struct Payload<'a>(frame::ConnectionClose<'a>);
+
+impl<'a> PacketPayloadEncoder for Payload<'a> {
+    fn encoding_size_hint<E: Encoder>(&mut self, _encoder: &E, minimum_len: usize) -> usize {
+        self.0.encoding_size().max(minimum_len)
+    }
+
+    fn encode(
+        &mut self,
+        buffer: &mut EncoderBuffer,
+        minimum_len: usize,
+        _header_len: usize,
+        _tag_len: usize,
+    ) {
+        buffer.encode(&self.0);
+
+        let len = self.0.encoding_size();
+        if len < minimum_len {
+            buffer.encode(&frame::Padding {
+                length: minimum_len - len,
+            });
+        }
+    }
+}
+
+impl<Path: path::Handle> AsRef<[u8]> for Transmission<Path> {
+    fn as_ref(&self) -> &[u8] {
+        &self.packet[self.packet_range.clone()]
+    }
+}
+
+impl<Path: path::Handle> tx::Message for &Transmission<Path> {
+    type Handle = Path;
+
+    #[inline]
+    fn path_handle(&self) -> &Self::Handle {
+        &self.path
+    }
+
+    #[inline]
+    fn ecn(&mut self) -> ExplicitCongestionNotification {
+        Default::default()
+    }
+
+    #[inline]
+    fn delay(&mut self) -> time::Duration {
+        Default::default()
+    }
+
+    #[inline]
+    fn ipv6_flow_label(&mut self) -> u32 {
+        0
+    }
+
+    #[inline]
+    fn can_gso(&self, segment_len: usize, _segment_count: usize) -> bool {
+        segment_len >= self.as_ref().len()
+    }
+
+    #[inline]
+    fn write_payload(
+        &mut self,
+        mut buffer: tx::PayloadBuffer,
+        _gso_offset: usize,
+    ) -> Result<usize, tx::Error> {
+        buffer.write(self.as_ref())
+    }
+}
@@ -10,7 +10,7 @@ use crate::{
 };
 use core::{
     pin::Pin,
-    task::{Context, Poll, Waker},
+    task::{Context, Poll},
 };
 use futures_channel::mpsc;
 use futures_core::Stream;
@@ -26,9 +26,9 @@ pub(crate) type ConnectorReceiver = mpsc::Receiver<connect::Request>;
 pub(crate) type ConnectorSender = mpsc::Sender<connect::Request>;
 
 /// Held by library. Used to receive close attempts from the application.
-pub(crate) type CloseReceiver = mpsc::Receiver<Waker>;
+pub(crate) type CloseReceiver = mpsc::Receiver<close::Request>;
 /// Held by the application. Used to submit connection close attempts to the library.
-pub(crate) type CloseSender = mpsc::Sender<Waker>;
+pub(crate) type CloseSender = mpsc::Sender<close::Request>;
 
 /// The [`Handle`] allows applications to accept and open QUIC connections on an `Endpoint`.
 #[derive(Debug)]
@@ -52,6 +52,7 @@ impl Handle {
         let handle = Self {
             acceptor: Acceptor {
                 acceptor: acceptor_receiver,
+                closer: closer.clone(),
             },
             connector: Connector {
                 connector: connector_sender,
@@ -70,6 +71,7 @@ impl Handle {
 #[derive(Debug)]
 pub struct Acceptor {
     acceptor: AcceptorReceiver,
+    closer: close::Closer,
 }
 
 impl Acceptor {
@@ -92,6 +94,18 @@ impl Acceptor {
             Poll::Pending => Poll::Pending,
         }
     }
+
+    /// Polls to shut down the endpoint
+    ///
+    /// New connection attempts are rejected while existing connections are given the
+    /// configured grace period to finish before being closed.
+    pub fn poll_shutdown(
+        &mut self,
+        context: &mut Context,
+        shutdown: close::Shutdown,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.closer.poll_shutdown(context, shutdown)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -10,19 +10,25 @@ use crate::{
     },
     endpoint,
     recovery::congestion_controller::{self, Endpoint as _},
-    space::PacketSpaceManager,
+    space::{new_token, PacketSpaceManager, PendingSession},
 };
 use core::convert::TryInto;
 use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
-    crypto::{tls, tls::Endpoint as TLSEndpoint, CryptoSuite, InitialKey},
+    crypto::{tls, CryptoSuite, InitialKey, OneRttKey as _},
     datagram::{Endpoint, PreConnectionInfo},
-    event::{self, supervisor, ConnectionPublisher, IntoEvent, Subscriber as _},
+    event::{self, supervisor, IntoEvent, Subscriber as _},
     inet::{datagram, DatagramInfo},
     packet::initial::ProtectedInitial,
-    path::Handle as _,
+    path::{
+        preferred_address::{self, Endpoint as _},
+        Handle as _,
+    },
     stateless_reset::token::Generator as _,
-    transport::{self, parameters::ServerTransportParameters},
+    transport::{
+        self,
+        parameters::{PreferredAddress, ReservedParameter, ServerTransportParameters},
+    },
 };
 
 impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
@@ -106,6 +112,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
         //# a change to the keys used to protect the Initial packet.
         let (initial_key, initial_header_key) =
             <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_server(
+                packet.version,
                 datagram.destination_connection_id.as_bytes(),
             );
 
@@ -113,6 +120,8 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
         let packet = packet.unprotect(&initial_header_key, largest_packet_number)?;
         let packet = packet.decrypt(&initial_key)?;
 
+        let quic_versions = *self.config.context().quic_versions;
+
         // TODO handle token with stateless retry
 
         let internal_connection_id = self.connection_id_generator.generate_id();
@@ -130,7 +139,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             .stateless_reset_token_generator
             .generate(initial_connection_id.as_bytes());
 
-        let local_id_registry = self.connection_id_mapper.create_local_id_registry(
+        let mut local_id_registry = self.connection_id_mapper.create_local_id_registry(
             internal_connection_id,
             &initial_connection_id,
             initial_connection_id_expiration_time,
@@ -151,7 +160,10 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             .config
             .context()
             .connection_limits
-            .on_connection(&LimitsInfo::new(&remote_address));
+            .on_connection(&LimitsInfo::new(&remote_address))
+            .with_path_identifier_support(
+                <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey::SUPPORTS_PATH_IDENTIFIER,
+            );
 
         transport_parameters.load_limits(&limits);
 
@@ -201,6 +213,43 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
         .try_into()
         .unwrap();
 
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+        //# A server that chooses a zero-length connection ID MUST NOT provide a
+        //# preferred address.
+        if !initial_connection_id.is_empty() {
+            let endpoint_context = self.config.context();
+
+            if let Some(addresses) = endpoint_context
+                .preferred_address
+                .on_connection(&preferred_address::ConnectionInfo::new(&remote_address))
+            {
+                let preferred_connection_id = endpoint_context
+                    .connection_id_format
+                    .generate(&ConnectionInfo::new(&remote_address));
+                let stateless_reset_token = endpoint_context
+                    .stateless_reset_token_generator
+                    .generate(preferred_connection_id.as_bytes());
+
+                // The preferred address is only advertised if its connection ID can be
+                // used to route packets to this connection
+                if local_id_registry
+                    .register_preferred_address_connection_id(
+                        &preferred_connection_id,
+                        initial_connection_id_expiration_time,
+                        stateless_reset_token,
+                    )
+                    .is_ok()
+                {
+                    transport_parameters.preferred_address = Some(PreferredAddress {
+                        ipv4_address: addresses.ipv4,
+                        ipv6_address: addresses.ipv6,
+                        connection_id: preferred_connection_id.into(),
+                        stateless_reset_token,
+                    });
+                }
+            }
+        }
+
         let endpoint_context = self.config.context();
 
         transport_parameters.max_datagram_frame_size = endpoint_context
@@ -209,17 +258,16 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             .try_into()
             .expect("Failed to convert max_datagram_frame_size");
 
-        let tls_session = endpoint_context
-            .tls
-            .new_server_session(&transport_parameters);
+        if limits.grease_transport_parameters() {
+            transport_parameters.reserved_parameter =
+                Some(ReservedParameter::random(endpoint_context.random_generator));
+        }
 
         let path_info = congestion_controller::PathInfo::new(&remote_address);
         let congestion_controller = endpoint_context
             .congestion_controller
             .new_congestion_controller(path_info);
 
-        let quic_version = packet.version;
-
         let meta = event::builder::ConnectionMeta {
             endpoint_type: Config::ENDPOINT_TYPE,
             id: internal_connection_id.into(),
@@ -240,20 +288,30 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
 
         let mut publisher = event::ConnectionPublisherSubscriber::new(
             meta,
-            quic_version,
+            packet.version,
             endpoint_context.event_subscriber,
             &mut event_context,
         );
 
-        let space_manager = PacketSpaceManager::new(
-            original_destination_connection_id,
-            tls_session,
+        // The TLS session is created once the whole ClientHello has been received, which
+        // might take more than one Initial packet
+        let pending_session = PendingSession {
+            initial_cid: original_destination_connection_id,
+            initial_key_id: datagram.destination_connection_id,
+            transport_parameters,
+        };
+
+        let mut space_manager = PacketSpaceManager::new_server(
+            pending_session,
             initial_key,
             initial_header_key,
             datagram.timestamp,
             &mut publisher,
         );
 
+        // The token is generated once the handshake confirms the address of the client
+        space_manager.set_new_token(new_token::Manager::sender());
+
         let max_mtu = self.max_mtu;
         let connection_parameters = connection::Parameters {
             internal_connection_id,
@@ -269,13 +327,16 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             path_handle: header.path,
             congestion_controller,
             timestamp: datagram.timestamp,
-            quic_version,
+            quic_version: packet.version,
+            quic_versions,
             limits,
             max_mtu,
+            binder: self.binder.clone(),
             event_context,
             supervisor_context: &supervisor_context,
             event_subscriber: endpoint_context.event_subscriber,
             datagram_endpoint: endpoint_context.datagram,
+            random_generator: endpoint_context.random_generator,
         };
 
         let mut connection = <Config as endpoint::Config>::Connection::new(connection_parameters)?;
@@ -289,6 +350,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
                     endpoint_context.congestion_controller,
                     endpoint_context.path_migration,
                     max_mtu,
+                    endpoint_context.random_generator,
                     endpoint_context.event_subscriber,
                 );
 
@@ -348,6 +410,14 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
                         }
                     })?;
 
+                connection.on_client_hello(
+                    datagram,
+                    endpoint_context.tls,
+                    endpoint_context.early_data,
+                    endpoint_context.event_subscriber,
+                    endpoint_context.datagram,
+                )?;
+
                 connection.handle_remaining_packets(
                     &header.path,
                     datagram,
@@ -13,7 +13,7 @@ use crate::{
     endpoint,
     endpoint::close::CloseHandle,
     recovery::congestion_controller::{self, Endpoint as _},
-    space::PacketSpaceManager,
+    space::{new_token, PacketSpaceManager},
     wakeup_queue::WakeupQueue,
 };
 use alloc::collections::VecDeque;
@@ -27,7 +27,7 @@ use s2n_quic_core::{
         id::{ConnectionInfo, Generator},
         InitialId, LocalId, PeerId,
     },
-    crypto::{tls, tls::Endpoint as _, CryptoSuite, InitialKey},
+    crypto::{tls, tls::Endpoint as _, CryptoSuite, InitialKey, OneRttKey as _},
     datagram::{Endpoint as DatagramEndpoint, PreConnectionInfo},
     endpoint::{limits::Outcome, Limiter as _},
     event::{
@@ -35,19 +35,20 @@ use s2n_quic_core::{
     },
     inet::{datagram, DatagramInfo},
     io::{rx, tx},
-    packet::{initial::ProtectedInitial, interceptor::Interceptor, ProtectedPacket},
+    packet::{initial::ProtectedInitial, interceptor::Interceptor, ProtectedPacket, QuicBit},
     path,
     path::{Handle as _, MaxMtu},
     random::Generator as _,
     stateless_reset::token::{Generator as _, LEN as StatelessResetTokenLen},
     time::{Clock, Timestamp},
-    token::{self, Format},
-    transport::parameters::ClientTransportParameters,
+    token::{self, Format, Store as _},
+    transport::parameters::{ClientTransportParameters, ReservedParameter, VersionInformation},
 };
 
 pub mod close;
 mod config;
 pub mod connect;
+mod connection_refused;
 pub mod handle;
 mod initial;
 mod packet_buffer;
@@ -84,9 +85,12 @@ pub struct Endpoint<Cfg: Config> {
     version_negotiator: version::Negotiator<Cfg>,
     retry_dispatch: retry::Dispatch<Cfg::PathHandle>,
     stateless_reset_dispatch: stateless_reset::Dispatch<Cfg::PathHandle>,
+    connection_refused_dispatch: connection_refused::Dispatch<Cfg::PathHandle>,
     close_packet_buffer: packet_buffer::Buffer,
     /// The largest maximum transmission unit (MTU) that can be sent on a path
     max_mtu: MaxMtu,
+    /// Binds sockets to new local addresses, if the IO provider supports it
+    binder: Option<connection::Binder<Cfg>>,
 }
 
 impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
@@ -166,6 +170,8 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
             self.retry_dispatch.on_transmit(queue, &mut publisher);
             self.stateless_reset_dispatch
                 .on_transmit(queue, &mut publisher);
+            self.connection_refused_dispatch
+                .on_transmit(queue, &mut publisher);
         }
     }
 
@@ -174,7 +180,7 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
         cx: &mut task::Context<'_>,
         clock: &C,
     ) -> Poll<Result<usize, s2n_quic_core::endpoint::CloseError>> {
-        if self.close_handle.poll_interest().is_ready() // poll for close interest
+        if self.close_handle.poll_interest(clock).is_ready() // poll for close interest
             && self.connections.is_empty() // wait for all connections to close gracefully
             && self.connections.is_open()
         {
@@ -196,6 +202,7 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
         let mut now: Option<Timestamp> = None;
         let mut wakeup_count = self.dequeued_wakeups.len();
         let close_packet_buffer = &mut self.close_packet_buffer;
+        let max_mtu = self.max_mtu;
         let endpoint_context = self.config.context();
 
         for internal_id in self.dequeued_wakeups.drain(..) {
@@ -211,6 +218,9 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 
                 if let Err(error) = conn.on_wakeup(
                     timestamp,
+                    endpoint_context.congestion_controller,
+                    max_mtu,
+                    endpoint_context.random_generator,
                     endpoint_context.event_subscriber,
                     endpoint_context.datagram,
                 ) {
@@ -258,7 +268,13 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 
     #[inline]
     fn timeout(&self) -> Option<Timestamp> {
-        self.connections.next_expiration()
+        let timeout = self.connections.next_expiration();
+
+        // wake up to close the remaining connections once the shutdown grace period has elapsed
+        match (timeout, self.close_handle.shutdown_deadline()) {
+            (Some(timeout), Some(deadline)) => Some(timeout.min(deadline)),
+            (timeout, deadline) => timeout.or(deadline),
+        }
     }
 
     #[inline]
@@ -266,6 +282,49 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
         self.max_mtu = max_mtu
     }
 
+    #[inline]
+    fn set_binder(&mut self, binder: connection::Binder<Cfg>) {
+        self.binder = Some(binder);
+    }
+
+    fn enable_packet_too_big(&mut self) {
+        let random_generator = self.config.context().random_generator;
+        self.connection_id_mapper
+            .enable_peer_id_map(random_generator);
+    }
+
+    fn on_packet_too_big<C: Clock>(
+        &mut self,
+        remote_address: &path::RemoteAddress,
+        quoted_packet: &[u8],
+        mtu: u16,
+        clock: &C,
+    ) {
+        // The quoted packet contains the connection ID of the peer, which is only mapped to
+        // the connection which is using it
+        let internal_id = if let Some(internal_id) = self
+            .connection_id_mapper
+            .lookup_internal_connection_id_by_quoted_packet(quoted_packet)
+        {
+            internal_id
+        } else {
+            return;
+        };
+
+        let timestamp = clock.get_time();
+        let endpoint_context = self.config.context();
+
+        self.connections.with_connection(internal_id, |conn| {
+            conn.on_packet_too_big(
+                remote_address,
+                quoted_packet,
+                mtu,
+                timestamp,
+                endpoint_context.event_subscriber,
+            );
+        });
+    }
+
     #[inline]
     fn subscriber(&mut self) -> &mut Self::Subscriber {
         self.config.context().event_subscriber
@@ -302,6 +361,14 @@ impl<Cfg: Config> Endpoint<Cfg> {
         let connection_id_mapper =
             ConnectionIdMapper::new(config.context().random_generator, Cfg::ENDPOINT_TYPE);
 
+        let version_negotiator =
+            version::Negotiator::new(DEFAULT_MAX_PEERS, *config.context().quic_versions);
+
+        let stateless_reset_dispatch = stateless_reset::Dispatch::new(
+            DEFAULT_MAX_PEERS,
+            config.context().endpoint_limits.stateless_reset_limit(),
+        );
+
         let endpoint = Self {
             config,
             connections: ConnectionContainer::new(acceptor_sender, connector_receiver),
@@ -310,11 +377,13 @@ impl<Cfg: Config> Endpoint<Cfg> {
             wakeup_queue: WakeupQueue::new(),
             close_handle,
             dequeued_wakeups: VecDeque::new(),
-            version_negotiator: version::Negotiator::default(),
+            version_negotiator,
             retry_dispatch: retry::Dispatch::default(),
-            stateless_reset_dispatch: stateless_reset::Dispatch::default(),
+            stateless_reset_dispatch,
+            connection_refused_dispatch: connection_refused::Dispatch::default(),
             close_packet_buffer: Default::default(),
             max_mtu: Default::default(),
+            binder: None,
         };
 
         (endpoint, handle)
@@ -327,6 +396,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
         packet: &ProtectedInitial,
         payload_len: usize,
         timestamp: Timestamp,
+        address_validated: bool,
     ) -> Option<()> {
         if !self.connections.can_accept() {
             return None;
@@ -354,6 +424,10 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
         match outcome {
             Outcome::Allow { .. } => Some(()),
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+            //# If the validation succeeds, the server SHOULD then allow
+            //# the handshake to proceed.
+            Outcome::Retry { .. } if address_validated => Some(()),
             Outcome::Retry { .. } => {
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.2
                 //# A server can also use a Retry packet to defer the state and
@@ -383,11 +457,18 @@ impl<Cfg: Config> Endpoint<Cfg> {
             }
             Outcome::Close { .. } => {
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-5.2.2
-                //= type=TODO
-                //= tracking-issue=270
                 //# If a server refuses to accept a new connection, it SHOULD send an
                 //# Initial packet containing a CONNECTION_CLOSE frame with error code
                 //# CONNECTION_REFUSED.
+                let connection_info = ConnectionInfo::new(&remote_address);
+                let local_connection_id = context.connection_id_format.generate(&connection_info);
+                self.connection_refused_dispatch
+                    .queue::<<<Cfg as Config>::TLSEndpoint as tls::Endpoint>::Session>(
+                    header.path,
+                    packet,
+                    payload_len,
+                    local_connection_id,
+                );
 
                 publisher.on_endpoint_datagram_dropped(event::builder::EndpointDatagramDropped {
                     len: payload_len as u16,
@@ -497,7 +578,13 @@ impl<Cfg: Config> Endpoint<Cfg> {
         // length requirements for connection IDs.
         if self
             .version_negotiator
-            .on_packet(&header.path, payload_len, &packet, &mut publisher)
+            .on_packet(
+                &header.path,
+                payload_len,
+                &packet,
+                endpoint_context.random_generator,
+                &mut publisher,
+            )
             .is_err()
         {
             publisher.on_endpoint_datagram_dropped(event::builder::EndpointDatagramDropped {
@@ -557,6 +644,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
                         endpoint_context.congestion_controller,
                         endpoint_context.path_migration,
                         max_mtu,
+                        endpoint_context.random_generator,
                         endpoint_context.event_subscriber,
                     )
                     .map_err(|datagram_drop_reason| {
@@ -644,6 +732,25 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     }
                 }
 
+                // The rest of the ClientHello might have arrived in the packet
+                if let Err(err) = conn.on_client_hello(
+                    datagram,
+                    endpoint_context.tls,
+                    endpoint_context.early_data,
+                    endpoint_context.event_subscriber,
+                    endpoint_context.datagram,
+                ) {
+                    conn.close(
+                        err,
+                        endpoint_context.connection_close_formatter,
+                        close_packet_buffer,
+                        datagram.timestamp,
+                        endpoint_context.event_subscriber,
+                        endpoint_context.packet_interceptor,
+                    );
+                    return Err(());
+                }
+
                 if let Err(err) = conn.handle_remaining_packets(
                     &header.path,
                     datagram,
@@ -666,6 +773,9 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     return Err(());
                 }
 
+                // Issue an address validation token if the datagram confirmed the handshake
+                conn.on_new_token(endpoint_context.token, endpoint_context.random_generator);
+
                 Ok(())
             });
 
@@ -676,7 +786,31 @@ impl<Cfg: Config> Endpoint<Cfg> {
             return;
         }
 
+        let quic_bit = packet.quic_bit();
+
         match (Cfg::ENDPOINT_TYPE, packet) {
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
+            //# Packets containing a zero
+            //# value for this bit are not valid packets in this version and MUST
+            //# be discarded.
+
+            //= https://www.rfc-editor.org/rfc/rfc9287#section-3.1
+            //# A client MAY also clear the QUIC Bit in Initial packets that are sent
+            //# to establish a new connection.  A client can only clear the QUIC Bit
+            //# if the packet includes a token provided by the server in a NEW_TOKEN
+            //# frame on a connection where the server also included the
+            //# grease_quic_bit transport parameter.
+            //
+            // Address tokens don't record whether the `grease_quic_bit` transport parameter was
+            // negotiated so new connections require the QUIC bit to be set.
+            (s2n_quic_core::endpoint::Type::Server, ProtectedPacket::Initial(_))
+                if quic_bit == QuicBit::Zero =>
+            {
+                publisher.on_endpoint_datagram_dropped(event::builder::EndpointDatagramDropped {
+                    len: payload_len as u16,
+                    reason: event::builder::DatagramDropReason::QuicBitCleared,
+                });
+            }
             (s2n_quic_core::endpoint::Type::Server, ProtectedPacket::Initial(packet)) => {
                 let source_connection_id =
                     match connection::PeerId::try_from_bytes(packet.source_connection_id()) {
@@ -693,6 +827,33 @@ impl<Cfg: Config> Endpoint<Cfg> {
                         }
                     };
 
+                // the application is shutting down the endpoint so new connections are refused
+                if self.close_handle.is_shutting_down() {
+                    //= https://www.rfc-editor.org/rfc/rfc9000#section-5.2.2
+                    //# If a server refuses to accept a new connection, it SHOULD send an
+                    //# Initial packet containing a CONNECTION_CLOSE frame with error code
+                    //# CONNECTION_REFUSED.
+                    let connection_info = ConnectionInfo::new(&remote_address);
+                    let local_connection_id = endpoint_context
+                        .connection_id_format
+                        .generate(&connection_info);
+                    self.connection_refused_dispatch
+                        .queue::<<<Cfg as Config>::TLSEndpoint as tls::Endpoint>::Session>(
+                        header.path,
+                        &packet,
+                        payload_len,
+                        local_connection_id,
+                    );
+
+                    publisher.on_endpoint_datagram_dropped(
+                        event::builder::EndpointDatagramDropped {
+                            len: payload_len as u16,
+                            reason: event::builder::DatagramDropReason::RejectedConnectionAttempt,
+                        },
+                    );
+                    return;
+                }
+
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1
                 //= type=TODO
                 //= tracking-issue=140
@@ -704,7 +865,17 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 //# In response to processing an Initial packet containing a token that
                 //# was provided in a Retry packet, a server cannot send another Retry
                 //# packet; it can only refuse the connection or permit it to proceed.
-                let retry_token_dcid = if !packet.token().is_empty() {
+                let token_source = if packet.token().is_empty() {
+                    None
+                } else {
+                    Some(endpoint_context.token.token_source(packet.token()))
+                };
+
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+                //# When a server receives an Initial packet with an address validation
+                //# token, it MUST attempt to validate the token, unless it has already
+                //# completed address validation.
+                let retry_token_dcid = if let Some(token::Source::RetryPacket) = token_source {
                     let mut context = token::Context::new(
                         &remote_address,
                         &source_connection_id,
@@ -716,13 +887,6 @@ impl<Cfg: Config> Endpoint<Cfg> {
                         .validate_token(&mut context, packet.token());
 
                     if outcome.is_none() {
-                        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
-                        //= type=TODO
-                        //= tracking-issue=344
-                        //# If the token is invalid, then the
-                        //# server SHOULD proceed as if the client did not have a validated
-                        //# address, including potentially sending a Retry packet.
-
                         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.2
                         //= type=TODO
                         //= tracking-issue=344
@@ -747,12 +911,35 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     //# the handshake to proceed.
                     outcome
                 } else {
+                    let address_validated = token_source.is_some() && {
+                        let mut context = token::Context::new(
+                            &remote_address,
+                            &source_connection_id,
+                            endpoint_context.random_generator,
+                        );
+
+                        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+                        //# If the token is invalid, then the
+                        //# server SHOULD proceed as if the client did not have a validated
+                        //# address, including potentially sending a Retry packet.
+                        endpoint_context
+                            .token
+                            .validate_new_token(&mut context, packet.token())
+                            .is_some()
+                    };
+
                     //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.2
                     //# Upon receiving the client's Initial packet, the server can request
                     //# address validation by sending a Retry packet (Section 17.2.5)
                     //# containing a token.
                     if self
-                        .connection_allowed(header, &packet, payload_len, timestamp)
+                        .connection_allowed(
+                            header,
+                            &packet,
+                            payload_len,
+                            timestamp,
+                            address_validated,
+                        )
                         .is_none()
                     {
                         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.1
@@ -854,6 +1041,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
             token,
             max_tag_length,
             triggering_packet_len,
+            datagram.timestamp,
             self.config.context().random_generator,
         );
     }
@@ -956,6 +1144,22 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     panic!("Generated connection ID was already in use");
                 }
             });
+
+        // close any connections which didn't finish within the shutdown grace period
+        if let Some(error) = self.close_handle.on_timeout(timestamp) {
+            let error = connection::Error::application(error);
+
+            self.connections.for_each(|conn| {
+                conn.close(
+                    error,
+                    endpoint_context.connection_close_formatter,
+                    close_packet_buffer,
+                    timestamp,
+                    endpoint_context.event_subscriber,
+                    endpoint_context.packet_interceptor,
+                );
+            });
+        }
     }
 
     fn create_client_connection(
@@ -1037,10 +1241,10 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 .new_congestion_controller(path_info)
         };
 
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-15
-        //# This version of the specification is identified by the number
-        //# 0x00000001.
-        let quic_version = 0x00000001;
+        // The first Initial packet uses the original version. The server can choose to switch the
+        // connection to any of the other enabled versions that are compatible.
+        let quic_versions = *endpoint_context.quic_versions;
+        let quic_version = quic_versions.original();
 
         let meta = event::builder::ConnectionMeta {
             endpoint_type: Cfg::ENDPOINT_TYPE,
@@ -1066,13 +1270,25 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
         let mut transport_parameters = ClientTransportParameters {
             initial_source_connection_id: Some(local_connection_id.into()),
+            version_information: Some(VersionInformation::new(
+                quic_version,
+                quic_versions.versions(),
+            )),
             ..Default::default()
         };
         let limits = endpoint_context
             .connection_limits
-            .on_connection(&LimitsInfo::new(&remote_address));
+            .on_connection(&LimitsInfo::new(&remote_address))
+            .with_path_identifier_support(
+                <<Cfg::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey::SUPPORTS_PATH_IDENTIFIER,
+            );
         transport_parameters.load_limits(&limits);
 
+        if limits.grease_transport_parameters() {
+            transport_parameters.reserved_parameter =
+                Some(ReservedParameter::random(endpoint_context.random_generator));
+        }
+
         transport_parameters.max_datagram_frame_size = endpoint_context
             .datagram
             .max_datagram_frame_size(&PreConnectionInfo::new())
@@ -1094,17 +1310,16 @@ impl<Cfg: Config> Endpoint<Cfg> {
         // protection keys.
         let (initial_key, initial_header_key) =
             <<Cfg::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_client(
+                quic_version,
                 original_destination_connection_id.as_bytes(),
             );
+        // TODO should SNI be optional? rustls expects a SNI but other tls providers dont seem
+        // to require this value.
+        let server_name = hostname.expect("application should provide a valid server name");
         let tls_session = endpoint_context
             .tls
-            // TODO should SNI be optional? rustls expects a SNI but other tls providers dont seem
-            // to require this value.
-            .new_client_session(
-                &transport_parameters,
-                hostname.expect("application should provide a valid server name"),
-            );
-        let space_manager = PacketSpaceManager::new(
+            .new_client_session(&transport_parameters, server_name.clone());
+        let mut space_manager = PacketSpaceManager::new(
             original_destination_connection_id,
             tls_session,
             initial_key,
@@ -1113,6 +1328,27 @@ impl<Cfg: Config> Endpoint<Cfg> {
             &mut publisher,
         );
 
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# When connecting to a server for
+        //# which the client retains an applicable and unused token, it SHOULD
+        //# include that token in the Token field of its Initial packet.
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# A client MUST NOT include
+        //# a token that is not applicable to the server that it is connecting
+        //# to, unless the client has the knowledge that the server that issued
+        //# the token and the server the client is connecting to are jointly
+        //# managing the tokens.
+        if let Some(token) = endpoint_context.token_store.take(&server_name) {
+            if let Some((initial, _)) = space_manager.initial_mut() {
+                initial.set_token(&token);
+            }
+        }
+        space_manager.set_new_token(new_token::Manager::receiver(
+            server_name,
+            endpoint_context.token_store.clone(),
+        ));
+
         let wakeup_handle = self
             .wakeup_queue
             .create_wakeup_handle(internal_connection_id);
@@ -1134,12 +1370,15 @@ impl<Cfg: Config> Endpoint<Cfg> {
             congestion_controller,
             timestamp,
             quic_version,
+            quic_versions,
             limits,
             max_mtu: self.max_mtu,
+            binder: self.binder.clone(),
             event_context,
             supervisor_context: &supervisor_context,
             event_subscriber: endpoint_context.event_subscriber,
             datagram_endpoint: endpoint_context.datagram,
+            random_generator: endpoint_context.random_generator,
         };
         let connection = <Cfg as crate::endpoint::Config>::Connection::new(connection_parameters)?;
         self.connections
@@ -1168,6 +1407,7 @@ pub mod testing {
         type StatelessResetTokenGenerator = stateless_reset::token::testing::Generator;
         type RandomGenerator = random::testing::Generator;
         type TokenFormat = s2n_quic_core::token::testing::Format;
+        type TokenStore = s2n_quic_core::token::Disabled;
         type ConnectionLimits = s2n_quic_core::connection::limits::Limits;
         type StreamManager = crate::stream::DefaultStreamManager;
         type ConnectionCloseFormatter = s2n_quic_core::connection::close::Development;
@@ -1175,6 +1415,8 @@ pub mod testing {
         type PathMigrationValidator = path::migration::default::Validator;
         type PacketInterceptor = s2n_quic_core::packet::interceptor::Disabled;
         type DatagramEndpoint = s2n_quic_core::datagram::Disabled;
+        type EarlyDataController = s2n_quic_core::early_data::default::Controller;
+        type PreferredAddressEndpoint = path::preferred_address::Disabled;
 
         fn context(&mut self) -> super::Context<Self> {
             todo!()
@@ -1198,6 +1440,7 @@ pub mod testing {
         type StatelessResetTokenGenerator = stateless_reset::token::testing::Generator;
         type RandomGenerator = random::testing::Generator;
         type TokenFormat = s2n_quic_core::token::testing::Format;
+        type TokenStore = s2n_quic_core::token::Disabled;
         type ConnectionLimits = s2n_quic_core::connection::limits::Limits;
         type StreamManager = crate::stream::DefaultStreamManager;
         type ConnectionCloseFormatter = s2n_quic_core::connection::close::Development;
@@ -1205,6 +1448,8 @@ pub mod testing {
         type PathMigrationValidator = path::migration::default::Validator;
         type PacketInterceptor = s2n_quic_core::packet::interceptor::Disabled;
         type DatagramEndpoint = s2n_quic_core::datagram::Disabled;
+        type EarlyDataController = s2n_quic_core::early_data::default::Controller;
+        type PreferredAddressEndpoint = path::preferred_address::Disabled;
 
         fn context(&mut self) -> super::Context<Self> {
             todo!()
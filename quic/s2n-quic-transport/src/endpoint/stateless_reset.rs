@@ -4,25 +4,42 @@
 use crate::endpoint;
 use alloc::collections::VecDeque;
 use s2n_quic_core::{
-    event, inet::ExplicitCongestionNotification, io::tx, packet, path, path::MINIMUM_MTU, random,
-    stateless_reset, time,
+    endpoint::limits::StatelessResetLimit,
+    event,
+    inet::ExplicitCongestionNotification,
+    io::tx,
+    packet, path,
+    path::MINIMUM_MTU,
+    random, stateless_reset,
+    time::{self, Timestamp},
 };
 
 #[derive(Debug)]
 pub struct Dispatch<Path: path::Handle> {
     transmissions: VecDeque<Transmission<Path>>,
+    max_peers: usize,
+    /// The limit on the number of stateless resets queued in each period
+    rate_limit: StatelessResetLimit,
+    /// The end of the current rate limiting period
+    period_end: Option<Timestamp>,
+    /// The number of stateless resets queued in the current rate limiting period
+    period_count: usize,
 }
 
 impl<Path: path::Handle> Default for Dispatch<Path> {
     fn default() -> Self {
-        Self::new(endpoint::DEFAULT_MAX_PEERS)
+        Self::new(endpoint::DEFAULT_MAX_PEERS, StatelessResetLimit::default())
     }
 }
 
 impl<Path: path::Handle> Dispatch<Path> {
-    pub fn new(max_peers: usize) -> Self {
+    pub fn new(max_peers: usize, rate_limit: StatelessResetLimit) -> Self {
         Self {
             transmissions: VecDeque::with_capacity(max_peers),
+            max_peers,
+            rate_limit,
+            period_end: None,
+            period_count: 0,
         }
     }
 
@@ -32,8 +49,24 @@ impl<Path: path::Handle> Dispatch<Path> {
         token: stateless_reset::Token,
         max_tag_len: usize,
         triggering_packet_len: usize,
+        timestamp: Timestamp,
         random_generator: &mut dyn random::Generator,
     ) {
+        // drop the reset if we're at capacity
+        if self.transmissions.len() >= self.max_peers {
+            return;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-10.3.3
+        //# An endpoint can remember the number of Stateless Resets that it has
+        //# sent and stop generating new Stateless Resets once a limit is
+        //# reached.
+        if !self.on_rate_limit(timestamp) {
+            return;
+        }
+
+        // Amplification and looping are prevented by `encode_packet`, which only produces a
+        // stateless reset that is smaller than the triggering packet
         if let Some(transmission) = Transmission::new(
             path,
             token,
@@ -45,6 +78,24 @@ impl<Path: path::Handle> Dispatch<Path> {
         }
     }
 
+    /// Returns `true` if another stateless reset may be sent in the current period
+    fn on_rate_limit(&mut self, timestamp: Timestamp) -> bool {
+        match self.period_end {
+            Some(period_end) if period_end > timestamp => {}
+            _ => {
+                self.period_end = Some(timestamp + self.rate_limit.period);
+                self.period_count = 0;
+            }
+        }
+
+        if self.period_count >= self.rate_limit.max_resets {
+            return false;
+        }
+
+        self.period_count += 1;
+        true
+    }
+
     pub fn on_transmit<Tx: tx::Queue<Handle = Path>, Pub: event::EndpointPublisher>(
         &mut self,
         queue: &mut Tx,
@@ -157,3 +208,83 @@ impl<Path: path::Handle> tx::Message for &Transmission<Path> {
         buffer.write(self.as_ref())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{
+        path::RemoteAddress,
+        stateless_reset::token::testing::TEST_TOKEN_1,
+        time::{Clock, Duration, NoopClock},
+    };
+
+    fn queue(dispatch: &mut Dispatch<RemoteAddress>, timestamp: Timestamp) {
+        dispatch.queue(
+            RemoteAddress::default(),
+            TEST_TOKEN_1,
+            16,
+            MINIMUM_MTU as usize,
+            timestamp,
+            &mut random::testing::Generator(123),
+        );
+    }
+
+    #[test]
+    fn queue_bound_test() {
+        let rate_limit = StatelessResetLimit::new(10, Duration::from_secs(1));
+        let mut dispatch = Dispatch::new(2, rate_limit);
+        let now = NoopClock.get_time();
+
+        for _ in 0..5 {
+            queue(&mut dispatch, now);
+        }
+
+        assert_eq!(
+            dispatch.transmissions.len(),
+            2,
+            "the number of queued resets should not exceed max_peers"
+        );
+        assert_eq!(
+            dispatch.period_count, 2,
+            "resets dropped because the queue is full should not count towards the rate limit"
+        );
+
+        // transmitting the resets frees up space in the queue within the same period
+        dispatch.transmissions.clear();
+
+        for _ in 0..5 {
+            queue(&mut dispatch, now);
+        }
+        assert_eq!(dispatch.transmissions.len(), 2);
+    }
+
+    #[test]
+    fn rate_limit_test() {
+        let period = Duration::from_millis(100);
+        let rate_limit = StatelessResetLimit::new(3, period);
+        let mut dispatch = Dispatch::new(10, rate_limit);
+        let now = NoopClock.get_time();
+
+        for _ in 0..5 {
+            queue(&mut dispatch, now);
+        }
+
+        assert_eq!(
+            dispatch.transmissions.len(),
+            3,
+            "the number of resets in a period should not exceed the rate limit"
+        );
+
+        // transmitting the resets frees up space in the queue, but not the rate limit
+        dispatch.transmissions.clear();
+
+        queue(&mut dispatch, now + period / 2);
+        assert!(dispatch.transmissions.is_empty());
+
+        // the limit is reset in the next period
+        for _ in 0..5 {
+            queue(&mut dispatch, now + period);
+        }
+        assert_eq!(dispatch.transmissions.len(), 3);
+    }
+}
@@ -12,34 +12,30 @@ use s2n_quic_core::{
     packet,
     packet::ProtectedPacket,
     path::{self, MINIMUM_MTU},
+    random, version,
+    version::Preferences,
 };
 
 #[derive(Debug)]
 pub struct Negotiator<Config: endpoint::Config> {
     transmissions: VecDeque<Transmission<Config::PathHandle>>,
     max_peers: usize,
+    preferences: Preferences,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct Error;
 
-const SUPPORTED_VERSIONS: &[u32] = &[
-    0x1, // Draft 34 / Version 1 (https://github.com/quicwg/base-drafts/wiki/21st-Implementation-Draft)
-];
-
 macro_rules! is_supported {
-    ($packet:ident, $publisher:ident) => {{
-        let supported = SUPPORTED_VERSIONS
-            .iter()
-            .cloned()
-            .any(|v| v == $packet.version);
+    ($self:ident, $packet:ident, $publisher:ident) => {{
+        let supported = $self.preferences.contains($packet.version);
 
         if supported {
             //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.1
             //# Upon receiving a client initial with a supported version, the
             //# server logs this event with server_versions and chosen_version set
             $publisher.on_version_information(event::builder::VersionInformation {
-                server_versions: &SUPPORTED_VERSIONS,
+                server_versions: $self.preferences.versions(),
                 client_versions: &[],
                 chosen_version: Some($packet.version),
             });
@@ -51,7 +47,7 @@ macro_rules! is_supported {
             //# client's attempted version.  The absence of chosen_version implies
             //# no overlap was found.
             $publisher.on_version_information(event::builder::VersionInformation {
-                server_versions: &SUPPORTED_VERSIONS,
+                server_versions: $self.preferences.versions(),
                 client_versions: &[$packet.version],
                 chosen_version: None,
             });
@@ -63,12 +59,12 @@ macro_rules! is_supported {
 
 impl<Config: endpoint::Config> Default for Negotiator<Config> {
     fn default() -> Self {
-        Self::new(endpoint::DEFAULT_MAX_PEERS)
+        Self::new(endpoint::DEFAULT_MAX_PEERS, Preferences::default())
     }
 }
 
 impl<Config: endpoint::Config> Negotiator<Config> {
-    pub fn new(max_peers: usize) -> Self {
+    pub fn new(max_peers: usize, preferences: Preferences) -> Self {
         Self {
             transmissions: if Config::ENDPOINT_TYPE.is_server() {
                 VecDeque::with_capacity(max_peers)
@@ -76,6 +72,7 @@ impl<Config: endpoint::Config> Negotiator<Config> {
                 VecDeque::new()
             },
             max_peers,
+            preferences,
         }
     }
 
@@ -84,6 +81,7 @@ impl<Config: endpoint::Config> Negotiator<Config> {
         path: &Config::PathHandle,
         payload_len: usize,
         packet: &ProtectedPacket,
+        random_generator: &mut dyn random::Generator,
         publisher: &mut Pub,
     ) -> Result<(), Error> {
         // always forward packets for clients on to connections
@@ -93,13 +91,13 @@ impl<Config: endpoint::Config> Negotiator<Config> {
 
         let packet = match packet {
             ProtectedPacket::Initial(packet) => {
-                if is_supported!(packet, publisher) {
+                if is_supported!(self, packet, publisher) {
                     return Ok(());
                 }
                 packet
             }
             ProtectedPacket::ZeroRtt(packet) => {
-                if is_supported!(packet, publisher) {
+                if is_supported!(self, packet, publisher) {
                     return Ok(());
                 }
 
@@ -150,8 +148,9 @@ impl<Config: endpoint::Config> Negotiator<Config> {
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-5.2.2
                 //# Servers SHOULD respond with a Version
                 //# Negotiation packet, provided that the datagram is sufficiently long.
+                let supported_versions = SupportedVersions::new(self.preferences, random_generator);
                 self.transmissions
-                    .push_back(Transmission::new(*path, packet));
+                    .push_back(Transmission::new(*path, packet, supported_versions));
             }
         }
 
@@ -211,11 +210,15 @@ impl<Path: path::Handle> core::fmt::Debug for Transmission<Path> {
 }
 
 impl<Path: path::Handle> Transmission<Path> {
-    pub fn new(path: Path, initial_packet: &packet::initial::ProtectedInitial) -> Self {
+    pub fn new(
+        path: Path,
+        initial_packet: &packet::initial::ProtectedInitial,
+        supported_versions: SupportedVersions,
+    ) -> Self {
         let mut packet_buf = [0u8; MINIMUM_MTU as usize];
         let version_packet = packet::version_negotiation::VersionNegotiation::from_initial(
             initial_packet,
-            SupportedVersions,
+            supported_versions,
         );
 
         let mut buffer = EncoderBuffer::new(&mut packet_buf);
@@ -274,11 +277,34 @@ impl<Path: path::Handle> tx::Message for &Transmission<Path> {
 }
 
 #[derive(Clone, Copy, Debug)]
-pub struct SupportedVersions;
+pub struct SupportedVersions {
+    preferences: Preferences,
+    reserved_version: Option<u32>,
+}
+
+impl SupportedVersions {
+    pub fn new(preferences: Preferences, random_generator: &mut dyn random::Generator) -> Self {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-6.3
+        //# For a server to use a new version in the future, clients need to
+        //# correctly handle unsupported versions.  Some version numbers
+        //# (0x?a?a?a?a as defined in Section 15) are reserved for inclusion in
+        //# fields that contain version numbers.
+        let reserved_version = if preferences.grease() {
+            Some(version::random_reserved(random_generator))
+        } else {
+            None
+        };
+
+        Self {
+            preferences,
+            reserved_version,
+        }
+    }
+}
 
 impl EncoderValue for SupportedVersions {
     fn encode<E: Encoder>(&self, encoder: &mut E) {
-        for version in SUPPORTED_VERSIONS {
+        for version in self.preferences.versions() {
             encoder.encode(version);
         }
 
@@ -290,13 +316,9 @@ impl EncoderValue for SupportedVersions {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-6.3
         //# Endpoints MAY send packets with a reserved version to test that a
         //# peer correctly discards the packet.
-
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-6.3
-        //# For a server to use a new version in the future, clients need to
-        //# correctly handle unsupported versions.  Some version numbers
-        //# (0x?a?a?a?a as defined in Section 15) are reserved for inclusion in
-        //# fields that contain version numbers.
-        encoder.encode(&0xdadadadau32);
+        if let Some(reserved_version) = self.reserved_version {
+            encoder.encode(&reserved_version);
+        }
     }
 }
 
@@ -322,6 +344,7 @@ mod tests {
         path::RemoteAddress,
         time::clock::testing as time,
         varint::VarInt,
+        version::{VERSION_1, VERSION_2},
     };
 
     type Server = Negotiator<testing::Server>;
@@ -362,7 +385,14 @@ mod tests {
             let remote_address = SocketAddress::default();
             let connection_info = ConnectionInfo::new(&remote_address);
             let (packet, _) = ProtectedPacket::decode(decoder, &connection_info, &3).unwrap();
-            $negotiator.on_packet(&$remote_address, $payload_len, &packet, $publisher)
+            let mut random = random::testing::Generator(123);
+            $negotiator.on_packet(
+                &$remote_address,
+                $payload_len,
+                &packet,
+                &mut random,
+                $publisher,
+            )
         }};
     }
 
@@ -476,7 +506,10 @@ mod tests {
                 tag: 0,
                 destination_connection_id: &[1u8, 2, 3][..],
                 source_connection_id: &[4u8, 5, 6][..],
-                supported_versions: SupportedVersions,
+                supported_versions: SupportedVersions::new(
+                    Preferences::default(),
+                    &mut random::testing::Generator(123),
+                ),
             }
         )
     }
@@ -497,6 +530,7 @@ mod tests {
             Short {
                 destination_connection_id: &[1u8, 2, 3][..],
                 key_phase: Default::default(),
+                quic_bit: Default::default(),
                 spin_bit: Default::default(),
                 packet_number: pn(PacketNumberSpace::ApplicationData),
                 payload: payload.as_slice(),
@@ -634,7 +668,7 @@ mod tests {
 
     #[test]
     fn server_max_peers_test() {
-        let mut server = Server::new(2);
+        let mut server = Server::new(2, Preferences::default());
         let mut publisher = Publisher::snapshot();
 
         for _ in 0..5 {
@@ -657,6 +691,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn server_preferences_test() {
+        let preferences = Preferences::new(&[VERSION_2, VERSION_1]).unwrap();
+        let mut server = Server::new(endpoint::DEFAULT_MAX_PEERS, preferences);
+        let mut publisher = Publisher::snapshot();
+
+        assert_eq!(
+            on_initial_packet(datagram_info(1200), VERSION_2, &mut server, &mut publisher),
+            Ok(()),
+            "server implementations should allow enabled versions"
+        );
+
+        let mut server = Server::default();
+
+        assert_eq!(
+            on_initial_packet(datagram_info(1200), VERSION_2, &mut server, &mut publisher),
+            Err(Error),
+            "server implementations should error on versions that are not enabled"
+        );
+    }
+
+    fn transmitted_versions<C: endpoint::Config>(negotiator: &Negotiator<C>) -> Vec<u32> {
+        let mut packet = negotiator.transmissions[0].as_ref().to_vec();
+        let remote_address = SocketAddress::default();
+        let connection_info = ConnectionInfo::new(&remote_address);
+        let decoder = DecoderBufferMut::new(&mut packet);
+        match ProtectedPacket::decode(decoder, &connection_info, &3)
+            .unwrap()
+            .0
+        {
+            ProtectedPacket::VersionNegotiation(packet) => packet.iter().collect(),
+            packet => panic!("unexpected packet {:?}", packet),
+        }
+    }
+
+    #[test]
+    fn server_reserved_version_test() {
+        let mut server = Server::default();
+        let mut publisher = Publisher::no_snapshot();
+
+        assert_eq!(
+            on_initial_packet(
+                datagram_info(1200),
+                INVALID_VERSION,
+                &mut server,
+                &mut publisher
+            ),
+            Err(Error)
+        );
+
+        let versions = transmitted_versions(&server);
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0], VERSION_1);
+        assert!(
+            version::is_reserved(versions[1]),
+            "servers should include a reserved version by default"
+        );
+
+        let preferences = Preferences::default().with_grease(false).unwrap();
+        let mut server = Server::new(endpoint::DEFAULT_MAX_PEERS, preferences);
+
+        assert_eq!(
+            on_initial_packet(
+                datagram_info(1200),
+                INVALID_VERSION,
+                &mut server,
+                &mut publisher
+            ),
+            Err(Error)
+        );
+
+        assert_eq!(transmitted_versions(&server), vec![VERSION_1]);
+    }
+
     #[test]
     fn server_other_packets_test() {
         let mut server = Server::default();
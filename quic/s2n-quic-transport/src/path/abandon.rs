@@ -0,0 +1,121 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks the PATH_ABANDON frames which close a path when the multipath extension is enabled
+//!
+//! See https://datatracker.ietf.org/doc/html/draft-ietf-quic-multipath-03
+
+use crate::{contexts::WriteContext, transmission};
+use s2n_quic_core::{ack, frame, packet::number::PacketNumber, varint::VarInt};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Abandon {
+    state: State,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    /// The path is in use
+    Active,
+
+    /// A PATH_ABANDON frame must be sent
+    RequiresTransmission,
+
+    /// A PATH_ABANDON frame was lost and must be sent again
+    RequiresRetransmission,
+
+    /// A PATH_ABANDON frame was sent in the given packet
+    InFlight(PacketNumber),
+
+    /// The PATH_ABANDON frame was acknowledged by the peer
+    Abandoned,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self::Active
+    }
+}
+
+impl Abandon {
+    /// Returns `true` if the local endpoint or the peer abandoned the path
+    #[inline]
+    pub fn is_abandoned(&self) -> bool {
+        self.state != State::Active
+    }
+
+    /// Abandons the path and schedules a PATH_ABANDON frame to inform the peer
+    #[inline]
+    pub fn abandon(&mut self) {
+        if self.state == State::Active {
+            self.state = State::RequiresTransmission;
+        }
+    }
+
+    /// Called when the path was abandoned by the peer
+    #[inline]
+    pub fn on_peer_abandon(&mut self) {
+        // An endpoint that receives a PATH_ABANDON frame responds with its own
+        // PATH_ABANDON frame, unless it already abandoned the path.
+        self.abandon();
+    }
+
+    /// Writes the PATH_ABANDON frame for the path with the given identifier
+    #[inline]
+    pub fn on_transmit<W: WriteContext>(&mut self, path_identifier: VarInt, context: &mut W) {
+        let can_transmit = match self.state {
+            State::RequiresTransmission => context.transmission_constraint().can_transmit(),
+            State::RequiresRetransmission => context.transmission_constraint().can_retransmit(),
+            _ => false,
+        };
+
+        if !can_transmit {
+            return;
+        }
+
+        let frame = frame::PathAbandon {
+            path_identifier,
+            // NO_ERROR
+            error_code: VarInt::from_u8(0),
+            reason: None,
+        };
+
+        if let Some(packet_nr) = context.write_frame(&frame) {
+            self.state = State::InFlight(packet_nr);
+        }
+    }
+
+    /// This method gets called when a packet delivery got acknowledged
+    #[inline]
+    pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
+        if let State::InFlight(packet_nr) = self.state {
+            if ack_set.contains(packet_nr) {
+                self.state = State::Abandoned;
+            }
+        }
+    }
+
+    /// This method gets called when a packet loss is reported
+    #[inline]
+    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
+        if let State::InFlight(packet_nr) = self.state {
+            if ack_set.contains(packet_nr) {
+                self.state = State::RequiresRetransmission;
+            }
+        }
+    }
+}
+
+impl transmission::interest::Provider for Abandon {
+    #[inline]
+    fn transmission_interest<Q: transmission::interest::Query>(
+        &self,
+        query: &mut Q,
+    ) -> transmission::interest::Result {
+        match self.state {
+            State::RequiresTransmission => query.on_new_data(),
+            State::RequiresRetransmission => query.on_lost_data(),
+            _ => Ok(()),
+        }
+    }
+}
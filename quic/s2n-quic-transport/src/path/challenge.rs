@@ -142,6 +142,10 @@ impl Challenge {
         )
     }
 
+    pub fn is_validated(&self) -> bool {
+        matches!(self.state, State::Validated)
+    }
+
     pub fn on_validated(&mut self, data: &[u8]) -> bool {
         if self.is_pending() && ConstantTimeEq::ct_eq(&self.data[..], data).into() {
             self.state = State::Validated;
@@ -4,22 +4,23 @@
 //! This module contains the Manager implementation
 
 use crate::{
-    connection::PeerIdRegistry,
+    connection::{LocalIdRegistry, PeerIdRegistry},
     endpoint, path,
     path::{challenge, Path},
     transmission,
 };
+use core::task::{Context, Poll, Waker};
 use s2n_quic_core::{
     ack,
     connection::{self, PeerId},
     event::{self, builder::DatagramDropReason, IntoEvent},
     frame,
     frame::path_validation,
-    inet::DatagramInfo,
+    inet::{DatagramInfo, SocketAddress},
     packet::number::PacketNumberSpace,
     path::{
         migration::{self, Validator as _},
-        Handle as _, Id, MaxMtu,
+        preferred_address, Handle as _, Id, MaxMtu, RemoteAddress,
     },
     random,
     recovery::{
@@ -28,7 +29,11 @@ use s2n_quic_core::{
     },
     stateless_reset,
     time::{timer, Timestamp},
-    transport,
+    transport::{
+        self,
+        parameters::{EnableMultipath, MigrationSupport, PreferredAddress},
+    },
+    varint::VarInt,
 };
 use smallvec::SmallVec;
 
@@ -36,6 +41,31 @@ use smallvec::SmallVec;
 /// This value is also used to limit the number of connection migrations.
 const MAX_ALLOWED_PATHS: usize = 5;
 
+/// Tracks the client's migration to the server's preferred address
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PreferredAddressState {
+    /// The server did not provide a preferred address or the migration has finished
+    None,
+    /// The server provided a preferred address which will be probed once the
+    /// handshake is confirmed
+    Pending(RemoteAddress),
+    /// The path to the preferred address is being validated
+    Probing(u8),
+}
+
+/// Tracks a migration to a new local address requested by the application
+#[derive(Clone, Copy, Debug)]
+enum LocalMigrationState<Handle> {
+    /// The application has not requested a migration
+    None,
+    /// The application requested a migration which will be started on the next wakeup
+    Requested(Handle),
+    /// The path from the new local address is being validated
+    Probing(Handle, u8),
+    /// The migration finished but the application has not observed the result yet
+    Complete(Handle, Result<(), migration::Error>),
+}
+
 /// The PathManager handles paths for a specific connection.
 /// It will handle path validation operations, and track the active path for a connection.
 #[derive(Debug)]
@@ -68,6 +98,27 @@ pub struct Manager<Config: endpoint::Config> {
     /// The `paths` data structure will need to be enhanced to include garbage collection
     /// of old paths to overcome this limitation.
     pending_packet_authentication: Option<u8>,
+
+    /// The state of the client's migration to the server's preferred address
+    preferred_address: PreferredAddressState,
+
+    /// The state of the client's migration to a new local address
+    local_migration: LocalMigrationState<Config::PathHandle>,
+
+    /// Notifies the application once the migration to a new local address completes
+    local_migration_waker: Option<Waker>,
+
+    /// Set if the peer does not support active migration on the handshake address
+    active_migration_disabled: bool,
+
+    /// Set if the application requested an additional path instead of a migration
+    local_migration_opens_path: bool,
+
+    /// Set if the multipath extension was negotiated with the peer
+    multipath: EnableMultipath,
+
+    /// Selects the QUIC bit of 1-RTT packets sent on any of the paths
+    pub(crate) quic_bit: path::quic_bit::Controller,
 }
 
 impl<Config: endpoint::Config> Manager<Config> {
@@ -78,6 +129,13 @@ impl<Config: endpoint::Config> Manager<Config> {
             active: 0,
             last_known_active_validated_path: None,
             pending_packet_authentication: None,
+            preferred_address: PreferredAddressState::None,
+            local_migration: LocalMigrationState::None,
+            local_migration_waker: None,
+            active_migration_disabled: false,
+            local_migration_opens_path: false,
+            multipath: EnableMultipath::Disabled,
+            quic_bit: Default::default(),
         };
         manager.paths[0].activated = true;
         manager.paths[0].is_active = true;
@@ -112,6 +170,7 @@ impl<Config: endpoint::Config> Manager<Config> {
                     // TODO: add an event if active path update fails due to insufficient ids
                     transport::Error::INTERNAL_ERROR,
                 )?;
+            self[new_path_id].spin_controller.on_connection_id_change();
         };
         self[new_path_id].peer_connection_id = peer_connection_id;
 
@@ -151,6 +210,12 @@ impl<Config: endpoint::Config> Manager<Config> {
         &mut self.paths[self.active as usize]
     }
 
+    /// Returns a snapshot of the statistics for each path
+    #[inline]
+    pub fn stats(&self) -> impl Iterator<Item = connection::stats::Path> + '_ {
+        self.paths.iter().map(|path| path.stats())
+    }
+
     /// Return the Id of the active path
     #[inline]
     pub fn active_path_id(&self) -> Id {
@@ -433,8 +498,12 @@ impl<Config: endpoint::Config> Manager<Config> {
             rtt,
             cc,
             true,
-            max_mtu,
+            self.active_path()
+                .mtu_controller
+                .config()
+                .limit_max_mtu(max_mtu),
         );
+        path.spin_controller = self.active_path().spin_controller.for_new_path();
 
         let unblocked = path.on_bytes_received(datagram.payload_len);
 
@@ -496,6 +565,596 @@ impl<Config: endpoint::Config> Manager<Config> {
         self[path_id].set_challenge(challenge);
     }
 
+    /// Called when the client receives the server's preferred_address transport parameter
+    pub fn on_preferred_address(
+        &mut self,
+        preferred_address: &PreferredAddress,
+    ) -> Result<(), transport::Error> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+        //# A server that chooses a zero-length connection ID MUST NOT provide a
+        //# preferred address.  Similarly, a server MUST NOT include a zero-
+        //# length connection ID in this transport parameter.  A client MUST
+        //# treat a violation of these requirements as a connection error of
+        //# type TRANSPORT_PARAMETER_ERROR.
+        let peer_connection_id = PeerId::try_from_bytes(preferred_address.connection_id.as_bytes())
+            .filter(|id| !id.is_empty() && !self.active_path().peer_connection_id.is_empty())
+            .ok_or_else(|| {
+                transport::Error::TRANSPORT_PARAMETER_ERROR
+                    .with_reason("invalid preferred_address connection ID")
+            })?;
+
+        self.peer_id_registry
+            .register_preferred_address_connection_id(
+                &peer_connection_id,
+                &preferred_address.stateless_reset_token,
+            )
+            .map_err(transport::Error::from)?;
+
+        let addresses = preferred_address::Addresses {
+            ipv4: preferred_address.ipv4_address,
+            ipv6: preferred_address.ipv6_address,
+        };
+        let active_remote_address = self.active_path().remote_address();
+
+        // Only migrate if the server advertised an address in the address family
+        // currently in use and it differs from the current address
+        if let Some(remote_address) = addresses
+            .select(&active_remote_address)
+            .map(RemoteAddress::from)
+            .filter(|remote_address| *remote_address != active_remote_address)
+        {
+            self.preferred_address = PreferredAddressState::Pending(remote_address);
+        }
+
+        Ok(())
+    }
+
+    /// Starts validating the path to the server's preferred address, if one was provided
+    ///
+    /// This is called by the client after the handshake is confirmed.
+    pub fn probe_preferred_address<Pub: event::ConnectionPublisher>(
+        &mut self,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        max_mtu: MaxMtu,
+        random_generator: &mut dyn random::Generator,
+        publisher: &mut Pub,
+    ) {
+        let remote_address =
+            if let PreferredAddressState::Pending(remote_address) = self.preferred_address {
+                remote_address
+            } else {
+                return;
+            };
+
+        self.preferred_address = PreferredAddressState::None;
+
+        if self.paths.len() >= MAX_ALLOWED_PATHS {
+            return;
+        }
+
+        // The connection ID may have been retired by the server in the meantime
+        let peer_connection_id = if let Some(peer_connection_id) =
+            self.peer_id_registry.consume_preferred_address_id()
+        {
+            peer_connection_id
+        } else {
+            return;
+        };
+
+        let new_path_id = self.push_probing_path(
+            Config::PathHandle::from_remote_address(remote_address),
+            peer_connection_id,
+            congestion_controller_endpoint,
+            max_mtu,
+            publisher,
+        );
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.1
+        //# Once the handshake is confirmed, the client SHOULD select one of the
+        //# two addresses provided by the server and initiate path validation
+        //# (see Section 8.2).
+        self.set_challenge(new_path_id, random_generator);
+        self.preferred_address = PreferredAddressState::Probing(new_path_id.as_u8());
+    }
+
+    /// Called when the path to the server's preferred address was validated
+    fn on_preferred_address_validated<Pub: event::ConnectionPublisher>(
+        &mut self,
+        new_path_id: Id,
+        publisher: &mut Pub,
+    ) {
+        self.preferred_address = PreferredAddressState::None;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.1
+        //# If path validation succeeds, the client SHOULD immediately begin
+        //# sending all future packets to the new server address using the new
+        //# connection ID and discontinue use of the old server address.
+        let prev_path_id =
+            if let Some(prev_path_id) = self.activate_probed_path(new_path_id, publisher) {
+                prev_path_id
+            } else {
+                return;
+            };
+
+        let prev_path = &self[prev_path_id];
+        let new_path = &self[new_path_id];
+        publisher.on_preferred_address_migrated(event::builder::PreferredAddressMigrated {
+            previous: path_event!(prev_path, prev_path_id),
+            active: path_event!(new_path, new_path_id),
+        });
+    }
+
+    /// Called when the client receives the server's disable_active_migration transport parameter
+    pub fn on_migration_support(&mut self, migration_support: MigrationSupport) {
+        self.active_migration_disabled = matches!(migration_support, MigrationSupport::Disabled);
+    }
+
+    /// Called when the transport parameters of both endpoints are known
+    ///
+    /// An endpoint that supports a packet number space per path also supports sharing the
+    /// application data packet number space, so the endpoints use the mode both of them
+    /// support. Paths are identified by connection ids, so the extension can't be used with
+    /// zero-length connection ids.
+    pub fn on_multipath_support(&mut self, local: EnableMultipath, peer: EnableMultipath) {
+        use EnableMultipath::*;
+
+        self.multipath = match (local, peer) {
+            _ if self.active_path().peer_connection_id.is_empty() => Disabled,
+            (Disabled, _) | (_, Disabled) => Disabled,
+            (MultiplePacketNumberSpaces, MultiplePacketNumberSpaces) => MultiplePacketNumberSpaces,
+            _ => SinglePacketNumberSpace,
+        };
+    }
+
+    /// Returns `true` if the multipath extension was negotiated with the peer
+    #[inline]
+    pub fn is_multipath_enabled(&self) -> bool {
+        self.multipath.is_enabled()
+    }
+
+    /// Returns `true` if each path uses the packet number space of its connection ids
+    #[inline]
+    pub fn has_path_packet_number_spaces(&self) -> bool {
+        self.multipath == EnableMultipath::MultiplePacketNumberSpaces
+    }
+
+    /// Returns the identifier of the packet number space used for packets sent to the
+    /// peer connection id
+    ///
+    /// This is the sequence number of the connection id if each path uses its own packet
+    /// number space, otherwise `0`.
+    #[inline]
+    pub fn tx_path_identifier(&self, peer_connection_id: &connection::PeerId) -> Option<u32> {
+        if !self.has_path_packet_number_spaces() {
+            return Some(0);
+        }
+
+        self.peer_id_registry.sequence_number(peer_connection_id)
+    }
+
+    /// Returns `true` if a path that is not abandoned sends packets in the packet number
+    /// space of the path identifier
+    #[inline]
+    pub fn is_tx_path_identifier_in_use(&self, path_identifier: u32) -> bool {
+        self.paths.iter().any(|path| {
+            !path.is_abandoned()
+                && self.tx_path_identifier(&path.peer_connection_id) == Some(path_identifier)
+        })
+    }
+
+    /// Returns the Ids of the paths, other than the active path, which can carry
+    /// application data
+    ///
+    /// Paths with a lower smoothed RTT are returned first. The list is empty if the
+    /// multipath extension is not enabled.
+    pub fn multipath_transmission_order(&self) -> SmallVec<[Id; MAX_ALLOWED_PATHS]> {
+        let mut path_ids = SmallVec::new();
+
+        if !self.multipath.is_enabled() {
+            return path_ids;
+        }
+
+        for (idx, path) in self.paths.iter().enumerate() {
+            if idx as u8 != self.active && path.is_multipath_usable() {
+                path_ids.push(path_id(idx as u8));
+            }
+        }
+
+        path_ids.sort_by_key(|id| self[*id].rtt_estimator.smoothed_rtt());
+
+        path_ids
+    }
+
+    /// Polls the application's migration of the connection to the local address of `path_handle`
+    ///
+    /// The migration is started on the next call to [`Self::on_local_migration_request`] and
+    /// completes once the path from the new local address is validated.
+    pub fn poll_local_migration(
+        &mut self,
+        path_handle: Config::PathHandle,
+        context: &Context,
+    ) -> Poll<Result<(), migration::Error>> {
+        self.poll_local_path_request(path_handle, false, context)
+    }
+
+    /// Polls opening an additional path from the local address of `path_handle`
+    ///
+    /// The path is probed on the next call to [`Self::on_local_migration_request`] and
+    /// can carry application data once it is validated. The active path doesn't change.
+    pub fn poll_open_local_path(
+        &mut self,
+        path_handle: Config::PathHandle,
+        context: &Context,
+    ) -> Poll<Result<(), migration::Error>> {
+        if !self.multipath.is_enabled() {
+            return Err(migration::Error::MultipathDisabled).into();
+        }
+
+        self.poll_local_path_request(path_handle, true, context)
+    }
+
+    /// Closes the path from the local address of `path_handle` with a PATH_ABANDON frame
+    ///
+    /// If the path is the active path, the usable path with the lowest RTT becomes active on
+    /// the next call to [`Self::on_local_migration_request`].
+    pub fn close_local_path(
+        &mut self,
+        path_handle: &Config::PathHandle,
+    ) -> Result<(), migration::Error> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        if !self.multipath.is_enabled() {
+            return Err(migration::Error::MultipathDisabled);
+        }
+
+        let closed_path_id = self
+            .paths
+            .iter()
+            .position(|path| path.handle.strict_eq(path_handle) && !path.is_abandoned())
+            .map(|idx| path_id(idx as u8))
+            .ok_or(migration::Error::PathNotFound)?;
+
+        if closed_path_id == self.active_path_id() && self.multipath_transmission_order().is_empty()
+        {
+            return Err(migration::Error::LastPath);
+        }
+
+        self.on_path_abandoned(closed_path_id);
+        self[closed_path_id].abandon.abandon();
+
+        Ok(())
+    }
+
+    /// Returns the handle of a path, or of the pending local migration, from `local_address`
+    /// to the peer address of the active path
+    ///
+    /// A port of `0` matches any port. This resolves repeated requests of the application for
+    /// the same local address to the same handle.
+    pub fn local_path_handle(&self, local_address: &SocketAddress) -> Option<Config::PathHandle> {
+        let local_address = local_address.unmap();
+        let remote_address = self.active_path().remote_address().unmap();
+        let is_match = |handle: &Config::PathHandle| {
+            let handle_address = handle.local_address().unmap();
+            handle.remote_address().unmap() == remote_address
+                && handle_address.ip() == local_address.ip()
+                && (local_address.port() == 0 || handle_address.port() == local_address.port())
+        };
+
+        let pending_handle = match self.local_migration {
+            LocalMigrationState::Requested(handle)
+            | LocalMigrationState::Probing(handle, _)
+            | LocalMigrationState::Complete(handle, _) => Some(handle),
+            LocalMigrationState::None => None,
+        };
+
+        pending_handle
+            .into_iter()
+            .chain(self.paths.iter().map(|path| path.handle))
+            .find(is_match)
+    }
+
+    /// Called when a PATH_ABANDON frame is received
+    ///
+    /// The path identifier is the sequence number of the local connection id the peer
+    /// used on the abandoned path.
+    pub fn on_path_abandon<Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: &frame::PathAbandon,
+        local_id_registry: &LocalIdRegistry,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        debug_assert!(self.multipath.is_enabled());
+
+        let abandoned_path_id = self.paths.iter().position(|path| {
+            !path.is_abandoned()
+                && local_id_registry
+                    .sequence_number(&path.local_connection_id)
+                    .map(VarInt::from)
+                    == Some(frame.path_identifier)
+        });
+
+        // The path was already abandoned or never used
+        let abandoned_path_id = if let Some(idx) = abandoned_path_id {
+            path_id(idx as u8)
+        } else {
+            return Ok(());
+        };
+
+        if abandoned_path_id == self.active_path_id() {
+            let new_path_id = self
+                .multipath_transmission_order()
+                .first()
+                .copied()
+                .ok_or_else(|| {
+                    transport::Error::PROTOCOL_VIOLATION
+                        .with_reason("the peer abandoned the last path")
+                })?;
+            self.activate_path(publisher, abandoned_path_id, new_path_id);
+        }
+
+        self.on_path_abandoned(abandoned_path_id);
+        self[abandoned_path_id].abandon.on_peer_abandon();
+
+        Ok(())
+    }
+
+    /// Stops treating an abandoned path as a fallback for the active path
+    fn on_path_abandoned(&mut self, abandoned_path_id: Id) {
+        if self.last_known_active_validated_path == Some(abandoned_path_id.as_u8()) {
+            self.last_known_active_validated_path = None;
+        }
+    }
+
+    fn poll_local_path_request(
+        &mut self,
+        path_handle: Config::PathHandle,
+        opens_path: bool,
+        context: &Context,
+    ) -> Poll<Result<(), migration::Error>> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+        //# An endpoint that receives this transport parameter MUST NOT use a new
+        //# local address when sending to the address that the peer used during
+        //# the handshake.
+        //
+        // The initial path is always the first path for clients
+        let handshake_address = self.paths[0].remote_address();
+        if self.active_migration_disabled
+            && handshake_address.unmap() == path_handle.remote_address().unmap()
+        {
+            return Err(migration::Error::DisabledByPeer).into();
+        }
+
+        let is_same_request = |handle: &Config::PathHandle, local_migration_opens_path: bool| {
+            handle.strict_eq(&path_handle) && local_migration_opens_path == opens_path
+        };
+
+        match self.local_migration {
+            LocalMigrationState::Requested(handle) | LocalMigrationState::Probing(handle, _) => {
+                if !is_same_request(&handle, self.local_migration_opens_path) {
+                    return Err(migration::Error::InProgress).into();
+                }
+            }
+            LocalMigrationState::Complete(handle, result)
+                if is_same_request(&handle, self.local_migration_opens_path) =>
+            {
+                self.local_migration = LocalMigrationState::None;
+                self.local_migration_waker = None;
+                return result.into();
+            }
+            // a new request replaces the result of a previous migration that was never observed
+            LocalMigrationState::None | LocalMigrationState::Complete(..) => {
+                self.local_migration = LocalMigrationState::Requested(path_handle);
+                self.local_migration_opens_path = opens_path;
+            }
+        }
+
+        self.local_migration_waker = Some(context.waker().clone());
+
+        Poll::Pending
+    }
+
+    /// Starts validating the path from the local address requested by the application and
+    /// moves the connection off an active path the application closed
+    ///
+    /// This is called by the client after the handshake is confirmed.
+    pub fn on_local_migration_request<Pub: event::ConnectionPublisher>(
+        &mut self,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        max_mtu: MaxMtu,
+        random_generator: &mut dyn random::Generator,
+        publisher: &mut Pub,
+    ) {
+        // stop sending on the active path if the application closed it
+        if self.active_path().is_abandoned() {
+            if let Some(new_path_id) = self.multipath_transmission_order().first().copied() {
+                self.activate_path(publisher, self.active_path_id(), new_path_id);
+            }
+        }
+
+        let path_handle = if let LocalMigrationState::Requested(path_handle) = self.local_migration
+        {
+            path_handle
+        } else {
+            return;
+        };
+
+        let probing_path_id = match self.path(&path_handle) {
+            // the connection is already using the requested address
+            Some((id, _)) if id == self.active_path_id() => {
+                self.on_local_migration_complete(path_handle, Ok(()));
+                return;
+            }
+            // the connection already has a usable path from the requested address
+            Some((_, path)) if self.local_migration_opens_path && path.is_multipath_usable() => {
+                self.on_local_migration_complete(path_handle, Ok(()));
+                return;
+            }
+            Some((id, _)) => Some(id),
+            None if self.paths.len() >= MAX_ALLOWED_PATHS => {
+                self.on_local_migration_complete(
+                    path_handle,
+                    Err(migration::Error::PathLimitExceeded),
+                );
+                return;
+            }
+            None => None,
+        };
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.5
+        //# An endpoint MUST NOT reuse a connection ID when sending from more
+        //# than one local address
+        let peer_connection_id =
+            if let Some(peer_connection_id) = self.peer_id_registry.consume_new_id_for_new_path() {
+                peer_connection_id
+            } else {
+                self.on_local_migration_complete(
+                    path_handle,
+                    Err(migration::Error::InsufficientConnectionIds),
+                );
+                return;
+            };
+
+        let probing_path_id = if let Some(probing_path_id) = probing_path_id {
+            // the application is migrating back to a local address it used previously
+            self[probing_path_id].peer_connection_id = peer_connection_id;
+            self[probing_path_id]
+                .spin_controller
+                .on_connection_id_change();
+            // a new connection id identifies a new path, even if the previous one was abandoned
+            self[probing_path_id].abandon = Default::default();
+            probing_path_id
+        } else {
+            self.push_probing_path(
+                path_handle,
+                peer_connection_id,
+                congestion_controller_endpoint,
+                max_mtu,
+                publisher,
+            )
+        };
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.1
+        //# An endpoint MAY probe for peer reachability from a new local address
+        //# using path validation (Section 8.2) prior to migrating the connection
+        //# to the new local address.
+        self.set_challenge(probing_path_id, random_generator);
+        self.local_migration = LocalMigrationState::Probing(path_handle, probing_path_id.as_u8());
+    }
+
+    /// Called when the path from the application's new local address was validated
+    fn on_local_migration_validated<Pub: event::ConnectionPublisher>(
+        &mut self,
+        path_handle: Config::PathHandle,
+        new_path_id: Id,
+        publisher: &mut Pub,
+    ) {
+        // an additional path carries application data next to the active path
+        if self.local_migration_opens_path {
+            self.on_local_migration_complete(path_handle, Ok(()));
+            return;
+        }
+
+        if let Some(prev_path_id) = self.activate_probed_path(new_path_id, publisher) {
+            let prev_path = &self[prev_path_id];
+            let new_path = &self[new_path_id];
+            publisher.on_local_address_migrated(event::builder::LocalAddressMigrated {
+                previous: path_event!(prev_path, prev_path_id),
+                active: path_event!(new_path, new_path_id),
+            });
+        }
+
+        self.on_local_migration_complete(path_handle, Ok(()));
+    }
+
+    /// Stores the result of the migration and notifies the application
+    fn on_local_migration_complete(
+        &mut self,
+        path_handle: Config::PathHandle,
+        result: Result<(), migration::Error>,
+    ) {
+        self.local_migration = LocalMigrationState::Complete(path_handle, result);
+
+        if let Some(waker) = self.local_migration_waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Creates a path that will be probed by the client and returns its Id
+    fn push_probing_path<Pub: event::ConnectionPublisher>(
+        &mut self,
+        path_handle: Config::PathHandle,
+        peer_connection_id: PeerId,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        max_mtu: MaxMtu,
+        publisher: &mut Pub,
+    ) -> Id {
+        debug_assert!(self.paths.len() < MAX_ALLOWED_PATHS);
+
+        let new_path_id = path_id(self.paths.len() as u8);
+        let remote_address = path_handle.remote_address();
+
+        let rtt = RttEstimator::new(self.active_path().rtt_estimator.max_ack_delay());
+        let path_info = congestion_controller::PathInfo::new(&remote_address);
+        let cc = congestion_controller_endpoint.new_congestion_controller(path_info);
+
+        let mut path = Path::new(
+            path_handle,
+            peer_connection_id,
+            self.active_path().local_connection_id,
+            rtt,
+            cc,
+            true,
+            self.active_path()
+                .mtu_controller
+                .config()
+                .limit_max_mtu(max_mtu),
+        );
+        path.spin_controller = self.active_path().spin_controller.for_new_path();
+
+        let active_path = self.active_path();
+        let active_path_id = self.active_path_id();
+        publisher.on_path_created(event::builder::PathCreated {
+            active: path_event!(active_path, active_path_id),
+            new: path_event!(path, new_path_id),
+        });
+
+        publisher.on_mtu_updated(event::builder::MtuUpdated {
+            path_id: new_path_id.into_event(),
+            mtu: path.mtu_controller.mtu() as u16,
+            cause: MtuUpdatedCause::NewPath,
+        });
+
+        self.paths.push(path);
+
+        new_path_id
+    }
+
+    /// Activates a path that was validated after being probed by the client
+    ///
+    /// Returns the Id of the previously active path if the active path changed.
+    fn activate_probed_path<Pub: event::ConnectionPublisher>(
+        &mut self,
+        new_path_id: Id,
+        publisher: &mut Pub,
+    ) -> Option<Id> {
+        let prev_path_id = self.active_path_id();
+        if prev_path_id == new_path_id {
+            return None;
+        }
+
+        if self.active_path().is_validated() {
+            self.last_known_active_validated_path = Some(self.active);
+        }
+
+        self.activate_path(publisher, prev_path_id, new_path_id);
+
+        Some(prev_path_id)
+    }
+
     /// Returns true if a valid initial packet has been received
     pub fn valid_initial_received(&self) -> bool {
         if Config::ENDPOINT_TYPE.is_server() {
@@ -514,7 +1173,19 @@ impl<Config: endpoint::Config> Manager<Config> {
     /// Writes any frames the path manager wishes to transmit to the given context
     #[inline]
     pub fn on_transmit<W: transmission::WriteContext>(&mut self, context: &mut W) {
-        self.peer_id_registry.on_transmit(context)
+        self.peer_id_registry.on_transmit(context);
+
+        for path in self.paths.iter_mut() {
+            // The path is identified by the sequence number of the connection id used to send
+            // packets to the peer on the path
+            if let Some(sequence_number) = self
+                .peer_id_registry
+                .sequence_number(&path.peer_connection_id)
+            {
+                path.abandon
+                    .on_transmit(VarInt::from_u32(sequence_number), context);
+            }
+        }
 
         // TODO Add in per-path constraints based on whether a Challenge needs to be
         // transmitted.
@@ -524,12 +1195,20 @@ impl<Config: endpoint::Config> Manager<Config> {
     #[inline]
     pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
         self.peer_id_registry.on_packet_ack(ack_set);
+
+        for path in self.paths.iter_mut() {
+            path.abandon.on_packet_ack(ack_set);
+        }
     }
 
     /// Called when packets are lost
     #[inline]
     pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
         self.peer_id_registry.on_packet_loss(ack_set);
+
+        for path in self.paths.iter_mut() {
+            path.abandon.on_packet_loss(ack_set);
+        }
     }
 
     #[inline]
@@ -570,8 +1249,11 @@ impl<Config: endpoint::Config> Manager<Config> {
         //# A PATH_RESPONSE frame received on any network path validates the path
         //# on which the PATH_CHALLENGE was sent.
 
+        let mut validated_path_id = None;
+
         for (id, path) in self.paths.iter_mut().enumerate() {
             if path.on_path_response(response.data) {
+                validated_path_id = Some(path_id(id as u8));
                 let id = id as u64;
                 publisher.on_path_challenge_updated(event::builder::PathChallengeUpdated {
                     path_challenge_status: event::builder::PathChallengeStatus::Validated,
@@ -586,6 +1268,24 @@ impl<Config: endpoint::Config> Manager<Config> {
                 break;
             }
         }
+
+        if let (Some(validated_path_id), PreferredAddressState::Probing(probing_path_id)) =
+            (validated_path_id, self.preferred_address)
+        {
+            if validated_path_id.as_u8() == probing_path_id {
+                self.on_preferred_address_validated(validated_path_id, publisher);
+            }
+        }
+
+        if let (
+            Some(validated_path_id),
+            LocalMigrationState::Probing(path_handle, probing_path_id),
+        ) = (validated_path_id, self.local_migration)
+        {
+            if validated_path_id.as_u8() == probing_path_id {
+                self.on_local_migration_validated(path_handle, validated_path_id, publisher);
+            }
+        }
     }
 
     /// Process a packet and update internal state.
@@ -631,7 +1331,10 @@ impl<Config: endpoint::Config> Manager<Config> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-9.2
         //# An endpoint can migrate a connection to a new local address by
         //# sending packets containing non-probing frames from that address.
-        if !path_validation_probing.is_probing() && self.active_path_id() != path_id {
+        if !path_validation_probing.is_probing()
+            && self.active_path_id() != path_id
+            && !self.is_additional_path(path_id)
+        {
             self.update_active_path(path_id, random_generator, publisher)?;
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-9.3
@@ -654,6 +1357,22 @@ impl<Config: endpoint::Config> Manager<Config> {
         Ok(())
     }
 
+    /// Returns `true` if packets received on the path should not migrate the connection
+    /// since the path is used next to the active path
+    ///
+    /// With the multipath extension, the peer uses a separate connection id on each
+    /// path. Packets with the connection id of the active path from a new address are
+    /// still treated as a migration, e.g. after a NAT rebinding.
+    #[inline]
+    fn is_additional_path(&self, path_id: Id) -> bool {
+        if !self.multipath.is_enabled() {
+            return false;
+        }
+
+        let path = &self[path_id];
+        path.is_abandoned() || path.local_connection_id != self.active_path().local_connection_id
+    }
+
     #[inline]
     fn abandon_all_path_challenges<Pub: event::ConnectionPublisher>(
         &mut self,
@@ -710,6 +1429,9 @@ impl<Config: endpoint::Config> Manager<Config> {
                     via the new_connection_id frames, there will always be a new id available \
                     to consume if necessary",
                 );
+            self.active_path_mut()
+                .spin_controller
+                .on_connection_id_change();
         }
 
         Ok(())
@@ -725,6 +1447,40 @@ impl<Config: endpoint::Config> Manager<Config> {
             path.on_timeout(timestamp, path_id(id as u8), random_generator, publisher);
         }
 
+        if let PreferredAddressState::Probing(probing_path_id) = self.preferred_address {
+            let probing_path_id = path_id(probing_path_id);
+            let path = &self[probing_path_id];
+
+            if !path.is_challenge_pending() {
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.1
+                //# If path validation fails, the client MUST continue sending all
+                //# future packets to the server's original IP address.
+                publisher.on_preferred_address_migration_failed(
+                    event::builder::PreferredAddressMigrationFailed {
+                        path: path_event!(path, probing_path_id),
+                    },
+                );
+                self.preferred_address = PreferredAddressState::None;
+            }
+        }
+
+        if let LocalMigrationState::Probing(path_handle, probing_path_id) = self.local_migration {
+            let probing_path_id = path_id(probing_path_id);
+            let path = &self[probing_path_id];
+
+            if !path.is_challenge_pending() {
+                publisher.on_local_address_migration_failed(
+                    event::builder::LocalAddressMigrationFailed {
+                        path: path_event!(path, probing_path_id),
+                    },
+                );
+                self.on_local_migration_complete(
+                    path_handle,
+                    Err(migration::Error::ValidationFailed),
+                );
+            }
+        }
+
         if self.active_path().failed_validation() {
             match self.last_known_active_validated_path {
                 Some(last_known_active_validated_path) => {
@@ -765,10 +1521,69 @@ impl<Config: endpoint::Config> Manager<Config> {
         Ok(())
     }
 
+    /// Called when an ICMP Packet Too Big message is received for a datagram sent to
+    /// `remote_address`
+    ///
+    /// `is_unacknowledged` is called with the peer connection ID and the path identifier of
+    /// each path the quoted packet may have been sent on, and returns `true` if the quoted
+    /// packet was sent in that packet number space and is still unacknowledged.
+    pub fn on_packet_too_big<F, Pub>(
+        &mut self,
+        remote_address: &RemoteAddress,
+        quoted_packet: &[u8],
+        mtu: u16,
+        mut is_unacknowledged: F,
+        timestamp: Timestamp,
+        publisher: &mut Pub,
+    ) where
+        F: FnMut(&connection::PeerId, u32) -> bool,
+        Pub: event::ConnectionPublisher,
+    {
+        for id in 0..self.paths.len() {
+            let path = &self.paths[id];
+
+            //= https://www.rfc-editor.org/rfc/rfc8899#section-4.6.1
+            //# The validation SHOULD utilize information that is not simple for an
+            //# off-path attacker to determine [BCP145].
+            if !path::Handle::eq(&path.remote_address(), remote_address)
+                || !is_quoted_connection_id(quoted_packet, path.peer_connection_id.as_bytes())
+            {
+                continue;
+            }
+
+            //= https://www.rfc-editor.org/rfc/rfc8899#section-4.6.1
+            //# Because DPLPMTUD operates at the PL, the PL needs to
+            //# check that each received PTB message is received in response to a
+            //# packet transmitted by the endpoint PL performing DPLPMTUD.
+            let is_sent = self
+                .tx_path_identifier(&path.peer_connection_id)
+                .map_or(false, |path_identifier| {
+                    is_unacknowledged(&path.peer_connection_id, path_identifier)
+                });
+            if !is_sent {
+                continue;
+            }
+
+            let path = &mut self.paths[id];
+            path.mtu_controller.on_packet_too_big(
+                mtu,
+                timestamp,
+                &mut path.congestion_controller,
+                path_id(id as u8),
+                publisher,
+            );
+        }
+    }
+
     /// Notifies the path manager of the connection closing event
     pub fn on_closing(&mut self) {
         self.active_path_mut().on_closing();
         // TODO clean up other paths
+
+        // notify the application that the migration won't complete
+        if let Some(waker) = self.local_migration_waker.take() {
+            waker.wake();
+        }
     }
 
     /// true if ALL paths are amplification_limited
@@ -821,6 +1636,38 @@ fn path_id(id: u8) -> path::Id {
     unsafe { path::Id::new(id) }
 }
 
+/// Returns `true` if the quoted packet was sent to the peer with the given connection ID
+#[inline]
+fn is_quoted_connection_id(quoted_packet: &[u8], connection_id: &[u8]) -> bool {
+    let quoted_connection_id = match quoted_packet.first() {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
+        //# Long Header Packet {
+        //#   Header Form (1) = 1,
+        //#   Fixed Bit (1) = 1,
+        //#   Long Packet Type (2),
+        //#   Type-Specific Bits (4),
+        //#   Version (32),
+        //#   Destination Connection ID Length (8),
+        //#   Destination Connection ID (0..160),
+        Some(tag) if tag & 0x80 != 0 => quoted_packet
+            .get(5)
+            .and_then(|len| quoted_packet.get(6..6 + *len as usize)),
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-17.3.1
+        //# 1-RTT Packet {
+        //#   Header Form (1) = 0,
+        //#   Fixed Bit (1) = 1,
+        //#   Spin Bit (1),
+        //#   Reserved Bits (2),
+        //#   Key Phase (1),
+        //#   Packet Number Length (2),
+        //#   Destination Connection ID (0..160),
+        Some(_) => quoted_packet.get(1..1 + connection_id.len()),
+        None => None,
+    };
+
+    quoted_connection_id == Some(connection_id)
+}
+
 impl<Config: endpoint::Config> timer::Provider for Manager<Config> {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
@@ -877,6 +1724,7 @@ impl<Config: endpoint::Config> transmission::interest::Provider for Manager<Conf
         for path in self.paths.iter() {
             // query PATH_CHALLENGE and PATH_RESPONSE interest for each path
             path.transmission_interest(query)?;
+            path.abandon.transmission_interest(query)?;
         }
 
         Ok(())
@@ -11,8 +11,10 @@ use crate::{
     },
     path,
     path::DEFAULT_MAX_MTU,
+    transmission::interest::Provider as _,
 };
 use core::time::Duration;
+use futures_test::task::new_count_waker;
 use s2n_quic_core::{
     event::testing::Publisher,
     inet::{DatagramInfo, ExplicitCongestionNotification, SocketAddress},
@@ -59,7 +61,7 @@ fn get_path_by_address_test() {
         RttEstimator::default(),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
 
     let second_conn_id = connection::PeerId::try_from_bytes(&[5, 4, 3, 2, 1]).unwrap();
@@ -70,7 +72,7 @@ fn get_path_by_address_test() {
         RttEstimator::default(),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
 
     let mut manager = manager_server(first_path.clone());
@@ -101,7 +103,7 @@ fn test_invalid_path_fallback() {
         RttEstimator::default(),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     // simulate receiving a handshake packet to force path validation
     first_path.on_handshake_packet();
@@ -117,7 +119,7 @@ fn test_invalid_path_fallback() {
         RttEstimator::default(),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     second_path.set_challenge(challenge);
 
@@ -489,7 +491,7 @@ fn silently_return_when_there_is_no_valid_path() {
         RttEstimator::default(),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     first_path.set_challenge(challenge);
     let mut manager = manager_server(first_path);
@@ -692,7 +694,7 @@ fn test_adding_new_path() {
         RttEstimator::default(),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     let mut manager = manager_server(first_path);
 
@@ -753,7 +755,7 @@ fn do_not_add_new_path_if_handshake_not_confirmed() {
         RttEstimator::default(),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     let mut manager = manager_server(first_path);
 
@@ -813,7 +815,7 @@ fn do_not_add_new_path_if_client() {
         RttEstimator::default(),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     let mut manager = manager_client(first_path);
     let mut publisher = Publisher::snapshot();
@@ -848,6 +850,482 @@ fn do_not_add_new_path_if_client() {
     assert_eq!(manager.paths.len(), 1);
 }
 
+// Helper function to create a client PathManager which received the server's
+// preferred_address transport parameter
+fn helper_preferred_address_manager() -> (ClientManager, PreferredAddress) {
+    let first_conn_id = connection::PeerId::try_from_bytes(&[1]).unwrap();
+    let first_addr: SocketAddr = "127.0.0.1:443".parse().unwrap();
+    let first_path = ClientPath::new(
+        RemoteAddress::from(SocketAddress::from(first_addr)),
+        first_conn_id,
+        connection::LocalId::TEST_ID,
+        RttEstimator::default(),
+        Default::default(),
+        true,
+        DEFAULT_MAX_MTU.into(),
+    );
+    let mut manager = manager_client(first_path);
+    manager
+        .peer_id_registry
+        .register_initial_connection_id(first_conn_id);
+
+    let preferred_address = PreferredAddress {
+        ipv4_address: Some(
+            "127.0.0.2:443"
+                .parse::<std::net::SocketAddrV4>()
+                .unwrap()
+                .into(),
+        ),
+        ipv6_address: None,
+        connection_id: connection::UnboundedId::try_from_bytes(&[2]).unwrap(),
+        stateless_reset_token: TEST_TOKEN_2,
+    };
+
+    (manager, preferred_address)
+}
+
+#[test]
+fn preferred_address_migration() {
+    // Setup:
+    let (mut manager, preferred_address) = helper_preferred_address_manager();
+    let mut publisher = Publisher::no_snapshot();
+    assert!(manager.on_preferred_address(&preferred_address).is_ok());
+
+    // Trigger 1:
+    manager.probe_preferred_address(
+        &mut Default::default(),
+        DEFAULT_MAX_MTU,
+        &mut random::testing::Generator(123),
+        &mut publisher,
+    );
+
+    // Expectation 1:
+    let preferred_path_id = path_id(1);
+    assert_eq!(manager.paths.len(), 2);
+    assert_eq!(manager.active_path_id(), path_id(0));
+    assert_eq!(
+        manager[preferred_path_id].peer_connection_id.as_bytes(),
+        &[2]
+    );
+    assert!(manager[preferred_path_id].is_challenge_pending());
+
+    // Trigger 2:
+    let data: challenge::Data = manager[preferred_path_id]
+        .challenge
+        .challenge_data()
+        .try_into()
+        .unwrap();
+    let frame = s2n_quic_core::frame::PathResponse { data: &data };
+    manager.on_path_response(&frame, &mut publisher);
+
+    // Expectation 2:
+    assert_eq!(manager.active_path_id(), preferred_path_id);
+    assert_eq!(manager.last_known_active_validated_path, Some(0));
+    assert_eq!(manager.preferred_address, PreferredAddressState::None);
+
+    // The preferred address is only probed once
+    manager.probe_preferred_address(
+        &mut Default::default(),
+        DEFAULT_MAX_MTU,
+        &mut random::testing::Generator(123),
+        &mut publisher,
+    );
+    assert_eq!(manager.paths.len(), 2);
+}
+
+#[test]
+fn preferred_address_migration_failed() {
+    // Setup:
+    let (mut manager, preferred_address) = helper_preferred_address_manager();
+    let mut publisher = Publisher::no_snapshot();
+    assert!(manager.on_preferred_address(&preferred_address).is_ok());
+    manager.probe_preferred_address(
+        &mut Default::default(),
+        DEFAULT_MAX_MTU,
+        &mut random::testing::Generator(123),
+        &mut publisher,
+    );
+    let preferred_path_id = path_id(1);
+
+    // Trigger:
+    manager[preferred_path_id].abandon_challenge(&mut publisher, 1);
+    assert!(manager
+        .on_timeout(
+            NoopClock {}.get_time(),
+            &mut random::testing::Generator(123),
+            &mut publisher,
+        )
+        .is_ok());
+
+    // Expectation:
+    assert_eq!(manager.active_path_id(), path_id(0));
+    assert_eq!(manager.preferred_address, PreferredAddressState::None);
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+//= type=test
+//# A server that chooses a zero-length connection ID MUST NOT provide a
+//# preferred address.  Similarly, a server MUST NOT include a zero-
+//# length connection ID in this transport parameter.  A client MUST
+//# treat a violation of these requirements as a connection error of
+//# type TRANSPORT_PARAMETER_ERROR.
+#[test]
+fn preferred_address_zero_length_connection_id() {
+    let (mut manager, mut preferred_address) = helper_preferred_address_manager();
+    preferred_address.connection_id = connection::UnboundedId::try_from_bytes(&[]).unwrap();
+
+    assert_eq!(
+        manager
+            .on_preferred_address(&preferred_address)
+            .unwrap_err()
+            .code,
+        transport::Error::TRANSPORT_PARAMETER_ERROR.code
+    );
+}
+
+// The testing config's path handle only tracks the remote address so a different
+// remote address is used to represent the path from the new local address
+fn helper_local_migration_manager() -> (ClientManager, RemoteAddress) {
+    let first_conn_id = connection::PeerId::try_from_bytes(&[1]).unwrap();
+    let first_addr: SocketAddr = "127.0.0.1:443".parse().unwrap();
+    let first_path = ClientPath::new(
+        RemoteAddress::from(SocketAddress::from(first_addr)),
+        first_conn_id,
+        connection::LocalId::TEST_ID,
+        RttEstimator::default(),
+        Default::default(),
+        true,
+        DEFAULT_MAX_MTU.into(),
+    );
+    let mut manager = manager_client(first_path);
+    manager
+        .peer_id_registry
+        .register_initial_connection_id(first_conn_id);
+
+    let new_addr: SocketAddr = "127.0.0.2:443".parse().unwrap();
+    (manager, RemoteAddress::from(SocketAddress::from(new_addr)))
+}
+
+#[test]
+fn local_migration() {
+    // Setup:
+    let (mut manager, new_handle) = helper_local_migration_manager();
+    let second_conn_id = connection::PeerId::try_from_bytes(&[2]).unwrap();
+    assert!(manager
+        .peer_id_registry
+        .on_new_connection_id(&second_conn_id, 1, 0, &TEST_TOKEN_2)
+        .is_ok());
+    let mut publisher = Publisher::no_snapshot();
+    let (waker, wake_count) = new_count_waker();
+    let cx = Context::from_waker(&waker);
+
+    // Trigger 1:
+    assert!(manager.poll_local_migration(new_handle, &cx).is_pending());
+    manager.on_local_migration_request(
+        &mut Default::default(),
+        DEFAULT_MAX_MTU,
+        &mut random::testing::Generator(123),
+        &mut publisher,
+    );
+
+    // Expectation 1:
+    let new_path_id = path_id(1);
+    assert_eq!(manager.paths.len(), 2);
+    assert_eq!(manager.active_path_id(), path_id(0));
+    assert_eq!(manager[new_path_id].peer_connection_id, second_conn_id);
+    assert!(manager[new_path_id].is_challenge_pending());
+    assert!(manager.poll_local_migration(new_handle, &cx).is_pending());
+
+    // A migration to another address is rejected while one is in progress
+    assert_eq!(
+        manager.poll_local_migration(RemoteAddress::default(), &cx),
+        Poll::Ready(Err(migration::Error::InProgress))
+    );
+
+    // Trigger 2:
+    let data: challenge::Data = manager[new_path_id]
+        .challenge
+        .challenge_data()
+        .try_into()
+        .unwrap();
+    let frame = s2n_quic_core::frame::PathResponse { data: &data };
+    manager.on_path_response(&frame, &mut publisher);
+
+    // Expectation 2:
+    assert_eq!(manager.active_path_id(), new_path_id);
+    assert_eq!(manager.last_known_active_validated_path, Some(0));
+    assert_eq!(wake_count, 1);
+    assert_eq!(
+        manager.poll_local_migration(new_handle, &cx),
+        Poll::Ready(Ok(()))
+    );
+}
+
+#[test]
+fn local_migration_failed() {
+    // Setup:
+    let (mut manager, new_handle) = helper_local_migration_manager();
+    let second_conn_id = connection::PeerId::try_from_bytes(&[2]).unwrap();
+    assert!(manager
+        .peer_id_registry
+        .on_new_connection_id(&second_conn_id, 1, 0, &TEST_TOKEN_2)
+        .is_ok());
+    let mut publisher = Publisher::no_snapshot();
+    let (waker, wake_count) = new_count_waker();
+    let cx = Context::from_waker(&waker);
+    assert!(manager.poll_local_migration(new_handle, &cx).is_pending());
+    manager.on_local_migration_request(
+        &mut Default::default(),
+        DEFAULT_MAX_MTU,
+        &mut random::testing::Generator(123),
+        &mut publisher,
+    );
+    let new_path_id = path_id(1);
+
+    // Trigger:
+    manager[new_path_id].abandon_challenge(&mut publisher, 1);
+    assert!(manager
+        .on_timeout(
+            NoopClock {}.get_time(),
+            &mut random::testing::Generator(123),
+            &mut publisher,
+        )
+        .is_ok());
+
+    // Expectation:
+    assert_eq!(manager.active_path_id(), path_id(0));
+    assert_eq!(wake_count, 1);
+    assert_eq!(
+        manager.poll_local_migration(new_handle, &cx),
+        Poll::Ready(Err(migration::Error::ValidationFailed))
+    );
+}
+
+#[test]
+fn local_migration_insufficient_connection_ids() {
+    // Setup:
+    let (mut manager, new_handle) = helper_local_migration_manager();
+    let mut publisher = Publisher::no_snapshot();
+    let (waker, _wake_count) = new_count_waker();
+    let cx = Context::from_waker(&waker);
+    assert!(manager.poll_local_migration(new_handle, &cx).is_pending());
+
+    // Trigger:
+    manager.on_local_migration_request(
+        &mut Default::default(),
+        DEFAULT_MAX_MTU,
+        &mut random::testing::Generator(123),
+        &mut publisher,
+    );
+
+    // Expectation:
+    assert_eq!(manager.paths.len(), 1);
+    assert_eq!(
+        manager.poll_local_migration(new_handle, &cx),
+        Poll::Ready(Err(migration::Error::InsufficientConnectionIds))
+    );
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+//= type=test
+//# An endpoint that receives this transport parameter MUST NOT use a new
+//# local address when sending to the address that the peer used during
+//# the handshake.
+#[test]
+fn local_migration_disabled_by_peer() {
+    let (mut manager, _new_handle) = helper_local_migration_manager();
+    manager.on_migration_support(MigrationSupport::Disabled);
+    let (waker, _wake_count) = new_count_waker();
+    let cx = Context::from_waker(&waker);
+
+    let handshake_handle = manager.active_path().handle;
+    assert_eq!(
+        manager.poll_local_migration(handshake_handle, &cx),
+        Poll::Ready(Err(migration::Error::DisabledByPeer))
+    );
+}
+
+#[test]
+fn multipath_open_and_close_path() {
+    // Setup:
+    let (mut manager, new_handle) = helper_local_migration_manager();
+    let second_conn_id = connection::PeerId::try_from_bytes(&[2]).unwrap();
+    assert!(manager
+        .peer_id_registry
+        .on_new_connection_id(&second_conn_id, 1, 0, &TEST_TOKEN_2)
+        .is_ok());
+    manager.on_multipath_support(
+        EnableMultipath::SinglePacketNumberSpace,
+        EnableMultipath::SinglePacketNumberSpace,
+    );
+    assert!(manager.is_multipath_enabled());
+    let mut publisher = Publisher::no_snapshot();
+    let (waker, wake_count) = new_count_waker();
+    let cx = Context::from_waker(&waker);
+
+    // Trigger 1:
+    assert!(manager.poll_open_local_path(new_handle, &cx).is_pending());
+    manager.on_local_migration_request(
+        &mut Default::default(),
+        DEFAULT_MAX_MTU,
+        &mut random::testing::Generator(123),
+        &mut publisher,
+    );
+    let new_path_id = path_id(1);
+    assert!(manager.multipath_transmission_order().is_empty());
+
+    let data: challenge::Data = manager[new_path_id]
+        .challenge
+        .challenge_data()
+        .try_into()
+        .unwrap();
+    let frame = s2n_quic_core::frame::PathResponse { data: &data };
+    manager.on_path_response(&frame, &mut publisher);
+
+    // Expectation 1:
+    // the new path carries data next to the active path
+    assert_eq!(manager.active_path_id(), path_id(0));
+    assert_eq!(wake_count, 1);
+    assert_eq!(
+        manager.poll_open_local_path(new_handle, &cx),
+        Poll::Ready(Ok(()))
+    );
+    assert_eq!(&manager.multipath_transmission_order()[..], &[new_path_id]);
+
+    // Trigger 2:
+    let active_handle = manager.active_path().handle;
+    assert_eq!(manager.close_local_path(&active_handle), Ok(()));
+    manager.on_local_migration_request(
+        &mut Default::default(),
+        DEFAULT_MAX_MTU,
+        &mut random::testing::Generator(123),
+        &mut publisher,
+    );
+
+    // Expectation 2:
+    assert_eq!(manager.active_path_id(), new_path_id);
+    assert!(manager[path_id(0)].is_abandoned());
+    assert!(manager.has_transmission_interest());
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut context = MockWriteContext::new(
+        NoopClock {}.get_time(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Client,
+    );
+    manager.on_transmit(&mut context);
+    let mut written = context.frame_buffer.pop_front().unwrap();
+    match written.as_frame() {
+        s2n_quic_core::frame::Frame::PathAbandon(frame) => {
+            // the sequence number of the initial connection id identifies the closed path
+            assert_eq!(frame.path_identifier, VarInt::from_u8(0));
+        }
+        frame => panic!("unexpected frame {:?}", frame),
+    }
+
+    // The last path can't be closed
+    assert_eq!(
+        manager.close_local_path(&new_handle),
+        Err(migration::Error::LastPath)
+    );
+    assert_eq!(
+        manager.close_local_path(&active_handle),
+        Err(migration::Error::PathNotFound)
+    );
+}
+
+#[test]
+fn multipath_negotiation() {
+    use EnableMultipath::*;
+
+    for (local, peer, expected) in [
+        (Disabled, MultiplePacketNumberSpaces, Disabled),
+        (SinglePacketNumberSpace, Disabled, Disabled),
+        (
+            SinglePacketNumberSpace,
+            SinglePacketNumberSpace,
+            SinglePacketNumberSpace,
+        ),
+        (
+            SinglePacketNumberSpace,
+            MultiplePacketNumberSpaces,
+            SinglePacketNumberSpace,
+        ),
+        (
+            MultiplePacketNumberSpaces,
+            SinglePacketNumberSpace,
+            SinglePacketNumberSpace,
+        ),
+        (
+            MultiplePacketNumberSpaces,
+            MultiplePacketNumberSpaces,
+            MultiplePacketNumberSpaces,
+        ),
+    ] {
+        let (mut manager, _new_handle) = helper_local_migration_manager();
+        manager.on_multipath_support(local, peer);
+
+        assert_eq!(manager.is_multipath_enabled(), expected != Disabled);
+        assert_eq!(
+            manager.has_path_packet_number_spaces(),
+            expected == MultiplePacketNumberSpaces
+        );
+    }
+}
+
+#[test]
+fn path_packet_number_spaces() {
+    let (mut manager, _new_handle) = helper_local_migration_manager();
+    let second_conn_id = connection::PeerId::try_from_bytes(&[2]).unwrap();
+    assert!(manager
+        .peer_id_registry
+        .on_new_connection_id(&second_conn_id, 1, 0, &TEST_TOKEN_2)
+        .is_ok());
+    let active_conn_id = manager.active_path().peer_connection_id;
+
+    // all packets are sent in the application data space with a single space
+    manager.on_multipath_support(
+        EnableMultipath::SinglePacketNumberSpace,
+        EnableMultipath::MultiplePacketNumberSpaces,
+    );
+    assert_eq!(manager.tx_path_identifier(&second_conn_id), Some(0));
+
+    // each connection id identifies a space with multiple spaces
+    manager.on_multipath_support(
+        EnableMultipath::MultiplePacketNumberSpaces,
+        EnableMultipath::MultiplePacketNumberSpaces,
+    );
+    assert_eq!(manager.tx_path_identifier(&active_conn_id), Some(0));
+    assert_eq!(manager.tx_path_identifier(&second_conn_id), Some(1));
+    assert!(manager.is_tx_path_identifier_in_use(0));
+    assert!(!manager.is_tx_path_identifier_in_use(1));
+}
+
+#[test]
+fn multipath_disabled() {
+    let (mut manager, new_handle) = helper_local_migration_manager();
+    // the peer doesn't support the multipath extension
+    manager.on_multipath_support(
+        EnableMultipath::SinglePacketNumberSpace,
+        EnableMultipath::Disabled,
+    );
+    let (waker, _wake_count) = new_count_waker();
+    let cx = Context::from_waker(&waker);
+
+    assert!(!manager.is_multipath_enabled());
+    assert_eq!(
+        manager.poll_open_local_path(new_handle, &cx),
+        Poll::Ready(Err(migration::Error::MultipathDisabled))
+    );
+    let active_handle = manager.active_path().handle;
+    assert_eq!(
+        manager.close_local_path(&active_handle),
+        Err(migration::Error::MultipathDisabled)
+    );
+}
+
 #[test]
 //= https://www.rfc-editor.org/rfc/rfc9000#section-7.2
 //= type=test
@@ -866,7 +1344,7 @@ fn switch_destination_connection_id_after_first_server_response() {
         RttEstimator::default(),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     let mut manager = manager_client(zero_path);
     assert_eq!(manager[zero_path_id].peer_connection_id, initial_cid);
@@ -904,7 +1382,7 @@ fn limit_number_of_connection_migrations() {
         RttEstimator::default(),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     let mut manager = manager_server(first_path);
     let mut total_paths = 1;
@@ -962,7 +1440,7 @@ fn connection_migration_challenge_behavior() {
         RttEstimator::default(),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     let mut manager = manager_server(first_path);
 
@@ -1055,7 +1533,7 @@ fn connection_migration_use_max_ack_delay_from_active_path() {
         RttEstimator::new(Duration::from_millis(30)),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     let mut manager = manager_server(first_path);
 
@@ -1133,7 +1611,7 @@ fn connection_migration_new_path_abandon_timer() {
         RttEstimator::new(Duration::from_millis(30)),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     let mut manager = manager_server(first_path);
 
@@ -1253,7 +1731,7 @@ fn stop_using_a_retired_connection_id() {
         RttEstimator::default(),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     let mut manager = manager_server(first_path);
 
@@ -1347,7 +1825,7 @@ fn pending_paths_should_return_paths_pending_validation() {
         RttEstimator::default(),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     let expected_response_data = [0; 8];
     third_path.on_path_challenge(&expected_response_data);
@@ -1411,7 +1889,7 @@ fn temporary_until_authenticated() {
         RttEstimator::default(),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     let mut manager = manager_server(first_path);
 
@@ -1677,6 +2155,77 @@ fn last_known_validated_path_should_update_on_path_response() {
     assert_eq!(manager.last_known_active_validated_path, None);
 }
 
+//= https://www.rfc-editor.org/rfc/rfc8899#section-4.6.1
+//= type=test
+//# Because DPLPMTUD operates at the PL, the PL needs to
+//# check that each received PTB message is received in response to a
+//# packet transmitted by the endpoint PL performing DPLPMTUD.
+#[test]
+fn packet_too_big_requires_unacknowledged_packet() {
+    // Setup:
+    let peer_id = connection::PeerId::try_from_bytes(b"id01").unwrap();
+    let mut first_path = helper_path(peer_id);
+    first_path.mtu_controller = path::mtu::testing::test_controller(1400, 1450);
+    first_path.mtu_controller.enable();
+    let remote_address = first_path.remote_address();
+    let mut manager = manager_server(first_path);
+    let mut publisher = Publisher::no_snapshot();
+    let now = NoopClock.get_time();
+    let quoted_packet = b"\x40id01payload";
+
+    // Trigger 1:
+    // - the quoted packet is not pending acknowledgement
+    let mut queries = vec![];
+    manager.on_packet_too_big(
+        &remote_address,
+        quoted_packet,
+        1300,
+        |peer_connection_id, path_identifier| {
+            queries.push((*peer_connection_id, path_identifier));
+            false
+        },
+        now,
+        &mut publisher,
+    );
+
+    // Expectation 1:
+    assert_eq!(queries, [(peer_id, 0)]);
+    assert_eq!(manager.active_path().mtu_controller.mtu(), 1400);
+
+    // Trigger 2:
+    // - the message was for another remote address
+    let other_address: SocketAddr = "127.0.0.2:443".parse().unwrap();
+    let other_address = RemoteAddress::from(SocketAddress::from(other_address));
+    manager.on_packet_too_big(
+        &other_address,
+        quoted_packet,
+        1300,
+        |_, _| true,
+        now,
+        &mut publisher,
+    );
+
+    // Expectation 2:
+    assert_eq!(manager.active_path().mtu_controller.mtu(), 1400);
+
+    // Trigger 3:
+    // - the quoted packet was sent and is pending acknowledgement
+    manager.on_packet_too_big(
+        &remote_address,
+        quoted_packet,
+        1300,
+        |_, _| true,
+        now,
+        &mut publisher,
+    );
+
+    // Expectation 3:
+    assert_eq!(
+        manager.active_path().mtu_controller.mtu(),
+        s2n_quic_core::path::mtu::BASE_PLPMTU as usize
+    );
+}
+
 // creates a test path_manager. also check out `helper_manager_with_paths`
 // which calls this helper with preset options
 pub fn helper_manager_with_paths_base(
@@ -1790,7 +2339,7 @@ pub fn helper_path(peer_id: connection::PeerId) -> ServerPath {
         RttEstimator::new(Duration::from_millis(30)),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     )
 }
 
@@ -18,11 +18,16 @@ use s2n_quic_core::{
     time::{timer, Timestamp},
 };
 
+mod abandon;
 mod challenge;
 pub(crate) mod ecn;
 mod manager;
-pub(crate) mod mtu;
+pub mod mtu;
+pub mod quic_bit;
+pub mod spin;
+pub(crate) mod stats;
 
+use abandon::Abandon;
 pub use challenge::*;
 pub use manager::*;
 
@@ -60,6 +65,10 @@ pub struct Path<Config: endpoint::Config> {
     pub mtu_controller: mtu::Controller,
     /// Controller for determining the ECN capability of the path
     pub ecn_controller: ecn::Controller,
+    /// Maintains the latency spin bit of the path
+    pub spin_controller: spin::Controller,
+    /// Counts the packets sent and lost on the path
+    pub counters: stats::Counters,
 
     /// True if the path has been validated by the peer
     peer_validated: bool,
@@ -80,6 +89,9 @@ pub struct Path<Config: endpoint::Config> {
 
     /// True if the path is currently active
     is_active: bool,
+
+    /// Tracks whether the path was closed with a PATH_ABANDON frame
+    abandon: Abandon,
 }
 
 impl<Config: endpoint::Config> Clone for Path<Config> {
@@ -94,11 +106,14 @@ impl<Config: endpoint::Config> Clone for Path<Config> {
             state: self.state,
             mtu_controller: self.mtu_controller.clone(),
             ecn_controller: self.ecn_controller.clone(),
+            spin_controller: self.spin_controller,
+            counters: self.counters,
             peer_validated: self.peer_validated,
             challenge: self.challenge.clone(),
             response_data: self.response_data,
             activated: self.activated,
             is_active: self.is_active,
+            abandon: self.abandon,
         }
     }
 }
@@ -114,7 +129,7 @@ impl<Config: endpoint::Config> Path<Config> {
         rtt_estimator: RttEstimator,
         congestion_controller: <Config::CongestionControllerEndpoint as congestion_controller::Endpoint>::CongestionController,
         peer_validated: bool,
-        max_mtu: MaxMtu,
+        mtu_config: mtu::Config,
     ) -> Path<Config> {
         let state = match Config::ENDPOINT_TYPE {
             Type::Server => {
@@ -139,13 +154,16 @@ impl<Config: endpoint::Config> Path<Config> {
             congestion_controller,
             pto_backoff: INITIAL_PTO_BACKOFF,
             state,
-            mtu_controller: mtu::Controller::new(max_mtu, &peer_socket_address),
+            mtu_controller: mtu::Controller::new(mtu_config, &peer_socket_address),
             ecn_controller: ecn::Controller::default(),
+            spin_controller: spin::Controller::default(),
+            counters: stats::Counters::default(),
             peer_validated,
             challenge: Challenge::disabled(),
             response_data: None,
             activated: false,
             is_active: false,
+            abandon: Abandon::default(),
         }
     }
 
@@ -159,6 +177,24 @@ impl<Config: endpoint::Config> Path<Config> {
         self.handle.local_address()
     }
 
+    /// Returns a snapshot of the statistics for the path
+    pub fn stats(&self) -> connection::stats::Path {
+        let mut stats = connection::stats::Path::default();
+        stats.local_address = *self.local_address();
+        stats.remote_address = *self.remote_address();
+        stats.is_active = self.is_active;
+        stats.min_rtt = self.rtt_estimator.min_rtt();
+        stats.smoothed_rtt = self.rtt_estimator.smoothed_rtt();
+        stats.latest_rtt = self.rtt_estimator.latest_rtt();
+        stats.rtt_variance = self.rtt_estimator.rttvar();
+        stats.congestion_window = self.congestion_controller.congestion_window();
+        stats.bytes_in_flight = self.congestion_controller.bytes_in_flight();
+        stats.mtu = self.mtu_controller.mtu() as u16;
+        stats.pto_count = self.pto_backoff.trailing_zeros();
+        self.counters.write_stats(&mut stats);
+        stats
+    }
+
     #[inline]
     pub fn set_challenge(&mut self, challenge: Challenge) {
         self.challenge = challenge;
@@ -370,6 +406,24 @@ impl<Config: endpoint::Config> Path<Config> {
         self.state == State::Validated
     }
 
+    /// Returns whether the path was abandoned by either endpoint
+    #[inline]
+    pub fn is_abandoned(&self) -> bool {
+        self.abandon.is_abandoned()
+    }
+
+    /// Returns whether the path can carry application data when the multipath extension is
+    /// enabled
+    ///
+    /// Paths other than the initial path must have been validated with a PATH_CHALLENGE,
+    /// since clients don't apply amplification limits to new paths.
+    #[inline]
+    pub fn is_multipath_usable(&self) -> bool {
+        !self.is_abandoned()
+            && self.is_validated()
+            && (self.challenge.is_disabled() || self.challenge.is_validated())
+    }
+
     /// The path received a non-path-validation-probing packet so mark it as activated.
     #[inline]
     pub fn on_activated(&mut self) {
@@ -610,7 +664,7 @@ pub mod testing {
             RttEstimator::new(Duration::from_millis(30)),
             Default::default(),
             true,
-            DEFAULT_MAX_MTU,
+            DEFAULT_MAX_MTU.into(),
         )
     }
 
@@ -622,7 +676,7 @@ pub mod testing {
             RttEstimator::new(Duration::from_millis(30)),
             Default::default(),
             false,
-            DEFAULT_MAX_MTU,
+            DEFAULT_MAX_MTU.into(),
         )
     }
 }
@@ -1113,7 +1167,7 @@ mod tests {
             RttEstimator::new(Duration::from_millis(30)),
             Default::default(),
             false,
-            DEFAULT_MAX_MTU,
+            DEFAULT_MAX_MTU.into(),
         );
         let now = NoopClock.get_time();
         let random = &mut random::testing::Generator::default();
@@ -1227,4 +1281,19 @@ mod tests {
         // There isn't room for an MTU sized packet after including the 501 bytes, so the path is congestion limited
         assert!(path.is_congestion_limited(501));
     }
+
+    #[test]
+    fn stats_pto_count() {
+        let mut path = testing::helper_path_client();
+        assert_eq!(path.stats().pto_count, 0);
+
+        // the backoff doubles on every PTO so the count is exact for every power of two
+        for expected in 1..32 {
+            path.pto_backoff *= 2;
+            assert_eq!(path.stats().pto_count, expected);
+        }
+
+        path.reset_pto_backoff();
+        assert_eq!(path.stats().pto_count, 0);
+    }
 }
@@ -2,13 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    contexts::WriteContext,
-    path,
-    path::{MaxMtu, MINIMUM_MTU},
-    recovery::congestion_controller,
-    transmission,
+    contexts::WriteContext, path, path::MaxMtu, recovery::congestion_controller, transmission,
 };
-use core::time::Duration;
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{
     counter::{Counter, Saturating},
@@ -17,11 +12,13 @@ use s2n_quic_core::{
     frame,
     inet::SocketAddress,
     packet::number::PacketNumber,
-    path::{IPV4_MIN_HEADER_LEN, IPV6_MIN_HEADER_LEN, UDP_HEADER_LEN},
+    path::{mtu::Bounds, IPV4_MIN_HEADER_LEN, IPV6_MIN_HEADER_LEN, MINIMUM_MTU, UDP_HEADER_LEN},
     recovery::CongestionController,
     time::{timer, Timer, Timestamp},
 };
 
+pub use s2n_quic_core::path::mtu::Config;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 enum State {
     //= https://www.rfc-editor.org/rfc/rfc8899#section-5.2
@@ -38,55 +35,6 @@ enum State {
     SearchComplete,
 }
 
-//= https://www.rfc-editor.org/rfc/rfc9000#section-14.3
-//# Endpoints SHOULD set the initial value of BASE_PLPMTU (Section 5.1 of
-//# [DPLPMTUD]) to be consistent with QUIC's smallest allowed maximum
-//# datagram size.
-
-//= https://www.rfc-editor.org/rfc/rfc8899#section-5.1.2
-//# When using IPv4, there is no currently equivalent size specified,
-//# and a default BASE_PLPMTU of 1200 bytes is RECOMMENDED.
-const BASE_PLPMTU: u16 = MINIMUM_MTU;
-
-//= https://www.rfc-editor.org/rfc/rfc8899#section-5.1.2
-//# The MAX_PROBES is the maximum value of the PROBE_COUNT
-//# counter (see Section 5.1.3).  MAX_PROBES represents the limit for
-//# the number of consecutive probe attempts of any size.  Search
-//# algorithms benefit from a MAX_PROBES value greater than 1 because
-//# this can provide robustness to isolated packet loss.  The default
-//# value of MAX_PROBES is 3.
-const MAX_PROBES: u8 = 3;
-
-/// The minimum length of the data field of a packet sent over an
-/// Ethernet is 1500 octets, thus the maximum length of an IP datagram
-/// sent over an Ethernet is 1500 octets.
-/// See https://www.rfc-editor.org/rfc/rfc894.txt
-const ETHERNET_MTU: u16 = 1500;
-
-/// If the next value to probe is within the PROBE_THRESHOLD bytes of
-/// the current Path MTU, probing will be considered complete.
-const PROBE_THRESHOLD: u16 = 20;
-
-/// When the black_hole_counter exceeds this threshold, on_black_hole_detected will be
-/// called to reduce the MTU to the BASE_PLPMTU. The black_hole_counter is incremented when
-/// a burst of consecutive packets is lost that starts with a packet that is:
-///      1) not an MTU probe
-///      2) larger than the BASE_PLPMTU
-///      3) sent after the largest MTU-sized acknowledged packet number
-/// This is a possible indication that the path cannot support the MTU that was previously confirmed.
-const BLACK_HOLE_THRESHOLD: u8 = 3;
-
-/// After a black hole has been detected, the mtu::Controller will wait this duration
-/// before probing for a larger MTU again.
-const BLACK_HOLE_COOL_OFF_DURATION: Duration = Duration::from_secs(60);
-
-//= https://www.rfc-editor.org/rfc/rfc8899#section-5.1.1
-//# The PMTU_RAISE_TIMER is configured to the period a
-//# sender will continue to use the current PLPMTU, after which it
-//# reenters the Search Phase.  This timer has a period of 600
-//# seconds, as recommended by PLPMTUD [RFC4821].
-const PMTU_RAISE_TIMER_DURATION: Duration = Duration::from_secs(600);
-
 #[derive(Clone, Debug)]
 pub struct Controller {
     state: State,
@@ -94,8 +42,14 @@ pub struct Controller {
     //# The Packetization Layer PMTU is an estimate of the largest size
     //# of PL datagram that can be sent by a path, controlled by PLPMTUD
     plpmtu: u16,
-    /// The maximum size any packet can reach
-    max_mtu: MaxMtu,
+    /// The settings used to search for the PLPMTU, including the maximum size any packet
+    /// can reach
+    config: Config,
+    /// The combined length of the IP and UDP headers for the path
+    header_len: u16,
+    /// The UDP payload size the PLPMTU starts at and falls back to when a black hole
+    /// is detected
+    base_plpmtu: u16,
     /// The maximum size the UDP payload can reach for any probe packet.
     max_udp_payload: u16,
     //= https://www.rfc-editor.org/rfc/rfc8899#section-5.1.3
@@ -106,6 +60,8 @@ pub struct Controller {
     /// The maximum size datagram to probe for. In contrast to the max_udp_payload,
     /// this value will decrease if probes are not acknowledged.
     max_probe_size: u16,
+    /// True if the max_probe_size was probed MAX_PROBES times without acknowledgement
+    max_probe_size_lost: bool,
     //= https://www.rfc-editor.org/rfc/rfc8899#section-5.1.3
     //# The PROBE_COUNT is a count of the number of successive
     //# unsuccessful probe packets that have been sent.
@@ -124,35 +80,49 @@ pub struct Controller {
 }
 
 impl Controller {
-    /// Construct a new mtu::Controller with the given `max_mtu` and `peer_socket_address`
+    /// Construct a new mtu::Controller with the given `config` and `peer_socket_address`
     ///
-    /// The UDP header length and IP header length will be subtracted from `max_mtu` to
-    /// determine the max_udp_payload used for limiting the payload length of probe packets.
-    /// max_mtu is the maximum allowed mtu, e.g. for jumbo frames this value is expected to
-    /// be over 9000.
-    pub fn new(max_mtu: MaxMtu, peer_socket_address: &SocketAddress) -> Self {
+    /// The UDP header length and IP header length will be subtracted from the max MTU of the
+    /// `config` to determine the max_udp_payload used for limiting the payload length of probe
+    /// packets. The max MTU is the maximum allowed mtu, e.g. for jumbo frames this value is
+    /// expected to be over 9000.
+    pub fn new(config: Config, peer_socket_address: &SocketAddress) -> Self {
         let min_ip_header_len = match peer_socket_address {
             SocketAddress::IpV4(_) => IPV4_MIN_HEADER_LEN,
             SocketAddress::IpV6(_) => IPV6_MIN_HEADER_LEN,
         };
-        let max_udp_payload = u16::from(max_mtu) - UDP_HEADER_LEN - min_ip_header_len;
+        let header_len = UDP_HEADER_LEN + min_ip_header_len;
+        let max_udp_payload = u16::from(config.max_mtu()) - header_len;
         debug_assert!(
-            max_udp_payload >= BASE_PLPMTU,
-            "max_udp_payload must be at least {BASE_PLPMTU}"
+            max_udp_payload >= MINIMUM_MTU,
+            "max_udp_payload must be at least {MINIMUM_MTU}"
         );
 
-        // The UDP payload size for the most likely MTU is based on standard Ethernet MTU minus
-        // the minimum length IP headers (without IPv4 options or IPv6 extensions) and UPD header
-        let initial_probed_size =
-            (ETHERNET_MTU - UDP_HEADER_LEN - min_ip_header_len).min(max_udp_payload);
+        let base_plpmtu = config
+            .base_mtu()
+            .saturating_sub(header_len)
+            .max(MINIMUM_MTU)
+            .min(max_udp_payload);
+
+        // The UDP payload size for the most likely MTU is based on the initial MTU (by default
+        // the standard Ethernet MTU) minus the minimum length IP headers (without IPv4 options
+        // or IPv6 extensions) and UPD header
+        let initial_probed_size = config
+            .initial_mtu()
+            .saturating_sub(header_len)
+            .min(max_udp_payload)
+            .max(base_plpmtu);
 
         Self {
             state: State::Disabled,
-            plpmtu: BASE_PLPMTU,
+            plpmtu: base_plpmtu,
             probed_size: initial_probed_size,
-            max_mtu,
+            config,
+            header_len,
+            base_plpmtu,
             max_udp_payload,
             max_probe_size: max_udp_payload,
+            max_probe_size_lost: false,
             probe_count: 0,
             black_hole_counter: Default::default(),
             largest_acked_mtu_sized_packet: None,
@@ -261,10 +231,11 @@ impl Controller {
             State::Disabled => {}
             State::Searching(probe_pn, _) if *probe_pn == packet_number => {
                 // The MTU probe was lost
-                if self.probe_count == MAX_PROBES {
+                if self.probe_count == self.config.max_probes() {
                     // We've sent MAX_PROBES without acknowledgement, so
                     // attempt a smaller probe size
                     self.max_probe_size = self.probed_size;
+                    self.max_probe_size_lost = true;
                     self.update_probed_size();
                     self.request_new_search(None);
                 } else {
@@ -273,7 +244,7 @@ impl Controller {
                 }
             }
             State::Searching(_, _) | State::SearchComplete | State::SearchRequested => {
-                if (BASE_PLPMTU + 1..=self.plpmtu).contains(&lost_bytes)
+                if (self.base_plpmtu + 1..=self.plpmtu).contains(&lost_bytes)
                     && self
                         .largest_acked_mtu_sized_packet
                         .map_or(true, |pn| packet_number > pn)
@@ -284,13 +255,75 @@ impl Controller {
                     self.black_hole_counter += 1;
                 }
 
-                if self.black_hole_counter > BLACK_HOLE_THRESHOLD {
+                if self.black_hole_counter > self.config.black_hole_threshold() {
                     self.on_black_hole_detected(now, congestion_controller, path_id, publisher);
                 }
             }
         }
     }
 
+    /// This method gets called when a validated ICMP Packet Too Big message reports that the
+    /// path supports an MTU of at most `mtu`, including the IP and UDP headers
+    pub fn on_packet_too_big<CC: CongestionController, Pub: event::ConnectionPublisher>(
+        &mut self,
+        mtu: u16,
+        now: Timestamp,
+        congestion_controller: &mut CC,
+        path_id: path::Id,
+        publisher: &mut Pub,
+    ) {
+        if self.state == State::Disabled {
+            return;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc8899#section-4.6.2
+        //# Before using the size reported in the PTB message, it must first be
+        //# converted to a PL_PTB_SIZE.
+        let pl_ptb_size = mtu.saturating_sub(self.header_len);
+
+        if pl_ptb_size < self.base_plpmtu {
+            // The reported size is below the smallest size QUIC allows, so ignore it
+            return;
+        }
+
+        if pl_ptb_size < self.plpmtu {
+            //= https://www.rfc-editor.org/rfc/rfc8899#section-4.6.2
+            //# This could be an indication of a black hole.  The PLPMTU SHOULD
+            //# be set to BASE_PLPMTU (the PLPMTU is reduced to the BASE_PLPMTU
+            //# to avoid unnecessary packet loss when a black hole is
+            //# encountered).
+            self.black_hole_counter = Default::default();
+            self.largest_acked_mtu_sized_packet = None;
+            self.plpmtu = self.base_plpmtu;
+            congestion_controller.on_mtu_update(
+                self.base_plpmtu,
+                &mut congestion_controller::PathPublisher::new(publisher, path_id),
+            );
+
+            publisher.on_mtu_updated(event::builder::MtuUpdated {
+                path_id: path_id.into_event(),
+                mtu: self.plpmtu,
+                cause: MtuUpdatedCause::PacketTooBig,
+            });
+        } else if !matches!(self.state, State::Searching(..))
+            || pl_ptb_size <= self.plpmtu
+            || pl_ptb_size >= self.probed_size
+        {
+            //= https://www.rfc-editor.org/rfc/rfc8899#section-4.6.2
+            //# PTB message ought to be discarded without further processing
+            //# (i.e., PLPMTU is not modified).
+            return;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc8899#section-4.6.2
+        //# The PL can use the reported PL_PTB_SIZE from the PTB message as
+        //# the next search point when it resumes the search algorithm.
+        self.max_probe_size = pl_ptb_size;
+        self.max_probe_size_lost = false;
+        self.probed_size = pl_ptb_size;
+        self.request_new_search(Some(now));
+    }
+
     /// Queries the component for any outgoing frames that need to get sent
     ///
     /// This method assumes that no other data (other than the packet header) has been written
@@ -348,7 +381,12 @@ impl Controller {
 
     /// Returns the maximum size any packet can reach
     pub fn max_mtu(&self) -> MaxMtu {
-        self.max_mtu
+        self.config.max_mtu()
+    }
+
+    /// Returns the settings used to search for the PLPMTU
+    pub fn config(&self) -> Config {
+        self.config
     }
 
     /// Gets the MTU currently being probed for
@@ -356,12 +394,20 @@ impl Controller {
         self.probed_size as usize
     }
 
-    /// Sets `probed_size` to the next MTU size to probe for based on a binary search
+    /// Sets `probed_size` to the next MTU size to probe for based on the configured search
     fn update_probed_size(&mut self) {
-        //= https://www.rfc-editor.org/rfc/rfc8899#section-5.3.2
-        //# Implementations SHOULD select the set of probe packet sizes to
-        //# maximize the gain in PLPMTU from each search step.
-        self.probed_size = self.plpmtu + ((self.max_probe_size - self.plpmtu) / 2);
+        let bounds = Bounds::new(
+            self.plpmtu,
+            self.max_probe_size,
+            self.max_probe_size_lost,
+            self.header_len,
+        );
+        self.probed_size = self
+            .config
+            .search()
+            .next_probe_size(&bounds)
+            .min(self.max_probe_size)
+            .max(self.plpmtu);
     }
 
     /// Requests a new search to be initiated
@@ -370,7 +416,7 @@ impl Controller {
     /// necessary if the probed_size is already within the PROBE_THRESHOLD
     /// of the current PLPMTU
     fn request_new_search(&mut self, last_probe_time: Option<Timestamp>) {
-        if self.probed_size - self.plpmtu >= PROBE_THRESHOLD {
+        if self.probed_size - self.plpmtu >= self.config.probe_threshold() {
             self.probe_count = 0;
             self.state = State::SearchRequested;
         } else {
@@ -379,7 +425,9 @@ impl Controller {
             self.state = State::SearchComplete;
 
             if let Some(last_probe_time) = last_probe_time {
-                self.arm_pmtu_raise_timer(last_probe_time + PMTU_RAISE_TIMER_DURATION);
+                self.arm_pmtu_raise_timer(
+                    last_probe_time + self.config.pmtu_raise_timer_duration(),
+                );
             }
         }
     }
@@ -395,15 +443,15 @@ impl Controller {
         self.black_hole_counter = Default::default();
         self.largest_acked_mtu_sized_packet = None;
         // Reset the plpmtu back to the BASE_PLPMTU and notify the congestion controller
-        self.plpmtu = BASE_PLPMTU;
+        self.plpmtu = self.base_plpmtu;
         congestion_controller.on_mtu_update(
-            BASE_PLPMTU,
+            self.base_plpmtu,
             &mut congestion_controller::PathPublisher::new(publisher, path_id),
         );
         // Cancel any current probes
         self.state = State::SearchComplete;
         // Arm the PMTU raise timer to try a larger MTU again after a cooling off period
-        self.arm_pmtu_raise_timer(now + BLACK_HOLE_COOL_OFF_DURATION);
+        self.arm_pmtu_raise_timer(now + self.config.black_hole_cool_off_duration());
 
         publisher.on_mtu_updated(event::builder::MtuUpdated {
             path_id: path_id.into_event(),
@@ -417,9 +465,10 @@ impl Controller {
     fn arm_pmtu_raise_timer(&mut self, timestamp: Timestamp) {
         // Reset the max_probe_size to the max_udp_payload to allow for larger probe sizes
         self.max_probe_size = self.max_udp_payload;
+        self.max_probe_size_lost = false;
         self.update_probed_size();
 
-        if self.probed_size - self.plpmtu >= PROBE_THRESHOLD {
+        if self.probed_size - self.plpmtu >= self.config.probe_threshold() {
             // There is still some room to try a larger MTU again,
             // so arm the pmtu raise timer
             self.pmtu_raise_timer.set(timestamp);
@@ -471,6 +520,10 @@ mod test {
         event::testing::Publisher,
         frame::Frame,
         packet::number::PacketNumberSpace,
+        path::mtu::{
+            Fixed, Steps, BASE_PLPMTU, BLACK_HOLE_COOL_OFF_DURATION, BLACK_HOLE_THRESHOLD,
+            ETHERNET_MTU, MAX_PROBES, PMTU_RAISE_TIMER_DURATION, PROBE_THRESHOLD,
+        },
         recovery::congestion_controller::testing::mock::CongestionController,
         time::{clock::testing::now, timer::Provider as _},
         varint::VarInt,
@@ -479,8 +532,14 @@ mod test {
 
     /// Creates a new mtu::Controller with an IPv4 address and the given `max_mtu`
     pub fn new_controller(max_mtu: u16) -> Controller {
+        let max_mtu: MaxMtu = max_mtu.try_into().unwrap();
+        new_controller_with_config(max_mtu.into())
+    }
+
+    /// Creates a new mtu::Controller with an IPv4 address and the given `config`
+    fn new_controller_with_config(config: Config) -> Controller {
         let addr: SocketAddr = "127.0.0.1:443".parse().unwrap();
-        Controller::new(max_mtu.try_into().unwrap(), &addr.into())
+        Controller::new(config, &addr.into())
     }
 
     /// Creates an application space packet number with the given value
@@ -516,7 +575,8 @@ mod test {
     #[test]
     fn new_ipv4() {
         let addr: SocketAddr = "127.0.0.1:443".parse().unwrap();
-        let controller = Controller::new(1600.try_into().unwrap(), &addr.into());
+        let max_mtu: MaxMtu = 1600.try_into().unwrap();
+        let controller = Controller::new(max_mtu.into(), &addr.into());
         assert_eq!(
             1600 - UDP_HEADER_LEN - IPV4_MIN_HEADER_LEN,
             controller.max_udp_payload
@@ -540,7 +600,8 @@ mod test {
         let addr: SocketAddr = "[2001:0db8:85a3:0001:0002:8a2e:0370:7334]:9000"
             .parse()
             .unwrap();
-        let controller = Controller::new(2000.try_into().unwrap(), &addr.into());
+        let max_mtu: MaxMtu = 2000.try_into().unwrap();
+        let controller = Controller::new(max_mtu.into(), &addr.into());
         assert_eq!(
             2000 - UDP_HEADER_LEN - IPV6_MIN_HEADER_LEN,
             controller.max_udp_payload
@@ -1094,4 +1155,150 @@ mod test {
         );
         assert_eq!(State::Searching(packet_number, now), controller.state);
     }
+
+    #[test]
+    fn new_with_config() {
+        let config = Config::default()
+            .with_base_mtu(1400)
+            .unwrap()
+            .with_initial_mtu(9001)
+            .unwrap()
+            .with_max_mtu(4000)
+            .unwrap();
+        let header_len = UDP_HEADER_LEN + IPV4_MIN_HEADER_LEN;
+        let controller = new_controller_with_config(config);
+
+        assert_eq!(1400 - header_len, controller.base_plpmtu);
+        assert_eq!(1400 - header_len, controller.plpmtu);
+        assert_eq!(4000 - header_len, controller.max_udp_payload);
+        // the initial MTU is limited to the max MTU
+        assert_eq!(4000 - header_len, controller.probed_size);
+    }
+
+    #[test]
+    fn steps_search() {
+        static STEPS: Steps = Steps(&[1280, 1400, 1500, 9001]);
+        let header_len = UDP_HEADER_LEN + IPV4_MIN_HEADER_LEN;
+        let config = Config::default().with_search(&STEPS).unwrap();
+        let mut controller = new_controller_with_config(config);
+        let mut cc = CongestionController::default();
+        let now = now();
+        let mut publisher = Publisher::snapshot();
+        assert_eq!(1500 - header_len, controller.probed_size);
+
+        // the initial MTU is lost, so the steps are probed from the smallest
+        controller.state = State::Searching(pn(1), now);
+        controller.probe_count = MAX_PROBES;
+        controller.on_packet_loss(
+            pn(1),
+            controller.probed_size,
+            false,
+            now,
+            &mut cc,
+            path::Id::test_id(),
+            &mut publisher,
+        );
+        assert_eq!(1280 - header_len, controller.probed_size);
+        assert_eq!(State::SearchRequested, controller.state);
+
+        controller.state = State::Searching(pn(2), now);
+        controller.on_packet_ack(
+            pn(2),
+            controller.probed_size,
+            &mut cc,
+            path::Id::test_id(),
+            &mut publisher,
+        );
+        assert_eq!(1280 - header_len, controller.plpmtu);
+        assert_eq!(1400 - header_len, controller.probed_size);
+        assert_eq!(State::SearchRequested, controller.state);
+
+        // the lost step is not probed again after the next smaller step is acknowledged
+        controller.state = State::Searching(pn(3), now);
+        controller.on_packet_ack(
+            pn(3),
+            controller.probed_size,
+            &mut cc,
+            path::Id::test_id(),
+            &mut publisher,
+        );
+        assert_eq!(1400 - header_len, controller.plpmtu);
+        assert_eq!(State::SearchComplete, controller.state);
+
+        // once the PMTU raise timer expires, larger steps are probed again
+        assert!(controller.pmtu_raise_timer.is_armed());
+        controller.on_timeout(now + PMTU_RAISE_TIMER_DURATION);
+        assert_eq!(1500 - header_len, controller.probed_size);
+        assert_eq!(State::SearchRequested, controller.state);
+    }
+
+    #[test]
+    fn fixed_search() {
+        let config = Config::default().with_search(&Fixed).unwrap();
+        let mut controller = new_controller_with_config(config);
+        let mut cc = CongestionController::default();
+        let now = now();
+        let mut publisher = Publisher::snapshot();
+
+        controller.state = State::Searching(pn(1), now);
+        controller.on_packet_ack(
+            pn(1),
+            controller.probed_size,
+            &mut cc,
+            path::Id::test_id(),
+            &mut publisher,
+        );
+
+        assert_eq!(1472, controller.plpmtu);
+        assert_eq!(State::SearchComplete, controller.state);
+        assert!(!controller.pmtu_raise_timer.is_armed());
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc8899#section-4.6.2
+    //= type=test
+    //# The PL can use the reported PL_PTB_SIZE from the PTB message as
+    //# the next search point when it resumes the search algorithm.
+    #[test]
+    fn on_packet_too_big() {
+        let mut controller = new_controller(9001);
+        let header_len = UDP_HEADER_LEN + IPV4_MIN_HEADER_LEN;
+        let mut cc = CongestionController::default();
+        let now = now();
+        let mut publisher = Publisher::snapshot();
+
+        // PTB messages are ignored until probing is enabled
+        controller.on_packet_too_big(1300, now, &mut cc, path::Id::test_id(), &mut publisher);
+        assert_eq!(State::Disabled, controller.state);
+
+        controller.enable();
+        controller.state = State::Searching(pn(1), now);
+        assert_eq!(1500 - header_len, controller.probed_size);
+
+        // a size larger than the probed size is inconsistent
+        controller.on_packet_too_big(1500, now, &mut cc, path::Id::test_id(), &mut publisher);
+        assert_eq!(State::Searching(pn(1), now), controller.state);
+        assert_eq!(1500 - header_len, controller.probed_size);
+
+        // a size between the PLPMTU and the probed size is probed next
+        controller.on_packet_too_big(1400, now, &mut cc, path::Id::test_id(), &mut publisher);
+        assert_eq!(State::SearchRequested, controller.state);
+        assert_eq!(1400 - header_len, controller.probed_size);
+        assert_eq!(1400 - header_len, controller.max_probe_size);
+        assert_eq!(0, cc.on_mtu_update);
+
+        // a size smaller than the PLPMTU resets the PLPMTU to the BASE_PLPMTU
+        controller.plpmtu = 1400 - header_len;
+        controller.state = State::SearchComplete;
+        controller.on_packet_too_big(1300, now, &mut cc, path::Id::test_id(), &mut publisher);
+        assert_eq!(BASE_PLPMTU, controller.plpmtu);
+        assert_eq!(1300 - header_len, controller.probed_size);
+        assert_eq!(State::SearchRequested, controller.state);
+        assert_eq!(1, cc.on_mtu_update);
+
+        // a size smaller than the BASE_PLPMTU is ignored
+        controller.on_packet_too_big(1000, now, &mut cc, path::Id::test_id(), &mut publisher);
+        assert_eq!(BASE_PLPMTU, controller.plpmtu);
+        assert_eq!(1300 - header_len, controller.probed_size);
+        assert_eq!(1, cc.on_mtu_update);
+    }
 }
@@ -0,0 +1,93 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use s2n_quic_core::{packet::QuicBit, random, transport::parameters::GreaseQuicBit};
+
+/// Selects the value of the QUIC bit for 1-RTT packets sent on a connection
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Controller {
+    greased: bool,
+    state: u64,
+}
+
+impl Controller {
+    pub fn new(random_generator: &mut dyn random::Generator) -> Self {
+        let mut seed = [0; 8];
+        random_generator.public_random_fill(&mut seed);
+
+        Self {
+            greased: false,
+            // the state of the xorshift generator can't be zero
+            state: u64::from_le_bytes(seed) | 1,
+        }
+    }
+
+    /// Called when the peer's transport parameters are received
+    ///
+    /// The QUIC bit is greased if the peer advertised the `grease_quic_bit` transport parameter
+    /// and the application allows randomizing the QUIC bit of the packets it sends.
+    pub fn on_transport_parameters(&mut self, peer: GreaseQuicBit, randomized: bool) {
+        //= https://www.rfc-editor.org/rfc/rfc9287#section-3.1
+        //# An endpoint that receives the grease_quic_bit transport parameter
+        //# from a peer SHOULD set the QUIC Bit to an unpredictable value unless
+        //# another extension assigns specific meaning to the value of the bit.
+        self.greased = randomized && peer.is_enabled();
+    }
+
+    /// Returns `true` if the QUIC bit of 1-RTT packets is set to a random value
+    #[inline]
+    pub fn is_greased(&self) -> bool {
+        self.greased
+    }
+
+    /// Returns the QUIC bit for the next 1-RTT packet
+    #[inline]
+    pub fn next_quic_bit(&mut self) -> QuicBit {
+        if !self.greased {
+            return QuicBit::One;
+        }
+
+        // The value only needs to be unpredictable to observers, so a per-connection
+        // xorshift generator avoids requesting randomness for each packet
+        let mut state = self.state;
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        self.state = state;
+
+        if state >> 63 == 1 {
+            QuicBit::One
+        } else {
+            QuicBit::Zero
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quic_bit_test() {
+        let mut random = random::testing::Generator(123);
+        let mut controller = Controller::new(&mut random);
+
+        // the QUIC bit is set until the peer enables greasing
+        controller.on_transport_parameters(GreaseQuicBit::Disabled, true);
+        assert!(!controller.is_greased());
+        assert!((0..100).all(|_| controller.next_quic_bit() == QuicBit::One));
+
+        // the application can keep the QUIC bit set even if the peer enables greasing
+        controller.on_transport_parameters(GreaseQuicBit::Enabled, false);
+        assert!(!controller.is_greased());
+        assert!((0..100).all(|_| controller.next_quic_bit() == QuicBit::One));
+
+        controller.on_transport_parameters(GreaseQuicBit::Enabled, true);
+        assert!(controller.is_greased());
+
+        let cleared = (0..1000)
+            .filter(|_| controller.next_quic_bit() == QuicBit::Zero)
+            .count();
+        assert!((400..600).contains(&cleared), "{}", cleared);
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use s2n_quic_core::{
+    endpoint,
+    packet::{number::PacketNumber, short::SpinBit},
+    random,
+};
+
+pub use s2n_quic_core::path::spin::{Config, Observer};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-17.4
+    //# When the spin bit is disabled, endpoints MAY set the spin bit to any
+    //# value and MUST ignore any incoming value.
+    Disabled,
+    Enabled {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-17.4
+        //# Each endpoint also remembers the
+        //# highest packet number seen from its peer on each path.
+        largest_packet_number: Option<PacketNumber>,
+    },
+}
+
+/// Maintains the spin value of a path
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Controller {
+    state: State,
+    spin_bit: SpinBit,
+}
+
+impl Default for Controller {
+    fn default() -> Self {
+        Self {
+            state: State::Disabled,
+            spin_bit: SpinBit::Zero,
+        }
+    }
+}
+
+impl Controller {
+    /// Creates a controller for the first path of a connection
+    ///
+    /// Whether the spin bit is enabled is decided once per connection and applies to all of
+    /// its paths.
+    pub fn new(config: Config, random_generator: &mut dyn random::Generator) -> Self {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-17.4
+        //# Each endpoint unilaterally decides if the spin bit is enabled or
+        //# disabled for a connection.
+        if config.select(random_generator) {
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-17.4
+            //# The spin value is initialized to 0 in the
+            //# endpoint for each network path.
+            return Self {
+                state: State::Enabled {
+                    largest_packet_number: None,
+                },
+                spin_bit: SpinBit::Zero,
+            };
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-17.4
+        //# It is RECOMMENDED that
+        //# endpoints set the spin bit to a random value either chosen
+        //# independently for each packet or chosen independently for each
+        //# connection ID.
+        let mut value = [0];
+        random_generator.public_random_fill(&mut value);
+        let spin_bit = if value[0] & 1 == 1 {
+            SpinBit::One
+        } else {
+            SpinBit::Zero
+        };
+
+        Self {
+            state: State::Disabled,
+            spin_bit,
+        }
+    }
+
+    /// Creates a controller for a new path of the same connection
+    #[must_use]
+    pub fn for_new_path(&self) -> Self {
+        match self.state {
+            State::Enabled { .. } => Self {
+                state: State::Enabled {
+                    largest_packet_number: None,
+                },
+                spin_bit: SpinBit::Zero,
+            },
+            State::Disabled => *self,
+        }
+    }
+
+    /// Returns the value of the spin bit for 1-RTT packets sent on the path
+    #[inline]
+    pub fn spin_bit(&self) -> SpinBit {
+        self.spin_bit
+    }
+
+    /// Called when a 1-RTT packet is received on the path
+    #[inline]
+    pub fn on_packet_received(
+        &mut self,
+        endpoint_type: endpoint::Type,
+        spin_bit: SpinBit,
+        packet_number: PacketNumber,
+    ) {
+        if let State::Enabled {
+            largest_packet_number,
+        } = &mut self.state
+        {
+            if largest_packet_number.map_or(false, |largest| largest >= packet_number) {
+                return;
+            }
+            *largest_packet_number = Some(packet_number);
+
+            self.spin_bit = match endpoint_type {
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-17.4
+                //# When a server receives a 1-RTT packet that increases the highest
+                //# packet number seen by the server from the client on a given network
+                //# path, it sets the spin value for that path to be equal to the spin
+                //# bit in the received packet.
+                endpoint::Type::Server => spin_bit,
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-17.4
+                //# When a client receives a 1-RTT packet that increases the highest
+                //# packet number seen by the client from the server on a given network
+                //# path, it sets the spin value for that path to the inverse of the spin
+                //# bit in the received packet.
+                endpoint::Type::Client => spin_bit.invert(),
+            };
+        }
+    }
+
+    /// Called when the connection ID used to send packets on the path changes
+    #[inline]
+    pub fn on_connection_id_change(&mut self) {
+        if let State::Enabled { .. } = self.state {
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-17.4
+            //# An endpoint resets the spin value for a network path to 0 when
+            //# changing the connection ID being used on that network path.
+            self.spin_bit = SpinBit::Zero;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{packet::number::PacketNumberSpace, varint::VarInt};
+
+    fn pn(value: u32) -> PacketNumber {
+        PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u32(value))
+    }
+
+    fn enabled() -> Controller {
+        let config = Config::new()
+            .with_enabled(true)
+            .unwrap()
+            .with_disable_ratio(16)
+            .unwrap();
+        let mut random = random::testing::Generator(123);
+        // retry until the random selection enables the spin bit
+        loop {
+            let controller = Controller::new(config, &mut random);
+            if controller.state != State::Disabled {
+                return controller;
+            }
+        }
+    }
+
+    #[test]
+    fn server_reflects_test() {
+        let mut controller = enabled();
+        assert_eq!(controller.spin_bit(), SpinBit::Zero);
+
+        controller.on_packet_received(endpoint::Type::Server, SpinBit::One, pn(1));
+        assert_eq!(controller.spin_bit(), SpinBit::One);
+
+        // packets which don't increase the largest packet number are ignored
+        controller.on_packet_received(endpoint::Type::Server, SpinBit::Zero, pn(0));
+        assert_eq!(controller.spin_bit(), SpinBit::One);
+
+        controller.on_packet_received(endpoint::Type::Server, SpinBit::Zero, pn(2));
+        assert_eq!(controller.spin_bit(), SpinBit::Zero);
+    }
+
+    #[test]
+    fn client_inverts_test() {
+        let mut controller = enabled();
+
+        controller.on_packet_received(endpoint::Type::Client, SpinBit::Zero, pn(1));
+        assert_eq!(controller.spin_bit(), SpinBit::One);
+
+        controller.on_packet_received(endpoint::Type::Client, SpinBit::One, pn(2));
+        assert_eq!(controller.spin_bit(), SpinBit::Zero);
+
+        controller.on_packet_received(endpoint::Type::Client, SpinBit::Zero, pn(3));
+        assert_eq!(controller.spin_bit(), SpinBit::One);
+
+        // changing the connection ID resets the spin value
+        controller.on_connection_id_change();
+        assert_eq!(controller.spin_bit(), SpinBit::Zero);
+        controller.on_packet_received(endpoint::Type::Client, SpinBit::Zero, pn(3));
+        assert_eq!(controller.spin_bit(), SpinBit::Zero);
+
+        // new paths start spinning from 0 and track their own packet numbers
+        controller.on_packet_received(endpoint::Type::Client, SpinBit::Zero, pn(4));
+        let mut path = controller.for_new_path();
+        assert_eq!(path.spin_bit(), SpinBit::Zero);
+        path.on_packet_received(endpoint::Type::Client, SpinBit::Zero, pn(1));
+        assert_eq!(path.spin_bit(), SpinBit::One);
+    }
+
+    #[test]
+    fn disabled_test() {
+        let mut random = random::testing::Generator(123);
+        let mut controller = Controller::new(Config::default(), &mut random);
+        let spin_bit = controller.spin_bit();
+
+        // incoming values are ignored
+        for value in 0..4 {
+            controller.on_packet_received(endpoint::Type::Client, spin_bit, pn(value));
+            controller.on_packet_received(endpoint::Type::Server, spin_bit.invert(), pn(value));
+            assert_eq!(controller.spin_bit(), spin_bit);
+        }
+    }
+}
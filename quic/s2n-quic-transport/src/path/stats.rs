@@ -0,0 +1,91 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks the packets sent and lost on a path
+
+use crate::connection::stats;
+use s2n_quic_core::{
+    counter::{Counter, Saturating},
+    frame::ack::EcnCounts,
+};
+
+/// Counts the packets sent and lost on a path over the lifetime of the path
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Counters {
+    packets_sent: Counter<u64, Saturating>,
+    bytes_sent: Counter<u64, Saturating>,
+    packets_lost: Counter<u64, Saturating>,
+    bytes_lost: Counter<u64, Saturating>,
+    ect_0_count: Counter<u64, Saturating>,
+    ect_1_count: Counter<u64, Saturating>,
+    ce_count: Counter<u64, Saturating>,
+}
+
+impl Counters {
+    /// Called when a packet is sent on the path
+    #[inline]
+    pub fn on_packet_sent(&mut self, bytes: usize) {
+        self.packets_sent += 1u64;
+        self.bytes_sent += bytes as u64;
+    }
+
+    /// Called when a packet sent on the path is declared lost
+    #[inline]
+    pub fn on_packet_lost(&mut self, bytes: u16) {
+        self.packets_lost += 1u64;
+        self.bytes_lost += bytes as u64;
+    }
+
+    /// Called with the increase in ECN counts reported by the peer in an ACK frame
+    #[inline]
+    pub fn on_ecn_counts(&mut self, counts: EcnCounts) {
+        self.ect_0_count += counts.ect_0_count.as_u64();
+        self.ect_1_count += counts.ect_1_count.as_u64();
+        self.ce_count += counts.ce_count.as_u64();
+    }
+
+    /// Writes the counters into a path statistics snapshot
+    #[inline]
+    pub fn write_stats(&self, stats: &mut stats::Path) {
+        stats.packets_sent = *self.packets_sent;
+        stats.bytes_sent = *self.bytes_sent;
+        stats.packets_lost = *self.packets_lost;
+        stats.bytes_lost = *self.bytes_lost;
+        stats.ecn_counts.ect_0 = *self.ect_0_count;
+        stats.ecn_counts.ect_1 = *self.ect_1_count;
+        stats.ecn_counts.ce = *self.ce_count;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{inet::ExplicitCongestionNotification, varint::VarInt};
+
+    #[test]
+    fn counters_test() {
+        let mut counters = Counters::default();
+
+        counters.on_packet_sent(1200);
+        counters.on_packet_sent(100);
+        counters.on_packet_lost(1200);
+
+        let mut counts = EcnCounts::default();
+        counts.increment(ExplicitCongestionNotification::Ect0);
+        counts.increment(ExplicitCongestionNotification::Ce);
+        counters.on_ecn_counts(counts);
+        counts.ect_0_count = VarInt::from_u8(2);
+        counters.on_ecn_counts(counts);
+
+        let mut stats = stats::Path::default();
+        counters.write_stats(&mut stats);
+
+        assert_eq!(stats.packets_sent, 2);
+        assert_eq!(stats.bytes_sent, 1300);
+        assert_eq!(stats.packets_lost, 1);
+        assert_eq!(stats.bytes_lost, 1200);
+        assert_eq!(stats.ecn_counts.ect_0, 3);
+        assert_eq!(stats.ecn_counts.ect_1, 0);
+        assert_eq!(stats.ecn_counts.ce, 2);
+    }
+}
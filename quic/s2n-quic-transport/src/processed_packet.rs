@@ -14,6 +14,7 @@ pub struct ProcessedPacket<'a> {
     pub(crate) datagram: &'a DatagramInfo,
     pub(crate) ack_elicitation: AckElicitation,
     pub(crate) path_challenge_on_active_path: bool,
+    pub(crate) immediate_ack: bool,
     pub(crate) frames: usize,
     pub(crate) path_validation_probing: path_validation::Probe,
     pub(crate) bytes_progressed: usize,
@@ -27,6 +28,7 @@ impl<'a> ProcessedPacket<'a> {
             datagram,
             ack_elicitation: AckElicitation::default(),
             path_challenge_on_active_path: false,
+            immediate_ack: false,
             frames: 0,
             path_validation_probing: path_validation::Probe::default(),
             bytes_progressed: 0,
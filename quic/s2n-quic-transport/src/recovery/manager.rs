@@ -16,6 +16,7 @@ use s2n_quic_core::{
     frame,
     frame::ack::EcnCounts,
     inet::ExplicitCongestionNotification,
+    number::CheckedSub,
     packet::number::{PacketNumber, PacketNumberRange, PacketNumberSpace},
     recovery::{congestion_controller, CongestionController, RttEstimator, K_GRANULARITY},
     time::{timer, Timer, Timestamp},
@@ -154,6 +155,58 @@ impl<Config: endpoint::Config> Manager<Config> {
         *self = Self::new(self.space);
     }
 
+    /// Called on the client when the server rejected early data
+    ///
+    /// The frames sent in 0-RTT packets are declared lost so they are sent again in
+    /// 1-RTT packets.
+    pub fn on_zero_rtt_rejected<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
+        &mut self,
+        context: &mut Ctx,
+        publisher: &mut Pub,
+    ) {
+        debug_assert!(
+            Config::ENDPOINT_TYPE.is_client(),
+            "only a Client can have early data rejected"
+        );
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-6.4
+        //# When 0-RTT is rejected, recovery state for all in-flight 0-RTT
+        //# packets is discarded.
+        self.on_packets_discarded(context, publisher)
+    }
+
+    /// Discards the recovery state of all sent packets
+    ///
+    /// The packets are declared lost so their frames are sent again, which is used when the
+    /// packet number space the packets were sent in is no longer used.
+    pub fn on_packets_discarded<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
+        &mut self,
+        context: &mut Ctx,
+        publisher: &mut Pub,
+    ) {
+        let mut lost_packets: Option<PacketNumberRange> = None;
+
+        for (packet_number, unacked_sent_info) in self.sent_packets.iter() {
+            let path_id = unacked_sent_info.path_id;
+            context
+                .path_mut_by_id(path_id)
+                .congestion_controller
+                .on_packet_discarded(
+                    unacked_sent_info.sent_bytes as usize,
+                    &mut congestion_controller::PathPublisher::new(publisher, path_id),
+                );
+
+            let start = lost_packets.map_or(packet_number, |range| range.start());
+            lost_packets = Some(PacketNumberRange::new(start, packet_number));
+        }
+
+        *self = Self::new(self.space);
+
+        if let Some(range) = lost_packets {
+            context.on_packet_loss(&range, publisher);
+        }
+    }
+
     pub fn on_timeout<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
         &mut self,
         timestamp: Timestamp,
@@ -249,6 +302,7 @@ impl<Config: endpoint::Config> Manager<Config> {
         );
         path.ecn_controller
             .on_packet_sent(ecn, path_event!(path, path_id), publisher);
+        path.counters.on_packet_sent(outcome.bytes_sent);
         self.sent_packet_ecn_counts.increment(ecn);
 
         if outcome.is_congestion_controlled {
@@ -683,6 +737,12 @@ impl<Config: endpoint::Config> Manager<Config> {
         let path_id = context.path_id();
         let path = context.path_mut();
 
+        if let Some(increase) = ack_frame_ecn_counts
+            .and_then(|ecn_counts| ecn_counts.checked_sub(self.baseline_ecn_counts))
+        {
+            path.counters.on_ecn_counts(increase);
+        }
+
         let outcome = path.ecn_controller.validate(
             newly_acked_ecn_counts,
             self.sent_packet_ecn_counts,
@@ -725,6 +785,19 @@ impl<Config: endpoint::Config> Manager<Config> {
         matches!(self.pto.state, PtoState::RequiresTransmission(_))
     }
 
+    /// Returns `true` if there are sent packets that have not been acknowledged or declared
+    /// lost
+    #[inline]
+    pub fn has_sent_packets(&self) -> bool {
+        !self.sent_packets.is_empty()
+    }
+
+    /// Returns `true` if the packet was sent and has not been acknowledged or declared lost
+    #[inline]
+    pub fn is_unacknowledged(&self, packet_number: PacketNumber) -> bool {
+        self.sent_packets.get(packet_number).is_some()
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9002#appendix-B.9
     //# When Initial or Handshake keys are discarded, packets sent in that
     //# space no longer count toward bytes in flight.
@@ -936,6 +1009,8 @@ impl<Config: endpoint::Config> Manager<Config> {
                 is_congestion_event = true;
             }
 
+            path.counters.on_packet_lost(sent_info.sent_bytes);
+
             publisher.on_packet_lost(event::builder::PacketLost {
                 packet_header: event::builder::PacketHeader::new(
                     packet_number,
@@ -55,7 +55,7 @@ fn one_second_pto_when_no_previous_rtt_available() {
         RttEstimator::new(max_ack_delay),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
 
     manager
@@ -462,7 +462,7 @@ fn on_ack_frame() {
         context.path().rtt_estimator,
         MockCongestionController::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     context.path_mut().pto_backoff = 2;
     let ack_receive_time = ack_receive_time + Duration::from_millis(500);
@@ -2504,7 +2504,7 @@ fn update_pto_timer() {
         RttEstimator::new(Duration::from_millis(10)),
         MockCongestionController::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
     // simulate receiving a handshake packet to force path validation
     context.path_mut().on_handshake_packet();
@@ -2589,7 +2589,7 @@ fn pto_armed_if_handshake_not_confirmed() {
         RttEstimator::new(Duration::from_millis(10)),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
 
     // simulate receiving a handshake packet to force path validation
@@ -2617,7 +2617,7 @@ fn pto_must_be_at_least_k_granularity() {
         RttEstimator::new(max_ack_delay),
         Default::default(),
         false,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
 
     // Update RTT with the smallest possible sample
@@ -3213,7 +3213,7 @@ fn helper_generate_path_manager_with_first_addr(
         RttEstimator::new(max_ack_delay),
         MockCongestionController::default(),
         true,
-        DEFAULT_MAX_MTU,
+        DEFAULT_MAX_MTU.into(),
     );
 
     path::Manager::new(path, registry)
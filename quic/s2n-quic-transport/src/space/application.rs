@@ -2,39 +2,50 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack::AckManager,
+    ack::{ack_frequency::AckFrequencySync, AckManager},
     connection::{self, ConnectionTransmissionContext, ProcessingError},
     endpoint, path,
     path::{path_event, Path},
     processed_packet::ProcessedPacket,
     recovery,
-    space::{datagram, keep_alive::KeepAlive, HandshakeStatus, PacketSpace, TxPacketNumbers},
+    space::{
+        datagram,
+        keep_alive::KeepAlive,
+        new_token,
+        path_space::{self, PathSpaces, RxSpace},
+        HandshakeStatus, PacketSpace, TxPacketNumbers, ZeroRttCrypto,
+    },
     stream::Manager as _,
     sync::flag,
     transmission,
     transmission::interest::Provider,
 };
-use core::{convert::TryInto, fmt, marker::PhantomData};
+use core::{convert::TryInto, fmt, marker::PhantomData, time::Duration};
 use once_cell::sync::OnceCell;
-use s2n_codec::EncoderBuffer;
+use s2n_codec::{DecoderBufferMut, EncoderBuffer};
 use s2n_quic_core::{
-    crypto::{application::KeySet, limited, tls, CryptoSuite},
+    connection::id::ConnectionInfo,
+    crypto::{application::KeySet, limited, tls, CryptoSuite, PathKey},
     event::{self, ConnectionPublisher as _, IntoEvent},
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
-        ConnectionClose, DataBlocked, HandshakeDone, MaxData, MaxStreamData, MaxStreams,
-        NewConnectionId, NewToken, PathChallenge, PathResponse, ResetStream, RetireConnectionId,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        AckFrequency, AckMp, ConnectionClose, DataBlocked, HandshakeDone, ImmediateAck, MaxData,
+        MaxStreamData, MaxStreams, NewConnectionId, NewToken, PathAbandon, PathChallenge,
+        PathResponse, ResetStream, RetireConnectionId, StopSending, StreamDataBlocked,
+        StreamsBlocked,
     },
     inet::DatagramInfo,
     packet::{
         encoding::{PacketEncoder, PacketEncodingError},
         number::{PacketNumber, PacketNumberRange, PacketNumberSpace, SlidingWindow},
-        short::{CleartextShort, ProtectedShort, Short, SpinBit},
+        short::{CleartextShort, ProtectedShort, Short},
+        zero_rtt::{CleartextZeroRtt, ProtectedZeroRtt, ZeroRtt},
+        ProtectedPacket,
     },
-    path::MaxMtu,
+    path::{MaxMtu, RemoteAddress},
     time::{timer, Timestamp},
-    transport,
+    transport::{self, parameters::InitialFlowControlLimits},
+    varint::VarInt,
 };
 
 pub struct ApplicationSpace<Config: endpoint::Config> {
@@ -42,13 +53,16 @@ pub struct ApplicationSpace<Config: endpoint::Config> {
     pub tx_packet_numbers: TxPacketNumbers,
     /// Ack manager
     pub ack_manager: AckManager,
+    /// Requests the peer to adjust its acknowledgement frequency
+    ack_frequency: AckFrequencySync,
+    /// Sends or receives address validation tokens
+    pub new_token: new_token::Manager<Config>,
     /// All streams that are managed through this connection
     pub stream_manager: Config::StreamManager,
-    /// The current state of the Spin bit
-    /// TODO: Spin me
-    pub spin_bit: SpinBit,
     /// The crypto suite for application data
-    /// TODO: What about ZeroRtt?
+    ///
+    /// This is only `None` for clients that are sending early data and have not yet
+    /// derived the 1-RTT keys.
     //= https://www.rfc-editor.org/rfc/rfc9001#section-6.3
     //# For this reason, endpoints MUST be able to retain two sets of packet
     //# protection keys for receiving packets: the current and the next.
@@ -56,13 +70,21 @@ pub struct ApplicationSpace<Config: endpoint::Config> {
     //= https://www.rfc-editor.org/rfc/rfc9001#section-6.1
     //# An endpoint MUST NOT initiate a key update prior to having confirmed
     //# the handshake (Section 4.1.2).
-    key_set: KeySet<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey>,
-    header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
+    key_set:
+        Option<KeySet<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey>>,
+    header_key:
+        Option<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey>,
+    /// The 0-RTT keys used by the client to send early data before the 1-RTT keys are available
+    zero_rtt_crypto: Option<Box<ZeroRttCrypto<Config>>>,
 
     ping: flag::Ping,
     keep_alive: KeepAlive,
     processed_packet_numbers: SlidingWindow,
     recovery_manager: recovery::Manager<Config>,
+    /// The packet number spaces of the paths, if each path uses its own space
+    path_spaces: PathSpaces<Config>,
+    /// The path identifier of the packet that is being processed
+    rx_path_identifier: u32,
     pub datagram_manager: datagram::Manager<Config>,
 }
 
@@ -73,6 +95,7 @@ impl<Config: endpoint::Config> fmt::Debug for ApplicationSpace<Config> {
             .field("ping", &self.ping)
             .field("processed_packet_numbers", &self.processed_packet_numbers)
             .field("recovery_manager", &self.recovery_manager)
+            .field("path_spaces", &self.path_spaces)
             .field("stream_manager", &self.stream_manager)
             .field("tx_packet_numbers", &self.tx_packet_numbers)
             .finish()
@@ -90,24 +113,122 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         keep_alive: KeepAlive,
         max_mtu: MaxMtu,
         datagram_manager: datagram::Manager<Config>,
+        new_token: new_token::Manager<Config>,
     ) -> Self {
         let key_set = KeySet::new(key, Self::key_limits(max_mtu));
 
         Self {
             tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::ApplicationData, now),
             ack_manager,
-            spin_bit: SpinBit::Zero,
+            ack_frequency: AckFrequencySync::default(),
+            new_token,
+            stream_manager,
+            key_set: Some(key_set),
+            header_key: Some(header_key),
+            zero_rtt_crypto: None,
+            ping: flag::Ping::default(),
+            keep_alive,
+            processed_packet_numbers: SlidingWindow::default(),
+            recovery_manager: recovery::Manager::new(PacketNumberSpace::ApplicationData),
+            path_spaces: PathSpaces::default(),
+            rx_path_identifier: 0,
+            datagram_manager,
+        }
+    }
+
+    /// Creates an application space for a client sending early data
+    ///
+    /// The 1-RTT keys are installed with [`Self::on_one_rtt_keys`] once they are available.
+    pub fn new_zero_rtt(
+        zero_rtt_crypto: ZeroRttCrypto<Config>,
+        now: Timestamp,
+        stream_manager: Config::StreamManager,
+        ack_manager: AckManager,
+        keep_alive: KeepAlive,
+        datagram_manager: datagram::Manager<Config>,
+        new_token: new_token::Manager<Config>,
+    ) -> Self {
+        debug_assert!(
+            Config::ENDPOINT_TYPE.is_client(),
+            "only clients send early data"
+        );
+
+        Self {
+            tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::ApplicationData, now),
+            ack_manager,
+            ack_frequency: AckFrequencySync::default(),
+            new_token,
             stream_manager,
-            key_set,
-            header_key,
+            key_set: None,
+            header_key: None,
+            zero_rtt_crypto: Some(Box::new(zero_rtt_crypto)),
             ping: flag::Ping::default(),
             keep_alive,
             processed_packet_numbers: SlidingWindow::default(),
             recovery_manager: recovery::Manager::new(PacketNumberSpace::ApplicationData),
+            path_spaces: PathSpaces::default(),
+            rx_path_identifier: 0,
             datagram_manager,
         }
     }
 
+    /// Installs the 1-RTT keys on a space that was created for sending early data
+    pub fn on_one_rtt_keys(
+        &mut self,
+        key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey,
+        header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
+        max_mtu: MaxMtu,
+    ) {
+        debug_assert!(self.key_set.is_none(), "1-RTT keys were already installed");
+
+        self.key_set = Some(KeySet::new(key, Self::key_limits(max_mtu)));
+        self.header_key = Some(header_key);
+
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9.3
+        //# Therefore, a client SHOULD discard 0-RTT keys as soon as it installs
+        //# 1-RTT keys as they have no use after that moment.
+        self.zero_rtt_crypto = None;
+    }
+
+    /// Called on the client when the server rejected the early data
+    ///
+    /// The streams are rewound so their data is sent again within the limits advertised by
+    /// the server.
+    pub fn on_zero_rtt_rejected<Pub: event::ConnectionPublisher>(
+        &mut self,
+        peer_limits: InitialFlowControlLimits,
+        handshake_status: &mut HandshakeStatus,
+        local_id_registry: &mut connection::LocalIdRegistry,
+        path_manager: &mut path::Manager<Config>,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        let (recovery_manager, mut context) = self.recovery(
+            handshake_status,
+            local_id_registry,
+            path_manager.active_path_id(),
+            path_manager,
+        );
+        recovery_manager.on_zero_rtt_rejected(&mut context, publisher);
+
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-4.6.2
+        //# The client therefore MUST reset the state of all
+        //# streams, including application state bound to those streams.
+        self.stream_manager.on_zero_rtt_rejected(peer_limits)
+    }
+
+    /// Enables requesting the peer to adjust its acknowledgement frequency
+    ///
+    /// This is called if the peer advertised support for the ACK frequency extension with
+    /// the `min_ack_delay` transport parameter.
+    pub fn on_peer_min_ack_delay(&mut self, min_ack_delay: Duration, max_ack_delay: Duration) {
+        self.ack_frequency.enable(min_ack_delay, max_ack_delay);
+    }
+
+    /// Returns true if the space is still sending packets with the 0-RTT keys
+    pub fn is_zero_rtt(&self) -> bool {
+        self.key_set.is_none()
+    }
+
     /// Returns true if the packet number has already been processed
     pub fn is_duplicate<Pub: event::ConnectionPublisher>(
         &self,
@@ -116,7 +237,23 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         path: &path::Path<Config>,
         publisher: &mut Pub,
     ) -> bool {
-        let packet_check = self.processed_packet_numbers.check(packet_number);
+        Self::check_duplicate(
+            &self.processed_packet_numbers,
+            packet_number,
+            path_id,
+            path,
+            publisher,
+        )
+    }
+
+    fn check_duplicate<Pub: event::ConnectionPublisher>(
+        processed_packet_numbers: &SlidingWindow,
+        packet_number: PacketNumber,
+        path_id: path::Id,
+        path: &path::Path<Config>,
+        publisher: &mut Pub,
+    ) -> bool {
+        let packet_check = processed_packet_numbers.check(packet_number);
         if let Err(error) = packet_check {
             publisher.on_duplicate_packet(event::builder::DuplicatePacket {
                 packet_header: event::builder::PacketHeader::new(
@@ -140,9 +277,48 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         handshake_status: &mut HandshakeStatus,
         buffer: EncoderBuffer<'a>,
     ) -> Result<(transmission::Outcome, EncoderBuffer<'a>), PacketEncodingError<'a>> {
-        let mut packet_number = self.tx_packet_numbers.next();
+        if self.key_set.is_none() && self.zero_rtt_crypto.is_none() {
+            return Err(PacketEncodingError::EmptyPayload(buffer));
+        }
+
+        let destination_connection_id = context.path().peer_connection_id;
+
+        // 0-RTT packets are always sent in the application data space
+        let path_identifier = if self.key_set.is_some() {
+            context
+                .path_manager
+                .tx_path_identifier(&destination_connection_id)
+        } else {
+            Some(0)
+        };
+        let tx_space = match path_identifier {
+            Some(0) => None,
+            Some(path_identifier) => {
+                match self.path_spaces.tx_space_index(
+                    path_identifier,
+                    context.path_id,
+                    context.timestamp,
+                ) {
+                    Some(index) => Some(index),
+                    None => return Err(PacketEncodingError::EmptyPayload(buffer)),
+                }
+            }
+            None => return Err(PacketEncodingError::EmptyPayload(buffer)),
+        };
+        let path_identifier = path_identifier.unwrap_or_default();
+
+        let (tx_packet_numbers, recovery_manager) = match tx_space {
+            Some(index) => {
+                let space = &mut self.path_spaces.tx[index];
+                space.path_id = context.path_id;
+                (&mut space.tx_packet_numbers, &mut space.recovery_manager)
+            }
+            None => (&mut self.tx_packet_numbers, &mut self.recovery_manager),
+        };
+
+        let mut packet_number = tx_packet_numbers.next();
 
-        if self.recovery_manager.requires_probe() {
+        if recovery_manager.requires_probe() {
             //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
             //# If the sender wants to elicit a faster acknowledgement on PTO, it can
             //# skip a packet number to eliminate the acknowledgment delay.
@@ -152,11 +328,22 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             packet_number = packet_number.next().unwrap();
         }
 
-        let packet_number_encoder = self.packet_number_encoder();
+        let mut packet_number_encoder = tx_packet_numbers.largest_sent_packet_number_acked();
+
+        // The packet is tracked with the path identifier in the packet number but carries the
+        // packet number of the path's space
+        let mut wire_packet_number = packet_number;
+        if tx_space.is_some() {
+            wire_packet_number = path_space::path_packet_number(packet_number);
+            packet_number_encoder = path_space::path_packet_number(packet_number_encoder);
+        }
 
         let mut outcome = transmission::Outcome::default();
 
-        let destination_connection_id = context.path().peer_connection_id;
+        let spin_bit = context.path().spin_controller.spin_bit();
+        let quic_bit = context.path_manager.quic_bit.next_quic_bit();
+        let source_connection_id = context.path().local_connection_id;
+        let quic_version = context.quic_version;
         let timestamp = context.timestamp;
         let transmission_mode = context.transmission_mode;
         let min_packet_len = context.min_packet_len;
@@ -172,48 +359,98 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 context.local_id_registry,
                 context.transmission_mode,
                 &mut self.ack_manager,
+                &mut self.path_spaces.rx,
+                &mut self.ack_frequency,
+                &mut self.new_token,
                 handshake_status,
                 &mut self.ping,
                 &mut self.stream_manager,
-                &mut self.recovery_manager,
+                recovery_manager,
                 &mut self.datagram_manager,
             ),
             timestamp,
             transmission_constraint,
             transmission_mode,
-            tx_packet_numbers: &mut self.tx_packet_numbers,
+            tx_packet_numbers,
             path_id: context.path_id,
             publisher: context.publisher,
             packet_interceptor: context.packet_interceptor,
         };
 
-        let spin_bit = self.spin_bit;
-        let header_key = &self.header_key;
-        let (_protected_packet, buffer) =
-            self.key_set
-                .encrypt_packet(buffer, |buffer, key, key_phase| {
+        let packet_header;
+        let (_protected_packet, buffer) = match (&mut self.key_set, &self.header_key) {
+            (Some(key_set), Some(header_key)) => {
+                packet_header = event::builder::PacketHeader::new(packet_number, quic_version);
+
+                key_set.encrypt_packet(buffer, |buffer, key, key_phase| {
                     let packet = Short {
+                        quic_bit,
                         spin_bit,
                         key_phase,
                         destination_connection_id,
-                        packet_number,
+                        packet_number: wire_packet_number,
                         payload,
                     };
-                    packet.encode_packet(
-                        key,
-                        header_key,
-                        packet_number_encoder,
-                        min_packet_len,
-                        buffer,
-                    )
-                })?;
+
+                    if path_identifier == 0 {
+                        packet.encode_packet(
+                            &*key,
+                            header_key,
+                            packet_number_encoder,
+                            min_packet_len,
+                            buffer,
+                        )
+                    } else {
+                        packet.encode_packet(
+                            &PathKey::new(key, path_identifier),
+                            header_key,
+                            packet_number_encoder,
+                            min_packet_len,
+                            buffer,
+                        )
+                    }
+                })?
+            }
+            _ => {
+                let zero_rtt_crypto = self
+                    .zero_rtt_crypto
+                    .as_deref()
+                    .expect("0-RTT keys should be available without 1-RTT keys");
+
+                packet_header = event::builder::PacketHeader::ZeroRtt {
+                    number: packet_number.into_event(),
+                    version: quic_version,
+                };
+
+                //= https://www.rfc-editor.org/rfc/rfc9001#section-4.6.1
+                //# A client that wishes to send 0-RTT packets uses the
+                //# early_data extension in the ClientHello message of a subsequent
+                //# handshake; see Section 4.2.10 of [TLS13].  It then sends
+                //# application data in 0-RTT packets.
+                let packet = ZeroRtt {
+                    version: quic_version,
+                    destination_connection_id,
+                    source_connection_id,
+                    packet_number,
+                    payload,
+                };
+                packet.encode_packet(
+                    &zero_rtt_crypto.key,
+                    &zero_rtt_crypto.header_key,
+                    packet_number_encoder,
+                    min_packet_len,
+                    buffer,
+                )?
+            }
+        };
 
         outcome.bytes_progressed +=
             (self.stream_manager.outgoing_bytes_progressed() - bytes_progressed).as_u64() as usize;
 
         let app_limited = self.is_app_limited(context.path(), outcome.bytes_sent);
 
-        let (recovery_manager, mut recovery_context) = self.recovery(
+        let (recovery_manager, mut recovery_context) = self.recovery_for_space(
+            tx_space,
             handshake_status,
             context.local_id_registry,
             context.path_id,
@@ -238,13 +475,18 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         context
             .publisher
             .on_packet_sent(event::builder::PacketSent {
-                packet_header: event::builder::PacketHeader::new(
-                    packet_number,
-                    context.publisher.quic_version(),
-                ),
+                packet_header,
                 packet_len: outcome.bytes_sent,
             });
 
+        let publisher = &mut *context.publisher;
+        self.stream_manager.on_receive_window_updates(|update| {
+            publisher.on_receive_window_updated(event::builder::ReceiveWindowUpdated {
+                stream_id: update.stream_id.map(|id| id.as_varint().as_u64()),
+                window: update.window as u64,
+            })
+        });
+
         Ok((outcome, buffer))
     }
 
@@ -254,12 +496,47 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         connection_close: &ConnectionClose,
         buffer: EncoderBuffer<'a>,
     ) -> Result<(transmission::Outcome, EncoderBuffer<'a>), PacketEncodingError<'a>> {
-        let packet_number = self.tx_packet_numbers.next();
+        // The CONNECTION_CLOSE is sent in the Initial or Handshake space until the 1-RTT keys
+        // are available
+        let (key_set, header_key) = match (&mut self.key_set, &self.header_key) {
+            (Some(key_set), Some(header_key)) => (key_set, header_key),
+            _ => return Err(PacketEncodingError::EmptyPayload(buffer)),
+        };
+
+        let destination_connection_id = context.path().peer_connection_id;
+        let path_identifier = match context
+            .path_manager
+            .tx_path_identifier(&destination_connection_id)
+        {
+            Some(path_identifier) => path_identifier,
+            None => return Err(PacketEncodingError::EmptyPayload(buffer)),
+        };
+
+        let tx_packet_numbers = if path_identifier == 0 {
+            &mut self.tx_packet_numbers
+        } else {
+            match self.path_spaces.tx_space_index(
+                path_identifier,
+                context.path_id,
+                context.timestamp,
+            ) {
+                Some(index) => &mut self.path_spaces.tx[index].tx_packet_numbers,
+                None => return Err(PacketEncodingError::EmptyPayload(buffer)),
+            }
+        };
 
-        let packet_number_encoder = self.packet_number_encoder();
+        let packet_number = tx_packet_numbers.next();
+        let mut packet_number_encoder = tx_packet_numbers.largest_sent_packet_number_acked();
+
+        let mut wire_packet_number = packet_number;
+        if path_identifier != 0 {
+            wire_packet_number = path_space::path_packet_number(packet_number);
+            packet_number_encoder = path_space::path_packet_number(packet_number_encoder);
+        }
 
         let mut outcome = transmission::Outcome::default();
-        let destination_connection_id = context.path().peer_connection_id;
+        let spin_bit = context.path().spin_controller.spin_bit();
+        let quic_bit = context.path_manager.quic_bit.next_quic_bit();
 
         let payload = transmission::Transmission {
             config: <PhantomData<Config>>::default(),
@@ -272,33 +549,42 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             timestamp: context.timestamp,
             transmission_constraint: transmission::Constraint::None,
             transmission_mode: transmission::Mode::Normal,
-            tx_packet_numbers: &mut self.tx_packet_numbers,
+            tx_packet_numbers,
             path_id: context.path_id,
             publisher: context.publisher,
             packet_interceptor: context.packet_interceptor,
         };
 
-        let spin_bit = self.spin_bit;
         let min_packet_len = context.min_packet_len;
-        let header_key = &self.header_key;
         let (_protected_packet, buffer) =
-            self.key_set
-                .encrypt_packet(buffer, |buffer, key, key_phase| {
-                    let packet = Short {
-                        spin_bit,
-                        key_phase,
-                        destination_connection_id,
-                        packet_number,
-                        payload,
-                    };
+            key_set.encrypt_packet(buffer, |buffer, key, key_phase| {
+                let packet = Short {
+                    quic_bit,
+                    spin_bit,
+                    key_phase,
+                    destination_connection_id,
+                    packet_number: wire_packet_number,
+                    payload,
+                };
+
+                if path_identifier == 0 {
+                    packet.encode_packet(
+                        &*key,
+                        header_key,
+                        packet_number_encoder,
+                        min_packet_len,
+                        buffer,
+                    )
+                } else {
                     packet.encode_packet(
-                        key,
+                        &PathKey::new(key, path_identifier),
                         header_key,
                         packet_number_encoder,
                         min_packet_len,
                         buffer,
                     )
-                })?;
+                }
+            })?;
 
         context
             .publisher
@@ -317,6 +603,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
     /// but is now no longer limited.
     pub fn on_amplification_unblocked(
         &mut self,
+        path_id: path::Id,
         path: &Path<Config>,
         timestamp: Timestamp,
         is_handshake_confirmed: bool,
@@ -333,6 +620,14 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         //# be re-armed.
         self.recovery_manager
             .update_pto_timer(path, timestamp, is_handshake_confirmed);
+
+        for space in &mut self.path_spaces.tx {
+            if space.path_id == path_id {
+                space
+                    .recovery_manager
+                    .update_pto_timer(path, timestamp, is_handshake_confirmed);
+            }
+        }
     }
 
     /// Signals the handshake is confirmed
@@ -345,6 +640,8 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         // Retire the local connection ID used during the handshake to reduce linkability
         local_id_registry.retire_handshake_connection_id();
 
+        self.new_token.on_handshake_confirmed();
+
         //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
         //# A sender SHOULD restart its PTO timer every time an ack-eliciting
         //# packet is sent or acknowledged, or when Initial or Handshake keys are
@@ -373,7 +670,9 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         publisher: &mut Pub,
     ) {
         self.ack_manager.on_timeout(timestamp);
-        self.key_set.on_timeout(timestamp);
+        if let Some(key_set) = self.key_set.as_mut() {
+            key_set.on_timeout(timestamp);
+        }
 
         let (recovery_manager, mut context) = self.recovery(
             handshake_status,
@@ -384,6 +683,29 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
 
         recovery_manager.on_timeout(timestamp, random_generator, &mut context, publisher);
 
+        for space in &mut self.path_spaces.rx {
+            space.ack_manager.on_timeout(timestamp);
+        }
+
+        for index in 0..self.path_spaces.tx.len() {
+            let path_id = self.path_spaces.tx[index].path_id;
+            let (recovery_manager, mut context) = self.recovery_for_space(
+                Some(index),
+                handshake_status,
+                local_id_registry,
+                path_id,
+                path_manager,
+            );
+            recovery_manager.on_timeout(timestamp, random_generator, &mut context, publisher);
+        }
+
+        self.discard_unused_path_spaces(
+            handshake_status,
+            local_id_registry,
+            path_manager,
+            publisher,
+        );
+
         self.stream_manager.on_timeout(timestamp);
 
         if self.keep_alive.on_timeout(timestamp).is_ready() {
@@ -399,7 +721,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
     /// Returns `true` if the recovery manager for this packet space requires a probe
     /// packet to be sent.
     pub fn requires_probe(&self) -> bool {
-        self.recovery_manager.requires_probe()
+        self.recovery_manager.requires_probe() || self.path_spaces.requires_probe()
     }
 
     pub fn ping(&mut self) {
@@ -410,9 +732,61 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         self.keep_alive.update(enabled);
     }
 
-    /// Returns the Packet Number to be used when encoding outgoing packets
-    fn packet_number_encoder(&self) -> PacketNumber {
-        self.tx_packet_numbers.largest_sent_packet_number_acked()
+    /// Returns `true` if the packet quoted by an ICMP message is a 1-RTT packet that was sent
+    /// in the packet number space of the path identifier and is still unacknowledged
+    ///
+    /// The packet number is recovered by removing the header protection, so the quote has to
+    /// include the header protection sample. Long header packets are ignored since their
+    /// quotes are usually shorter than the length they encode.
+    pub fn is_unacknowledged_quoted_packet(
+        &self,
+        quoted_packet: &[u8],
+        remote_address: &RemoteAddress,
+        peer_connection_id: &connection::PeerId,
+        path_identifier: u32,
+    ) -> bool {
+        let header_key = if let Some(header_key) = self.header_key.as_ref() {
+            header_key
+        } else {
+            return false;
+        };
+
+        let (tx_packet_numbers, recovery_manager) = if path_identifier == 0 {
+            (&self.tx_packet_numbers, &self.recovery_manager)
+        } else if let Some(index) = self.path_spaces.find_tx_space(path_identifier) {
+            let space = &self.path_spaces.tx[index];
+            (&space.tx_packet_numbers, &space.recovery_manager)
+        } else {
+            return false;
+        };
+
+        // the header protection is removed in place
+        let mut quoted_packet = quoted_packet.to_vec();
+        let connection_info = ConnectionInfo::new(remote_address);
+        let packet = match ProtectedPacket::decode(
+            DecoderBufferMut::new(&mut quoted_packet),
+            &connection_info,
+            &peer_connection_id.len(),
+        ) {
+            Ok((ProtectedPacket::Short(packet), _)) => packet,
+            _ => return false,
+        };
+
+        // Packet numbers are encoded relative to the largest one acknowledged by the peer
+        let mut largest_acked = tx_packet_numbers.largest_sent_packet_number_acked();
+        if path_identifier != 0 {
+            largest_acked = path_space::path_packet_number(largest_acked);
+        }
+
+        let packet_number = match packet.unprotect(header_key, largest_acked) {
+            Ok(packet) if path_identifier == 0 => Some(packet.packet_number),
+            Ok(packet) => path_space::tracked_packet_number(path_identifier, packet.packet_number),
+            Err(_) => None,
+        };
+
+        packet_number.map_or(false, |packet_number| {
+            recovery_manager.is_unacknowledged(packet_number)
+        })
     }
 
     fn recovery<'a>(
@@ -425,21 +799,91 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         &'a mut recovery::Manager<Config>,
         RecoveryContext<'a, Config>,
     ) {
+        self.recovery_for_space(
+            None,
+            handshake_status,
+            local_id_registry,
+            path_id,
+            path_manager,
+        )
+    }
+
+    /// Returns the recovery manager of the packets sent in the space of a path, or the
+    /// application data space if `tx_space` is `None`
+    fn recovery_for_space<'a>(
+        &'a mut self,
+        tx_space: Option<usize>,
+        handshake_status: &'a mut HandshakeStatus,
+        local_id_registry: &'a mut connection::LocalIdRegistry,
+        path_id: path::Id,
+        path_manager: &'a mut path::Manager<Config>,
+    ) -> (
+        &'a mut recovery::Manager<Config>,
+        RecoveryContext<'a, Config>,
+    ) {
+        let (recovery_manager, tx_packet_numbers) = match tx_space {
+            Some(index) => {
+                let space = &mut self.path_spaces.tx[index];
+                (&mut space.recovery_manager, &mut space.tx_packet_numbers)
+            }
+            None => (&mut self.recovery_manager, &mut self.tx_packet_numbers),
+        };
+
         (
-            &mut self.recovery_manager,
+            recovery_manager,
             RecoveryContext {
                 ack_manager: &mut self.ack_manager,
+                rx_spaces: &mut self.path_spaces.rx,
+                ack_frequency: &mut self.ack_frequency,
+                new_token: &mut self.new_token,
                 handshake_status,
                 ping: &mut self.ping,
                 stream_manager: &mut self.stream_manager,
                 local_id_registry,
                 path_id,
                 path_manager,
-                tx_packet_numbers: &mut self.tx_packet_numbers,
+                tx_packet_numbers,
             },
         )
     }
 
+    /// Discards the spaces of connection ids that no path sends packets to anymore
+    ///
+    /// A space is kept until its packets are acknowledged or declared lost, unless the path
+    /// that sent them was abandoned or a probe can't be sent in the space. Any packets that
+    /// are still in flight are then declared lost so their frames are sent again.
+    fn discard_unused_path_spaces<Pub: event::ConnectionPublisher>(
+        &mut self,
+        handshake_status: &mut HandshakeStatus,
+        local_id_registry: &mut connection::LocalIdRegistry,
+        path_manager: &mut path::Manager<Config>,
+        publisher: &mut Pub,
+    ) {
+        let mut index = 0;
+        while let Some(space) = self.path_spaces.tx.get(index) {
+            let path_id = space.path_id;
+            let is_unused = !path_manager.is_tx_path_identifier_in_use(space.path_identifier)
+                && (!space.recovery_manager.has_sent_packets()
+                    || space.recovery_manager.requires_probe()
+                    || path_manager[path_id].is_abandoned());
+
+            if !is_unused {
+                index += 1;
+                continue;
+            }
+
+            let (recovery_manager, mut context) = self.recovery_for_space(
+                Some(index),
+                handshake_status,
+                local_id_registry,
+                path_id,
+                path_manager,
+            );
+            recovery_manager.on_packets_discarded(&mut context, publisher);
+            self.path_spaces.tx.remove(index);
+        }
+    }
+
     /// Returns `true` if sending is limited by the application and not the congestion controller
     ///
     /// Sending is app limited if the application is not fully utilizing the available
@@ -449,17 +893,53 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
     }
 
     /// Validate packets in the Application packet space
+    ///
+    /// `path_identifier` identifies the packet number space of the path the packet was sent
+    /// in, which is `0` for the application data space.
+    #[allow(clippy::too_many_arguments)]
     pub fn validate_and_decrypt_packet<'a, Pub: event::ConnectionPublisher>(
         &mut self,
         protected: ProtectedShort<'a>,
+        path_identifier: u32,
         datagram: &DatagramInfo,
         path_id: path::Id,
         path: &path::Path<Config>,
         publisher: &mut Pub,
     ) -> Result<CleartextShort<'a>, ProcessingError> {
-        let largest_acked = self.ack_manager.largest_received_packet_number_acked();
+        let (key_set, header_key) = match (&mut self.key_set, &self.header_key) {
+            (Some(key_set), Some(header_key)) => (key_set, header_key),
+            _ => {
+                // Clients sending early data can receive 1-RTT packets before the keys are
+                // derived from the server's handshake flight
+                publisher.on_packet_dropped(event::builder::PacketDropped {
+                    reason: event::builder::PacketDropReason::HandshakeNotComplete {
+                        path: path_event!(path, path_id),
+                    },
+                });
+                return Err(ProcessingError::CryptoError(
+                    s2n_quic_core::crypto::CryptoError::DECRYPT_ERROR,
+                ));
+            }
+        };
+
+        let (largest_acked, processed_packet_numbers) = if path_identifier == 0 {
+            (
+                self.ack_manager.largest_received_packet_number_acked(),
+                &self.processed_packet_numbers,
+            )
+        } else {
+            // The space is created before the first packet in it is decrypted so every packet
+            // is checked for duplicates against the same window
+            let space = self
+                .path_spaces
+                .rx_space_mut(path_identifier, &self.ack_manager);
+            (
+                space.ack_manager.largest_received_packet_number_acked(),
+                &space.processed_packet_numbers,
+            )
+        };
         let packet = protected
-            .unprotect(&self.header_key, largest_acked)
+            .unprotect(header_key, largest_acked)
             .map_err(|err| {
                 publisher.on_packet_dropped(event::builder::PacketDropped {
                     reason: event::builder::PacketDropReason::UnprotectFailed {
@@ -473,8 +953,9 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         let packet_number = packet.packet_number;
         let packet_header =
             event::builder::PacketHeader::new(packet.packet_number, publisher.quic_version());
-        let decrypted = self.key_set.decrypt_packet(
+        let decrypted = key_set.decrypt_packet(
             packet,
+            path_identifier,
             largest_acked,
             //= https://www.rfc-editor.org/rfc/rfc9001#section-6.3
             //# For a short period after a key
@@ -496,7 +977,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             Ok((_, Some(generation))) => {
                 publisher.on_key_update(event::builder::KeyUpdate {
                     key_type: event::builder::KeyType::OneRtt { generation },
-                    cipher_suite: self.key_set.cipher_suite().into_event(),
+                    cipher_suite: key_set.cipher_suite().into_event(),
                 });
             }
             Ok(_) => {}
@@ -517,18 +998,77 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
 
         // We perform decryption prior to checking for duplicate to avoid short-circuiting
         // and maintain constant-time operation.
-        if self.is_duplicate(packet_number, path_id, path, publisher) {
+        if Self::check_duplicate(
+            processed_packet_numbers,
+            packet_number,
+            path_id,
+            path,
+            publisher,
+        ) {
             return Err(ProcessingError::DuplicatePacket);
         }
 
         if decrypted.is_ok() {
             // reset the keep alive timer after receiving a packet
             self.keep_alive.reset(datagram.timestamp);
+            self.rx_path_identifier = path_identifier;
         }
 
         decrypted.map(|x| x.0)
     }
 
+    /// Validate 0-RTT packets received by the server
+    pub fn validate_and_decrypt_zero_rtt_packet<'a, Pub: event::ConnectionPublisher>(
+        &mut self,
+        protected: ProtectedZeroRtt<'a>,
+        crypto: &ZeroRttCrypto<Config>,
+        datagram: &DatagramInfo,
+        path_id: path::Id,
+        path: &path::Path<Config>,
+        publisher: &mut Pub,
+    ) -> Result<CleartextZeroRtt<'a>, ProcessingError> {
+        let largest_acked = self.ack_manager.largest_received_packet_number_acked();
+        let packet = protected
+            .unprotect(&crypto.header_key, largest_acked)
+            .map_err(|err| {
+                publisher.on_packet_dropped(event::builder::PacketDropped {
+                    reason: event::builder::PacketDropReason::UnprotectFailed {
+                        space: event::builder::KeySpace::ZeroRtt,
+                        path: path_event!(path, path_id),
+                    },
+                });
+                err
+            })?;
+
+        let packet_number = packet.packet_number;
+        let packet_header = event::builder::PacketHeader::ZeroRtt {
+            number: packet_number.into_event(),
+            version: packet.version,
+        };
+        let decrypted = packet.decrypt(&crypto.key);
+
+        if decrypted.is_err() {
+            publisher.on_packet_dropped(event::builder::PacketDropped {
+                reason: event::builder::PacketDropReason::DecryptionFailed {
+                    packet_header,
+                    path: path_event!(path, path_id),
+                },
+            });
+        }
+
+        // 0-RTT and 1-RTT packets share a packet number space so the same duplicate
+        // detection applies
+        if self.is_duplicate(packet_number, path_id, path, publisher) {
+            return Err(ProcessingError::DuplicatePacket);
+        }
+
+        if decrypted.is_ok() {
+            self.keep_alive.reset(datagram.timestamp);
+        }
+
+        Ok(decrypted?)
+    }
+
     fn key_limits(max_mtu: MaxMtu) -> limited::Limits {
         let mut limits = limited::Limits::default();
 
@@ -566,7 +1106,10 @@ impl<Config: endpoint::Config> timer::Provider for ApplicationSpace<Config> {
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
         self.ack_manager.timers(query)?;
         self.recovery_manager.timers(query)?;
-        self.key_set.timers(query)?;
+        self.path_spaces.timers(query)?;
+        if let Some(key_set) = self.key_set.as_ref() {
+            key_set.timers(query)?;
+        }
         self.stream_manager.timers(query)?;
         self.keep_alive.timers(query)?;
 
@@ -582,7 +1125,10 @@ impl<Config: endpoint::Config> transmission::interest::Provider for ApplicationS
     ) -> transmission::interest::Result {
         self.ack_manager.transmission_interest(query)?;
         self.ping.transmission_interest(query)?;
+        self.ack_frequency.transmission_interest(query)?;
+        self.new_token.transmission_interest(query)?;
         self.recovery_manager.transmission_interest(query)?;
+        self.path_spaces.transmission_interest(query)?;
         self.stream_manager.transmission_interest(query)?;
         self.datagram_manager.transmission_interest(query)?;
         Ok(())
@@ -597,6 +1143,9 @@ impl<Config: endpoint::Config> connection::finalization::Provider for Applicatio
 
 struct RecoveryContext<'a, Config: endpoint::Config> {
     ack_manager: &'a mut AckManager,
+    rx_spaces: &'a mut [RxSpace],
+    ack_frequency: &'a mut AckFrequencySync,
+    new_token: &'a mut new_token::Manager<Config>,
     handshake_status: &'a mut HandshakeStatus,
     ping: &'a mut flag::Ping,
     stream_manager: &'a mut Config::StreamManager,
@@ -650,6 +1199,8 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
         self.handshake_status
             .on_packet_ack(packet_number_range, publisher);
         self.ping.on_packet_ack(packet_number_range);
+        self.ack_frequency.on_packet_ack(packet_number_range);
+        self.new_token.on_packet_ack(packet_number_range);
         self.stream_manager.on_packet_ack(packet_number_range);
         self.local_id_registry.on_packet_ack(packet_number_range);
         self.path_manager.on_packet_ack(packet_number_range);
@@ -658,6 +1209,11 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
     fn on_packet_ack(&mut self, timestamp: Timestamp, packet_number_range: &PacketNumberRange) {
         self.ack_manager
             .on_packet_ack(timestamp, packet_number_range);
+        for space in self.rx_spaces.iter_mut() {
+            space
+                .ack_manager
+                .on_packet_ack(timestamp, packet_number_range);
+        }
     }
 
     fn on_packet_loss<Pub: event::ConnectionPublisher>(
@@ -666,9 +1222,14 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
         publisher: &mut Pub,
     ) {
         self.ack_manager.on_packet_loss(packet_number_range);
+        for space in self.rx_spaces.iter_mut() {
+            space.ack_manager.on_packet_loss(packet_number_range);
+        }
         self.handshake_status
             .on_packet_loss(packet_number_range, publisher);
         self.ping.on_packet_loss(packet_number_range);
+        self.ack_frequency.on_packet_loss(packet_number_range);
+        self.new_token.on_packet_loss(packet_number_range);
         self.stream_manager.on_packet_loss(packet_number_range);
         self.local_id_registry.on_packet_loss(packet_number_range);
         self.path_manager.on_packet_loss(packet_number_range);
@@ -730,6 +1291,88 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         )
     }
 
+    fn handle_ack_mp_frame<A: AckRanges, Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: AckMp<A>,
+        timestamp: Timestamp,
+        path_id: path::Id,
+        path_manager: &mut path::Manager<Config>,
+        packet_number: PacketNumber,
+        handshake_status: &mut HandshakeStatus,
+        local_id_registry: &mut connection::LocalIdRegistry,
+        random_generator: &mut Config::RandomGenerator,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        // ACK_MP frames are only sent if each path uses its own packet number space
+        if !path_manager.has_path_packet_number_spaces() {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason(Self::INVALID_FRAME_ERROR)
+                .with_frame_type(frame.frame_type()));
+        }
+
+        // The path identifier 0 refers to the application data space, which is also
+        // acknowledged with ACK frames
+        if frame.path_identifier == VarInt::from_u8(0) {
+            return self.handle_ack_frame(
+                frame.ack,
+                timestamp,
+                path_id,
+                path_manager,
+                packet_number,
+                handshake_status,
+                local_id_registry,
+                random_generator,
+                publisher,
+            );
+        }
+
+        let path_identifier = frame
+            .path_identifier
+            .as_u64()
+            .try_into()
+            .map_err(|_err| transport::Error::PROTOCOL_VIOLATION)?;
+        let ack = path_space::tracked_ack(path_identifier, frame.ack)?;
+
+        // The space was discarded after all of its packets were acknowledged or declared
+        // lost, so there is nothing left to acknowledge
+        let index = if let Some(index) = self.path_spaces.find_tx_space(path_identifier) {
+            index
+        } else {
+            return Ok(());
+        };
+
+        path_manager[path_id].on_peer_validated();
+
+        // The congestion controller and RTT estimator of the path that sent the packets are
+        // updated, which may not be the path the frame was received on
+        let space_path_id = self.path_spaces.tx[index].path_id;
+        let (recovery_manager, mut context) = self.recovery_for_space(
+            Some(index),
+            handshake_status,
+            local_id_registry,
+            space_path_id,
+            path_manager,
+        );
+
+        recovery_manager.on_ack_frame(
+            timestamp,
+            ack,
+            packet_number,
+            random_generator,
+            &mut context,
+            publisher,
+        )?;
+
+        self.discard_unused_path_spaces(
+            handshake_status,
+            local_id_registry,
+            path_manager,
+            publisher,
+        );
+
+        Ok(())
+    }
+
     fn handle_connection_close_frame(
         &mut self,
         _frame: ConnectionClose,
@@ -814,7 +1457,33 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
                 .with_reason(Self::INVALID_FRAME_ERROR)
                 .with_frame_type(frame.tag().into()));
         }
-        // TODO add support for NEW_TOKEN_FRAMEs on the client
+
+        self.new_token.on_new_token_frame(&frame);
+        Ok(())
+    }
+
+    fn handle_ack_frequency_frame(&mut self, frame: AckFrequency) -> Result<(), transport::Error> {
+        self.ack_manager.on_ack_frequency_frame(&frame)?;
+
+        for space in &mut self.path_spaces.rx {
+            space.ack_manager.on_ack_frequency_frame(&frame)?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_immediate_ack_frame(&mut self, frame: ImmediateAck) -> Result<(), transport::Error> {
+        // Receiving an IMMEDIATE_ACK frame when the min_ack_delay transport parameter was
+        // not advertised is a PROTOCOL_VIOLATION.
+        //
+        // See https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency#section-3
+        if self.ack_manager.ack_settings.min_ack_delay.is_none() {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason(Self::INVALID_FRAME_ERROR)
+                .with_frame_type(frame.tag().into()));
+        }
+
+        // The ACK frame is triggered once the packet is processed
         Ok(())
     }
 
@@ -889,7 +1558,13 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
                 path.rtt_estimator.smoothed_rtt(),
                 datagram.timestamp,
             )
-            .map_err(|err| transport::Error::PROTOCOL_VIOLATION.with_reason(err.message()))
+            .map_err(|err| transport::Error::PROTOCOL_VIOLATION.with_reason(err.message()))?;
+
+        // The peer no longer sends packets in the space of the retired connection id
+        self.path_spaces
+            .on_local_connection_id_retired(sequence_number);
+
+        Ok(())
     }
 
     fn handle_path_challenge_frame(
@@ -912,6 +1587,22 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         Ok(())
     }
 
+    fn handle_path_abandon_frame<Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: PathAbandon,
+        path_manager: &mut path::Manager<Config>,
+        local_id_registry: &mut connection::LocalIdRegistry,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        if !path_manager.is_multipath_enabled() {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason(Self::INVALID_FRAME_ERROR)
+                .with_frame_type(frame.frame_type()));
+        }
+
+        path_manager.on_path_abandon(&frame, local_id_registry, publisher)
+    }
+
     fn handle_handshake_done_frame<Pub: event::ConnectionPublisher>(
         &mut self,
         frame: HandshakeDone,
@@ -950,12 +1641,19 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         path: &Path<Config>,
         publisher: &mut Pub,
     ) -> Result<(), transport::Error> {
-        self.ack_manager.on_processed_packet(
-            &processed_packet,
-            path_event!(path, path_id),
-            publisher,
-        );
-        self.processed_packet_numbers
+        let path_identifier = core::mem::take(&mut self.rx_path_identifier);
+
+        let (ack_manager, processed_packet_numbers) = if path_identifier == 0 {
+            (&mut self.ack_manager, &mut self.processed_packet_numbers)
+        } else {
+            let space = self
+                .path_spaces
+                .rx_space_mut(path_identifier, &self.ack_manager);
+            (&mut space.ack_manager, &mut space.processed_packet_numbers)
+        };
+
+        ack_manager.on_processed_packet(&processed_packet, path_event!(path, path_id), publisher);
+        processed_packet_numbers
             .insert(processed_packet.packet_number)
             .expect("packet number was already checked");
 
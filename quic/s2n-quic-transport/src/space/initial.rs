@@ -15,7 +15,7 @@ use core::{fmt, marker::PhantomData};
 use s2n_codec::EncoderBuffer;
 use s2n_quic_core::{
     connection::PeerId,
-    crypto::{tls, CryptoSuite, InitialKey},
+    crypto::{tls, tls::client_hello::ClientHello, CryptoSuite, InitialKey, Key as _},
     event::{self, ConnectionPublisher as _, IntoEvent},
     frame::{ack::AckRanges, crypto::CryptoRef, Ack, ConnectionClose},
     inet::DatagramInfo,
@@ -44,14 +44,34 @@ pub struct InitialSpace<Config: endpoint::Config> {
     pub crypto_stream: CryptoStream,
     pub tx_packet_numbers: TxPacketNumbers,
     pub received_hello_message: bool,
-    //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.3
-    //# Subsequent Initial packets from the client include the connection ID
-    //# and token values from the Retry packet.
-    retry_token: Vec<u8>,
+    /// The token included in Initial packets sent by the client
+    ///
+    /// This is either a token from a NEW_TOKEN frame on a previous connection or the token from
+    /// a Retry packet.
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+    //# The client
+    //# MUST include the token in all Initial packets it sends, unless a
+    //# Retry replaces the token with a newer one.
+    token: Vec<u8>,
+    /// The keys for the client's original version, if the server switched to a compatible
+    /// version
+    original_version: Option<OriginalVersion<Config>>,
     processed_packet_numbers: SlidingWindow,
     recovery_manager: recovery::Manager<Config>,
 }
 
+/// The keys used by a server to receive Initial packets that the client sent with its
+/// original version
+///
+/// After a server switches to a compatible version, the client keeps using its original
+/// version until it has processed the server's first Initial packet. The keys are discarded
+/// along with the rest of the Initial space, once the server processes a Handshake packet.
+struct OriginalVersion<Config: endpoint::Config> {
+    version: u32,
+    key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
+    header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
+}
+
 impl<Config: endpoint::Config> fmt::Debug for InitialSpace<Config> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("InitialSpace")
@@ -77,12 +97,19 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             crypto_stream: CryptoStream::new(),
             tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::Initial, now),
             received_hello_message: false,
-            retry_token: Vec::new(),
+            token: Vec::new(),
+            original_version: None,
             processed_packet_numbers: SlidingWindow::default(),
             recovery_manager: recovery::Manager::new(PacketNumberSpace::Initial),
         }
     }
 
+    /// Sets the token from a NEW_TOKEN frame which is included in Initial packets
+    pub fn set_token(&mut self, token: &[u8]) {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+        self.token = token.to_vec();
+    }
+
     /// This method gets called when a Retry packet is processed.
     ///
     /// Reset the TLS stack and recover state when the first Retry packet is processed.
@@ -96,13 +123,18 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         publisher: &mut Pub,
     ) {
         debug_assert!(Config::ENDPOINT_TYPE.is_client());
-        self.retry_token = retry_token.to_vec();
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.3
+        //# Subsequent Initial packets from the client include the connection ID
+        //# and token values from the Retry packet.
+        self.token = retry_token.to_vec();
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.2
         //# Changing the Destination Connection ID field also results in
         //# a change to the keys used to protect the Initial packet.
         let (initial_key, initial_header_key) =
                             <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_client(
+                                publisher.quic_version(),
                                 retry_source_connection_id.as_bytes(),
                             );
 
@@ -122,6 +154,91 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             .on_retry_packet(path, path_id, publisher);
     }
 
+    /// This method gets called when the client switches to the version chosen by the server.
+    ///
+    /// The Initial keys are derived again for the new version using the Destination Connection ID
+    /// the client is currently sending.
+    pub fn on_version_upgrade<Pub: event::ConnectionPublisher>(
+        &mut self,
+        peer_connection_id: &PeerId,
+        publisher: &mut Pub,
+    ) {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        let (initial_key, initial_header_key) =
+            <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_client(
+                publisher.quic_version(),
+                peer_connection_id.as_bytes(),
+            );
+
+        publisher.on_key_update(event::builder::KeyUpdate {
+            key_type: event::builder::KeyType::Initial,
+            cipher_suite: initial_key.cipher_suite().into_event(),
+        });
+
+        self.key = initial_key;
+        self.header_key = initial_header_key;
+    }
+
+    /// This method gets called when a server switched to a version that is compatible with
+    /// the client's original version.
+    ///
+    /// The keys derived for the negotiated version replace the current keys. Initial packets
+    /// that still use the original version are accepted with the keys derived for that version.
+    pub fn on_compatible_version<Pub: event::ConnectionPublisher>(
+        &mut self,
+        original_version: u32,
+        key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
+        header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
+        publisher: &mut Pub,
+    ) {
+        debug_assert!(Config::ENDPOINT_TYPE.is_server());
+
+        publisher.on_key_update(event::builder::KeyUpdate {
+            key_type: event::builder::KeyType::Initial,
+            cipher_suite: key.cipher_suite().into_event(),
+        });
+
+        self.original_version = Some(OriginalVersion {
+            version: original_version,
+            key: core::mem::replace(&mut self.key, key),
+            header_key: core::mem::replace(&mut self.header_key, header_key),
+        });
+    }
+
+    /// Calls `f` with the client's ClientHello message once it has been fully received
+    ///
+    /// The message is decoded in place if it's contiguous in the crypto stream and is only
+    /// copied when it's split across the buffers of the stream.
+    pub fn with_client_hello<F: FnOnce(&ClientHello) -> R, R>(&self, f: F) -> Option<R> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_server());
+
+        if !self.received_hello_message {
+            return None;
+        }
+
+        let crypto_stream = &self.crypto_stream.rx;
+        debug_assert_eq!(crypto_stream.consumed_len(), 0);
+
+        if let Some(client_hello) = crypto_stream.iter().next().and_then(ClientHello::decode) {
+            return Some(f(&client_hello));
+        }
+
+        let message: Vec<u8> = crypto_stream.iter().flatten().copied().collect();
+
+        // A malformed ClientHello is rejected by the TLS provider once the session is created
+        let client_hello = ClientHello::decode(&message).unwrap_or_default();
+
+        Some(f(&client_hello))
+    }
+
+    /// Returns `true` if Initial packets with the client's original `version` are accepted
+    pub fn accepts_original_version(&self, version: u32) -> bool {
+        self.original_version
+            .as_ref()
+            .map_or(false, |original| original.version == version)
+    }
+
     /// Returns true if the packet number has already been processed
     pub fn is_duplicate<Pub: event::ConnectionPublisher>(
         &self,
@@ -193,7 +310,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             version: context.quic_version,
             destination_connection_id,
             source_connection_id: context.path_manager[context.path_id].local_connection_id,
-            token: self.retry_token.as_slice(),
+            token: self.token.as_slice(),
             packet_number,
             payload,
         };
@@ -391,9 +508,18 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         path: &path::Path<Config>,
         publisher: &mut Pub,
     ) -> Result<CleartextInitial<'a>, ProcessingError> {
+        // Packets with the client's original version are protected with the keys for that
+        // version
+        let (key, header_key) = match &self.original_version {
+            Some(original) if original.version == protected.version => {
+                (&original.key, &original.header_key)
+            }
+            _ => (&self.key, &self.header_key),
+        };
+
         let packet_number_decoder = self.packet_number_decoder();
         let packet = protected
-            .unprotect(&self.header_key, packet_number_decoder)
+            .unprotect(header_key, packet_number_decoder)
             .map_err(|err| {
                 publisher.on_packet_dropped(event::builder::PacketDropped {
                     reason: event::builder::PacketDropReason::UnprotectFailed {
@@ -408,9 +534,8 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             return Err(ProcessingError::DuplicatePacket);
         }
 
-        let packet_header =
-            event::builder::PacketHeader::new(packet.packet_number, publisher.quic_version());
-        let decrypted = packet.decrypt(&self.key).map_err(|err| {
+        let packet_header = event::builder::PacketHeader::new(packet.packet_number, packet.version);
+        let decrypted = packet.decrypt(key).map_err(|err| {
             publisher.on_packet_dropped(event::builder::PacketDropped {
                 reason: event::builder::PacketDropReason::DecryptionFailed {
                     packet_header,
@@ -495,7 +620,9 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         }
 
         // wait until we have more chunks
-        if crypto_stream.total_received_len() < len {
+        if crypto_stream.total_received_len()
+            < len + core::mem::size_of::<tls::HandshakeHeader>() as u64
+        {
             return Ok(None);
         }
 
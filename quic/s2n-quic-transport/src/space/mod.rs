@@ -18,19 +18,26 @@ use core::{
 use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
     application::ServerName,
-    connection::{limits::Limits, InitialId, PeerId},
-    crypto::{tls, tls::Session, CryptoSuite, Key},
+    connection::{limits::Limits, InitialId, LocalId, PeerId},
+    crypto::{tls, tls::Endpoint as _, tls::Session, CryptoSuite, InitialKey, Key},
+    early_data::{self, Controller as _},
     event::{self, IntoEvent},
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
-        ConnectionClose, DataBlocked, HandshakeDone, MaxData, MaxStreamData, MaxStreams,
-        NewConnectionId, NewToken, PathChallenge, PathResponse, ResetStream, RetireConnectionId,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        AckFrequency, AckMp, ConnectionClose, DataBlocked, HandshakeDone, ImmediateAck, MaxData,
+        MaxStreamData, MaxStreams, NewConnectionId, NewToken, PathAbandon, PathChallenge,
+        PathResponse, ResetStream, RetireConnectionId, StopSending, StreamDataBlocked,
+        StreamsBlocked,
     },
     inet::DatagramInfo,
     packet::number::{PacketNumber, PacketNumberSpace},
+    path::RemoteAddress,
     time::{timer, Timestamp},
-    transport,
+    transport::{
+        self,
+        parameters::{ServerTransportParameters, VersionInformation, ZeroRttParameters},
+    },
+    version,
 };
 
 mod application;
@@ -40,6 +47,8 @@ mod handshake;
 mod handshake_status;
 mod initial;
 mod keep_alive;
+pub(crate) mod new_token;
+pub(crate) mod path_space;
 mod session_context;
 mod tx_packet_numbers;
 
@@ -51,20 +60,55 @@ pub(crate) use initial::InitialSpace;
 pub(crate) use session_context::SessionContext;
 pub(crate) use tx_packet_numbers::TxPacketNumbers;
 
+/// The keys used to protect 0-RTT packets
+pub struct ZeroRttCrypto<Config: endpoint::Config> {
+    pub key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey,
+    pub header_key:
+        <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttHeaderKey,
+}
+
 struct SessionInfo<Config: endpoint::Config> {
     session: <Config::TLSEndpoint as tls::Endpoint>::Session,
     initial_cid: InitialId,
 }
 
+/// The state a server needs to create its TLS session once the ClientHello has been received
+///
+/// The QUIC version and the early data outcome are decided from the ClientHello, which can span
+/// multiple Initial packets, so the session can't be created along with the connection.
+pub struct PendingSession {
+    pub initial_cid: InitialId,
+    /// The Destination Connection ID the client's Initial keys were derived from
+    pub initial_key_id: LocalId,
+    /// The server's transport parameters, without the version_information parameter
+    pub transport_parameters: ServerTransportParameters,
+}
+
 pub struct PacketSpaceManager<Config: endpoint::Config> {
     session_info: Option<SessionInfo<Config>>,
+    /// Set on the server until the whole ClientHello has been received
+    pending_session: Option<Box<PendingSession>>,
+    /// The TLS session after the handshake completes, which is kept to export keying material
+    completed_session: Option<<Config::TLSEndpoint as tls::Endpoint>::Session>,
     retry_cid: Option<Box<PeerId>>,
     initial: Option<Box<InitialSpace<Config>>>,
     handshake: Option<Box<HandshakeSpace<Config>>>,
     application: Option<Box<ApplicationSpace<Config>>>,
-    zero_rtt_crypto:
-        Option<Box<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey>>,
+    /// The 0-RTT keys used by the server to open packets from the client
+    ///
+    /// Clients store their 0-RTT keys in the `ApplicationSpace` since they are only used to send
+    /// packets.
+    zero_rtt_crypto: Option<Box<ZeroRttCrypto<Config>>>,
+    /// Discards the server's 0-RTT keys after the first 1-RTT packet has been received
+    zero_rtt_discard_timer: timer::Timer,
+    /// Set on the client when the server accepted the early data sent by the client
+    early_data_accepted: bool,
+    /// The transport parameters remembered by the client from a previous connection
+    zero_rtt_parameters: Option<ZeroRttParameters>,
     handshake_status: HandshakeStatus,
+    /// Sends or receives NEW_TOKEN frames, which is moved to the application space once it's
+    /// created
+    new_token: new_token::Manager<Config>,
     /// Server Name Indication
     pub server_name: Option<ServerName>,
     //= https://www.rfc-editor.org/rfc/rfc9000#section-7
@@ -75,6 +119,8 @@ pub struct PacketSpaceManager<Config: endpoint::Config> {
     //# another mechanism is used for agreeing on an application protocol,
     //# endpoints MUST use ALPN for this purpose.
     pub application_protocol: Bytes,
+    /// The DER-encoded certificates presented by the peer
+    pub peer_certificate_chain: Vec<Bytes>,
 }
 
 impl<Config: endpoint::Config> fmt::Debug for PacketSpaceManager<Config> {
@@ -146,6 +192,43 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
         now: Timestamp,
         publisher: &mut Pub,
+    ) -> Self {
+        let session_info = Some(SessionInfo {
+            session,
+            initial_cid,
+        });
+        Self::with_session(session_info, None, initial_key, header_key, now, publisher)
+    }
+
+    /// Creates the packet spaces for a server, which creates the TLS session once the
+    /// ClientHello has been received
+    pub fn new_server<Pub: event::ConnectionPublisher>(
+        pending_session: PendingSession,
+        initial_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
+        header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
+        now: Timestamp,
+        publisher: &mut Pub,
+    ) -> Self {
+        debug_assert!(Config::ENDPOINT_TYPE.is_server());
+
+        let pending_session = Some(Box::new(pending_session));
+        Self::with_session(
+            None,
+            pending_session,
+            initial_key,
+            header_key,
+            now,
+            publisher,
+        )
+    }
+
+    fn with_session<Pub: event::ConnectionPublisher>(
+        session_info: Option<SessionInfo<Config>>,
+        pending_session: Option<Box<PendingSession>>,
+        initial_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
+        header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
+        now: Timestamp,
+        publisher: &mut Pub,
     ) -> Self {
         let ack_manager = AckManager::new(PacketNumberSpace::Initial, ack::Settings::EARLY);
 
@@ -154,10 +237,9 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
             cipher_suite: initial_key.cipher_suite().into_event(),
         });
         Self {
-            session_info: Some(SessionInfo {
-                session,
-                initial_cid,
-            }),
+            session_info,
+            pending_session,
+            completed_session: None,
             retry_cid: None,
             initial: Some(Box::new(InitialSpace::new(
                 initial_key,
@@ -168,12 +250,22 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
             handshake: None,
             application: None,
             zero_rtt_crypto: None,
+            zero_rtt_discard_timer: Default::default(),
+            early_data_accepted: false,
+            zero_rtt_parameters: None,
             handshake_status: HandshakeStatus::default(),
+            new_token: new_token::Manager::default(),
             server_name: None,
             application_protocol: Bytes::new(),
+            peer_certificate_chain: Vec::new(),
         }
     }
 
+    /// Configures the NEW_TOKEN frames sent or received on the connection
+    pub fn set_new_token(&mut self, new_token: new_token::Manager<Config>) {
+        self.new_token = new_token;
+    }
+
     packet_space_api!(InitialSpace<Config>, initial, initial_mut, discard_initial);
 
     packet_space_api!(
@@ -186,15 +278,148 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
 
     packet_space_api!(ApplicationSpace<Config>, application, application_mut);
 
-    #[allow(dead_code)] // 0RTT hasn't been started yet
-    pub fn zero_rtt_crypto(
+    /// Returns the application space along with the 0-RTT keys used to open early data
+    pub fn zero_rtt_mut(
+        &mut self,
+    ) -> Option<(&mut ApplicationSpace<Config>, &ZeroRttCrypto<Config>)> {
+        let space = self.application.as_mut().map(Box::as_mut)?;
+        let crypto = self.zero_rtt_crypto.as_ref().map(Box::as_ref)?;
+        Some((space, crypto))
+    }
+
+    /// Fills `output` with keying material exported from the TLS session
+    ///
+    /// Returns `None` if the handshake hasn't completed or the connection is closed.
+    pub fn export_keying_material(
         &self,
-    ) -> Option<&<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey> {
-        self.zero_rtt_crypto.as_ref().map(Box::as_ref)
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Option<Result<(), transport::Error>> {
+        let session = self.completed_session.as_ref()?;
+        Some(session.export_keying_material(label, context, output))
     }
 
     pub fn discard_zero_rtt_crypto(&mut self) {
         self.zero_rtt_crypto = None;
+        self.zero_rtt_discard_timer.cancel();
+    }
+
+    /// Called on the server when a 1-RTT packet has been processed
+    pub fn on_one_rtt_packet(&mut self, path: &Path<Config>, now: Timestamp) {
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9.3
+        //# Servers MAY temporarily retain 0-RTT keys to allow decrypting
+        //# reordered packets without requiring their contents to be
+        //# retransmitted with 1-RTT keys.  After receiving a 1-RTT packet,
+        //# servers MUST discard 0-RTT keys within a short time; the RECOMMENDED
+        //# time period is three times the Probe Timeout (PTO, see
+        //# [QUIC-RECOVERY]).
+        if self.zero_rtt_crypto.is_some() && !self.zero_rtt_discard_timer.is_armed() {
+            let pto = path.pto_period(PacketNumberSpace::ApplicationData);
+            self.zero_rtt_discard_timer.set(now + pto * 3);
+        }
+    }
+
+    /// Creates the server's TLS session once the whole ClientHello has been received
+    ///
+    /// The QUIC version is negotiated and the early data controller is consulted before the
+    /// session is created, so the handshake only accepts early data that the controller allows.
+    /// Returns the negotiated version and the early data outcome once the session is created.
+    pub fn on_client_hello<Pub: event::ConnectionPublisher>(
+        &mut self,
+        remote_address: &RemoteAddress,
+        quic_versions: &version::Preferences,
+        tls_endpoint: &mut Config::TLSEndpoint,
+        early_data_controller: &mut Config::EarlyDataController,
+        now: Timestamp,
+        publisher: &mut Pub,
+    ) -> Option<(u32, Option<early_data::Outcome>)> {
+        if self.pending_session.is_none() {
+            return None;
+        }
+
+        let space = self.initial.as_deref_mut()?;
+
+        let original_version = publisher.quic_version();
+
+        let (quic_version, early_data) = space.with_client_hello(|client_hello| {
+            // Only negotiate if there are other versions the connection could use
+            let quic_version = if quic_versions.versions().len() > 1 {
+                client_hello.version_information.map(|info| {
+                    quic_versions
+                        .negotiate(original_version, info.available_versions().iter().copied())
+                })
+            } else {
+                Some(original_version)
+            };
+
+            let early_data = if client_hello.early_data {
+                let server_name = client_hello.server_name.map(ServerName::from);
+                let attempt = early_data::Attempt::new(
+                    remote_address,
+                    server_name.as_ref(),
+                    client_hello.application_protocols,
+                    now.into_event(),
+                );
+                Some(early_data_controller.on_early_data(&attempt))
+            } else {
+                None
+            };
+
+            (quic_version, early_data)
+        })?;
+
+        // Without the client's version_information, the server can't tell which versions the
+        // client supports, so the connection stays on the original version
+        let quic_version = quic_version.unwrap_or_else(|| {
+            publisher.on_version_upgrade_skipped(event::builder::VersionUpgradeSkipped {
+                version: original_version,
+            });
+            original_version
+        });
+
+        let PendingSession {
+            initial_cid,
+            initial_key_id,
+            mut transport_parameters,
+        } = *self.pending_session.take()?;
+
+        transport_parameters.version_information = Some(VersionInformation::new(
+            quic_version,
+            quic_versions.versions(),
+        ));
+
+        let session = if let Some(early_data::Outcome::Accept) = early_data {
+            tls_endpoint.new_server_session(&transport_parameters)
+        } else {
+            //= https://www.rfc-editor.org/rfc/rfc9001#section-4.6.2
+            //# A server rejects 0-RTT by sending the EncryptedExtensions without an
+            //# early_data extension.
+            tls_endpoint.new_server_session_without_early_data(&transport_parameters)
+        };
+
+        self.session_info = Some(SessionInfo {
+            session,
+            initial_cid,
+        });
+
+        // The rest of the connection uses the keys for the negotiated version. The keys for the
+        // original version are kept to receive any Initial packets the client sends before it
+        // switches to the negotiated version.
+        if quic_version != original_version {
+            let (key, header_key) =
+                <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_server(
+                    quic_version,
+                    initial_key_id.as_bytes(),
+                );
+            space.on_compatible_version(original_version, key, header_key, publisher);
+        }
+
+        if let Some(early_data::Outcome::Reject(reason)) = early_data {
+            publisher.on_early_data_rejected(reason.into_event());
+        }
+
+        Some((quic_version, early_data))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -203,11 +428,17 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         path_manager: &mut path::Manager<Config>,
         local_id_registry: &mut connection::LocalIdRegistry,
         limits: &mut Limits,
+        quic_versions: &version::Preferences,
         now: Timestamp,
         waker: &Waker,
         publisher: &mut Pub,
         datagram: &mut Config::DatagramEndpoint,
     ) -> Poll<Result<(), transport::Error>> {
+        // The server's session is created once the whole ClientHello has been received
+        if self.pending_session.is_some() {
+            return Poll::Pending;
+        }
+
         if let Some(session_info) = self.session_info.as_mut() {
             let mut context: SessionContext<Config, Pub> = SessionContext {
                 now,
@@ -217,12 +448,17 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 handshake: &mut self.handshake,
                 application: &mut self.application,
                 zero_rtt_crypto: &mut self.zero_rtt_crypto,
+                zero_rtt_parameters: &mut self.zero_rtt_parameters,
+                early_data_accepted: &mut self.early_data_accepted,
                 path_manager,
                 handshake_status: &mut self.handshake_status,
+                new_token: &mut self.new_token,
                 local_id_registry,
                 limits,
+                quic_versions,
                 server_name: &mut self.server_name,
                 application_protocol: &mut self.application_protocol,
+                peer_certificate_chain: &mut self.peer_certificate_chain,
                 waker,
                 publisher,
                 datagram,
@@ -230,8 +466,9 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
 
             match session_info.session.poll(&mut context)? {
                 Poll::Ready(_success) => {
-                    // The TLS session and retry_cid is no longer needed
-                    self.session_info = None;
+                    // The retry_cid is no longer needed and the TLS session is only used to
+                    // export keying material
+                    self.completed_session = self.session_info.take().map(|info| info.session);
                     self.retry_cid = None;
                 }
                 Poll::Pending => return Poll::Pending,
@@ -250,6 +487,14 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         timestamp: Timestamp,
         publisher: &mut Pub,
     ) {
+        if self
+            .zero_rtt_discard_timer
+            .poll_expiration(timestamp)
+            .is_ready()
+        {
+            self.discard_zero_rtt_crypto();
+        }
+
         let path_id = path_manager.active_path_id();
         let path = path_manager.active_path_mut();
 
@@ -293,7 +538,12 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
 
     /// Signals the connection was previously blocked by anti-amplification limits
     /// but is now no longer limited.
-    pub fn on_amplification_unblocked(&mut self, path: &Path<Config>, timestamp: Timestamp) {
+    pub fn on_amplification_unblocked(
+        &mut self,
+        path_id: path::Id,
+        path: &Path<Config>,
+        timestamp: Timestamp,
+    ) {
         if let Some((space, handshake_status)) = self.initial_mut() {
             space.on_amplification_unblocked(path, timestamp, handshake_status.is_confirmed());
         }
@@ -303,7 +553,12 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         }
 
         if let Some((space, handshake_status)) = self.application_mut() {
-            space.on_amplification_unblocked(path, timestamp, handshake_status.is_confirmed());
+            space.on_amplification_unblocked(
+                path_id,
+                path,
+                timestamp,
+                handshake_status.is_confirmed(),
+            );
         }
     }
 
@@ -424,6 +679,8 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         publisher: &mut Pub,
     ) {
         self.session_info = None;
+        self.pending_session = None;
+        self.completed_session = None;
         self.retry_cid = None;
         self.discard_initial(path, path_id, publisher);
         self.discard_handshake(path, path_id, publisher);
@@ -468,6 +725,7 @@ impl<Config: endpoint::Config> timer::Provider for PacketSpaceManager<Config> {
         if let Some(space) = self.initial.as_ref() {
             space.timers(query)?;
         }
+        self.zero_rtt_discard_timer.timers(query)?;
         Ok(())
     }
 }
@@ -478,6 +736,12 @@ impl<Config: endpoint::Config> transmission::interest::Provider for PacketSpaceM
         &self,
         query: &mut Q,
     ) -> transmission::interest::Result {
+        // Nothing is sent until the server has negotiated the QUIC version, since the client
+        // follows the version of the first Initial packet from the server
+        if self.pending_session.is_some() {
+            return Ok(());
+        }
+
         if let Some(space) = self.application.as_ref() {
             space.transmission_interest(query)?;
         }
@@ -636,6 +900,38 @@ pub trait PacketSpace<Config: endpoint::Config> {
             .with_frame_type(frame.tag().into()))
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn handle_ack_mp_frame<A: AckRanges, Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: AckMp<A>,
+        _timestamp: Timestamp,
+        _path_id: path::Id,
+        _path_manager: &mut path::Manager<Config>,
+        _packet_number: PacketNumber,
+        _handshake_status: &mut HandshakeStatus,
+        _local_id_registry: &mut connection::LocalIdRegistry,
+        _random_generator: &mut Config::RandomGenerator,
+        _publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        // ACK_MP frames acknowledge packets in the packet number space of a path, which are
+        // only sent with 1-RTT packets
+        Err(transport::Error::PROTOCOL_VIOLATION
+            .with_reason(Self::INVALID_FRAME_ERROR)
+            .with_frame_type(frame.frame_type()))
+    }
+
+    fn handle_path_abandon_frame<Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: PathAbandon,
+        _path_manager: &mut path::Manager<Config>,
+        _local_id_registry: &mut connection::LocalIdRegistry,
+        _publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        Err(transport::Error::PROTOCOL_VIOLATION
+            .with_reason(Self::INVALID_FRAME_ERROR)
+            .with_frame_type(frame.frame_type()))
+    }
+
     default_frame_handler!(handle_data_blocked_frame, DataBlocked);
     default_frame_handler!(handle_max_data_frame, MaxData);
     default_frame_handler!(handle_max_stream_data_frame, MaxStreamData);
@@ -645,6 +941,8 @@ pub trait PacketSpace<Config: endpoint::Config> {
     default_frame_handler!(handle_stream_data_blocked_frame, StreamDataBlocked);
     default_frame_handler!(handle_streams_blocked_frame, StreamsBlocked);
     default_frame_handler!(handle_new_token_frame, NewToken);
+    default_frame_handler!(handle_ack_frequency_frame, AckFrequency);
+    default_frame_handler!(handle_immediate_ack_frame, ImmediateAck);
 
     fn on_processed_packet<Pub: event::ConnectionPublisher>(
         &mut self,
@@ -691,10 +989,14 @@ pub trait PacketSpace<Config: endpoint::Config> {
         let mut processed_packet = ProcessedPacket::new(packet_number, datagram);
 
         macro_rules! on_frame_processed {
-            ($frame:ident) => {{
-                let frame_type = $frame.tag();
+            ($frame:ident) => {
+                on_frame_processed!($frame, VarInt::from_u8($frame.tag()))
+            };
+            // frames from extensions can have types that don't fit in the tag
+            ($frame:ident, $frame_type:expr) => {{
+                let frame_type = $frame_type;
                 processed_packet.on_processed_frame(&$frame);
-                move |err: transport::Error| err.with_frame_type(VarInt::from_u8(frame_type))
+                move |err: transport::Error| err.with_frame_type(frame_type)
             }};
         }
 
@@ -867,6 +1169,43 @@ pub trait PacketSpace<Config: endpoint::Config> {
                     )
                     .map_err(on_error)?;
                 }
+                Frame::AckFrequency(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_ack_frequency_frame(frame).map_err(on_error)?;
+                }
+                Frame::ImmediateAck(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_immediate_ack_frame(frame).map_err(on_error)?;
+
+                    // An IMMEDIATE_ACK frame requests the peer to send an ACK frame
+                    // immediately, regardless of the current ack-eliciting threshold
+                    processed_packet.immediate_ack = true;
+                }
+                Frame::AckMp(frame) => {
+                    let on_error = on_frame_processed!(frame, frame.frame_type());
+                    self.handle_ack_mp_frame(
+                        frame,
+                        datagram.timestamp,
+                        path_id,
+                        path_manager,
+                        packet_number,
+                        handshake_status,
+                        local_id_registry,
+                        random_generator,
+                        publisher,
+                    )
+                    .map_err(on_error)?;
+                }
+                Frame::PathAbandon(frame) => {
+                    let on_error = on_frame_processed!(frame, frame.frame_type());
+                    self.handle_path_abandon_frame(
+                        frame,
+                        path_manager,
+                        local_id_registry,
+                        publisher,
+                    )
+                    .map_err(on_error)?;
+                }
             }
 
             payload = remaining;
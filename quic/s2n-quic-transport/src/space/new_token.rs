@@ -0,0 +1,291 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Issues and receives address validation tokens in NEW_TOKEN frames
+//!
+//! Servers generate a single token once the handshake is confirmed, which means the address of
+//! the client has been validated, and send it to the client. Clients pass the
+//! tokens they receive to the token store of the endpoint, which includes them in the Initial
+//! packets of future connections to the same server.
+
+use crate::{contexts::WriteContext, endpoint, transmission};
+use alloc::vec::Vec;
+use s2n_quic_core::{
+    ack, application::ServerName, frame::NewToken, packet::number::PacketNumber, token::Store as _,
+};
+
+pub enum Manager<Config: endpoint::Config> {
+    /// NEW_TOKEN frames are not sent or stored
+    Disabled,
+    /// The server sends a token to the client
+    Sender {
+        token: Vec<u8>,
+        delivery: DeliveryState,
+    },
+    /// The client stores the tokens sent by the server
+    Receiver {
+        server_name: ServerName,
+        store: Config::TokenStore,
+    },
+}
+
+impl<Config: endpoint::Config> Default for Manager<Config> {
+    fn default() -> Self {
+        Self::Disabled
+    }
+}
+
+impl<Config: endpoint::Config> core::fmt::Debug for Manager<Config> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Disabled => f.write_str("Disabled"),
+            Self::Sender { delivery, .. } => f
+                .debug_struct("Sender")
+                .field("delivery", delivery)
+                .finish_non_exhaustive(),
+            Self::Receiver { server_name, .. } => f
+                .debug_struct("Receiver")
+                .field("server_name", server_name)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeliveryState {
+    /// The handshake has not been confirmed
+    Idle,
+    /// The handshake is confirmed and the token has yet to be generated
+    RequiresToken,
+    RequiresTransmission,
+    RequiresRetransmission,
+    InFlight(PacketNumber),
+    Delivered,
+}
+
+impl DeliveryState {
+    #[inline]
+    fn can_transmit(self, constraint: transmission::Constraint) -> bool {
+        match self {
+            Self::RequiresTransmission => constraint.can_transmit(),
+            Self::RequiresRetransmission => constraint.can_retransmit(),
+            _ => false,
+        }
+    }
+}
+
+impl<Config: endpoint::Config> Manager<Config> {
+    /// Creates a manager which sends a token to the client once the handshake is confirmed
+    pub fn sender() -> Self {
+        debug_assert!(Config::ENDPOINT_TYPE.is_server());
+
+        Self::Sender {
+            token: Vec::new(),
+            delivery: DeliveryState::Idle,
+        }
+    }
+
+    /// Creates a manager which passes the tokens received from `server_name` to the `store`
+    pub fn receiver(server_name: ServerName, store: Config::TokenStore) -> Self {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        Self::Receiver { server_name, store }
+    }
+
+    /// Called when the handshake is confirmed
+    pub fn on_handshake_confirmed(&mut self) {
+        if let Self::Sender { delivery, .. } = self {
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+            //# The server uses the NEW_TOKEN frame (Section 19.7) to provide the
+            //# client with an address validation token that can be used to validate
+            //# future connections.
+            if *delivery == DeliveryState::Idle {
+                *delivery = DeliveryState::RequiresToken;
+            }
+        }
+    }
+
+    /// Returns `true` if the handshake is confirmed and the token has yet to be generated
+    #[inline]
+    pub fn requires_token(&self) -> bool {
+        matches!(
+            self,
+            Self::Sender {
+                delivery: DeliveryState::RequiresToken,
+                ..
+            }
+        )
+    }
+
+    /// Called with the token generated after the handshake was confirmed
+    ///
+    /// No token is sent if `token` is `None`, which means the token format doesn't support
+    /// NEW_TOKEN frames.
+    pub fn on_token(&mut self, new_token: Option<Vec<u8>>) {
+        if !self.requires_token() {
+            return;
+        }
+
+        match new_token {
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-19.7
+            //# The token MUST NOT be empty.
+            Some(new_token) if !new_token.is_empty() => {
+                if let Self::Sender { token, delivery } = self {
+                    *token = new_token;
+                    *delivery = DeliveryState::RequiresTransmission;
+                }
+            }
+            _ => *self = Self::Disabled,
+        }
+    }
+
+    /// Called when a NEW_TOKEN frame is received from the server
+    pub fn on_new_token_frame(&mut self, frame: &NewToken) {
+        if let Self::Receiver { server_name, store } = self {
+            store.on_new_token(server_name, frame.token);
+        }
+    }
+
+    /// Called when an outgoing packet is being assembled
+    pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) {
+        if let Self::Sender { token, delivery } = self {
+            if !delivery.can_transmit(context.transmission_constraint()) {
+                return;
+            }
+
+            let frame = NewToken {
+                token: token.as_slice(),
+            };
+
+            if let Some(packet_nr) = context.write_frame(&frame) {
+                *delivery = DeliveryState::InFlight(packet_nr);
+            }
+        }
+    }
+
+    /// This method gets called when a packet delivery got acknowledged
+    pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
+        if let Self::Sender { token, delivery } = self {
+            if let DeliveryState::InFlight(packet_nr) = *delivery {
+                if ack_set.contains(packet_nr) {
+                    *delivery = DeliveryState::Delivered;
+                    // the token is no longer needed once the client has it
+                    *token = Vec::new();
+                }
+            }
+        }
+    }
+
+    /// This method gets called when a packet loss is reported
+    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
+        if let Self::Sender { delivery, .. } = self {
+            if let DeliveryState::InFlight(packet_nr) = *delivery {
+                if ack_set.contains(packet_nr) {
+                    //= https://www.rfc-editor.org/rfc/rfc9000#section-13.3
+                    //# *  NEW_TOKEN frames are retransmitted if the packet containing them
+                    //#    is lost.
+                    *delivery = DeliveryState::RequiresRetransmission;
+                }
+            }
+        }
+    }
+}
+
+impl<Config: endpoint::Config> transmission::interest::Provider for Manager<Config> {
+    #[inline]
+    fn transmission_interest<Q: transmission::interest::Query>(
+        &self,
+        query: &mut Q,
+    ) -> transmission::interest::Result {
+        if let Self::Sender { delivery, .. } = self {
+            match delivery {
+                DeliveryState::RequiresTransmission => query.on_new_data()?,
+                DeliveryState::RequiresRetransmission => query.on_lost_data()?,
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{contexts::testing::*, transmission::interest::Provider};
+    use s2n_quic_core::{endpoint, frame::Frame, time::clock::testing as time};
+
+    type Server = crate::endpoint::testing::Server;
+
+    #[test]
+    fn sender_test() {
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut context = MockWriteContext::new(
+            time::now(),
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Server,
+        );
+
+        let mut manager = Manager::<Server>::sender();
+
+        // the token isn't generated until the handshake is confirmed
+        assert!(!manager.requires_token());
+        manager.on_token(Some(vec![4, 5, 6]));
+        assert!(!manager.has_transmission_interest());
+        manager.on_transmit(&mut context);
+        assert!(context.frame_buffer.is_empty());
+
+        manager.on_handshake_confirmed();
+        assert!(manager.requires_token());
+        assert!(!manager.has_transmission_interest());
+        manager.on_token(Some(vec![1, 2, 3]));
+        assert!(!manager.requires_token());
+        assert!(manager.has_transmission_interest());
+        manager.on_transmit(&mut context);
+
+        let mut written = context.frame_buffer.pop_front().unwrap();
+        let packet_nr = written.packet_nr;
+        match written.as_frame() {
+            Frame::NewToken(frame) => assert_eq!(frame.token, &[1, 2, 3]),
+            frame => panic!("unexpected frame {:?}", frame),
+        }
+        assert!(!manager.has_transmission_interest());
+
+        // lost tokens are retransmitted
+        manager.on_packet_loss(&packet_nr);
+        assert_eq!(
+            manager.get_transmission_interest(),
+            transmission::Interest::LostData
+        );
+        manager.on_transmit(&mut context);
+        let written = context.frame_buffer.pop_front().unwrap();
+        let packet_nr = written.packet_nr;
+
+        // the token is only sent once after it's acknowledged
+        manager.on_packet_ack(&packet_nr);
+        assert!(!manager.has_transmission_interest());
+        manager.on_handshake_confirmed();
+        manager.on_transmit(&mut context);
+        assert!(context.frame_buffer.is_empty());
+    }
+
+    #[test]
+    fn empty_token_test() {
+        let mut manager = Manager::<Server>::sender();
+        manager.on_handshake_confirmed();
+        manager.on_token(Some(vec![]));
+        assert!(!manager.requires_token());
+        assert!(!manager.has_transmission_interest());
+    }
+
+    #[test]
+    fn unsupported_token_test() {
+        let mut manager = Manager::<Server>::sender();
+        manager.on_handshake_confirmed();
+        manager.on_token(None);
+        assert!(!manager.requires_token());
+        assert!(!manager.has_transmission_interest());
+    }
+}
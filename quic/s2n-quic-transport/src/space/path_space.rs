@@ -0,0 +1,383 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Packet number spaces of the paths opened with the multipath extension
+//!
+//! If both endpoints support it, each path uses its own packet number space. Packets are
+//! numbered, protected and acknowledged in the space of their destination connection id,
+//! which is identified by the sequence number of the connection id. This sequence number is
+//! the path identifier. The space with the path identifier `0` is the application data space
+//! of the connection, which is managed by the `ApplicationSpace` itself.
+//!
+//! The connection components track the frames they send by packet number. To keep the
+//! numbers unique across spaces, a packet sent in the space of a path is tracked with the
+//! path identifier in the upper bits of the packet number. The tracked packet number is
+//! also used for the events emitted while sending the packet.
+//!
+//! See https://datatracker.ietf.org/doc/html/draft-ietf-quic-multipath-03
+
+use crate::{ack::AckManager, endpoint, path, recovery, space::TxPacketNumbers, transmission};
+use core::ops::RangeInclusive;
+use s2n_quic_core::{
+    frame::{ack::AckRanges, Ack},
+    packet::number::{PacketNumber, PacketNumberSpace, SlidingWindow},
+    time::{timer, Timestamp},
+    transport,
+    varint::VarInt,
+};
+
+/// The number of low bits of a tracked packet number that hold the packet number in the
+/// space of the path
+///
+/// This allows for more than 10^12 packets per path, which exceeds the AEAD limits of
+/// the 1-RTT keys many times over.
+const PACKET_NUMBER_BITS: u32 = 40;
+
+const PACKET_NUMBER_MASK: u64 = (1 << PACKET_NUMBER_BITS) - 1;
+
+/// The largest path identifier that fits into the 62 bits of a tracked packet number
+const MAX_PATH_IDENTIFIER: u32 = (1 << (62 - PACKET_NUMBER_BITS)) - 1;
+
+/// Returns the packet number used to track a packet in the space of a path
+///
+/// `None` is returned if the path identifier or packet number is too large to be tracked.
+#[inline]
+pub fn tracked_packet_number(
+    path_identifier: u32,
+    packet_number: PacketNumber,
+) -> Option<PacketNumber> {
+    if path_identifier > MAX_PATH_IDENTIFIER || packet_number.as_u64() > PACKET_NUMBER_MASK {
+        return None;
+    }
+
+    let value = (path_identifier as u64) << PACKET_NUMBER_BITS | packet_number.as_u64();
+    let value = VarInt::new(value).ok()?;
+    Some(PacketNumberSpace::ApplicationData.new_packet_number(value))
+}
+
+/// Returns the packet number in the space of the path for a tracked packet number
+#[inline]
+pub fn path_packet_number(tracked_packet_number: PacketNumber) -> PacketNumber {
+    let value = tracked_packet_number.as_u64() & PACKET_NUMBER_MASK;
+    PacketNumberSpace::ApplicationData.new_packet_number(VarInt::new(value).unwrap())
+}
+
+/// The packet number spaces of the paths, other than the application data space
+pub struct PathSpaces<Config: endpoint::Config> {
+    pub rx: Vec<RxSpace>,
+    pub tx: Vec<TxSpace<Config>>,
+}
+
+impl<Config: endpoint::Config> Default for PathSpaces<Config> {
+    fn default() -> Self {
+        Self {
+            rx: Vec::new(),
+            tx: Vec::new(),
+        }
+    }
+}
+
+impl<Config: endpoint::Config> core::fmt::Debug for PathSpaces<Config> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PathSpaces")
+            .field("rx", &self.rx)
+            .field("tx", &self.tx)
+            .finish()
+    }
+}
+
+impl<Config: endpoint::Config> PathSpaces<Config> {
+    /// Returns the space for packets received with the path identifier, creating it if
+    /// this is the first packet
+    ///
+    /// The space is created before the packet is authenticated, which is bounded by the number
+    /// of connection ids issued to the peer.
+    ///
+    /// The ack manager of the space is configured like the `ack_manager` of the
+    /// application data space.
+    pub fn rx_space_mut(&mut self, path_identifier: u32, ack_manager: &AckManager) -> &mut RxSpace {
+        debug_assert_ne!(path_identifier, 0);
+
+        let index = match self
+            .rx
+            .iter()
+            .position(|space| space.path_identifier == path_identifier)
+        {
+            Some(index) => index,
+            None => {
+                self.rx.push(RxSpace {
+                    path_identifier,
+                    ack_manager: ack_manager.new_for_path(path_identifier),
+                    processed_packet_numbers: SlidingWindow::default(),
+                });
+                self.rx.len() - 1
+            }
+        };
+
+        &mut self.rx[index]
+    }
+
+    /// Called when the peer retired the local connection id with the sequence number
+    ///
+    /// The peer no longer sends packets in the space of the connection id.
+    pub fn on_local_connection_id_retired(&mut self, sequence_number: u32) {
+        self.rx
+            .retain(|space| space.path_identifier != sequence_number);
+    }
+
+    /// Returns the index of the space for packets sent with the path identifier, creating
+    /// it if this is the first packet
+    ///
+    /// `None` is returned if the path identifier is too large to be tracked.
+    pub fn tx_space_index(
+        &mut self,
+        path_identifier: u32,
+        path_id: path::Id,
+        now: Timestamp,
+    ) -> Option<usize> {
+        debug_assert_ne!(path_identifier, 0);
+
+        if let Some(index) = self
+            .tx
+            .iter()
+            .position(|space| space.path_identifier == path_identifier)
+        {
+            return Some(index);
+        }
+
+        let initial_packet_number = tracked_packet_number(
+            path_identifier,
+            PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(0)),
+        )?;
+
+        self.tx.push(TxSpace {
+            path_identifier,
+            path_id,
+            tx_packet_numbers: TxPacketNumbers::starting_at(initial_packet_number, now),
+            recovery_manager: recovery::Manager::new(PacketNumberSpace::ApplicationData),
+        });
+
+        Some(self.tx.len() - 1)
+    }
+
+    /// Returns the index of the space for packets sent with the path identifier
+    #[inline]
+    pub fn find_tx_space(&self, path_identifier: u32) -> Option<usize> {
+        self.tx
+            .iter()
+            .position(|space| space.path_identifier == path_identifier)
+    }
+
+    /// Returns `true` if any of the spaces requires a probe packet to be sent
+    #[inline]
+    pub fn requires_probe(&self) -> bool {
+        self.tx
+            .iter()
+            .any(|space| space.recovery_manager.requires_probe())
+    }
+}
+
+impl<Config: endpoint::Config> timer::Provider for PathSpaces<Config> {
+    #[inline]
+    fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        for space in &self.rx {
+            space.ack_manager.timers(query)?;
+        }
+
+        for space in &self.tx {
+            space.recovery_manager.timers(query)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<Config: endpoint::Config> transmission::interest::Provider for PathSpaces<Config> {
+    #[inline]
+    fn transmission_interest<Q: transmission::interest::Query>(
+        &self,
+        query: &mut Q,
+    ) -> transmission::interest::Result {
+        for space in &self.rx {
+            space.ack_manager.transmission_interest(query)?;
+        }
+
+        for space in &self.tx {
+            space.recovery_manager.transmission_interest(query)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The packets received in the packet number space of a path
+#[derive(Debug)]
+pub struct RxSpace {
+    path_identifier: u32,
+    /// Acknowledges the received packets with ACK_MP frames
+    pub ack_manager: AckManager,
+    pub processed_packet_numbers: SlidingWindow,
+}
+
+/// The packets sent in the packet number space of a path
+pub struct TxSpace<Config: endpoint::Config> {
+    pub path_identifier: u32,
+    /// The path that last sent a packet in the space
+    pub path_id: path::Id,
+    /// The tracked packet numbers of the sent packets
+    pub tx_packet_numbers: TxPacketNumbers,
+    pub recovery_manager: recovery::Manager<Config>,
+}
+
+impl<Config: endpoint::Config> core::fmt::Debug for TxSpace<Config> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TxSpace")
+            .field("path_identifier", &self.path_identifier)
+            .field("path_id", &self.path_id)
+            .field("tx_packet_numbers", &self.tx_packet_numbers)
+            .field("recovery_manager", &self.recovery_manager)
+            .finish()
+    }
+}
+
+/// Translates the ranges of an ACK_MP frame to the tracked packet numbers of the space
+///
+/// Returns a PROTOCOL_VIOLATION if the frame acknowledges packet numbers that can't have
+/// been sent in the space.
+pub fn tracked_ack<A: AckRanges>(
+    path_identifier: u32,
+    ack: Ack<A>,
+) -> Result<Ack<TrackedAckRanges<A>>, transport::Error> {
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-13.1
+    //# An endpoint SHOULD treat receipt of an acknowledgment for a packet it
+    //# did not send as a connection error of type PROTOCOL_VIOLATION, if it
+    //# is able to detect the condition.
+    let largest =
+        PacketNumberSpace::ApplicationData.new_packet_number(ack.ack_ranges.largest_acknowledged());
+    let offset = tracked_packet_number(path_identifier, largest)
+        .map(|tracked| tracked.as_u64() - largest.as_u64())
+        .ok_or_else(|| {
+            transport::Error::PROTOCOL_VIOLATION
+                .with_reason("received an ACK for a packet that was not sent")
+        })?;
+
+    Ok(Ack {
+        ack_delay: ack.ack_delay,
+        ack_ranges: TrackedAckRanges {
+            ack_ranges: ack.ack_ranges,
+            offset,
+        },
+        ecn_counts: ack.ecn_counts,
+    })
+}
+
+/// The ranges of an ACK_MP frame, translated to tracked packet numbers
+pub struct TrackedAckRanges<A> {
+    ack_ranges: A,
+    offset: u64,
+}
+
+impl<A: AckRanges> AckRanges for TrackedAckRanges<A> {
+    type Iter = TrackedAckRangesIter<A::Iter>;
+
+    #[inline]
+    fn ack_ranges(&self) -> Self::Iter {
+        TrackedAckRangesIter {
+            ranges: self.ack_ranges.ack_ranges(),
+            offset: self.offset,
+        }
+    }
+}
+
+pub struct TrackedAckRangesIter<I> {
+    ranges: I,
+    offset: u64,
+}
+
+impl<I: Iterator<Item = RangeInclusive<VarInt>>> Iterator for TrackedAckRangesIter<I> {
+    type Item = RangeInclusive<VarInt>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let range = self.ranges.next()?;
+        // The ranges are smaller than the largest acknowledged packet number, which was
+        // validated to be trackable
+        let offset = VarInt::new(self.offset).ok()?;
+        Some(range.start().checked_add(offset)?..=range.end().checked_add(offset)?)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ranges.size_hint()
+    }
+}
+
+impl<I: ExactSizeIterator<Item = RangeInclusive<VarInt>>> ExactSizeIterator
+    for TrackedAckRangesIter<I>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pn(value: u64) -> PacketNumber {
+        PacketNumberSpace::ApplicationData.new_packet_number(VarInt::new(value).unwrap())
+    }
+
+    #[test]
+    fn tracked_packet_number_test() {
+        assert_eq!(tracked_packet_number(0, pn(7)), Some(pn(7)));
+
+        let tracked = tracked_packet_number(3, pn(7)).unwrap();
+        assert_eq!(tracked, pn(3 << PACKET_NUMBER_BITS | 7));
+        assert_eq!(path_packet_number(tracked), pn(7));
+
+        assert!(tracked_packet_number(MAX_PATH_IDENTIFIER, pn(PACKET_NUMBER_MASK)).is_some());
+        assert!(tracked_packet_number(MAX_PATH_IDENTIFIER + 1, pn(0)).is_none());
+        assert!(tracked_packet_number(1, pn(PACKET_NUMBER_MASK + 1)).is_none());
+    }
+
+    #[test]
+    fn tracked_ack_test() {
+        let mut ranges = crate::ack::ack_ranges::AckRanges::new(10);
+        ranges.insert_packet_number(pn(1)).unwrap();
+        ranges.insert_packet_number(pn(2)).unwrap();
+        ranges.insert_packet_number(pn(5)).unwrap();
+
+        let ack = Ack {
+            ack_delay: VarInt::from_u8(0),
+            ack_ranges: &ranges,
+            ecn_counts: None,
+        };
+
+        let tracked = tracked_ack(2, ack).unwrap();
+        let offset = 2 << PACKET_NUMBER_BITS;
+        let ranges: Vec<_> = tracked
+            .ack_ranges
+            .ack_ranges()
+            .map(|range| {
+                (
+                    range.start().as_u64() - offset,
+                    range.end().as_u64() - offset,
+                )
+            })
+            .collect();
+        assert_eq!(ranges, [(5, 5), (1, 2)]);
+        assert_eq!(
+            tracked.ack_ranges.largest_acknowledged().as_u64(),
+            offset + 5
+        );
+
+        let mut ranges = crate::ack::ack_ranges::AckRanges::new(10);
+        ranges
+            .insert_packet_number(pn(PACKET_NUMBER_MASK + 1))
+            .unwrap();
+        let ack = Ack {
+            ack_delay: VarInt::from_u8(0),
+            ack_ranges: &ranges,
+            ecn_counts: None,
+        };
+        assert!(tracked_ack(2, ack).is_err());
+    }
+}
@@ -6,10 +6,10 @@ use crate::{
     connection::{self, limits::Limits},
     endpoint, path,
     space::{
-        datagram, keep_alive::KeepAlive, ApplicationSpace, HandshakeSpace, HandshakeStatus,
-        InitialSpace,
+        datagram, keep_alive::KeepAlive, new_token, ApplicationSpace, HandshakeSpace,
+        HandshakeStatus, InitialSpace, ZeroRttCrypto,
     },
-    stream,
+    stream::{self, Manager as _},
 };
 use bytes::Bytes;
 use core::{ops::Not, task::Waker};
@@ -24,16 +24,19 @@ use s2n_quic_core::{
     datagram::{ConnectionInfo, Endpoint},
     event,
     event::IntoEvent,
+    frame::{MaxData, MaxStreams},
     packet::number::PacketNumberSpace,
+    stream::StreamType,
     time::Timestamp,
     transport::{
         self,
         parameters::{
             ActiveConnectionIdLimit, ClientTransportParameters, DatagramLimits,
-            InitialFlowControlLimits, InitialSourceConnectionId, MaxAckDelay,
-            ServerTransportParameters,
+            InitialFlowControlLimits, InitialSourceConnectionId, MaxAckDelay, MinAckDelay,
+            ServerTransportParameters, VersionInformation, ZeroRttParameters,
         },
     },
+    version,
 };
 
 pub struct SessionContext<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher> {
@@ -44,14 +47,17 @@ pub struct SessionContext<'a, Config: endpoint::Config, Pub: event::ConnectionPu
     pub initial: &'a mut Option<Box<InitialSpace<Config>>>,
     pub handshake: &'a mut Option<Box<HandshakeSpace<Config>>>,
     pub application: &'a mut Option<Box<ApplicationSpace<Config>>>,
-    pub zero_rtt_crypto: &'a mut Option<
-        Box<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey>,
-    >,
+    pub zero_rtt_crypto: &'a mut Option<Box<ZeroRttCrypto<Config>>>,
+    pub zero_rtt_parameters: &'a mut Option<ZeroRttParameters>,
+    pub early_data_accepted: &'a mut bool,
     pub handshake_status: &'a mut HandshakeStatus,
+    pub new_token: &'a mut new_token::Manager<Config>,
     pub local_id_registry: &'a mut connection::LocalIdRegistry,
     pub limits: &'a mut Limits,
+    pub quic_versions: &'a version::Preferences,
     pub server_name: &'a mut Option<ServerName>,
     pub application_protocol: &'a mut Bytes,
+    pub peer_certificate_chain: &'a mut Vec<Bytes>,
     pub waker: &'a Waker,
     pub publisher: &'a mut Pub,
     pub datagram: &'a mut Config::DatagramEndpoint,
@@ -70,6 +76,7 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             ActiveConnectionIdLimit,
             DatagramLimits,
             MaxAckDelay,
+            Option<MinAckDelay>,
         ),
         transport::Error,
     > {
@@ -163,6 +170,22 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
                 .with_reason("missing original_destination_connection_id"));
         }
 
+        self.validate_version_information(peer_parameters.version_information)?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4.1
+        //# If 0-RTT data is accepted by the server, the server MUST NOT reduce
+        //# any limits or alter any values that might be violated by the client
+        //# with its 0-RTT data.
+        //
+        // The server is free to change its limits if the early data was rejected.
+        if let Some(remembered) = self.zero_rtt_parameters.take() {
+            if *self.early_data_accepted {
+                peer_parameters
+                    .zero_rtt_parameters()
+                    .validate_resumption(&remembered)?;
+            }
+        }
+
         //= https://www.rfc-editor.org/rfc/rfc9000#section-10.3
         //# Servers can also issue a stateless_reset_token transport parameter during the
         //# handshake that applies to the connection ID that it selected during
@@ -176,6 +199,25 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
                 .register_initial_stateless_reset_token(stateless_reset_token);
         }
 
+        if let Some(preferred_address) = &peer_parameters.preferred_address {
+            self.path_manager.on_preferred_address(preferred_address)?;
+        }
+
+        self.path_manager
+            .on_migration_support(peer_parameters.migration_support);
+
+        Self::validate_min_ack_delay(peer_parameters.min_ack_delay, peer_parameters.max_ack_delay)?;
+
+        self.path_manager.on_multipath_support(
+            self.limits.enable_multipath(),
+            peer_parameters.enable_multipath,
+        );
+
+        self.path_manager.quic_bit.on_transport_parameters(
+            peer_parameters.grease_quic_bit,
+            self.limits.randomized_quic_bit(),
+        );
+
         // Load the peer's transport parameters into the connection's limits
         self.limits.load_peer(&peer_parameters);
 
@@ -188,6 +230,7 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             active_connection_id_limit,
             datagram_limits,
             peer_parameters.max_ack_delay,
+            peer_parameters.min_ack_delay,
         ))
     }
 
@@ -201,6 +244,7 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             ActiveConnectionIdLimit,
             DatagramLimits,
             MaxAckDelay,
+            Option<MinAckDelay>,
         ),
         transport::Error,
     > {
@@ -234,6 +278,18 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
                 .as_bytes(),
         )?;
 
+        Self::validate_min_ack_delay(peer_parameters.min_ack_delay, peer_parameters.max_ack_delay)?;
+
+        self.path_manager.on_multipath_support(
+            self.limits.enable_multipath(),
+            peer_parameters.enable_multipath,
+        );
+
+        self.path_manager.quic_bit.on_transport_parameters(
+            peer_parameters.grease_quic_bit,
+            self.limits.randomized_quic_bit(),
+        );
+
         // Load the peer's transport parameters into the connection's limits
         self.limits.load_peer(&peer_parameters);
 
@@ -246,6 +302,7 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             active_connection_id_limit,
             datagram_limits,
             peer_parameters.max_ack_delay,
+            peer_parameters.min_ack_delay,
         ))
     }
 
@@ -291,12 +348,147 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
 
         Ok(())
     }
+
+    // A min_ack_delay that is greater than the max_ack_delay of the same endpoint is a
+    // TRANSPORT_PARAMETER_ERROR.
+    //
+    // See https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency#section-3
+    fn validate_min_ack_delay(
+        min_ack_delay: Option<MinAckDelay>,
+        max_ack_delay: MaxAckDelay,
+    ) -> Result<(), transport::Error> {
+        match min_ack_delay {
+            Some(min_ack_delay) if min_ack_delay.as_duration() > max_ack_delay.as_duration() => {
+                Err(transport::Error::TRANSPORT_PARAMETER_ERROR
+                    .with_reason("min_ack_delay is greater than max_ack_delay"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // This is called by the client to ensure the server chose the version being used for
+    // the connection
+    fn validate_version_information(
+        &self,
+        peer_value: Option<VersionInformation>,
+    ) -> Result<(), transport::Error> {
+        let quic_version = self.publisher.quic_version();
+
+        match peer_value {
+            Some(peer_value) if peer_value.chosen_version() != quic_version => {
+                Err(transport::Error::VERSION_NEGOTIATION_ERROR
+                    .with_reason("version_information chosen_version mismatch"))
+            }
+            // The client never acts on Version Negotiation packets so the connection always
+            // started with the original version. The server is expected to pick its most
+            // preferred version out of the ones the client offered, which is authenticated
+            // by the handshake.
+            Some(peer_value)
+                if self.quic_versions.is_downgrade(
+                    self.quic_versions.original(),
+                    quic_version,
+                    peer_value.available_versions(),
+                ) =>
+            {
+                Err(transport::Error::VERSION_NEGOTIATION_ERROR
+                    .with_reason("version_information indicates a version downgrade"))
+            }
+            Some(_) => Ok(()),
+            // Servers that don't implement compatible version negotiation are only able
+            // to use version 1
+            None if quic_version != version::VERSION_1 => {
+                Err(transport::Error::VERSION_NEGOTIATION_ERROR
+                    .with_reason("missing version_information"))
+            }
+            None => Ok(()),
+        }
+    }
+
+    // This is called by the client
+    fn on_early_data_keys(
+        &mut self,
+        crypto: ZeroRttCrypto<Config>,
+        application_parameters: tls::ApplicationParameters,
+    ) -> Result<(), transport::Error> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        // The TLS provider passes the transport parameters remembered from the previous
+        // connection with the server
+        let (remembered, remaining) = ServerTransportParameters::decode(DecoderBuffer::new(
+            application_parameters.transport_parameters,
+        ))
+        .map_err(|_| {
+            transport::Error::TRANSPORT_PARAMETER_ERROR
+                .with_reason("Invalid remembered transport parameters")
+        })?;
+        debug_assert_eq!(remaining.len(), 0);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4.1
+        //# When sending 0-RTT packets, the client MUST use the
+        //# remembered transport parameters and apply them to the new connection.
+        //
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4.1
+        //# A client MUST NOT use remembered values for the following parameters:
+        //# ack_delay_exponent, max_ack_delay, initial_source_connection_id,
+        //# original_destination_connection_id, preferred_address,
+        //# retry_source_connection_id, and stateless_reset_token.
+        //
+        // Only the limits covered by `ZeroRttParameters` are applied. The rest of the
+        // parameters are loaded once the server's transport parameters are received.
+        let zero_rtt_parameters = remembered.zero_rtt_parameters();
+        let peer_flow_control_limits = remembered.flow_control_limits();
+        let datagram_limits = remembered.datagram_limits();
+        *self.zero_rtt_parameters = Some(zero_rtt_parameters);
+
+        let stream_manager = <Config::StreamManager as stream::Manager>::new(
+            self.limits,
+            Config::ENDPOINT_TYPE,
+            self.limits.initial_flow_control_limits(),
+            peer_flow_control_limits,
+        );
+
+        let ack_manager = AckManager::new(
+            PacketNumberSpace::ApplicationData,
+            self.limits.ack_settings(),
+        );
+
+        let keep_alive = KeepAlive::new(
+            self.limits.max_idle_timeout(),
+            self.limits.max_keep_alive_period(),
+        );
+
+        let conn_info =
+            ConnectionInfo::new(datagram_limits.max_datagram_payload, self.waker.clone());
+        let (datagram_sender, datagram_receiver) = self.datagram.create_connection(&conn_info);
+        let datagram_manager = datagram::Manager::new(
+            datagram_sender,
+            datagram_receiver,
+            datagram_limits.max_datagram_payload,
+        );
+
+        *self.application = Some(Box::new(ApplicationSpace::new_zero_rtt(
+            crypto,
+            self.now,
+            stream_manager,
+            ack_manager,
+            keep_alive,
+            datagram_manager,
+            core::mem::take(self.new_token),
+        )));
+
+        Ok(())
+    }
 }
 
 impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
     tls::Context<<Config::TLSEndpoint as tls::Endpoint>::Session>
     for SessionContext<'a, Config, Pub>
 {
+    #[inline]
+    fn quic_version(&self) -> u32 {
+        self.publisher.quic_version()
+    }
+
     fn on_handshake_keys(
         &mut self,
         key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::HandshakeKey,
@@ -332,18 +524,29 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
     fn on_zero_rtt_keys(
         &mut self,
         key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey,
-        _header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttHeaderKey,
-        _application_parameters: tls::ApplicationParameters,
+        header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttHeaderKey,
+        application_parameters: tls::ApplicationParameters,
     ) -> Result<(), transport::Error> {
-        if self.zero_rtt_crypto.is_some() {
+        if self.zero_rtt_crypto.is_some() || self.application.is_some() {
             return Err(transport::Error::INTERNAL_ERROR
                 .with_reason("zero rtt keys initialized more than once"));
         }
 
         let cipher_suite = key.cipher_suite().into_event();
-
-        // TODO: also store the header_key https://github.com/aws/s2n-quic/issues/319
-        *self.zero_rtt_crypto = Some(Box::new(key));
+        let crypto = ZeroRttCrypto { key, header_key };
+
+        match Config::ENDPOINT_TYPE {
+            endpoint::Type::Server => {
+                // The server opens 0-RTT packets with the keys until the 1-RTT keys are
+                // available
+                *self.zero_rtt_crypto = Some(Box::new(crypto));
+                self.publisher
+                    .on_early_data_accepted(event::builder::EarlyDataAccepted {});
+            }
+            endpoint::Type::Client => {
+                self.on_early_data_keys(crypto, application_parameters)?;
+            }
+        }
 
         self.publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::ZeroRtt,
@@ -352,36 +555,119 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
         Ok(())
     }
 
+    fn on_early_data(&mut self, accepted: bool) -> Result<(), transport::Error> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        *self.early_data_accepted = accepted;
+
+        if accepted {
+            self.publisher
+                .on_early_data_accepted(event::builder::EarlyDataAccepted {});
+        } else {
+            self.publisher
+                .on_early_data_rejected(event::builder::EarlyDataRejected {
+                    reason: event::builder::EarlyDataRejectReason::Peer,
+                });
+        }
+
+        Ok(())
+    }
+
     fn on_one_rtt_keys(
         &mut self,
         key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey,
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
         application_parameters: tls::ApplicationParameters,
     ) -> Result<(), transport::Error> {
-        if self.application.is_some() {
+        if self
+            .application
+            .as_ref()
+            .map_or(false, |space| !space.is_zero_rtt())
+        {
             return Err(transport::Error::INTERNAL_ERROR
                 .with_reason("application keys initialized more than once"));
         }
 
-        if Config::ENDPOINT_TYPE.is_client() {
-            //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9.3
-            //# Therefore, a client SHOULD discard 0-RTT keys as soon as it installs
-            //# 1-RTT keys as they have no use after that moment.
-
-            *self.zero_rtt_crypto = None;
-        }
-
         // Parse transport parameters
         let param_decoder = DecoderBuffer::new(application_parameters.transport_parameters);
-        let (peer_flow_control_limits, active_connection_id_limit, datagram_limits, max_ack_delay) =
-            match Config::ENDPOINT_TYPE {
-                endpoint::Type::Client => self.on_server_params(param_decoder)?,
-                endpoint::Type::Server => self.on_client_params(param_decoder)?,
-            };
+        let (
+            peer_flow_control_limits,
+            active_connection_id_limit,
+            datagram_limits,
+            max_ack_delay,
+            min_ack_delay,
+        ) = match Config::ENDPOINT_TYPE {
+            endpoint::Type::Client => self.on_server_params(param_decoder)?,
+            endpoint::Type::Server => self.on_client_params(param_decoder)?,
+        };
 
         self.local_id_registry
             .set_active_connection_id_limit(active_connection_id_limit.as_u64());
 
+        self.path_manager
+            .active_path_mut()
+            .rtt_estimator
+            .on_max_ack_delay(max_ack_delay);
+
+        let cipher_suite = key.cipher_suite().into_event();
+        let max_mtu = self.path_manager.max_mtu();
+
+        // A client that sent early data already has an application space using the remembered
+        // transport parameters
+        if let Some(application) = self.application.as_mut() {
+            debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+            application.on_one_rtt_keys(key, header_key, max_mtu);
+
+            if let Some(min_ack_delay) = min_ack_delay {
+                application.on_peer_min_ack_delay(
+                    min_ack_delay.as_duration(),
+                    max_ack_delay.as_duration(),
+                );
+            }
+
+            self.publisher.on_key_update(event::builder::KeyUpdate {
+                key_type: event::builder::KeyType::OneRtt { generation: 0 },
+                cipher_suite,
+            });
+
+            if !*self.early_data_accepted {
+                // The streams are replayed with the limits the server advertised for this
+                // connection
+                return application.on_zero_rtt_rejected(
+                    peer_flow_control_limits,
+                    self.handshake_status,
+                    self.local_id_registry,
+                    self.path_manager,
+                    self.publisher,
+                );
+            }
+
+            // The server's limits may only be increased from the remembered values, which
+            // was validated in `on_server_params`. Streams continue to use the remembered
+            // per-stream limits until the server sends MAX_STREAM_DATA.
+            application.stream_manager.on_max_data(MaxData {
+                maximum_data: peer_flow_control_limits.max_data,
+            })?;
+            for (stream_type, maximum_streams) in [
+                (
+                    StreamType::Bidirectional,
+                    peer_flow_control_limits.max_open_remote_bidirectional_streams,
+                ),
+                (
+                    StreamType::Unidirectional,
+                    peer_flow_control_limits.max_open_remote_unidirectional_streams,
+                ),
+            ] {
+                application.stream_manager.on_max_streams(&MaxStreams {
+                    stream_type,
+                    maximum_streams,
+                })?;
+            }
+
+            return Ok(());
+        }
+
         let stream_manager = <Config::StreamManager as stream::Manager>::new(
             self.limits,
             Config::ENDPOINT_TYPE,
@@ -408,14 +694,7 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             datagram_limits.max_datagram_payload,
         );
 
-        self.path_manager
-            .active_path_mut()
-            .rtt_estimator
-            .on_max_ack_delay(max_ack_delay);
-
-        let cipher_suite = key.cipher_suite().into_event();
-        let max_mtu = self.path_manager.max_mtu();
-        *self.application = Some(Box::new(ApplicationSpace::new(
+        let mut application = ApplicationSpace::new(
             key,
             header_key,
             self.now,
@@ -424,7 +703,15 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             keep_alive,
             max_mtu,
             datagram_manager,
-        )));
+            core::mem::take(self.new_token),
+        );
+
+        if let Some(min_ack_delay) = min_ack_delay {
+            application
+                .on_peer_min_ack_delay(min_ack_delay.as_duration(), max_ack_delay.as_duration());
+        }
+
+        *self.application = Some(Box::new(application));
         self.publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::OneRtt { generation: 0 },
             cipher_suite,
@@ -457,6 +744,28 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
         Ok(())
     }
 
+    fn on_session_resumption(&mut self, resumed: bool) -> Result<(), transport::Error> {
+        self.publisher
+            .on_session_resumption(event::builder::SessionResumption { resumed });
+
+        Ok(())
+    }
+
+    fn on_server_certificate(&mut self, server_name: Option<&str>) -> Result<(), transport::Error> {
+        self.publisher
+            .on_server_certificate_selected(event::builder::ServerCertificateSelected {
+                server_name,
+            });
+
+        Ok(())
+    }
+
+    fn on_peer_certificate_chain(&mut self, chain: Vec<Bytes>) -> Result<(), transport::Error> {
+        *self.peer_certificate_chain = chain;
+
+        Ok(())
+    }
+
     fn on_handshake_complete(&mut self) -> Result<(), transport::Error> {
         // After the handshake is complete, the handshake crypto stream should be completely
         // finished
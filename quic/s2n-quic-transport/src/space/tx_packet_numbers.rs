@@ -19,6 +19,11 @@ pub struct TxPacketNumbers {
 impl TxPacketNumbers {
     pub fn new(packet_space: PacketNumberSpace, now: Timestamp) -> Self {
         let initial_packet_number = packet_space.new_packet_number(VarInt::from_u8(0));
+        Self::starting_at(initial_packet_number, now)
+    }
+
+    /// Creates packet numbers that start at `initial_packet_number`
+    pub fn starting_at(initial_packet_number: PacketNumber, now: Timestamp) -> Self {
         Self {
             largest_sent_acked: (initial_packet_number, now),
             next: initial_packet_number,
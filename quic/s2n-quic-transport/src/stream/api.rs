@@ -13,7 +13,7 @@ use core::{
 };
 pub use s2n_quic_core::{
     application,
-    stream::{ops, StreamError, StreamId, StreamType},
+    stream::{ops, Priority, StreamError, StreamId, StreamType},
 };
 
 #[derive(Clone)]
@@ -224,6 +224,17 @@ macro_rules! tx_stream_apis {
             self.tx_request()?.reset(error_code).poll(None)?;
             Ok(())
         }
+
+        /// Sets the transmission priority of the stream.
+        ///
+        /// The method will return:
+        /// - `Ok(())` if the priority was updated
+        /// - `Err(stream_error)` if the priority could not be updated, because the stream
+        ///   had previously entered an error state.
+        pub fn set_priority(&mut self, priority: Priority) -> Result<(), StreamError> {
+            self.tx_request()?.with_priority(priority).poll(None)?;
+            Ok(())
+        }
     };
 }
 
@@ -494,6 +505,11 @@ macro_rules! tx_request_apis {
             self.request.flush();
             self
         }
+
+        pub fn with_priority(&mut self, priority: Priority) -> &mut Self {
+            self.request.with_priority(priority);
+            self
+        }
     };
 }
 
@@ -95,6 +95,18 @@ impl Controller {
         }
     }
 
+    /// This method is called on the client when the server rejected early data, which
+    /// replaces the remembered stream limits with the limits in `peer_limits`.
+    pub fn on_zero_rtt_rejected(
+        &mut self,
+        peer_limits: InitialFlowControlLimits,
+    ) -> Result<(), transport::Error> {
+        self.local_bidi_controller
+            .on_zero_rtt_rejected(peer_limits.max_open_remote_bidirectional_streams)?;
+        self.local_uni_controller
+            .on_zero_rtt_rejected(peer_limits.max_open_remote_unidirectional_streams)
+    }
+
     /// This method is called when the local application wishes to open the next stream
     /// of a type (Bidirectional/Unidirectional).
     ///
@@ -201,6 +213,28 @@ impl Controller {
         }
     }
 
+    /// Writes the number of open streams into a statistics snapshot
+    pub fn write_stats(&self, stats: &mut connection::stats::Streams) {
+        stats.local_bidirectional.open = self.local_bidi_controller.open_stream_count().as_u64();
+        stats.local_bidirectional.total = self
+            .local_bidi_controller
+            .total_open_stream_count()
+            .as_u64();
+        stats.local_unidirectional.open = self.local_uni_controller.open_stream_count().as_u64();
+        stats.local_unidirectional.total =
+            self.local_uni_controller.total_open_stream_count().as_u64();
+        stats.remote_bidirectional.open = self.remote_bidi_controller.open_stream_count().as_u64();
+        stats.remote_bidirectional.total = self
+            .remote_bidi_controller
+            .total_open_stream_count()
+            .as_u64();
+        stats.remote_unidirectional.open = self.remote_uni_controller.open_stream_count().as_u64();
+        stats.remote_unidirectional.total = self
+            .remote_uni_controller
+            .total_open_stream_count()
+            .as_u64();
+    }
+
     /// This method is called when the stream manager is closed. All wakers will be woken
     /// to unblock waiting tasks.
     pub fn close(&mut self) {
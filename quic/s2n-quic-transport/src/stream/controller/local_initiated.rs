@@ -18,6 +18,7 @@ use s2n_quic_core::{
     packet::number::PacketNumber,
     stream::{limits::LocalLimits, StreamId},
     time::{timer, Timestamp},
+    transport,
     varint::VarInt,
 };
 use smallvec::SmallVec;
@@ -83,6 +84,26 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
         self.wake_unblocked();
     }
 
+    /// Replaces the remembered stream limit after the peer rejected early data
+    ///
+    /// Unlike `MAX_STREAMS` frames, the new limit may be lower than the remembered limit.
+    pub fn on_zero_rtt_rejected(
+        &mut self,
+        peer_maximum_streams: VarInt,
+    ) -> Result<(), transport::Error> {
+        // The streams opened during 0-RTT are replayed so they need to fit in the new limit
+        if self.opened_streams > peer_maximum_streams {
+            return Err(transport::Error::STREAM_LIMIT_ERROR
+                .with_reason("more streams were opened during 0-RTT than the peer allows"));
+        }
+
+        self.peer_cumulative_stream_limit = peer_maximum_streams;
+        self.streams_blocked_sync.stop_sync();
+        self.wake_unblocked();
+
+        Ok(())
+    }
+
     pub fn update_sync_period(&mut self, blocked_sync_period: Duration) {
         self.streams_blocked_sync
             .update_sync_period(blocked_sync_period);
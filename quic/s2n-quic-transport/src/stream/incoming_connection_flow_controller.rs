@@ -5,15 +5,17 @@
 
 use crate::{
     contexts::{OnTransmitError, WriteContext},
+    stream::window_tuner::{WindowTuner, WindowUpdate},
     sync::{IncrementalValueSync, ValueToFrameWriter},
     transmission,
 };
 use alloc::rc::Rc;
-use core::cell::RefCell;
+use core::{cell::RefCell, time::Duration};
 use s2n_quic_core::{
-    ack, frame::max_data::MaxData, packet::number::PacketNumber, stream::StreamId, transport,
-    varint::VarInt,
+    ack, frame::max_data::MaxData, packet::number::PacketNumber, recovery::DEFAULT_INITIAL_RTT,
+    stream::StreamId, transport, varint::VarInt,
 };
+use smallvec::SmallVec;
 
 /// Writes `MAX_DATA` frames based on the connections flow control window.
 #[derive(Default, Debug)]
@@ -46,10 +48,22 @@ struct IncomingConnectionFlowControllerImpl {
     /// The amount of flow control credits which had been acquired and where the
     /// data had already been consumed by the application
     pub(super) consumed_window: VarInt,
+    /// Grows the `desired_flow_control_window` based on the rate at which
+    /// the application consumes data
+    window_tuner: WindowTuner,
+    /// The smoothed RTT of the active path
+    smoothed_rtt: Duration,
+    /// Receive windows which have been grown by the connection or its streams
+    /// and not yet reported
+    window_updates: SmallVec<[WindowUpdate; 4]>,
 }
 
 impl IncomingConnectionFlowControllerImpl {
-    pub fn new(initial_window_size: VarInt, desired_flow_control_window: u32) -> Self {
+    pub fn new(
+        initial_window_size: VarInt,
+        desired_flow_control_window: u32,
+        max_flow_control_window: u32,
+    ) -> Self {
         Self {
             read_window_sync: IncrementalValueSync::new(
                 VarInt::from_u32(desired_flow_control_window),
@@ -59,6 +73,9 @@ impl IncomingConnectionFlowControllerImpl {
             desired_flow_control_window,
             acquired_window: VarInt::from_u32(0),
             consumed_window: VarInt::from_u32(0),
+            window_tuner: WindowTuner::new(max_flow_control_window),
+            smoothed_rtt: DEFAULT_INITIAL_RTT,
+            window_updates: SmallVec::new(),
         }
     }
 
@@ -107,6 +124,24 @@ impl IncomingConnectionFlowControllerImpl {
 
     #[inline]
     pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) -> Result<(), OnTransmitError> {
+        if self.window_tuner.on_transmit(
+            &mut self.desired_flow_control_window,
+            self.consumed_window,
+            context.current_time(),
+            self.smoothed_rtt,
+        ) {
+            self.read_window_sync
+                .set_threshold(VarInt::from_u32(self.desired_flow_control_window / 10));
+            self.read_window_sync.update_latest_value(
+                self.consumed_window
+                    .saturating_add(VarInt::from_u32(self.desired_flow_control_window)),
+            );
+            self.window_updates.push(WindowUpdate {
+                stream_id: None,
+                window: self.desired_flow_control_window,
+            });
+        }
+
         // Stream ID does not matter here, since it does not get transmitted
         self.read_window_sync
             .on_transmit(StreamId::from_varint(VarInt::from_u32(0)), context)
@@ -134,11 +169,19 @@ impl IncomingConnectionFlowController {
     /// `desired_flow_control_window`. This means if the window which is indicated
     /// to the peer is lower than this value the new value will be communicated
     /// to the peer.
-    pub fn new(initial_window_size: VarInt, desired_flow_control_window: u32) -> Self {
+    ///
+    /// If the application consumes data fast enough, the desired window will be
+    /// grown up to `max_flow_control_window`.
+    pub fn new(
+        initial_window_size: VarInt,
+        desired_flow_control_window: u32,
+        max_flow_control_window: u32,
+    ) -> Self {
         Self {
             inner: Rc::new(RefCell::new(IncomingConnectionFlowControllerImpl::new(
                 initial_window_size,
                 desired_flow_control_window,
+                max_flow_control_window,
             ))),
         }
     }
@@ -173,6 +216,26 @@ impl IncomingConnectionFlowController {
         self.inner.borrow_mut().on_transmit(context)
     }
 
+    /// Updates the RTT estimate used to tune the receive windows
+    pub fn on_rtt_update(&mut self, smoothed_rtt: Duration) {
+        self.inner.borrow_mut().smoothed_rtt = smoothed_rtt;
+    }
+
+    /// Returns the smoothed RTT of the active path
+    pub fn smoothed_rtt(&self) -> Duration {
+        self.inner.borrow().smoothed_rtt
+    }
+
+    /// Records that a receive window has been grown
+    pub fn on_window_update(&mut self, update: WindowUpdate) {
+        self.inner.borrow_mut().window_updates.push(update);
+    }
+
+    /// Removes all of the recorded receive window updates
+    pub fn take_window_updates(&mut self) -> SmallVec<[WindowUpdate; 4]> {
+        core::mem::take(&mut self.inner.borrow_mut().window_updates)
+    }
+
     pub fn acquired_window(&self) -> VarInt {
         self.inner.borrow().acquired_window
     }
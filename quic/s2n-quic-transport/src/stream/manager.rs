@@ -14,7 +14,7 @@ use crate::{
         stream_container::{StreamContainer, StreamContainerIterationResult},
         stream_events::StreamEvents,
         stream_impl::StreamConfig,
-        StreamError, StreamTrait,
+        StreamError, StreamTrait, WindowUpdate,
     },
     transmission::{self, interest::Provider as _},
 };
@@ -26,6 +26,7 @@ use futures_core::ready;
 use s2n_quic_core::{
     ack,
     connection::error::Error,
+    counter::{Counter, Saturating},
     endpoint,
     frame::{
         stream::StreamRef, DataBlocked, MaxData, MaxStreamData, MaxStreams, ResetStream,
@@ -188,6 +189,10 @@ pub struct StreamManagerState<S> {
     /// Limits for the Stream manager. Since only Stream limits are utilized at
     /// the moment we only store those
     stream_limits: stream::Limits,
+    /// The maximum size the receive window of each Stream may grow to
+    max_stream_data_window: u32,
+    /// The number of stream bytes which were declared lost and queued for retransmission
+    retransmitted_bytes: Counter<u64, Saturating>,
 }
 
 impl<S: StreamTrait> StreamManagerState<S> {
@@ -246,6 +251,7 @@ impl<S: StreamTrait> StreamManagerState<S> {
             stream_id,
             initial_receive_window,
             desired_flow_control_window: initial_receive_window.as_u64() as u32,
+            max_flow_control_window: self.max_stream_data_window,
             initial_send_window,
             max_send_buffer_size: self.stream_limits.max_send_buffer_size.as_u32(),
         }));
@@ -574,6 +580,7 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                 incoming_connection_flow_controller: IncomingConnectionFlowController::new(
                     initial_local_limits.max_data,
                     initial_local_limits.max_data.as_u64() as u32,
+                    connection_limits.max_data_window(),
                 ),
                 outgoing_connection_flow_controller: OutgoingConnectionFlowController::new(
                     initial_peer_limits.max_data,
@@ -584,7 +591,7 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                     initial_local_limits,
                     connection_limits.stream_limits(),
                 ),
-                streams: StreamContainer::new(),
+                streams: StreamContainer::new(connection_limits.stream_scheduler()),
                 next_stream_ids: StreamIdSet::initial(),
                 local_endpoint_type,
                 initial_local_limits,
@@ -592,6 +599,8 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                 close_reason: None,
                 accept_state: AcceptState::new(local_endpoint_type),
                 stream_limits: connection_limits.stream_limits(),
+                max_stream_data_window: connection_limits.max_stream_data_window(),
+                retransmitted_bytes: Default::default(),
             },
             last_blocked_sync_period: Duration::ZERO,
         }
@@ -718,19 +727,25 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
             .on_packet_loss(ack_set);
         self.inner.stream_controller.on_packet_loss(ack_set);
 
+        let mut retransmitted_bytes = 0u64;
         self.inner.streams.iterate_frame_delivery_list(
             &mut self.inner.stream_controller,
             |stream| {
                 // We have to wake inside the lock, since `StreamEvent`s has no capacity
                 // to carry wakers in another iteration
                 let mut events = StreamEvents::new();
-                stream.on_packet_loss(ack_set, &mut events);
+                retransmitted_bytes += stream.on_packet_loss(ack_set, &mut events);
                 events.wake_all();
             },
         );
+        self.inner.retransmitted_bytes += retransmitted_bytes;
     }
 
     fn on_rtt_update(&mut self, rtt_estimator: &RttEstimator) {
+        self.inner
+            .incoming_connection_flow_controller
+            .on_rtt_update(rtt_estimator.smoothed_rtt());
+
         let blocked_sync_period = self.blocked_sync_period(rtt_estimator);
 
         {
@@ -838,9 +853,16 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
             self.inner.streams.iterate_transmission_list(
                 &mut self.inner.stream_controller,
                 |stream: &mut S| {
+                    let remaining_capacity = context.remaining_capacity();
                     transmit_result = stream.on_transmit(context);
                     if transmit_result.is_err() {
-                        StreamContainerIterationResult::BreakAndInsertAtBack
+                        // Let the scheduler know if the stream filled the
+                        // packet so it can give other streams a turn
+                        if context.remaining_capacity() < remaining_capacity {
+                            StreamContainerIterationResult::BreakAfterProgress
+                        } else {
+                            StreamContainerIterationResult::BreakAndInsertAtBack
+                        }
                     } else {
                         StreamContainerIterationResult::Continue
                     }
@@ -930,6 +952,31 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         Ok(())
     }
 
+    fn on_zero_rtt_rejected(
+        &mut self,
+        peer_limits: InitialFlowControlLimits,
+    ) -> Result<(), transport::Error> {
+        let state = &mut self.inner;
+
+        state.stream_controller.on_zero_rtt_rejected(peer_limits)?;
+        state.initial_peer_limits = peer_limits;
+        state
+            .outgoing_connection_flow_controller
+            .reset(peer_limits.max_data);
+
+        let peer_type = state.local_endpoint_type.peer_type();
+        state
+            .streams
+            .iterate_streams(&mut state.stream_controller, |stream| {
+                let max_stream_data = peer_limits
+                    .stream_limits
+                    .max_data(peer_type, stream.stream_id());
+                stream.on_zero_rtt_rejected(max_stream_data);
+            });
+
+        Ok(())
+    }
+
     fn on_streams_blocked(&mut self, _frame: &StreamsBlocked) -> Result<(), transport::Error> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-4.6
         //= type=TODO
@@ -962,6 +1009,23 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
     fn has_pending_streams(&self) -> bool {
         self.inner.streams.has_pending_streams()
     }
+
+    fn on_receive_window_updates<F: FnMut(WindowUpdate)>(&mut self, on_update: F) {
+        self.inner
+            .incoming_connection_flow_controller
+            .take_window_updates()
+            .into_iter()
+            .for_each(on_update);
+    }
+
+    fn write_stats(&self, stats: &mut connection::stats::Stats) {
+        self.inner.stream_controller.write_stats(&mut stats.streams);
+        stats.flow_control_blocked_time = self
+            .inner
+            .outgoing_connection_flow_controller
+            .blocked_time();
+        stats.retransmitted_stream_bytes = *self.inner.retransmitted_bytes;
+    }
 }
 
 impl<S: StreamTrait> timer::Provider for AbstractStreamManager<S> {
@@ -40,7 +40,7 @@ use s2n_quic_core::{
         StopSending, Stream as StreamFrame, StreamDataBlocked, StreamsBlocked,
     },
     packet::number::{PacketNumberRange, PacketNumberSpace},
-    stream::{ops, StreamId, StreamType},
+    stream::{ops, scheduler, Priority, StreamId, StreamType},
     time::{
         clock::testing as time,
         timer::{self, Provider as _},
@@ -67,6 +67,7 @@ struct MockStream {
     on_connection_window_available_retrieve_window: u64,
     on_packet_ack_count: usize,
     on_packet_loss_count: usize,
+    retransmitted_bytes: u64,
     update_blocked_sync_period_count: usize,
     on_timeout_count: usize,
     on_internal_reset_count: usize,
@@ -104,6 +105,7 @@ impl MockStream {
 impl StreamInterestProvider for MockStream {
     fn stream_interests(&self, interests: &mut StreamInterests) {
         interests.merge(&self.interests);
+        interests.priority = self.interests.priority;
 
         interests.connection_flow_control_credits |=
             self.on_connection_window_available_retrieve_window > 0;
@@ -138,6 +140,7 @@ impl StreamTrait for MockStream {
             on_connection_window_available_retrieve_window: 0,
             on_packet_ack_count: 0,
             on_packet_loss_count: 0,
+            retransmitted_bytes: 0,
             update_blocked_sync_period_count: 0,
             on_timeout_count: 0,
             on_internal_reset_count: 0,
@@ -247,11 +250,14 @@ impl StreamTrait for MockStream {
         self.store_wakers(events);
     }
 
-    fn on_packet_loss<A: AckSet>(&mut self, _ack_set: &A, events: &mut StreamEvents) {
+    fn on_packet_loss<A: AckSet>(&mut self, _ack_set: &A, events: &mut StreamEvents) -> u64 {
         self.on_packet_loss_count += 1;
         self.store_wakers(events);
+        self.retransmitted_bytes
     }
 
+    fn on_zero_rtt_rejected(&mut self, _max_stream_data: VarInt) {}
+
     fn update_blocked_sync_period(&mut self, _blocked_sync_period: Duration) {
         self.update_blocked_sync_period_count += 1;
     }
@@ -389,6 +395,13 @@ fn assert_wakeups(wakeup_queue: &mut WakeupQueue<InternalConnectionId>, expected
 }
 
 fn create_stream_manager(local_ep_type: endpoint::Type) -> AbstractStreamManager<MockStream> {
+    create_stream_manager_with_scheduler(local_ep_type, &scheduler::StrictPriority)
+}
+
+fn create_stream_manager_with_scheduler(
+    local_ep_type: endpoint::Type,
+    scheduler: &'static dyn scheduler::Scheduler,
+) -> AbstractStreamManager<MockStream> {
     let initial_local_limits = create_default_initial_flow_control_limits();
     let initial_peer_limits = create_default_initial_flow_control_limits();
 
@@ -397,6 +410,8 @@ fn create_stream_manager(local_ep_type: endpoint::Type) -> AbstractStreamManager
         .with_max_open_local_bidirectional_streams(1000)
         .unwrap()
         .with_max_open_local_unidirectional_streams(1000)
+        .unwrap()
+        .with_stream_scheduler(scheduler)
         .unwrap();
 
     AbstractStreamManager::<MockStream>::new(
@@ -993,6 +1008,69 @@ fn data_blocked_period() {
     assert_blocked_frame_based_on_pto(blocked_func);
 }
 
+#[test]
+fn data_blocked_time_stats() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let current_window =
+        manager.with_outgoing_connection_flow_controller(|ctrl| ctrl.total_window());
+    manager
+        .with_outgoing_connection_flow_controller(|ctrl| ctrl.acquire_window(current_window + 1));
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut write_context = MockWriteContext::new(
+        time::now(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Server,
+    );
+
+    // the connection is blocked for 2 transmission opportunities
+    for _ in 0..2 {
+        assert!(manager.on_transmit(&mut write_context).is_ok());
+        write_context.current_time += Duration::from_millis(100);
+    }
+
+    // the peer increases the window, which unblocks the connection
+    assert!(manager
+        .on_max_data(MaxData {
+            maximum_data: current_window + 100
+        })
+        .is_ok());
+
+    for _ in 0..2 {
+        assert!(manager.on_transmit(&mut write_context).is_ok());
+        write_context.current_time += Duration::from_millis(100);
+    }
+
+    let mut stats = connection::Stats::default();
+    manager.write_stats(&mut stats);
+    assert_eq!(stats.flow_control_blocked_time, Duration::from_millis(200));
+}
+
+#[test]
+fn retransmitted_bytes_stats() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let stream_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let stream_2 = try_open(&mut manager, StreamType::Unidirectional).unwrap();
+
+    for (stream_id, retransmitted_bytes) in [(stream_1, 100), (stream_2, 20)] {
+        manager.with_asserted_stream(stream_id, |stream| {
+            stream.interests.delivery_notifications = true;
+            stream.retransmitted_bytes = retransmitted_bytes;
+        });
+    }
+
+    manager.on_packet_loss(&pn(1));
+    manager.on_packet_loss(&pn(2));
+
+    let mut stats = connection::Stats::default();
+    manager.write_stats(&mut stats);
+    assert_eq!(stats.retransmitted_stream_bytes, 2 * 120);
+}
+
 fn assert_blocked_frame_based_on_pto<F>(mut block_func: F)
 where
     F: FnMut(&mut AbstractStreamManager<MockStream>),
@@ -3109,3 +3187,106 @@ fn stream_transmission_fairness_test() {
         }
     }
 }
+
+#[test]
+fn stream_priority_transmission_order_test() {
+    let priorities = [
+        Priority::new(5, false).unwrap(),
+        Priority::new(1, false).unwrap(),
+        Priority::default(),
+        Priority::new(3, true).unwrap(),
+    ];
+
+    let create_streams = |manager: &mut AbstractStreamManager<MockStream>| -> Vec<StreamId> {
+        priorities
+            .iter()
+            .map(|priority| {
+                let stream_id = try_open(manager, StreamType::Bidirectional).unwrap();
+                manager.with_asserted_stream(stream_id, |stream| {
+                    stream.on_transmit_try_write_frames = 1;
+                    stream.interests.priority = *priority;
+                });
+                stream_id
+            })
+            .collect()
+    };
+
+    // streams with a lower urgency are transmitted first
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+    let streams = create_streams(&mut manager);
+    assert_eq!(
+        [streams[1], streams[2], streams[3], streams[0]],
+        *manager.streams_waiting_for_transmission()
+    );
+
+    // the FIFO scheduler ignores priorities
+    let mut manager =
+        create_stream_manager_with_scheduler(endpoint::Type::Server, &scheduler::Fifo);
+    let streams = create_streams(&mut manager);
+    assert_eq!(streams, manager.streams_waiting_for_transmission());
+
+    // priorities can be updated while the stream is waiting for transmission
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+    let streams = create_streams(&mut manager);
+    manager.with_asserted_stream(streams[0], |stream| {
+        stream.interests.priority = Priority::new(0, false).unwrap();
+    });
+    assert_eq!(
+        [streams[0], streams[1], streams[2], streams[3]],
+        *manager.streams_waiting_for_transmission()
+    );
+}
+
+#[test]
+fn incremental_stream_transmission_test() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    // Create some incremental streams, followed by a less urgent stream
+    let mut streams: VecDeque<_> = (0..3)
+        .map(|_| try_open(&mut manager, StreamType::Bidirectional).unwrap())
+        .collect();
+    let background_stream = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    for stream_id in &streams {
+        manager.with_asserted_stream(*stream_id, |stream| {
+            stream.on_transmit_try_write_frames = 100;
+            stream.interests.priority = Priority::new(2, true).unwrap();
+        });
+    }
+    manager.with_asserted_stream(background_stream, |stream| {
+        stream.on_transmit_try_write_frames = 100;
+    });
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    frame_buffer.set_max_packet_size(Some(1000));
+    let mut write_context = MockWriteContext::new(
+        time::now(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Server,
+    );
+
+    // Each transmission fills a packet with frames from a single stream
+    //
+    // The incremental stream that filled the packet should be moved behind the others
+    for _ in 0..(streams.len() * 2) {
+        let expected: Vec<_> = streams
+            .iter()
+            .copied()
+            .chain(Some(background_stream))
+            .collect();
+        assert_eq!(expected, manager.streams_waiting_for_transmission());
+
+        write_context.frame_buffer.set_error_write_after_n_frames(2);
+        assert!(manager.on_transmit(&mut write_context).is_err());
+        write_context.frame_buffer.flush();
+
+        streams.rotate_left(1);
+    }
+
+    // The background stream didn't get a chance to transmit
+    manager.with_asserted_stream(background_stream, |stream| {
+        assert_eq!(stream.on_transmit_try_write_frames, 100);
+    });
+}
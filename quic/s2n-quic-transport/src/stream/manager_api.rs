@@ -5,7 +5,7 @@ use crate::{
     connection,
     contexts::{ConnectionApiCallContext, OnTransmitError, WriteContext},
     recovery::RttEstimator,
-    stream::StreamError,
+    stream::{StreamError, WindowUpdate},
     transmission,
 };
 use core::task::{Context, Poll};
@@ -119,6 +119,15 @@ pub trait Manager:
     /// This is called when a `MAX_DATA` frame had been received
     fn on_max_data(&mut self, frame: MaxData) -> Result<(), transport::Error>;
 
+    /// This is called on the client when the server rejected early data
+    ///
+    /// The limits remembered from the previous connection are replaced with `peer_limits` and
+    /// all of the streams send their data again.
+    fn on_zero_rtt_rejected(
+        &mut self,
+        peer_limits: InitialFlowControlLimits,
+    ) -> Result<(), transport::Error>;
+
     /// This is called when a `STREAMS_BLOCKED` frame had been received
     fn on_streams_blocked(&mut self, frame: &StreamsBlocked) -> Result<(), transport::Error>;
 
@@ -137,4 +146,10 @@ pub trait Manager:
 
     /// Returns whether or not streams have data to send
     fn has_pending_streams(&self) -> bool;
+
+    /// Calls `on_update` for each receive window which was grown since the last call
+    fn on_receive_window_updates<F: FnMut(WindowUpdate)>(&mut self, on_update: F);
+
+    /// Writes the stream and flow control statistics into a connection statistics snapshot
+    fn write_stats(&self, stats: &mut connection::stats::Stats);
 }
@@ -15,6 +15,7 @@ mod stream_container;
 mod stream_events;
 mod stream_impl;
 mod stream_interests;
+mod window_tuner;
 
 #[cfg(debug_assertions)]
 pub(crate) mod contract;
@@ -26,6 +27,7 @@ pub use manager_api::Manager;
 pub use s2n_quic_core::stream::limits::Limits;
 pub use stream_events::StreamEvents;
 pub use stream_impl::{StreamImpl, StreamTrait};
+pub use window_tuner::WindowUpdate;
 
 pub type DefaultStreamManager = AbstractStreamManager<StreamImpl>;
 
@@ -29,6 +29,13 @@ struct OutgoingConnectionFlowControllerImpl {
     available_window: VarInt,
     /// For periodically sending `DATA_BLOCKED` frames when blocked by peer limits
     data_blocked_sync: PeriodicSync<VarInt, DataBlockedToFrameWriter>,
+    /// True if a stream was unable to acquire its desired window since the
+    /// last `MAX_DATA` frame was received
+    is_blocked: bool,
+    /// The time at which `blocked_time` was last updated while blocked
+    blocked_timestamp: Option<Timestamp>,
+    /// The total amount of time the connection has been blocked by peer limits
+    blocked_time: Duration,
 }
 
 impl OutgoingConnectionFlowControllerImpl {
@@ -37,6 +44,9 @@ impl OutgoingConnectionFlowControllerImpl {
             total_available_window: initial_window_size,
             available_window: initial_window_size,
             data_blocked_sync: PeriodicSync::new(),
+            is_blocked: false,
+            blocked_timestamp: None,
+            blocked_time: Duration::ZERO,
         }
     }
 
@@ -62,6 +72,7 @@ impl OutgoingConnectionFlowControllerImpl {
             //# control; see Section 4.
             self.data_blocked_sync
                 .request_delivery(self.total_available_window);
+            self.is_blocked = true;
         }
 
         result
@@ -81,6 +92,32 @@ impl OutgoingConnectionFlowControllerImpl {
 
         // We now have more capacity from the peer so stop sending DATA_BLOCKED frames
         self.data_blocked_sync.stop_sync();
+        self.is_blocked = false;
+    }
+
+    /// Replaces the connection window after the peer rejected early data
+    ///
+    /// Any window previously handed out to streams is returned, since the streams resend
+    /// their data from the beginning.
+    pub fn reset(&mut self, max_data: VarInt) {
+        self.total_available_window = max_data;
+        self.available_window = max_data;
+        self.data_blocked_sync.stop_sync();
+        self.is_blocked = false;
+    }
+
+    /// Accumulates the time spent blocked since the last update
+    ///
+    /// Blocked time is only accounted for at transmission opportunities, since those are
+    /// the only points in time the connection could have sent data.
+    fn update_blocked_time(&mut self, now: Timestamp) {
+        if let Some(prev) = self.blocked_timestamp.take() {
+            self.blocked_time += now.saturating_duration_since(prev);
+        }
+
+        if self.is_blocked {
+            self.blocked_timestamp = Some(now);
+        }
     }
 }
 
@@ -135,6 +172,12 @@ impl OutgoingConnectionFlowController {
         inner.total_available_window - inner.available_window
     }
 
+    /// Returns the total amount of time the connection has been blocked by the
+    /// peer's connection flow control limit
+    pub fn blocked_time(&self) -> Duration {
+        self.inner.borrow().blocked_time
+    }
+
     /// Acquires a part of the window from the `ConnectionFlowController` in
     /// order to be able to use it for sending data. `desired` is the window
     /// size that is intended to be borrowed. The returned window size might
@@ -154,6 +197,12 @@ impl OutgoingConnectionFlowController {
         self.inner.borrow_mut().on_max_data(frame)
     }
 
+    /// This method should be called when the peer rejected early data and the window
+    /// advertised in its transport parameters replaces the remembered window.
+    pub fn reset(&mut self, max_data: VarInt) {
+        self.inner.borrow_mut().reset(max_data)
+    }
+
     /// This method is called when a packet delivery got acknowledged
     pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
         self.inner
@@ -186,7 +235,9 @@ impl OutgoingConnectionFlowController {
         //# connection from closing, a sender that is flow control limited SHOULD
         //# periodically send a STREAM_DATA_BLOCKED or DATA_BLOCKED frame when it
         //# has no ack-eliciting packets in flight.
-        let data_blocked_sync = &mut self.inner.borrow_mut().data_blocked_sync;
+        let inner = &mut *self.inner.borrow_mut();
+        inner.update_blocked_time(context.current_time());
+        let data_blocked_sync = &mut inner.data_blocked_sync;
 
         if context.ack_elicitation().is_ack_eliciting() && data_blocked_sync.has_delivered() {
             // We are already sending an ack-eliciting packet, so no need to send another DATA_BLOCKED
@@ -200,7 +251,9 @@ impl OutgoingConnectionFlowController {
 
     /// Called when the connection timer expires
     pub fn on_timeout(&mut self, now: Timestamp) {
-        self.inner.borrow_mut().data_blocked_sync.on_timeout(now)
+        let inner = &mut *self.inner.borrow_mut();
+        inner.update_blocked_time(now);
+        inner.data_blocked_sync.on_timeout(now)
     }
 }
 
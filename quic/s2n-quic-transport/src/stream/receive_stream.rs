@@ -7,6 +7,7 @@ use crate::{
         incoming_connection_flow_controller::IncomingConnectionFlowController,
         stream_events::StreamEvents,
         stream_interests::{StreamInterestProvider, StreamInterests},
+        window_tuner::{WindowTuner, WindowUpdate},
         StreamError,
     },
     sync::{IncrementalValueSync, OnceSync, ValueToFrameWriter},
@@ -24,6 +25,7 @@ use s2n_quic_core::{
     frame::{stream::StreamRef, MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
     packet::number::PacketNumber,
     stream::{ops, StreamId},
+    time::Timestamp,
     transport,
     varint::VarInt,
 };
@@ -183,6 +185,9 @@ pub(super) struct ReceiveStreamFlowController {
     pub(super) acquired_connection_window: VarInt,
     /// The amount of credits which had been released in total
     pub(super) released_connection_window: VarInt,
+    /// Grows the `desired_flow_control_window` based on the rate at which
+    /// the application consumes data
+    window_tuner: WindowTuner,
 }
 
 impl ReceiveStreamFlowController {
@@ -190,6 +195,7 @@ impl ReceiveStreamFlowController {
         connection_flow_controller: IncomingConnectionFlowController,
         initial_window: VarInt,
         desired_flow_control_window: u32,
+        max_flow_control_window: u32,
     ) -> Self {
         Self {
             connection_flow_controller,
@@ -201,6 +207,7 @@ impl ReceiveStreamFlowController {
             acquired_connection_window: VarInt::from_u32(0),
             released_connection_window: VarInt::from_u32(0),
             desired_flow_control_window,
+            window_tuner: WindowTuner::new(max_flow_control_window),
         }
     }
 
@@ -299,6 +306,35 @@ impl ReceiveStreamFlowController {
         self.release_window(unreleased);
     }
 
+    /// Grows the Streams flow control window if the application consumes data
+    /// fast enough
+    fn tune_window(&mut self, stream_id: StreamId, now: Timestamp) {
+        if self.read_window_sync.is_cancelled() {
+            return;
+        }
+
+        if !self.window_tuner.on_transmit(
+            &mut self.desired_flow_control_window,
+            self.released_connection_window,
+            now,
+            self.connection_flow_controller.smoothed_rtt(),
+        ) {
+            return;
+        }
+
+        self.read_window_sync
+            .set_threshold(VarInt::from_u32(self.desired_flow_control_window / 10));
+        self.read_window_sync.update_latest_value(
+            self.released_connection_window
+                .saturating_add(VarInt::from_u32(self.desired_flow_control_window)),
+        );
+        self.connection_flow_controller
+            .on_window_update(WindowUpdate {
+                stream_id: Some(stream_id),
+                window: self.desired_flow_control_window,
+            });
+    }
+
     /// Stop to synchronize the Streams flow control window to the peer
     fn stop_sync(&mut self) {
         self.read_window_sync.stop_sync();
@@ -357,6 +393,7 @@ impl ReceiveStream {
         connection_flow_controller: IncomingConnectionFlowController,
         initial_window: VarInt,
         desired_flow_control_window: u32,
+        max_flow_control_window: u32,
     ) -> ReceiveStream {
         // If the stream is created in closed state directly move into the
         // terminal state.
@@ -373,6 +410,7 @@ impl ReceiveStream {
                 connection_flow_controller,
                 initial_window,
                 desired_flow_control_window,
+                max_flow_control_window,
             ),
             stop_sending_sync: OnceSync::new(),
             read_waiter: None,
@@ -713,6 +751,8 @@ impl ReceiveStream {
         //# To avoid blocking a sender, a receiver MAY send a MAX_STREAM_DATA or
         //# MAX_DATA frame multiple times within a round trip or send it early
         //# enough to allow time for loss of the frame and subsequent recovery.
+        self.flow_controller
+            .tune_window(stream_id, context.current_time());
         self.flow_controller
             .read_window_sync
             .on_transmit(stream_id, context)
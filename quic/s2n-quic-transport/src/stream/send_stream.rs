@@ -26,7 +26,7 @@ use s2n_quic_core::{
     ack, application,
     frame::{MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
     packet::number::PacketNumber,
-    stream::{ops, StreamId},
+    stream::{ops, Priority, StreamId},
     time::{timer, Timestamp},
     transport,
     varint::VarInt,
@@ -261,6 +261,20 @@ impl StreamFlowController {
         }
     }
 
+    /// Replaces the stream window after the peer rejected early data
+    ///
+    /// The connection window that was acquired is released, since the stream sends its data
+    /// again from the beginning.
+    pub fn reset(&mut self, max_stream_data: VarInt) {
+        self.acquired_connection_flow_controller_window = VarInt::from_u32(0);
+        self.highest_requested_connection_flow_control_window = VarInt::from_u32(0);
+        self.max_stream_data = max_stream_data;
+        if self.state != StreamFlowControllerState::Finished {
+            self.state = StreamFlowControllerState::Ready;
+        }
+        self.stream_data_blocked_sync.stop_sync();
+    }
+
     /// Returns the window/offset up to which data can be written
     fn available_window(&self) -> VarInt {
         core::cmp::min(
@@ -457,6 +471,8 @@ pub struct SendStream {
     final_state_observed: bool,
     /// Marks the stream as detached from the application
     detached: bool,
+    /// The transmission priority set by the application
+    priority: Priority,
 }
 
 impl SendStream {
@@ -485,6 +501,7 @@ impl SendStream {
             write_waiter: None,
             final_state_observed: is_closed,
             detached: is_closed,
+            priority: Priority::default(),
         };
 
         if is_closed {
@@ -534,6 +551,19 @@ impl SendStream {
         Ok(())
     }
 
+    /// This is called on the client when the server rejected early data
+    ///
+    /// Any data that was sent in 0-RTT packets is sent again within the `max_stream_data`
+    /// limit advertised by the server.
+    pub fn on_zero_rtt_rejected(&mut self, max_stream_data: VarInt) {
+        if let SendStreamState::Sending = self.state {
+            self.data_sender
+                .flow_controller_mut()
+                .reset(max_stream_data);
+            self.data_sender.rewind();
+        }
+    }
+
     /// This is called when a `STOP_SENDING` frame had been received for
     /// this stream
     pub fn on_stop_sending(
@@ -638,12 +668,15 @@ impl SendStream {
     }
 
     /// This method gets called when a packet loss is reported
-    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
-        self.data_sender.on_packet_loss(ack_set);
+    ///
+    /// Returns the number of bytes which were queued for retransmission
+    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) -> u64 {
+        let retransmitted_bytes = self.data_sender.on_packet_loss(ack_set);
         self.data_sender
             .flow_controller_mut()
             .on_packet_loss(ack_set);
         self.reset_sync.on_packet_loss(ack_set);
+        retransmitted_bytes
     }
 
     /// Queries the component for any outgoing frames that need to get sent
@@ -755,6 +788,10 @@ impl SendStream {
             self.detach();
         }
 
+        if let Some(priority) = request.priority {
+            self.priority = priority;
+        }
+
         macro_rules! store_waker {
             ($should_flush:expr) => {
                 // Store the waker, in order to be able to wakeup the caller
@@ -1028,6 +1065,8 @@ impl timer::Provider for SendStream {
 impl StreamInterestProvider for SendStream {
     #[inline]
     fn stream_interests(&self, interests: &mut StreamInterests) {
+        interests.priority = self.priority;
+
         match self.state {
             //= https://www.rfc-editor.org/rfc/rfc9000#section-3.3
             //# A sender MUST NOT send any of these frames from a terminal state
@@ -2854,3 +2854,24 @@ fn can_query_stream_readiness() {
         }
     }
 }
+
+#[test]
+fn priority_is_reported_in_interests() {
+    let mut test_env = setup_send_only_test_env();
+    assert_eq!(
+        test_env.stream.get_stream_interests().priority,
+        Priority::default()
+    );
+
+    let priority = Priority::new(1, true).unwrap();
+    test_env
+        .run_request(ops::Request::default().with_priority(priority), false)
+        .expect("request should succeed");
+    assert_eq!(test_env.stream.get_stream_interests().priority, priority);
+
+    // requests without a priority keep the previous value
+    test_env
+        .run_request(ops::Request::default().flush(), false)
+        .expect("request should succeed");
+    assert_eq!(test_env.stream.get_stream_interests().priority, priority);
+}
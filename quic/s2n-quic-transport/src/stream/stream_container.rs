@@ -13,11 +13,20 @@ use crate::{
     transmission,
 };
 use alloc::rc::Rc;
-use core::{cell::RefCell, ops::Deref};
+use core::{
+    cell::{Cell, RefCell},
+    ops::Deref,
+};
 use intrusive_collections::{
     intrusive_adapter, KeyAdapter, LinkedList, LinkedListLink, RBTree, RBTreeLink,
 };
-use s2n_quic_core::{stream::StreamId, time::timer};
+use s2n_quic_core::{
+    stream::{
+        scheduler::{Scheduler, QUEUE_COUNT},
+        StreamId,
+    },
+    time::timer,
+};
 
 // Intrusive list adapter for managing the list of `done` streams
 intrusive_adapter!(DoneStreamsAdapter<S> = Rc<StreamNode<S>>: StreamNode<S> {
@@ -73,6 +82,8 @@ struct StreamNode<S> {
     waiting_for_frame_delivery_link: LinkedListLink,
     /// Allows the Stream to be part of the `waiting_for_transmission` collection
     waiting_for_transmission_link: LinkedListLink,
+    /// The index of the `waiting_for_transmission` queue the Stream is part of
+    transmission_queue: Cell<usize>,
     /// Allows the Stream to be part of the `waiting_for_transmission` collection
     waiting_for_retransmission_link: LinkedListLink,
     /// Allows the Stream to be part of the `waiting_for_connection_flow_control_credits` collection
@@ -90,6 +101,7 @@ impl<S> StreamNode<S> {
             done_streams_link: LinkedListLink::new(),
             waiting_for_frame_delivery_link: LinkedListLink::new(),
             waiting_for_transmission_link: LinkedListLink::new(),
+            transmission_queue: Cell::new(0),
             waiting_for_retransmission_link: LinkedListLink::new(),
             waiting_for_connection_flow_control_credits_link: LinkedListLink::new(),
             waiting_for_stream_flow_control_credits_link: LinkedListLink::new(),
@@ -133,8 +145,9 @@ struct InterestLists<S> {
     /// Streams which are waiting for packet acknowledgements and
     /// packet loss notifications
     waiting_for_frame_delivery: LinkedList<WaitingForFrameDeliveryAdapter<S>>,
-    /// Streams which need to transmit data
-    waiting_for_transmission: LinkedList<WaitingForTransmissionAdapter<S>>,
+    /// Streams which need to transmit data, grouped into the queues assigned
+    /// by the `scheduler`
+    waiting_for_transmission: [LinkedList<WaitingForTransmissionAdapter<S>>; QUEUE_COUNT],
     /// Streams which need to transmit data
     waiting_for_retransmission: LinkedList<WaitingForRetransmissionAdapter<S>>,
     /// Streams which are blocked on transmission due to waiting on the
@@ -145,14 +158,18 @@ struct InterestLists<S> {
     /// stream flow control window to increase
     waiting_for_stream_flow_control_credits:
         LinkedList<WaitingForStreamFlowControlCreditsAdapter<S>>,
+    /// Assigns Streams which need to transmit data to a queue
+    scheduler: &'static dyn Scheduler,
 }
 
 impl<S: StreamTrait> InterestLists<S> {
-    fn new() -> Self {
+    fn new(scheduler: &'static dyn Scheduler) -> Self {
         Self {
             done_streams: LinkedList::new(DoneStreamsAdapter::new()),
             waiting_for_frame_delivery: LinkedList::new(WaitingForFrameDeliveryAdapter::new()),
-            waiting_for_transmission: LinkedList::new(WaitingForTransmissionAdapter::new()),
+            waiting_for_transmission: core::array::from_fn(|_| {
+                LinkedList::new(WaitingForTransmissionAdapter::new())
+            }),
             waiting_for_retransmission: LinkedList::new(WaitingForRetransmissionAdapter::new()),
             waiting_for_connection_flow_control_credits: LinkedList::new(
                 WaitingForConnectionFlowControlCreditsAdapter::new(),
@@ -160,6 +177,7 @@ impl<S: StreamTrait> InterestLists<S> {
             waiting_for_stream_flow_control_credits: LinkedList::new(
                 WaitingForStreamFlowControlCreditsAdapter::new(),
             ),
+            scheduler,
         }
     }
 
@@ -206,11 +224,7 @@ impl<S: StreamTrait> InterestLists<S> {
             waiting_for_frame_delivery_link,
             waiting_for_frame_delivery
         );
-        sync_interests!(
-            matches!(interests.transmission, transmission::Interest::NewData),
-            waiting_for_transmission_link,
-            waiting_for_transmission
-        );
+        self.sync_transmission_interest(node, interests, result);
         sync_interests!(
             matches!(interests.transmission, transmission::Interest::LostData),
             waiting_for_retransmission_link,
@@ -238,6 +252,47 @@ impl<S: StreamTrait> InterestLists<S> {
             false
         }
     }
+
+    /// Moves the Stream into the `waiting_for_transmission` queue which the
+    /// scheduler assigns for its current priority
+    fn sync_transmission_interest(
+        &mut self,
+        node: &Rc<StreamNode<S>>,
+        interests: StreamInterests,
+        result: StreamContainerIterationResult,
+    ) {
+        let interest = matches!(interests.transmission, transmission::Interest::NewData);
+        let queue = self.scheduler.queue(interests.priority);
+
+        if node.waiting_for_transmission_link.is_linked()
+            && (!interest || node.transmission_queue.get() != queue.index())
+        {
+            // Safety: We know that the node is only ever part of the queue
+            // recorded in `transmission_queue`.
+            let mut cursor = unsafe {
+                self.waiting_for_transmission[node.transmission_queue.get()]
+                    .cursor_mut_from_ptr(node.deref() as *const StreamNode<S>)
+            };
+            cursor.remove();
+        }
+
+        if interest && !node.waiting_for_transmission_link.is_linked() {
+            node.transmission_queue.set(queue.index());
+            let list = &mut self.waiting_for_transmission[queue.index()];
+
+            match result {
+                StreamContainerIterationResult::Continue => list.push_back(node.clone()),
+                // Streams in round robin queues give up their position after
+                // filling a packet
+                StreamContainerIterationResult::BreakAfterProgress if queue.is_round_robin() => {
+                    list.push_back(node.clone())
+                }
+                _ => list.push_front(node.clone()),
+            }
+        }
+
+        debug_assert_eq!(interest, node.waiting_for_transmission_link.is_linked());
+    }
 }
 
 /// A collection of all intrusive lists Streams are part of.
@@ -312,7 +367,8 @@ macro_rules! iterate_interruptible {
                 .update_interests(&stream, interests, result);
 
             match result {
-                StreamContainerIterationResult::BreakAndInsertAtBack => {
+                StreamContainerIterationResult::BreakAndInsertAtBack
+                | StreamContainerIterationResult::BreakAfterProgress => {
                     $sel.interest_lists
                         .$list_name
                         .front_mut()
@@ -330,12 +386,13 @@ macro_rules! iterate_interruptible {
 }
 
 impl<S: StreamTrait> StreamContainer<S> {
-    /// Creates a new `StreamContainer`
-    pub fn new() -> Self {
+    /// Creates a new `StreamContainer` which orders transmissions with the
+    /// given `Scheduler`
+    pub fn new(scheduler: &'static dyn Scheduler) -> Self {
         Self {
             stream_map: RBTree::new(StreamTreeAdapter::new()),
             nr_active_streams: 0,
-            interest_lists: InterestLists::new(),
+            interest_lists: InterestLists::new(scheduler),
         }
     }
 
@@ -459,7 +516,16 @@ impl<S: StreamTrait> StreamContainer<S> {
             }
 
             remove_stream_from_list!(waiting_for_frame_delivery, waiting_for_frame_delivery_link);
-            remove_stream_from_list!(waiting_for_transmission, waiting_for_transmission_link);
+            if stream.waiting_for_transmission_link.is_linked() {
+                // Safety: We know that the Stream is part of the queue recorded
+                // in `transmission_queue`, because it is linked.
+                let mut cursor = unsafe {
+                    self.interest_lists.waiting_for_transmission[stream.transmission_queue.get()]
+                        .cursor_mut_from_ptr(stream_ptr)
+                };
+                let remove_result = cursor.remove();
+                debug_assert!(remove_result.is_some());
+            }
             remove_stream_from_list!(waiting_for_retransmission, waiting_for_retransmission_link);
             remove_stream_from_list!(
                 waiting_for_connection_flow_control_credits,
@@ -540,19 +606,53 @@ impl<S: StreamTrait> StreamContainer<S> {
     /// Iterates over all `Stream`s which are waiting for transmission,
     /// and executes the given function on each `Stream`
     ///
+    /// Queues are visited in the order of their index, as assigned by the
+    /// `Scheduler`.
+    ///
     /// The `stream::Controller` will be notified of streams that have been
     /// closed to allow for further streams to be opened.
     pub fn iterate_transmission_list<F>(&mut self, controller: &mut stream::Controller, mut func: F)
     where
         F: FnMut(&mut S) -> StreamContainerIterationResult,
     {
-        iterate_interruptible!(
-            self,
-            waiting_for_transmission,
-            waiting_for_transmission_link,
-            controller,
-            func
-        );
+        'queues: for index in 0..QUEUE_COUNT {
+            let mut extracted_list = self.interest_lists.waiting_for_transmission[index].take();
+            let mut cursor = extracted_list.front_mut();
+
+            while let Some(stream) = cursor.remove() {
+                debug_assert!(!stream.waiting_for_transmission_link.is_linked());
+                let mut mut_stream = stream.inner.borrow_mut();
+                let result = func(&mut *mut_stream);
+
+                // Update the interests after the interaction
+                let interests = mut_stream.get_stream_interests();
+                self.interest_lists
+                    .update_interests(&stream, interests, result);
+
+                if matches!(result, StreamContainerIterationResult::Continue) {
+                    continue;
+                }
+
+                let list = &mut self.interest_lists.waiting_for_transmission[index];
+                let queue = self.interest_lists.scheduler.queue(interests.priority);
+
+                if matches!(result, StreamContainerIterationResult::BreakAfterProgress)
+                    && queue.is_round_robin()
+                {
+                    // The Stream moved itself to the back of the queue, so the
+                    // remaining items are visited first the next time
+                    list.cursor_mut().splice_after(extracted_list);
+                } else {
+                    list.front_mut().splice_after(extracted_list);
+                }
+
+                break 'queues;
+            }
+        }
+
+        if !self.interest_lists.done_streams.is_empty() {
+            self.finalize_done_streams(controller);
+        }
     }
 
     /// Iterates over all `Stream`s which are waiting for retransmission,
@@ -617,9 +717,17 @@ impl<S: StreamTrait> StreamContainer<S> {
 
     /// Returns whether or not streams have data to send
     pub fn has_pending_streams(&self) -> bool {
-        !self.interest_lists.waiting_for_transmission.is_empty()
+        self.has_transmission_streams()
             || !self.interest_lists.waiting_for_retransmission.is_empty()
     }
+
+    /// Returns whether or not any of the transmission queues contain streams
+    fn has_transmission_streams(&self) -> bool {
+        self.interest_lists
+            .waiting_for_transmission
+            .iter()
+            .any(|list| !list.is_empty())
+    }
 }
 
 impl<S: StreamTrait> timer::Provider for StreamContainer<S> {
@@ -645,7 +753,7 @@ impl<S: StreamTrait> transmission::interest::Provider for StreamContainer<S> {
     ) -> transmission::interest::Result {
         if !self.interest_lists.waiting_for_retransmission.is_empty() {
             query.on_lost_data()?;
-        } else if !self.interest_lists.waiting_for_transmission.is_empty() {
+        } else if self.has_transmission_streams() {
             query.on_new_data()?;
         }
 
@@ -662,4 +770,9 @@ pub enum StreamContainerIterationResult {
     /// Aborts the iteration over a list and add the remaining items at the
     /// back of the list
     BreakAndInsertAtBack,
+    /// Aborts the iteration over a list after the `Stream` made progress.
+    ///
+    /// `Stream`s in round robin transmission queues are moved behind the
+    /// remaining items. Otherwise this behaves like `BreakAndInsertAtBack`.
+    BreakAfterProgress,
 }
@@ -38,6 +38,8 @@ pub struct StreamConfig {
     pub initial_receive_window: VarInt,
     /// The desired flow control window that we want to maintain on the receiving side
     pub desired_flow_control_window: u32,
+    /// The maximum size the receiving flow control window may grow to
+    pub max_flow_control_window: u32,
     /// The initial flow control window for sending data
     pub initial_send_window: VarInt,
     /// The maximum buffered amount of data on the sending side
@@ -98,7 +100,14 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
     fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A, events: &mut StreamEvents);
 
     /// This method gets called when a packet loss is reported
-    fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A, events: &mut StreamEvents);
+    ///
+    /// Returns the number of stream bytes which were queued for retransmission
+    fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A, events: &mut StreamEvents) -> u64;
+
+    /// This method gets called on the client when the server rejected early data
+    ///
+    /// `max_stream_data` is the stream limit advertised by the server for the connection.
+    fn on_zero_rtt_rejected(&mut self, max_stream_data: VarInt);
 
     /// Updates the period at which `STREAM_DATA_BLOCKED` frames are sent to the peer
     /// if the application is blocked by peer limits.
@@ -204,6 +213,7 @@ impl StreamTrait for StreamImpl {
                 config.incoming_connection_flow_controller,
                 config.initial_receive_window,
                 config.desired_flow_control_window,
+                config.max_flow_control_window,
             ),
             has_send: !send_is_closed,
             send_stream: SendStream::new(
@@ -283,9 +293,14 @@ impl StreamTrait for StreamImpl {
     }
 
     #[inline]
-    fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A, _events: &mut StreamEvents) {
+    fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A, _events: &mut StreamEvents) -> u64 {
         self.receive_stream.on_packet_loss(ack_set);
-        self.send_stream.on_packet_loss(ack_set);
+        self.send_stream.on_packet_loss(ack_set)
+    }
+
+    #[inline]
+    fn on_zero_rtt_rejected(&mut self, max_stream_data: VarInt) {
+        self.send_stream.on_zero_rtt_rejected(max_stream_data);
     }
 
     #[inline]
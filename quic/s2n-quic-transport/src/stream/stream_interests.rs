@@ -4,6 +4,7 @@
 //! A collection of a all the interactions a `Stream` is interested in
 
 use crate::transmission::interest::{Interest, Query, QueryBreak, Result};
+use s2n_quic_core::stream::Priority;
 
 /// A collection of a all the interactions a `Stream` is interested in
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
@@ -22,6 +23,11 @@ pub struct StreamInterests {
     pub delivery_notifications: bool,
     /// Transmission interest for the component
     pub transmission: Interest,
+    /// The priority used to order the `Stream` for transmission
+    ///
+    /// This is reported by the sending half of the `Stream` and is not
+    /// modified by `merge`.
+    pub priority: Priority,
 }
 
 impl StreamInterests {
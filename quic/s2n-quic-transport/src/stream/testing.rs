@@ -479,10 +479,12 @@ pub struct TestEnvironmentConfig {
     pub stream_id: StreamId,
     pub initial_receive_window: u64,
     pub desired_flow_control_window: u32,
+    pub max_flow_control_window: u32,
     pub initial_send_window: u64,
     pub initial_connection_send_window_size: u64,
     pub initial_connection_receive_window_size: u64,
     pub desired_connection_flow_control_window: u32,
+    pub max_connection_flow_control_window: u32,
     pub max_send_buffer_size: usize,
     pub transmission_constraint: transmission::Constraint,
     pub local_endpoint_type: endpoint::Type,
@@ -505,6 +507,7 @@ impl TestEnvironmentConfig {
             ),
             initial_receive_window: TestEnvironment::DEFAULT_INITIAL_RECEIVE_WINDOW,
             desired_flow_control_window: TestEnvironment::DEFAULT_INITIAL_RECEIVE_WINDOW as u32,
+            max_flow_control_window: TestEnvironment::DEFAULT_INITIAL_RECEIVE_WINDOW as u32,
             initial_send_window: TestEnvironment::DEFAULT_INITIAL_SEND_WINDOW,
             initial_connection_send_window_size:
                 TestEnvironment::DEFAULT_INITIAL_CONNECTION_SEND_WINDOW,
@@ -512,6 +515,8 @@ impl TestEnvironmentConfig {
                 TestEnvironment::DEFAULT_INITIAL_CONNECTION_RECEIVE_WINDOW,
             desired_connection_flow_control_window:
                 TestEnvironment::DEFAULT_INITIAL_CONNECTION_RECEIVE_WINDOW as u32,
+            max_connection_flow_control_window:
+                TestEnvironment::DEFAULT_INITIAL_CONNECTION_RECEIVE_WINDOW as u32,
             max_send_buffer_size: TestEnvironment::DEFAULT_MAX_SEND_BUFFER_SIZE,
             transmission_constraint: transmission::Constraint::None,
             max_packet_size: None,
@@ -530,6 +535,7 @@ pub fn setup_stream_test_env_with_config(config: TestEnvironmentConfig) -> TestE
     let rx_connection_flow_controller = IncomingConnectionFlowController::new(
         VarInt::new(config.initial_connection_receive_window_size).unwrap(),
         config.desired_connection_flow_control_window,
+        config.max_connection_flow_control_window,
     );
 
     let tx_connection_flow_controller = OutgoingConnectionFlowController::new(
@@ -543,6 +549,7 @@ pub fn setup_stream_test_env_with_config(config: TestEnvironmentConfig) -> TestE
         stream_id: config.stream_id,
         initial_receive_window: VarInt::new(config.initial_receive_window).unwrap(),
         desired_flow_control_window: config.desired_flow_control_window,
+        max_flow_control_window: config.max_flow_control_window,
         initial_send_window: VarInt::new(config.initial_send_window).unwrap(),
         max_send_buffer_size: config.max_send_buffer_size as u32,
     });
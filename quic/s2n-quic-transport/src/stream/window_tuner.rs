@@ -0,0 +1,126 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Grows receive windows based on how fast the application consumes data
+
+use core::time::Duration;
+use s2n_quic_core::{stream::StreamId, time::Timestamp, varint::VarInt};
+
+/// The number of round trips in which the application needs to consume a full
+/// window in order for the window to grow
+const GROWTH_RTT_MULTIPLIER: u32 = 2;
+
+/// A receive window which was increased by a [`WindowTuner`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WindowUpdate {
+    /// The stream of the window, or `None` for the connection window
+    pub stream_id: Option<StreamId>,
+    /// The new size of the window
+    pub window: u32,
+}
+
+/// Tracks the rate at which the application consumes a receive window
+///
+/// If a full window is consumed in less than 2 round trips, the peer is likely
+/// limited by flow control rather than the network. In this case the window is
+/// doubled, up to the configured maximum.
+#[derive(Debug)]
+pub struct WindowTuner {
+    /// The maximum size the window may grow to
+    max_window: u32,
+    /// The time at which the current measurement started
+    epoch_start: Option<Timestamp>,
+    /// The amount of data consumed by the application when the current
+    /// measurement started
+    epoch_offset: VarInt,
+}
+
+impl WindowTuner {
+    /// Creates a new `WindowTuner` which grows windows up to `max_window`
+    pub fn new(max_window: u32) -> Self {
+        Self {
+            max_window,
+            epoch_start: None,
+            epoch_offset: VarInt::from_u32(0),
+        }
+    }
+
+    /// Called before the window is synchronized to the peer
+    ///
+    /// `consumed` is the total amount of data consumed by the application.
+    /// Returns `true` if `window` was increased.
+    pub fn on_transmit(
+        &mut self,
+        window: &mut u32,
+        consumed: VarInt,
+        now: Timestamp,
+        smoothed_rtt: Duration,
+    ) -> bool {
+        if *window >= self.max_window {
+            return false;
+        }
+
+        let epoch_start = if let Some(epoch_start) = self.epoch_start {
+            epoch_start
+        } else {
+            self.start_epoch(consumed, now);
+            return false;
+        };
+
+        // wait until the application has consumed a full window
+        if consumed.saturating_sub(self.epoch_offset) < VarInt::from_u32(*window) {
+            return false;
+        }
+
+        let elapsed = now.saturating_duration_since(epoch_start);
+        self.start_epoch(consumed, now);
+
+        if elapsed >= smoothed_rtt * GROWTH_RTT_MULTIPLIER {
+            return false;
+        }
+
+        *window = window.saturating_mul(2).min(self.max_window);
+        true
+    }
+
+    fn start_epoch(&mut self, consumed: VarInt, now: Timestamp) {
+        self.epoch_start = Some(now);
+        self.epoch_offset = consumed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::time::clock::testing as time;
+
+    #[test]
+    fn window_growth_test() {
+        let rtt = Duration::from_millis(100);
+        let mut now = time::now();
+        let mut window = 1000;
+        let mut tuner = WindowTuner::new(3000);
+
+        // the first call starts the measurement
+        assert!(!tuner.on_transmit(&mut window, VarInt::from_u32(0), now, rtt));
+
+        // the window is not grown until a full window is consumed
+        now += rtt;
+        assert!(!tuner.on_transmit(&mut window, VarInt::from_u32(999), now, rtt));
+        assert!(tuner.on_transmit(&mut window, VarInt::from_u32(1000), now, rtt));
+        assert_eq!(window, 2000);
+
+        // consuming the window slower than 2 RTTs doesn't grow the window
+        now += rtt * 2;
+        assert!(!tuner.on_transmit(&mut window, VarInt::from_u32(3000), now, rtt));
+        assert_eq!(window, 2000);
+
+        // the window doesn't exceed the maximum
+        now += rtt;
+        assert!(tuner.on_transmit(&mut window, VarInt::from_u32(5000), now, rtt));
+        assert_eq!(window, 3000);
+        now += rtt;
+        assert!(!tuner.on_transmit(&mut window, VarInt::from_u32(8000), now, rtt));
+        assert_eq!(window, 3000);
+    }
+}
@@ -190,6 +190,24 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
         self.check_integrity();
     }
 
+    /// Sends all of the unacknowledged data again as if it was never transmitted
+    ///
+    /// This is used by clients when the peer rejected the early data. Unlike lost data, the
+    /// rewound data acquires the flow control window again.
+    pub fn rewind(&mut self) {
+        self.transmissions.clear();
+        self.lost.clear();
+        self.transmission_offset = self.buffer.head();
+
+        if let Some(fin_state) = self.state.fin_state_mut() {
+            if matches!(fin_state, FinState::InFlight(_) | FinState::Lost) {
+                *fin_state = FinState::Pending;
+            }
+        }
+
+        self.check_integrity();
+    }
+
     /// Returns the amount of bytes that have ever been enqueued for writing on
     /// this Stream. This equals the offset of the highest enqueued byte + 1.
     pub fn total_enqueued_len(&self) -> VarInt {
@@ -333,7 +351,10 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
     }
 
     /// This method gets called when a packet loss is reported
-    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
+    ///
+    /// Returns the number of bytes which were queued for retransmission
+    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) -> u64 {
+        let lost_len = self.lost_len();
         let lost = &mut self.lost;
 
         let mut any_lost = self.transmissions.on_ack_signal(ack_set, |range| {
@@ -358,6 +379,17 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
         }
 
         self.check_integrity();
+
+        // data which was already acked in another packet is removed from the lost intervals
+        self.lost_len().saturating_sub(lost_len)
+    }
+
+    /// Returns the number of bytes which are waiting to be retransmitted
+    fn lost_len(&self) -> u64 {
+        self.lost
+            .intervals()
+            .map(|interval| interval.len() as u64)
+            .sum()
     }
 
     /// Queries the component for any outgoing frames that need to get sent
@@ -656,6 +688,8 @@ mod tests {
                     let packet = context.frame_buffer.frames[index].packet_nr;
                     lost.insert(packet);
                     sender.on_packet_loss(&packet);
+                    // the data in a packet is only queued for retransmission once
+                    assert_eq!(sender.on_packet_loss(&packet), 0);
                 }
                 Event::IncFlowControl(amount) => {
                     let flow_controller = sender.flow_controller_mut();
@@ -73,6 +73,11 @@ impl<
         self.request_delivery_if_necessary();
     }
 
+    /// Sets the minimum increase of the value which requires an update to be sent
+    pub fn set_threshold(&mut self, threshold: T) {
+        self.threshold = threshold;
+    }
+
     /// Stop to synchronize the value to the peer
     pub fn stop_sync(&mut self) {
         self.delivery.cancel();
@@ -2,19 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack::AckManager,
+    ack::{ack_frequency::AckFrequencySync, AckManager},
     connection,
     contexts::WriteContext,
     endpoint, path,
     path::mtu,
     recovery,
-    space::{datagram, HandshakeStatus},
+    space::{datagram, new_token, path_space::RxSpace, HandshakeStatus},
     stream::Manager as _,
     sync::{flag, flag::Ping},
     transmission::{self, Mode},
 };
 use core::ops::RangeInclusive;
-use s2n_quic_core::packet::number::PacketNumberSpace;
+use s2n_quic_core::{packet::number::PacketNumberSpace, recovery::CongestionController};
+use smallvec::SmallVec;
 
 pub enum Payload<'a, Config: endpoint::Config> {
     Normal(Normal<'a, Config>),
@@ -33,6 +34,9 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
         local_id_registry: &'a mut connection::LocalIdRegistry,
         transmission_mode: transmission::Mode,
         ack_manager: &'a mut AckManager,
+        rx_spaces: &'a mut [RxSpace],
+        ack_frequency: &'a mut AckFrequencySync,
+        new_token: &'a mut new_token::Manager<Config>,
         handshake_status: &'a mut HandshakeStatus,
         ping: &'a mut flag::Ping,
         stream_manager: &'a mut Config::StreamManager,
@@ -40,13 +44,20 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
         datagram_manager: &'a mut datagram::Manager<Config>,
     ) -> Self {
         if transmission_mode != Mode::PathValidationOnly {
-            debug_assert_eq!(path_id, path_manager.active_path_id());
+            // With the multipath extension, application data is also sent on other paths
+            debug_assert!(
+                path_id == path_manager.active_path_id() || path_manager.is_multipath_enabled()
+            );
         }
 
         match transmission_mode {
             Mode::LossRecoveryProbing | Mode::Normal => {
                 transmission::application::Payload::Normal(Normal {
+                    path_id,
                     ack_manager,
+                    rx_spaces,
+                    ack_frequency,
+                    new_token,
                     handshake_status,
                     ping,
                     stream_manager,
@@ -102,7 +113,13 @@ impl<'a, Config: endpoint::Config> transmission::interest::Provider for Payload<
 }
 
 pub struct Normal<'a, Config: endpoint::Config> {
+    path_id: path::Id,
     ack_manager: &'a mut AckManager,
+    /// The packet number spaces of the paths the peer sends packets in, which are
+    /// acknowledged with ACK_MP frames
+    rx_spaces: &'a mut [RxSpace],
+    ack_frequency: &'a mut AckFrequencySync,
+    new_token: &'a mut new_token::Manager<Config>,
     handshake_status: &'a mut HandshakeStatus,
     ping: &'a mut Ping,
     stream_manager: &'a mut Config::StreamManager,
@@ -134,6 +151,13 @@ impl<'a, Config: endpoint::Config> Normal<'a, Config> {
         }
         let did_send_ack = self.ack_manager.on_transmit(context);
 
+        let mut did_send_ack_mp = SmallVec::<[usize; 4]>::new();
+        for (index, space) in self.rx_spaces.iter_mut().enumerate() {
+            if space.ack_manager.on_transmit(context) {
+                did_send_ack_mp.push(index);
+            }
+        }
+
         // Payloads can only transmit and retransmit
         if can_transmit {
             self.transmit_control_data(context);
@@ -164,6 +188,12 @@ impl<'a, Config: endpoint::Config> Normal<'a, Config> {
             self.ack_manager.on_transmit_complete(context);
         }
 
+        for index in did_send_ack_mp {
+            self.rx_spaces[index]
+                .ack_manager
+                .on_transmit_complete(context);
+        }
+
         // Alternate between prioritizing datagrams or not each packet
         self.prioritize_datagrams = !self.prioritize_datagrams;
     }
@@ -178,11 +208,23 @@ impl<'a, Config: endpoint::Config> Normal<'a, Config> {
         //# An endpoint MAY include other frames with the PATH_CHALLENGE and
         //# PATH_RESPONSE frames used for path validation.
         // prioritize PATH_CHALLENGE and PATH_RESPONSE frames higher than app data
-        self.path_manager.active_path_mut().on_transmit(context);
+        self.path_manager[self.path_id].on_transmit(context);
 
         self.local_id_registry.on_transmit(context);
 
         self.path_manager.on_transmit(context);
+
+        // request the peer to acknowledge packets as often as the congestion controller needs
+        let ack_eliciting_threshold = self
+            .path_manager
+            .active_path()
+            .congestion_controller
+            .ack_eliciting_threshold();
+        self.ack_frequency
+            .update_ack_eliciting_threshold(ack_eliciting_threshold);
+        self.ack_frequency.on_transmit(context);
+
+        self.new_token.on_transmit(context);
     }
 }
 
@@ -192,15 +234,18 @@ impl<'a, Config: endpoint::Config> transmission::interest::Provider for Normal<'
         query: &mut Q,
     ) -> transmission::interest::Result {
         self.ack_manager.transmission_interest(query)?;
+        self.ack_frequency.transmission_interest(query)?;
+        self.new_token.transmission_interest(query)?;
         self.handshake_status.transmission_interest(query)?;
         self.stream_manager.transmission_interest(query)?;
         self.datagram_manager.transmission_interest(query)?;
         self.local_id_registry.transmission_interest(query)?;
         self.path_manager.transmission_interest(query)?;
         self.recovery_manager.transmission_interest(query)?;
-        self.path_manager
-            .active_path()
-            .transmission_interest(query)?;
+        for space in self.rx_spaces.iter() {
+            space.ack_manager.transmission_interest(query)?;
+        }
+        self.path_manager[self.path_id].transmission_interest(query)?;
         self.ping.transmission_interest(query)?;
         Ok(())
     }
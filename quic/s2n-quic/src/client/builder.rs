@@ -276,6 +276,84 @@ impl<Providers: ClientProviders> Builder<Providers> {
         ClientProviders
     );
 
+    impl_provider_method!(
+        /// Sets the QUIC versions enabled on the [`Client`]
+        ///
+        /// Only QUIC version 1 is enabled by default. Enabling additional versions allows
+        /// the endpoint to switch to a compatible version during the handshake.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Client, provider::version};
+        ///
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// let client = Client::builder()
+        ///     .with_version(version::Preferences::new(&[version::VERSION_2, version::VERSION_1])?)?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        with_version,
+        version,
+        ClientProviders
+    );
+
+    impl_provider_method!(
+        /// Sets the store for the address validation tokens issued by servers
+        ///
+        /// Tokens received in NEW_TOKEN frames are included in the Initial packets of future
+        /// connections to the same server name, which allows the server to skip address
+        /// validation with a Retry packet. By default, tokens are stored in an in-memory
+        /// [`Cache`](address_token_store::Cache).
+        ///
+        /// # Examples
+        ///
+        /// Share tokens between multiple clients
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Client, provider::address_token_store};
+        ///
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// let store = address_token_store::Cache::default();
+        ///
+        /// let client = Client::builder()
+        ///     .with_address_token_store(store.clone())?
+        ///     .start()?;
+        ///
+        /// let other_client = Client::builder()
+        ///     .with_address_token_store(store)?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        ///
+        /// Disable storing tokens
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Client, provider::address_token_store};
+        ///
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// let client = Client::builder()
+        ///     .with_address_token_store(address_token_store::Disabled)?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        with_address_token_store,
+        address_token_store,
+        ClientProviders
+    );
+
     /// Starts the [`Client`] with the configured providers
     ///
     /// # Examples
@@ -3,7 +3,11 @@
 
 use super::*;
 use core::marker::PhantomData;
-use s2n_quic_core::{connection::id::Generator, crypto, path};
+use s2n_quic_core::{
+    connection::id::Generator,
+    crypto::{self, tls::Endpoint as _},
+    path,
+};
 use s2n_quic_transport::{connection, endpoint, stream};
 
 impl_providers_state! {
@@ -21,6 +25,8 @@ impl_providers_state! {
         sync: Sync,
         tls: Tls,
         datagram: Datagram,
+        version: Version,
+        address_token_store: AddressTokenStore,
     }
 
     /// Opaque trait containing all of the configured providers
@@ -40,6 +46,8 @@ impl<
         Sync: sync::Provider,
         Tls: tls::Provider,
         Datagram: datagram::Provider,
+        Version: version::Provider,
+        AddressTokenStore: address_token_store::Provider,
     >
     Providers<
         CongestionController,
@@ -54,6 +62,8 @@ impl<
         Sync,
         Tls,
         Datagram,
+        Version,
+        AddressTokenStore,
     >
 {
     pub fn start(self) -> Result<Client, StartError> {
@@ -70,6 +80,8 @@ impl<
             sync,
             tls,
             datagram,
+            version,
+            address_token_store,
         } = self;
 
         let congestion_controller = congestion_controller.start().map_err(StartError::new)?;
@@ -84,10 +96,14 @@ impl<
         let limits = limits.start().map_err(StartError::new)?;
         let event = event.start().map_err(StartError::new)?;
         let token = Token;
+        let token_store = address_token_store.start().map_err(StartError::new)?;
         let sync = sync.start().map_err(StartError::new)?;
         let path_migration = PathMigration;
+        let early_data = EarlyData;
+        let preferred_address = PreferredAddress;
         let tls = tls.start_client().map_err(StartError::new)?;
         let datagram = datagram.start().map_err(StartError::new)?;
+        let quic_versions = version.start().map_err(StartError::new)?;
 
         // Validate providers
         // TODO: Add more validation https://github.com/aws/s2n-quic/issues/285
@@ -101,6 +117,16 @@ impl<
             return Err(StartError::new(connection::id::Error::InvalidLifetime));
         };
 
+        if !quic_versions
+            .versions()
+            .iter()
+            .all(|version| tls.supports_quic_version(*version))
+        {
+            return Err(StartError::new(
+                "the TLS provider does not support all of the enabled QUIC versions",
+            ));
+        }
+
         let endpoint_config = EndpointConfig {
             congestion_controller,
             connection_close_formatter,
@@ -114,9 +140,13 @@ impl<
             sync,
             tls,
             token,
+            token_store,
             path_handle: PhantomData,
             path_migration,
             datagram,
+            early_data,
+            preferred_address,
+            quic_versions,
         };
 
         let (endpoint, connector) = endpoint::Endpoint::new_client(endpoint_config);
@@ -188,6 +218,30 @@ impl crate::provider::path_migration::Validator for PathMigration {
     }
 }
 
+#[derive(Debug)]
+struct EarlyData;
+
+impl crate::provider::early_data::Controller for EarlyData {
+    fn on_early_data(
+        &mut self,
+        _attempt: &crate::provider::early_data::Attempt,
+    ) -> crate::provider::early_data::Outcome {
+        unreachable!("early data should not be accepted with clients")
+    }
+}
+
+#[derive(Debug)]
+struct PreferredAddress;
+
+impl crate::provider::preferred_address::Endpoint for PreferredAddress {
+    fn on_connection(
+        &mut self,
+        _info: &crate::provider::preferred_address::ConnectionInfo,
+    ) -> Option<crate::provider::preferred_address::Addresses> {
+        unreachable!("preferred addresses should not be advertised by clients")
+    }
+}
+
 #[allow(dead_code)] // don't warn on unused providers for now
 struct EndpointConfig<
     CongestionController,
@@ -202,6 +256,7 @@ struct EndpointConfig<
     Sync,
     Tls,
     Datagram,
+    TokenStore,
 > {
     congestion_controller: CongestionController,
     connection_close_formatter: ConnectionCloseFormatter,
@@ -215,9 +270,13 @@ struct EndpointConfig<
     sync: Sync,
     tls: Tls,
     token: Token,
+    token_store: TokenStore,
     path_handle: PhantomData<PathHandle>,
     path_migration: PathMigration,
     datagram: Datagram,
+    early_data: EarlyData,
+    preferred_address: PreferredAddress,
+    quic_versions: version::Preferences,
 }
 
 impl<
@@ -233,6 +292,7 @@ impl<
         Sync,
         Tls: crypto::tls::Endpoint,
         Datagram: s2n_quic_core::datagram::Endpoint,
+        TokenStore: address_token_store::Store + Clone,
     > core::fmt::Debug
     for EndpointConfig<
         CongestionController,
@@ -247,6 +307,7 @@ impl<
         Sync,
         Tls,
         Datagram,
+        TokenStore,
     >
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -267,6 +328,7 @@ impl<
         Sync: 'static + Send,
         Tls: crypto::tls::Endpoint,
         Datagram: s2n_quic_core::datagram::Endpoint,
+        TokenStore: address_token_store::Store + Clone,
     > endpoint::Config
     for EndpointConfig<
         CongestionController,
@@ -281,6 +343,7 @@ impl<
         Sync,
         Tls,
         Datagram,
+        TokenStore,
     >
 {
     type ConnectionIdFormat = ConnectionID;
@@ -296,11 +359,14 @@ impl<
     type EventSubscriber = Event;
     type TLSEndpoint = Tls;
     type TokenFormat = Token;
+    type TokenStore = TokenStore;
     type ConnectionLimits = Limits;
     type StreamManager = stream::DefaultStreamManager;
     type PathMigrationValidator = PathMigration;
     type PacketInterceptor = PacketInterceptor;
     type DatagramEndpoint = Datagram;
+    type EarlyDataController = EarlyData;
+    type PreferredAddressEndpoint = PreferredAddress;
 
     const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Client;
 
@@ -315,10 +381,14 @@ impl<
             tls: &mut self.tls,
             endpoint_limits: &mut self.endpoint_limits,
             token: &mut self.token,
+            token_store: &mut self.token_store,
             connection_limits: &mut self.limits,
             event_subscriber: &mut self.event,
             path_migration: &mut self.path_migration,
             datagram: &mut self.datagram,
+            early_data: &mut self.early_data,
+            preferred_address: &mut self.preferred_address,
+            quic_versions: &self.quic_versions,
         }
     }
 }
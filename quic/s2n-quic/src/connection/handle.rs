@@ -149,6 +149,31 @@ macro_rules! impl_handle_api {
             self.0.remote_address().map(std::net::SocketAddr::from)
         }
 
+        /// Returns a snapshot of the connection's statistics
+        ///
+        /// The snapshot includes the RTT estimates, congestion window, MTU and packet counters
+        /// for each path, along with the number of open streams and the amount of time the
+        /// connection was blocked by the peer's flow control limits.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let stats = connection.stats()?;
+        /// if let Some(path) = stats.active_path() {
+        ///     println!("rtt: {:?}, cwnd: {}", path.smoothed_rtt, path.congestion_window);
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn stats(&self) -> $crate::connection::Result<$crate::connection::Stats> {
+            self.0.stats()
+        }
+
         /// Returns the negotiated server name the connection is using.
         #[inline]
         pub fn server_name(&self) -> $crate::connection::Result<Option<$crate::server::Name>> {
@@ -161,6 +186,44 @@ macro_rules! impl_handle_api {
             self.0.application_protocol()
         }
 
+        /// Returns the DER-encoded certificate chain presented by the peer
+        ///
+        /// The chain starts with the peer's own certificate and is empty if the peer didn't
+        /// present one, such as a client when client authentication isn't required or a peer
+        /// resuming a previous session.
+        #[inline]
+        pub fn peer_certificate_chain(
+            &self,
+        ) -> $crate::connection::Result<::std::vec::Vec<::bytes::Bytes>> {
+            self.0.peer_certificate_chain()
+        }
+
+        /// Exports `len` bytes of keying material bound to the connection, as described in
+        /// [RFC 5705](https://www.rfc-editor.org/rfc/rfc5705)
+        ///
+        /// Both endpoints derive the same value for the same `label` and `context`.
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let token_binding = connection.export_keying_material(b"EXPORTER-my-app", b"", 32)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn export_keying_material(
+            &self,
+            label: &[u8],
+            context: &[u8],
+            len: usize,
+        ) -> $crate::connection::Result<::bytes::Bytes> {
+            let mut output = ::std::vec![0; len];
+            self.0.export_keying_material(label, context, &mut output)?;
+            Ok(output.into())
+        }
+
         /// Returns the internal identifier for the [`Connection`](`crate::Connection`)
         ///
         /// Note: This internal identifier is not the same as the connection ID included in packet
@@ -186,6 +249,122 @@ macro_rules! impl_handle_api {
             self.0.keep_alive(enabled)
         }
 
+        /// Migrates the connection to a new local address
+        ///
+        /// The peer is probed with a PATH_CHALLENGE from the new address using a connection ID
+        /// that hasn't been used on any other path. Once the peer responds, the connection
+        /// switches to the new path. If the peer doesn't respond, the connection continues to
+        /// use the current path and an error is returned.
+        ///
+        /// The IO provider binds a new socket to the address, which must be assigned to a local
+        /// interface. A port of `0` binds the socket to an ephemeral port. The socket is released
+        /// when the connection is closed.
+        ///
+        /// IO providers that can't bind sockets send the packets from the socket of the endpoint
+        /// with the source IP set to the requested address instead. In that case the port must
+        /// either be `0` or the port of the current local address.
+        ///
+        /// Only clients can migrate connections, and only after the handshake is confirmed.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// // move the connection to the cellular interface
+        /// handle.migrate("198.51.100.7:0".parse()?).await?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn migrate(
+            &mut self,
+            local_address: std::net::SocketAddr,
+        ) -> $crate::connection::Result<(), $crate::connection::MigrationError> {
+            futures::future::poll_fn(|cx| self.poll_migrate(local_address, cx)).await
+        }
+
+        /// Polls migrating the connection to a new local address
+        ///
+        /// The method will return
+        /// - `Poll::Ready(Ok(()))` if the connection migrated to the new local address
+        /// - `Poll::Ready(Err(error))` if the connection could not be migrated
+        /// - `Poll::Pending` if the path from the new local address is still being validated
+        #[inline]
+        pub fn poll_migrate(
+            &mut self,
+            local_address: std::net::SocketAddr,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::connection::Result<(), $crate::connection::MigrationError>> {
+            self.0.poll_migrate(local_address.into(), cx)
+        }
+
+        /// Opens an additional path from a new local address
+        ///
+        /// The path is validated in the same way as a migration, but the connection keeps using
+        /// the current path. Once validated, application data is sent on all open paths. The
+        /// multipath extension must be enabled in the connection limits and supported by the peer.
+        ///
+        /// The IO provider binds a new socket to the address, which must be assigned to a local
+        /// interface. A port of `0` binds the socket to an ephemeral port. The socket is released
+        /// when the connection is closed.
+        ///
+        /// IO providers that can't bind sockets send the packets from the socket of the endpoint
+        /// with the source IP set to the requested address instead. In that case the port must
+        /// either be `0` or the port of the current local address.
+        ///
+        /// Only clients can open paths, and only after the handshake is confirmed.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// // send data on the cellular interface as well
+        /// handle.open_path("198.51.100.7:0".parse()?).await?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn open_path(
+            &mut self,
+            local_address: std::net::SocketAddr,
+        ) -> $crate::connection::Result<(), $crate::connection::MigrationError> {
+            futures::future::poll_fn(|cx| self.poll_open_path(local_address, cx)).await
+        }
+
+        /// Polls opening an additional path from a new local address
+        ///
+        /// The method will return
+        /// - `Poll::Ready(Ok(()))` if the path can carry application data
+        /// - `Poll::Ready(Err(error))` if the path could not be opened
+        /// - `Poll::Pending` if the path from the new local address is still being validated
+        #[inline]
+        pub fn poll_open_path(
+            &mut self,
+            local_address: std::net::SocketAddr,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::connection::Result<(), $crate::connection::MigrationError>> {
+            self.0.poll_open_path(local_address.into(), cx)
+        }
+
+        /// Closes the path from a local address
+        ///
+        /// The peer is informed with a PATH_ABANDON frame. If the path is the active path, the
+        /// connection switches to the open path with the lowest round trip time. The last path
+        /// of a connection can't be closed. A port of `0` closes the path from the IP on any port.
+        #[inline]
+        pub fn close_path(
+            &mut self,
+            local_address: std::net::SocketAddr,
+        ) -> $crate::connection::Result<(), $crate::connection::MigrationError> {
+            self.0.close_path(local_address.into())
+        }
+
         /// Closes the Connection with the provided error code
         ///
         /// This will immediately terminate all outstanding streams.
@@ -8,14 +8,19 @@ use core::fmt;
 mod macros;
 
 pub mod address_token;
+pub mod address_token_store;
 pub mod congestion_controller;
 pub mod connection_id;
+pub mod early_data;
 pub mod endpoint_limits;
 pub mod event;
 pub mod io;
 pub mod limits;
+pub mod preferred_address;
+pub mod session_ticket;
 pub mod stateless_reset_token;
 pub mod tls;
+pub mod version;
 
 // These providers are not currently exposed to applications
 pub(crate) mod connection_close_formatter;
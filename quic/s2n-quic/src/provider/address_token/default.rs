@@ -6,7 +6,9 @@
 //! The default provider will randomly generate a 256 bit key. This key will be used to sign and
 //! verify tokens. The key can be rotated at a duration set by the user.
 //!
-//! The default provider does not support tokens delivered in a NEW_TOKEN frame.
+//! Tokens delivered in NEW_TOKEN frames are signed with a separate set of keys, which rotate
+//! much less frequently than the keys for tokens delivered in Retry packets. This allows clients
+//! to use them on future connections, while Retry tokens are only accepted for a short time.
 
 use core::{mem::size_of, time::Duration};
 use hash_hasher::HashHasher;
@@ -79,8 +81,58 @@ impl BaseKey {
     }
 }
 
+struct KeySet {
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+    //= type=exception
+    //= reason=We use a duplicate filter to prevent tokens from being used more than once.
+    //# Servers are encouraged to allow tokens to be used only
+    //# once, if possible; tokens MAY include additional information about
+    //# clients to further narrow applicability or reuse.
+    /// Key validity period
+    key_rotation_period: Duration,
+
+    /// Timestamp to rotate current key
+    current_key_rotates_at: s2n_quic_core::time::Timestamp,
+
+    /// Which key is used to sign
+    current_key: u8,
+
+    /// Key used to sign keys
+    keys: [BaseKey; 2],
+}
+
+impl KeySet {
+    fn new(key_rotation_period: Duration) -> Self {
+        // The keys must remain valid for two rotation periods or they will regenerate their
+        // material and validation will fail.
+        Self {
+            key_rotation_period,
+            current_key_rotates_at: s2n_quic_platform::time::now(),
+            current_key: 0,
+            keys: [
+                BaseKey::new(key_rotation_period * 2),
+                BaseKey::new(key_rotation_period * 2),
+            ],
+        }
+    }
+
+    fn current_key(&mut self) -> u8 {
+        let now = s2n_quic_platform::time::now();
+        if now > self.current_key_rotates_at {
+            self.current_key ^= 1;
+            self.current_key_rotates_at = now + self.key_rotation_period;
+
+            // TODO either clear the duplicate filter here, or implement in the BaseKey logic
+            // https://github.com/aws/s2n-quic/issues/173
+        }
+        self.current_key
+    }
+}
+
 const DEFAULT_KEY_ROTATION_PERIOD: Duration = Duration::from_millis(1000);
 
+const DEFAULT_NEW_TOKEN_KEY_ROTATION_PERIOD: Duration = Duration::from_secs(60 * 60 * 12);
+
 #[derive(Debug)]
 pub struct Provider {
     //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
@@ -91,31 +143,47 @@ pub struct Provider {
     /// To fulfill this SHOULD, we rotate the key periodically. This allows
     /// customers to control the token lifetime without adding bytes to the token itself.
     key_rotation_period: Duration,
+
+    /// The rotation period of the keys for tokens delivered in NEW_TOKEN frames
+    ///
+    /// Setting this to `None` disables sending NEW_TOKEN frames.
+    new_token_key_rotation_period: Option<Duration>,
 }
 
 impl Default for Provider {
     fn default() -> Self {
         Self {
             key_rotation_period: DEFAULT_KEY_ROTATION_PERIOD,
+            new_token_key_rotation_period: Some(DEFAULT_NEW_TOKEN_KEY_ROTATION_PERIOD),
         }
     }
 }
 
+impl Provider {
+    /// Sets the lifetime of the tokens sent to clients in NEW_TOKEN frames
+    ///
+    /// Tokens are accepted for at least `lifetime` after they are issued, and at most twice as
+    /// long. The default is 12 hours.
+    pub fn with_new_token_lifetime(mut self, lifetime: Duration) -> Self {
+        self.new_token_key_rotation_period = Some(lifetime);
+        self
+    }
+
+    /// Disables sending tokens to clients in NEW_TOKEN frames
+    pub fn without_new_tokens(mut self) -> Self {
+        self.new_token_key_rotation_period = None;
+        self
+    }
+}
+
 impl super::Provider for Provider {
     type Format = Format;
     type Error = core::convert::Infallible;
 
     fn start(self) -> Result<Self::Format, Self::Error> {
-        // The keys must remain valid for two rotation periods or they will regenerate their
-        // material and validation will fail.
         let format = Format {
-            key_rotation_period: self.key_rotation_period,
-            current_key_rotates_at: s2n_quic_platform::time::now(),
-            current_key: 0,
-            keys: [
-                BaseKey::new(self.key_rotation_period * 2),
-                BaseKey::new(self.key_rotation_period * 2),
-            ],
+            retry: KeySet::new(self.key_rotation_period),
+            new_token: self.new_token_key_rotation_period.map(KeySet::new),
         };
 
         Ok(format)
@@ -123,41 +191,21 @@ impl super::Provider for Provider {
 }
 
 pub struct Format {
-    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
-    //= type=exception
-    //= reason=We use a duplicate filter to prevent tokens from being used more than once.
-    //# Servers are encouraged to allow tokens to be used only
-    //# once, if possible; tokens MAY include additional information about
-    //# clients to further narrow applicability or reuse.
-    /// Key validity period
-    key_rotation_period: Duration,
-
     //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
     //# Servers SHOULD ensure that
     //# tokens sent in Retry packets are only accepted for a short time.
-    /// Timestamp to rotate current key
-    current_key_rotates_at: s2n_quic_core::time::Timestamp,
-
-    /// Which key is used to sign
-    current_key: u8,
+    /// Keys used to sign tokens delivered in Retry packets
+    retry: KeySet,
 
-    /// Key used to sign keys
-    keys: [BaseKey; 2],
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+    //# Unlike the token that is created for a Retry packet, which is used
+    //# immediately, the token sent in the NEW_TOKEN frame can be used after
+    //# some period of time has passed.
+    /// Keys used to sign tokens delivered in NEW_TOKEN frames
+    new_token: Option<KeySet>,
 }
 
 impl Format {
-    fn current_key(&mut self) -> u8 {
-        let now = s2n_quic_platform::time::now();
-        if now > self.current_key_rotates_at {
-            self.current_key ^= 1;
-            self.current_key_rotates_at = now + self.key_rotation_period;
-
-            // TODO either clear the duplicate filter here, or implement in the BaseKey logic
-            // https://github.com/aws/s2n-quic/issues/173
-        }
-        self.current_key
-    }
-
     // Retry Tokens need to include the original destination connection id from the transport
     // parameters. This OCID is included in the tag.
     fn tag_retry_token(
@@ -165,7 +213,7 @@ impl Format {
         token: &Token,
         context: &mut super::Context<'_>,
     ) -> Option<hmac::Tag> {
-        let mut ctx = self.keys[token.header.key_id() as usize].hasher(context.random)?;
+        let mut ctx = self.retry.keys[token.header.key_id() as usize].hasher(context.random)?;
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
         //# Tokens
@@ -199,7 +247,7 @@ impl Format {
         context: &mut super::Context<'_>,
         token: &Token,
     ) -> Option<connection::InitialId> {
-        if self.keys[token.header.key_id() as usize]
+        if self.retry.keys[token.header.key_id() as usize]
             .duplicate_filter
             .contains(token)
         {
@@ -214,7 +262,7 @@ impl Format {
 
             // Ignore the outcome of adding a token to the filter because we always want to
             // continue the connection if the filter fails.
-            let _ = self.keys[token.header.key_id() as usize]
+            let _ = self.retry.keys[token.header.key_id() as usize]
                 .duplicate_filter
                 .add(token);
 
@@ -223,52 +271,119 @@ impl Format {
 
         None
     }
+
+    // Tokens delivered in NEW_TOKEN frames are used on future connections, so they only include
+    // the IP address of the client in the tag.
+    fn tag_new_token(
+        &mut self,
+        token: &Token,
+        context: &mut super::Context<'_>,
+    ) -> Option<hmac::Tag> {
+        let keys = self.new_token.as_mut()?;
+        let mut ctx = keys.keys[token.header.key_id() as usize].hasher(context.random)?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+        //# Tokens sent in NEW_TOKEN frames MUST include information that allows
+        //# the server to verify that the client IP address has not changed from
+        //# when the token was issued.
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# It is unlikely that the client port number is the same on two
+        //# different connections; validating the port is therefore unlikely to
+        //# be successful.
+        ctx.update(&token.nonce);
+        match context.remote_address {
+            SocketAddress::IpV4 { ip, .. } => ctx.update(ip),
+            SocketAddress::IpV6 { ip, .. } => ctx.update(ip),
+            _ => {
+                // we are unable to hash the address so bail
+                return None;
+            }
+        };
+
+        Some(ctx.sign())
+    }
+
+    fn verify_new_token(&mut self, context: &mut super::Context<'_>, token: &Token) -> Option<()> {
+        let key_id = token.header.key_id() as usize;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+        //# Tokens that are provided
+        //# in NEW_TOKEN frames (Section 19.7) need to be valid for longer but
+        //# SHOULD NOT be accepted multiple times.
+        if self.new_token.as_ref()?.keys[key_id]
+            .duplicate_filter
+            .contains(token)
+        {
+            return None;
+        }
+
+        let tag = self.tag_new_token(token, context)?;
+
+        if constant_time::verify_slices_are_equal(&token.hmac, tag.as_ref()).is_ok() {
+            // A token which can't be recorded in the filter could be replayed, so it's rejected
+            // and the client proceeds without a validated address
+            self.new_token.as_mut()?.keys[key_id]
+                .duplicate_filter
+                .add(token)
+                .ok()?;
+
+            return Some(());
+        }
+
+        None
+    }
+
+    fn decode_token(token: &[u8]) -> Option<&Token> {
+        let buffer = DecoderBuffer::new(token);
+        let (token, remaining) = buffer.decode::<&Token>().ok()?;
+
+        // Verify the provided token doesn't have any additional data
+        remaining.ensure_empty().ok()?;
+
+        if token.header.version() != TOKEN_VERSION {
+            return None;
+        }
+
+        Some(token)
+    }
 }
 
 impl super::Format for Format {
     const TOKEN_LEN: usize = size_of::<Token>();
 
-    /// The default provider does not support NEW_TOKEN frame tokens
     fn generate_new_token(
         &mut self,
-        _context: &mut super::Context<'_>,
+        context: &mut super::Context<'_>,
         _source_connection_id: &connection::LocalId,
-        _output_buffer: &mut [u8],
+        output_buffer: &mut [u8],
     ) -> Option<()> {
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
-        //= type=TODO
-        //= tracking-issue=418
-        //# A server MAY provide clients with an address validation token during
-        //# one connection that can be used on a subsequent connection.
+        let key_id = self.new_token.as_mut()?.current_key();
 
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
-        //= type=TODO
-        //= tracking-issue=346
-        //# Tokens sent in NEW_TOKEN frames MUST include information that allows
-        //# the server to verify that the client IP address has not changed from
-        //# when the token was issued.
+        let buffer = DecoderBufferMut::new(output_buffer);
+        let (token, _) = buffer
+            .decode::<&mut Token>()
+            .expect("Provided output buffer did not match TOKEN_LEN");
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
-        //= type=TODO
-        //= tracking-issue=345
         //# A token issued with NEW_TOKEN MUST NOT include information that would
         //# allow values to be linked by an observer to the connection on which
         //# it was issued.
+        token.header = Header::new(Source::NewTokenFrame, key_id);
+        token.odcid_len = 0;
+        token.original_destination_connection_id = [0; 20];
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
-        //= type=TODO
-        //= tracking-issue=387
         //# A server MUST ensure that every NEW_TOKEN frame it sends
         //# is unique across all clients, with the exception of those sent to
         //# repair losses of previously sent NEW_TOKEN frames.
+        context.random.public_random_fill(&mut token.nonce[..]);
 
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
-        //= type=TODO
-        //= tracking-issue=394
-        //# A server MAY provide clients with an address validation token during
-        //# one connection that can be used on a subsequent connection.
+        let tag = self.tag_new_token(token, context)?;
 
-        None
+        token.hmac.copy_from_slice(tag.as_ref());
+
+        Some(())
     }
 
     //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.2
@@ -289,7 +404,7 @@ impl super::Format for Format {
             .decode::<&mut Token>()
             .expect("Provided output buffer did not match TOKEN_LEN");
 
-        let header = Header::new(Source::RetryPacket, self.current_key());
+        let header = Header::new(Source::RetryPacket, self.retry.current_key());
 
         token.header = header;
         token.original_destination_connection_id[..original_destination_connection_id.len()]
@@ -324,34 +439,27 @@ impl super::Format for Format {
         context: &mut super::Context<'_>,
         token: &[u8],
     ) -> Option<connection::InitialId> {
-        let buffer = DecoderBuffer::new(token);
-        let (token, remaining) = buffer.decode::<&Token>().ok()?;
+        let token = Self::decode_token(token)?;
 
-        // Verify the provided token doesn't have any additional data
-        remaining.ensure_empty().ok()?;
-
-        if token.header.version() != TOKEN_VERSION {
-            return None;
+        match token.header.token_source() {
+            Source::RetryPacket => self.validate_retry_token(context, token),
+            Source::NewTokenFrame => None,
         }
+    }
 
-        let source = token.header.token_source();
+    fn validate_new_token(&mut self, context: &mut super::Context<'_>, token: &[u8]) -> Option<()> {
+        let token = Self::decode_token(token)?;
 
-        match source {
-            Source::RetryPacket => self.validate_retry_token(context, token),
-            Source::NewTokenFrame => None, // Not supported in the default provider
+        match token.header.token_source() {
+            Source::RetryPacket => None,
+            Source::NewTokenFrame => self.verify_new_token(context, token),
         }
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
-        //= type=TODO
-        //= tracking-issue=347
-        //# Tokens that are provided
-        //# in NEW_TOKEN frames (Section 19.7) need to be valid for longer but
-        //# SHOULD NOT be accepted multiple times.
+    }
 
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
-        //= type=TODO
-        //= tracking-issue=388
-        //# Clients that want to break continuity of identity with a server can
-        //# discard tokens provided using the NEW_TOKEN frame.
+    fn token_source(&self, token: &[u8]) -> Source {
+        // Retry tokens are only sent immediately after they were issued by this format, so tokens
+        // that fail to decode are likely from a NEW_TOKEN frame issued by a previous configuration
+        Self::decode_token(token).map_or(Source::NewTokenFrame, |token| token.header.token_source())
     }
 }
 
@@ -359,7 +467,7 @@ impl super::Format for Format {
 #[repr(C)]
 pub(crate) struct Header(u8);
 
-const TOKEN_VERSION: u8 = 0x00;
+pub(super) const TOKEN_VERSION: u8 = 0x00;
 
 const VERSION_SHIFT: u8 = 7;
 const VERSION_MASK: u8 = 0x80;
@@ -371,7 +479,7 @@ const KEY_ID_SHIFT: u8 = 5;
 const KEY_ID_MASK: u8 = 0x20;
 
 impl Header {
-    fn new(source: Source, key_id: u8) -> Header {
+    pub(super) fn new(source: Source, key_id: u8) -> Header {
         let mut header: u8 = 0;
         header |= TOKEN_VERSION << VERSION_SHIFT;
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
@@ -390,7 +498,7 @@ impl Header {
         Header(header)
     }
 
-    fn version(self) -> u8 {
+    pub(super) fn version(self) -> u8 {
         (self.0 & VERSION_MASK) >> VERSION_SHIFT
     }
 
@@ -403,7 +511,7 @@ impl Header {
     //# constructed in a way that allows the server to identify how it was
     //# provided to a client.  These tokens are carried in the same field but
     //# require different handling from servers.
-    fn token_source(self) -> Source {
+    pub(super) fn token_source(self) -> Source {
         match (self.0 & TOKEN_SOURCE_MASK) >> TOKEN_SOURCE_SHIFT {
             0 => Source::NewTokenFrame,
             1 => Source::RetryPacket,
@@ -462,6 +570,7 @@ impl Token {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::provider::address_token::Provider as _;
     use s2n_quic_core::{
         inet::SocketAddress,
         random,
@@ -472,15 +581,12 @@ mod tests {
 
     const TEST_KEY_ROTATION_PERIOD: Duration = Duration::from_millis(1000);
 
+    const TEST_NEW_TOKEN_KEY_ROTATION_PERIOD: Duration = Duration::from_secs(60);
+
     fn get_test_format() -> Format {
         Format {
-            key_rotation_period: TEST_KEY_ROTATION_PERIOD,
-            keys: [
-                BaseKey::new(TEST_KEY_ROTATION_PERIOD * 2),
-                BaseKey::new(TEST_KEY_ROTATION_PERIOD * 2),
-            ],
-            current_key_rotates_at: time::now(),
-            current_key: 0,
+            retry: KeySet::new(TEST_KEY_ROTATION_PERIOD),
+            new_token: Some(KeySet::new(TEST_NEW_TOKEN_KEY_ROTATION_PERIOD)),
         }
     }
 
@@ -723,6 +829,155 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_valid_new_tokens() {
+        let clock = Arc::new(time::testing::MockClock::new());
+        time::testing::set_local_clock(clock.clone());
+
+        let mut format = get_test_format();
+        let first_conn_id = connection::PeerId::try_from_bytes(&[2, 4, 6, 8, 10]).unwrap();
+        let second_conn_id = connection::PeerId::try_from_bytes(&[1, 3, 5, 7, 9]).unwrap();
+        let local_conn_id = connection::LocalId::TEST_ID;
+        let first_addr: SocketAddress = "127.0.0.1:443".parse::<SocketAddr>().unwrap().into();
+        let mut token = [0; Format::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&first_addr, &first_conn_id, &mut random);
+
+        format
+            .generate_new_token(&mut context, &local_conn_id, &mut token)
+            .unwrap();
+        assert_eq!(format.token_source(&token), Source::NewTokenFrame);
+
+        // NEW_TOKEN tokens can't be used as Retry tokens
+        assert!(format.validate_token(&mut context, &token).is_none());
+
+        // The token is valid on a future connection from another port, long after any Retry
+        // token would have expired
+        clock.adjust_by(TEST_NEW_TOKEN_KEY_ROTATION_PERIOD);
+        let second_addr: SocketAddress = "127.0.0.1:444".parse::<SocketAddr>().unwrap().into();
+        context = Context::new(&second_addr, &second_conn_id, &mut random);
+        assert!(format.validate_new_token(&mut context, &token).is_some());
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+        //= type=test
+        //# Tokens that are provided
+        //# in NEW_TOKEN frames (Section 19.7) need to be valid for longer but
+        //# SHOULD NOT be accepted multiple times.
+        assert!(format.validate_new_token(&mut context, &token).is_none());
+    }
+
+    #[test]
+    fn test_new_token_full_duplicate_filter() {
+        let mut format = get_test_format();
+        let conn_id = connection::PeerId::TEST_ID;
+        let local_conn_id = connection::LocalId::TEST_ID;
+        let addr = SocketAddress::default();
+        let mut token = [0; Format::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+        format
+            .generate_new_token(&mut context, &local_conn_id, &mut token)
+            .unwrap();
+
+        // fill the filter of the key which signed the token
+        let key_id = Format::decode_token(&token).unwrap().header.key_id() as usize;
+        let filter = &mut format.new_token.as_mut().unwrap().keys[key_id].duplicate_filter;
+        *filter = cuckoofilter::CuckooFilter::with_capacity(1);
+        let mut value = 0u64;
+        while filter.add(&value).is_ok() {
+            value += 1;
+        }
+
+        // the token can't be recorded as used, so it could be replayed if it was accepted
+        assert!(format.validate_new_token(&mut context, &token).is_none());
+    }
+
+    #[test]
+    fn test_new_token_ip_validation() {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+        //= type=test
+        //# Tokens sent in NEW_TOKEN frames MUST include information that allows
+        //# the server to verify that the client IP address has not changed from
+        //# when the token was issued.
+        let mut format = get_test_format();
+        let conn_id = connection::PeerId::TEST_ID;
+        let local_conn_id = connection::LocalId::TEST_ID;
+        let correct_address: SocketAddress = "127.0.0.1:443".parse::<SocketAddr>().unwrap().into();
+        let mut token = [0; Format::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&correct_address, &conn_id, &mut random);
+        format
+            .generate_new_token(&mut context, &local_conn_id, &mut token)
+            .unwrap();
+
+        let incorrect_address: SocketAddress =
+            "127.0.0.2:443".parse::<SocketAddr>().unwrap().into();
+        context = Context::new(&incorrect_address, &conn_id, &mut random);
+        assert!(format.validate_new_token(&mut context, &token).is_none());
+
+        context = Context::new(&correct_address, &conn_id, &mut random);
+        assert!(format.validate_new_token(&mut context, &token).is_some());
+    }
+
+    #[test]
+    fn test_unique_new_tokens() {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //= type=test
+        //# A server MUST ensure that every NEW_TOKEN frame it sends
+        //# is unique across all clients, with the exception of those sent to
+        //# repair losses of previously sent NEW_TOKEN frames.
+        let mut format = get_test_format();
+        let conn_id = connection::PeerId::TEST_ID;
+        let local_conn_id = connection::LocalId::TEST_ID;
+        let addr = SocketAddress::default();
+        let mut first_token = [0; Format::TOKEN_LEN];
+        let mut second_token = [0; Format::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+        format
+            .generate_new_token(&mut context, &local_conn_id, &mut first_token)
+            .unwrap();
+        format
+            .generate_new_token(&mut context, &local_conn_id, &mut second_token)
+            .unwrap();
+
+        assert_ne!(first_token, second_token);
+    }
+
+    #[test]
+    fn test_expired_new_token() {
+        let clock = Arc::new(time::testing::MockClock::new());
+        time::testing::set_local_clock(clock.clone());
+
+        let mut format = get_test_format();
+        let conn_id = connection::PeerId::TEST_ID;
+        let local_conn_id = connection::LocalId::TEST_ID;
+        let addr = SocketAddress::default();
+        let mut token = [0; Format::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+        format
+            .generate_new_token(&mut context, &local_conn_id, &mut token)
+            .unwrap();
+
+        clock.adjust_by(TEST_NEW_TOKEN_KEY_ROTATION_PERIOD * 2);
+        assert!(format.validate_new_token(&mut context, &token).is_none());
+    }
+
+    #[test]
+    fn test_disabled_new_tokens() {
+        let mut format = Provider::default().without_new_tokens().start().unwrap();
+        let conn_id = connection::PeerId::TEST_ID;
+        let local_conn_id = connection::LocalId::TEST_ID;
+        let addr = SocketAddress::default();
+        let mut token = [0; Format::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+        assert!(format
+            .generate_new_token(&mut context, &local_conn_id, &mut token)
+            .is_none());
+    }
+
     #[test]
     fn test_token_length_check() {
         let mut format = get_test_format();
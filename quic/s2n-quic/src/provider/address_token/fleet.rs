@@ -0,0 +1,1184 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Address token provider for fleets of servers
+//!
+//! The [`default`](super::default) provider generates random keys in each process, so a token
+//! issued by one host is rejected by every other host behind the same load balancer. This
+//! provider encrypts and signs tokens with versioned keys loaded from a [`KeySource`] which is
+//! shared by the fleet, such as a [`KeyFile`] or a callback.
+//!
+//! Each key has an activation time, which gives the fleet an explicit rotation schedule. Hosts
+//! issue tokens with the most recently activated key and continue to accept tokens issued with
+//! the previous key while the fleet rolls over to the new one. The key version and the issue time
+//! are encrypted, so they can't be used by an observer to link a token to the connection on which
+//! it was issued.
+//!
+//! Replayed tokens are detected by a [`ReplayFilter`]. The default [`LocalReplayFilter`] only
+//! detects tokens replayed to the same host, while a filter backed by a shared store can detect
+//! them across the fleet.
+
+use super::default::{Header, TOKEN_VERSION};
+use core::{fmt, mem::size_of, time::Duration};
+use cuckoofilter::CuckooFilter;
+use hash_hasher::HashHasher;
+use s2n_codec::{DecoderBuffer, DecoderBufferMut};
+use s2n_quic_core::{connection, event::api::SocketAddress, token::Source};
+use s2n_quic_crypto::{constant_time, digest, hkdf, hmac};
+use std::{
+    collections::BTreeMap,
+    hash::{Hash, Hasher},
+    io,
+    path::PathBuf,
+    sync::mpsc,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use zerocopy::{AsBytes, FromBytes, Unaligned};
+use zeroize::Zeroizing;
+
+const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+const DEFAULT_RETRY_TOKEN_LIFETIME: Duration = Duration::from_secs(1);
+
+const DEFAULT_NEW_TOKEN_LIFETIME: Duration = Duration::from_secs(60 * 60 * 12);
+
+const DEFAULT_MAX_CLOCK_SKEW: Duration = Duration::from_secs(5);
+
+const DEFAULT_REPLAY_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// Fills the default filter of 2^20 entries to about 86%, which keeps the chance of an insertion
+/// failure low
+const DEFAULT_REPLAY_WINDOW_CAPACITY: usize = 900_000;
+
+/// A versioned key used to protect address tokens
+#[derive(Clone)]
+pub struct Key {
+    version: u32,
+    activates_at: Duration,
+    /// Authenticates the tokens
+    signing_key: hmac::Key,
+    /// Derives the keystream which encrypts the body of the tokens
+    encryption_key: hmac::Key,
+}
+
+impl Key {
+    /// The minimum length of the key material
+    pub const MIN_LEN: usize = 32;
+
+    /// Creates a key which is used to sign tokens from `activates_at` until the next key in the
+    /// schedule is activated
+    ///
+    /// The key material should contain at least 256 bits of entropy and must be the same on every
+    /// host in the fleet. Material shorter than [`Self::MIN_LEN`] bytes is rejected.
+    pub fn new(version: u32, activates_at: SystemTime, material: &[u8]) -> Result<Self, KeyError> {
+        if material.len() < Self::MIN_LEN {
+            return Err(KeyError(
+                "the address token key material must be at least 32 bytes long",
+            ));
+        }
+
+        let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, &[]).extract(material);
+        let derive = |label: &[u8]| {
+            let info = [label];
+            let okm = prk
+                .expand(&info, hmac::HMAC_SHA256)
+                .expect("the key length is within the HKDF output limit");
+            hmac::Key::from(okm)
+        };
+
+        Ok(Self {
+            version,
+            activates_at: activates_at.duration_since(UNIX_EPOCH).unwrap_or_default(),
+            signing_key: derive(b"s2n-quic address token signing key"),
+            encryption_key: derive(b"s2n-quic address token encryption key"),
+        })
+    }
+
+    /// Returns the version of the key
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the time at which the key starts to be used to sign tokens
+    pub fn activates_at(&self) -> SystemTime {
+        UNIX_EPOCH + self.activates_at
+    }
+
+    /// Encrypts or decrypts the body of a token with a keystream derived from its nonce
+    ///
+    /// Each token has a unique random nonce, so the keystream is never reused.
+    fn apply_keystream(&self, nonce: &[u8], body: &mut Body) {
+        let keystream = hmac::sign(&self.encryption_key, nonce);
+        for (byte, mask) in body.as_bytes_mut().iter_mut().zip(keystream.as_ref()) {
+            *byte ^= mask;
+        }
+    }
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Key")
+            .field("version", &self.version)
+            .field("activates_at", &self.activates_at)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An error returned when a [`Key`] can't be created
+#[derive(Clone, Copy, Debug)]
+pub struct KeyError(&'static str);
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl std::error::Error for KeyError {}
+
+/// Loads the key rotation schedule shared by the fleet
+pub trait KeySource: 'static + Send {
+    type Error: 'static + fmt::Display;
+
+    /// Returns all of the keys in the rotation schedule
+    ///
+    /// This is called when the provider is started and then periodically on a background thread
+    /// to pick up new keys, so it may block without stalling the endpoint. If a periodic load
+    /// fails, the previously loaded keys continue to be used.
+    fn load(&mut self) -> Result<Vec<Key>, Self::Error>;
+
+    /// Called when a periodic load fails
+    ///
+    /// The default implementation logs the error with `tracing` if the `provider-event-tracing`
+    /// feature is enabled.
+    fn on_load_error(&mut self, error: Self::Error) {
+        #[cfg(feature = "provider-event-tracing")]
+        tracing::warn!(%error, "failed to reload the address token keys");
+        #[cfg(not(feature = "provider-event-tracing"))]
+        let _ = error;
+    }
+}
+
+impl<F, E> KeySource for F
+where
+    F: 'static + Send + FnMut() -> Result<Vec<Key>, E>,
+    E: 'static + fmt::Display,
+{
+    type Error = E;
+
+    fn load(&mut self) -> Result<Vec<Key>, Self::Error> {
+        (self)()
+    }
+}
+
+/// Loads keys from a file which is distributed to each host in the fleet
+///
+/// Each line in the file contains a key version, the activation time of the key in seconds since
+/// the UNIX epoch and the hex-encoded key material, separated by whitespace. Empty lines and lines
+/// starting with `#` are ignored.
+///
+/// ```text
+/// # version activates_at key
+/// 1 1672531200 6c1f5e0d0a3b...
+/// 2 1675209600 9a0e77c4b2d1...
+/// ```
+#[derive(Clone, Debug)]
+pub struct KeyFile {
+    path: PathBuf,
+}
+
+impl KeyFile {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl KeySource for KeyFile {
+    type Error = io::Error;
+
+    fn load(&mut self) -> Result<Vec<Key>, Self::Error> {
+        let contents = Zeroizing::new(std::fs::read_to_string(&self.path)?);
+        let mut keys = vec![];
+
+        for (index, line) in contents.as_str().lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let key = parse_key(line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid address token key on line {}", index + 1),
+                )
+            })?;
+            keys.push(key);
+        }
+
+        Ok(keys)
+    }
+}
+
+fn parse_key(line: &str) -> Option<Key> {
+    let mut parts = line.split_whitespace();
+    let version = parts.next()?.parse().ok()?;
+    let activates_at = parts.next()?.parse().ok()?;
+    let material = decode_hex(parts.next()?)?;
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let activates_at = UNIX_EPOCH + Duration::from_secs(activates_at);
+    Key::new(version, activates_at, &material).ok()
+}
+
+fn decode_hex(value: &str) -> Option<Zeroizing<Vec<u8>>> {
+    let value = value.as_bytes();
+    if value.is_empty() || value.len() % 2 != 0 {
+        return None;
+    }
+
+    let nibble = |c: u8| (c as char).to_digit(16).map(|v| v as u8);
+
+    let mut material = Zeroizing::new(Vec::with_capacity(value.len() / 2));
+    for pair in value.chunks(2) {
+        material.push((nibble(pair[0])? << 4) | nibble(pair[1])?);
+    }
+
+    Some(material)
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+//# To protect against such attacks, servers MUST ensure that
+//# replay of tokens is prevented or limited.
+/// Detects tokens which have already been used to validate an address
+pub trait ReplayFilter: 'static + Send {
+    /// Records the use of a valid token and returns `true` if it was already used
+    ///
+    /// `token_id` uniquely identifies a token issued with the key `key_version`. The token is no
+    /// longer accepted after `expires_at`, so it only needs to be remembered until then.
+    fn is_replay(
+        &mut self,
+        key_version: u32,
+        token_id: &[u8],
+        now: SystemTime,
+        expires_at: SystemTime,
+    ) -> bool;
+
+    /// Called when tokens issued with the key are no longer accepted
+    ///
+    /// This may be called more than once for the same key.
+    fn on_key_retired(&mut self, key_version: u32) {
+        let _ = key_version;
+    }
+}
+
+/// Detects tokens replayed to the local host
+///
+/// Tokens are grouped into windows by their expiration time, with a separate filter for each
+/// window. A filter is dropped once all of the tokens in its window have expired, so the memory
+/// used is bounded by the token lifetime rather than the lifetime of the keys.
+///
+/// If more tokens expire in a window than the filter can remember, the additional tokens are
+/// treated as replays. The client then has to validate its address with a Retry instead of
+/// risking a replay being accepted.
+pub struct LocalReplayFilter {
+    window: Duration,
+    capacity: usize,
+    /// The filters, keyed by the index of their window since the UNIX epoch
+    filters: BTreeMap<u64, CuckooFilter<HashHasher>>,
+}
+
+impl LocalReplayFilter {
+    /// Creates a filter which groups tokens into windows of `window` and remembers up to
+    /// `capacity` tokens in each one
+    ///
+    /// Each window allocates 1 byte per token, rounded up to a power of two with some headroom.
+    /// The default is windows of 1 hour which remember 900,000 tokens each, using 1MiB.
+    pub fn new(window: Duration, capacity: usize) -> Self {
+        assert!(!window.is_zero(), "the replay window must not be empty");
+
+        Self {
+            window,
+            capacity,
+            filters: BTreeMap::new(),
+        }
+    }
+
+    fn window_index(&self, time: SystemTime) -> u64 {
+        let time = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        (time.as_nanos() / self.window.as_nanos()) as u64
+    }
+}
+
+impl Default for LocalReplayFilter {
+    fn default() -> Self {
+        Self::new(DEFAULT_REPLAY_WINDOW, DEFAULT_REPLAY_WINDOW_CAPACITY)
+    }
+}
+
+impl fmt::Debug for LocalReplayFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalReplayFilter")
+            .field("window", &self.window)
+            .field("capacity", &self.capacity)
+            .field("windows", &self.filters.keys())
+            .finish()
+    }
+}
+
+impl ReplayFilter for LocalReplayFilter {
+    fn is_replay(
+        &mut self,
+        _key_version: u32,
+        token_id: &[u8],
+        now: SystemTime,
+        expires_at: SystemTime,
+    ) -> bool {
+        // Forget the windows in which every token has expired
+        let current = self.window_index(now);
+        self.filters.retain(|index, _| *index >= current);
+
+        let index = self.window_index(expires_at);
+        let capacity = self.capacity;
+        // Leave some headroom, since insertions start to fail as the filter fills up
+        let filter = self
+            .filters
+            .entry(index)
+            .or_insert_with(|| CuckooFilter::with_capacity(capacity + capacity / 8));
+
+        let token_id = TokenId(token_id);
+        if filter.contains(&token_id) {
+            return true;
+        }
+
+        // The filter can't remember any more tokens, so reject them rather than allow them to be
+        // replayed
+        if filter.len() >= capacity {
+            return true;
+        }
+
+        // Adding a token can only fail once the filter is close to full, which the capacity
+        // check above prevents
+        let _ = filter.add(&token_id);
+
+        false
+    }
+}
+
+struct TokenId<'a>(&'a [u8]);
+
+impl Hash for TokenId<'_> {
+    /// Token ids are taken from the hmac, which is already uniformly distributed
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write(self.0);
+    }
+}
+
+#[derive(Debug)]
+pub struct Provider<S, R = LocalReplayFilter> {
+    key_source: S,
+    replay_filter: R,
+    refresh_interval: Duration,
+    max_clock_skew: Duration,
+    retry_token_lifetime: Duration,
+    new_token_lifetime: Option<Duration>,
+}
+
+impl<S: KeySource> Provider<S> {
+    /// Creates a provider which loads keys from the `key_source`
+    pub fn new(key_source: S) -> Self {
+        Self {
+            key_source,
+            replay_filter: LocalReplayFilter::default(),
+            refresh_interval: DEFAULT_REFRESH_INTERVAL,
+            max_clock_skew: DEFAULT_MAX_CLOCK_SKEW,
+            retry_token_lifetime: DEFAULT_RETRY_TOKEN_LIFETIME,
+            new_token_lifetime: Some(DEFAULT_NEW_TOKEN_LIFETIME),
+        }
+    }
+}
+
+impl<S, R> Provider<S, R> {
+    /// Sets the filter used to detect replayed tokens
+    pub fn with_replay_filter<T: ReplayFilter>(self, replay_filter: T) -> Provider<S, T> {
+        Provider {
+            key_source: self.key_source,
+            replay_filter,
+            refresh_interval: self.refresh_interval,
+            max_clock_skew: self.max_clock_skew,
+            retry_token_lifetime: self.retry_token_lifetime,
+            new_token_lifetime: self.new_token_lifetime,
+        }
+    }
+
+    /// Sets how often keys are reloaded from the key source. The default is 60 seconds.
+    pub fn with_refresh_interval(mut self, interval: Duration) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    /// Sets how far the clocks of the hosts in the fleet may drift apart. The default is 5
+    /// seconds.
+    ///
+    /// Tokens signed with the next key in the schedule are accepted for this long before the key
+    /// activates, so a host whose clock is behind accepts the tokens issued by a host whose clock
+    /// is ahead.
+    pub fn with_max_clock_skew(mut self, max_clock_skew: Duration) -> Self {
+        self.max_clock_skew = max_clock_skew;
+        self
+    }
+
+    /// Sets how long tokens sent in Retry packets are accepted. The default is 1 second.
+    ///
+    /// The clocks of the hosts in the fleet should be synchronized to well within this lifetime.
+    pub fn with_retry_token_lifetime(mut self, lifetime: Duration) -> Self {
+        self.retry_token_lifetime = lifetime;
+        self
+    }
+
+    /// Sets how long tokens sent in NEW_TOKEN frames are accepted. The default is 12 hours.
+    pub fn with_new_token_lifetime(mut self, lifetime: Duration) -> Self {
+        self.new_token_lifetime = Some(lifetime);
+        self
+    }
+
+    /// Disables sending tokens to clients in NEW_TOKEN frames
+    pub fn without_new_tokens(mut self) -> Self {
+        self.new_token_lifetime = None;
+        self
+    }
+}
+
+/// An error returned when the [`Provider`] fails to start
+#[derive(Debug)]
+pub enum StartError<E> {
+    /// The key source failed to load the keys
+    KeySource(E),
+    /// None of the loaded keys has been activated, so the server can't issue tokens
+    NoActiveKey,
+}
+
+impl<E: fmt::Display> fmt::Display for StartError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeySource(error) => write!(f, "could not load the address token keys: {}", error),
+            Self::NoActiveKey => f.write_str("none of the address token keys has been activated"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for StartError<E> {}
+
+impl<S: KeySource, R: ReplayFilter> super::Provider for Provider<S, R> {
+    type Format = Format<R>;
+    type Error = StartError<S::Error>;
+
+    fn start(mut self) -> Result<Self::Format, Self::Error> {
+        let keys = self.key_source.load().map_err(StartError::KeySource)?;
+
+        let now = unix_time();
+        if !keys.iter().any(|key| key.activates_at <= now) {
+            return Err(StartError::NoActiveKey);
+        }
+
+        let mut format = Format {
+            key_updates: KeyUpdates::spawn(self.key_source, self.refresh_interval),
+            replay_filter: self.replay_filter,
+            keys: vec![],
+            max_clock_skew: self.max_clock_skew,
+            retry_token_lifetime: self.retry_token_lifetime,
+            new_token_lifetime: self.new_token_lifetime,
+            clock: unix_time,
+        };
+        format.set_keys(keys);
+
+        Ok(format)
+    }
+}
+
+fn unix_time() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Reloads the keys from the key source on a background thread
+///
+/// Key sources may block, for example while reading a file, which would otherwise stall the
+/// endpoint.
+struct KeyUpdates {
+    keys: mpsc::Receiver<Vec<Key>>,
+    /// Stops the thread once the format is dropped
+    _shutdown: mpsc::Sender<()>,
+}
+
+impl KeyUpdates {
+    fn spawn<S: KeySource>(mut key_source: S, refresh_interval: Duration) -> Self {
+        let (keys_tx, keys) = mpsc::channel();
+        let (shutdown, shutdown_rx) = mpsc::channel::<()>();
+
+        thread::Builder::new()
+            .name("s2n-quic-address-token-keys".into())
+            .spawn(move || {
+                while let Err(mpsc::RecvTimeoutError::Timeout) =
+                    shutdown_rx.recv_timeout(refresh_interval)
+                {
+                    match key_source.load() {
+                        Ok(keys) => {
+                            if keys_tx.send(keys).is_err() {
+                                break;
+                            }
+                        }
+                        // keep using the previously loaded keys
+                        Err(error) => key_source.on_load_error(error),
+                    }
+                }
+            })
+            .expect("failed to spawn the address token key thread");
+
+        Self {
+            keys,
+            _shutdown: shutdown,
+        }
+    }
+
+    /// Returns the most recently loaded keys, if they changed since the last call
+    fn latest(&self) -> Option<Vec<Key>> {
+        self.keys.try_iter().last()
+    }
+}
+
+pub struct Format<R = LocalReplayFilter> {
+    key_updates: KeyUpdates,
+    replay_filter: R,
+
+    /// The loaded keys, ordered by activation time
+    keys: Vec<Key>,
+
+    /// How long before its activation a key is accepted
+    max_clock_skew: Duration,
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+    //# Servers SHOULD ensure that
+    //# tokens sent in Retry packets are only accepted for a short time.
+    retry_token_lifetime: Duration,
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+    //# Thus, a token SHOULD have an
+    //# expiration time, which could be either an explicit expiration time or
+    //# an issued timestamp that can be used to dynamically calculate the
+    //# expiration time.
+    new_token_lifetime: Option<Duration>,
+
+    /// Returns the time since the UNIX epoch. Keys are activated by wall-clock time so every host
+    /// in the fleet rotates at the same time.
+    clock: fn() -> Duration,
+}
+
+impl<R: ReplayFilter> Format<R> {
+    fn set_keys(&mut self, mut keys: Vec<Key>) {
+        keys.sort_by_key(|key| (key.activates_at, key.version));
+        self.keys = keys;
+    }
+
+    /// Picks up reloaded keys and retires the keys that are no longer accepted
+    fn poll_keys(&mut self) -> Duration {
+        let now = (self.clock)();
+
+        if let Some(keys) = self.key_updates.latest() {
+            self.set_keys(keys);
+        }
+
+        // Only the current and previous keys are accepted
+        let active = self
+            .keys
+            .iter()
+            .take_while(|key| key.activates_at <= now)
+            .count();
+        if active > 2 {
+            for key in self.keys.drain(..active - 2) {
+                self.replay_filter.on_key_retired(key.version);
+            }
+        }
+
+        now
+    }
+
+    fn signing_key(&mut self) -> Option<(Key, Duration)> {
+        let now = self.poll_keys();
+        let key = self
+            .keys
+            .iter()
+            .rev()
+            .find(|key| key.activates_at <= now)?
+            .clone();
+        Some((key, now))
+    }
+
+    fn generate_token(
+        &mut self,
+        context: &mut super::Context<'_>,
+        source: Source,
+        original_destination_connection_id: Option<&connection::InitialId>,
+        output_buffer: &mut [u8],
+    ) -> Option<()> {
+        let (key, now) = self.signing_key()?;
+
+        let buffer = DecoderBufferMut::new(output_buffer);
+        let (token, _) = buffer
+            .decode::<&mut Token>()
+            .expect("Provided output buffer did not match TOKEN_LEN");
+
+        token.header = Header::new(source, 0);
+        token.body.issued_at = (now.as_millis() as u64).to_be_bytes();
+        token.body.original_destination_connection_id = [0; 20];
+        token.body.odcid_len = 0;
+
+        if let Some(odcid) = original_destination_connection_id {
+            token.body.original_destination_connection_id[..odcid.len()]
+                .copy_from_slice(odcid.as_bytes());
+            token.body.odcid_len = odcid.len() as u8;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# A server MUST ensure that every NEW_TOKEN frame it sends
+        //# is unique across all clients, with the exception of those sent to
+        //# repair losses of previously sent NEW_TOKEN frames.
+        context.random.public_random_fill(&mut token.nonce[..]);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# A token issued with NEW_TOKEN MUST NOT include information that would
+        //# allow values to be linked by an observer to the connection on which
+        //# it was issued.
+        key.apply_keystream(&token.nonce, &mut token.body);
+
+        let tag = Self::tag(&key, token, context)?;
+        token.hmac.copy_from_slice(tag.as_ref());
+
+        Some(())
+    }
+
+    fn verify_token(
+        &mut self,
+        context: &mut super::Context<'_>,
+        token: &Token,
+        lifetime: Duration,
+    ) -> Option<Body> {
+        let now = self.poll_keys();
+
+        // The key version isn't included in the token, so each of the accepted keys is tried.
+        // Hosts with a clock ahead of this one may already issue tokens with the next key.
+        let accepted_until = now + self.max_clock_skew;
+        let key = self
+            .keys
+            .iter()
+            .take_while(|key| key.activates_at <= accepted_until)
+            .find(|key| {
+                Self::tag(key, token, context).map_or(false, |tag| {
+                    constant_time::verify_slices_are_equal(&token.hmac, tag.as_ref()).is_ok()
+                })
+            })?;
+
+        let mut body = token.body;
+        key.apply_keystream(&token.nonce, &mut body);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-21.3
+        //# Servers SHOULD provide mitigations for this attack by limiting the
+        //# usage and lifetime of address validation tokens; see Section 8.1.3.
+        let issued_at = u64::from_be_bytes(body.issued_at);
+        if (now.as_millis() as u64).abs_diff(issued_at) > lifetime.as_millis() as u64 {
+            return None;
+        }
+
+        // Only record the token once it has been validated. This will prevent the filter from
+        // being filled with garbage tokens.
+        let expires_at = UNIX_EPOCH + Duration::from_millis(issued_at) + lifetime;
+        if self
+            .replay_filter
+            .is_replay(key.version, &token.hmac, UNIX_EPOCH + now, expires_at)
+        {
+            return None;
+        }
+
+        Some(body)
+    }
+
+    fn tag(key: &Key, token: &Token, context: &mut super::Context<'_>) -> Option<hmac::Tag> {
+        let mut ctx = hmac::Context::with_key(&key.signing_key);
+
+        // Everything preceding the tag is covered, including the encrypted body
+        ctx.update(&token.as_bytes()[..size_of::<Token>() - digest::SHA256_OUTPUT_LEN]);
+
+        match token.header.token_source() {
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+            //# Tokens
+            //# sent in Retry packets SHOULD include information that allows the
+            //# server to verify that the source IP address and port in client
+            //# packets remain constant.
+            Source::RetryPacket => {
+                ctx.update(context.peer_connection_id);
+                match context.remote_address {
+                    SocketAddress::IpV4 { ip, port, .. } => {
+                        ctx.update(ip);
+                        ctx.update(&port.to_be_bytes());
+                    }
+                    SocketAddress::IpV6 { ip, port, .. } => {
+                        ctx.update(ip);
+                        ctx.update(&port.to_be_bytes());
+                    }
+                    _ => return None,
+                }
+            }
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+            //# Tokens sent in NEW_TOKEN frames MUST include information that allows
+            //# the server to verify that the client IP address has not changed from
+            //# when the token was issued.
+            Source::NewTokenFrame => match context.remote_address {
+                SocketAddress::IpV4 { ip, .. } => ctx.update(ip),
+                SocketAddress::IpV6 { ip, .. } => ctx.update(ip),
+                _ => return None,
+            },
+        }
+
+        Some(ctx.sign())
+    }
+
+    fn decode_token(token: &[u8]) -> Option<&Token> {
+        let buffer = DecoderBuffer::new(token);
+        let (token, remaining) = buffer.decode::<&Token>().ok()?;
+
+        // Verify the provided token doesn't have any additional data
+        remaining.ensure_empty().ok()?;
+
+        if token.header.version() != TOKEN_VERSION {
+            return None;
+        }
+
+        Some(token)
+    }
+}
+
+impl<R: ReplayFilter> super::Format for Format<R> {
+    const TOKEN_LEN: usize = size_of::<Token>();
+
+    fn generate_new_token(
+        &mut self,
+        context: &mut super::Context<'_>,
+        _source_connection_id: &connection::LocalId,
+        output_buffer: &mut [u8],
+    ) -> Option<()> {
+        self.new_token_lifetime?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# A token issued with NEW_TOKEN MUST NOT include information that would
+        //# allow values to be linked by an observer to the connection on which
+        //# it was issued.
+        self.generate_token(context, Source::NewTokenFrame, None, output_buffer)
+    }
+
+    fn generate_retry_token(
+        &mut self,
+        context: &mut super::Context<'_>,
+        original_destination_connection_id: &connection::InitialId,
+        output_buffer: &mut [u8],
+    ) -> Option<()> {
+        self.generate_token(
+            context,
+            Source::RetryPacket,
+            Some(original_destination_connection_id),
+            output_buffer,
+        )
+    }
+
+    fn validate_token(
+        &mut self,
+        context: &mut super::Context<'_>,
+        token: &[u8],
+    ) -> Option<connection::InitialId> {
+        let token = Self::decode_token(token)?;
+
+        if token.header.token_source() != Source::RetryPacket {
+            return None;
+        }
+
+        let body = self.verify_token(context, token, self.retry_token_lifetime)?;
+
+        let odcid = body
+            .original_destination_connection_id
+            .get(..body.odcid_len as usize)?;
+        connection::InitialId::try_from_bytes(odcid)
+    }
+
+    fn validate_new_token(&mut self, context: &mut super::Context<'_>, token: &[u8]) -> Option<()> {
+        let lifetime = self.new_token_lifetime?;
+        let token = Self::decode_token(token)?;
+
+        if token.header.token_source() != Source::NewTokenFrame {
+            return None;
+        }
+
+        self.verify_token(context, token, lifetime)?;
+
+        Some(())
+    }
+
+    fn token_source(&self, token: &[u8]) -> Source {
+        // Tokens that fail to decode are likely from a NEW_TOKEN frame issued by a previous
+        // configuration
+        Self::decode_token(token).map_or(Source::NewTokenFrame, |token| token.header.token_source())
+    }
+}
+
+#[derive(Copy, Clone, Debug, FromBytes, AsBytes, Unaligned)]
+#[repr(C)]
+struct Token {
+    header: Header,
+
+    /// Encrypted with a keystream derived from the nonce
+    body: Body,
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+    //# An address validation token MUST be difficult to guess.  Including a
+    //# random value with at least 128 bits of entropy in the token would be
+    //# sufficient, but this depends on the server remembering the value it
+    //# sends to clients.
+    nonce: [u8; 32],
+
+    hmac: [u8; 32],
+}
+
+s2n_codec::zerocopy_value_codec!(Token);
+
+#[derive(Copy, Clone, Debug, FromBytes, AsBytes, Unaligned)]
+#[repr(C)]
+struct Body {
+    /// Milliseconds since the UNIX epoch
+    issued_at: [u8; 8],
+
+    odcid_len: u8,
+    original_destination_connection_id: [u8; 20],
+}
+
+// The body is encrypted with a single block of keystream
+const _: () = assert!(size_of::<Body>() <= digest::SHA256_OUTPUT_LEN);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::address_token::{Format as _, Provider as _};
+    use core::cell::Cell;
+    use s2n_quic_core::{inet::SocketAddress, random, token::Context};
+    use std::net::SocketAddr;
+
+    thread_local! {
+        static NOW: Cell<Duration> = Cell::new(Duration::from_secs(1_000_000));
+    }
+
+    fn now() -> Duration {
+        NOW.with(|now| now.get())
+    }
+
+    fn advance(duration: Duration) {
+        NOW.with(|now| now.set(now.get() + duration));
+    }
+
+    const ROTATION_PERIOD: Duration = Duration::from_secs(100);
+
+    /// Returns a schedule with a new key activated every `ROTATION_PERIOD`
+    fn schedule() -> Result<Vec<Key>, io::Error> {
+        Ok((0..3u32)
+            .map(|version| {
+                let activates_at = UNIX_EPOCH + now() + ROTATION_PERIOD * version;
+                Key::new(version, activates_at, &[version as u8; 32]).unwrap()
+            })
+            .collect())
+    }
+
+    fn test_format<S: KeySource>(key_source: S) -> Format {
+        let mut format = Provider::new(key_source)
+            .start()
+            .map_err(|error| error.to_string())
+            .unwrap();
+        format.clock = now;
+        format
+    }
+
+    #[test]
+    fn fleet_test() {
+        let keys = schedule().unwrap();
+        let mut issuer = test_format(move || Ok::<_, io::Error>(keys.clone()));
+        let keys = schedule().unwrap();
+        let mut validator = test_format(move || Ok::<_, io::Error>(keys.clone()));
+
+        let conn_id = connection::PeerId::TEST_ID;
+        let odcid = connection::InitialId::TEST_ID;
+        let addr = SocketAddress::default();
+        let mut token = [0; <Format>::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+
+        issuer
+            .generate_retry_token(&mut context, &odcid, &mut token)
+            .unwrap();
+
+        // the contents of the token are encrypted
+        assert!(!token
+            .windows(odcid.len())
+            .any(|window| window == odcid.as_bytes()));
+
+        // tokens issued by one host are valid on the others
+        assert_eq!(validator.validate_token(&mut context, &token), Some(odcid));
+
+        // replays are detected by the replay filter of the host
+        assert!(validator.validate_token(&mut context, &token).is_none());
+
+        // tokens expire after their lifetime
+        issuer
+            .generate_retry_token(&mut context, &odcid, &mut token)
+            .unwrap();
+        advance(DEFAULT_RETRY_TOKEN_LIFETIME * 2);
+        assert!(validator.validate_token(&mut context, &token).is_none());
+    }
+
+    #[test]
+    fn rollover_test() {
+        let keys = schedule().unwrap();
+        let mut format = test_format(move || Ok::<_, io::Error>(keys.clone()));
+        let conn_id = connection::PeerId::TEST_ID;
+        let local_conn_id = connection::LocalId::TEST_ID;
+        let addr: SocketAddress = "127.0.0.1:443".parse::<SocketAddr>().unwrap().into();
+        let mut token = [0; <Format>::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+
+        format
+            .generate_new_token(&mut context, &local_conn_id, &mut token)
+            .unwrap();
+        assert_eq!(format.token_source(&token), Source::NewTokenFrame);
+
+        // tokens signed with the previous key are accepted after the next key activates
+        advance(ROTATION_PERIOD);
+        let mut second_token = [0; <Format>::TOKEN_LEN];
+        format
+            .generate_new_token(&mut context, &local_conn_id, &mut second_token)
+            .unwrap();
+        assert!(format.validate_new_token(&mut context, &token).is_some());
+
+        // keys are retired once two newer keys have been activated
+        advance(ROTATION_PERIOD);
+        format
+            .generate_new_token(&mut context, &local_conn_id, &mut token)
+            .unwrap();
+        assert!(format
+            .validate_new_token(&mut context, &second_token)
+            .is_some());
+        assert_eq!(format.keys.len(), 2);
+
+        // the client IP address is included in the token
+        let other_addr: SocketAddress = "127.0.0.2:443".parse::<SocketAddr>().unwrap().into();
+        let mut context = Context::new(&other_addr, &conn_id, &mut random);
+        assert!(format.validate_new_token(&mut context, &token).is_none());
+    }
+
+    #[test]
+    fn key_file_test() {
+        let path = std::env::temp_dir().join(format!(
+            "s2n-quic-address-token-keys-{}",
+            std::process::id()
+        ));
+        let material = "0a0B".repeat(16);
+        std::fs::write(
+            &path,
+            format!(
+                "# version activates_at key\n\n2 200 {}\n1 100 {}\n",
+                material,
+                "ff".repeat(Key::MIN_LEN)
+            ),
+        )
+        .unwrap();
+
+        let keys = KeyFile::new(&path).load().unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].version(), 2);
+        assert_eq!(
+            keys[0].activates_at(),
+            UNIX_EPOCH + Duration::from_secs(200)
+        );
+
+        let short = format!("1 100 {}", "00".repeat(Key::MIN_LEN - 1));
+        let extra = format!("1 100 {} extra", material);
+        for invalid in ["1 100", "1 100 abc", "1 100 zz", &short, &extra] {
+            std::fs::write(&path, invalid).unwrap();
+            assert!(KeyFile::new(&path).load().is_err(), "{}", invalid);
+        }
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn key_reload_test() {
+        let version = std::sync::Arc::new(std::sync::atomic::AtomicU32::new(1));
+        let key_source = {
+            let version = version.clone();
+            move || {
+                let version = version.load(std::sync::atomic::Ordering::Relaxed);
+                Ok::<_, io::Error>(vec![Key::new(version, UNIX_EPOCH, &[1; 32]).unwrap()])
+            }
+        };
+        let mut format = Provider::new(key_source)
+            .with_refresh_interval(Duration::from_millis(1))
+            .start()
+            .unwrap();
+        assert_eq!(format.keys[0].version(), 1);
+
+        // keys are picked up once the background thread reloads them
+        version.store(2, std::sync::atomic::Ordering::Relaxed);
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        while format.keys[0].version() != 2 {
+            assert!(std::time::Instant::now() < deadline);
+            std::thread::sleep(Duration::from_millis(1));
+            format.poll_keys();
+        }
+    }
+
+    #[test]
+    fn replay_window_test() {
+        let window = Duration::from_secs(10);
+        let mut filter = LocalReplayFilter::new(window, 2);
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+        let expires_at = now + window;
+
+        assert!(!filter.is_replay(0, &[1; 32], now, expires_at));
+        assert!(filter.is_replay(0, &[1; 32], now, expires_at));
+
+        // tokens are rejected once the window is full
+        assert!(!filter.is_replay(0, &[2; 32], now, expires_at));
+        assert!(filter.is_replay(0, &[3; 32], now, expires_at));
+
+        // tokens which expire in another window are tracked separately
+        assert!(!filter.is_replay(0, &[3; 32], now, expires_at + window));
+        assert_eq!(filter.filters.len(), 2);
+
+        // windows are dropped once their tokens have expired
+        let now = expires_at + window;
+        assert!(!filter.is_replay(0, &[4; 32], now, now + window));
+        assert!(!filter.filters.contains_key(&101));
+        assert_eq!(filter.filters.len(), 2);
+    }
+
+    #[test]
+    fn short_key_test() {
+        assert!(Key::new(1, UNIX_EPOCH, &[1; Key::MIN_LEN - 1]).is_err());
+        assert!(Key::new(1, UNIX_EPOCH, &[1; Key::MIN_LEN]).is_ok());
+    }
+
+    #[test]
+    fn no_active_key_test() {
+        let start =
+            |keys: Vec<Key>| Provider::new(move || Ok::<_, io::Error>(keys.clone())).start();
+
+        assert!(matches!(start(vec![]), Err(StartError::NoActiveKey)));
+
+        // the server can't issue tokens until a key is activated
+        let future = SystemTime::now() + Duration::from_secs(60 * 60);
+        let key = Key::new(1, future, &[1; 32]).unwrap();
+        assert!(matches!(start(vec![key]), Err(StartError::NoActiveKey)));
+
+        let error = Provider::new(|| Err::<Vec<Key>, _>(io::Error::from(io::ErrorKind::NotFound)))
+            .start()
+            .err()
+            .unwrap();
+        assert!(matches!(error, StartError::KeySource(_)));
+    }
+
+    #[test]
+    fn clock_skew_test() {
+        fn behind() -> Duration {
+            now() - DEFAULT_MAX_CLOCK_SKEW / 2
+        }
+
+        fn far_behind() -> Duration {
+            now() - DEFAULT_MAX_CLOCK_SKEW * 2
+        }
+
+        let keys = schedule().unwrap();
+        let mut issuer = test_format(move || Ok::<_, io::Error>(keys.clone()));
+        let keys = schedule().unwrap();
+        let mut validator = test_format(move || Ok::<_, io::Error>(keys.clone()));
+
+        let conn_id = connection::PeerId::TEST_ID;
+        let local_conn_id = connection::LocalId::TEST_ID;
+        let addr: SocketAddress = "127.0.0.1:443".parse::<SocketAddr>().unwrap().into();
+        let mut token = [0; <Format>::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+
+        // the issuer's clock reaches the activation of the next key before the validator's
+        advance(ROTATION_PERIOD);
+        issuer
+            .generate_new_token(&mut context, &local_conn_id, &mut token)
+            .unwrap();
+
+        // the next key is accepted within the clock skew
+        validator.clock = behind;
+        assert!(validator.validate_new_token(&mut context, &token).is_some());
+
+        issuer
+            .generate_new_token(&mut context, &local_conn_id, &mut token)
+            .unwrap();
+        validator.clock = far_behind;
+        assert!(validator.validate_new_token(&mut context, &token).is_none());
+    }
+
+    #[test]
+    fn key_load_error_test() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        /// Fails every load after the first one
+        struct Flaky {
+            loads: usize,
+            errors: Arc<AtomicUsize>,
+        }
+
+        impl KeySource for Flaky {
+            type Error = io::Error;
+
+            fn load(&mut self) -> Result<Vec<Key>, Self::Error> {
+                self.loads += 1;
+                if self.loads > 1 {
+                    return Err(io::Error::from(io::ErrorKind::NotFound));
+                }
+                Ok(vec![Key::new(1, UNIX_EPOCH, &[1; 32]).unwrap()])
+            }
+
+            fn on_load_error(&mut self, _error: Self::Error) {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let errors = Arc::new(AtomicUsize::new(0));
+        let key_source = Flaky {
+            loads: 0,
+            errors: errors.clone(),
+        };
+        let mut format = Provider::new(key_source)
+            .with_refresh_interval(Duration::from_millis(1))
+            .start()
+            .unwrap();
+
+        // failed loads are reported and the previously loaded keys are kept
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        while errors.load(Ordering::Relaxed) == 0 {
+            assert!(std::time::Instant::now() < deadline);
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        format.poll_keys();
+        assert_eq!(format.keys.len(), 1);
+    }
+}
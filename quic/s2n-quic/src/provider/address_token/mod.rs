@@ -14,7 +14,10 @@ pub trait Provider: 'static {
 }
 
 pub mod default;
+pub mod fleet;
+pub mod shared;
 
 pub use default::Provider as Default;
+pub use shared::Shared;
 
 impl_provider_utils!();
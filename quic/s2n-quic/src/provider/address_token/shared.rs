@@ -0,0 +1,139 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Address token formats shared between multiple endpoints
+//!
+//! Tokens issued by one endpoint can be validated by any other endpoint holding a clone of the
+//! same [`Shared`] format. This includes the replay protection of the inner format, so a token
+//! can't be used once with each endpoint.
+//!
+//! The endpoints share a single instance of the inner format behind a mutex, so only one endpoint
+//! can generate or validate a token at a time. The state can't be split between the endpoints,
+//! since a replayed token may be sent from a different address and be delivered to any of them.
+//!
+//! The lock is only taken for packets which carry a token and when issuing tokens, rather than
+//! for every packet. With the [`default`](super::default) format, each operation is a single
+//! HMAC over the token and a lookup in the replay filter. A server which spends a lot of time
+//! sending Retry packets or validating tokens, such as one under a flood of Initial packets, will
+//! see the endpoints contend for the lock.
+
+use s2n_quic_core::{connection, token};
+use std::sync::{Arc, Mutex};
+
+/// An address token format which is shared between endpoints
+///
+/// Every token operation on any of the clones takes the same lock. See the
+/// [module documentation](self) for the effect on sharded servers.
+pub struct Shared<F>(Arc<Mutex<F>>);
+
+impl<F> Shared<F> {
+    pub fn new(format: F) -> Self {
+        Self(Arc::new(Mutex::new(format)))
+    }
+}
+
+impl<F> Clone for Shared<F> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<F> core::fmt::Debug for Shared<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Shared").finish()
+    }
+}
+
+impl<F: super::Format> super::Provider for Shared<F> {
+    type Format = Self;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Format, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl<F: super::Format> super::Format for Shared<F> {
+    const TOKEN_LEN: usize = F::TOKEN_LEN;
+
+    fn generate_new_token(
+        &mut self,
+        context: &mut token::Context<'_>,
+        source_connection_id: &connection::LocalId,
+        output_buffer: &mut [u8],
+    ) -> Option<()> {
+        self.0
+            .lock()
+            .ok()?
+            .generate_new_token(context, source_connection_id, output_buffer)
+    }
+
+    fn generate_retry_token(
+        &mut self,
+        context: &mut token::Context<'_>,
+        original_destination_connection_id: &connection::InitialId,
+        output_buffer: &mut [u8],
+    ) -> Option<()> {
+        self.0.lock().ok()?.generate_retry_token(
+            context,
+            original_destination_connection_id,
+            output_buffer,
+        )
+    }
+
+    fn validate_token(
+        &mut self,
+        context: &mut token::Context<'_>,
+        token: &[u8],
+    ) -> Option<connection::InitialId> {
+        self.0.lock().ok()?.validate_token(context, token)
+    }
+
+    fn validate_new_token(&mut self, context: &mut token::Context<'_>, token: &[u8]) -> Option<()> {
+        self.0.lock().ok()?.validate_new_token(context, token)
+    }
+
+    fn token_source(&self, token: &[u8]) -> token::Source {
+        match self.0.lock() {
+            Ok(format) => format.token_source(token),
+            Err(_) => token::Source::RetryPacket,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::address_token::{default, Format as _, Provider as _};
+    use s2n_quic_core::{inet::SocketAddress, random, token::Context};
+
+    #[test]
+    fn shared_test() {
+        let format = default::Provider::default().start().unwrap();
+        let mut issuer = Shared::new(format);
+        let mut validator = issuer.clone();
+
+        let conn_id = connection::PeerId::try_from_bytes(&[2, 4, 6, 8, 10]).unwrap();
+        let orig_conn_id =
+            connection::InitialId::try_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+        let addr = SocketAddress::default();
+        let mut token = [0; default::Format::TOKEN_LEN];
+        let mut random = random::testing::Generator(5);
+
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+        issuer
+            .generate_retry_token(&mut context, &orig_conn_id, &mut token)
+            .unwrap();
+
+        // tokens issued by one endpoint are valid for the others
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+        assert_eq!(
+            validator.validate_token(&mut context, &token),
+            Some(orig_conn_id)
+        );
+
+        // tokens are only accepted once across all of the endpoints
+        let mut context = Context::new(&addr, &conn_id, &mut random);
+        assert_eq!(issuer.validate_token(&mut context, &token), None);
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Stores the address validation tokens servers send to clients in NEW_TOKEN frames
+//!
+//! Tokens are included in the Initial packets of future connections to the same server, which
+//! allows the server to validate the address of the client without a Retry packet. Servers
+//! issue tokens with the [`address_token`](super::address_token) provider.
+
+pub use s2n_quic_core::token::{Disabled, Store};
+
+use bytes::Bytes;
+use s2n_quic_core::application::ServerName;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+/// Provides a token store for a client
+pub trait Provider {
+    type Store: 'static + Store + Clone;
+    type Error: 'static + core::fmt::Display;
+
+    fn start(self) -> Result<Self::Store, Self::Error>;
+}
+
+impl_provider_utils!();
+
+impl<T: 'static + Store + Clone> Provider for T {
+    type Store = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Store, Self::Error> {
+        Ok(self)
+    }
+}
+
+pub type Default = Cache;
+
+/// An in-memory [`Store`] which holds a limited number of tokens for each server
+///
+/// Clones of the cache share the same tokens.
+#[derive(Clone, Debug)]
+pub struct Cache(Arc<State>);
+
+#[derive(Debug)]
+struct State {
+    max_servers: usize,
+    max_tokens_per_server: usize,
+    entries: Mutex<HashMap<Bytes, VecDeque<Entry>>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    received_at: Instant,
+    token: Bytes,
+}
+
+impl core::default::Default for Cache {
+    fn default() -> Self {
+        Self::new(256, 4)
+    }
+}
+
+impl Cache {
+    /// Creates a cache holding up to `max_tokens_per_server` tokens for each of
+    /// `max_servers` servers
+    pub fn new(max_servers: usize, max_tokens_per_server: usize) -> Self {
+        Self(Arc::new(State {
+            max_servers,
+            max_tokens_per_server,
+            entries: Mutex::new(HashMap::new()),
+        }))
+    }
+}
+
+impl Store for Cache {
+    fn on_new_token(&self, server_name: &ServerName, token: &[u8]) {
+        let state = &self.0;
+
+        if state.max_servers == 0 || state.max_tokens_per_server == 0 {
+            return;
+        }
+
+        let key = server_name.clone().into_bytes();
+        let mut entries = state.entries.lock().unwrap();
+
+        // evict the server which least recently issued a token
+        if !entries.contains_key(&key) && entries.len() >= state.max_servers {
+            if let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, tokens)| tokens.back().map(|entry| entry.received_at))
+                .map(|(key, _)| key.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+
+        let tokens = entries.entry(key).or_default();
+        if tokens.len() >= state.max_tokens_per_server {
+            tokens.pop_front();
+        }
+        tokens.push_back(Entry {
+            received_at: Instant::now(),
+            token: Bytes::copy_from_slice(token),
+        });
+    }
+
+    fn take(&self, server_name: &ServerName) -> Option<Bytes> {
+        let key = server_name.clone().into_bytes();
+        let mut entries = self.0.entries.lock().unwrap();
+        let tokens = entries.get_mut(&key)?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# For a client, this ambiguity means that
+        //# sending the most recent unused token is most likely to be effective.
+        let token = tokens.pop_back().map(|entry| entry.token);
+
+        if tokens.is_empty() {
+            entries.remove(&key);
+        }
+
+        token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_test() {
+        let cache = Cache::new(1, 2);
+        let server_name: ServerName = "example.com".into();
+
+        for id in 0..3 {
+            cache.on_new_token(&server_name, &[id]);
+        }
+
+        // tokens are single use and the newest is returned first
+        let clone = cache.clone();
+        assert_eq!(clone.take(&server_name).unwrap()[..], [2]);
+        assert_eq!(cache.take(&server_name).unwrap()[..], [1]);
+        assert!(cache.take(&server_name).is_none());
+
+        // storing tokens for another server evicts the first one
+        cache.on_new_token(&server_name, &[3]);
+        let other: ServerName = "example.org".into();
+        cache.on_new_token(&other, &[4]);
+        assert!(cache.take(&server_name).is_none());
+        assert_eq!(cache.take(&other).unwrap()[..], [4]);
+    }
+}
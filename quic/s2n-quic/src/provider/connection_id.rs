@@ -25,6 +25,12 @@ impl<T: 'static + Format> Provider for T {
     }
 }
 
+/// Connection IDs which encode the server ID for load balancer routing
+#[cfg(any(feature = "provider-connection-id-quic-lb", test))]
+pub mod quic_lb;
+
+pub mod sharded;
+
 pub mod default {
     use core::{
         convert::{Infallible, TryInto},
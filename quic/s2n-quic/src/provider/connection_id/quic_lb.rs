@@ -0,0 +1,744 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Connection IDs which can be routed by a [QUIC-LB](https://datatracker.ietf.org/doc/draft-ietf-quic-load-balancers/)
+//! compatible load balancer
+//!
+//! Each connection ID encodes the identifier of the server which issued it, allowing a load
+//! balancer to route packets to the correct server without sharing any per-connection state.
+//! The server ID is either carried in plaintext or encrypted with a key shared between the
+//! servers and the load balancer.
+//!
+//! The first octet of each connection ID contains the config rotation bits, which identify the
+//! configuration used to encode the connection ID, followed by the length of the connection ID.
+//!
+//! ```rust
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use s2n_quic::provider::connection_id::quic_lb::{Config, Decoder, Format};
+//!
+//! let config = Config::new(1, 3, 8)?.with_key([42; 16])?;
+//!
+//! // each server is configured with its own server ID
+//! let format = Format::builder()
+//!     .with_config(config.clone())
+//!     .with_server_id(&[1, 2, 3])?
+//!     .build()?;
+//!
+//! // the load balancer uses the same configuration to recover the server ID
+//! let decoder = Decoder::default().with_config(config);
+//! #
+//! #   Ok(())
+//! # }
+//! ```
+
+use aes::{
+    cipher::{generic_array::GenericArray, BlockDecrypt, BlockEncrypt, KeyInit},
+    Aes128,
+};
+use core::{convert::TryInto, fmt, time::Duration};
+use rand::prelude::*;
+use s2n_quic_core::connection::{
+    self,
+    id::{ConnectionInfo, Generator, Validator},
+};
+
+/// The largest config ID which can be used to encode connection IDs
+///
+/// The remaining codepoint is reserved for connection IDs which can't be routed.
+pub const MAX_CONFIG_ID: u8 = 6;
+
+/// The maximum length of a server ID
+pub const MAX_SERVER_ID_LEN: usize = 15;
+
+/// The minimum length of the nonce which makes each connection ID unique
+pub const MIN_NONCE_LEN: usize = 4;
+
+/// The maximum length of the nonce which makes each connection ID unique
+pub const MAX_NONCE_LEN: usize = 18;
+
+/// The length of the key used to encrypt connection IDs
+pub const KEY_LEN: usize = 16;
+
+const CONFIG_ID_SHIFT: u8 = 5;
+const LENGTH_MASK: u8 = 0b1_1111;
+const BLOCK_LEN: usize = 16;
+/// The length of the plaintext which follows the first octet
+const MAX_PLAINTEXT_LEN: usize = connection::id::MAX_LEN - 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The config ID exceeds [`MAX_CONFIG_ID`]
+    InvalidConfigId,
+    /// The server ID is empty, exceeds [`MAX_SERVER_ID_LEN`] or doesn't match the length in
+    /// the [`Config`]
+    InvalidServerId,
+    /// The nonce length is outside of [`MIN_NONCE_LEN`] and [`MAX_NONCE_LEN`]
+    InvalidNonceLength,
+    /// The connection ID is too long or too long to be encrypted
+    InvalidLength,
+    /// The connection ID lifetime is outside of the allowed range
+    InvalidLifetime,
+    /// A [`Config`] was not provided
+    MissingConfig,
+}
+
+impl Error {
+    fn message(&self) -> &'static str {
+        match self {
+            Error::InvalidConfigId => "The config ID exceeds the maximum config ID",
+            Error::InvalidServerId => "The server ID length is invalid",
+            Error::InvalidNonceLength => "The nonce length is invalid",
+            Error::InvalidLength => "The connection ID length is invalid",
+            Error::InvalidLifetime => "The connection ID lifetime is invalid",
+            Error::MissingConfig => "A QUIC-LB config was not provided",
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Identifies the server which issued a connection ID
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ServerId {
+    bytes: [u8; MAX_SERVER_ID_LEN],
+    len: u8,
+}
+
+impl ServerId {
+    pub fn new(bytes: &[u8]) -> Result<Self, Error> {
+        if !(1..=MAX_SERVER_ID_LEN).contains(&bytes.len()) {
+            return Err(Error::InvalidServerId);
+        }
+
+        let mut id = Self {
+            bytes: [0; MAX_SERVER_ID_LEN],
+            len: bytes.len() as u8,
+        };
+        id.bytes[..bytes.len()].copy_from_slice(bytes);
+        Ok(id)
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
+
+impl AsRef<[u8]> for ServerId {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl fmt::Debug for ServerId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ServerId").field(&self.as_bytes()).finish()
+    }
+}
+
+/// The parameters shared between the servers and the load balancer
+///
+/// Connection IDs are `1 + server_id_len + nonce_len` bytes long. Without a key, the server ID
+/// is carried in plaintext. With a key, connection IDs with a 16 byte server ID and nonce are
+/// encrypted with a single AES-128-ECB pass, while shorter ones are encrypted with a four-pass
+/// Feistel network.
+#[derive(Clone)]
+pub struct Config {
+    config_id: u8,
+    server_id_len: usize,
+    nonce_len: usize,
+    cipher: Option<Aes128>,
+    encode_length: bool,
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("config_id", &self.config_id)
+            .field("server_id_len", &self.server_id_len)
+            .field("nonce_len", &self.nonce_len)
+            .field("is_encrypted", &self.cipher.is_some())
+            .field("encode_length", &self.encode_length)
+            .finish()
+    }
+}
+
+impl Config {
+    /// Creates a plaintext configuration
+    ///
+    /// The `config_id` is encoded in the config rotation bits of each connection ID and
+    /// allows the load balancer to transition between configurations.
+    pub fn new(config_id: u8, server_id_len: usize, nonce_len: usize) -> Result<Self, Error> {
+        if config_id > MAX_CONFIG_ID {
+            return Err(Error::InvalidConfigId);
+        }
+
+        if !(1..=MAX_SERVER_ID_LEN).contains(&server_id_len) {
+            return Err(Error::InvalidServerId);
+        }
+
+        if !(MIN_NONCE_LEN..=MAX_NONCE_LEN).contains(&nonce_len) {
+            return Err(Error::InvalidNonceLength);
+        }
+
+        if server_id_len + nonce_len > MAX_PLAINTEXT_LEN {
+            return Err(Error::InvalidLength);
+        }
+
+        Ok(Self {
+            config_id,
+            server_id_len,
+            nonce_len,
+            cipher: None,
+            encode_length: true,
+        })
+    }
+
+    /// Encrypts the server ID and nonce with the given key
+    ///
+    /// Encryption requires the server ID and nonce to be no longer than 16 bytes combined.
+    pub fn with_key(mut self, key: [u8; KEY_LEN]) -> Result<Self, Error> {
+        if self.plaintext_len() > BLOCK_LEN {
+            return Err(Error::InvalidLength);
+        }
+
+        self.cipher = Some(Aes128::new(&GenericArray::from(key)));
+        Ok(self)
+    }
+
+    /// Sets whether the length of the connection ID is encoded in the first octet
+    ///
+    /// This is enabled by default. If disabled, the load balancer and server must know the
+    /// length of connection IDs in advance.
+    pub fn with_length_encoding(mut self, enabled: bool) -> Self {
+        self.encode_length = enabled;
+        self
+    }
+
+    /// Returns the config ID encoded in the config rotation bits
+    #[inline]
+    pub fn config_id(&self) -> u8 {
+        self.config_id
+    }
+
+    /// Returns the length of the connection IDs
+    #[inline]
+    pub fn connection_id_len(&self) -> usize {
+        1 + self.plaintext_len()
+    }
+
+    #[inline]
+    fn plaintext_len(&self) -> usize {
+        self.server_id_len + self.nonce_len
+    }
+
+    /// Returns the length of the connection ID in `buffer`
+    fn decode_len(&self, buffer: &[u8]) -> Option<usize> {
+        let len = if self.encode_length {
+            decode_len(*buffer.first()?)?
+        } else {
+            self.connection_id_len()
+        };
+
+        if buffer.len() >= len {
+            Some(len)
+        } else {
+            None
+        }
+    }
+
+    /// Writes a connection ID for the server ID and nonce into `out`
+    fn encode(&self, server_id: &ServerId, nonce: &[u8], random: u8, out: &mut [u8]) {
+        debug_assert_eq!(server_id.len as usize, self.server_id_len);
+        debug_assert_eq!(nonce.len(), self.nonce_len);
+
+        let first_octet = if self.encode_length {
+            (self.connection_id_len() - 1) as u8
+        } else {
+            random & LENGTH_MASK
+        };
+        out[0] = (self.config_id << CONFIG_ID_SHIFT) | first_octet;
+
+        let plaintext = &mut out[1..self.connection_id_len()];
+        let (id, tail) = plaintext.split_at_mut(self.server_id_len);
+        id.copy_from_slice(server_id.as_bytes());
+        tail.copy_from_slice(nonce);
+
+        if let Some(cipher) = self.cipher.as_ref() {
+            if plaintext.len() == BLOCK_LEN {
+                cipher.encrypt_block(GenericArray::from_mut_slice(plaintext));
+            } else {
+                four_pass(cipher, plaintext, Direction::Encrypt);
+            }
+        }
+    }
+
+    /// Recovers the server ID from the connection ID
+    fn decode(&self, connection_id: &[u8]) -> Option<ServerId> {
+        if connection_id.len() < self.connection_id_len() {
+            return None;
+        }
+
+        let mut plaintext = [0u8; MAX_PLAINTEXT_LEN];
+        let plaintext = &mut plaintext[..self.plaintext_len()];
+        plaintext.copy_from_slice(&connection_id[1..self.connection_id_len()]);
+
+        if let Some(cipher) = self.cipher.as_ref() {
+            if plaintext.len() == BLOCK_LEN {
+                cipher.decrypt_block(GenericArray::from_mut_slice(plaintext));
+            } else {
+                four_pass(cipher, plaintext, Direction::Decrypt);
+            }
+        }
+
+        ServerId::new(&plaintext[..self.server_id_len]).ok()
+    }
+}
+
+/// Decodes the length of the connection ID from its first octet
+#[inline]
+fn decode_len(first_octet: u8) -> Option<usize> {
+    let len = (first_octet & LENGTH_MASK) as usize + 1;
+
+    if (connection::LocalId::MIN_LEN..=connection::id::MAX_LEN).contains(&len) {
+        Some(len)
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Encrypt,
+    Decrypt,
+}
+
+/// Encrypts or decrypts a plaintext shorter than a block with a four-round Feistel network
+fn four_pass(cipher: &Aes128, buffer: &mut [u8], direction: Direction) {
+    let len = buffer.len();
+    debug_assert!(len < BLOCK_LEN);
+
+    let half_len = (len + 1) / 2;
+    let is_odd = len % 2 == 1;
+
+    // when the length is odd, the middle octet is split between both halves
+    let mut left = [0u8; BLOCK_LEN / 2];
+    let mut right = [0u8; BLOCK_LEN / 2];
+    let left = &mut left[..half_len];
+    let right = &mut right[..half_len];
+    left.copy_from_slice(&buffer[..half_len]);
+    right.copy_from_slice(&buffer[len - half_len..]);
+    if is_odd {
+        left[half_len - 1] &= 0xf0;
+        right[0] &= 0x0f;
+    }
+
+    let round = |input: &[u8], pass: u8| {
+        let mut block = [0u8; BLOCK_LEN];
+        block[..input.len()].copy_from_slice(input);
+        block[BLOCK_LEN - 2] = len as u8;
+        block[BLOCK_LEN - 1] = pass;
+        let mut block = GenericArray::from(block);
+        cipher.encrypt_block(&mut block);
+        block
+    };
+
+    let xor_left = |left: &mut [u8], mask: &[u8]| {
+        for (a, b) in left.iter_mut().zip(mask) {
+            *a ^= b;
+        }
+        if is_odd {
+            left[half_len - 1] &= 0xf0;
+        }
+    };
+
+    let xor_right = |right: &mut [u8], mask: &[u8]| {
+        for (a, b) in right.iter_mut().zip(mask) {
+            *a ^= b;
+        }
+        if is_odd {
+            right[0] &= 0x0f;
+        }
+    };
+
+    // The left half is expanded first, and each expanded half masks the other half with the
+    // leading octets of its ciphertext
+    match direction {
+        Direction::Encrypt => {
+            xor_right(right, &round(left, 1));
+            xor_left(left, &round(right, 2));
+            xor_right(right, &round(left, 3));
+            xor_left(left, &round(right, 4));
+        }
+        Direction::Decrypt => {
+            xor_left(left, &round(right, 4));
+            xor_right(right, &round(left, 3));
+            xor_left(left, &round(right, 2));
+            xor_right(right, &round(left, 1));
+        }
+    }
+
+    buffer[..half_len].copy_from_slice(left);
+    if is_odd {
+        buffer[half_len - 1] |= right[0];
+        buffer[half_len..].copy_from_slice(&right[1..]);
+    } else {
+        buffer[half_len..].copy_from_slice(right);
+    }
+}
+
+/// Generates connection IDs which encode the server ID
+#[derive(Debug)]
+pub struct Format {
+    config: Config,
+    server_id: ServerId,
+    lifetime: Option<Duration>,
+    /// Incremented for each connection ID to ensure encrypted connection IDs are unique
+    counter: u64,
+}
+
+impl Format {
+    /// Creates a builder for the format
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
+
+/// A builder for [`Format`] providers
+#[derive(Debug, Default)]
+pub struct Builder {
+    config: Option<Config>,
+    server_id: Option<ServerId>,
+    lifetime: Option<Duration>,
+}
+
+impl Builder {
+    /// Sets the configuration shared with the load balancer
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Sets the ID of the server which is encoded in each connection ID
+    pub fn with_server_id(mut self, server_id: &[u8]) -> Result<Self, Error> {
+        self.server_id = Some(ServerId::new(server_id)?);
+        Ok(self)
+    }
+
+    /// Sets the lifetime of each generated connection Id
+    pub fn with_lifetime(mut self, lifetime: Duration) -> Result<Self, Error> {
+        if !(connection::id::MIN_LIFETIME..=connection::id::MAX_LIFETIME).contains(&lifetime) {
+            return Err(Error::InvalidLifetime);
+        }
+        self.lifetime = Some(lifetime);
+        Ok(self)
+    }
+
+    /// Builds the [`Format`] into a provider
+    pub fn build(self) -> Result<Format, Error> {
+        let config = self.config.ok_or(Error::MissingConfig)?;
+        let server_id = self.server_id.ok_or(Error::InvalidServerId)?;
+
+        if server_id.len as usize != config.server_id_len {
+            return Err(Error::InvalidServerId);
+        }
+
+        Ok(Format {
+            config,
+            server_id,
+            lifetime: self.lifetime,
+            counter: rand::thread_rng().gen(),
+        })
+    }
+}
+
+impl Generator for Format {
+    fn generate(&mut self, _connection_info: &ConnectionInfo) -> connection::LocalId {
+        let mut rng = rand::thread_rng();
+
+        let mut nonce = [0u8; MAX_NONCE_LEN];
+        let nonce = &mut nonce[..self.config.nonce_len];
+        rng.fill_bytes(nonce);
+
+        if self.config.cipher.is_some() {
+            // Encrypted connection IDs can carry a counter without being linkable, which
+            // guarantees they are unique.
+            let counter = self.counter.to_be_bytes();
+            let len = counter.len().min(nonce.len());
+            let offset = nonce.len() - len;
+            nonce[offset..].copy_from_slice(&counter[counter.len() - len..]);
+            self.counter = self.counter.wrapping_add(1);
+        }
+
+        let mut id = [0u8; connection::id::MAX_LEN];
+        let id = &mut id[..self.config.connection_id_len()];
+        self.config.encode(&self.server_id, nonce, rng.gen(), id);
+        (&*id).try_into().expect("length already checked")
+    }
+
+    fn lifetime(&self) -> Option<Duration> {
+        self.lifetime
+    }
+}
+
+impl Validator for Format {
+    fn validate(&self, _connection_info: &ConnectionInfo, buffer: &[u8]) -> Option<usize> {
+        self.config.decode_len(buffer)
+    }
+}
+
+/// Recovers server IDs from connection IDs
+///
+/// Load balancers can use the decoder to route packets to the server which issued the
+/// destination connection ID. A decoder can hold a [`Config`] for each config ID, which allows
+/// the configuration to be rotated without disrupting existing connections.
+#[derive(Clone, Debug, Default)]
+pub struct Decoder {
+    configs: [Option<Config>; MAX_CONFIG_ID as usize + 1],
+}
+
+impl Decoder {
+    /// Adds a configuration, replacing any existing configuration with the same config ID
+    pub fn with_config(mut self, config: Config) -> Self {
+        let index = config.config_id as usize;
+        self.configs[index] = Some(config);
+        self
+    }
+
+    /// Returns the length of the connection ID at the start of `buffer`
+    ///
+    /// This is used to find the destination connection ID in short header packets.
+    pub fn connection_id_len(&self, buffer: &[u8]) -> Option<usize> {
+        self.config(buffer)?.decode_len(buffer)
+    }
+
+    /// Returns the server ID encoded in `connection_id`
+    ///
+    /// `None` is returned if the connection ID was not encoded with a known configuration, in
+    /// which case the load balancer should fall back to another routing method.
+    pub fn server_id(&self, connection_id: &[u8]) -> Option<ServerId> {
+        self.config(connection_id)?.decode(connection_id)
+    }
+
+    fn config(&self, connection_id: &[u8]) -> Option<&Config> {
+        let config_id = connection_id.first()? >> CONFIG_ID_SHIFT;
+        self.configs.get(config_id as usize)?.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const KEY: [u8; KEY_LEN] = [
+        0x8f, 0x95, 0xf0, 0x92, 0x45, 0x76, 0x5f, 0x80, 0x25, 0x69, 0x34, 0xe5, 0x0c, 0x66, 0x20,
+        0x7f,
+    ];
+
+    fn round_trip(config: Config) {
+        let remote_address = &s2n_quic_core::inet::SocketAddress::default();
+        let connection_info = ConnectionInfo::new(remote_address);
+
+        let server_id: Vec<u8> = (1..=config.server_id_len as u8).collect();
+        let mut format = Format::builder()
+            .with_config(config.clone())
+            .with_server_id(&server_id)
+            .unwrap()
+            .build()
+            .unwrap();
+        let decoder = Decoder::default().with_config(config.clone());
+
+        let mut ids = HashSet::new();
+
+        for _ in 0..100 {
+            let id = format.generate(&connection_info);
+            let bytes = id.as_bytes();
+
+            assert_eq!(bytes.len(), config.connection_id_len());
+            assert_eq!(bytes[0] >> CONFIG_ID_SHIFT, config.config_id);
+            assert_eq!(
+                format.validate(&connection_info, bytes),
+                Some(config.connection_id_len())
+            );
+            assert_eq!(
+                decoder.connection_id_len(bytes),
+                Some(config.connection_id_len())
+            );
+            assert_eq!(decoder.server_id(bytes).unwrap().as_bytes(), &server_id[..]);
+
+            // short server IDs may match by chance
+            if config.cipher.is_some() && server_id.len() >= 4 {
+                assert_ne!(&bytes[1..=server_id.len()], &server_id[..]);
+            }
+
+            assert!(
+                ids.insert(bytes.to_vec()),
+                "connection IDs should be unique"
+            );
+        }
+    }
+
+    #[test]
+    fn plaintext_test() {
+        round_trip(Config::new(0, 3, 8).unwrap());
+        round_trip(Config::new(6, 15, 4).unwrap());
+    }
+
+    #[test]
+    fn single_pass_test() {
+        round_trip(Config::new(1, 8, 8).unwrap().with_key(KEY).unwrap());
+    }
+
+    /// Checks the encoding against test vectors from the draft, since round trips alone can't
+    /// detect an encoder and decoder which deviate from it in the same way
+    #[test]
+    fn known_answer_test() {
+        fn hex(value: &str) -> Vec<u8> {
+            (0..value.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+                .collect()
+        }
+
+        let single_pass = Config::new(2, 8, 8).unwrap().with_key(KEY).unwrap();
+        let four_pass = Config::new(0, 3, 4).unwrap().with_key(KEY).unwrap();
+        let four_pass_long = Config::new(1, 10, 5).unwrap().with_key(KEY).unwrap();
+
+        for (config, server_id, nonce, connection_id) in [
+            (
+                Config::new(0, 3, 4).unwrap(),
+                "ed793a",
+                "ee080dbf",
+                "07ed793aee080dbf",
+            ),
+            (
+                single_pass,
+                "ed793a51d49b8f5f",
+                "ee080dbf48c0d1e5",
+                "504dd2d05a7b0de9b2b9907afb5ecf8cc3",
+            ),
+            (four_pass, "ed793a", "ee080dbf", "0720b1d07b359d3c"),
+            (
+                four_pass_long,
+                "ed793a51d49b8f5fab65",
+                "ee080dbf48",
+                "2fcc381bc74cb4fbad2823a3d1f8fed2",
+            ),
+        ] {
+            let server_id = ServerId::new(&hex(server_id)).unwrap();
+            let connection_id = hex(connection_id);
+
+            let mut out = [0; connection::id::MAX_LEN];
+            config.encode(&server_id, &hex(nonce), 0, &mut out);
+            assert_eq!(&out[..config.connection_id_len()], &connection_id[..]);
+
+            let decoder = Decoder::default().with_config(config);
+            assert_eq!(decoder.server_id(&connection_id), Some(server_id));
+        }
+    }
+
+    #[test]
+    fn four_pass_test() {
+        for server_id_len in 1..=12 {
+            for nonce_len in MIN_NONCE_LEN..=(BLOCK_LEN - 1 - server_id_len) {
+                let config = Config::new(2, server_id_len, nonce_len)
+                    .unwrap()
+                    .with_key(KEY)
+                    .unwrap();
+                round_trip(config);
+            }
+        }
+    }
+
+    #[test]
+    fn length_encoding_test() {
+        let config = Config::new(3, 4, 8).unwrap().with_length_encoding(false);
+        round_trip(config.clone());
+
+        let decoder = Decoder::default().with_config(config);
+        // the length isn't encoded so the configured length is used
+        assert_eq!(
+            decoder.connection_id_len(&[3 << CONFIG_ID_SHIFT; 20]),
+            Some(13)
+        );
+        assert_eq!(decoder.connection_id_len(&[3 << CONFIG_ID_SHIFT; 12]), None);
+    }
+
+    #[test]
+    fn config_rotation_test() {
+        let old = Config::new(0, 4, 8).unwrap().with_key(KEY).unwrap();
+        let new = Config::new(1, 4, 8)
+            .unwrap()
+            .with_key([1; KEY_LEN])
+            .unwrap();
+        let decoder = Decoder::default()
+            .with_config(old.clone())
+            .with_config(new.clone());
+
+        let remote_address = &s2n_quic_core::inet::SocketAddress::default();
+        let connection_info = ConnectionInfo::new(remote_address);
+
+        for (config, server_id) in [(old, [1, 2, 3, 4]), (new, [5, 6, 7, 8])] {
+            let mut format = Format::builder()
+                .with_config(config)
+                .with_server_id(&server_id)
+                .unwrap()
+                .build()
+                .unwrap();
+            let id = format.generate(&connection_info);
+            assert_eq!(
+                decoder.server_id(id.as_bytes()).unwrap().as_bytes(),
+                &server_id
+            );
+        }
+
+        // unknown config IDs can't be decoded
+        assert_eq!(decoder.server_id(&[(2 << CONFIG_ID_SHIFT) | 12; 13]), None);
+        assert_eq!(
+            decoder.server_id(&[(0b111 << CONFIG_ID_SHIFT) | 12; 13]),
+            None
+        );
+    }
+
+    #[test]
+    fn config_error_test() {
+        assert_eq!(Config::new(7, 4, 8).err(), Some(Error::InvalidConfigId));
+        assert_eq!(Config::new(0, 0, 8).err(), Some(Error::InvalidServerId));
+        assert_eq!(Config::new(0, 16, 4).err(), Some(Error::InvalidServerId));
+        assert_eq!(Config::new(0, 4, 3).err(), Some(Error::InvalidNonceLength));
+        assert_eq!(Config::new(0, 4, 19).err(), Some(Error::InvalidNonceLength));
+        assert_eq!(Config::new(0, 15, 5).err(), Some(Error::InvalidLength));
+        assert_eq!(
+            Config::new(0, 8, 9).unwrap().with_key(KEY).err(),
+            Some(Error::InvalidLength)
+        );
+
+        let config = Config::new(0, 4, 8).unwrap();
+        assert_eq!(
+            Format::builder()
+                .with_config(config)
+                .with_server_id(&[1, 2, 3])
+                .unwrap()
+                .build()
+                .err(),
+            Some(Error::InvalidServerId)
+        );
+        assert_eq!(
+            Format::builder()
+                .with_server_id(&[1, 2, 3])
+                .unwrap()
+                .build()
+                .err(),
+            Some(Error::MissingConfig)
+        );
+    }
+}
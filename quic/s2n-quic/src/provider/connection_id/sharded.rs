@@ -0,0 +1,96 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Connection IDs which identify the endpoint shard that issued them
+//!
+//! The first octet of each connection ID is replaced with the index of the shard, which allows
+//! the kernel to steer packets to the socket of the shard that owns the connection. See
+//! [`Server::sharded`](crate::Server::sharded) for more details.
+//!
+//! Since the first octet is used to carry the shard index, this format can't be combined with
+//! formats which also encode information in it, such as [`quic_lb`](super::quic_lb).
+
+use core::{convert::TryInto, time::Duration};
+use s2n_quic_core::connection::{
+    self,
+    id::{ConnectionInfo, Generator, Validator},
+};
+
+#[derive(Debug)]
+pub struct Format<F = super::default::Format> {
+    index: u8,
+    inner: F,
+}
+
+impl Format {
+    /// Creates a format for the shard at `index`, which generates the remainder of the connection
+    /// ID with the default format
+    pub fn new(index: u8) -> Self {
+        Self::with_format(index, Default::default())
+    }
+}
+
+impl<F> Format<F> {
+    /// Creates a format for the shard at `index`, which generates the remainder of the connection
+    /// ID with the provided format
+    pub fn with_format(index: u8, format: F) -> Self {
+        Self {
+            index,
+            inner: format,
+        }
+    }
+}
+
+/// Returns the index of the shard which issued the connection ID, if it is long enough to have
+/// been issued by a shard
+pub fn shard_index(connection_id: &[u8]) -> Option<u8> {
+    if connection_id.len() < connection::LocalId::MIN_LEN {
+        return None;
+    }
+    connection_id.first().copied()
+}
+
+impl<F: Generator> Generator for Format<F> {
+    fn generate(&mut self, connection_info: &ConnectionInfo) -> connection::LocalId {
+        let id = self.inner.generate(connection_info);
+        let mut bytes = [0u8; connection::id::MAX_LEN];
+        let bytes = &mut bytes[..id.len()];
+        bytes.copy_from_slice(id.as_bytes());
+        bytes[0] = self.index;
+        (&*bytes).try_into().expect("length already checked")
+    }
+
+    fn lifetime(&self) -> Option<Duration> {
+        self.inner.lifetime()
+    }
+}
+
+impl<F: Validator> Validator for Format<F> {
+    fn validate(&self, connection_info: &ConnectionInfo, buffer: &[u8]) -> Option<usize> {
+        self.inner.validate(connection_info, buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generator_test() {
+        let remote_address = &s2n_quic_core::inet::SocketAddress::default();
+        let connection_info = ConnectionInfo::new(remote_address);
+
+        for index in [0, 1, 7, u8::MAX] {
+            let mut format = Format::new(index);
+
+            for _ in 0..10 {
+                let id = format.generate(&connection_info);
+                assert_eq!(shard_index(id.as_bytes()), Some(index));
+                assert_eq!(
+                    format.validate(&connection_info, id.as_bytes()),
+                    Some(id.len())
+                );
+            }
+        }
+    }
+}
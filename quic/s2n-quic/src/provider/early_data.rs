@@ -0,0 +1,28 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allows servers to control which connections are able to process early data (0-RTT)
+
+pub use s2n_quic_core::early_data::{
+    default::{self, Controller as Default},
+    Attempt, Controller, Disabled, Outcome, RateLimited, RejectReason,
+};
+
+/// Provides early data support for an endpoint
+pub trait Provider {
+    type Controller: 'static + Send + Controller;
+    type Error: 'static + core::fmt::Display;
+
+    fn start(self) -> Result<Self::Controller, Self::Error>;
+}
+
+impl_provider_utils!();
+
+impl<T: 'static + Send + Controller> Provider for T {
+    type Controller = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Controller, Self::Error> {
+        Ok(self)
+    }
+}
@@ -4,7 +4,7 @@
 //! Allows applications to limit peer's ability to open new connections
 
 pub use s2n_quic_core::endpoint::{
-    limits::{ConnectionAttempt, Outcome},
+    limits::{ConnectionAttempt, Outcome, StatelessResetLimit},
     Limiter,
 };
 use s2n_quic_core::{event::Timestamp, path::THROTTLED_PORTS_LEN};
@@ -180,6 +180,7 @@ pub mod default {
     #[derive(Default)]
     pub struct Builder {
         max_inflight_handshake_limit: Option<usize>,
+        stateless_reset_limit: StatelessResetLimit,
     }
 
     impl Builder {
@@ -189,10 +190,24 @@ pub mod default {
             Ok(self)
         }
 
+        /// Sets the maximum number of stateless resets sent in each `period`
+        ///
+        /// Stateless resets are sent in response to packets for unknown connections, so this
+        /// limits the number of packets any peer can cause the endpoint to send.
+        pub fn with_stateless_reset_limit(
+            mut self,
+            max_resets: usize,
+            period: Duration,
+        ) -> Result<Self, Infallible> {
+            self.stateless_reset_limit = StatelessResetLimit::new(max_resets, period);
+            Ok(self)
+        }
+
         /// Build the limits
         pub fn build(self) -> Result<Limits, Infallible> {
             Ok(Limits {
                 max_inflight_handshake_limit: self.max_inflight_handshake_limit,
+                stateless_reset_limit: self.stateless_reset_limit,
                 rate_limiter: [BasicRateLimiter::default(); THROTTLED_PORTS_LEN],
             })
         }
@@ -202,6 +217,8 @@ pub mod default {
     pub struct Limits {
         /// Maximum number of handshakes to allow before Retry packets are queued
         max_inflight_handshake_limit: Option<usize>,
+        /// Maximum number of stateless resets sent in each period
+        stateless_reset_limit: StatelessResetLimit,
         rate_limiter: [BasicRateLimiter; THROTTLED_PORTS_LEN],
     }
 
@@ -235,6 +252,10 @@ pub mod default {
 
             Outcome::allow()
         }
+
+        fn stateless_reset_limit(&self) -> StatelessResetLimit {
+            self.stateless_reset_limit
+        }
     }
 
     /// Default limit values are as non-intrusive as possible
@@ -242,6 +263,7 @@ pub mod default {
         fn default() -> Self {
             Self {
                 max_inflight_handshake_limit: None,
+                stateless_reset_limit: StatelessResetLimit::default(),
                 rate_limiter: [BasicRateLimiter::default(); THROTTLED_PORTS_LEN],
             }
         }
@@ -255,6 +277,17 @@ pub mod default {
             .build()
             .unwrap();
         assert_eq!(elp.max_inflight_handshake_limit, Some(100));
+        assert_eq!(elp.stateless_reset_limit(), StatelessResetLimit::default());
+
+        let elp = Limits::builder()
+            .with_stateless_reset_limit(10, Duration::from_millis(100))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            elp.stateless_reset_limit(),
+            StatelessResetLimit::new(10, Duration::from_millis(100))
+        );
     }
 
     #[test]
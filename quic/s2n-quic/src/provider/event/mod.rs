@@ -29,6 +29,10 @@ pub mod disabled;
 #[cfg(any(feature = "provider-event-tracing", test))]
 pub mod tracing;
 
+/// This module contains event integration with [qlog](https://datatracker.ietf.org/doc/draft-ietf-quic-qlog-main-schema/)
+#[cfg(any(feature = "provider-event-qlog", test))]
+pub mod qlog;
+
 cfg_if! {
     if #[cfg(any(feature = "provider-event-tracing", test))] {
         pub use self::tracing as default;
@@ -0,0 +1,905 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Writes connection events as [qlog](https://datatracker.ietf.org/doc/draft-ietf-quic-qlog-main-schema/)
+//! traces
+//!
+//! Each connection is written to a separate `<group_id>_<client|server>.sqlog` file in the
+//! configured directory using the JSON Text Sequences (JSON-SEQ) serialization. The traces can be
+//! loaded directly into tools such as [qvis](https://qvis.quictools.info).
+//!
+//! The `group_id` combines a prefix which is unique to each started endpoint with the internal ID
+//! of the connection, so endpoints can share a directory without overwriting each other's traces,
+//! including across restarts. Existing files are never overwritten; if a file already exists the
+//! connection isn't traced.
+//!
+//! Events are formatted and written to a buffered file on the thread of the endpoint, which adds
+//! file IO to the processing of each connection. The provider is meant for debugging and
+//! interoperability testing rather than tracing every connection of a busy endpoint.
+//!
+//! ```rust,no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use s2n_quic::{provider::event::qlog, Server};
+//!
+//! let server = Server::builder()
+//!     .with_event(qlog::Provider::new("/tmp/qlog/server"))?
+//!     .with_io("127.0.0.1:443")?
+//!     .start()?;
+//! #
+//! #   Ok(())
+//! # }
+//! ```
+
+use crate::provider::event::{events, ConnectionInfo, ConnectionMeta, Timestamp};
+use core::fmt::{self, Write as _};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Write as _},
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The version of the qlog schema the traces conform to
+const QLOG_VERSION: &str = "0.3";
+
+/// The record separator which precedes each record in a JSON-SEQ file
+const RECORD_SEPARATOR: char = '\x1e';
+
+/// Distinguishes the endpoints which are started by the same process
+static STARTED_ENDPOINTS: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug)]
+pub struct Provider {
+    dir: PathBuf,
+}
+
+impl Provider {
+    /// Creates a provider which writes qlog traces to the given directory
+    ///
+    /// The directory is created when the endpoint is started if it does not already exist.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl super::Provider for Provider {
+    type Subscriber = Subscriber;
+    type Error = io::Error;
+
+    fn start(self) -> Result<Self::Subscriber, Self::Error> {
+        fs::create_dir_all(&self.dir)?;
+
+        // the start time and process ID distinguish restarts and other processes, while the
+        // counter distinguishes the endpoints in this process
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let prefix = format!(
+            "{started_at}-{}-{}",
+            std::process::id(),
+            STARTED_ENDPOINTS.fetch_add(1, Ordering::Relaxed)
+        );
+
+        Ok(Subscriber {
+            dir: self.dir,
+            prefix,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Subscriber {
+    dir: PathBuf,
+    /// Makes the group IDs of the connections unique across endpoints
+    prefix: String,
+}
+
+/// The qlog trace for a single connection
+pub struct ConnectionContext {
+    /// The trace file, or `None` if the file could not be written
+    out: Option<BufWriter<File>>,
+    /// The time the connection was created, to which all event times are relative
+    start: Timestamp,
+    /// Frames which were sent in the packet currently being assembled
+    sent_frames: String,
+    /// A received packet which is waiting for its frames to be processed
+    received_packet: Option<ReceivedPacket>,
+}
+
+struct ReceivedPacket {
+    time: Timestamp,
+    header: String,
+    frames: String,
+}
+
+impl fmt::Debug for ConnectionContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConnectionContext")
+            .field("is_enabled", &self.out.is_some())
+            .finish()
+    }
+}
+
+impl ConnectionContext {
+    fn new(mut out: Option<BufWriter<File>>, group_id: &str, meta: &ConnectionMeta) -> Self {
+        let vantage_point = match meta.endpoint_type {
+            events::EndpointType::Client { .. } => "client",
+            _ => "server",
+        };
+
+        // relate the connection-relative times in the trace to the wall clock
+        let reference_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64()
+            * 1000.0;
+
+        if let Some(writer) = out.as_mut() {
+            let header = format!(
+                "{RECORD_SEPARATOR}{{\"qlog_version\":\"{QLOG_VERSION}\",\"qlog_format\":\"JSON-SEQ\",\
+                \"title\":\"s2n-quic\",\"trace\":{{\"vantage_point\":{{\"name\":\"s2n-quic\",\
+                \"type\":\"{vantage_point}\"}},\"common_fields\":{{\"group_id\":\"{group_id}\",\
+                \"time_format\":\"relative\",\"reference_time\":{reference_time:.3}}}}}}}\n"
+            );
+
+            if writer.write_all(header.as_bytes()).is_err() {
+                out = None;
+            }
+        }
+
+        Self {
+            out,
+            start: meta.timestamp,
+            sent_frames: String::new(),
+            received_packet: None,
+        }
+    }
+
+    /// Writes an event to the trace
+    fn write(&mut self, time: Timestamp, name: &str, data: fmt::Arguments) {
+        // any frames for the previously received packet have been processed at this point
+        self.flush_received_packet();
+        self.write_record(time, name, data);
+    }
+
+    fn write_record(&mut self, time: Timestamp, name: &str, data: fmt::Arguments) {
+        let writer = if let Some(writer) = self.out.as_mut() {
+            writer
+        } else {
+            return;
+        };
+
+        let time = time.saturating_duration_since(self.start).as_secs_f64() * 1000.0;
+        let record = format!(
+            "{RECORD_SEPARATOR}{{\"time\":{time:.3},\"name\":\"{name}\",\"data\":{data}}}\n"
+        );
+
+        // stop tracing the connection if the file can no longer be written
+        if writer.write_all(record.as_bytes()).is_err() {
+            self.out = None;
+        }
+    }
+
+    fn flush_received_packet(&mut self) {
+        if let Some(packet) = self.received_packet.take() {
+            self.write_record(
+                packet.time,
+                "transport:packet_received",
+                format_args!(
+                    "{{\"header\":{},\"frames\":[{}]}}",
+                    packet.header, packet.frames
+                ),
+            );
+        }
+    }
+
+    fn on_key_event(
+        &mut self,
+        time: Timestamp,
+        name: &str,
+        key_type: &str,
+        generation: Option<u16>,
+    ) {
+        // s2n-quic installs and discards the keys for both directions at the same time
+        for owner in ["client", "server"] {
+            match generation {
+                Some(generation) => self.write(
+                    time,
+                    name,
+                    format_args!(
+                        "{{\"key_type\":\"{owner}_{key_type}_secret\",\"generation\":{generation}}}"
+                    ),
+                ),
+                None => self.write(
+                    time,
+                    name,
+                    format_args!("{{\"key_type\":\"{owner}_{key_type}_secret\"}}"),
+                ),
+            }
+        }
+    }
+}
+
+impl Drop for ConnectionContext {
+    fn drop(&mut self) {
+        self.flush_received_packet();
+
+        if let Some(writer) = self.out.as_mut() {
+            let _ = writer.flush();
+        }
+    }
+}
+
+impl super::Subscriber for Subscriber {
+    type ConnectionContext = ConnectionContext;
+
+    fn create_connection_context(
+        &mut self,
+        meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        let endpoint = match meta.endpoint_type {
+            events::EndpointType::Client { .. } => "client",
+            _ => "server",
+        };
+        let group_id = format!("{}-{}", self.prefix, meta.id);
+        let path = self.dir.join(format!("{group_id}_{endpoint}.sqlog"));
+
+        // tracing is best-effort and shouldn't prevent the connection from being created
+        let out = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .ok()
+            .map(BufWriter::new);
+
+        ConnectionContext::new(out, &group_id, meta)
+    }
+
+    fn on_application_protocol_information(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::ApplicationProtocolInformation,
+    ) {
+        let alpn = String::from_utf8_lossy(event.chosen_application_protocol);
+        context.write(
+            meta.timestamp,
+            "transport:alpn_information",
+            format_args!("{{\"chosen_alpn\":{{\"string_value\":{}}}}}", Str(&alpn)),
+        );
+    }
+
+    fn on_packet_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::PacketSent,
+    ) {
+        let frames = core::mem::take(&mut context.sent_frames);
+        context.write(
+            meta.timestamp,
+            "transport:packet_sent",
+            format_args!(
+                "{{\"header\":{},\"raw\":{{\"length\":{}}},\"frames\":[{frames}]}}",
+                Header(&event.packet_header),
+                event.packet_len
+            ),
+        );
+    }
+
+    fn on_packet_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::PacketReceived,
+    ) {
+        context.flush_received_packet();
+        // the packet is written once its frames have been processed
+        context.received_packet = Some(ReceivedPacket {
+            time: meta.timestamp,
+            header: Header(&event.packet_header).to_string(),
+            frames: String::new(),
+        });
+    }
+
+    fn on_frame_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::FrameSent,
+    ) {
+        let frames = &mut context.sent_frames;
+        if !frames.is_empty() {
+            frames.push(',');
+        }
+        let _ = write!(frames, "{}", QlogFrame(&event.frame));
+    }
+
+    fn on_frame_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::FrameReceived,
+    ) {
+        if let Some(packet) = context.received_packet.as_mut() {
+            if !packet.frames.is_empty() {
+                packet.frames.push(',');
+            }
+            let _ = write!(packet.frames, "{}", QlogFrame(&event.frame));
+        }
+    }
+
+    fn on_packet_lost(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::PacketLost,
+    ) {
+        context.write(
+            meta.timestamp,
+            "recovery:packet_lost",
+            format_args!(
+                "{{\"header\":{},\"is_mtu_probe\":{}}}",
+                Header(&event.packet_header),
+                event.is_mtu_probe
+            ),
+        );
+    }
+
+    fn on_recovery_metrics(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::RecoveryMetrics,
+    ) {
+        context.write(
+            meta.timestamp,
+            "recovery:metrics_updated",
+            format_args!(
+                "{{\"min_rtt\":{:.3},\"smoothed_rtt\":{:.3},\"latest_rtt\":{:.3},\
+                \"rtt_variance\":{:.3},\"pto_count\":{},\"congestion_window\":{},\
+                \"bytes_in_flight\":{}}}",
+                event.min_rtt.as_secs_f64() * 1000.0,
+                event.smoothed_rtt.as_secs_f64() * 1000.0,
+                event.latest_rtt.as_secs_f64() * 1000.0,
+                event.rtt_variance.as_secs_f64() * 1000.0,
+                event.pto_count,
+                event.congestion_window,
+                event.bytes_in_flight
+            ),
+        );
+    }
+
+    fn on_congestion(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::Congestion,
+    ) {
+        let data = match event.source {
+            events::CongestionSource::Ecn { .. } => "{\"new\":\"recovery\",\"trigger\":\"ECN\"}",
+            _ => "{\"new\":\"recovery\"}",
+        };
+        context.write(
+            meta.timestamp,
+            "recovery:congestion_state_updated",
+            format_args!("{data}"),
+        );
+    }
+
+    fn on_packet_dropped(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::PacketDropped,
+    ) {
+        // use the name of the reason variant as the details
+        let reason = format!("{:?}", event.reason);
+        let reason = reason.split(' ').next().unwrap_or_default();
+        context.write(
+            meta.timestamp,
+            "transport:packet_dropped",
+            format_args!("{{\"trigger\":\"general\",\"details\":{}}}", Str(reason)),
+        );
+    }
+
+    fn on_key_update(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::KeyUpdate,
+    ) {
+        let (key_type, generation) = match event.key_type {
+            events::KeyType::Initial { .. } => ("initial", None),
+            events::KeyType::Handshake { .. } => ("handshake", None),
+            events::KeyType::ZeroRtt { .. } => ("0rtt", None),
+            events::KeyType::OneRtt { generation, .. } => ("1rtt", Some(generation)),
+            _ => return,
+        };
+        context.on_key_event(meta.timestamp, "security:key_updated", key_type, generation);
+    }
+
+    fn on_key_space_discarded(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::KeySpaceDiscarded,
+    ) {
+        let key_type = match event.space {
+            events::KeySpace::Initial { .. } => "initial",
+            events::KeySpace::Handshake { .. } => "handshake",
+            events::KeySpace::ZeroRtt { .. } => "0rtt",
+            events::KeySpace::OneRtt { .. } => "1rtt",
+            _ => return,
+        };
+        context.on_key_event(meta.timestamp, "security:key_discarded", key_type, None);
+    }
+
+    fn on_connection_started(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::ConnectionStarted,
+    ) {
+        let local = std::net::SocketAddr::from(&event.path.local_addr);
+        let remote = std::net::SocketAddr::from(&event.path.remote_addr);
+        let ip_version = if remote.is_ipv4() { "ipv4" } else { "ipv6" };
+        context.write(
+            meta.timestamp,
+            "connectivity:connection_started",
+            format_args!(
+                "{{\"ip_version\":\"{ip_version}\",\"src_ip\":\"{}\",\"dst_ip\":\"{}\",\
+                \"src_port\":{},\"dst_port\":{},\"src_cid\":\"{}\",\"dst_cid\":\"{}\"}}",
+                local.ip(),
+                remote.ip(),
+                local.port(),
+                remote.port(),
+                Hex(event.path.local_cid.bytes),
+                Hex(event.path.remote_cid.bytes)
+            ),
+        );
+    }
+
+    fn on_connection_closed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::ConnectionClosed,
+    ) {
+        let reason = event.error.to_string();
+        context.write(
+            meta.timestamp,
+            "connectivity:connection_closed",
+            format_args!("{{\"reason\":{}}}", Str(&reason)),
+        );
+    }
+
+    fn on_transport_parameters_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::TransportParametersReceived,
+    ) {
+        let params = &event.transport_parameters;
+        context.write(
+            meta.timestamp,
+            "transport:parameters_set",
+            format_args!(
+                "{{\"owner\":\"remote\",\"max_idle_timeout\":{},\"max_udp_payload_size\":{},\
+                \"ack_delay_exponent\":{},\"max_ack_delay\":{},\"active_connection_id_limit\":{},\
+                \"initial_max_stream_data_bidi_local\":{},\
+                \"initial_max_stream_data_bidi_remote\":{},\"initial_max_stream_data_uni\":{},\
+                \"initial_max_streams_bidi\":{},\"initial_max_streams_uni\":{},\
+                \"disable_active_migration\":{},\"max_datagram_frame_size\":{}}}",
+                params.max_idle_timeout.as_millis(),
+                params.max_udp_payload_size,
+                params.ack_delay_exponent,
+                params.max_ack_delay.as_millis(),
+                params.active_connection_id_limit,
+                params.initial_max_stream_data_bidi_local,
+                params.initial_max_stream_data_bidi_remote,
+                params.initial_max_stream_data_uni,
+                params.initial_max_streams_bidi,
+                params.initial_max_streams_uni,
+                !params.migration_support,
+                params.max_datagram_frame_size
+            ),
+        );
+    }
+
+    fn on_datagram_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::DatagramSent,
+    ) {
+        context.write(
+            meta.timestamp,
+            "transport:datagrams_sent",
+            format_args!("{{\"count\":1,\"raw\":[{{\"length\":{}}}]}}", event.len),
+        );
+    }
+
+    fn on_datagram_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::DatagramReceived,
+    ) {
+        context.write(
+            meta.timestamp,
+            "transport:datagrams_received",
+            format_args!("{{\"count\":1,\"raw\":[{{\"length\":{}}}]}}", event.len),
+        );
+    }
+
+    fn on_datagram_dropped(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::DatagramDropped,
+    ) {
+        context.write(
+            meta.timestamp,
+            "transport:datagram_dropped",
+            format_args!("{{\"raw\":{{\"length\":{}}}}}", event.len),
+        );
+    }
+
+    fn on_connection_id_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::ConnectionIdUpdated,
+    ) {
+        let owner = match event.cid_consumer {
+            crate::provider::event::Location::Local => "local",
+            crate::provider::event::Location::Remote => "remote",
+        };
+        context.write(
+            meta.timestamp,
+            "connectivity:connection_id_updated",
+            format_args!(
+                "{{\"owner\":\"{owner}\",\"old\":\"{}\",\"new\":\"{}\"}}",
+                Hex(event.previous.bytes),
+                Hex(event.current.bytes)
+            ),
+        );
+    }
+
+    fn on_mtu_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::MtuUpdated,
+    ) {
+        let done = matches!(
+            event.cause,
+            events::MtuUpdatedCause::ProbeAcknowledged { .. }
+        );
+        context.write(
+            meta.timestamp,
+            "connectivity:mtu_updated",
+            format_args!("{{\"new\":{},\"done\":{done}}}", event.mtu),
+        );
+    }
+}
+
+/// Formats a packet header as a qlog `PacketHeader`
+struct Header<'a>(&'a events::PacketHeader);
+
+impl fmt::Display for Header<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use events::PacketHeader::*;
+
+        let (packet_type, number) = match self.0 {
+            Initial { number, .. } => ("initial", Some(number)),
+            Handshake { number, .. } => ("handshake", Some(number)),
+            ZeroRtt { number, .. } => ("0RTT", Some(number)),
+            OneRtt { number, .. } => ("1RTT", Some(number)),
+            Retry { .. } => ("retry", None),
+            VersionNegotiation { .. } => ("version_negotiation", None),
+            StatelessReset { .. } => ("stateless_reset", None),
+            _ => ("unknown", None),
+        };
+
+        if let Some(number) = number {
+            write!(
+                f,
+                "{{\"packet_type\":\"{packet_type}\",\"packet_number\":{number}}}"
+            )
+        } else {
+            write!(f, "{{\"packet_type\":\"{packet_type}\"}}")
+        }
+    }
+}
+
+/// Formats a frame as a qlog `QuicFrame`
+struct QlogFrame<'a>(&'a events::Frame);
+
+impl fmt::Display for QlogFrame<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use events::Frame::*;
+
+        let stream_type = |stream_type: &events::StreamType| match stream_type {
+            events::StreamType::Unidirectional { .. } => "unidirectional",
+            _ => "bidirectional",
+        };
+
+        match self.0 {
+            Padding { .. } => write!(f, "{{\"frame_type\":\"padding\"}}"),
+            Ping { .. } => write!(f, "{{\"frame_type\":\"ping\"}}"),
+            Ack {
+                ecn_counts,
+                largest_acknowledged,
+                ..
+            } => {
+                write!(
+                    f,
+                    "{{\"frame_type\":\"ack\",\"largest_acknowledged\":{largest_acknowledged}"
+                )?;
+                if let Some(ecn) = ecn_counts {
+                    write!(
+                        f,
+                        ",\"ect0\":{},\"ect1\":{},\"ce\":{}",
+                        ecn.ect_0_count, ecn.ect_1_count, ecn.ce_count
+                    )?;
+                }
+                write!(f, "}}")
+            }
+            ResetStream {
+                id,
+                error_code,
+                final_size,
+                ..
+            } => write!(
+                f,
+                "{{\"frame_type\":\"reset_stream\",\"stream_id\":{id},\"error_code\":{error_code},\"final_size\":{final_size}}}"
+            ),
+            StopSending { id, error_code, .. } => write!(
+                f,
+                "{{\"frame_type\":\"stop_sending\",\"stream_id\":{id},\"error_code\":{error_code}}}"
+            ),
+            Crypto { offset, len, .. } => write!(
+                f,
+                "{{\"frame_type\":\"crypto\",\"offset\":{offset},\"length\":{len}}}"
+            ),
+            NewToken { .. } => write!(f, "{{\"frame_type\":\"new_token\"}}"),
+            Stream {
+                id,
+                offset,
+                len,
+                is_fin,
+                ..
+            } => write!(
+                f,
+                "{{\"frame_type\":\"stream\",\"stream_id\":{id},\"offset\":{offset},\"length\":{len},\"fin\":{is_fin}}}"
+            ),
+            MaxData { value, .. } => {
+                write!(f, "{{\"frame_type\":\"max_data\",\"maximum\":{value}}}")
+            }
+            MaxStreamData { id, value, .. } => write!(
+                f,
+                "{{\"frame_type\":\"max_stream_data\",\"stream_id\":{id},\"maximum\":{value}}}"
+            ),
+            MaxStreams {
+                stream_type: ty,
+                value,
+                ..
+            } => write!(
+                f,
+                "{{\"frame_type\":\"max_streams\",\"stream_type\":\"{}\",\"maximum\":{value}}}",
+                stream_type(ty)
+            ),
+            DataBlocked { data_limit, .. } => write!(
+                f,
+                "{{\"frame_type\":\"data_blocked\",\"limit\":{data_limit}}}"
+            ),
+            StreamDataBlocked {
+                stream_id,
+                stream_data_limit,
+                ..
+            } => write!(
+                f,
+                "{{\"frame_type\":\"stream_data_blocked\",\"stream_id\":{stream_id},\"limit\":{stream_data_limit}}}"
+            ),
+            StreamsBlocked {
+                stream_type: ty,
+                stream_limit,
+                ..
+            } => write!(
+                f,
+                "{{\"frame_type\":\"streams_blocked\",\"stream_type\":\"{}\",\"limit\":{stream_limit}}}",
+                stream_type(ty)
+            ),
+            NewConnectionId { .. } => write!(f, "{{\"frame_type\":\"new_connection_id\"}}"),
+            RetireConnectionId { .. } => {
+                write!(f, "{{\"frame_type\":\"retire_connection_id\"}}")
+            }
+            PathChallenge { .. } => write!(f, "{{\"frame_type\":\"path_challenge\"}}"),
+            PathResponse { .. } => write!(f, "{{\"frame_type\":\"path_response\"}}"),
+            ConnectionClose { .. } => write!(f, "{{\"frame_type\":\"connection_close\"}}"),
+            HandshakeDone { .. } => write!(f, "{{\"frame_type\":\"handshake_done\"}}"),
+            Datagram { len, .. } => {
+                write!(f, "{{\"frame_type\":\"datagram\",\"length\":{len}}}")
+            }
+            AckFrequency {
+                sequence_number,
+                ack_eliciting_threshold,
+                request_max_ack_delay,
+                reordering_threshold,
+                ..
+            } => write!(
+                f,
+                "{{\"frame_type\":\"ack_frequency\",\"sequence_number\":{sequence_number},\"ack_eliciting_threshold\":{ack_eliciting_threshold},\"request_max_ack_delay\":{request_max_ack_delay},\"reordering_threshold\":{reordering_threshold}}}"
+            ),
+            ImmediateAck { .. } => write!(f, "{{\"frame_type\":\"immediate_ack\"}}"),
+            AckMp {
+                path_identifier,
+                ecn_counts,
+                largest_acknowledged,
+                ..
+            } => {
+                write!(
+                    f,
+                    "{{\"frame_type\":\"ack_mp\",\"path_identifier\":{path_identifier},\"largest_acknowledged\":{largest_acknowledged}"
+                )?;
+                if let Some(ecn) = ecn_counts {
+                    write!(
+                        f,
+                        ",\"ect0\":{},\"ect1\":{},\"ce\":{}",
+                        ecn.ect_0_count, ecn.ect_1_count, ecn.ce_count
+                    )?;
+                }
+                write!(f, "}}")
+            }
+            PathAbandon {
+                path_identifier,
+                error_code,
+                ..
+            } => write!(
+                f,
+                "{{\"frame_type\":\"path_abandon\",\"path_identifier\":{path_identifier},\"error_code\":{error_code}}}"
+            ),
+            _ => write!(f, "{{\"frame_type\":\"unknown\"}}"),
+        }
+    }
+}
+
+/// Formats bytes as a lowercase hex string
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats a value as a quoted and escaped JSON string
+struct Str<'a>(&'a str);
+
+impl fmt::Display for Str<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_char('"')?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+                c => f.write_char(c)?,
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::event::{Provider as _, Subscriber as _};
+    use s2n_quic_core::{
+        endpoint,
+        event::{builder, IntoEvent},
+        time::{testing, Duration},
+    };
+
+    #[test]
+    fn escape_test() {
+        assert_eq!(Str("abc").to_string(), "\"abc\"");
+        assert_eq!(Str("a\"b\\c\n").to_string(), "\"a\\\"b\\\\c\\n\"");
+        assert_eq!(Str("\u{1}").to_string(), "\"\\u0001\"");
+        assert_eq!(Hex(&[0x01, 0xab]).to_string(), "01ab");
+    }
+
+    #[test]
+    fn trace_test() {
+        let dir = std::env::temp_dir().join(format!("s2n-quic-qlog-{}", std::process::id()));
+        let mut subscriber = Provider::new(&dir).start().unwrap();
+
+        let now = testing::now();
+        let meta = |time: Duration| -> ConnectionMeta {
+            builder::ConnectionMeta {
+                endpoint_type: endpoint::Type::Server,
+                id: 123,
+                timestamp: now + time,
+            }
+            .into_event()
+        };
+        let info: ConnectionInfo = builder::ConnectionInfo {}.into_event();
+
+        let mut context = subscriber.create_connection_context(&meta(Duration::ZERO), &info);
+
+        let header = builder::PacketHeader::OneRtt { number: 4 };
+        let frame = builder::Frame::Ping;
+
+        subscriber.on_packet_received(
+            &mut context,
+            &meta(Duration::from_millis(1)),
+            &builder::PacketReceived {
+                packet_header: header.clone(),
+            }
+            .into_event(),
+        );
+
+        subscriber.on_frame_sent(
+            &mut context,
+            &meta(Duration::from_millis(2)),
+            &builder::FrameSent {
+                packet_header: header.clone(),
+                path_id: 0,
+                frame: frame.clone(),
+            }
+            .into_event(),
+        );
+
+        subscriber.on_packet_sent(
+            &mut context,
+            &meta(Duration::from_millis(2)),
+            &builder::PacketSent {
+                packet_header: header,
+                packet_len: 100,
+            }
+            .into_event(),
+        );
+
+        drop(context);
+
+        let group_id = format!("{}-123", subscriber.prefix);
+        let trace = fs::read_to_string(dir.join(format!("{group_id}_server.sqlog"))).unwrap();
+        let records: Vec<_> = trace.split(RECORD_SEPARATOR).skip(1).collect();
+
+        assert_eq!(records.len(), 3);
+        assert!(records[0].contains("\"qlog_format\":\"JSON-SEQ\""));
+        assert!(records[0].contains(&format!("\"group_id\":\"{group_id}\"")));
+        assert!(records[0].contains("\"type\":\"server\""));
+        assert_eq!(
+            records[1],
+            "{\"time\":1.000,\"name\":\"transport:packet_received\",\"data\":{\"header\":{\"packet_type\":\"1RTT\",\"packet_number\":4},\"frames\":[]}}\n"
+        );
+        assert_eq!(
+            records[2],
+            "{\"time\":2.000,\"name\":\"transport:packet_sent\",\"data\":{\"header\":{\"packet_type\":\"1RTT\",\"packet_number\":4},\"raw\":{\"length\":100},\"frames\":[{\"frame_type\":\"ping\"}]}}\n"
+        );
+
+        // another endpoint writing to the same directory doesn't overwrite the trace
+        let mut other = Provider::new(&dir).start().unwrap();
+        assert_ne!(other.prefix, subscriber.prefix);
+        drop(other.create_connection_context(&meta(Duration::ZERO), &info));
+        assert_eq!(
+            fs::read_to_string(dir.join(format!("{group_id}_server.sqlog"))).unwrap(),
+            trace
+        );
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
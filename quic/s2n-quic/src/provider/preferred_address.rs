@@ -0,0 +1,27 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allows servers to advertise a preferred address to clients after the handshake
+
+pub use s2n_quic_core::path::preferred_address::{
+    Addresses, ConnectionInfo, Disabled, Disabled as Default, Endpoint,
+};
+
+/// Provides preferred address support for an endpoint
+pub trait Provider {
+    type Endpoint: 'static + Send + Endpoint;
+    type Error: 'static + core::fmt::Display;
+
+    fn start(self) -> Result<Self::Endpoint, Self::Error>;
+}
+
+impl_provider_utils!();
+
+impl<T: 'static + Send + Endpoint> Provider for T {
+    type Endpoint = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Endpoint, Self::Error> {
+        Ok(self)
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides TLS session resumption with session tickets
+//!
+//! Clients remember tickets in a [`Store`], which is passed to the TLS provider builder. Servers
+//! configure the TLS provider with a [`KeyRotation`] policy for the keys used to encrypt tickets,
+//! and optionally a [`KeySource`] which shares the keys between servers.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use s2n_quic::provider::{session_ticket, tls};
+//! use std::sync::Arc;
+//!
+//! let store = Arc::new(session_ticket::Cache::default());
+//! let tls = tls::default::Client::builder()
+//!     .with_certificate(CERT_PEM)?
+//!     .with_session_ticket_store(store)?
+//!     .build()?;
+//! ```
+
+pub use s2n_quic_core::crypto::tls::session_ticket::{
+    Key, KeyError, KeyRotation, KeySource, Store, Ticket,
+};
+
+use bytes::Bytes;
+use s2n_quic_core::application::ServerName;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::Instant,
+};
+
+/// An in-memory [`Store`] which holds a limited number of tickets for each server
+#[derive(Debug)]
+pub struct Cache {
+    max_servers: usize,
+    max_tickets_per_server: usize,
+    entries: Mutex<HashMap<Bytes, VecDeque<Entry>>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    expires_at: Instant,
+    ticket: Ticket,
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Self::new(256, 4)
+    }
+}
+
+impl Cache {
+    /// Creates a cache holding up to `max_tickets_per_server` tickets for each of
+    /// `max_servers` servers
+    pub fn new(max_servers: usize, max_tickets_per_server: usize) -> Self {
+        Self {
+            max_servers,
+            max_tickets_per_server,
+            entries: Default::default(),
+        }
+    }
+}
+
+impl Store for Cache {
+    fn on_ticket(&self, server_name: &ServerName, ticket: Ticket) {
+        if self.max_servers == 0 || self.max_tickets_per_server == 0 {
+            return;
+        }
+
+        let now = Instant::now();
+        let key = server_name.clone().into_bytes();
+        let mut entries = self.entries.lock().unwrap();
+
+        if !entries.contains_key(&key) && entries.len() >= self.max_servers {
+            entries.retain(|_, tickets| {
+                tickets.retain(|entry| entry.expires_at > now);
+                !tickets.is_empty()
+            });
+
+            // evict the server whose tickets expire first
+            if entries.len() >= self.max_servers {
+                if let Some(oldest) = entries
+                    .iter()
+                    .min_by_key(|(_, tickets)| tickets.back().map(|entry| entry.expires_at))
+                    .map(|(key, _)| key.clone())
+                {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+
+        let tickets = entries.entry(key).or_default();
+        if tickets.len() >= self.max_tickets_per_server {
+            tickets.pop_front();
+        }
+        tickets.push_back(Entry {
+            expires_at: now + ticket.lifetime,
+            ticket,
+        });
+    }
+
+    fn take(&self, server_name: &ServerName) -> Option<Ticket> {
+        let now = Instant::now();
+        let key = server_name.clone().into_bytes();
+        let mut entries = self.entries.lock().unwrap();
+        let tickets = entries.get_mut(&key)?;
+
+        // prefer the most recently issued ticket
+        let mut ticket = None;
+        while let Some(entry) = tickets.pop_back() {
+            if entry.expires_at > now {
+                ticket = Some(entry.ticket);
+                break;
+            }
+        }
+
+        if tickets.is_empty() {
+            entries.remove(&key);
+        }
+
+        ticket
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+
+    fn ticket(id: u8, lifetime: Duration) -> Ticket {
+        Ticket::new(Bytes::from(vec![id]), None, lifetime)
+    }
+
+    #[test]
+    fn cache_test() {
+        let cache = Cache::new(1, 2);
+        let lifetime = Duration::from_secs(60);
+        let server_name: ServerName = "example.com".into();
+
+        for id in 0..3 {
+            cache.on_ticket(&server_name, ticket(id, lifetime));
+        }
+
+        // tickets are single use and the newest is returned first
+        assert_eq!(cache.take(&server_name).unwrap().session[..], [2]);
+        assert_eq!(cache.take(&server_name).unwrap().session[..], [1]);
+        assert!(cache.take(&server_name).is_none());
+
+        // storing tickets for another server evicts the first one
+        cache.on_ticket(&server_name, ticket(3, lifetime));
+        let other: ServerName = "example.org".into();
+        cache.on_ticket(&other, ticket(4, lifetime));
+        assert!(cache.take(&server_name).is_none());
+        assert_eq!(cache.take(&other).unwrap().session[..], [4]);
+
+        // expired tickets are not returned
+        cache.on_ticket(&server_name, ticket(5, Duration::ZERO));
+        assert!(cache.take(&server_name).is_none());
+    }
+}
@@ -2,6 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! Provides stateless reset token support for an endpoint
+//!
+//! By default, tokens are derived from the connection ID with a randomly generated key. See the
+//! [`default`] module for configuring a key which is shared across endpoints.
 
 pub use s2n_quic_core::stateless_reset::token::Generator;
 
@@ -12,11 +15,15 @@ pub trait Provider: 'static {
     fn start(self) -> Result<Self::Generator, Self::Error>;
 }
 
-pub use random::Provider as Default;
+pub mod default;
+
+pub use default::Provider as Default;
 
 impl_provider_utils!();
 
-mod random {
+pub mod random {
+    //! Provides randomly generated stateless reset tokens, which disables stateless reset
+
     use core::convert::Infallible;
     use rand::prelude::*;
     use s2n_quic_core::{frame::new_connection_id::STATELESS_RESET_TOKEN_LEN, stateless_reset};
@@ -0,0 +1,160 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Default provider for stateless reset tokens
+//!
+//! Tokens are derived from the local connection ID with a static key, which allows an endpoint
+//! that has lost the state for a connection to reset it. Unless a key is provided, the default
+//! provider will randomly generate a 256 bit key when the endpoint is started. Endpoints that
+//! should be able to reset each other's connections, such as the hosts in a fleet or across
+//! restarts of a process, should be configured with the same key.
+
+use core::{convert::Infallible, fmt};
+use rand::prelude::*;
+use s2n_quic_core::{frame::new_connection_id::STATELESS_RESET_TOKEN_LEN, stateless_reset};
+use s2n_quic_crypto::hmac;
+use zeroize::Zeroizing;
+
+/// The minimum length of a configured key, in bytes
+pub const MIN_KEY_LEN: usize = 16;
+
+const RANDOM_KEY_LEN: usize = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The key is shorter than [`MIN_KEY_LEN`]
+    InvalidKeyLength,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidKeyLength => write!(
+                f,
+                "The stateless reset key must be at least {MIN_KEY_LEN} bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Default)]
+pub struct Provider(Option<Generator>);
+
+impl Provider {
+    /// Creates a provider which derives tokens with the provided `key`
+    ///
+    /// The key should be kept secret, since anyone holding it is able to reset connections
+    /// issued by the endpoint.
+    pub fn new(key: &[u8]) -> Result<Self, Error> {
+        Ok(Self(Some(Generator::new(key)?)))
+    }
+}
+
+impl super::Provider for Provider {
+    type Generator = Generator;
+    type Error = Infallible;
+
+    fn start(self) -> Result<Self::Generator, Self::Error> {
+        Ok(self.0.unwrap_or_else(Generator::random))
+    }
+}
+
+impl super::TryInto for Generator {
+    type Provider = Provider;
+    type Error = Infallible;
+
+    fn try_into(self) -> Result<Self::Provider, Self::Error> {
+        Ok(Provider(Some(self)))
+    }
+}
+
+/// Derives stateless reset tokens from the local connection ID with a static key
+#[derive(Clone)]
+pub struct Generator {
+    key: hmac::Key,
+}
+
+impl fmt::Debug for Generator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // don't include the key in the output
+        f.debug_struct("Generator").finish_non_exhaustive()
+    }
+}
+
+impl Generator {
+    /// Creates a generator which derives tokens with the provided `key`
+    pub fn new(key: &[u8]) -> Result<Self, Error> {
+        if key.len() < MIN_KEY_LEN {
+            return Err(Error::InvalidKeyLength);
+        }
+
+        Ok(Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, key),
+        })
+    }
+
+    /// Creates a generator with a randomly generated key
+    pub fn random() -> Self {
+        let mut key = Zeroizing::new([0u8; RANDOM_KEY_LEN]);
+        rand::thread_rng().fill_bytes(&mut key[..]);
+        Self::new(&key[..]).expect("key length is valid")
+    }
+}
+
+impl stateless_reset::token::Generator for Generator {
+    /// Tokens are the same for a given connection ID as long as the key doesn't change, so
+    /// stateless resets sent by this generator are recognized by the peer.
+    const ENABLED: bool = true;
+
+    fn generate(&mut self, local_connection_id: &[u8]) -> stateless_reset::Token {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-10.3.2
+        //# An endpoint could use HMAC [RFC2104] (for
+        //# example, HMAC(static_key, connection_id)) or the HMAC-based Key
+        //# Derivation Function (HKDF) [RFC5869] (for example, using the static
+        //# key as input keying material, with the connection ID as salt).  The
+        //# output of this function is truncated to 16 bytes to produce the
+        //# stateless reset token for that connection.
+        let tag = hmac::sign(&self.key, local_connection_id);
+        let mut token = [0u8; STATELESS_RESET_TOKEN_LEN];
+        token.copy_from_slice(&tag.as_ref()[..STATELESS_RESET_TOKEN_LEN]);
+        token.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{connection, stateless_reset::token::Generator as _};
+
+    #[test]
+    fn stateless_reset_token_test() {
+        let id_1 = connection::LocalId::try_from_bytes(b"id01").unwrap();
+        let id_2 = connection::LocalId::try_from_bytes(b"id02").unwrap();
+
+        let mut generator = Generator::new(&[1; 32]).unwrap();
+        let token_1 = generator.generate(id_1.as_bytes());
+
+        // tokens are deterministic for a given key and connection ID
+        assert_eq!(token_1, generator.generate(id_1.as_bytes()));
+        let mut fleet_generator = Generator::new(&[1; 32]).unwrap();
+        assert_eq!(token_1, fleet_generator.generate(id_1.as_bytes()));
+
+        // tokens differ across connection IDs and keys
+        assert_ne!(token_1, generator.generate(id_2.as_bytes()));
+        let mut other_generator = Generator::new(&[2; 32]).unwrap();
+        assert_ne!(token_1, other_generator.generate(id_1.as_bytes()));
+        assert_ne!(token_1, Generator::random().generate(id_1.as_bytes()));
+    }
+
+    #[test]
+    fn key_length_test() {
+        assert_eq!(
+            Generator::new(&[0; MIN_KEY_LEN - 1]).unwrap_err(),
+            Error::InvalidKeyLength
+        );
+        assert!(Provider::new(&[0; MIN_KEY_LEN]).is_ok());
+    }
+}
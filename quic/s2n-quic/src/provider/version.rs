@@ -0,0 +1,38 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides the set of QUIC versions an endpoint is willing to use
+
+pub use s2n_quic_core::version::{Preferences, VERSION_1, VERSION_2};
+
+pub trait Provider {
+    type Error: 'static + core::fmt::Display;
+
+    fn start(self) -> Result<Preferences, Self::Error>;
+}
+
+pub use default::Provider as Default;
+
+impl_provider_utils!();
+
+impl Provider for Preferences {
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Preferences, Self::Error> {
+        Ok(self)
+    }
+}
+
+pub mod default {
+    /// Only QUIC version 1 is enabled by default
+    #[derive(Debug, Default)]
+    pub struct Provider(());
+
+    impl super::Provider for Provider {
+        type Error = core::convert::Infallible;
+
+        fn start(self) -> Result<super::Preferences, Self::Error> {
+            Ok(super::Preferences::default())
+        }
+    }
+}
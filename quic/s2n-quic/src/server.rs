@@ -1,24 +1,37 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{connection::Connection, provider::*};
+use crate::{
+    application,
+    connection::{self, Connection},
+    provider::*,
+};
 use core::{
     fmt,
     task::{Context, Poll},
+    time::Duration,
 };
-use s2n_quic_transport::endpoint::handle::Acceptor;
+use s2n_quic_transport::endpoint::{close::Shutdown, handle::Acceptor};
 
 mod builder;
 mod providers;
+mod shard;
 
 pub use builder::*;
 pub use providers::*;
 pub use s2n_quic_core::application::ServerName as Name;
+pub use shard::Shard;
 
 /// A QUIC server endpoint, capable of accepting connections
 pub struct Server {
-    acceptor: Acceptor,
+    /// The acceptors of each endpoint, which is more than one for sharded servers
+    acceptors: Vec<Acceptor>,
+    /// The acceptor which is polled first, so shards are given the same priority
+    next_acceptor: usize,
     local_addr: s2n_quic_core::inet::SocketAddress,
+    /// Handles to the sockets of a sharded server, which keep the sockets in the `SO_REUSEPORT`
+    /// group until the server is dropped
+    sockets: Vec<std::net::UdpSocket>,
 }
 
 impl fmt::Debug for Server {
@@ -51,6 +64,106 @@ impl Server {
         Builder::default()
     }
 
+    /// Starts a [`Server`] made up of `count` endpoints, each running its own event loop
+    ///
+    /// `start` is called with each [`Shard`] in order and should start a server using the
+    /// providers returned by the shard, in addition to any of its own. The endpoints share a port
+    /// with `SO_REUSEPORT` and each connection ID encodes the index of the endpoint which issued
+    /// it, which the kernel uses to deliver packets to the endpoint which owns the connection.
+    /// Connections accepted by any endpoint are returned by [`Server::accept`].
+    ///
+    /// The endpoints are spawned on the current Tokio runtime, which should be multi-threaded
+    /// to make use of more than one CPU core. Steering packets to the shards is currently only
+    /// supported on Linux.
+    ///
+    /// Since any endpoint may receive packets for connections it doesn't own, the endpoints
+    /// should be configured with the stateless reset token generator returned by the shard, which
+    /// derives each token from the connection ID with a key shared by all of the endpoints.
+    ///
+    /// The address token format returned by the shard is shared by all of the endpoints, so
+    /// tokens are only accepted once across the server. Token operations are serialized by a
+    /// single lock, which the endpoints contend for when sending many Retry packets; see
+    /// [`address_token::Shared`](crate::provider::address_token::Shared).
+    ///
+    /// The kernel steers packets by the index of each socket in the `SO_REUSEPORT` group, which
+    /// changes when a socket in the group is closed. To keep the indices stable, the sockets bound
+    /// by [`Shard::io`] are held open by the returned server until it is dropped, even if some of
+    /// the endpoints shut down before it. Binding other sockets to the port of the server while it
+    /// is running will also change the indices and should be avoided.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path};
+    /// # use s2n_quic::Server;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut server = Server::sharded(4, |shard| {
+    ///     let server = Server::builder()
+    ///         .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///         .with_io(shard.io("127.0.0.1:443".parse()?)?)?
+    ///         .with_connection_id(shard.connection_id())?
+    ///         .with_address_token(shard.address_token())?
+    ///         .with_stateless_reset_token(shard.stateless_reset_token())?
+    ///         .start()?;
+    ///     Ok::<_, Box<dyn Error>>(server)
+    /// })?;
+    ///
+    /// while let Some(connection) = server.accept().await {
+    ///     // spawn a task for the connection
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn sharded<F, E>(count: u8, mut start: F) -> Result<Self, E>
+    where
+        F: FnMut(Shard) -> Result<Self, E>,
+        E: From<StartError>,
+    {
+        use crate::provider::{
+            address_token::{self, Provider as _},
+            stateless_reset_token,
+        };
+
+        if count == 0 {
+            return Err(StartError::new("a server requires at least one shard").into());
+        }
+
+        let format = address_token::Default::default()
+            .start()
+            .map_err(StartError::new)?;
+        let mut shard = Shard {
+            index: 0,
+            count,
+            local_addr: None,
+            address_token: address_token::Shared::new(format),
+            stateless_reset_token: stateless_reset_token::default::Generator::random(),
+            sockets: Default::default(),
+        };
+
+        // the shards must be started in order, since the kernel indexes the sockets in the
+        // order they were bound
+        let mut server = start(shard.clone())?;
+        shard.local_addr = Some(server.local_addr.into());
+
+        for index in 1..count {
+            shard.index = index;
+            let shard_server = start(shard.clone())?;
+            server.acceptors.extend(shard_server.acceptors);
+        }
+
+        server.sockets.append(
+            &mut shard
+                .sockets
+                .lock()
+                .expect("sockets lock should not be poisoned"),
+        );
+
+        Ok(server)
+    }
+
     /// Accepts a new incoming [`Connection`] from this [`Server`].
     ///
     /// This function will yield once a new QUIC connection is established. When established,
@@ -94,13 +207,95 @@ impl Server {
     /// - `Poll::Ready(None)` the attempt failed because the server has closed. Once
     /// None is returned, this function should not be called again.
     pub fn poll_accept(&mut self, cx: &mut Context) -> Poll<Option<Connection>> {
-        match self.acceptor.poll_accept(cx) {
-            Poll::Ready(Some(connection)) => Poll::Ready(Some(Connection::new(connection))),
-            Poll::Ready(None) => Poll::Ready(None),
-            Poll::Pending => Poll::Pending,
+        let len = self.acceptors.len();
+        let mut is_closed = true;
+
+        for offset in 0..len {
+            let index = (self.next_acceptor + offset) % len;
+            match self.acceptors[index].poll_accept(cx) {
+                Poll::Ready(Some(connection)) => {
+                    self.next_acceptor = (index + 1) % len;
+                    return Poll::Ready(Some(Connection::new(connection)));
+                }
+                Poll::Ready(None) => {}
+                Poll::Pending => is_closed = false,
+            }
+        }
+
+        if is_closed {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
         }
     }
 
+    /// Gracefully shuts down the [`Server`]
+    ///
+    /// The server immediately stops accepting new connections and refuses any new connection
+    /// attempts with the `CONNECTION_REFUSED` transport error. Existing connections are given
+    /// `grace_period` to finish their streams and close, after which any remaining connections
+    /// are closed with the [`application::Error::UNKNOWN`] error code. The returned future
+    /// resolves once all of the connections have closed and the endpoint is idle.
+    ///
+    /// Once shut down, [`Server::accept`] will return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path, time::Duration};
+    /// # use s2n_quic::Server;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut server = Server::builder()
+    ///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///     .with_io("127.0.0.1:443")?
+    ///     .start()?;
+    ///
+    /// server.shutdown(Duration::from_secs(30)).await?;
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown(&mut self, grace_period: Duration) -> Result<(), connection::Error> {
+        self.shutdown_with_error(grace_period, application::Error::UNKNOWN)
+            .await
+    }
+
+    /// Gracefully shuts down the [`Server`], closing any connections remaining after
+    /// `grace_period` with the provided `error`
+    ///
+    /// See [`Server::shutdown`] for more details.
+    pub async fn shutdown_with_error(
+        &mut self,
+        grace_period: Duration,
+        error: application::Error,
+    ) -> Result<(), connection::Error> {
+        let shutdown = Shutdown::new(grace_period).with_error(error);
+        let mut error = None;
+        futures::future::poll_fn(|cx| {
+            let mut is_pending = false;
+
+            // keep polling the other endpoints so they all shut down concurrently
+            for acceptor in &mut self.acceptors {
+                match acceptor.poll_shutdown(cx, shutdown) {
+                    Poll::Ready(Ok(())) => {}
+                    Poll::Ready(Err(err)) => {
+                        error.get_or_insert(err);
+                    }
+                    Poll::Pending => is_pending = true,
+                }
+            }
+
+            if is_pending {
+                return Poll::Pending;
+            }
+
+            Poll::Ready(error.take().map_or(Ok(()), Err))
+        })
+        .await
+    }
+
     /// Returns the local address that this listener is bound to.
     ///
     /// This can be useful, for example, when binding to port `0` to figure out which
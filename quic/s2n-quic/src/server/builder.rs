@@ -201,6 +201,63 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
+    impl_provider_method!(
+        /// Sets the early data provider for the [`Server`]
+        ///
+        /// # Examples
+        ///
+        /// Limits the number of connections that are able to use 0-RTT each second
+        ///
+        /// ```rust,no_run
+        /// # use std::{error::Error, time::Duration};
+        /// use s2n_quic::{Server, provider::early_data};
+        ///
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// let server = Server::builder()
+        ///     .with_early_data(early_data::RateLimited::new(100, Duration::from_secs(1)))?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        with_early_data,
+        early_data,
+        ServerProviders
+    );
+
+    impl_provider_method!(
+        /// Sets the preferred address provider for the [`Server`]
+        ///
+        /// Clients that support migrating to a preferred address will validate the advertised
+        /// address after the handshake is confirmed and move the connection to it.
+        ///
+        /// # Examples
+        ///
+        /// Hands connections off to a unicast address after the handshake
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Server, provider::preferred_address};
+        ///
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// let addresses = preferred_address::Addresses::default()
+        ///     .with_ipv4("192.0.2.1:443".parse::<std::net::SocketAddrV4>()?);
+        ///
+        /// let server = Server::builder()
+        ///     .with_io("0.0.0.0:443")?
+        ///     .with_preferred_address(addresses)?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        with_preferred_address,
+        preferred_address,
+        ServerProviders
+    );
+
     impl_provider_method!(
         /// Sets the IO provider for the [`Server`]
         ///
@@ -326,6 +383,32 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
+    impl_provider_method!(
+        /// Sets the QUIC versions enabled on the [`Server`]
+        ///
+        /// Only QUIC version 1 is enabled by default. Enabling additional versions allows
+        /// the endpoint to switch to a compatible version during the handshake.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Server, provider::version};
+        ///
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// let server = Server::builder()
+        ///     .with_version(version::Preferences::new(&[version::VERSION_2, version::VERSION_1])?)?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        with_version,
+        version,
+        ServerProviders
+    );
+
     /// Starts the [`Server`] with the configured providers
     ///
     /// # Examples
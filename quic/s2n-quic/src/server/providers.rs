@@ -3,7 +3,11 @@
 
 use super::*;
 use core::marker::PhantomData;
-use s2n_quic_core::{connection::id::Generator, crypto, path};
+use s2n_quic_core::{
+    connection::id::Generator,
+    crypto::{self, tls::Endpoint as _},
+    path,
+};
 use s2n_quic_transport::{connection, endpoint, stream};
 
 impl_providers_state! {
@@ -24,6 +28,9 @@ impl_providers_state! {
         tls: Tls,
         address_token: AddressToken,
         datagram: Datagram,
+        early_data: EarlyData,
+        version: Version,
+        preferred_address: PreferredAddress,
     }
 
     /// Opaque trait containing all of the configured providers
@@ -46,6 +53,9 @@ impl<
         Tls: tls::Provider,
         AddressToken: address_token::Provider,
         Datagram: datagram::Provider,
+        EarlyData: early_data::Provider,
+        Version: version::Provider,
+        PreferredAddress: preferred_address::Provider,
     >
     Providers<
         CongestionController,
@@ -63,6 +73,9 @@ impl<
         Tls,
         AddressToken,
         Datagram,
+        EarlyData,
+        Version,
+        PreferredAddress,
     >
 {
     pub fn start(self) -> Result<Server, StartError> {
@@ -82,6 +95,9 @@ impl<
             sync,
             tls,
             datagram,
+            early_data,
+            version,
+            preferred_address,
         } = self;
 
         let congestion_controller = congestion_controller.start().map_err(StartError::new)?;
@@ -100,6 +116,9 @@ impl<
         let path_migration = path_migration.start().map_err(StartError::new)?;
         let tls = tls.start_server().map_err(StartError::new)?;
         let datagram = datagram.start().map_err(StartError::new)?;
+        let early_data = early_data.start().map_err(StartError::new)?;
+        let quic_versions = version.start().map_err(StartError::new)?;
+        let preferred_address = preferred_address.start().map_err(StartError::new)?;
 
         // Validate providers
         // TODO: Add more validation https://github.com/aws/s2n-quic/issues/285
@@ -113,6 +132,16 @@ impl<
             return Err(StartError::new(connection::id::Error::InvalidLifetime));
         };
 
+        if !quic_versions
+            .versions()
+            .iter()
+            .all(|version| tls.supports_quic_version(*version))
+        {
+            return Err(StartError::new(
+                "the TLS provider does not support all of the enabled QUIC versions",
+            ));
+        }
+
         let endpoint_config = EndpointConfig {
             congestion_controller,
             connection_close_formatter,
@@ -126,9 +155,13 @@ impl<
             sync,
             tls,
             address_token,
+            token_store: s2n_quic_core::token::Disabled,
             path_handle: PhantomData,
             path_migration,
             datagram,
+            early_data,
+            preferred_address,
+            quic_versions,
         };
 
         let (endpoint, acceptor) = endpoint::Endpoint::new_server(endpoint_config);
@@ -137,8 +170,10 @@ impl<
         let local_addr = io.start(endpoint).map_err(StartError::new)?;
 
         Ok(Server {
-            acceptor,
+            acceptors: vec![acceptor],
+            next_acceptor: 0,
             local_addr,
+            sockets: Vec::new(),
         })
     }
 }
@@ -160,6 +195,8 @@ struct EndpointConfig<
     Tls,
     AddressToken,
     Datagram,
+    EarlyData,
+    PreferredAddress,
 > {
     congestion_controller: CongestionController,
     connection_close_formatter: ConnectionCloseFormatter,
@@ -173,9 +210,13 @@ struct EndpointConfig<
     sync: Sync,
     tls: Tls,
     address_token: AddressToken,
+    token_store: s2n_quic_core::token::Disabled,
     path_handle: PhantomData<PathHandle>,
     path_migration: PathMigration,
     datagram: Datagram,
+    early_data: EarlyData,
+    preferred_address: PreferredAddress,
+    quic_versions: version::Preferences,
 }
 
 impl<
@@ -194,6 +235,8 @@ impl<
         Tls: crypto::tls::Endpoint,
         AddressToken: address_token::Format,
         Datagram: s2n_quic_core::datagram::Endpoint,
+        EarlyData: early_data::Controller,
+        PreferredAddress: preferred_address::Endpoint,
     > core::fmt::Debug
     for EndpointConfig<
         CongestionController,
@@ -211,6 +254,8 @@ impl<
         Tls,
         AddressToken,
         Datagram,
+        EarlyData,
+        PreferredAddress,
     >
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -234,6 +279,8 @@ impl<
         Tls: crypto::tls::Endpoint,
         AddressToken: address_token::Format,
         Datagram: s2n_quic_core::datagram::Endpoint,
+        EarlyData: early_data::Controller,
+        PreferredAddress: preferred_address::Endpoint,
     > endpoint::Config
     for EndpointConfig<
         CongestionController,
@@ -251,6 +298,8 @@ impl<
         Tls,
         AddressToken,
         Datagram,
+        EarlyData,
+        PreferredAddress,
     >
 {
     type ConnectionIdFormat = ConnectionID;
@@ -266,11 +315,14 @@ impl<
     type EventSubscriber = Event;
     type TLSEndpoint = Tls;
     type TokenFormat = AddressToken;
+    type TokenStore = s2n_quic_core::token::Disabled;
     type ConnectionLimits = Limits;
     type StreamManager = stream::DefaultStreamManager;
     type PathMigrationValidator = PathMigration;
     type PacketInterceptor = PacketInterceptor;
     type DatagramEndpoint = Datagram;
+    type EarlyDataController = EarlyData;
+    type PreferredAddressEndpoint = PreferredAddress;
 
     const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Server;
 
@@ -285,10 +337,14 @@ impl<
             tls: &mut self.tls,
             endpoint_limits: &mut self.endpoint_limits,
             token: &mut self.address_token,
+            token_store: &mut self.token_store,
             connection_limits: &mut self.limits,
             event_subscriber: &mut self.event,
             path_migration: &mut self.path_migration,
             datagram: &mut self.datagram,
+            early_data: &mut self.early_data,
+            preferred_address: &mut self.preferred_address,
+            quic_versions: &self.quic_versions,
         }
     }
 }
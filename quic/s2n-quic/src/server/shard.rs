@@ -0,0 +1,76 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::provider::{address_token, connection_id, io, stateless_reset_token};
+use std::{
+    net::{SocketAddr, UdpSocket},
+    sync::{Arc, Mutex},
+};
+
+/// Describes one of the endpoints of a [`Server`](crate::Server) created with
+/// [`Server::sharded`](crate::Server::sharded)
+///
+/// The providers returned by the `Shard` route packets to the endpoint which owns the connection
+/// and share the state needed to validate address tokens and reset connections across all of the
+/// endpoints.
+#[derive(Clone, Debug)]
+pub struct Shard {
+    pub(super) index: u8,
+    pub(super) count: u8,
+    pub(super) local_addr: Option<SocketAddr>,
+    pub(super) address_token: address_token::Shared<address_token::default::Format>,
+    pub(super) stateless_reset_token: stateless_reset_token::default::Generator,
+    /// Handles to the sockets bound by the shards, which are held by the server
+    pub(super) sockets: Arc<Mutex<Vec<UdpSocket>>>,
+}
+
+impl Shard {
+    /// Returns the index of the shard
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Returns the total number of shards in the server
+    pub fn count(&self) -> u8 {
+        self.count
+    }
+
+    /// Returns an IO provider which shares the port with the other shards
+    ///
+    /// The first shard binds to `addr`, after which the remaining shards bind to the address of
+    /// the first shard. This allows `addr` to use port `0`.
+    ///
+    /// The socket is bound immediately and stays open until the server is dropped, even if the
+    /// endpoint of the shard shuts down first. Otherwise the kernel would reorder the sockets in
+    /// the group and deliver packets to the wrong shards.
+    pub fn io(&self, addr: SocketAddr) -> std::io::Result<io::tokio::Provider> {
+        let (builder, socket) = io::tokio::Builder::default()
+            .with_receive_address(self.local_addr.unwrap_or(addr))?
+            .with_reuse_port()?
+            .with_reuse_port_steering(self.count)?
+            .bind_rx_socket()?;
+
+        self.sockets
+            .lock()
+            .expect("sockets lock should not be poisoned")
+            .push(socket);
+
+        builder.build()
+    }
+
+    /// Returns a connection ID format which encodes the index of the shard
+    pub fn connection_id(&self) -> connection_id::sharded::Format {
+        connection_id::sharded::Format::new(self.index)
+    }
+
+    /// Returns an address token format which validates tokens issued by any shard
+    pub fn address_token(&self) -> address_token::Shared<address_token::default::Format> {
+        self.address_token.clone()
+    }
+
+    /// Returns a stateless reset token generator which derives tokens with a key shared by all of
+    /// the shards
+    pub fn stateless_reset_token(&self) -> stateless_reset_token::default::Generator {
+        self.stateless_reset_token.clone()
+    }
+}
@@ -15,7 +15,7 @@ mod bidirectional;
 mod local;
 mod peer;
 
-pub use s2n_quic_core::stream::{StreamError as Error, StreamType as Type};
+pub use s2n_quic_core::stream::{scheduler, Priority, StreamError as Error, StreamType as Type};
 
 pub use bidirectional::*;
 pub use local::*;
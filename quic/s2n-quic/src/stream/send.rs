@@ -376,6 +376,52 @@ macro_rules! impl_send_stream_api {
             let $stream = self;
             $dispatch_body
         }
+
+        /// Sets the transmission priority of the stream.
+        ///
+        /// Streams with pending data are ordered by the
+        /// [`Scheduler`](crate::stream::scheduler::Scheduler) configured on the connection's
+        /// [`Limits`](crate::provider::limits::Limits). By default, streams with a lower
+        /// urgency are transmitted first.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the priority was updated.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// use s2n_quic::stream::Priority;
+        ///
+        /// // transmit the stream before other streams and interleave it with streams of the
+        /// // same urgency
+        /// stream.set_priority(Priority::new(0, true).unwrap())?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_priority(
+            &mut self,
+            priority: $crate::stream::Priority,
+        ) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.set_priority(priority)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
     };
 }
 
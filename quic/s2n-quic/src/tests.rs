@@ -3,13 +3,18 @@
 
 use crate::{
     client::Connect,
+    connection,
     provider::{
         self,
         event::{
-            events::{MtuUpdated, MtuUpdatedCause, PacketSent, RecoveryMetrics},
+            events::{self, MtuUpdated, MtuUpdatedCause, PacketSent, RecoveryMetrics},
             ConnectionInfo, ConnectionMeta, Subscriber,
         },
-        io::testing::{rand, spawn, test, time::delay, Model},
+        io::testing::{
+            rand, spawn, test,
+            time::{delay, now},
+            Model,
+        },
         packet_interceptor::Loss,
     },
     Client, Server,
@@ -17,7 +22,9 @@ use crate::{
 use bytes::Bytes;
 #[cfg(not(target_os = "windows"))]
 use s2n_quic_core::event::api::HandshakeStatusUpdated;
-use s2n_quic_core::{crypto::tls::testing::certificates, stream::testing::Data};
+use s2n_quic_core::{
+    crypto::tls::testing::certificates, endpoint, stream::testing::Data, transport,
+};
 use s2n_quic_platform::io::testing::{network::Packet, primary, TxRecorder};
 use std::{
     net::SocketAddr,
@@ -31,6 +38,8 @@ use setup::*;
 
 #[cfg(not(target_os = "windows"))]
 mod client_handshake_confirm;
+#[cfg(target_os = "linux")]
+mod sharded;
 
 #[test]
 fn client_server_test() {
@@ -187,6 +196,94 @@ fn stream_reset_test() {
     .unwrap();
 }
 
+/// Ensures the server stops accepting connections and closes the remaining ones after the
+/// shutdown grace period
+#[test]
+fn server_shutdown_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            // hold on to the connection so it stays open until the grace period elapses
+            let _connection = server.accept().await.unwrap();
+
+            server
+                .shutdown_with_error(Duration::from_secs(1), 123u8.into())
+                .await
+                .unwrap();
+
+            assert!(server.accept().await.is_none());
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // the connection is idle until the server closes it
+            let error = connection.accept_bidirectional_stream().await.unwrap_err();
+            assert!(
+                matches!(
+                    error,
+                    connection::Error::Application { error, .. } if u64::from(error) == 123
+                ),
+                "unexpected error: {error}"
+            );
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures clients are refused while the server is shutting down instead of waiting for the
+/// handshake to time out
+#[test]
+fn server_shutdown_refused_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            // hold on to the connection so the server keeps shutting down
+            let _connection = server.accept().await.unwrap();
+            server.shutdown(Duration::from_secs(60)).await.unwrap();
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let _connection = client.connect(connect.clone()).await.unwrap();
+
+            // give the server time to start shutting down
+            delay(Duration::from_millis(100)).await;
+
+            let start = now();
+            let error = client.connect(connect).await.unwrap_err();
+            assert!(
+                matches!(
+                    error,
+                    connection::Error::Transport {
+                        code,
+                        initiator: endpoint::Location::Remote,
+                        ..
+                    } if code == transport::Error::CONNECTION_REFUSED.code
+                ),
+                "unexpected error: {error}"
+            );
+            assert!(now() - start < Duration::from_secs(1));
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
 /// Ensures tokio `AsyncRead` implementation functions properly
 ///
 /// See https://github.com/aws/s2n-quic/issues/1427
@@ -387,6 +484,15 @@ event_recorder!(
     HandshakeStatusUpdated,
     on_handshake_status_updated
 );
+event_recorder!(
+    SessionResumptionRecorder,
+    events::SessionResumption,
+    on_session_resumption,
+    bool,
+    |event: &events::SessionResumption, storage: &mut Vec<bool>| {
+        storage.push(event.resumed);
+    }
+);
 
 #[test]
 fn packet_sent_event_test() {
@@ -866,3 +972,1200 @@ fn mtls_auth_failure() {
     // confirm server connection was attempted but failed
     assert!(server_connection_closed.load(Ordering::SeqCst));
 }
+
+/// Clears the QUIC bit on every datagram received by an endpoint
+struct ClearQuicBit;
+
+impl provider::packet_interceptor::PacketInterceptor for ClearQuicBit {
+    fn intercept_rx_datagram<'a>(
+        &mut self,
+        _subject: &events::Subject,
+        _datagram: &s2n_quic_core::packet::interceptor::Datagram,
+        payload: s2n_codec::DecoderBufferMut<'a>,
+    ) -> s2n_codec::DecoderBufferMut<'a> {
+        let payload = payload.into_less_safe_slice();
+        if let Some(tag) = payload.first_mut() {
+            // an Initial packet tag of 0xc0 becomes 0x80
+            *tag &= !0x40;
+        }
+        s2n_codec::DecoderBufferMut::new(payload)
+    }
+}
+
+#[derive(Clone, Default)]
+struct DatagramDropRecorder {
+    reasons: Arc<Mutex<Vec<events::DatagramDropReason>>>,
+}
+
+impl Subscriber for DatagramDropRecorder {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_endpoint_datagram_dropped(
+        &mut self,
+        _meta: &events::EndpointMeta,
+        event: &events::EndpointDatagramDropped,
+    ) {
+        self.reasons.lock().unwrap().push(event.reason.clone());
+    }
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
+//= type=test
+//# Packets containing a zero
+//# value for this bit are not valid packets in this version and MUST
+//# be discarded.
+#[test]
+fn quic_bit_cleared_initial_test() {
+    let subscriber = DatagramDropRecorder::default();
+    let reasons = subscriber.reasons.clone();
+
+    test(Model::default(), |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(subscriber)?
+            .with_packet_interceptor(ClearQuicBit)?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            if server.accept().await.is_some() {
+                panic!("connection should not be accepted with a cleared QUIC bit");
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            assert!(client.connect(connect).await.is_err());
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let reasons = reasons.lock().unwrap();
+    assert!(!reasons.is_empty());
+    assert!(reasons
+        .iter()
+        .all(|reason| matches!(reason, events::DatagramDropReason::QuicBitCleared { .. })));
+}
+
+/// Returns the first byte of each 1-RTT packet sent by a client to a server which advertises
+/// the `grease_quic_bit` transport parameter
+fn client_short_header_tags(randomized_quic_bit: bool) -> Vec<u8> {
+    let recorder = TxRecorder::default();
+    let network_packets = recorder.get_packets();
+    let mut server_socket = None;
+
+    test((recorder, Model::default()), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_limits(provider::limits::Limits::new().with_grease_quic_bit(true)?)?
+            .start()?;
+        let addr = start_server(server)?;
+        server_socket = Some(addr);
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_limits(
+                provider::limits::Limits::new().with_randomized_quic_bit(randomized_quic_bit)?,
+            )?
+            .start()?;
+        start_client(client, addr, Data::new(100_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let server_socket = server_socket.unwrap();
+    let network_packets = network_packets.lock().unwrap();
+    network_packets
+        .iter()
+        .filter(|packet| {
+            let local_socket: SocketAddr = packet.path.local_address.0.into();
+            local_socket != server_socket
+        })
+        .filter_map(|packet| packet.payload.first().copied())
+        // the header form bit is 0 for short headers
+        .filter(|tag| tag & 0x80 == 0)
+        .collect()
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9287#section-3.1
+//= type=test
+//# An endpoint that receives the grease_quic_bit transport parameter
+//# from a peer SHOULD set the QUIC Bit to an unpredictable value unless
+//# another extension assigns specific meaning to the value of the bit.
+#[test]
+fn randomized_quic_bit_test() {
+    let tags = client_short_header_tags(true);
+    assert!(tags.iter().any(|tag| tag & 0x40 == 0));
+    assert!(tags.iter().any(|tag| tag & 0x40 != 0));
+
+    // the application can keep the QUIC bit set even if the peer advertises greasing
+    let tags = client_short_header_tags(false);
+    assert!(!tags.is_empty());
+    assert!(tags.iter().all(|tag| tag & 0x40 != 0));
+}
+
+/// Records the outcome of each early data attempt
+#[cfg(any(feature = "s2n-quic-rustls", feature = "s2n-quic-tls"))]
+#[derive(Clone, Default)]
+struct EarlyDataRecorder {
+    outcomes: Arc<Mutex<Vec<Result<(), events::EarlyDataRejectReason>>>>,
+}
+
+#[cfg(any(feature = "s2n-quic-rustls", feature = "s2n-quic-tls"))]
+impl Subscriber for EarlyDataRecorder {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_early_data_accepted(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        _event: &events::EarlyDataAccepted,
+    ) {
+        self.outcomes.lock().unwrap().push(Ok(()));
+    }
+
+    fn on_early_data_rejected(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::EarlyDataRejected,
+    ) {
+        self.outcomes
+            .lock()
+            .unwrap()
+            .push(Err(event.reason.clone()));
+    }
+}
+
+/// Connects to a server twice, sending data in 0-RTT packets on the resumed connection
+///
+/// The client offers the `application_protocols`, which must include `h3`. Returns the early
+/// data outcomes recorded by the server and the client.
+#[cfg(feature = "s2n-quic-rustls")]
+fn early_data_round_trip<C: provider::early_data::Provider>(
+    controller: C,
+    application_protocols: Vec<Vec<u8>>,
+) -> (
+    Vec<Result<(), events::EarlyDataRejectReason>>,
+    Vec<Result<(), events::EarlyDataRejectReason>>,
+) {
+    use provider::tls::rustls;
+
+    let server_subscriber = EarlyDataRecorder::default();
+    let server_outcomes = server_subscriber.outcomes.clone();
+    let client_subscriber = EarlyDataRecorder::default();
+    let client_outcomes = client_subscriber.outcomes.clone();
+
+    test(Model::default(), |handle| {
+        let server_tls = rustls::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+            .with_early_data()?
+            .build()?;
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls)?
+            .with_early_data(controller)?
+            .with_event(server_subscriber)?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client_tls = rustls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)?
+            .with_application_protocols(application_protocols.iter())?
+            .with_early_data()?
+            .build()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(client_tls)?
+            .with_event(client_subscriber)?
+            .start()?;
+
+        primary::spawn(async move {
+            // the first connection receives the session ticket used to resume the second
+            for _ in 0..2 {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                let data = Bytes::from_static(&[42; 1000]);
+                stream.send(data.clone()).await.unwrap();
+                stream.finish().unwrap();
+
+                let mut received = vec![];
+                while let Some(chunk) = stream.receive().await.unwrap() {
+                    received.extend_from_slice(&chunk);
+                }
+                assert_eq!(received, data);
+
+                // give the session ticket time to arrive before closing the connection
+                delay(Duration::from_millis(100)).await;
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let server_outcomes = server_outcomes.lock().unwrap().clone();
+    let client_outcomes = client_outcomes.lock().unwrap().clone();
+    (server_outcomes, client_outcomes)
+}
+
+#[cfg(feature = "s2n-quic-rustls")]
+#[test]
+fn early_data_accepted_test() {
+    let (server, client) = early_data_round_trip(
+        provider::early_data::Default::default(),
+        vec![b"h3".to_vec()],
+    );
+
+    assert!(matches!(server[..], [Ok(())]), "{:?}", server);
+    assert!(matches!(client[..], [Ok(())]), "{:?}", client);
+}
+
+/// The early data controller is consulted once a ClientHello that spans multiple Initial
+/// packets has been received
+#[cfg(feature = "s2n-quic-rustls")]
+#[test]
+fn early_data_split_client_hello_test() {
+    // the ALPN extension alone doesn't fit in a single Initial packet
+    let mut application_protocols: Vec<_> = (0..300)
+        .map(|i| format!("proto-{i:03}").into_bytes())
+        .collect();
+    application_protocols.push(b"h3".to_vec());
+
+    let (server, client) = early_data_round_trip(
+        provider::early_data::Default::default(),
+        application_protocols,
+    );
+
+    assert!(matches!(server[..], [Ok(())]), "{:?}", server);
+    assert!(matches!(client[..], [Ok(())]), "{:?}", client);
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9001#section-4.6.2
+//= type=test
+//# The client therefore MUST reset the state of all
+//# streams, including application state bound to those streams.
+#[cfg(feature = "s2n-quic-rustls")]
+#[test]
+fn early_data_rejected_test() {
+    // the echoed data is checked by the client after the streams are replayed
+    let (server, client) =
+        early_data_round_trip(provider::early_data::Disabled, vec![b"h3".to_vec()]);
+
+    assert!(
+        matches!(
+            server[..],
+            [Err(events::EarlyDataRejectReason::Disabled { .. })]
+        ),
+        "{:?}",
+        server
+    );
+    assert!(
+        matches!(
+            client[..],
+            [Err(events::EarlyDataRejectReason::Peer { .. })]
+        ),
+        "{:?}",
+        client
+    );
+}
+
+/// The s2n-tls provider sends data in 0-RTT packets on a resumed connection
+#[cfg(feature = "s2n-quic-tls")]
+#[test]
+fn s2n_tls_early_data_test() {
+    use provider::{
+        session_ticket::{Cache, KeyRotation},
+        tls::s2n_tls,
+    };
+
+    let server_subscriber = EarlyDataRecorder::default();
+    let server_outcomes = server_subscriber.outcomes.clone();
+    let client_subscriber = EarlyDataRecorder::default();
+    let client_outcomes = client_subscriber.outcomes.clone();
+
+    test(Model::default(), |handle| {
+        let server_tls = s2n_tls::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+            .with_session_tickets(KeyRotation::default())?
+            .with_early_data()?
+            .build()?;
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls)?
+            .with_early_data(provider::early_data::Default::default())?
+            .with_event(server_subscriber)?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client_tls = s2n_tls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)?
+            .with_session_ticket_store(Arc::new(Cache::default()))?
+            .with_early_data()?
+            .build()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(client_tls)?
+            .with_event(client_subscriber)?
+            .start()?;
+
+        primary::spawn(async move {
+            // the first connection receives the session ticket used to resume the second
+            for _ in 0..2 {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                let data = Bytes::from_static(&[42; 1000]);
+                stream.send(data.clone()).await.unwrap();
+                stream.finish().unwrap();
+
+                let mut received = vec![];
+                while let Some(chunk) = stream.receive().await.unwrap() {
+                    received.extend_from_slice(&chunk);
+                }
+                assert_eq!(received, data);
+
+                // give the session ticket time to arrive before closing the connection
+                delay(Duration::from_millis(100)).await;
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let server = server_outcomes.lock().unwrap().clone();
+    let client = client_outcomes.lock().unwrap().clone();
+    assert!(matches!(server[..], [Ok(())]), "{:?}", server);
+    assert!(matches!(client[..], [Ok(())]), "{:?}", client);
+}
+
+/// Both endpoints of a connection export the same keying material with the s2n-tls provider
+#[cfg(feature = "s2n-quic-tls")]
+#[test]
+fn s2n_tls_export_keying_material_test() {
+    use provider::tls::s2n_tls;
+
+    const LABEL: &[u8] = b"EXPORTER-s2n-quic-test";
+    const CONTEXT: &[u8] = b"context";
+
+    let exported = Arc::new(Mutex::new(vec![]));
+    let server_exported = exported.clone();
+
+    test(Model::default(), |handle| {
+        let server_tls = s2n_tls::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+            .build()?;
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls)?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let material = connection
+                .export_keying_material(LABEL, CONTEXT, 32)
+                .unwrap();
+            server_exported.lock().unwrap().push(material);
+
+            // keep the connection open until the client is done
+            let _ = connection.accept_bidirectional_stream().await;
+        });
+
+        let client_tls = s2n_tls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)?
+            .build()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(client_tls)?
+            .start()?;
+        let client_exported = exported.clone();
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let material = connection
+                .export_keying_material(LABEL, CONTEXT, 32)
+                .unwrap();
+
+            // make sure the server has completed the handshake before comparing
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"done")).await.unwrap();
+            stream.flush().await.unwrap();
+
+            client_exported.lock().unwrap().push(material);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let exported = exported.lock().unwrap();
+    assert_eq!(exported.len(), 2);
+    assert_eq!(exported[0], exported[1]);
+    assert_ne!(exported[0], Bytes::from_static(&[0; 32]));
+}
+
+/// Servers which derive their session ticket keys from the same secret resume each other's
+/// sessions with the s2n-tls provider
+#[cfg(feature = "s2n-quic-tls")]
+#[test]
+fn s2n_tls_shared_session_ticket_keys_test() {
+    use provider::{
+        session_ticket::{Cache, KeyRotation},
+        tls::s2n_tls::{self, session_ticket::SharedSecret},
+    };
+
+    let server_subscriber = SessionResumptionRecorder::new();
+    let server_events = server_subscriber.events();
+    let client_subscriber = SessionResumptionRecorder::new();
+    let client_events = client_subscriber.events();
+
+    test(Model::default(), |handle| {
+        let mut addrs = vec![];
+        for _ in 0..2 {
+            let server_tls = s2n_tls::Server::builder()
+                .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+                .with_session_ticket_keys(KeyRotation::default(), SharedSecret::new(&[42; 32])?)?
+                .build()?;
+            let server = Server::builder()
+                .with_io(handle.builder().build()?)?
+                .with_tls(server_tls)?
+                .with_event(server_subscriber.clone())?
+                .start()?;
+            addrs.push(start_server(server)?);
+        }
+
+        let client_tls = s2n_tls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)?
+            .with_session_ticket_store(Arc::new(Cache::default()))?
+            .build()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(client_tls)?
+            .with_event(client_subscriber.clone())?
+            .start()?;
+
+        primary::spawn(async move {
+            // the ticket issued by the first server is used to resume the session with the second
+            for addr in addrs {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                stream.send(Bytes::from_static(b"ping")).await.unwrap();
+                stream.finish().unwrap();
+                while stream.receive().await.unwrap().is_some() {}
+
+                // give the session ticket time to arrive before closing the connection
+                delay(Duration::from_millis(100)).await;
+            }
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(*server_events.lock().unwrap(), [false, true]);
+    assert_eq!(*client_events.lock().unwrap(), [false, true]);
+}
+
+/// The rustls client stores tickets with the lifetime issued by the server and reports whether
+/// the server resumed its session
+#[cfg(feature = "s2n-quic-rustls")]
+#[test]
+fn rustls_session_resumption_test() {
+    use provider::{
+        session_ticket::{Cache, KeyRotation, Store, Ticket},
+        tls::rustls,
+    };
+    use s2n_quic_core::application::ServerName;
+
+    /// Records the lifetime of each ticket stored in the cache
+    #[derive(Default)]
+    struct LifetimeRecorder {
+        cache: Cache,
+        lifetimes: Mutex<Vec<Duration>>,
+    }
+
+    impl Store for LifetimeRecorder {
+        fn on_ticket(&self, server_name: &ServerName, ticket: Ticket) {
+            self.lifetimes.lock().unwrap().push(ticket.lifetime);
+            self.cache.on_ticket(server_name, ticket);
+        }
+
+        fn take(&self, server_name: &ServerName) -> Option<Ticket> {
+            self.cache.take(server_name)
+        }
+    }
+
+    let rotation = KeyRotation::default()
+        .with_decrypt_period(Duration::from_secs(3600))
+        .unwrap();
+    let store = Arc::new(LifetimeRecorder::default());
+    let server_subscriber = SessionResumptionRecorder::new();
+    let server_events = server_subscriber.events();
+    let client_subscriber = SessionResumptionRecorder::new();
+    let client_events = client_subscriber.events();
+
+    let client_store = store.clone();
+    test(Model::default(), move |handle| {
+        let server_tls = rustls::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+            .with_session_tickets(rotation)?
+            .build()?;
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls)?
+            .with_event(server_subscriber)?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client_tls = rustls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)?
+            .with_session_ticket_store(client_store)?
+            .build()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(client_tls)?
+            .with_event(client_subscriber)?
+            .start()?;
+
+        primary::spawn(async move {
+            // the first connection receives the session ticket used to resume the second
+            for _ in 0..2 {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                stream.send(Bytes::from_static(b"ping")).await.unwrap();
+                stream.finish().unwrap();
+                while stream.receive().await.unwrap().is_some() {}
+
+                // give the session ticket time to arrive before closing the connection
+                delay(Duration::from_millis(100)).await;
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert_eq!(*server_events.lock().unwrap(), [false, true]);
+    assert_eq!(*client_events.lock().unwrap(), [false, true]);
+
+    let lifetimes = store.lifetimes.lock().unwrap();
+    assert!(!lifetimes.is_empty());
+    assert!(lifetimes
+        .iter()
+        .all(|lifetime| *lifetime == rotation.ticket_lifetime()));
+}
+
+/// Asks every client to validate its address with a Retry packet
+struct AlwaysRetry;
+
+impl provider::endpoint_limits::Limiter for AlwaysRetry {
+    fn on_connection_attempt(
+        &mut self,
+        _info: &provider::endpoint_limits::ConnectionAttempt,
+    ) -> provider::endpoint_limits::Outcome {
+        provider::endpoint_limits::Outcome::retry()
+    }
+}
+
+/// Counts the Retry packets sent by an endpoint
+#[derive(Clone, Default)]
+struct RetryRecorder {
+    retries: Arc<Mutex<usize>>,
+}
+
+impl Subscriber for RetryRecorder {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_endpoint_packet_sent(
+        &mut self,
+        _meta: &events::EndpointMeta,
+        event: &events::EndpointPacketSent,
+    ) {
+        if matches!(event.packet_header, events::PacketHeader::Retry { .. }) {
+            *self.retries.lock().unwrap() += 1;
+        }
+    }
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+//= type=test
+//# A server MAY provide clients with an address validation token during
+//# one connection that can be used on a subsequent connection.
+#[test]
+fn new_token_skips_retry_test() {
+    let subscriber = RetryRecorder::default();
+    let retries = subscriber.retries.clone();
+
+    test(Model::default(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_endpoint_limits(AlwaysRetry)?
+            .with_event((events(), subscriber))?
+            .start()?;
+        let addr = start_server(server)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            // the first connection receives the token used to skip the Retry on the second
+            for _ in 0..2 {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                let data = Bytes::from_static(&[42; 1000]);
+                stream.send(data.clone()).await.unwrap();
+                stream.finish().unwrap();
+
+                let mut received = vec![];
+                while let Some(chunk) = stream.receive().await.unwrap() {
+                    received.extend_from_slice(&chunk);
+                }
+                assert_eq!(received, data);
+
+                // give the NEW_TOKEN frame time to arrive before closing the connection
+                delay(Duration::from_millis(100)).await;
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert_eq!(*retries.lock().unwrap(), 1);
+}
+
+/// Records the server name of each certificate a server selected from its certificate store
+#[cfg(any(feature = "s2n-quic-rustls", feature = "s2n-quic-tls"))]
+#[derive(Clone, Default)]
+struct CertificateRecorder {
+    server_names: Arc<Mutex<Vec<Option<String>>>>,
+}
+
+#[cfg(any(feature = "s2n-quic-rustls", feature = "s2n-quic-tls"))]
+impl Subscriber for CertificateRecorder {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_server_certificate_selected(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::ServerCertificateSelected,
+    ) {
+        self.server_names
+            .lock()
+            .unwrap()
+            .push(event.server_name.map(String::from));
+    }
+}
+
+/// Connects to a server using `server_tls`
+///
+/// Returns the server names of the certificates reported by the server.
+#[cfg(any(feature = "s2n-quic-rustls", feature = "s2n-quic-tls"))]
+fn certificate_store_round_trip<T: provider::tls::Provider>(server_tls: T) -> Vec<Option<String>> {
+    let subscriber = CertificateRecorder::default();
+    let server_names = subscriber.server_names.clone();
+
+    test(Model::default(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls)?
+            .with_event((events(), subscriber))?
+            .start()?;
+        let addr = start_server(server)?;
+        let client = build_client(handle)?;
+        start_client(client, addr, Data::new(1000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let server_names = server_names.lock().unwrap().clone();
+    server_names
+}
+
+#[cfg(feature = "s2n-quic-rustls")]
+#[test]
+fn rustls_certificate_store_test() {
+    use provider::tls::rustls::{self, certificate};
+
+    let certified_key =
+        certificate::certified_key(certificates::CERT_PEM, certificates::KEY_PEM).unwrap();
+    let store = certificate::Store::new(
+        certificate::Certificates::new().with_server_name("localhost", certified_key),
+    );
+    let server_tls = rustls::Server::builder()
+        .with_certificate_store(store)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let server_names = certificate_store_round_trip(server_tls);
+    assert_eq!(server_names, [Some("localhost".to_string())]);
+}
+
+#[cfg(feature = "s2n-quic-tls")]
+#[test]
+fn s2n_tls_certificate_store_test() {
+    use provider::tls::s2n_tls::{self, certificate};
+
+    let config = s2n_tls::Server::builder()
+        .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+    let store = certificate::Store::new(
+        certificate::Certificates::new().with_server_name("localhost", config.into()),
+    );
+    // the default certificate isn't trusted by the client, so it must not be presented
+    let server_tls = s2n_tls::Server::builder()
+        .with_certificate(
+            certificates::UNTRUSTED_CERT_PEM,
+            certificates::UNTRUSTED_KEY_PEM,
+        )
+        .unwrap()
+        .with_certificate_store(store)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let server_names = certificate_store_round_trip(server_tls);
+    assert_eq!(server_names, [Some("localhost".to_string())]);
+}
+
+/// Migrates a client to a different local port, for which the IO provider binds a new socket
+#[test]
+fn migrate_local_port_test() {
+    let subscriber = StreamPathRecorder::new();
+    let stream_paths = subscriber.events();
+    let client_addresses = Arc::new(Mutex::new(Vec::new()));
+    let recorded_addresses = client_addresses.clone();
+
+    test(Model::default(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((events(), subscriber))?
+            .start()?;
+        let server_addr = start_server(server)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // exchange data so the handshake is confirmed
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.write_all(b"hello").await.unwrap();
+            stream.read_exact(&mut [0; 5]).await.unwrap();
+
+            let previous_address = connection.local_addr().unwrap();
+            let mut local_address = previous_address;
+            local_address.set_port(local_address.port() ^ 1);
+
+            connection.migrate(local_address).await.unwrap();
+            assert_eq!(connection.local_addr().unwrap(), local_address);
+
+            // the connection keeps working from the new socket
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.write_all(b"hello").await.unwrap();
+            stream.read_exact(&mut [0; 5]).await.unwrap();
+
+            *recorded_addresses.lock().unwrap() = vec![previous_address, local_address];
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let client_addresses = client_addresses.lock().unwrap();
+    assert_eq!(client_addresses.len(), 2);
+    assert_eq!(*stream_paths.lock().unwrap(), *client_addresses);
+}
+
+event_recorder!(
+    StreamPathRecorder,
+    events::FrameReceived,
+    on_frame_received,
+    SocketAddr,
+    |event: &events::FrameReceived, storage: &mut Vec<SocketAddr>| {
+        if matches!(event.frame, events::Frame::Stream { .. }) {
+            let addr: SocketAddr = event.path.remote_addr.to_string().parse().unwrap();
+            if !storage.contains(&addr) {
+                storage.push(addr);
+            }
+        }
+    }
+);
+
+/// Opens a second path from an additional client address, sends stream data on both paths and
+/// closes the second path
+#[test]
+fn multipath_open_close_path_test() {
+    use crate::provider::limits::Limits;
+    use s2n_quic_core::inet::IpV4Address;
+
+    const LEN: usize = 1_000_000;
+    const ADDITIONAL_IP: [u8; 4] = [10, 0, 0, 1];
+
+    let subscriber = StreamPathRecorder::new();
+    let stream_paths = subscriber.events();
+
+    test(Model::default(), |handle| {
+        let limits = Limits::new().with_multipath(true)?;
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_limits(limits)?
+            .with_event((events(), subscriber))?
+            .start()?;
+        let server_addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(
+                handle
+                    .builder()
+                    .with_additional_ip(IpV4Address::new(ADDITIONAL_IP).into())
+                    .build()?,
+            )?
+            .with_tls(certificates::CERT_PEM)?
+            .with_limits(limits)?
+            .with_event(events())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            // exchange data so the handshake is confirmed
+            stream.write_all(b"hello").await.unwrap();
+            stream.read_exact(&mut [0; 5]).await.unwrap();
+
+            let local_address: SocketAddr = (ADDITIONAL_IP, 0).into();
+            connection.open_path(local_address).await.unwrap();
+
+            // send enough data to fill the congestion window of the active path, after which
+            // data is also sent on the new path
+            let (mut recv, mut send) = stream.split();
+            primary::spawn(async move {
+                send.write_all(&[42; LEN]).await.unwrap();
+            });
+            let mut received = vec![0; LEN];
+            recv.read_exact(&mut received).await.unwrap();
+
+            connection.close_path(local_address).unwrap();
+
+            // the connection keeps working on the remaining path
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.write_all(b"hello").await.unwrap();
+            stream.read_exact(&mut [0; 5]).await.unwrap();
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let addresses = stream_paths.lock().unwrap();
+    let ips: Vec<_> = addresses.iter().map(|addr| addr.ip()).collect();
+    assert_eq!(ips.len(), 2, "{:?}", addresses);
+    assert!(ips.contains(&ADDITIONAL_IP.into()));
+}
+
+/// Drops the first datagrams received by an endpoint
+struct DropFirstRx(usize);
+
+impl provider::packet_interceptor::PacketInterceptor for DropFirstRx {
+    fn intercept_rx_datagram<'a>(
+        &mut self,
+        _subject: &events::Subject,
+        _datagram: &s2n_quic_core::packet::interceptor::Datagram,
+        payload: s2n_codec::DecoderBufferMut<'a>,
+    ) -> s2n_codec::DecoderBufferMut<'a> {
+        if let Some(remaining) = self.0.checked_sub(1) {
+            self.0 = remaining;
+            return s2n_codec::DecoderBufferMut::new(&mut payload.into_less_safe_slice()[..0]);
+        }
+        payload
+    }
+}
+
+event_recorder!(
+    InitialVersionRecorder,
+    events::PacketReceived,
+    on_packet_received,
+    u32,
+    |event: &events::PacketReceived, storage: &mut Vec<u32>| {
+        if let events::PacketHeader::Initial { version, .. } = event.packet_header {
+            storage.push(version);
+        }
+    }
+);
+
+/// Returns the versions of the Initial packets received by the server
+///
+/// The server's first flight is dropped so the client retransmits its Initial packet before
+/// it learns about the version chosen by the server.
+fn server_initial_versions(client_versions: &[u32], server_versions: &[u32]) -> Vec<u32> {
+    use crate::provider::version::Preferences;
+
+    let subscriber = InitialVersionRecorder::new();
+    let versions = subscriber.events();
+    let client_versions = Preferences::new(client_versions).unwrap();
+    let server_versions = Preferences::new(server_versions).unwrap();
+
+    test(Model::default(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_version(server_versions)?
+            .with_event((events(), subscriber))?
+            .start()?;
+        let server_addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_version(client_versions)?
+            .with_packet_interceptor(DropFirstRx(2))?
+            .with_event(events())?
+            .start()?;
+
+        start_client(client, server_addr, Data::new(1000))
+    })
+    .unwrap();
+
+    let versions = versions.lock().unwrap();
+    versions.clone()
+}
+
+#[test]
+fn compatible_version_original_initial_test() {
+    use crate::provider::version::{VERSION_1, VERSION_2};
+
+    let versions = server_initial_versions(&[VERSION_2, VERSION_1], &[VERSION_2, VERSION_1]);
+
+    // the server accepted the client's retransmitted Initial packet with the original version
+    // after switching to version 2
+    assert!(
+        versions.iter().filter(|v| **v == VERSION_1).count() > 1,
+        "{:x?}",
+        versions
+    );
+    // the client switched once it received the server's first Initial packet
+    assert_eq!(versions.last(), Some(&VERSION_2), "{:x?}", versions);
+}
+
+#[test]
+fn compatible_version_server_preference_test() {
+    use crate::provider::version::{VERSION_1, VERSION_2};
+
+    // the server prefers the original version so the client doesn't treat it as a downgrade
+    let versions = server_initial_versions(&[VERSION_2, VERSION_1], &[VERSION_1, VERSION_2]);
+    assert!(versions.iter().all(|v| *v == VERSION_1), "{:x?}", versions);
+}
+
+/// The server switches to a compatible version when the ClientHello spans multiple Initial
+/// packets
+#[cfg(feature = "s2n-quic-rustls")]
+#[test]
+fn compatible_version_split_client_hello_test() {
+    use crate::provider::{
+        tls::rustls,
+        version::{Preferences, VERSION_1, VERSION_2},
+    };
+
+    let subscriber = InitialVersionRecorder::new();
+    let versions = subscriber.events();
+    let preferences = Preferences::new(&[VERSION_2, VERSION_1]).unwrap();
+
+    // the ALPN extension alone doesn't fit in a single Initial packet
+    let mut application_protocols: Vec<_> = (0..300)
+        .map(|i| format!("proto-{i:03}").into_bytes())
+        .collect();
+    application_protocols.push(b"h3".to_vec());
+
+    test(Model::default(), |handle| {
+        let server_tls = rustls::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+            .build()?;
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls)?
+            .with_version(preferences)?
+            .with_event((events(), subscriber))?
+            .start()?;
+        let server_addr = start_server(server)?;
+
+        let client_tls = rustls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)?
+            .with_application_protocols(application_protocols.iter())?
+            .build()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(client_tls)?
+            .with_version(preferences)?
+            .with_event(events())?
+            .start()?;
+
+        start_client(client, server_addr, Data::new(1000))
+    })
+    .unwrap();
+
+    let versions = versions.lock().unwrap();
+    // the ClientHello was sent in more than one Initial packet with the original version
+    assert!(
+        versions.iter().filter(|v| **v == VERSION_1).count() > 1,
+        "{:x?}",
+        versions
+    );
+    assert_eq!(versions.last(), Some(&VERSION_2), "{:x?}", versions);
+}
+
+/// Replaces the destination connection ID of the first large 1-RTT datagram sent by an
+/// endpoint, which the peer isn't able to associate with a connection
+struct UnknownConnectionId {
+    connection_id: [u8; 16],
+    /// The length of the datagram with the replaced connection ID
+    triggering_len: Arc<Mutex<Option<usize>>>,
+}
+
+impl provider::packet_interceptor::PacketInterceptor for UnknownConnectionId {
+    fn intercept_tx_datagram(
+        &mut self,
+        _subject: &events::Subject,
+        _datagram: &s2n_quic_core::packet::interceptor::Datagram,
+        payload: &mut s2n_codec::EncoderBuffer,
+    ) {
+        let mut triggering_len = self.triggering_len.lock().unwrap();
+        let payload = payload.as_mut_slice();
+
+        // only short header packets trigger a stateless reset and the reset needs to be smaller
+        // than the triggering packet
+        let is_short_header = payload.first().map_or(false, |tag| tag & 0x80 == 0);
+        if triggering_len.is_some() || !is_short_header || payload.len() < 200 {
+            return;
+        }
+
+        payload[1..][..self.connection_id.len()].copy_from_slice(&self.connection_id);
+        *triggering_len = Some(payload.len());
+    }
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-10.3
+//= type=test
+//# An endpoint MAY send a Stateless Reset in response to receiving a packet
+//# that it cannot associate with an active connection.
+#[test]
+fn stateless_reset_unknown_connection_id_test() {
+    use crate::provider::stateless_reset_token;
+    use s2n_quic_core::stateless_reset::token::Generator as _;
+
+    const KEY: [u8; 32] = [7; 32];
+    // the default connection ID format issues IDs with 16 bytes
+    const CONNECTION_ID: [u8; 16] = [42; 16];
+
+    let recorder = TxRecorder::default();
+    let network_packets = recorder.get_packets();
+    let triggering_len = Arc::new(Mutex::new(None));
+    let interceptor = UnknownConnectionId {
+        connection_id: CONNECTION_ID,
+        triggering_len: triggering_len.clone(),
+    };
+    let mut server_addr = None;
+
+    test((recorder, Model::default()), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_stateless_reset_token(stateless_reset_token::Default::new(&KEY)?)?
+            .with_event(events())?
+            .start()?;
+        let addr = start_server(server)?;
+        server_addr = Some(addr);
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_packet_interceptor(interceptor)?
+            .with_event(events())?
+            .start()?;
+
+        // the connection recovers from the packet the server wasn't able to process
+        start_client(client, addr, Data::new(10_000))
+    })
+    .unwrap();
+
+    let server_addr = server_addr.unwrap();
+    let triggering_len = triggering_len
+        .lock()
+        .unwrap()
+        .expect("the client sent a packet with an unknown connection ID");
+
+    // tokens are derived from the connection ID with the configured key
+    let token = stateless_reset_token::default::Generator::new(&KEY)
+        .unwrap()
+        .generate(&CONNECTION_ID);
+
+    let resets: Vec<_> = network_packets
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|packet| {
+            let local_addr: SocketAddr = packet.path.local_address.0.into();
+            local_addr == server_addr && packet.payload.ends_with(token.as_ref())
+        })
+        .map(|packet| packet.payload.clone())
+        .collect();
+
+    assert_eq!(resets.len(), 1);
+    let reset = &resets[0];
+    // stateless resets look like short header packets
+    assert_eq!(reset[0] & 0xc0, 0x40);
+    assert!(reset.len() < triggering_len);
+}
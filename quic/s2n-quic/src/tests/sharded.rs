@@ -0,0 +1,149 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests a [`Server`] made up of multiple shards
+//!
+//! The packets are steered to the shards by the kernel, so these tests run on the Tokio runtime
+//! with real sockets rather than the testing IO provider.
+
+use super::*;
+use crate::provider::event::events::{DatagramDropReason, PacketHeader};
+use std::error::Error;
+
+const SHARD_COUNT: u8 = 2;
+const CONNECTION_COUNT: usize = 32;
+const ROUND_TRIPS: usize = 4;
+
+/// Records the connections accepted by each shard, along with the datagrams it received for
+/// connections it doesn't own
+#[derive(Clone, Default)]
+struct ShardRecorder {
+    index: u8,
+    state: Arc<Mutex<ShardState>>,
+}
+
+#[derive(Default)]
+struct ShardState {
+    /// The number of connections accepted by each shard
+    connections: [usize; SHARD_COUNT as usize],
+    /// The number of 1-RTT packets received by the connections of each shard
+    one_rtt_packets: [usize; SHARD_COUNT as usize],
+    /// The number of datagrams which didn't match a connection on the shard which received them
+    unknown_connection_id: usize,
+}
+
+impl Subscriber for ShardRecorder {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        self.state.lock().unwrap().connections[self.index as usize] += 1;
+    }
+
+    fn on_packet_received(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &ConnectionMeta,
+        event: &events::PacketReceived,
+    ) {
+        if matches!(event.packet_header, PacketHeader::OneRtt { .. }) {
+            self.state.lock().unwrap().one_rtt_packets[self.index as usize] += 1;
+        }
+    }
+
+    fn on_endpoint_datagram_dropped(
+        &mut self,
+        _meta: &events::EndpointMeta,
+        event: &events::EndpointDatagramDropped,
+    ) {
+        if matches!(
+            event.reason,
+            DatagramDropReason::UnknownDestinationConnectionId { .. }
+        ) {
+            self.state.lock().unwrap().unknown_connection_id += 1;
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn sharded_server_test() -> Result<(), Box<dyn Error>> {
+    let recorder = ShardRecorder::default();
+
+    let mut server = Server::sharded(SHARD_COUNT, |shard| {
+        let recorder = ShardRecorder {
+            index: shard.index(),
+            ..recorder.clone()
+        };
+        let server = Server::builder()
+            .with_tls(SERVER_CERTS)?
+            .with_io(shard.io("127.0.0.1:0".parse()?)?)?
+            .with_connection_id(shard.connection_id())?
+            .with_address_token(shard.address_token())?
+            .with_stateless_reset_token(shard.stateless_reset_token())?
+            .with_event(recorder)?
+            .start()?;
+        Ok::<_, Box<dyn Error>>(server)
+    })?;
+    let server_addr = server.local_addr()?;
+
+    // accept connections from all of the shards and echo back
+    tokio::spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            tokio::spawn(async move {
+                while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await {
+                    tokio::spawn(async move {
+                        while let Ok(Some(chunk)) = stream.receive().await {
+                            let _ = stream.send(chunk).await;
+                        }
+                    });
+                }
+            });
+        }
+    });
+
+    let client = Client::builder()
+        .with_tls(certificates::CERT_PEM)?
+        .with_io("127.0.0.1:0")?
+        .start()?;
+
+    let exchange = async {
+        // keep the connections open so late packets don't count as unknown connection IDs
+        let mut connections = vec![];
+
+        for _ in 0..CONNECTION_COUNT {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await?;
+            let mut stream = connection.open_bidirectional_stream().await?;
+
+            // exchange data over multiple round trips after the handshake
+            for _ in 0..ROUND_TRIPS {
+                stream.write_all(b"hello").await?;
+                let mut buf = [0; 5];
+                stream.read_exact(&mut buf).await?;
+                assert_eq!(&buf, b"hello");
+            }
+
+            connections.push((connection, stream));
+        }
+
+        Ok::<_, Box<dyn Error>>(connections)
+    };
+    let _connections = tokio::time::timeout(Duration::from_secs(30), exchange).await??;
+
+    let state = recorder.state.lock().unwrap();
+
+    assert_eq!(state.connections.iter().sum::<usize>(), CONNECTION_COUNT);
+    for index in 0..SHARD_COUNT as usize {
+        // the Initial packets are steered by the random connection ID chosen by the client,
+        // so each shard should accept connections
+        assert_ne!(state.connections[index], 0);
+        assert_ne!(state.one_rtt_packets[index], 0);
+    }
+    // all of the packets after the handshake reached the shard which owns the connection
+    assert_eq!(state.unknown_connection_id, 0);
+
+    Ok(())
+}